@@ -0,0 +1,126 @@
+use camino::Utf8PathBuf;
+use divan::Bencher;
+use divan::black_box;
+use djls_bench::BATCH_INNER_ITERS;
+use djls_bench::BenchmarkSetupError;
+use djls_bench::Db;
+use djls_bench::generated_library_name;
+use djls_bench::primed_inventory_db;
+use djls_bench::require;
+use djls_bench::require_some;
+use djls_conf::CompletionsConfig;
+use djls_ide::completion;
+use djls_source::File;
+use djls_source::FileError;
+use djls_source::Offset;
+use djls_source::PositionEncoding;
+use tower_lsp_server::ls_types;
+
+fn main() {
+    divan::main();
+}
+
+/// Generated template library counts; each library registers tags and filters.
+const LIBRARY_COUNTS: &[usize] = &[5, 50, 500];
+
+/// Generated libraries loaded by the benchmark template, so completions mix
+/// loaded and load-requiring symbols.
+const LOADED_LIBRARIES: usize = 3;
+
+#[derive(Debug, thiserror::Error)]
+enum CompletionSetupError {
+    #[error(transparent)]
+    Database(#[from] BenchmarkSetupError),
+    #[error("failed to register completion fixture {path}: {source}")]
+    Register {
+        path: Utf8PathBuf,
+        #[source]
+        source: FileError,
+    },
+    #[error("completion fixture {path} has no cursor marker")]
+    MissingCursor { path: Utf8PathBuf },
+}
+
+struct CompletionInput {
+    db: Db,
+    file: File,
+    offset: Offset,
+}
+
+/// Register `source` after the generated loads, with its `§` cursor marker removed.
+fn completion_input(
+    library_count: usize,
+    name: &str,
+    source: &str,
+) -> Result<CompletionInput, CompletionSetupError> {
+    let mut db = primed_inventory_db(library_count)?;
+    let path = Utf8PathBuf::from(format!("/templates/completion/{name}.html"));
+    let loads = (0..LOADED_LIBRARIES.min(library_count))
+        .map(generated_library_name)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let source = format!("{{% load {loads} %}}\n{source}");
+    let cursor = source
+        .find('§')
+        .ok_or_else(|| CompletionSetupError::MissingCursor { path: path.clone() })?;
+    let source = source.replacen('§', "", 1);
+    let file = db
+        .file_with_contents(path.clone(), &source)
+        .map_err(|source| CompletionSetupError::Register { path, source })?;
+    let offset = Offset::new(u32::try_from(cursor).unwrap_or(u32::MAX));
+    Ok(CompletionInput { db, file, offset })
+}
+
+fn completion_count(input: &CompletionInput) -> usize {
+    let response = require_some(
+        "complete at the benchmark cursor",
+        completion(
+            &input.db,
+            input.file,
+            input.offset,
+            PositionEncoding::Utf16,
+            true,
+            &CompletionsConfig::default(),
+        ),
+    );
+    match response {
+        ls_types::CompletionResponse::Array(items) => items.len(),
+        ls_types::CompletionResponse::List(list) => list.items.len(),
+    }
+}
+
+fn bench_completion(bencher: Bencher, library_count: usize, name: &str, source: &str) {
+    let input = require(
+        format_args!("prepare {name} completion input with {library_count} libraries"),
+        completion_input(library_count, name, source),
+    );
+    black_box(completion_count(&input));
+
+    bencher.bench_local(move || {
+        let mut total = 0;
+        for _ in 0..BATCH_INNER_ITERS {
+            total += completion_count(&input);
+        }
+        black_box(total);
+    });
+}
+
+#[divan::bench(args = LIBRARY_COUNTS)]
+fn tag_name(bencher: Bencher, library_count: usize) {
+    bench_completion(bencher, library_count, "tag_name", "{% lib1§");
+}
+
+#[divan::bench(args = LIBRARY_COUNTS)]
+fn tag_name_empty_prefix(bencher: Bencher, library_count: usize) {
+    bench_completion(bencher, library_count, "tag_name_empty", "{% §");
+}
+
+#[divan::bench(args = LIBRARY_COUNTS)]
+fn filter(bencher: Bencher, library_count: usize) {
+    bench_completion(bencher, library_count, "filter", "{{ value|lib1§");
+}
+
+#[divan::bench(args = LIBRARY_COUNTS)]
+fn library(bencher: Bencher, library_count: usize) {
+    bench_completion(bencher, library_count, "library", "{% load bench_lib_1§");
+}
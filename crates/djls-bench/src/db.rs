@@ -9,6 +9,7 @@ use djls_project::Db as ProjectDb;
 use djls_project::Project;
 use djls_semantic::Db as SemanticDb;
 use djls_semantic::FilterAritySpecs;
+use djls_semantic::LintRules;
 use djls_semantic::TagSpecs;
 use djls_source::CaseSensitivity;
 use djls_source::ChangeEvent;
@@ -233,7 +234,7 @@ impl SemanticDb for Db {
         &self.projectless_tag_specs
     }
 
-    fn diagnostics_config(&self) -> djls_conf::DiagnosticsConfig {
+    fn diagnostics_config(&self, _file: File) -> djls_conf::DiagnosticsConfig {
         djls_conf::DiagnosticsConfig::default()
     }
 
@@ -244,6 +245,14 @@ impl SemanticDb for Db {
     fn model_graph(&self) -> &djls_project::ModelGraph {
         djls_project::ModelGraph::empty_ref()
     }
+
+    fn lint_rules(&self) -> &LintRules {
+        LintRules::empty_ref()
+    }
+
+    fn untracked_lint_rules(&self) -> &LintRules {
+        LintRules::empty_ref()
+    }
 }
 
 #[cfg(test)]
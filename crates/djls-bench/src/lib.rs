@@ -24,6 +24,9 @@ pub use fixtures::python_fixtures;
 pub use fixtures::template_fixtures;
 pub use fixtures::validation_error_fixtures;
 pub use specs::BenchmarkSetupError;
+pub use specs::GENERATED_SYMBOLS_PER_LIBRARY;
+pub use specs::generated_library_name;
+pub use specs::primed_inventory_db;
 pub use specs::primed_realistic_db;
 pub use specs::realistic_db;
 pub use specs::structure_db;
@@ -4,6 +4,8 @@ use std::sync::OnceLock;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::DialectsConfig;
+use djls_conf::PathMappings;
 use djls_conf::TagSpecDef;
 use djls_ide::prime_template_library_products;
 use djls_project::FilterArity;
@@ -83,6 +85,7 @@ fn build_filter_arities(
             FilterArity {
                 expects_arg,
                 arg_optional,
+                arg_kind: None,
             },
         );
     }
@@ -155,13 +158,46 @@ fn realistic_specs() -> Result<&'static RealisticSpecs, BenchmarkSetupError> {
     }
 }
 
+/// Tags and filters registered by each generated inventory library.
+pub const GENERATED_SYMBOLS_PER_LIBRARY: usize = 10;
+
+/// Load name of the generated inventory library at `index`.
+#[must_use]
+pub fn generated_library_name(index: usize) -> String {
+    format!("bench_lib_{index}")
+}
+
+fn generated_library_source(index: usize) -> String {
+    let mut source = String::from("from django import template\nregister = template.Library()\n");
+    for symbol in 0..GENERATED_SYMBOLS_PER_LIBRARY {
+        source.push_str(&format!(
+            "@register.simple_tag\ndef lib{index}_tag{symbol}(): pass\n\
+             @register.filter\ndef lib{index}_filter{symbol}(value): pass\n"
+        ));
+    }
+    source
+}
+
+fn settings_source(generated_libraries: usize) -> String {
+    let mut libraries = String::from(
+        "'i18n': 'django.templatetags.i18n', 'static': 'django.templatetags.static'",
+    );
+    for index in 0..generated_libraries {
+        let name = generated_library_name(index);
+        libraries.push_str(&format!(", '{name}': 'benchlibs.templatetags.{name}'"));
+    }
+    format!(
+        "INSTALLED_APPS = []\nTEMPLATES = [{{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/templates'], 'APP_DIRS': False, 'OPTIONS': {{'libraries': {{{libraries}}}}}}}]\n"
+    )
+}
+
 fn install_template_library_fixture(
     db: &mut Db,
     specs: &RealisticSpecs,
+    generated_libraries: usize,
 ) -> Result<(), BenchmarkSetupError> {
     // Canonical builtin identities make extracted source facts fuse with semantic's hardcoded
     // Django roles and fallback grammar, matching production project analysis.
-    const SETTINGS: &str = "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/templates'], 'APP_DIRS': False, 'OPTIONS': {'libraries': {'i18n': 'django.templatetags.i18n', 'static': 'django.templatetags.static'}}}]\n";
     const DEFAULTFILTERS: &str = concat!(
         "from django import template\nregister = template.Library()\n",
         "@register.filter\ndef title(value): pass\n",
@@ -186,7 +222,6 @@ fn install_template_library_fixture(
 
     for (path, source) in [
         ("/project/__init__.py", ""),
-        ("/project/settings.py", SETTINGS),
         ("/django/__init__.py", ""),
         ("/django/template/__init__.py", ""),
         ("/django/template/defaultfilters.py", DEFAULTFILTERS),
@@ -204,6 +239,20 @@ fn install_template_library_fixture(
         specs.defaulttags_source.clone(),
     );
     db.add_fixture_source("/django/templatetags/i18n.py", specs.i18n_source.clone());
+    db.add_fixture_source("/project/settings.py", settings_source(generated_libraries));
+    if generated_libraries > 0 {
+        db.add_fixture_source("/benchlibs/__init__.py", "");
+        db.add_fixture_source("/benchlibs/templatetags/__init__.py", "");
+    }
+    for index in 0..generated_libraries {
+        db.add_fixture_source(
+            format!(
+                "/benchlibs/templatetags/{}.py",
+                generated_library_name(index)
+            ),
+            generated_library_source(index),
+        );
+    }
 
     let root = Utf8Path::new("/");
     let interpreter = Interpreter::Auto;
@@ -225,6 +274,8 @@ fn install_template_library_fixture(
         Vec::new(),
         Vec::new(),
         TagSpecDef::default(),
+        DialectsConfig::default(),
+        PathMappings::default(),
     );
     db.set_project(project);
     Ok(())
@@ -244,11 +295,15 @@ pub fn realistic_db() -> Result<Db, BenchmarkSetupError> {
 }
 
 fn configure_realistic_db(db: Db) -> Result<Db, BenchmarkSetupError> {
+    configure_inventory_db(db, 0)
+}
+
+fn configure_inventory_db(db: Db, generated_libraries: usize) -> Result<Db, BenchmarkSetupError> {
     let specs = realistic_specs()?;
     let mut db = db
         .with_projectless_tag_specs(specs.tag_specs.clone())
         .with_projectless_filter_arity_specs(specs.filter_arity_specs.clone());
-    install_template_library_fixture(&mut db, specs)?;
+    install_template_library_fixture(&mut db, specs, generated_libraries)?;
     Ok(db)
 }
 
@@ -259,6 +314,15 @@ pub fn primed_realistic_db() -> Result<Db, BenchmarkSetupError> {
     Ok(db)
 }
 
+/// Create a primed realistic database that also configures `library_count`
+/// generated template libraries, each registering
+/// [`GENERATED_SYMBOLS_PER_LIBRARY`] tags and filters.
+pub fn primed_inventory_db(library_count: usize) -> Result<Db, BenchmarkSetupError> {
+    let db = configure_inventory_db(Db::new(), library_count)?;
+    prime_template_library_products(&db).ok_or(BenchmarkSetupError::MissingProject)?;
+    Ok(db)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -276,7 +340,9 @@ mod tests {
     use salsa::Event;
 
     use super::Db;
+    use super::GENERATED_SYMBOLS_PER_LIBRARY;
     use super::configure_realistic_db;
+    use super::primed_inventory_db;
     use super::primed_realistic_db;
 
     impl Db {
@@ -340,4 +406,26 @@ mod tests {
             } if name == "trans" && loaded_libraries == ["i18n"]
         ));
     }
+
+    #[test]
+    fn inventory_project_configures_generated_libraries() {
+        let db = primed_inventory_db(2).expect("inventory benchmark database should initialize");
+        let project = db
+            .project()
+            .expect("inventory fixture should install a Project");
+        let environment =
+            ScopedTemplateLibraries::from_project_inventory(template_library_catalog(&db, project));
+        let generated: Vec<_> = environment
+            .resolved_libraries()
+            .into_iter()
+            .filter(|library| {
+                library
+                    .load_name()
+                    .is_some_and(|name| name.as_str().starts_with("bench_lib_"))
+            })
+            .map(|library| library.symbols().len())
+            .collect();
+
+        assert_eq!(generated, [2 * GENERATED_SYMBOLS_PER_LIBRARY; 2]);
+    }
 }
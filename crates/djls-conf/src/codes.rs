@@ -0,0 +1,406 @@
+//! The diagnostic codes djls reports, with the text `djls explain` prints for each.
+//!
+//! Codes are stable: once published, a code keeps its meaning so that severity overrides and
+//! suppressions keep working. Lint rule plugins choose their own codes and are not listed.
+
+/// Where the per-code documentation lives; each code has an anchor of its lowercase name.
+const DOCS_URL: &str = "https://djls.joshthomas.dev/diagnostics/";
+
+/// One diagnostic code and its explanation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagnosticCode {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// A template that reports this code, when one can show it without project setup.
+    pub example: Option<&'static str>,
+}
+
+impl DiagnosticCode {
+    /// Link to this code's section of the documentation.
+    #[must_use]
+    pub fn href(&self) -> String {
+        format!("{DOCS_URL}#{}", self.code.to_ascii_lowercase())
+    }
+}
+
+/// Look up a code such as `S109`, ignoring case.
+#[must_use]
+pub fn diagnostic_code(code: &str) -> Option<&'static DiagnosticCode> {
+    DIAGNOSTIC_CODES
+        .iter()
+        .find(|entry| entry.code.eq_ignore_ascii_case(code))
+}
+
+/// Every built-in diagnostic code, in code order.
+pub const DIAGNOSTIC_CODES: &[DiagnosticCode] = &[
+    DiagnosticCode {
+        code: "A100",
+        title: "Image without alt text",
+        description: "An `<img>` has no `alt` attribute, so screen readers announce its file name or nothing at all. Describe the image in `alt`, or write `alt=\"\"` for a decorative one. Tags with a template tag among their attributes are skipped, since it may add `alt`. Accessibility checks are off unless `A` or the code is given a severity.",
+        example: Some("<img src=\"{{ product.photo.url }}\">"),
+    },
+    DiagnosticCode {
+        code: "A101",
+        title: "Form field without a label",
+        description: "A `{% for field in form %}` loop renders `{{ field }}` but never `{{ field.label_tag }}`, `{{ field.legend_tag }}`, `{{ field.as_field_group }}`, or a `<label>`, so the inputs have no accessible name. Accessibility checks are off unless `A` or the code is given a severity.",
+        example: Some("{% for field in form %}{{ field }}{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "A102",
+        title: "Repeated id in a loop",
+        description: "A literal `id` inside a `{% for %}` body is written once per iteration, so the page has duplicate ids and labels, `aria-describedby`, and fragment links point at the first one only. Build the id from the loop, for example with `{{ forloop.counter }}`. Accessibility checks are off unless `A` or the code is given a severity.",
+        example: Some("{% for item in items %}<li id=\"item\">{{ item }}</li>{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "S100",
+        title: "Unclosed tag",
+        description: "A block tag is opened but its end tag never appears, so Django fails to parse the template.",
+        example: Some("{% block content %}<p>Hello</p>"),
+    },
+    DiagnosticCode {
+        code: "S101",
+        title: "Unbalanced structure",
+        description: "An end tag closes a different block than the innermost open one, or closes a block that was never opened.",
+        example: Some("{% if user %}{% for item in items %}{% endif %}{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "S102",
+        title: "Orphaned tag",
+        description: "An intermediate tag such as `{% else %}` or `{% empty %}` appears outside the block it belongs to.",
+        example: Some("<p>Hello</p>{% else %}"),
+    },
+    DiagnosticCode {
+        code: "S103",
+        title: "Unmatched block name",
+        description: "The name given to `{% endblock %}` does not match the name of the block it closes.",
+        example: Some("{% block header %}{% endblock footer %}"),
+    },
+    DiagnosticCode {
+        code: "S108",
+        title: "Unknown tag",
+        description: "The tag is not defined by Django's builtins or by any template tag library djls knows about. Close misspellings of known tags are suggested.",
+        example: Some("{% blocktrnas %}Hello{% endblocktrans %}"),
+    },
+    DiagnosticCode {
+        code: "S109",
+        title: "Unloaded tag",
+        description: "The tag comes from a template tag library that this template has not loaded. Add `{% load %}` for the library before the tag.",
+        example: Some("{% static 'logo.png' %}"),
+    },
+    DiagnosticCode {
+        code: "S110",
+        title: "Ambiguous unloaded tag",
+        description: "The tag is defined by more than one library and none of them is loaded. Load the library you mean.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S111",
+        title: "Unknown filter",
+        description: "The filter is not defined by Django's builtins or by any template tag library djls knows about. Close misspellings of known filters are suggested.",
+        example: Some("{{ name|uppr }}"),
+    },
+    DiagnosticCode {
+        code: "S112",
+        title: "Unloaded filter",
+        description: "The filter comes from a template tag library that this template has not loaded. Add `{% load %}` for the library before the filter.",
+        example: Some("{{ count|intcomma }}"),
+    },
+    DiagnosticCode {
+        code: "S113",
+        title: "Ambiguous unloaded filter",
+        description: "The filter is defined by more than one library and none of them is loaded. Load the library you mean.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S114",
+        title: "Expression syntax error",
+        description: "The expression in `{% if %}` or `{% elif %}` is not valid: an operator is missing an operand, or two operands have no operator between them.",
+        example: Some("{% if and user %}{% endif %}"),
+    },
+    DiagnosticCode {
+        code: "S115",
+        title: "Filter missing argument",
+        description: "The filter requires an argument but none was given.",
+        example: Some("{{ value|default }}"),
+    },
+    DiagnosticCode {
+        code: "S116",
+        title: "Filter does not take an argument",
+        description: "The filter accepts no argument but one was given.",
+        example: Some("{{ value|title:\"arg\" }}"),
+    },
+    DiagnosticCode {
+        code: "S117",
+        title: "Tag argument error",
+        description: "The tag's arguments break a rule read from its Python implementation, such as the number of arguments or a required keyword.",
+        example: Some("{% for item %}{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "S118",
+        title: "Tag from an app not in INSTALLED_APPS",
+        description: "The tag is defined by a library on the project's search paths, but the app that provides it is not in `INSTALLED_APPS`.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S119",
+        title: "Filter from an app not in INSTALLED_APPS",
+        description: "The filter is defined by a library on the project's search paths, but the app that provides it is not in `INSTALLED_APPS`.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S120",
+        title: "Unknown template tag library",
+        description: "`{% load %}` names a library that no installed app or `TEMPLATES` option provides. Close misspellings of known libraries are suggested.",
+        example: Some("{% load statc %}"),
+    },
+    DiagnosticCode {
+        code: "S121",
+        title: "Library from an app not in INSTALLED_APPS",
+        description: "`{% load %}` names a library found on the project's search paths, but the app that provides it is not in `INSTALLED_APPS`.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S122",
+        title: "Extends is not first",
+        description: "`{% extends %}` must be the first tag in the template. Only text and comments may come before it.",
+        example: Some("{% load static %}{% extends 'base.html' %}"),
+    },
+    DiagnosticCode {
+        code: "S123",
+        title: "Multiple extends",
+        description: "A template can extend only one parent, so `{% extends %}` may appear once.",
+        example: Some("{% extends 'base.html' %}{% extends 'other.html' %}"),
+    },
+    DiagnosticCode {
+        code: "S124",
+        title: "Invalid filter argument",
+        description: "A quoted filter argument does not have the shape the filter expects, such as an integer, a date format, or a fixed number of comma-separated values. Django renders it without an error, but the output is wrong.",
+        example: Some("{{ text|truncatewords:\"ten\" }}"),
+    },
+    DiagnosticCode {
+        code: "S125",
+        title: "Unbound expression in blocktrans",
+        description: "A `{% blocktrans %}` body may only use plain variable names. Bind attribute lookups and filtered values with `with` first.",
+        example: Some("{% load i18n %}{% blocktrans %}Hello {{ user.name }}{% endblocktrans %}"),
+    },
+    DiagnosticCode {
+        code: "S126",
+        title: "Plural branch mismatch",
+        description: "A variable appears in only one branch of a `{% blocktrans count %}` block, so one of the translated forms cannot show it.",
+        example: Some("{% load i18n %}{% blocktrans count n=items|length %}{{ name }} item{% plural %}items{% endblocktrans %}"),
+    },
+    DiagnosticCode {
+        code: "S127",
+        title: "Non-literal trans argument",
+        description: "`{% trans %}` is given a variable instead of a string literal. Django translates it at runtime, but `makemessages` cannot extract it.",
+        example: Some("{% load i18n %}{% trans greeting %}"),
+    },
+    DiagnosticCode {
+        code: "S128",
+        title: "Unknown name in selective load",
+        description: "`{% load ... from ... %}` names a tag or filter that the library does not define.",
+        example: Some("{% load static from i18n %}"),
+    },
+    DiagnosticCode {
+        code: "S129",
+        title: "Invalid extends argument",
+        description: "The argument to `{% extends %}` is neither a quoted template name nor a variable.",
+        example: Some("{% extends 42 %}"),
+    },
+    DiagnosticCode {
+        code: "S130",
+        title: "Dynamic extends",
+        description: "The parent template comes from a variable and is only known at render time, so block navigation and inheritance checks cannot follow it.",
+        example: Some("{% extends layout %}"),
+    },
+    DiagnosticCode {
+        code: "S131",
+        title: "Duplicate block name",
+        description: "Two `{% block %}` tags in one template share a name. Django rejects this even when one block is nested inside another tag.",
+        example: Some("{% block content %}{% endblock %}{% block content %}{% endblock %}"),
+    },
+    DiagnosticCode {
+        code: "S132",
+        title: "Unbound blocktrans placeholder",
+        description: "A `%(name)s` placeholder written in a `{% blocktrans %}` body is not bound by `with` or `count`. Write `{{ name }}` and bind it instead.",
+        example: Some("{% load i18n %}{% blocktrans %}Hello %(name)s{% endblocktrans %}"),
+    },
+    DiagnosticCode {
+        code: "S133",
+        title: "Include with only is missing a variable",
+        description: "An `{% include ... only %}` does not pass a variable the included template uses, so Django renders it as an empty string.",
+        example: Some("{% include 'card.html' only %}"),
+    },
+    DiagnosticCode {
+        code: "S134",
+        title: "Unknown admin block",
+        description: "A template overriding one of Django admin's templates fills a block that the admin template never defines, so its content is never rendered.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S135",
+        title: "Removed builtin",
+        description: "A builtin tag, filter, or library that Django has removed. The message names the release that removed it and what to use instead.",
+        example: Some("{% load staticfiles %}"),
+    },
+    DiagnosticCode {
+        code: "S136",
+        title: "Deprecated builtin",
+        description: "A builtin tag, filter, or library that the project's Django version still provides but has deprecated.",
+        example: Some("{% if items|length_is:3 %}{% endif %}"),
+    },
+    DiagnosticCode {
+        code: "S137",
+        title: "Content outside a block",
+        description: "A template that extends another has text, a variable, or a tag outside every `{% block %}`. Django renders only the block overrides of a child template, so this content is silently dropped.",
+        example: Some("{% extends 'base.html' %}<h1>Title</h1>{% block content %}{% endblock %}"),
+    },
+    DiagnosticCode {
+        code: "S138",
+        title: "forloop outside a loop",
+        description: "A `forloop` lookup sits outside the body of every `{% for %}`, including in an `{% empty %}` branch, where Django does not define it, so it renders as an empty string. Templates that another template includes are not checked, since the include may sit inside a loop.",
+        example: Some("<p>{{ forloop.counter }}</p>"),
+    },
+    DiagnosticCode {
+        code: "S139",
+        title: "parentloop without an outer loop",
+        description: "A `forloop.parentloop` lookup sits in a `{% for %}` that no other loop encloses, so Django leaves it empty. Each further `parentloop` needs one more enclosing loop.",
+        example: Some("{% for item in items %}{{ forloop.parentloop.counter }}{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "S140",
+        title: "Shadowed variable",
+        description: "A `{% for %}` loop variable or `{% with %}` alias reuses the name of a binding from an enclosing tag, or of a variable a context processor adds, so the outer value is hidden for the rest of the body. This check is off unless `S140` is given a severity.",
+        example: Some("{% for item in items %}{% for item in item.children %}{{ item }}{% endfor %}{% endfor %}"),
+    },
+    DiagnosticCode {
+        code: "S141",
+        title: "Template not found",
+        description: "A string literal passed to `render`, `get_template`, `TemplateResponse`, or a similar call, or assigned to a view's `template_name`, names a template that no configured template directory or app contains, so Django raises `TemplateDoesNotExist`. Names built at runtime are not checked.",
+        example: Some("return render(request, \"blog/missing.html\")"),
+    },
+    DiagnosticCode {
+        code: "S142",
+        title: "Ambiguous template name",
+        description: "An `{% extends %}` or `{% include %}` name resolves to a template at the top of one app's `templates` directory while another installed app provides a template with the same name. The app directories loader picks whichever app comes first in `INSTALLED_APPS`, so reordering apps silently changes the template. Move the template under a directory named after its app and reference it as `app/name.html`.",
+        example: Some("{% include \"form.html\" %}"),
+    },
+    DiagnosticCode {
+        code: "S143",
+        title: "Shadowed template",
+        description: "Another template directory provides the same template name and comes earlier in `DIRS` or `INSTALLED_APPS` order, so Django's loaders never return this file under that name. Reported as a hint on the first line of the shadowed file; `djls check --shadowed` lists every shadowed template in the project.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S144",
+        title: "Template over its limits",
+        description: "The template is larger, has more parsed nodes, or nests block tags deeper than `[diagnostics.limits]` allows, so it is not validated and its other diagnostics are not reported. Generated templates and vendored minified files are the usual cause. Reported as information on the first line; raise `max_file_size`, `max_nodes`, or `max_nesting_depth` to check the file anyway.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S145",
+        title: "POST form without CSRF token",
+        description: "A `<form method=\"post\">` is closed without a `{% csrf_token %}` inside it, so Django's CSRF middleware rejects its submissions with a 403. Forms that contain an `{% include %}`, a `{% block %}` a child template may fill, or a tag from a third-party library are not reported, since any of those may render the token. Forms whose `action` is an absolute URL post to another site and must not carry the token. The quick fix adds `{% csrf_token %}` after the form's start tag.",
+        example: Some("<form method=\"post\">{{ form }}</form>"),
+    },
+    DiagnosticCode {
+        code: "S146",
+        title: "Template too complex",
+        description: "The template is over one of the `[diagnostics.complexity]` thresholds: it has more parsed nodes, deeper block nesting, more `{% include %}` tags, more distinct context variables, or more `{% if %}`, `{% elif %}`, `{% for %}`, and `{% ifchanged %}` branches than allowed. The template renders correctly, but is hard to read and review; consider moving parts of it into includes. Off by default, since the thresholds are a matter of taste; enable it with `S146 = \"hint\"`. Reported on the first line, once per exceeded threshold. `djls stats` prints every measurement.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S147",
+        title: "Unused load",
+        description: "A `{% load %}` names a library, or imports a tag or filter from one, that nothing after it in the template uses. Loads do not carry over to templates that extend or include this one, so the load can go. Only libraries whose tags and filters djls could read are checked, and any word in a later tag's arguments counts as a use. Off by default; enable it with `S147 = \"warning\"`. The quick fix removes the name, or the whole tag when nothing it loads is used.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "S148",
+        title: "Tag delimiter spacing",
+        description: "A tag does not have exactly one space after `{%` and before `%}`, as in `{%trans \"Hi\"%}`. Django reads it the same way, so this stylistic check is off by default; enable it with `S148 = \"hint\"`. The quick fix rewrites the spacing inside the delimiters and leaves the tag's arguments alone.",
+        example: Some("{%trans \"Hi\"%}"),
+    },
+    DiagnosticCode {
+        code: "T100",
+        title: "Template syntax error",
+        description: "The template lexer or parser could not read a construct: an unclosed `{{`, `{%`, or `{#`, an empty tag, or a malformed filter expression.",
+        example: Some("{{ user.name"),
+    },
+    DiagnosticCode {
+        code: "T900",
+        title: "Template could not be read",
+        description: "The template file could not be read from disk.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "T901",
+        title: "Template configuration error",
+        description: "The configuration used to validate the template could not be loaded.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "T902",
+        title: "Template is not valid UTF-8",
+        description: "The template file contains bytes that are not valid UTF-8, usually because it was saved in a legacy encoding such as Latin-1. The invalid bytes are read as U+FFFD replacement characters so the rest of the template is still checked; the diagnostic points at the first one. Re-save the file as UTF-8, which Django expects by default.",
+        example: None,
+    },
+    DiagnosticCode {
+        code: "X100",
+        title: "Output marked safe",
+        description: "The `safe` or `safeseq` filter turns off autoescaping for this output, so any HTML in the value is rendered as markup. Check that the value cannot contain user input. Security review hints are off unless `X` or the code is given a severity.",
+        example: Some("{{ article.body|safe }}"),
+    },
+    DiagnosticCode {
+        code: "X101",
+        title: "Autoescaping turned off",
+        description: "`{% autoescape off %}` renders every variable up to `{% endautoescape %}` without escaping. Check that none of them can contain user input. Security review hints are off unless `X` or the code is given a severity.",
+        example: Some("{% autoescape off %}{{ greeting }}{% endautoescape %}"),
+    },
+    DiagnosticCode {
+        code: "X102",
+        title: "Unescaped user input",
+        description: "A filter that returns its result wrapped in `mark_safe()` is applied to a variable that looks like user input, such as one under `request`, `form`, or `user`. Unless the filter escapes the value itself, the input is rendered as markup. Security review hints are off unless `X` or the code is given a severity.",
+        example: None,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_in_order() {
+        for pair in DIAGNOSTIC_CODES.windows(2) {
+            assert!(pair[0].code < pair[1].code, "{} before {}", pair[0].code, pair[1].code);
+        }
+    }
+
+    #[test]
+    fn looks_up_codes_case_insensitively() {
+        let code = diagnostic_code("s109").expect("S109 should be registered");
+
+        assert_eq!(code.code, "S109");
+        assert_eq!(code.href(), "https://djls.joshthomas.dev/diagnostics/#s109");
+        assert_eq!(diagnostic_code("S999"), None);
+    }
+
+    #[test]
+    fn every_code_is_documented() {
+        let docs = include_str!("../../../docs/diagnostics.md");
+        for code in DIAGNOSTIC_CODES {
+            let heading = format!(
+                "\n## {}: {} {{ #{} }}\n",
+                code.code,
+                code.title,
+                code.code.to_ascii_lowercase()
+            );
+            assert!(
+                docs.contains(&heading),
+                "docs/diagnostics.md has no section for {}",
+                code.code
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct CompletionsConfig {
+    #[serde(default)]
+    fuzzy: bool,
+    #[serde(default)]
+    htmx: bool,
+    #[serde(default)]
+    alpine: bool,
+}
+
+impl CompletionsConfig {
+    /// Whether completion matches names by subsequence instead of by prefix only.
+    #[must_use]
+    pub fn fuzzy(&self) -> bool {
+        self.fuzzy
+    }
+
+    pub fn set_fuzzy(&mut self, fuzzy: bool) {
+        self.fuzzy = fuzzy;
+    }
+
+    /// Whether `hx-*` attributes are offered inside HTML start tags, along with URL
+    /// names inside `hx-get` and `hx-post` values.
+    #[must_use]
+    pub fn htmx(&self) -> bool {
+        self.htmx
+    }
+
+    pub fn set_htmx(&mut self, htmx: bool) {
+        self.htmx = htmx;
+    }
+
+    /// Whether Alpine.js `x-*` attributes are offered inside HTML start tags.
+    #[must_use]
+    pub fn alpine(&self) -> bool {
+        self.alpine
+    }
+
+    pub fn set_alpine(&mut self, alpine: bool) {
+        self.alpine = alpine;
+    }
+}
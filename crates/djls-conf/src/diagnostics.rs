@@ -13,9 +13,216 @@ pub enum DiagnosticSeverity {
     Hint,
 }
 
+/// How `collect_diagnostics` reports an unknown tag or filter after its first use in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// Report every occurrence at its configured severity.
+    Report,
+    /// Report later occurrences as hints.
+    #[default]
+    Hint,
+    /// Drop later occurrences and list them as related information on the first.
+    Fold,
+}
+
+/// Guardrails for templates too large or deeply nested to validate.
+///
+/// A template over any limit is not validated; it gets one informational S144 diagnostic
+/// instead, so generated and minified files cannot stall the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct DiagnosticLimits {
+    /// Largest template source, in bytes, that is parsed and validated.
+    #[serde(default = "default_max_file_size")]
+    max_file_size: usize,
+    /// Most parsed nodes a validated template may have.
+    #[serde(default = "default_max_nodes")]
+    max_nodes: usize,
+    /// Deepest nesting of block tags a validated template may have.
+    #[serde(default = "default_max_nesting_depth")]
+    max_nesting_depth: usize,
+}
+
+impl Default for DiagnosticLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: default_max_file_size(),
+            max_nodes: default_max_nodes(),
+            max_nesting_depth: default_max_nesting_depth(),
+        }
+    }
+}
+
+impl DiagnosticLimits {
+    #[must_use]
+    pub fn max_file_size(&self) -> usize {
+        self.max_file_size
+    }
+
+    pub fn set_max_file_size(&mut self, max_file_size: usize) {
+        self.max_file_size = max_file_size;
+    }
+
+    #[must_use]
+    pub fn max_nodes(&self) -> usize {
+        self.max_nodes
+    }
+
+    pub fn set_max_nodes(&mut self, max_nodes: usize) {
+        self.max_nodes = max_nodes;
+    }
+
+    #[must_use]
+    pub fn max_nesting_depth(&self) -> usize {
+        self.max_nesting_depth
+    }
+
+    pub fn set_max_nesting_depth(&mut self, max_nesting_depth: usize) {
+        self.max_nesting_depth = max_nesting_depth;
+    }
+}
+
+fn default_max_file_size() -> usize {
+    5 * 1024 * 1024
+}
+
+fn default_max_nodes() -> usize {
+    200_000
+}
+
+fn default_max_nesting_depth() -> usize {
+    200
+}
+
+/// Thresholds above which a template gets an S146 hint suggesting it be split up.
+///
+/// S146 is off by default; these only take effect once it is given a severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ComplexityLimits {
+    /// Most parsed nodes a template may have.
+    #[serde(default = "default_complexity_max_nodes")]
+    max_nodes: usize,
+    /// Deepest nesting of block tags a template may have.
+    #[serde(default = "default_complexity_max_nesting_depth")]
+    max_nesting_depth: usize,
+    /// Most `{% include %}` tags a template may have.
+    #[serde(default = "default_complexity_max_includes")]
+    max_includes: usize,
+    /// Most distinct context variables a template may read.
+    #[serde(default = "default_complexity_max_variables")]
+    max_variables: usize,
+    /// Most branches (`{% if %}`, `{% elif %}`, `{% for %}`, and similar) a template may have.
+    #[serde(default = "default_complexity_max_branches")]
+    max_branches: usize,
+}
+
+impl Default for ComplexityLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes: default_complexity_max_nodes(),
+            max_nesting_depth: default_complexity_max_nesting_depth(),
+            max_includes: default_complexity_max_includes(),
+            max_variables: default_complexity_max_variables(),
+            max_branches: default_complexity_max_branches(),
+        }
+    }
+}
+
+impl ComplexityLimits {
+    #[must_use]
+    pub fn max_nodes(&self) -> usize {
+        self.max_nodes
+    }
+
+    pub fn set_max_nodes(&mut self, max_nodes: usize) {
+        self.max_nodes = max_nodes;
+    }
+
+    #[must_use]
+    pub fn max_nesting_depth(&self) -> usize {
+        self.max_nesting_depth
+    }
+
+    pub fn set_max_nesting_depth(&mut self, max_nesting_depth: usize) {
+        self.max_nesting_depth = max_nesting_depth;
+    }
+
+    #[must_use]
+    pub fn max_includes(&self) -> usize {
+        self.max_includes
+    }
+
+    pub fn set_max_includes(&mut self, max_includes: usize) {
+        self.max_includes = max_includes;
+    }
+
+    #[must_use]
+    pub fn max_variables(&self) -> usize {
+        self.max_variables
+    }
+
+    pub fn set_max_variables(&mut self, max_variables: usize) {
+        self.max_variables = max_variables;
+    }
+
+    #[must_use]
+    pub fn max_branches(&self) -> usize {
+        self.max_branches
+    }
+
+    pub fn set_max_branches(&mut self, max_branches: usize) {
+        self.max_branches = max_branches;
+    }
+}
+
+fn default_complexity_max_nodes() -> usize {
+    1000
+}
+
+fn default_complexity_max_nesting_depth() -> usize {
+    8
+}
+
+fn default_complexity_max_includes() -> usize {
+    20
+}
+
+fn default_complexity_max_variables() -> usize {
+    50
+}
+
+fn default_complexity_max_branches() -> usize {
+    40
+}
+
+/// Diagnostics that report lost static precision or a likely mistake rather than a runtime
+/// error, so they default to "warning".
+const WARNING_BY_DEFAULT: &[&str] = &[
+    "S130", "S133", "S134", "S136", "S137", "S138", "S139", "S142", "S145",
+];
+
+/// Diagnostics that describe how the project is laid out rather than a problem in the template,
+/// so they default to "hint".
+const HINT_BY_DEFAULT: &[&str] = &["S143"];
+
+/// Diagnostics that explain why a template was not checked rather than report a problem in it,
+/// so they default to "info".
+const INFO_BY_DEFAULT: &[&str] = &["S144"];
+
+/// Stylistic diagnostics for templates that render as intended, so they stay off until their
+/// exact code is given a severity. Prefix patterns such as "S" do not enable them.
+const OFF_BY_DEFAULT: &[&str] = &["S140", "S146", "S147", "S148"];
+
+/// Code prefixes for opt-in rule packs, accessibility checks and security review hints, which
+/// flag code to read rather than code that is wrong. They stay off until the prefix or one of
+/// its codes is given a severity.
+const OFF_BY_DEFAULT_PREFIXES: &[&str] = &["A", "X"];
+
 /// Configuration for diagnostic severity levels.
 ///
-/// All diagnostics are enabled by default at "error" severity.
+/// Diagnostics are enabled by default at "error" severity, except for the codes in
+/// `WARNING_BY_DEFAULT`, `HINT_BY_DEFAULT`, `INFO_BY_DEFAULT`, and `OFF_BY_DEFAULT`, and the
+/// codes under `OFF_BY_DEFAULT_PREFIXES`.
 /// Configure severity per diagnostic code or prefix pattern.
 /// Specific codes override prefix patterns.
 ///
@@ -39,6 +246,19 @@ pub struct DiagnosticsConfig {
     /// - More specific patterns override less specific ones
     #[serde(default)]
     severity: HashMap<String, DiagnosticSeverity>,
+    /// How repeated unknown tags and filters are reported within one file.
+    #[serde(default)]
+    duplicates: DuplicatePolicy,
+    /// Whether S137 also reports `{% load %}` tags and comments outside blocks in templates
+    /// that extend another.
+    #[serde(default)]
+    strict_child_templates: bool,
+    /// Size and nesting limits above which a template is not validated.
+    #[serde(default)]
+    limits: DiagnosticLimits,
+    /// Thresholds above which S146 reports a template as too complex.
+    #[serde(default)]
+    complexity: ComplexityLimits,
 }
 
 impl DiagnosticsConfig {
@@ -46,8 +266,11 @@ impl DiagnosticsConfig {
     ///
     /// Resolution order (most specific wins):
     /// 1. Exact match (e.g., "S100")
-    /// 2. Longest prefix match (e.g., "S1" over "S")
-    /// 3. Default: Error
+    /// 2. Off for `OFF_BY_DEFAULT` codes
+    /// 3. Longest prefix match (e.g., "S1" over "S")
+    /// 4. Default: Off for codes under `OFF_BY_DEFAULT_PREFIXES`, Warning for
+    ///    `WARNING_BY_DEFAULT` codes, Hint for `HINT_BY_DEFAULT` codes, Info for
+    ///    `INFO_BY_DEFAULT` codes, Error otherwise
     #[must_use]
     pub fn get_severity(&self, code: &str) -> DiagnosticSeverity {
         // First, check for exact match
@@ -55,6 +278,11 @@ impl DiagnosticsConfig {
             return severity;
         }
 
+        // Opt-in codes are only enabled by name
+        if OFF_BY_DEFAULT.contains(&code) {
+            return DiagnosticSeverity::Off;
+        }
+
         // Then, find the longest matching prefix
         let mut best_match: Option<(&str, DiagnosticSeverity)> = None;
 
@@ -72,13 +300,74 @@ impl DiagnosticsConfig {
             }
         }
 
-        best_match.map_or(DiagnosticSeverity::Error, |(_, severity)| severity)
+        best_match.map_or_else(|| default_severity(code), |(_, severity)| severity)
     }
 
     /// Set the severity for a specific diagnostic code or prefix.
     pub fn set_severity(&mut self, code: &str, severity: DiagnosticSeverity) {
         self.severity.insert(code.to_string(), severity);
     }
+
+    /// How repeated unknown tags and filters are reported within one file.
+    #[must_use]
+    pub fn duplicates(&self) -> DuplicatePolicy {
+        self.duplicates
+    }
+
+    /// Set how repeated unknown tags and filters are reported.
+    pub fn set_duplicates(&mut self, duplicates: DuplicatePolicy) {
+        self.duplicates = duplicates;
+    }
+
+    /// Whether S137 also reports `{% load %}` tags and comments outside blocks.
+    #[must_use]
+    pub fn strict_child_templates(&self) -> bool {
+        self.strict_child_templates
+    }
+
+    /// Set whether S137 also reports `{% load %}` tags and comments outside blocks.
+    pub fn set_strict_child_templates(&mut self, strict: bool) {
+        self.strict_child_templates = strict;
+    }
+
+    /// Size and nesting limits above which a template is not validated.
+    #[must_use]
+    pub fn limits(&self) -> DiagnosticLimits {
+        self.limits
+    }
+
+    /// Set the limits above which a template is not validated.
+    pub fn set_limits(&mut self, limits: DiagnosticLimits) {
+        self.limits = limits;
+    }
+
+    /// Thresholds above which S146 reports a template as too complex.
+    #[must_use]
+    pub fn complexity(&self) -> ComplexityLimits {
+        self.complexity
+    }
+
+    /// Set the thresholds above which S146 reports a template as too complex.
+    pub fn set_complexity(&mut self, complexity: ComplexityLimits) {
+        self.complexity = complexity;
+    }
+}
+
+fn default_severity(code: &str) -> DiagnosticSeverity {
+    if OFF_BY_DEFAULT_PREFIXES
+        .iter()
+        .any(|prefix| code.starts_with(prefix))
+    {
+        DiagnosticSeverity::Off
+    } else if WARNING_BY_DEFAULT.contains(&code) {
+        DiagnosticSeverity::Warning
+    } else if HINT_BY_DEFAULT.contains(&code) {
+        DiagnosticSeverity::Hint
+    } else if INFO_BY_DEFAULT.contains(&code) {
+        DiagnosticSeverity::Info
+    } else {
+        DiagnosticSeverity::Error
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +383,53 @@ mod tests {
         let config = DiagnosticsConfig::default();
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Error);
         assert_eq!(config.get_severity("T100"), DiagnosticSeverity::Error);
+        assert_eq!(config.get_severity("S130"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S133"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S134"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S136"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S137"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S138"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S139"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S140"), DiagnosticSeverity::Off);
+        assert_eq!(config.get_severity("S143"), DiagnosticSeverity::Hint);
+        assert_eq!(config.get_severity("S144"), DiagnosticSeverity::Info);
+        assert_eq!(config.get_severity("S145"), DiagnosticSeverity::Warning);
+        assert_eq!(config.get_severity("S146"), DiagnosticSeverity::Off);
+        assert_eq!(config.get_severity("S147"), DiagnosticSeverity::Off);
+        assert_eq!(config.get_severity("S148"), DiagnosticSeverity::Off);
+    }
+
+    #[test]
+    fn test_off_by_default_codes_need_their_exact_code() {
+        let mut severity = HashMap::new();
+        severity.insert("S".to_string(), DiagnosticSeverity::Warning);
+
+        let mut config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
+        assert_eq!(config.get_severity("S140"), DiagnosticSeverity::Off);
+
+        config.set_severity("S140", DiagnosticSeverity::Hint);
+        assert_eq!(config.get_severity("S140"), DiagnosticSeverity::Hint);
+    }
+
+    #[test]
+    fn test_off_by_default_prefixes_need_their_own_prefix() {
+        let mut severity = HashMap::new();
+        severity.insert("S".to_string(), DiagnosticSeverity::Warning);
+
+        let mut config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
+        assert_eq!(config.get_severity("A100"), DiagnosticSeverity::Off);
+        assert_eq!(config.get_severity("X100"), DiagnosticSeverity::Off);
+
+        config.set_severity("X", DiagnosticSeverity::Hint);
+        assert_eq!(config.get_severity("X100"), DiagnosticSeverity::Hint);
+        config.set_severity("X101", DiagnosticSeverity::Off);
+        assert_eq!(config.get_severity("X101"), DiagnosticSeverity::Off);
     }
 
     #[test]
@@ -102,7 +438,10 @@ mod tests {
         severity.insert("S100".to_string(), DiagnosticSeverity::Warning);
         severity.insert("S101".to_string(), DiagnosticSeverity::Off);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Warning);
         assert_eq!(config.get_severity("S101"), DiagnosticSeverity::Off);
@@ -115,7 +454,10 @@ mod tests {
         severity.insert("S".to_string(), DiagnosticSeverity::Warning);
         severity.insert("T".to_string(), DiagnosticSeverity::Off);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Warning);
         assert_eq!(config.get_severity("S101"), DiagnosticSeverity::Warning);
@@ -130,7 +472,10 @@ mod tests {
         severity.insert("S1".to_string(), DiagnosticSeverity::Off);
         severity.insert("S10".to_string(), DiagnosticSeverity::Hint);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         // S10 is most specific for S100
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Hint);
@@ -149,7 +494,10 @@ mod tests {
         severity.insert("S1".to_string(), DiagnosticSeverity::Off);
         severity.insert("S100".to_string(), DiagnosticSeverity::Error);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         // Exact match wins
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Error);
@@ -171,7 +519,10 @@ mod tests {
         let mut severity = HashMap::new();
         severity.insert("S100".to_string(), DiagnosticSeverity::Off);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         assert!(!is_enabled(&config, "S100"));
         assert!(is_enabled(&config, "S101"));
@@ -182,7 +533,10 @@ mod tests {
         let mut severity = HashMap::new();
         severity.insert("T".to_string(), DiagnosticSeverity::Off);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         assert!(!is_enabled(&config, "T100"));
         assert!(!is_enabled(&config, "T900"));
@@ -195,7 +549,10 @@ mod tests {
         severity.insert("T".to_string(), DiagnosticSeverity::Off);
         severity.insert("T100".to_string(), DiagnosticSeverity::Hint);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         // T100 has specific override, so it's enabled
         assert!(is_enabled(&config, "T100"));
@@ -224,6 +581,15 @@ mod tests {
         assert_eq!(config.get_severity("T900"), DiagnosticSeverity::Off);
         // T100 has specific override
         assert_eq!(config.get_severity("T100"), DiagnosticSeverity::Info);
+        assert_eq!(config.duplicates(), DuplicatePolicy::Hint);
+    }
+
+    #[test]
+    fn test_deserialize_duplicates() {
+        let config: DiagnosticsConfig = toml::from_str("duplicates = \"fold\"")
+            .expect("duplicates policy fixture should deserialize");
+        assert_eq!(config.duplicates(), DuplicatePolicy::Fold);
+        assert_eq!(config.get_severity("S111"), DiagnosticSeverity::Error);
     }
 
     #[test]
@@ -240,7 +606,10 @@ mod tests {
         // And S10x (S100-S109) should be info
         severity.insert("S10".to_string(), DiagnosticSeverity::Info);
 
-        let config = DiagnosticsConfig { severity };
+        let config = DiagnosticsConfig {
+            severity,
+            ..DiagnosticsConfig::default()
+        };
 
         // S100 is exact match - off
         assert_eq!(config.get_severity("S100"), DiagnosticSeverity::Off);
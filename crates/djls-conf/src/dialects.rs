@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+
+use crate::overrides::by_specificity;
+use crate::overrides::glob_matches;
+
+/// Template language a file is written in, from a `[dialects]` entry, an override, or the
+/// file's own `{# djls: dialect=... #}` pragma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    Django,
+    #[serde(alias = "jinja")]
+    Jinja2,
+}
+
+impl Dialect {
+    /// Parse a dialect name, accepting the same spellings as configuration.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "django" => Some(Self::Django),
+            "jinja2" | "jinja" => Some(Self::Jinja2),
+            _ => None,
+        }
+    }
+}
+
+/// `[dialects]`: template language per directory, relative to the project root.
+///
+/// Example configuration:
+/// ```toml
+/// [tool.djls.dialects]
+/// "jinja2" = "jinja2"
+/// "jinja2/django_compat" = "django"
+/// ```
+///
+/// A `dialect` in a matching `[overrides."<glob>"]` section takes precedence.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Default)]
+#[serde(from = "BTreeMap<Utf8PathBuf, Dialect>")]
+pub struct DialectsConfig {
+    directories: BTreeMap<Utf8PathBuf, Dialect>,
+    /// Override patterns with a dialect, least specific first.
+    patterns: Vec<(String, Dialect)>,
+}
+
+impl DialectsConfig {
+    /// The dialect of `path`, which is relative to the project root.
+    ///
+    /// The most specific matching override pattern decides, then the deepest configured
+    /// directory containing `path`.
+    #[must_use]
+    pub fn dialect_for(&self, path: &Utf8Path) -> Option<Dialect> {
+        if let Some((_, dialect)) = self
+            .patterns
+            .iter()
+            .rev()
+            .find(|(pattern, _)| glob_matches(pattern, path))
+        {
+            return Some(*dialect);
+        }
+
+        self.directories
+            .iter()
+            .filter(|(dir, _)| path.starts_with(dir))
+            .max_by_key(|(dir, _)| dir.components().count())
+            .map(|(_, dialect)| *dialect)
+    }
+
+    pub(crate) fn extend(&mut self, other: DialectsConfig) {
+        self.directories.extend(other.directories);
+    }
+
+    pub(crate) fn set_overrides(&mut self, patterns: impl IntoIterator<Item = (String, Dialect)>) {
+        self.patterns = patterns.into_iter().collect();
+        self.patterns.sort_by(|(a, _), (b, _)| by_specificity(a, b));
+    }
+}
+
+impl From<BTreeMap<Utf8PathBuf, Dialect>> for DialectsConfig {
+    fn from(directories: BTreeMap<Utf8PathBuf, Dialect>) -> Self {
+        Self {
+            directories,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl FromIterator<(Utf8PathBuf, Dialect)> for DialectsConfig {
+    fn from_iter<I: IntoIterator<Item = (Utf8PathBuf, Dialect)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<BTreeMap<_, _>>())
+    }
+}
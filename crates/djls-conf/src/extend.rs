@@ -0,0 +1,96 @@
+//! `extend = "..."`: layering a shared preset beneath a configuration file.
+//!
+//! A preset is an ordinary `djls.toml`, named by a path relative to the file that extends it,
+//! or by a directory holding one. It is loaded just below the extending file, so the extending
+//! file's values win; presets may extend other presets. `${VAR}` references in a preset are
+//! expanded before its own `extend` is followed.
+//!
+//! `extend` is only ever a path. Package names are not resolved, since configuration is loaded
+//! before the interpreter and its search paths are known.
+
+use std::fs;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use config::File;
+use config::FileFormat;
+use config::builder::ConfigBuilder;
+use config::builder::DefaultState;
+
+use crate::ConfigError;
+use crate::interpolate;
+
+pub(crate) type Builder = ConfigBuilder<DefaultState>;
+
+/// Add the presets a configuration file extends to `builder`, lowest precedence first.
+///
+/// `table` is the file's parsed contents and `path` is where it lives. The file itself is not
+/// added.
+pub(crate) fn add_presets(
+    builder: Builder,
+    table: &toml::Table,
+    path: &Utf8Path,
+) -> Result<Builder, ConfigError> {
+    let mut chain = vec![canonical_or_self(path)];
+    add_extended(builder, table, path, &mut chain)
+}
+
+fn add_extended(
+    mut builder: Builder,
+    table: &toml::Table,
+    path: &Utf8Path,
+    chain: &mut Vec<Utf8PathBuf>,
+) -> Result<Builder, ConfigError> {
+    let Some(extend) = table.get("extend") else {
+        return Ok(builder);
+    };
+    let Some(extend) = extend.as_str() else {
+        return Err(ConfigError::ExtendNotAPath {
+            path: path.to_owned(),
+        });
+    };
+
+    let base = path.parent().unwrap_or(Utf8Path::new("."));
+    let mut preset = base.join(extend);
+    if preset.is_dir() {
+        preset = preset.join("djls.toml");
+    }
+    let preset = preset
+        .canonicalize_utf8()
+        .map_err(|source| ConfigError::ExtendIo {
+            path: preset.clone(),
+            source,
+        })?;
+
+    if chain.contains(&preset) {
+        let cycle = chain
+            .iter()
+            .chain([&preset])
+            .map(Utf8PathBuf::as_str)
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(ConfigError::ExtendCycle(cycle));
+    }
+
+    let content = fs::read_to_string(&preset).map_err(|source| ConfigError::ExtendIo {
+        path: preset.clone(),
+        source,
+    })?;
+    let mut preset_table =
+        toml::from_str::<toml::Table>(&content).map_err(|source| ConfigError::ExtendParse {
+            path: preset.clone(),
+            source,
+        })?;
+    interpolate::expand_table(&mut preset_table, &preset)?;
+
+    chain.push(preset.clone());
+    builder = add_extended(builder, &preset_table, &preset, chain)?;
+    chain.pop();
+
+    let expanded = toml::to_string(&preset_table)?;
+    Ok(builder.add_source(File::from_str(&expanded, FileFormat::Toml)))
+}
+
+fn canonical_or_self(path: &Utf8Path) -> Utf8PathBuf {
+    path.canonicalize_utf8().unwrap_or_else(|_| path.to_owned())
+}
@@ -6,6 +6,10 @@ pub struct FormatConfig {
     enabled: bool,
     #[serde(default)]
     backend: FormatBackend,
+    #[serde(default)]
+    insert_end_tags: bool,
+    #[serde(default = "default_merge_loads")]
+    merge_loads: bool,
 }
 
 impl Default for FormatConfig {
@@ -13,6 +17,8 @@ impl Default for FormatConfig {
         Self {
             enabled: false,
             backend: FormatBackend::Djangofmt,
+            insert_end_tags: false,
+            merge_loads: true,
         }
     }
 }
@@ -27,6 +33,19 @@ impl FormatConfig {
     pub fn backend(&self) -> FormatBackend {
         self.backend
     }
+
+    /// Whether typing the `%}` of a block opener inserts its end tag below.
+    #[must_use]
+    pub fn insert_end_tags(&self) -> bool {
+        self.insert_end_tags
+    }
+
+    /// Whether organizing `{% load %}` tags merges whole-library loads into
+    /// one tag rather than one tag per library.
+    #[must_use]
+    pub fn merge_loads(&self) -> bool {
+        self.merge_loads
+    }
 }
 
 #[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
@@ -39,3 +58,7 @@ pub enum FormatBackend {
 fn default_enabled() -> bool {
     false
 }
+
+fn default_merge_loads() -> bool {
+    true
+}
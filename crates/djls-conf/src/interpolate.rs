@@ -0,0 +1,157 @@
+//! `${VAR}` references in configuration string values.
+//!
+//! Every string in a configuration file may name environment variables as `${NAME}`, with an
+//! optional fallback as `${NAME:-default}`. `$$` is a literal `$`. Referencing a variable that
+//! is unset and has no fallback is an error rather than an empty string, so a missing variable
+//! cannot silently point a path at the wrong place.
+
+use std::env;
+
+use camino::Utf8Path;
+
+use crate::ConfigError;
+
+/// Expand the variable references in every string of `table`, read from `path`.
+pub(crate) fn expand_table(table: &mut toml::Table, path: &Utf8Path) -> Result<(), ConfigError> {
+    expand_table_with(table, path, &|name| env::var(name).ok())
+}
+
+fn expand_table_with(
+    table: &mut toml::Table,
+    path: &Utf8Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+    for value in table.values_mut() {
+        expand_value(value, path, lookup)?;
+    }
+    Ok(())
+}
+
+fn expand_value(
+    value: &mut toml::Value,
+    path: &Utf8Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+    if let Some(text) = value.as_str() {
+        if text.contains('$') {
+            let expanded = expand_str(text, path, lookup)?;
+            *value = toml::Value::String(expanded);
+        }
+    } else if let Some(array) = value.as_array_mut() {
+        for item in array {
+            expand_value(item, path, lookup)?;
+        }
+    } else if let Some(table) = value.as_table_mut() {
+        expand_table_with(table, path, lookup)?;
+    }
+    Ok(())
+}
+
+fn expand_str(
+    text: &str,
+    path: &Utf8Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, ConfigError> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            expanded.push('$');
+            rest = after;
+        } else if let Some(reference) = after.strip_prefix('{') {
+            let Some(end) = reference.find('}') else {
+                return Err(ConfigError::Interpolation {
+                    path: path.to_owned(),
+                    message: format!("unclosed `${{` in \"{text}\""),
+                });
+            };
+            let (name, fallback) = match reference[..end].split_once(":-") {
+                Some((name, fallback)) => (name, Some(fallback)),
+                None => (&reference[..end], None),
+            };
+            match lookup(name).or_else(|| fallback.map(str::to_string)) {
+                Some(value) => expanded.push_str(&value),
+                None => {
+                    return Err(ConfigError::Interpolation {
+                        path: path.to_owned(),
+                        message: format!("environment variable `{name}` is not set"),
+                    });
+                }
+            }
+            rest = &reference[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = after;
+        }
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(text: &str) -> Result<String, ConfigError> {
+        let lookup = |name: &str| match name {
+            "HOME" => Some("/home/dev".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+        expand_str(text, Utf8Path::new("djls.toml"), &lookup)
+    }
+
+    #[test]
+    fn test_expands_references_and_fallbacks() {
+        assert_eq!(
+            expand("${HOME}/.venvs/site").expect("set variable should expand"),
+            "/home/dev/.venvs/site"
+        );
+        assert_eq!(
+            expand("${VENV:-.venv}").expect("unset variable should use its fallback"),
+            ".venv"
+        );
+        assert_eq!(
+            expand("a${EMPTY}b").expect("empty variable should expand"),
+            "ab"
+        );
+    }
+
+    #[test]
+    fn test_leaves_other_dollars_alone() {
+        assert_eq!(
+            expand("$$HOME costs $5").expect("text without references should expand"),
+            "$HOME costs $5"
+        );
+    }
+
+    #[test]
+    fn test_unset_variable_is_an_error() {
+        let error = expand("${MISSING}/lib").expect_err("unset variable should be rejected");
+        assert!(error.to_string().contains("MISSING"), "{error}");
+    }
+
+    #[test]
+    fn test_expands_nested_values() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+pythonpath = ["${HOME}/lib"]
+
+[format]
+backend = "${BACKEND:-djangofmt}"
+"#,
+        )
+        .expect("fixture should parse");
+        let lookup = |name: &str| (name == "HOME").then(|| "/home/dev".to_string());
+        expand_table_with(&mut table, Utf8Path::new("djls.toml"), &lookup)
+            .expect("fixture references should expand");
+
+        assert_eq!(table["pythonpath"][0].as_str(), Some("/home/dev/lib"));
+        assert_eq!(table["format"]["backend"].as_str(), Some("djangofmt"));
+    }
+}
@@ -1,8 +1,18 @@
+mod codes;
+mod completions;
 mod diagnostics;
+mod dialects;
 mod django_environments;
+mod extend;
 mod format;
+mod interpolate;
+mod overrides;
+mod path_mappings;
+mod plugins;
+mod schema;
 mod tagspecs;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -17,11 +27,28 @@ use directories::ProjectDirs;
 use serde::Deserialize;
 use thiserror::Error;
 
+pub use crate::codes::DIAGNOSTIC_CODES;
+pub use crate::codes::DiagnosticCode;
+pub use crate::codes::diagnostic_code;
+pub use crate::completions::CompletionsConfig;
+pub use crate::diagnostics::ComplexityLimits;
+pub use crate::diagnostics::DiagnosticLimits;
 pub use crate::diagnostics::DiagnosticSeverity;
 pub use crate::diagnostics::DiagnosticsConfig;
+pub use crate::diagnostics::DuplicatePolicy;
+pub use crate::dialects::Dialect;
+pub use crate::dialects::DialectsConfig;
 pub use crate::django_environments::DjangoEnvironmentConfig;
 pub use crate::format::FormatBackend;
 pub use crate::format::FormatConfig;
+pub use crate::overrides::OverrideConfig;
+pub use crate::path_mappings::PathMappings;
+pub use crate::plugins::PluginConfig;
+pub use crate::schema::ConfigFileCheck;
+pub use crate::schema::ConfigProblem;
+pub use crate::schema::check_config_file;
+pub use crate::schema::check_project_config;
+pub use crate::schema::check_pyproject;
 pub use crate::tagspecs::ArgKindDef;
 pub use crate::tagspecs::ArgTypeDef;
 pub use crate::tagspecs::EndTagDef;
@@ -29,9 +56,13 @@ pub use crate::tagspecs::IntermediateTagDef;
 pub use crate::tagspecs::TagArgDef;
 pub use crate::tagspecs::TagDef;
 pub use crate::tagspecs::TagLibraryDef;
+pub use crate::tagspecs::TagShorthandDef;
 pub use crate::tagspecs::TagSpecDef;
 pub use crate::tagspecs::TagTypeDef;
 
+/// Project configuration files read from the project root, lowest precedence first.
+pub const PROJECT_CONFIG_FILES: [&str; 3] = ["pyproject.toml", ".djls.toml", "djls.toml"];
+
 #[must_use]
 fn project_dirs() -> Option<ProjectDirs> {
     ProjectDirs::from("", "", "djls")
@@ -65,12 +96,31 @@ pub enum ConfigError {
     PyprojectParse(#[from] toml::de::Error),
     #[error("Failed to serialize extracted pyproject.toml data")]
     PyprojectSerialize(#[from] toml::ser::Error),
+    #[error("`extend` in {path} must be a path")]
+    ExtendNotAPath { path: Utf8PathBuf },
+    #[error("Failed to read extended configuration {path}")]
+    ExtendIo {
+        path: Utf8PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to parse extended configuration {path}")]
+    ExtendParse {
+        path: Utf8PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("Configuration `extend` chain forms a cycle: {0}")]
+    ExtendCycle(String),
+    #[error("Failed to expand environment variables in {path}: {message}")]
+    Interpolation { path: Utf8PathBuf, message: String },
 }
 
 #[derive(Debug, Deserialize, Default, PartialEq, Clone)]
 pub struct Settings {
     #[serde(default)]
     debug: bool,
+    log_level: Option<String>,
     venv_path: Option<Utf8PathBuf>,
     django_settings_module: Option<String>,
     #[serde(default)]
@@ -80,10 +130,24 @@ pub struct Settings {
     env_file: Option<String>,
     #[serde(default)]
     tagspecs: TagSpecDef,
+    /// `[tags.<name>]` shorthand tables, folded into `tagspecs` by [`Settings::new`].
+    #[serde(default)]
+    tags: BTreeMap<String, TagShorthandDef>,
+    #[serde(default)]
+    dialects: DialectsConfig,
+    #[serde(default)]
+    path_mappings: PathMappings,
     #[serde(default)]
     diagnostics: DiagnosticsConfig,
     #[serde(default)]
     format: FormatConfig,
+    #[serde(default)]
+    completions: CompletionsConfig,
+    #[serde(default)]
+    plugins: Vec<PluginConfig>,
+    /// `[overrides."<glob>"]` sections, keyed by pattern.
+    #[serde(default)]
+    overrides: BTreeMap<String, OverrideConfig>,
 }
 
 impl Settings {
@@ -97,6 +161,7 @@ impl Settings {
             let has_django_environments = !overrides.django_environments.is_empty();
 
             settings.debug = overrides.debug || settings.debug;
+            settings.log_level = overrides.log_level.or(settings.log_level);
             settings.venv_path = overrides.venv_path.or(settings.venv_path);
             settings.django_settings_module = overrides
                 .django_settings_module
@@ -108,9 +173,12 @@ impl Settings {
                 settings.pythonpath = overrides.pythonpath;
             }
             settings.env_file = overrides.env_file.or(settings.env_file);
-            if !overrides.tagspecs.libraries.is_empty() {
+            if !overrides.tagspecs.libraries.is_empty() || !overrides.tagspecs.tags.is_empty() {
                 settings.tagspecs = overrides.tagspecs;
             }
+            settings.tags.extend(overrides.tags);
+            settings.dialects.extend(overrides.dialects);
+            settings.path_mappings.extend(overrides.path_mappings);
             // For diagnostics, override if the config is non-default
             if overrides.diagnostics != DiagnosticsConfig::default() {
                 settings.diagnostics = overrides.diagnostics;
@@ -118,8 +186,24 @@ impl Settings {
             if overrides.format != FormatConfig::default() {
                 settings.format = overrides.format;
             }
+            if overrides.completions != CompletionsConfig::default() {
+                settings.completions = overrides.completions;
+            }
+            if !overrides.plugins.is_empty() {
+                settings.plugins = overrides.plugins;
+            }
+            settings.overrides.extend(overrides.overrides);
         }
 
+        let shorthand_tags = std::mem::take(&mut settings.tags);
+        settings.tagspecs.tags.extend(shorthand_tags);
+        settings.dialects.set_overrides(
+            settings
+                .overrides
+                .iter()
+                .filter_map(|(pattern, config)| Some((pattern.clone(), config.dialect()?))),
+        );
+
         Ok(settings)
     }
 
@@ -130,42 +214,54 @@ impl Settings {
         let mut builder = Config::builder();
 
         if let Some(path) = user_config_path {
-            builder = builder.add_source(File::from(path).format(FileFormat::Toml).required(false));
-        }
-
-        let pyproject_path = project_root.join("pyproject.toml");
-        if pyproject_path.exists() {
-            let content = fs::read_to_string(&pyproject_path)?;
-            let toml_str: toml::Value = toml::from_str(&content)?;
-            let tool_djls_value: Option<&toml::Value> =
-                ["tool", "djls"].iter().try_fold(&toml_str, |val, &key| {
-                    // Attempt to get the next key. If it exists, return Some(value) to continue.
-                    // If get returns None, try_fold automatically stops and returns None overall.
-                    val.get(key)
-                });
-            if let Some(tool_djls_table) = tool_djls_value.and_then(|v| v.as_table()) {
-                let tool_djls_string = toml::to_string(tool_djls_table)?;
-                builder = builder.add_source(File::from_str(&tool_djls_string, FileFormat::Toml));
-            }
+            builder = match Utf8Path::from_path(path) {
+                Some(utf8_path) => add_config_file(builder, utf8_path)?,
+                None => {
+                    builder.add_source(File::from(path).format(FileFormat::Toml).required(false))
+                }
+            };
         }
 
-        builder = builder.add_source(
-            File::from(project_root.join(".djls.toml").as_std_path())
-                .format(FileFormat::Toml)
-                .required(false),
-        );
-
-        builder = builder.add_source(
-            File::from(project_root.join("djls.toml").as_std_path())
-                .format(FileFormat::Toml)
-                .required(false),
-        );
+        for name in PROJECT_CONFIG_FILES {
+            let path = project_root.join(name);
+            builder = if name == "pyproject.toml" {
+                add_pyproject(builder, &path)?
+            } else {
+                add_config_file(builder, &path)?
+            };
+        }
 
         let config = builder.build()?;
         let settings: Self = config.try_deserialize()?;
         Ok(settings)
     }
 
+    /// Diagnostics configuration for a template at `path`, relative to the project root.
+    ///
+    /// The severities of every matching `[overrides."<glob>"]` section are layered over
+    /// `[diagnostics.severity]`, with longer patterns applied last.
+    #[must_use]
+    pub fn diagnostics_for(&self, path: &Utf8Path) -> DiagnosticsConfig {
+        let mut diagnostics = self.diagnostics.clone();
+        for config in overrides::matching(&self.overrides, path) {
+            for (code, severity) in config.severity() {
+                diagnostics.set_severity(code, *severity);
+            }
+        }
+        diagnostics
+    }
+
+    /// Log filter directive for the server log file, e.g. `"debug"` or
+    /// `"djls_server=trace,info"`.
+    ///
+    /// Falls back to `"debug"` when `debug = true` and no level is set.
+    #[must_use]
+    pub fn log_level(&self) -> Option<&str> {
+        self.log_level
+            .as_deref()
+            .or_else(|| self.debug.then_some("debug"))
+    }
+
     #[must_use]
     pub fn venv_path(&self) -> Option<&Utf8Path> {
         self.venv_path.as_deref()
@@ -197,6 +293,16 @@ impl Settings {
         &self.tagspecs
     }
 
+    #[must_use]
+    pub fn dialects(&self) -> &DialectsConfig {
+        &self.dialects
+    }
+
+    #[must_use]
+    pub fn path_mappings(&self) -> &PathMappings {
+        &self.path_mappings
+    }
+
     #[must_use]
     pub fn diagnostics(&self) -> &DiagnosticsConfig {
         &self.diagnostics
@@ -206,6 +312,69 @@ impl Settings {
     pub fn format(&self) -> &FormatConfig {
         &self.format
     }
+
+    #[must_use]
+    pub fn completions(&self) -> &CompletionsConfig {
+        &self.completions
+    }
+
+    #[must_use]
+    pub fn plugins(&self) -> &[PluginConfig] {
+        &self.plugins
+    }
+
+    /// `[overrides."<glob>"]` sections, by pattern.
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, &OverrideConfig)> {
+        self.overrides
+            .iter()
+            .map(|(pattern, config)| (pattern.as_str(), config))
+    }
+}
+
+/// Add the `[tool.djls]` table of the `pyproject.toml` at `path`, if it has one.
+fn add_pyproject(
+    builder: extend::Builder,
+    path: &Utf8Path,
+) -> Result<extend::Builder, ConfigError> {
+    if !path.exists() {
+        return Ok(builder);
+    }
+    let content = fs::read_to_string(path)?;
+    let toml_str: toml::Value = toml::from_str(&content)?;
+    let tool_djls_value: Option<&toml::Value> =
+        ["tool", "djls"].iter().try_fold(&toml_str, |val, &key| {
+            // Attempt to get the next key. If it exists, return Some(value) to continue.
+            // If get returns None, try_fold automatically stops and returns None overall.
+            val.get(key)
+        });
+    let Some(tool_djls_table) = tool_djls_value.and_then(|v| v.as_table()) else {
+        return Ok(builder);
+    };
+    let mut tool_djls_table = tool_djls_table.clone();
+    interpolate::expand_table(&mut tool_djls_table, path)?;
+    let builder = extend::add_presets(builder, &tool_djls_table, path)?;
+    let tool_djls_string = toml::to_string(&tool_djls_table)?;
+    Ok(builder.add_source(File::from_str(&tool_djls_string, FileFormat::Toml)))
+}
+
+/// Add the configuration file at `path`, if it exists, with its `${VAR}` references expanded
+/// and the presets it extends beneath it.
+///
+/// A file that does not parse is added as-is, so the config builder reports its syntax error.
+fn add_config_file(
+    builder: extend::Builder,
+    path: &Utf8Path,
+) -> Result<extend::Builder, ConfigError> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(builder);
+    };
+    let Ok(mut table) = toml::from_str::<toml::Table>(&content) else {
+        return Ok(builder.add_source(File::from(path.as_std_path()).format(FileFormat::Toml)));
+    };
+    interpolate::expand_table(&mut table, path)?;
+    let builder = extend::add_presets(builder, &table, path)?;
+    let expanded = toml::to_string(&table)?;
+    Ok(builder.add_source(File::from_str(&expanded, FileFormat::Toml)))
 }
 
 #[cfg(test)]
@@ -230,14 +399,21 @@ mod tests {
                 settings,
                 Settings {
                     debug: false,
+                    log_level: None,
                     venv_path: None,
                     django_settings_module: None,
                     django_environments: vec![],
                     pythonpath: vec![],
                     env_file: None,
                     tagspecs: TagSpecDef::default(),
+                    tags: BTreeMap::new(),
+                    dialects: DialectsConfig::default(),
+                    path_mappings: PathMappings::default(),
                     diagnostics: DiagnosticsConfig::default(),
                     format: FormatConfig::default(),
+                    completions: CompletionsConfig::default(),
+                    plugins: vec![],
+                    overrides: BTreeMap::new(),
                 }
             );
         }
@@ -264,6 +440,29 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_load_log_level_config() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                "debug = true\nlog_level = 'trace'",
+            )
+            .expect("test should write log level djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("log level djls.toml fixture should load settings");
+            assert_eq!(settings.log_level(), Some("trace"));
+            assert_eq!(
+                Settings {
+                    debug: true,
+                    ..Default::default()
+                }
+                .log_level(),
+                Some("debug")
+            );
+        }
+
         #[test]
         fn test_load_venv_path_config() {
             let dir = tempdir().expect("test should create temporary project directory");
@@ -440,6 +639,8 @@ django_settings_module = "override.settings"
 [format]
 enabled = true
 backend = "djangofmt"
+insert_end_tags = true
+merge_loads = false
 "#,
             )
             .expect("test should write format djls.toml fixture");
@@ -450,6 +651,55 @@ backend = "djangofmt"
 
             assert!(settings.format().enabled());
             assert_eq!(settings.format().backend(), FormatBackend::Djangofmt);
+            assert!(settings.format().insert_end_tags());
+            assert!(!settings.format().merge_loads());
+        }
+
+        #[test]
+        fn test_load_completions_config() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+[completions]
+fuzzy = true
+htmx = true
+"#,
+            )
+            .expect("test should write completions djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("completions djls.toml fixture should load settings");
+
+            assert!(settings.completions().fuzzy());
+            assert!(settings.completions().htmx());
+            assert!(!settings.completions().alpine());
+            assert!(!Settings::default().completions().fuzzy());
+            assert!(!Settings::default().completions().htmx());
+        }
+
+        #[test]
+        fn test_load_plugins_config() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+[[plugins]]
+name = "house-style"
+command = ["python", "tools/djls_rules.py"]
+"#,
+            )
+            .expect("test should write plugins djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("plugins djls.toml fixture should load settings");
+
+            assert_eq!(settings.plugins().len(), 1);
+            let plugin = &settings.plugins()[0];
+            assert_eq!(plugin.name(), "house-style");
+            assert_eq!(plugin.command(), ["python", "tools/djls_rules.py"]);
         }
 
         #[test]
@@ -487,6 +737,48 @@ T100 = "hint"
                 DiagnosticSeverity::Hint
             );
         }
+
+        #[test]
+        fn test_load_diagnostic_limits() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                "[diagnostics.limits]\nmax_nodes = 5000\nmax_nesting_depth = 40\n",
+            )
+            .expect("test should write limits djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("limits djls.toml fixture should load settings");
+            let limits = settings.diagnostics().limits();
+            assert_eq!(limits.max_nodes(), 5000);
+            assert_eq!(limits.max_nesting_depth(), 40);
+            assert_eq!(
+                limits.max_file_size(),
+                DiagnosticLimits::default().max_file_size()
+            );
+        }
+
+        #[test]
+        fn test_load_complexity_limits() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                "[diagnostics.complexity]\nmax_branches = 12\nmax_includes = 3\n",
+            )
+            .expect("test should write complexity djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("complexity djls.toml fixture should load settings");
+            let complexity = settings.diagnostics().complexity();
+            assert_eq!(complexity.max_branches(), 12);
+            assert_eq!(complexity.max_includes(), 3);
+            assert_eq!(
+                complexity.max_nodes(),
+                ComplexityLimits::default().max_nodes()
+            );
+        }
     }
 
     mod priority {
@@ -674,6 +966,99 @@ T100 = "hint"
         }
     }
 
+    mod extend {
+        use super::*;
+
+        #[test]
+        fn test_extending_file_overrides_preset() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::create_dir(dir.path().join("presets"))
+                .expect("test should create preset directory");
+            fs::write(
+                dir.path().join("presets/base.toml"),
+                "debug = true\n[completions]\nfuzzy = true\n",
+            )
+            .expect("test should write preset fixture");
+            fs::write(
+                dir.path().join("djls.toml"),
+                "extend = \"presets/base.toml\"\ndebug = false\n",
+            )
+            .expect("test should write djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::load_from_paths(project_root, None)
+                .expect("extended configuration should load settings");
+
+            assert!(!settings.debug);
+            assert!(settings.completions().fuzzy());
+        }
+
+        #[test]
+        fn test_presets_chain_and_resolve_directories() {
+            let dir = tempdir().expect("test should create temporary directory");
+            let shared = dir.path().join("shared");
+            let project = dir.path().join("project");
+            fs::create_dir(&shared).expect("test should create shared preset directory");
+            fs::create_dir(&project).expect("test should create project directory");
+            fs::write(shared.join("base.toml"), "[diagnostics.severity]\nS100 = \"off\"\n")
+                .expect("test should write base preset fixture");
+            fs::write(
+                shared.join("djls.toml"),
+                "extend = \"base.toml\"\n[diagnostics.severity]\nS101 = \"hint\"\n",
+            )
+            .expect("test should write directory preset fixture");
+            fs::write(
+                project.join("pyproject.toml"),
+                "[tool.djls]\nextend = \"../shared\"\n",
+            )
+            .expect("test should write pyproject.toml fixture");
+
+            let project_root = Utf8Path::from_path(&project)
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::load_from_paths(project_root, None)
+                .expect("chained presets should load settings");
+
+            let diagnostics = settings.diagnostics();
+            assert_eq!(diagnostics.get_severity("S100"), DiagnosticSeverity::Off);
+            assert_eq!(diagnostics.get_severity("S101"), DiagnosticSeverity::Hint);
+        }
+
+        #[test]
+        fn test_extend_cycle_is_an_error() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(dir.path().join("a.toml"), "extend = \"b.toml\"\n")
+                .expect("test should write first preset fixture");
+            fs::write(dir.path().join("b.toml"), "extend = \"a.toml\"\n")
+                .expect("test should write second preset fixture");
+            fs::write(dir.path().join("djls.toml"), "extend = \"a.toml\"\n")
+                .expect("test should write djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let error = Settings::load_from_paths(project_root, None)
+                .expect_err("cyclic presets should fail to load");
+
+            let message = error.to_string();
+            assert!(matches!(error, ConfigError::ExtendCycle(_)), "{error:?}");
+            assert!(message.ends_with("a.toml -> b.toml -> a.toml"), "{message}");
+        }
+
+        #[test]
+        fn test_missing_preset_is_an_error() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(dir.path().join("djls.toml"), "extend = \"missing.toml\"\n")
+                .expect("test should write djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let error = Settings::load_from_paths(project_root, None)
+                .expect_err("a missing preset should fail to load");
+
+            assert!(matches!(error, ConfigError::ExtendIo { .. }), "{error:?}");
+        }
+    }
+
     mod tagspecs {
         use super::*;
 
@@ -719,6 +1104,42 @@ kind = "variable"
             assert_eq!(doc.libraries[0].tags.len(), 1);
             assert_eq!(doc.libraries[0].tags[0].name, "switch");
         }
+
+        #[test]
+        fn test_load_tag_shorthand_from_djls_toml() {
+            let dir = tempdir().expect("test should create temporary project directory");
+
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+[tags.mytag]
+end = "endmytag"
+intermediates = ["else"]
+
+[tagspecs.tags.other]
+end = "endother"
+"#,
+            )
+            .expect("test should write tag shorthand djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("tag shorthand fixture should load settings");
+            let tags = &settings.tagspecs().tags;
+
+            assert_eq!(
+                tags.get("mytag"),
+                Some(&TagShorthandDef {
+                    end: Some("endmytag".to_string()),
+                    intermediates: vec!["else".to_string()],
+                })
+            );
+            assert_eq!(
+                tags.get("other").and_then(|tag| tag.end.as_deref()),
+                Some("endother")
+            );
+        }
     }
 
     mod errors {
@@ -784,4 +1205,325 @@ root = "site"
             );
         }
     }
+
+    mod dialects {
+        use super::*;
+
+        #[test]
+        fn test_deepest_configured_directory_wins() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+[dialects]
+"jinja2" = "jinja2"
+"jinja2/django_compat" = "django"
+"#,
+            )
+            .expect("test should write dialects djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("dialects djls.toml fixture should load settings");
+            let dialects = settings.dialects();
+
+            assert_eq!(
+                dialects.dialect_for(Utf8Path::new("jinja2/base.html")),
+                Some(Dialect::Jinja2)
+            );
+            assert_eq!(
+                dialects.dialect_for(Utf8Path::new("jinja2/django_compat/base.html")),
+                Some(Dialect::Django)
+            );
+            assert_eq!(
+                dialects.dialect_for(Utf8Path::new("jinja2_extra/base.html")),
+                None
+            );
+        }
+    }
+
+    mod path_mappings {
+        use super::*;
+
+        #[test]
+        fn test_longest_container_prefix_wins() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+[path_mappings]
+"/app" = "."
+"/app/shared" = "/opt/shared"
+"#,
+            )
+            .expect("test should write path mappings djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("path mappings djls.toml fixture should load settings");
+            let mappings = settings.path_mappings();
+            let root = Utf8Path::new("/work");
+
+            assert_eq!(
+                mappings.to_workspace(root, Utf8Path::new("/app/templates")),
+                Some(Utf8PathBuf::from("/work/./templates"))
+            );
+            assert_eq!(
+                mappings.to_workspace(root, Utf8Path::new("/app/shared/templates")),
+                Some(Utf8PathBuf::from("/opt/shared/templates"))
+            );
+            assert_eq!(
+                mappings.to_workspace(root, Utf8Path::new("/application/templates")),
+                None
+            );
+        }
+    }
+
+    mod overrides {
+        use super::*;
+
+        fn load(content: &str) -> Settings {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(dir.path().join("djls.toml"), content)
+                .expect("test should write overrides djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            Settings::new(project_root, None).expect("overrides fixture should load settings")
+        }
+
+        #[test]
+        fn test_override_severities_apply_under_the_pattern() {
+            let settings = load(
+                r#"
+[diagnostics.severity]
+S100 = "warning"
+
+[overrides."apps/legacy/**".diagnostics.severity]
+S100 = "off"
+S101 = "hint"
+
+[overrides."apps/legacy/admin/**".diagnostics.severity]
+S100 = "error"
+"#,
+            );
+
+            let legacy = settings.diagnostics_for(Utf8Path::new("apps/legacy/a/b.html"));
+            assert_eq!(legacy.get_severity("S100"), DiagnosticSeverity::Off);
+            assert_eq!(legacy.get_severity("S101"), DiagnosticSeverity::Hint);
+
+            let admin = settings.diagnostics_for(Utf8Path::new("apps/legacy/admin/x.html"));
+            assert_eq!(admin.get_severity("S100"), DiagnosticSeverity::Error);
+            assert_eq!(admin.get_severity("S101"), DiagnosticSeverity::Hint);
+
+            let other = settings.diagnostics_for(Utf8Path::new("apps/legacy_new/x.html"));
+            assert_eq!(other.get_severity("S100"), DiagnosticSeverity::Warning);
+        }
+
+        #[test]
+        fn test_override_dialect_beats_directories() {
+            let settings = load(
+                r#"
+[dialects]
+"apps" = "jinja2"
+
+[overrides."apps/*/emails/**"]
+dialect = "django"
+"#,
+            );
+            let dialects = settings.dialects();
+
+            assert_eq!(
+                dialects.dialect_for(Utf8Path::new("apps/shop/emails/receipt.html")),
+                Some(Dialect::Django)
+            );
+            assert_eq!(
+                dialects.dialect_for(Utf8Path::new("apps/shop/index.html")),
+                Some(Dialect::Jinja2)
+            );
+        }
+
+        #[test]
+        fn test_glob_patterns() {
+            let matches = |pattern: &str, path: &str| {
+                crate::overrides::glob_matches(pattern, Utf8Path::new(path))
+            };
+
+            assert!(matches("apps/legacy/**", "apps/legacy/x.html"));
+            assert!(matches("apps/legacy/**", "apps/legacy/a/b.html"));
+            assert!(!matches("apps/legacy/**", "apps/legacy_new/x.html"));
+            assert!(matches("**/emails/*.txt", "emails/welcome.txt"));
+            assert!(matches("**/emails/*.txt", "apps/shop/emails/welcome.txt"));
+            assert!(!matches("**/emails/*.txt", "apps/shop/emails/welcome.html"));
+            assert!(matches("apps/*/templates/**", "apps/shop/templates/base.html"));
+            assert!(!matches("apps/*/templates/**", "apps/templates/base.html"));
+            assert!(matches("partials/_?.html", "partials/_a.html"));
+        }
+    }
+
+    mod interpolation {
+        use super::*;
+
+        #[test]
+        fn test_variables_expand_in_project_and_preset_files() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("base.toml"),
+                "django_settings_module = \"${DJLS_TEST_UNSET_SETTINGS:-site.settings}\"\n",
+            )
+            .expect("test should write preset fixture");
+            fs::write(
+                dir.path().join("djls.toml"),
+                r#"
+extend = "${DJLS_TEST_UNSET_PRESET:-base.toml}"
+venv_path = "${DJLS_TEST_UNSET_VENV:-.venv}/$$"
+"#,
+            )
+            .expect("test should write djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let settings = Settings::new(project_root, None)
+                .expect("interpolated configuration should load settings");
+
+            assert_eq!(settings.django_settings_module(), Some("site.settings"));
+            assert_eq!(settings.venv_path(), Some(Utf8Path::new(".venv/$")));
+        }
+
+        #[test]
+        fn test_unset_variable_is_an_error() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(
+                dir.path().join("djls.toml"),
+                "venv_path = \"${DJLS_TEST_UNSET_VENV}\"\n",
+            )
+            .expect("test should write djls.toml fixture");
+
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+            let error = Settings::new(project_root, None)
+                .expect_err("an unset variable should fail to load");
+
+            assert!(matches!(error, ConfigError::Interpolation { .. }), "{error:?}");
+        }
+    }
+
+    mod schema {
+        use super::*;
+
+        fn located(content: &str, problems: &[ConfigProblem]) -> Vec<(String, String)> {
+            problems
+                .iter()
+                .map(|problem| {
+                    (
+                        content[problem.span()].to_string(),
+                        problem.message().to_string(),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_valid_file_has_no_problems() {
+            let content = r#"
+venv_path = ".venv"
+
+[diagnostics.severity]
+S100 = "warning"
+
+[format]
+enabled = true
+
+[[plugins]]
+name = "house-rules"
+command = ["python", "lint.py"]
+"#;
+            assert_eq!(check_config_file(content), vec![]);
+        }
+
+        #[test]
+        fn test_unknown_keys_are_located_at_the_key() {
+            let content = r#"
+venv = ".venv"
+
+[format]
+enable = true
+
+[[plugins]]
+name = "house-rules"
+cmd = ["python", "lint.py"]
+"#;
+            assert_eq!(
+                located(content, &check_config_file(content)),
+                vec![
+                    ("venv".to_string(), "unknown setting `venv`".to_string()),
+                    (
+                        "enable".to_string(),
+                        "unknown setting `format.enable`".to_string()
+                    ),
+                    (
+                        "cmd".to_string(),
+                        "unknown setting `plugins[0].cmd`".to_string()
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_wrong_types_are_located_at_the_value() {
+            let content = "debug = \"yes\"\n";
+            let problems = check_config_file(content);
+
+            assert_eq!(problems.len(), 1);
+            assert_eq!(&content[problems[0].span()], "\"yes\"");
+        }
+
+        #[test]
+        fn test_syntax_errors_are_reported() {
+            let problems = check_config_file("debug = not_a_boolean");
+
+            assert_eq!(problems.len(), 1);
+        }
+
+        #[test]
+        fn test_pyproject_checks_only_tool_djls() {
+            let content = r#"
+[project]
+name = "site"
+
+[tool.ruff]
+line-length = 100
+
+[tool.djls]
+debug = true
+log-level = "trace"
+"#;
+            assert_eq!(
+                located(content, &check_pyproject(content)),
+                vec![(
+                    "log-level".to_string(),
+                    "unknown setting `log-level`".to_string()
+                )]
+            );
+            assert_eq!(check_pyproject("[project]\nname = \"site\"\n"), vec![]);
+        }
+
+        #[test]
+        fn test_check_project_config_lists_existing_files() {
+            let dir = tempdir().expect("test should create temporary project directory");
+            fs::write(dir.path().join("djls.toml"), "debug = true")
+                .expect("test should write djls.toml fixture");
+            fs::write(dir.path().join(".djls.toml"), "debugg = true")
+                .expect("test should write .djls.toml fixture");
+            let project_root = Utf8Path::from_path(dir.path())
+                .expect("temporary project directory path should be valid UTF-8");
+
+            let checked = check_project_config(project_root);
+
+            assert_eq!(checked.len(), 2);
+            assert_eq!(checked[0].path(), project_root.join(".djls.toml"));
+            assert_eq!(checked[0].problems().len(), 1);
+            assert_eq!(checked[1].path(), project_root.join("djls.toml"));
+            assert!(checked[1].problems().is_empty());
+        }
+    }
 }
@@ -0,0 +1,117 @@
+//! `[overrides."<glob>"]`: settings scoped to part of the project tree.
+//!
+//! A pattern is matched against a template's path relative to the project root, one path
+//! component per `/`-separated segment. `**` matches any number of components, and `*` and `?`
+//! match within one component, so `apps/legacy/**` covers everything under `apps/legacy`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use camino::Utf8Path;
+use serde::Deserialize;
+
+use crate::Dialect;
+use crate::DiagnosticSeverity;
+
+/// One `[overrides."<glob>"]` section.
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct OverrideConfig {
+    #[serde(default)]
+    dialect: Option<Dialect>,
+    #[serde(default)]
+    diagnostics: OverrideDiagnostics,
+}
+
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone)]
+struct OverrideDiagnostics {
+    #[serde(default)]
+    severity: HashMap<String, DiagnosticSeverity>,
+}
+
+impl OverrideConfig {
+    /// Dialect for every template the pattern matches.
+    #[must_use]
+    pub fn dialect(&self) -> Option<Dialect> {
+        self.dialect
+    }
+
+    /// Severities layered over `[diagnostics.severity]` for matching templates.
+    #[must_use]
+    pub fn severity(&self) -> &HashMap<String, DiagnosticSeverity> {
+        &self.diagnostics.severity
+    }
+}
+
+/// The overrides whose pattern matches `path`, least specific first.
+///
+/// Longer patterns are more specific, so applying the result in order lets them win.
+pub(crate) fn matching<'a, I>(overrides: I, path: &Utf8Path) -> Vec<&'a OverrideConfig>
+where
+    I: IntoIterator<Item = (&'a String, &'a OverrideConfig)>,
+{
+    let mut matching: Vec<_> = overrides
+        .into_iter()
+        .filter(|(pattern, _)| glob_matches(pattern, path))
+        .collect();
+    matching.sort_by(|(a, _), (b, _)| by_specificity(a, b));
+    matching.into_iter().map(|(_, config)| config).collect()
+}
+
+/// Order patterns least specific first, treating longer patterns as more specific.
+pub(crate) fn by_specificity(a: &str, b: &str) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Whether `pattern` matches all of `path`, which is relative to the project root.
+pub(crate) fn glob_matches(pattern: &str, path: &Utf8Path) -> bool {
+    let segments: Vec<&str> = pattern
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect();
+    let components: Vec<&str> = path.components().map(|component| component.as_str()).collect();
+    segments_match(&segments, &components)
+}
+
+fn segments_match(segments: &[&str], components: &[&str]) -> bool {
+    let Some((segment, rest)) = segments.split_first() else {
+        return components.is_empty();
+    };
+    if *segment == "**" {
+        return (0..=components.len()).any(|skip| segments_match(rest, &components[skip..]));
+    }
+    components.split_first().is_some_and(|(component, components)| {
+        component_matches(segment, component) && segments_match(rest, components)
+    })
+}
+
+/// Match one path component against a segment with `*` and `?` wildcards.
+fn component_matches(segment: &str, component: &str) -> bool {
+    let pattern: Vec<char> = segment.chars().collect();
+    let text: Vec<char> = component.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is currently matched up to.
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            Some(_) | None => {
+                let Some((star, matched)) = backtrack else {
+                    return false;
+                };
+                backtrack = Some((star, matched + 1));
+                p = star + 1;
+                t = matched + 1;
+            }
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+
+/// `[path_mappings]`: where paths Django sees inside a container live in the workspace.
+///
+/// Keys are absolute paths as the project's settings name them; values are workspace paths,
+/// relative to the project root unless absolute.
+///
+/// Example configuration:
+/// ```toml
+/// [tool.djls.path_mappings]
+/// "/app" = "."
+/// "/srv/shared/templates" = "../shared/templates"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Default)]
+#[serde(transparent)]
+pub struct PathMappings(BTreeMap<Utf8PathBuf, Utf8PathBuf>);
+
+impl PathMappings {
+    /// The workspace location of `path` for a project rooted at `project_root`.
+    ///
+    /// The longest mapped prefix of `path` decides; `None` when no mapping covers `path`.
+    /// The result is not normalized, so callers should clean it before comparing paths.
+    #[must_use]
+    pub fn to_workspace(&self, project_root: &Utf8Path, path: &Utf8Path) -> Option<Utf8PathBuf> {
+        let (workspace, rest) = self
+            .0
+            .iter()
+            .filter_map(|(container, workspace)| {
+                Some((container, workspace, path.strip_prefix(container).ok()?))
+            })
+            .max_by_key(|(container, _, _)| container.components().count())
+            .map(|(_, workspace, rest)| (workspace, rest))?;
+        Some(project_root.join(workspace).join(rest))
+    }
+
+    pub(crate) fn extend(&mut self, other: PathMappings) {
+        self.0.extend(other.0);
+    }
+}
+
+impl FromIterator<(Utf8PathBuf, Utf8PathBuf)> for PathMappings {
+    fn from_iter<I: IntoIterator<Item = (Utf8PathBuf, Utf8PathBuf)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
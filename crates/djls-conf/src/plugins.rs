@@ -0,0 +1,49 @@
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+
+/// A `[[plugins]]` entry: an external lint rule server.
+///
+/// The command is spawned from the project root and speaks line-delimited JSON over stdio.
+/// Plugins only run once the user allows them outside the project configuration.
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct PluginConfig {
+    name: String,
+    command: Vec<String>,
+}
+
+impl PluginConfig {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Program followed by its arguments.
+    #[must_use]
+    pub fn command(&self) -> &[String] {
+        &self.command
+    }
+
+    /// The program to run, resolved against `root` when it is a relative path such as
+    /// `tools/rules`. A bare name such as `python` is left for `PATH` lookup.
+    #[must_use]
+    pub fn program(&self, root: &Utf8Path) -> Option<Utf8PathBuf> {
+        let program = Utf8Path::new(self.command.first()?);
+        if program.is_relative() && program.components().count() > 1 {
+            Some(root.join(program))
+        } else {
+            Some(program.to_owned())
+        }
+    }
+
+    /// Existing files the command names relative to `root`, such as the program or a script
+    /// passed to an interpreter. Their contents stand in for the plugin's version.
+    #[must_use]
+    pub fn files(&self, root: &Utf8Path) -> Vec<Utf8PathBuf> {
+        self.command
+            .iter()
+            .map(|part| root.join(part))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+}
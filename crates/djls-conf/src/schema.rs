@@ -0,0 +1,309 @@
+//! Checking configuration files against the settings schema.
+//!
+//! [`Settings::new`] stops at the first value of the wrong type and ignores keys it does not
+//! know, so a mistake either prevents the whole configuration from loading or does nothing at
+//! all. [`check_project_config`] locates both kinds of mistake in each file, so they can be
+//! reported on the file itself.
+
+use std::fs;
+use std::ops::Range;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+use toml::de::DeTable;
+use toml::de::DeValue;
+
+use crate::PROJECT_CONFIG_FILES;
+use crate::Settings;
+
+/// A mistake in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    span: Range<usize>,
+    message: String,
+}
+
+impl ConfigProblem {
+    /// Byte range of the offending key or value in the file.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn from_toml(error: &toml::de::Error) -> Self {
+        Self {
+            span: error.span().unwrap_or(0..0),
+            message: error.message().trim().to_string(),
+        }
+    }
+}
+
+/// A project configuration file and the problems found in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFileCheck {
+    path: Utf8PathBuf,
+    content: String,
+    problems: Vec<ConfigProblem>,
+}
+
+impl ConfigFileCheck {
+    /// Check `content`, read from `path`; a `pyproject.toml` is checked only for its
+    /// `[tool.djls]` table.
+    #[must_use]
+    pub fn new(path: Utf8PathBuf, content: String) -> Self {
+        let problems = if path.file_name() == Some("pyproject.toml") {
+            check_pyproject(&content)
+        } else {
+            check_config_file(&content)
+        };
+        Self {
+            path,
+            content,
+            problems,
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// The text the problem spans refer to.
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    #[must_use]
+    pub fn problems(&self) -> &[ConfigProblem] {
+        &self.problems
+    }
+}
+
+/// Check every project configuration file that exists under `project_root`.
+///
+/// Valid files are listed with no problems, so callers can clear earlier reports for them.
+#[must_use]
+pub fn check_project_config(project_root: &Utf8Path) -> Vec<ConfigFileCheck> {
+    PROJECT_CONFIG_FILES
+        .iter()
+        .filter_map(|name| {
+            let path = project_root.join(name);
+            let content = fs::read_to_string(&path).ok()?;
+            Some(ConfigFileCheck::new(path, content))
+        })
+        .collect()
+}
+
+/// Check the contents of a `djls.toml` file.
+#[must_use]
+pub fn check_config_file(content: &str) -> Vec<ConfigProblem> {
+    let table = match DeTable::parse(content) {
+        Ok(table) => table,
+        Err(error) => return vec![ConfigProblem::from_toml(&error)],
+    };
+
+    let mut problems = Vec::new();
+    check_table(table.get_ref(), SETTINGS_KEYS, "", &mut problems);
+    if let Err(error) = toml::from_str::<Settings>(content) {
+        problems.push(ConfigProblem::from_toml(&error));
+    }
+    problems.sort_by_key(|problem| problem.span.start);
+    problems
+}
+
+/// Check the `[tool.djls]` table of a `pyproject.toml` file.
+#[must_use]
+pub fn check_pyproject(content: &str) -> Vec<ConfigProblem> {
+    #[derive(Deserialize)]
+    struct Pyproject {
+        #[serde(default)]
+        tool: Tool,
+    }
+
+    #[derive(Deserialize, Default)]
+    struct Tool {
+        #[serde(default)]
+        djls: Option<Settings>,
+    }
+
+    let document = match DeTable::parse(content) {
+        Ok(document) => document,
+        Err(error) => return vec![ConfigProblem::from_toml(&error)],
+    };
+    let Some(table) = child_table(document.get_ref(), "tool")
+        .and_then(|tool| child_table(tool, "djls"))
+    else {
+        return Vec::new();
+    };
+
+    let mut problems = Vec::new();
+    check_table(table, SETTINGS_KEYS, "", &mut problems);
+    let settings = toml::from_str::<Pyproject>(content).map(|pyproject| pyproject.tool.djls);
+    if let Err(error) = settings {
+        problems.push(ConfigProblem::from_toml(&error));
+    }
+    problems.sort_by_key(|problem| problem.span.start);
+    problems
+}
+
+/// What the value under a key may contain, as far as key names are checked.
+#[derive(Clone, Copy)]
+enum Shape {
+    /// A scalar, or a table whose keys are not checked here.
+    Open,
+    /// A table with exactly these keys.
+    Table(&'static [(&'static str, Shape)]),
+    /// A table with any keys, each holding a value of one shape.
+    Map(&'static Shape),
+    /// An array of values of one shape.
+    Array(&'static Shape),
+}
+
+const SETTINGS_KEYS: &[(&str, Shape)] = &[
+    ("extend", Shape::Open),
+    ("debug", Shape::Open),
+    ("log_level", Shape::Open),
+    ("venv_path", Shape::Open),
+    ("django_settings_module", Shape::Open),
+    (
+        "django_environments",
+        Shape::Array(&Shape::Table(&[
+            ("root", Shape::Open),
+            ("django_settings_module", Shape::Open),
+        ])),
+    ),
+    ("pythonpath", Shape::Open),
+    ("env_file", Shape::Open),
+    // Versioned, with free-form `extra` tables at every level.
+    ("tagspecs", Shape::Open),
+    (
+        "tags",
+        Shape::Map(&Shape::Table(&[
+            ("end", Shape::Open),
+            ("intermediates", Shape::Open),
+        ])),
+    ),
+    ("dialects", Shape::Open),
+    ("path_mappings", Shape::Open),
+    (
+        "diagnostics",
+        Shape::Table(&[
+            ("severity", Shape::Open),
+            ("duplicates", Shape::Open),
+            ("strict_child_templates", Shape::Open),
+            (
+                "limits",
+                Shape::Table(&[
+                    ("max_file_size", Shape::Open),
+                    ("max_nodes", Shape::Open),
+                    ("max_nesting_depth", Shape::Open),
+                ]),
+            ),
+            (
+                "complexity",
+                Shape::Table(&[
+                    ("max_nodes", Shape::Open),
+                    ("max_nesting_depth", Shape::Open),
+                    ("max_includes", Shape::Open),
+                    ("max_variables", Shape::Open),
+                    ("max_branches", Shape::Open),
+                ]),
+            ),
+        ]),
+    ),
+    (
+        "format",
+        Shape::Table(&[
+            ("enabled", Shape::Open),
+            ("backend", Shape::Open),
+            ("insert_end_tags", Shape::Open),
+            ("merge_loads", Shape::Open),
+        ]),
+    ),
+    (
+        "completions",
+        Shape::Table(&[
+            ("fuzzy", Shape::Open),
+            ("htmx", Shape::Open),
+            ("alpine", Shape::Open),
+        ]),
+    ),
+    (
+        "plugins",
+        Shape::Array(&Shape::Table(&[
+            ("name", Shape::Open),
+            ("command", Shape::Open),
+        ])),
+    ),
+    (
+        "overrides",
+        Shape::Map(&Shape::Table(&[
+            ("dialect", Shape::Open),
+            ("diagnostics", Shape::Table(&[("severity", Shape::Open)])),
+        ])),
+    ),
+];
+
+fn check_table(
+    table: &DeTable<'_>,
+    keys: &[(&str, Shape)],
+    prefix: &str,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    for (key, value) in table {
+        let name: &str = key.get_ref();
+        let path = format!("{prefix}{name}");
+        match keys.iter().find(|(known, _)| *known == name) {
+            Some((_, shape)) => check_value(value.get_ref(), *shape, &path, problems),
+            None => problems.push(ConfigProblem {
+                span: key.span(),
+                message: format!("unknown setting `{path}`"),
+            }),
+        }
+    }
+}
+
+fn check_value(value: &DeValue<'_>, shape: Shape, path: &str, problems: &mut Vec<ConfigProblem>) {
+    match shape {
+        Shape::Open => {}
+        Shape::Table(keys) => {
+            if let DeValue::Table(table) = value {
+                check_table(table, keys, &format!("{path}."), problems);
+            }
+        }
+        Shape::Map(shape) => {
+            if let DeValue::Table(table) = value {
+                for (key, value) in table {
+                    let path = format!("{path}.{}", key.get_ref());
+                    check_value(value.get_ref(), *shape, &path, problems);
+                }
+            }
+        }
+        Shape::Array(shape) => {
+            if let DeValue::Array(array) = value {
+                for (index, value) in array.iter().enumerate() {
+                    let path = format!("{path}[{index}]");
+                    check_value(value.get_ref(), *shape, &path, problems);
+                }
+            }
+        }
+    }
+}
+
+fn child_table<'a, 'i>(table: &'a DeTable<'i>, name: &str) -> Option<&'a DeTable<'i>> {
+    table.iter().find_map(|(key, value)| {
+        if let DeValue::Table(child) = value.get_ref()
+            && key.get_ref() == name
+        {
+            return Some(child);
+        }
+        None
+    })
+}
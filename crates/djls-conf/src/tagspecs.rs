@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 use serde::Deserialize;
+use serde::Serialize;
 
 /// Root `TagSpec` document (v0.6.0)
-#[derive(Debug, Clone, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(default)]
 pub struct TagSpecDef {
     /// Specification version (defaults to "0.6.0")
@@ -21,13 +23,16 @@ pub struct TagSpecDef {
     /// Tag libraries grouped by module
     #[serde(default)]
     pub libraries: Vec<TagLibraryDef>,
+    /// Block structure keyed by tag name, applied to whichever library defines the tag
+    #[serde(default)]
+    pub tags: BTreeMap<String, TagShorthandDef>,
     /// Extra metadata for extensibility
     #[serde(default)]
     pub extra: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Tag library grouping tags by module
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TagLibraryDef {
     /// Dotted Python import path (e.g., "django.template.defaulttags")
     pub module: String,
@@ -43,7 +48,7 @@ pub struct TagLibraryDef {
 }
 
 /// Individual tag specification
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TagDef {
     /// Tag name (e.g., "for", "if", "url")
     pub name: String,
@@ -64,8 +69,23 @@ pub struct TagDef {
     pub extra: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// Name-keyed block structure from a `[tags.<name>]` table
+///
+/// Unlike [`TagDef`], a shorthand entry is not tied to a library module. It
+/// fills in block structure for a tag of that name in any library, for tags
+/// whose registration extraction cannot follow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TagShorthandDef {
+    /// End tag name; when set, the tag is a block
+    #[serde(default)]
+    pub end: Option<String>,
+    /// Intermediate tag names (e.g., "else")
+    #[serde(default)]
+    pub intermediates: Vec<String>,
+}
+
 /// Tag type classification
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum TagTypeDef {
     /// Block tag with opening/closing tags
@@ -77,7 +97,7 @@ pub enum TagTypeDef {
 }
 
 /// End tag specification
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EndTagDef {
     /// End tag name (e.g., "endfor", "endif")
     pub name: String,
@@ -93,7 +113,7 @@ pub struct EndTagDef {
 }
 
 /// Intermediate tag specification
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct IntermediateTagDef {
     /// Intermediate tag name (e.g., "elif", "else", "empty")
     pub name: String,
@@ -115,7 +135,7 @@ pub struct IntermediateTagDef {
 }
 
 /// Intermediate tag positioning
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum PositionDef {
     /// Can appear anywhere
@@ -125,7 +145,7 @@ pub(crate) enum PositionDef {
 }
 
 /// Tag argument specification
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TagArgDef {
     /// Argument name
     pub name: String,
@@ -146,7 +166,7 @@ pub struct TagArgDef {
 }
 
 /// Argument type (positional vs keyword)
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ArgTypeDef {
     /// Can be positional or keyword
@@ -158,7 +178,7 @@ pub enum ArgTypeDef {
 }
 
 /// Argument kind (semantic classification)
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ArgKindDef {
     /// Any template expression or literal
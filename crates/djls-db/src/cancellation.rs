@@ -0,0 +1,104 @@
+//! Cooperative cancellation for work running on a database snapshot.
+//!
+//! Salsa cancels every snapshot when the database is written to, but it has
+//! no way to stop one computation while leaving the others running. A
+//! [`CancellationToken`] fills that gap: [`CancellationToken::run`] marks the
+//! current thread with the token, and the database's event callback unwinds
+//! the computation at the next query read once the token is cancelled. The
+//! unwind happens where Salsa would itself unwind for a pending write, so
+//! memos and in-progress queries are cleaned up the same way.
+
+use std::cell::RefCell;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Shared flag that aborts the computation it was handed to.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+/// Tokens are equal when they are clones of one another.
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+/// Unwind payload for a computation whose [`CancellationToken`] was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aborted;
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abort the computation using this token at its next query read.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// A guard that cancels this token when dropped, for tying a
+    /// computation to the lifetime of the future waiting on it.
+    #[must_use]
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+
+    /// Run `f` on the current thread, unwinding out of it with [`Aborted`]
+    /// once this token is cancelled.
+    ///
+    /// Any other panic, including Salsa's own cancellation, propagates
+    /// unchanged.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> Result<R, Aborted> {
+        if self.is_cancelled() {
+            return Err(Aborted);
+        }
+
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(payload) if payload.is::<Aborted>() => Err(Aborted),
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+}
+
+/// Cancels its [`CancellationToken`] when dropped.
+#[derive(Debug)]
+pub struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Called from the database's event callback before each query read.
+pub(crate) fn unwind_if_aborted() {
+    let aborted = CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    });
+    if aborted {
+        std::panic::resume_unwind(Box::new(Aborted));
+    }
+}
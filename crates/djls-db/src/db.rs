@@ -17,11 +17,20 @@ use djls_project::Project;
 use djls_project::compute_model_graph;
 use djls_semantic::Db as SemanticDb;
 use djls_semantic::FilterAritySpecs;
+use djls_semantic::LintRules;
 use djls_semantic::TagSpecs;
 use djls_semantic::builtin_tag_specs;
 use djls_source::Db as SourceDb;
+use djls_source::File;
 use djls_source::FileSystem;
 use djls_source::SourceFiles;
+use salsa::Database as _;
+
+use crate::cancellation::unwind_if_aborted;
+use crate::memory::MemoryUsage;
+use crate::plugins::plugin_rules;
+use crate::stats::QueryStats;
+use crate::stats::QueryUsage;
 
 /// Concrete Salsa database for the Django Language Server.
 ///
@@ -52,6 +61,27 @@ pub struct DjangoDatabase {
     /// Configuration settings for this database snapshot.
     pub(crate) settings: Arc<Settings>,
 
+    /// Custom lint rules registered by an embedder.
+    ///
+    /// Like `project`, these are read outside Salsa's dependency graph, so
+    /// they are fixed at construction and settings reloads leave them alone.
+    lint_rules: Arc<LintRules>,
+
+    /// Rules backed by the project's `[[plugins]]`, run when diagnostics are
+    /// collected rather than inside tracked validation.
+    ///
+    /// Empty unless `plugins_allowed`, and rebuilt whenever the `plugins`
+    /// setting changes.
+    plugin_rules: Arc<LintRules>,
+
+    /// Whether the user allowed `[[plugins]]` to run, through a CLI flag or a
+    /// client option. Project configuration cannot set this, since plugins
+    /// execute commands.
+    plugins_allowed: bool,
+
+    /// Memo reuse counters fed by the Salsa event callback.
+    query_stats: Arc<QueryStats>,
+
     storage: salsa::Storage<Self>,
 
     // The logs are only used for testing and demonstrating reuse:
@@ -66,15 +96,24 @@ impl Default for DjangoDatabase {
         use djls_source::InMemoryFileSystem;
 
         let logs = <Arc<Mutex<Option<Vec<String>>>>>::default();
+        let query_stats = Arc::new(QueryStats::default());
 
         Self {
             fs: Arc::new(InMemoryFileSystem::new()),
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(Settings::default()),
+            lint_rules: Arc::new(LintRules::new()),
+            plugin_rules: Arc::new(LintRules::new()),
+            plugins_allowed: false,
+            query_stats: Arc::clone(&query_stats),
             storage: salsa::Storage::new(Some(Box::new({
                 let logs = Arc::clone(&logs);
                 move |event| {
+                    if matches!(event.kind, salsa::EventKind::WillCheckCancellation) {
+                        unwind_if_aborted();
+                    }
+                    query_stats.record(&event);
                     eprintln!("Event: {event:?}");
                     // Log interesting events, if logging is enabled
                     if let Some(logs) =
@@ -105,12 +144,22 @@ impl DjangoDatabase {
         settings: &Settings,
         project_path: Option<&Utf8Path>,
     ) -> Self {
+        let query_stats = Arc::new(QueryStats::default());
         let mut db = Self {
             fs: file_system,
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
-            storage: salsa::Storage::new(None),
+            lint_rules: Arc::new(LintRules::new()),
+            plugin_rules: Arc::new(LintRules::new()),
+            plugins_allowed: false,
+            query_stats: Arc::clone(&query_stats),
+            storage: salsa::Storage::new(Some(Box::new(move |event| {
+                if matches!(event.kind, salsa::EventKind::WillCheckCancellation) {
+                    unwind_if_aborted();
+                }
+                query_stats.record(&event);
+            }))),
             #[cfg(test)]
             logs: Arc::new(Mutex::new(None)),
         };
@@ -122,6 +171,69 @@ impl DjangoDatabase {
         db
     }
 
+    /// Run `rules` after built-in validation.
+    ///
+    /// Must be called before the database runs any query.
+    #[must_use]
+    pub fn with_lint_rules(mut self, rules: LintRules) -> Self {
+        self.lint_rules = Arc::new(rules);
+        self
+    }
+
+    /// Run the project's `[[plugins]]` when collecting diagnostics, or stop
+    /// running them.
+    ///
+    /// Plugins run commands from project configuration, so they stay off
+    /// until the user allows them.
+    pub fn set_plugins_allowed(&mut self, allowed: bool) {
+        if self.plugins_allowed != allowed {
+            self.plugins_allowed = allowed;
+            self.reload_plugins();
+        }
+    }
+
+    /// Replace the plugin rules from the current settings, stopping any
+    /// running plugin processes once no snapshot uses them.
+    pub(crate) fn reload_plugins(&mut self) {
+        let rules = self
+            .project
+            .filter(|_| self.plugins_allowed)
+            .map(|project| plugin_rules(self.settings.plugins(), project.root(self)))
+            .unwrap_or_default();
+        self.plugin_rules = Arc::new(rules);
+    }
+
+    /// Memo reuse counters for this database and its snapshots.
+    #[must_use]
+    pub fn query_stats(&self) -> &QueryStats {
+        &self.query_stats
+    }
+
+    /// Executions per query since the database was created, most executed
+    /// first.
+    #[must_use]
+    pub fn query_usage(&self) -> Vec<QueryUsage> {
+        self.query_stats
+            .usage(|ingredient| self.ingredient_debug_name(ingredient).to_string())
+    }
+
+    /// Memory Salsa holds for each query's memos and each struct's instances.
+    ///
+    /// Walks every memo, so it takes time proportional to what is cached.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::of(self)
+    }
+
+    /// Drop memos of LRU-capped queries beyond their capacity.
+    ///
+    /// Salsa only evicts when an input changes, so a long stretch of reads
+    /// without edits, such as browsing many templates, keeps every memo it
+    /// created until then. Like any write, this cancels running queries.
+    pub fn collect_garbage(&mut self) {
+        self.trigger_lru_eviction();
+    }
+
     fn set_project(&mut self, root: &Utf8Path, settings: &Settings) {
         let project = Project::initial(self, root, settings);
         self.project = Some(project);
@@ -153,8 +265,13 @@ impl SemanticDb for DjangoDatabase {
         &DEFAULT
     }
 
-    fn diagnostics_config(&self) -> DiagnosticsConfig {
-        self.settings().diagnostics().clone()
+    fn diagnostics_config(&self, file: File) -> DiagnosticsConfig {
+        self.project
+            .and_then(|project| {
+                let relative = file.path(self).strip_prefix(project.root(self)).ok()?;
+                Some(self.settings.diagnostics_for(relative))
+            })
+            .unwrap_or_else(|| self.settings.diagnostics().clone())
     }
 
     fn projectless_filter_arity_specs(&self) -> &FilterAritySpecs {
@@ -171,6 +288,14 @@ impl SemanticDb for DjangoDatabase {
                 compute_model_graph(self, project)
             })
     }
+
+    fn lint_rules(&self) -> &LintRules {
+        &self.lint_rules
+    }
+
+    fn untracked_lint_rules(&self) -> &LintRules {
+        &self.plugin_rules
+    }
 }
 
 #[salsa::db]
@@ -394,6 +519,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -489,6 +615,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1179,6 +1306,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1266,6 +1394,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1296,6 +1425,7 @@ mod invalidation_tests {
                 SemanticOffsetContext::LoadLibrary { .. }
                 | SemanticOffsetContext::LoadSymbol { .. }
                 | SemanticOffsetContext::TemplateBlock { .. }
+                | SemanticOffsetContext::Route { .. }
                 | SemanticOffsetContext::Tag { .. }
                 | SemanticOffsetContext::Filter { .. }
                 | SemanticOffsetContext::Variable { .. }
@@ -1314,6 +1444,7 @@ mod invalidation_tests {
             SemanticOffsetContext::LoadLibrary { .. }
             | SemanticOffsetContext::LoadSymbol { .. }
             | SemanticOffsetContext::TemplateBlock { .. }
+            | SemanticOffsetContext::Route { .. }
             | SemanticOffsetContext::Tag { .. }
             | SemanticOffsetContext::Filter { .. }
             | SemanticOffsetContext::Variable { .. }
@@ -1358,6 +1489,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -1421,6 +1553,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -1461,6 +1594,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1529,6 +1663,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1607,6 +1742,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1678,6 +1814,7 @@ mod invalidation_tests {
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -1921,6 +2058,7 @@ env_file = ".env.local"
                 }],
                 extra: None,
             }],
+            tags: BTreeMap::new(),
             extra: None,
         };
 
@@ -2001,6 +2139,7 @@ env_file = ".env.local"
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -2213,6 +2352,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -2328,6 +2468,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -2403,6 +2544,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -2480,6 +2622,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -2545,6 +2688,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -2634,6 +2778,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::default(),
             logs: Arc::new(Mutex::new(None)),
         };
@@ -2690,6 +2835,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
@@ -2800,6 +2946,7 @@ def my_filter(value, arg):
             files: SourceFiles::default(),
             project: None,
             settings: Arc::new(settings.clone()),
+            query_stats: Arc::default(),
             storage: salsa::Storage::new(Some(Box::new({
                 let log = event_log.clone();
                 move |event| {
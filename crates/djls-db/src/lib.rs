@@ -5,7 +5,18 @@
 //! `ProjectDb`). Both the LSP server and CLI
 //! commands consume this crate.
 //!
+mod cancellation;
 mod db;
+mod memory;
+mod plugins;
 mod settings;
+mod stats;
 
+pub use cancellation::Aborted;
+pub use cancellation::CancelOnDrop;
+pub use cancellation::CancellationToken;
 pub use db::DjangoDatabase;
+pub use memory::IngredientMemory;
+pub use memory::MemoryUsage;
+pub use stats::QueryStats;
+pub use stats::QueryUsage;
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+/// Memory Salsa holds for the memos of each query and the instances of each
+/// tracked, interned, or input struct, as reported by the server's
+/// `djls/memory` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// One entry per query with memos, largest first.
+    pub queries: Vec<IngredientMemory>,
+    /// One entry per struct with instances, largest first.
+    pub structs: Vec<IngredientMemory>,
+}
+
+/// Memory held by one Salsa query or struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IngredientMemory {
+    pub name: String,
+    /// Number of memos, or of struct instances.
+    pub count: usize,
+    /// Inline size of the memoized values or struct fields.
+    pub bytes: usize,
+    /// Size of Salsa's bookkeeping for them, such as dependency edges.
+    pub metadata_bytes: usize,
+    /// Heap memory the values own, for ingredients that report it.
+    pub heap_bytes: Option<usize>,
+}
+
+impl IngredientMemory {
+    fn new(
+        name: impl Display,
+        count: usize,
+        bytes: usize,
+        metadata_bytes: usize,
+        heap_bytes: Option<usize>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            count,
+            bytes,
+            metadata_bytes,
+            heap_bytes,
+        }
+    }
+
+    /// An ingredient holding nothing, such as a query whose memos were evicted.
+    #[must_use]
+    pub fn empty(name: &str) -> Self {
+        Self::new(name, 0, 0, 0, None)
+    }
+
+    /// Everything this ingredient is known to hold.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.bytes + self.metadata_bytes + self.heap_bytes.unwrap_or(0)
+    }
+}
+
+impl MemoryUsage {
+    pub(crate) fn of(db: &dyn salsa::Database) -> Self {
+        let info = db.memory_usage();
+        Self {
+            queries: largest_first(info.queries.into_iter().map(|(name, memos)| {
+                IngredientMemory::new(
+                    name,
+                    memos.count(),
+                    memos.size_of_fields(),
+                    memos.size_of_metadata(),
+                    memos.heap_size_of_fields(),
+                )
+            })),
+            structs: largest_first(info.structs.into_iter().map(|instances| {
+                IngredientMemory::new(
+                    instances.debug_name(),
+                    instances.count(),
+                    instances.size_of_fields(),
+                    instances.size_of_metadata(),
+                    instances.heap_size_of_fields(),
+                )
+            })),
+        }
+    }
+}
+
+fn largest_first(entries: impl Iterator<Item = IngredientMemory>) -> Vec<IngredientMemory> {
+    let mut entries = entries
+        .filter(|entry| entry.count > 0)
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| {
+        right
+            .total_bytes()
+            .cmp(&left.total_bytes())
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    entries
+}
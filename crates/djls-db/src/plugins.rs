@@ -0,0 +1,354 @@
+//! External lint rule servers declared with `[[plugins]]` in `djls.toml`.
+//!
+//! Plugins run only once the user allows them with
+//! [`DjangoDatabase::set_plugins_allowed`](crate::DjangoDatabase::set_plugins_allowed). They are
+//! served as [`untracked_lint_rules`](djls_semantic::Db::untracked_lint_rules), so they run
+//! each time diagnostics are collected rather than inside tracked validation.
+//!
+//! Each plugin is a long-lived subprocess, started the first time a Template is checked. For
+//! every Template djls writes one JSON request line to the plugin's stdin, carrying the
+//! Template's path, source, and a flat list of its nodes, and reads one JSON response line of
+//! diagnostics from its stdout. Offsets on both sides are UTF-8 byte offsets into the source.
+//!
+//! A plugin is restarted when a file its command names, such as its script, is modified. One
+//! that fails to start, exits, answers with invalid JSON, or does not answer within five seconds
+//! is logged and disabled until its files or the `plugins` setting change.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Write as _;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use djls_conf::PluginConfig;
+use djls_semantic::LintContext;
+use djls_semantic::LintRule;
+use djls_semantic::LintRules;
+use djls_semantic::LintTag;
+use djls_semantic::LintTemplate;
+use djls_semantic::LintVariable;
+use djls_semantic::NodeVisitor;
+use djls_source::Span;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How long a plugin may take to answer one request.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Build the lint rules for `plugins`, spawning them from `root` on first use.
+pub(crate) fn plugin_rules(plugins: &[PluginConfig], root: &Utf8Path) -> LintRules {
+    let mut rules = LintRules::new();
+    for plugin in plugins {
+        rules.register(PluginRule {
+            config: plugin.clone(),
+            root: root.to_owned(),
+            state: Mutex::new(PluginState::NotStarted),
+        });
+    }
+    rules
+}
+
+struct PluginRule {
+    config: PluginConfig,
+    root: Utf8PathBuf,
+    state: Mutex<PluginState>,
+}
+
+/// Modification times of the files a plugin's command names, in command order.
+type PluginVersion = Vec<Option<SystemTime>>;
+
+enum PluginState {
+    NotStarted,
+    Running(PluginProcess),
+    Disabled(PluginVersion),
+}
+
+impl PluginState {
+    /// The version of the plugin files this state was reached with.
+    fn version(&self) -> Option<&PluginVersion> {
+        match self {
+            Self::NotStarted => None,
+            Self::Running(process) => Some(&process.version),
+            Self::Disabled(version) => Some(version),
+        }
+    }
+}
+
+struct PluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines read from the plugin's stdout by a reader thread, so a request can time out.
+    responses: Receiver<io::Result<String>>,
+    version: PluginVersion,
+}
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        if let Err(error) = self.child.kill() {
+            tracing::debug!("Failed to stop lint plugin: {error}");
+        }
+        if let Err(error) = self.child.wait() {
+            tracing::debug!("Failed to reap lint plugin: {error}");
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PluginRequest<'a> {
+    path: &'a str,
+    source: &'a str,
+    nodes: Vec<PluginNode>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PluginNode {
+    Tag {
+        name: String,
+        bits: Vec<String>,
+        start: u32,
+        end: u32,
+    },
+    Variable {
+        var: String,
+        filters: Vec<String>,
+        start: u32,
+        end: u32,
+    },
+    Text {
+        start: u32,
+        end: u32,
+    },
+    Comment {
+        start: u32,
+        end: u32,
+    },
+}
+
+#[derive(Deserialize)]
+struct PluginResponse {
+    #[serde(default)]
+    diagnostics: Vec<PluginDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct PluginDiagnostic {
+    code: String,
+    message: String,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Default)]
+struct NodeCollector {
+    nodes: Vec<PluginNode>,
+}
+
+impl NodeVisitor for NodeCollector {
+    fn visit_tag(&mut self, _cx: &mut LintContext, tag: LintTag<'_>) {
+        self.nodes.push(PluginNode::Tag {
+            name: tag.name.to_string(),
+            bits: tag.bits.iter().map(|bit| bit.as_str().to_string()).collect(),
+            start: tag.span.start(),
+            end: tag.span.end(),
+        });
+    }
+
+    fn visit_variable(&mut self, _cx: &mut LintContext, variable: LintVariable<'_>) {
+        self.nodes.push(PluginNode::Variable {
+            var: variable.var.to_string(),
+            filters: variable
+                .filters
+                .iter()
+                .map(|filter| filter.name.clone())
+                .collect(),
+            start: variable.span.start(),
+            end: variable.span.end(),
+        });
+    }
+
+    fn visit_text(&mut self, _cx: &mut LintContext, span: Span) {
+        self.nodes.push(PluginNode::Text {
+            start: span.start(),
+            end: span.end(),
+        });
+    }
+
+    fn visit_comment(&mut self, _cx: &mut LintContext, span: Span) {
+        self.nodes.push(PluginNode::Comment {
+            start: span.start(),
+            end: span.end(),
+        });
+    }
+}
+
+impl LintRule for PluginRule {
+    fn name(&self) -> &str {
+        self.config.name()
+    }
+
+    fn check(&self, template: &LintTemplate<'_>, cx: &mut LintContext) {
+        let mut collector = NodeCollector::default();
+        template.walk_nodes(cx, &mut collector);
+        let request = PluginRequest {
+            path: template.path().as_str(),
+            source: template.source(),
+            nodes: collector.nodes,
+        };
+
+        let version = self.version();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.version().is_some_and(|started| *started != version) {
+            tracing::info!("Restarting lint plugin '{}' after its files changed", self.name());
+            *state = PluginState::NotStarted;
+        }
+        let diagnostics = match self.exchange(&mut state, &request, &version) {
+            Ok(diagnostics) => diagnostics,
+            Err(error) => {
+                tracing::warn!("Disabling lint plugin '{}': {error}", self.name());
+                *state = PluginState::Disabled(version);
+                return;
+            }
+        };
+        drop(state);
+
+        for diagnostic in diagnostics {
+            if diagnostic.code.is_empty() || is_builtin_code(&diagnostic.code) {
+                tracing::warn!(
+                    "Lint plugin '{}' reported reserved code '{}'",
+                    self.name(),
+                    diagnostic.code
+                );
+                continue;
+            }
+            let span = (diagnostic.start <= diagnostic.end)
+                .then(|| Span::new(diagnostic.start, diagnostic.end - diagnostic.start))
+                .filter(|span| template.source().get(..span.end_usize()).is_some());
+            let Some(span) = span else {
+                tracing::warn!(
+                    "Lint plugin '{}' reported an out-of-range span {}..{}",
+                    self.name(),
+                    diagnostic.start,
+                    diagnostic.end
+                );
+                continue;
+            };
+            cx.report(intern_code(&diagnostic.code), diagnostic.message, span);
+        }
+    }
+}
+
+impl PluginRule {
+    fn version(&self) -> PluginVersion {
+        self.config
+            .files(&self.root)
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+            .collect()
+    }
+
+    fn exchange(
+        &self,
+        state: &mut PluginState,
+        request: &PluginRequest<'_>,
+        version: &PluginVersion,
+    ) -> io::Result<Vec<PluginDiagnostic>> {
+        if matches!(state, PluginState::NotStarted) {
+            *state = PluginState::Running(self.spawn(version.clone())?);
+        }
+        let PluginState::Running(process) = state else {
+            return Ok(Vec::new());
+        };
+
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        process.stdin.write_all(line.as_bytes())?;
+        process.stdin.flush()?;
+
+        let response = match process.responses.recv_timeout(RESPONSE_TIMEOUT) {
+            Ok(response) => response?,
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "no response within the timeout",
+                ));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "plugin exited",
+                ));
+            }
+        };
+        let response: PluginResponse = serde_json::from_str(&response)?;
+        Ok(response.diagnostics)
+    }
+
+    fn spawn(&self, version: PluginVersion) -> io::Result<PluginProcess> {
+        let Some(program) = self.config.program(&self.root) else {
+            return Err(io::Error::other("plugin command is empty"));
+        };
+
+        let mut child = Command::new(program.as_std_path())
+            .args(&self.config.command()[1..])
+            .current_dir(self.root.as_std_path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(io::Error::other("plugin stdio is not piped"));
+        };
+
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(PluginProcess {
+            child,
+            stdin,
+            responses,
+            version,
+        })
+    }
+}
+
+/// Whether `code` has the shape of a built-in code, like `S100`, `A100`, or `X100`.
+fn is_builtin_code(code: &str) -> bool {
+    code.strip_prefix(['A', 'S', 'X'])
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Diagnostic codes are `&'static str`; keep one copy of each plugin code for the process.
+fn intern_code(code: &str) -> &'static str {
+    static CODES: LazyLock<Mutex<BTreeSet<&'static str>>> = LazyLock::new(Mutex::default);
+
+    let mut codes = CODES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(interned) = codes.get(code) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(code.to_owned().into_boxed_str());
+    codes.insert(interned);
+    interned
+}
@@ -12,11 +12,17 @@ impl DjangoDatabase {
     }
 
     /// Store project settings and update the stable project handle.
+    ///
+    /// A changed `plugins` list restarts the plugins, if they are allowed.
     pub fn apply_project_settings(&mut self, settings: Settings) {
         if let Some(project) = self.project() {
             project.reload_from_settings(self, &settings);
         }
 
+        let plugins_changed = self.settings.plugins() != settings.plugins();
         self.settings = Arc::new(settings);
+        if plugins_changed {
+            self.reload_plugins();
+        }
     }
 }
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Salsa memo reuse counters, shared by a database and all of its snapshots.
+///
+/// Salsa reports when a query executes and when an older memo is revalidated
+/// after an edit, but not when a memo already verified in the current revision
+/// is returned. The counts, reported by the server's `djls/perf` request,
+/// therefore show how much work edits caused rather than an overall hit rate.
+#[derive(Debug, Default)]
+pub struct QueryStats {
+    executed: AtomicU64,
+    revalidated: AtomicU64,
+    by_query: Mutex<HashMap<salsa::IngredientIndex, QueryCounts>>,
+}
+
+#[derive(Debug, Default)]
+struct QueryCounts {
+    executed: u64,
+    keys: HashSet<salsa::Id>,
+}
+
+/// How much one Salsa query has run, as reported by the server's
+/// `djls/memory` request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryUsage {
+    pub name: String,
+    /// Number of times the query has executed.
+    pub executed: u64,
+    /// Distinct inputs the query has executed for. Each may hold a memo, so
+    /// this bounds how many memos the query keeps; LRU-capped queries keep
+    /// fewer.
+    pub keys: usize,
+}
+
+impl QueryStats {
+    pub(crate) fn record(&self, event: &salsa::Event) {
+        if let salsa::EventKind::WillExecute { database_key } = event.kind {
+            self.executed.fetch_add(1, Ordering::Relaxed);
+            if let Ok(mut by_query) = self.by_query.lock() {
+                let counts = by_query.entry(database_key.ingredient_index()).or_default();
+                counts.executed += 1;
+                counts.keys.insert(database_key.key_index());
+            }
+        } else if let salsa::EventKind::DidValidateMemoizedValue { .. } = event.kind {
+            self.revalidated.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of query executions since the database was created.
+    #[must_use]
+    pub fn executed(&self) -> u64 {
+        self.executed.load(Ordering::Relaxed)
+    }
+
+    /// Number of memos from an earlier revision revalidated without
+    /// re-executing.
+    #[must_use]
+    pub fn revalidated(&self) -> u64 {
+        self.revalidated.load(Ordering::Relaxed)
+    }
+
+    /// Percentage of memo checks after an edit that avoided re-executing, or
+    /// `None` before any query has run.
+    #[must_use]
+    pub fn revalidated_percent(&self) -> Option<u64> {
+        let revalidated = self.revalidated();
+        let total = self.executed() + revalidated;
+        (total > 0).then(|| revalidated * 100 / total)
+    }
+
+    /// Executions per query, most executed first. `name` resolves a query's
+    /// ingredient to its name.
+    pub(crate) fn usage(&self, name: impl Fn(salsa::IngredientIndex) -> String) -> Vec<QueryUsage> {
+        let Ok(by_query) = self.by_query.lock() else {
+            return Vec::new();
+        };
+        let mut usage = by_query
+            .iter()
+            .map(|(ingredient, counts)| QueryUsage {
+                name: name(*ingredient),
+                executed: counts.executed,
+                keys: counts.keys.len(),
+            })
+            .collect::<Vec<_>>();
+        usage.sort_by(|left, right| {
+            right
+                .executed
+                .cmp(&left.executed)
+                .then_with(|| left.name.cmp(&right.name))
+        });
+        usage
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use camino::Utf8Path;
+use djls_conf::Settings;
+use djls_db::Aborted;
+use djls_db::CancellationToken;
+use djls_db::DjangoDatabase;
+use djls_source::InMemoryFileSystem;
+use djls_source::path_to_file;
+use djls_templates::lex_template;
+
+fn database() -> DjangoDatabase {
+    let mut fs = InMemoryFileSystem::new();
+    fs.add_file("/project/a.html".into(), "{{ a }}".to_string());
+    DjangoDatabase::new(Arc::new(fs), &Settings::default(), None)
+}
+
+#[test]
+fn cancelled_token_aborts_at_the_next_query_read() {
+    let db = database();
+    let file = path_to_file(&db, Utf8Path::new("/project/a.html"))
+        .expect("template should be tracked");
+    let token = CancellationToken::new();
+
+    let result = token.run(|| {
+        token.cancel();
+        lex_template(&db, file).is_ok()
+    });
+
+    assert_eq!(result, Err(Aborted));
+    assert_eq!(CancellationToken::new().run(|| lex_template(&db, file).is_ok()), Ok(true));
+}
+
+#[test]
+fn dropping_the_guard_cancels_the_token() {
+    let token = CancellationToken::new();
+    drop(token.cancel_on_drop());
+
+    assert!(token.is_cancelled());
+    assert_eq!(token.run(|| ()), Err(Aborted));
+}
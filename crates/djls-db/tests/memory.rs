@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use camino::Utf8Path;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_source::InMemoryFileSystem;
+use djls_source::path_to_file;
+use djls_templates::lex_template;
+
+fn database_with_lexed_templates() -> DjangoDatabase {
+    let mut fs = InMemoryFileSystem::new();
+    fs.add_file("/project/a.html".into(), "{{ a }}".to_string());
+    fs.add_file("/project/b.html".into(), "{{ b }}".to_string());
+    let db = DjangoDatabase::new(Arc::new(fs), &Settings::default(), None);
+
+    for path in ["/project/a.html", "/project/b.html"] {
+        let file = path_to_file(&db, Utf8Path::new(path)).expect("template should be tracked");
+        assert!(lex_template(&db, file).is_ok());
+    }
+    db
+}
+
+#[test]
+fn query_usage_counts_executions_and_distinct_inputs() {
+    let mut db = database_with_lexed_templates();
+    db.collect_garbage();
+
+    let lex = db
+        .query_usage()
+        .into_iter()
+        .find(|usage| usage.name.contains("lex_template"))
+        .expect("lex_template should have executed");
+    assert_eq!(lex.executed, 2);
+    assert_eq!(lex.keys, 2);
+}
+
+#[test]
+fn memory_usage_reports_the_size_of_each_querys_memos() {
+    let db = database_with_lexed_templates();
+
+    let lex = db
+        .memory_usage()
+        .queries
+        .into_iter()
+        .find(|memos| memos.name.contains("lex_template"))
+        .expect("lex_template should hold memos");
+    assert_eq!(lex.count, 2);
+    assert!(lex.bytes > 0);
+    assert!(lex.total_bytes() >= lex.bytes + lex.metadata_bytes);
+}
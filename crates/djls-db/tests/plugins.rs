@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_semantic::Db as _;
+use djls_source::InMemoryFileSystem;
+
+#[test]
+fn project_plugins_load_only_once_allowed() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf())
+        .expect("temporary test path should be valid UTF-8");
+    std::fs::write(
+        root.join("djls.toml"),
+        "[[plugins]]\nname = \"house-style\"\ncommand = [\"sh\", \"rules.sh\"]\n",
+    )
+    .expect("plugin config should be written");
+    let settings = Settings::new(&root, None).expect("plugin config should load");
+    let mut db = DjangoDatabase::new(Arc::new(InMemoryFileSystem::new()), &settings, Some(&root));
+    db.apply_project_settings(settings);
+
+    assert!(db.untracked_lint_rules().is_empty());
+
+    db.set_plugins_allowed(true);
+    assert_eq!(format!("{:?}", db.untracked_lint_rules()), r#"["house-style"]"#);
+
+    db.apply_project_settings(Settings::default());
+    assert!(db.untracked_lint_rules().is_empty());
+}
@@ -1,3 +1,4 @@
+use djls_conf::DiagnosticsConfig;
 use djls_semantic::ValidationError;
 use djls_semantic::ValidationErrorAccumulator;
 use djls_source::File;
@@ -48,9 +49,16 @@ pub fn code_actions(
     }
 
     let line_index = file.line_index(db);
-    let config = db.diagnostics_config();
+    let config = db.diagnostics_config(file);
     let uri = file.path(db).to_lsp_uri()?;
     let nodelist = parsed.nodelist(db);
+    let fix_context = FixContext {
+        uri: &uri,
+        source_text,
+        line_index,
+        encoding,
+        config: &config,
+    };
 
     let mut actions = Vec::new();
     for error_acc in errors {
@@ -107,45 +115,191 @@ pub fn code_actions(
                     ));
                 }
             }
-            ValidationError::UnmatchedBlockName {
-                expected, got_span, ..
-            } => {
-                let Some(diagnostic) = error.to_lsp_diagnostic(line_index, &config) else {
-                    continue;
-                };
-                let edit = ls_types::TextEdit::new(
-                    got_span.to_lsp_range_with_encoding(source_text, line_index, encoding),
-                    expected.clone(),
+            ValidationError::UnclosedTag { closer, .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    format!("Add '{{% {closer} %}}'"),
+                    &fix_context,
                 );
-                actions.push(vec![edit].to_quick_fix_action(
-                    uri.clone(),
+            }
+            ValidationError::UnmatchedBlockName { expected, .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
                     format!("Rename closing block to '{expected}'"),
-                    diagnostic,
-                    Some(true),
-                ));
+                    &fix_context,
+                );
+            }
+            ValidationError::UnknownTag { .. }
+            | ValidationError::UnknownFilter { .. }
+            | ValidationError::UnknownLibrary { .. }
+            | ValidationError::UnknownAdminBlock { .. } => {
+                push_suggestion_actions(&mut actions, error, &fix_context);
+            }
+            ValidationError::RemovedTemplateSymbol {
+                replacement: Some(replacement), ..
+            }
+            | ValidationError::DeprecatedTemplateSymbol {
+                replacement: Some(replacement), ..
+            } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    format!("Replace with '{replacement}'"),
+                    &fix_context,
+                );
+            }
+            ValidationError::MissingCsrfToken { .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    "Add '{% csrf_token %}'".to_string(),
+                    &fix_context,
+                );
+            }
+            ValidationError::UnknownLoadSymbol { symbol, .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    format!("Remove '{symbol}' from the load"),
+                    &fix_context,
+                );
+            }
+            ValidationError::UnusedLoad { name, .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    format!("Remove unused '{name}' from the load"),
+                    &fix_context,
+                );
             }
-            ValidationError::UnclosedTag { .. }
-            | ValidationError::OrphanedTag { .. }
+            ValidationError::TagDelimiterSpacing { .. } => {
+                push_fix_action(
+                    &mut actions,
+                    error,
+                    "Normalize tag delimiter spacing".to_string(),
+                    &fix_context,
+                );
+            }
+            ValidationError::OrphanedTag { .. }
             | ValidationError::OrphanedClosingTag { .. }
             | ValidationError::UnbalancedStructure { .. }
-            | ValidationError::UnknownTag { .. }
             | ValidationError::TagNotInInstalledApps { .. }
-            | ValidationError::UnknownFilter { .. }
             | ValidationError::FilterNotInInstalledApps { .. }
             | ValidationError::ExpressionSyntaxError { .. }
             | ValidationError::FilterMissingArgument { .. }
             | ValidationError::FilterUnexpectedArgument { .. }
+            | ValidationError::InvalidFilterArgument { .. }
             | ValidationError::ExtractedRuleViolation { .. }
-            | ValidationError::UnknownLibrary { .. }
             | ValidationError::LibraryNotInInstalledApps { .. }
             | ValidationError::ExtendsMustBeFirst { .. }
-            | ValidationError::MultipleExtends { .. } => {}
+            | ValidationError::MultipleExtends { .. }
+            | ValidationError::InvalidExtendsArgument { .. }
+            | ValidationError::DynamicExtends { .. }
+            | ValidationError::DuplicateBlockName { .. }
+            | ValidationError::TranslationVariableNotBound { .. }
+            | ValidationError::TranslationPluralMismatch { .. }
+            | ValidationError::TranslationNonLiteral { .. }
+            | ValidationError::TranslationPlaceholderNotBound { .. }
+            | ValidationError::IncludeMissingVariables { .. }
+            | ValidationError::RemovedTemplateSymbol {
+                replacement: None, ..
+            }
+            | ValidationError::DeprecatedTemplateSymbol {
+                replacement: None, ..
+            }
+            | ValidationError::ContentOutsideBlock { .. }
+            | ValidationError::ForloopOutsideLoop { .. }
+            | ValidationError::ParentloopWithoutOuterLoop { .. }
+            | ValidationError::ShadowedVariable { .. }
+            | ValidationError::TemplateNotFound { .. }
+            | ValidationError::AmbiguousTemplateName { .. }
+            | ValidationError::ShadowedTemplate { .. }
+            | ValidationError::TemplateLimitExceeded { .. }
+            | ValidationError::TemplateTooComplex { .. }
+            | ValidationError::ImageWithoutAlt { .. }
+            | ValidationError::FieldWithoutLabel { .. }
+            | ValidationError::RepeatedIdInLoop { .. }
+            | ValidationError::OutputMarkedSafe { .. }
+            | ValidationError::AutoescapeOff { .. }
+            | ValidationError::UnescapedUserInput { .. }
+            | ValidationError::LintRuleViolation { .. } => {}
         }
     }
 
     Some(actions)
 }
 
+struct FixContext<'a> {
+    uri: &'a ls_types::Uri,
+    source_text: &'a str,
+    line_index: &'a LineIndex,
+    encoding: PositionEncoding,
+    config: &'a DiagnosticsConfig,
+}
+
+/// Offer the error's own [`Fix`](djls_semantic::Fix) as the preferred quick fix.
+fn push_fix_action(
+    actions: &mut Vec<ls_types::CodeActionOrCommand>,
+    error: &ValidationError,
+    title: String,
+    context: &FixContext<'_>,
+) {
+    let Some(fix) = error.fix() else {
+        return;
+    };
+    let Some(diagnostic) = error.to_lsp_diagnostic(context.line_index, context.config) else {
+        return;
+    };
+    let edit = ls_types::TextEdit::new(
+        fix.span.to_lsp_range_with_encoding(
+            context.source_text,
+            context.line_index,
+            context.encoding,
+        ),
+        fix.replacement,
+    );
+    actions.push(vec![edit].to_quick_fix_action(
+        context.uri.clone(),
+        title,
+        diagnostic,
+        Some(true),
+    ));
+}
+
+/// Offer each "did you mean" replacement as a quick fix, none of them preferred.
+fn push_suggestion_actions(
+    actions: &mut Vec<ls_types::CodeActionOrCommand>,
+    error: &ValidationError,
+    context: &FixContext<'_>,
+) {
+    let replacements = error.suggested_replacements();
+    if replacements.is_empty() {
+        return;
+    }
+    let Some(diagnostic) = error.to_lsp_diagnostic(context.line_index, context.config) else {
+        return;
+    };
+    for fix in replacements {
+        let title = format!("Change to '{}'", fix.replacement);
+        let edit = ls_types::TextEdit::new(
+            fix.span.to_lsp_range_with_encoding(
+                context.source_text,
+                context.line_index,
+                context.encoding,
+            ),
+            fix.replacement,
+        );
+        actions.push(vec![edit].to_quick_fix_action(
+            context.uri.clone(),
+            title,
+            diagnostic.clone(),
+            None,
+        ));
+    }
+}
+
 fn load_tag_edit(
     source_text: &str,
     line_index: &LineIndex,
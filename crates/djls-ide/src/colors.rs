@@ -0,0 +1,259 @@
+//! Color swatches for hex and `rgb()` literals in template text.
+
+use djls_source::File;
+use djls_source::PositionEncoding;
+use djls_source::Span;
+use djls_templates::Node;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+
+/// Color literals in the text between template tags and variables.
+///
+/// Only text nodes are scanned, so a color built from `{{ }}` output is
+/// never reported, and `&#123;` entities and `#name` fragments that are not
+/// 3, 4, 6, or 8 hex digits are left alone.
+#[must_use]
+pub fn document_colors(
+    db: &dyn djls_semantic::Db,
+    file: File,
+    encoding: PositionEncoding,
+) -> Vec<ls_types::ColorInformation> {
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return Vec::new();
+    };
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    let source = source.as_str();
+    let line_index = file.line_index(db);
+
+    nodelist
+        .nodelist(db)
+        .iter()
+        .filter_map(|node| {
+            if let Node::Text { span } = node {
+                Some(*span)
+            } else {
+                None
+            }
+        })
+        .flat_map(|span| color_literals(source, span))
+        .map(|literal| ls_types::ColorInformation {
+            range: literal
+                .span
+                .to_lsp_range_with_encoding(source, line_index, encoding),
+            color: literal.color,
+        })
+        .collect()
+}
+
+/// Ways to write `color` in place of the literal at `span`, starting with the
+/// literal's own notation so picking a color does not change its style.
+#[must_use]
+pub fn color_presentations(
+    db: &dyn djls_semantic::Db,
+    file: File,
+    span: Span,
+    color: ls_types::Color,
+    encoding: PositionEncoding,
+) -> Vec<ls_types::ColorPresentation> {
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    let source = source.as_str();
+    let range = span.to_lsp_range_with_encoding(source, file.line_index(db), encoding);
+    let current = source
+        .get(span.start_usize()..span.end_usize())
+        .unwrap_or_default();
+
+    let mut notations = [Notation::Hex, Notation::Rgb];
+    if !current.starts_with('#') {
+        notations.reverse();
+    }
+
+    notations
+        .into_iter()
+        .map(|notation| {
+            let label = notation.format(color);
+            ls_types::ColorPresentation {
+                text_edit: Some(ls_types::TextEdit {
+                    range,
+                    new_text: label.clone(),
+                }),
+                label,
+                additional_text_edits: None,
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Notation {
+    Hex,
+    Rgb,
+}
+
+impl Notation {
+    fn format(self, color: ls_types::Color) -> String {
+        let [red, green, blue, alpha] =
+            [color.red, color.green, color.blue, color.alpha].map(to_byte);
+        let opaque = alpha == u8::MAX;
+        match self {
+            Notation::Hex if opaque => format!("#{red:02x}{green:02x}{blue:02x}"),
+            Notation::Hex => format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}"),
+            Notation::Rgb if opaque => format!("rgb({red}, {green}, {blue})"),
+            Notation::Rgb => {
+                let alpha = format!("{:.2}", color.alpha.clamp(0.0, 1.0));
+                let alpha = alpha.trim_end_matches('0').trim_end_matches('.');
+                format!("rgba({red}, {green}, {blue}, {alpha})")
+            }
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_byte(channel: f32) -> u8 {
+    // The product is within 0.0..=255.0, so the cast cannot wrap.
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+struct ColorLiteral {
+    span: Span,
+    color: ls_types::Color,
+}
+
+fn color_literals(source: &str, span: Span) -> Vec<ColorLiteral> {
+    let Some(text) = source.get(span.start_usize()..span.end_usize()) else {
+        return Vec::new();
+    };
+    let bytes = text.as_bytes();
+
+    let mut literals = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let starts_word = index == 0 || !continues_literal(bytes[index - 1], bytes[index]);
+        let literal = match bytes[index] {
+            b'#' if starts_word => {
+                parse_hex(&text[index + 1..]).map(|(len, color)| (len + 1, color))
+            }
+            b'r' | b'R' if starts_word => parse_rgb(&text[index..]),
+            _ => None,
+        };
+        let Some((len, color)) = literal else {
+            index += 1;
+            continue;
+        };
+
+        let start = span.start_usize() + index;
+        literals.push(ColorLiteral {
+            span: Span::saturating_from_bounds_usize(start, start + len),
+            color,
+        });
+        index += len;
+    }
+    literals
+}
+
+/// Whether `byte` sits inside a longer word or entity when preceded by
+/// `previous`, as in `a#fff`, `&#123;`, or `/#top`.
+fn continues_literal(previous: u8, byte: u8) -> bool {
+    is_word_byte(previous) || (byte == b'#' && matches!(previous, b'&' | b'/'))
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-')
+}
+
+/// Parse the digits after a `#`, returning how many there are and the color.
+fn parse_hex(text: &str) -> Option<(usize, ls_types::Color)> {
+    let len = text.bytes().take_while(u8::is_ascii_hexdigit).count();
+    if text.as_bytes().get(len).copied().is_some_and(is_word_byte) {
+        return None;
+    }
+    let width = match len {
+        3 | 4 => 1,
+        6 | 8 => 2,
+        _ => return None,
+    };
+
+    let channels = text[..len]
+        .as_bytes()
+        .chunks(width)
+        .map(|digits| {
+            let digits = std::str::from_utf8(digits).ok()?;
+            let value = u8::from_str_radix(digits, 16).ok()?;
+            // `#abc` is shorthand for `#aabbcc`.
+            Some(if width == 1 { value * 0x11 } else { value })
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    let [red, green, blue] = [channels[0], channels[1], channels[2]].map(f32::from);
+    let alpha = channels.get(3).copied().map_or(1.0, |alpha| f32::from(alpha) / 255.0);
+
+    Some((
+        len,
+        ls_types::Color {
+            red: red / 255.0,
+            green: green / 255.0,
+            blue: blue / 255.0,
+            alpha,
+        },
+    ))
+}
+
+/// Parse an `rgb()` or `rgba()` call in either the comma-separated form or
+/// the space-separated form with an optional `/ alpha`.
+fn parse_rgb(text: &str) -> Option<(usize, ls_types::Color)> {
+    let open = ["rgba(", "rgb("]
+        .into_iter()
+        .find(|prefix| {
+            text.get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+        })?
+        .len();
+    let close = open + text[open..].find(')')?;
+    let args = &text[open..close];
+
+    let parts = if args.contains(',') {
+        args.split(',').map(str::trim).collect::<Vec<_>>()
+    } else {
+        args.split(|c: char| c.is_ascii_whitespace() || c == '/')
+            .filter(|part| !part.is_empty())
+            .collect()
+    };
+    let (red, green, blue, alpha) = match parts.as_slice() {
+        [red, green, blue] => (red, green, blue, 1.0),
+        [red, green, blue, alpha] => (red, green, blue, parse_alpha(alpha)?),
+        _ => return None,
+    };
+
+    Some((
+        close + 1,
+        ls_types::Color {
+            red: parse_channel(red)?,
+            green: parse_channel(green)?,
+            blue: parse_channel(blue)?,
+            alpha,
+        },
+    ))
+}
+
+/// A channel as `0`–`255` or `0%`–`100%`, scaled to `0.0..=1.0`.
+fn parse_channel(value: &str) -> Option<f32> {
+    let fraction = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()? / 255.0,
+    };
+    (0.0..=1.0).contains(&fraction).then_some(fraction)
+}
+
+/// An alpha value as `0`–`1` or `0%`–`100%`.
+fn parse_alpha(value: &str) -> Option<f32> {
+    let alpha = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()?,
+    };
+    (0.0..=1.0).contains(&alpha).then_some(alpha)
+}
@@ -6,25 +6,42 @@
 //! 5. Rank candidates by relevance.
 //! 6. Convert candidates into an LSP completion response using client/session facts.
 
+use std::collections::BTreeSet;
+
+use djls_conf::CompletionsConfig;
+use djls_project::ContextProcessorVariable;
+use djls_project::DeclaredSetting;
+use djls_project::DjangoVersion;
 use djls_project::LoadableLibraryLookup;
 use djls_project::ScopedTemplateLibraries;
 use djls_project::ScopedTemplateSymbolLookup;
 use djls_project::TemplateLibrary;
+use djls_project::TemplateName;
+use djls_project::TemplateResolution;
+use djls_project::TemplateResolutionResult;
 use djls_project::TemplateSymbol;
 use djls_project::TemplateSymbolAvailability;
 use djls_project::TemplateSymbolCandidate;
 use djls_project::TemplateSymbolKind;
+use djls_project::UrlRoute;
+use djls_project::declared_settings;
+use djls_project::django_version;
 use djls_project::template_resolution;
+use djls_project::url_routes;
 use djls_semantic::Db as SemanticDb;
 use djls_semantic::TagArgumentKind;
 use djls_semantic::TagRole;
 use djls_semantic::TagSpec;
 use djls_semantic::TagSpecs;
+use djls_semantic::TemplateReferenceKind;
+use djls_semantic::TemplateVariableNames;
 use djls_semantic::effective_symbol_candidate_at;
+use djls_semantic::enclosing_loops_at;
 use djls_semantic::scoped_template_libraries_for_file;
 use djls_semantic::tag_spec_at;
 use djls_semantic::tag_specs_at;
 use djls_semantic::tag_specs_for_file;
+use djls_semantic::template_variable_names_at;
 use djls_source::File;
 use djls_source::FileKind;
 use djls_source::Offset;
@@ -32,6 +49,7 @@ use djls_source::PositionEncoding;
 use djls_source::Span;
 use djls_templates::NodeList;
 use djls_templates::TemplateParseResult;
+use djls_templates::embedded_regions;
 use djls_templates::parse_template;
 use tower_lsp_server::ls_types;
 
@@ -41,8 +59,15 @@ use crate::context::OffsetSuffix;
 use crate::context::TagClose;
 use crate::context::TemplateCompletionContext;
 use crate::ext::CompletionCandidateExt;
+use crate::html_attributes::HtmlAttribute;
+use crate::html_attributes::enabled_html_attributes;
+use crate::matching::CompletionMatcher;
+use crate::python_context::PythonCompletionContext;
 use crate::snippets::generate_partial_snippet;
 use crate::snippets::generate_snippet_for_tag_with_end;
+use crate::template_paths::TemplatePathEntry;
+use crate::template_paths::prevailing_quote;
+use crate::template_paths::template_path_entries;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CompletionCandidateKind {
@@ -53,9 +78,17 @@ pub(crate) enum CompletionCandidateKind {
     TagArgumentPlaceholder,
     TagArgumentSnippet,
     TemplateName,
+    TemplateDirectory,
     LibraryName,
     LoadSymbol,
     Filter,
+    BoundVariable,
+    Variable,
+    ContextVariable,
+    Setting,
+    ForloopAttribute,
+    HtmlAttribute,
+    UrlName,
 }
 
 impl CompletionCandidateKind {
@@ -66,11 +99,19 @@ impl CompletionCandidateKind {
             | Self::TagArgumentLiteral
             | Self::TagArgumentChoice
             | Self::TemplateName
+            | Self::TemplateDirectory
             | Self::LibraryName
             | Self::LoadSymbol
-            | Self::Filter => 1,
-            Self::TagArgumentPlaceholder => 3,
-            Self::TagArgumentSnippet => 4,
+            | Self::Filter
+            | Self::BoundVariable
+            | Self::Setting
+            | Self::ForloopAttribute
+            | Self::HtmlAttribute
+            | Self::UrlName => 1,
+            Self::Variable => 2,
+            Self::ContextVariable => 3,
+            Self::TagArgumentPlaceholder => 4,
+            Self::TagArgumentSnippet => 5,
         }
     }
 }
@@ -207,6 +248,62 @@ impl CompletionEdit {
         )
     }
 
+    /// Quote an unquoted template name with `quote` and close the tag.
+    fn unquoted_template_name(
+        name: &str,
+        quote: char,
+        prefix: &OffsetPrefix<'_>,
+        close: TagClose,
+    ) -> Self {
+        let mut insert_text = format!("{quote}{name}{quote}");
+        Self::append_tag_close(&mut insert_text, close);
+
+        Self::plain(Self::tag_replacement_span(prefix, close), insert_text)
+    }
+
+    /// Replace the typed directory segment of a quoted template path, keeping any deeper path
+    /// already after the cursor so the next level can be completed.
+    fn template_directory(
+        path: &str,
+        prefix: &OffsetPrefix<'_>,
+        suffix: &OffsetSuffix<'_>,
+    ) -> Self {
+        let segment_len = suffix
+            .text
+            .find('/')
+            .map_or(suffix.text.len(), |slash| slash + 1);
+        let end = (suffix.span.start_usize() + segment_len).max(prefix.span.end_usize());
+
+        Self::plain(
+            Span::saturating_from_bounds_usize(prefix.span.start_usize(), end),
+            path,
+        )
+    }
+
+    /// Open a quoted template path at a directory segment.
+    fn unquoted_template_directory(path: &str, quote: char, prefix: &OffsetPrefix<'_>) -> Self {
+        Self::plain(prefix.span, format!("{quote}{path}"))
+    }
+
+    /// Replace the contents of a string literal, closing it if it is still open.
+    fn string_literal(
+        value: &str,
+        quote: char,
+        prefix: &OffsetPrefix<'_>,
+        suffix: &OffsetSuffix<'_>,
+        closed: bool,
+    ) -> Self {
+        let mut insert_text = value.to_string();
+        if !closed {
+            insert_text.push(quote);
+        }
+
+        Self::plain(
+            Self::span_with_source_suffix(prefix, suffix, 0),
+            insert_text,
+        )
+    }
+
     fn load_symbol(
         name: &str,
         prefix: &OffsetPrefix<'_>,
@@ -261,6 +358,8 @@ pub(crate) struct CompletionCandidate {
     pub(crate) edit: CompletionEdit,
     pub(crate) detail: Option<String>,
     pub(crate) documentation: Option<String>,
+    /// Lower-is-better `CompletionMatcher` score of the label against the typed prefix.
+    pub(crate) match_score: u32,
 }
 
 impl CompletionCandidate {
@@ -268,9 +367,15 @@ impl CompletionCandidate {
         left.kind
             .rank()
             .cmp(&right.kind.rank())
+            .then_with(|| left.match_score.cmp(&right.match_score))
             .then_with(|| left.label.cmp(&right.label))
     }
 
+    fn with_match_score(mut self, match_score: u32) -> Self {
+        self.match_score = match_score;
+        self
+    }
+
     fn tag_name(
         symbol: &TemplateSymbol,
         prefix: &OffsetPrefix<'_>,
@@ -296,6 +401,7 @@ impl CompletionCandidate {
             edit,
             detail: Some(tag_completion_detail(availability)),
             documentation: symbol.doc().map(str::to_string),
+            match_score: 0,
         }
     }
 
@@ -322,6 +428,7 @@ impl CompletionCandidate {
             edit,
             detail: Some("Django template tag".to_string()),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -338,6 +445,7 @@ impl CompletionCandidate {
             edit: CompletionEdit::tag_plain(name, prefix, needs_leading_space, close),
             detail: Some(format!("End tag for {opener_name}")),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -348,6 +456,7 @@ impl CompletionCandidate {
             edit: CompletionEdit::tag_argument(label, prefix, close),
             detail: Some("literal argument".to_string()),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -363,6 +472,7 @@ impl CompletionCandidate {
             edit: CompletionEdit::tag_argument(label, prefix, close),
             detail: Some(format!("choice for {argument_name}")),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -373,6 +483,7 @@ impl CompletionCandidate {
             kind: CompletionCandidateKind::TagArgumentPlaceholder,
             detail: Some("variable argument".to_string()),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -394,6 +505,7 @@ impl CompletionCandidate {
             ),
             detail: Some("Complete remaining arguments".to_string()),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -411,6 +523,51 @@ impl CompletionCandidate {
             edit: CompletionEdit::template_name(name, quote, prefix, suffix, closed, close),
             detail: Some("Django template".to_string()),
             documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn unquoted_template_name(
+        name: &str,
+        quote: char,
+        prefix: &OffsetPrefix<'_>,
+        close: TagClose,
+    ) -> Self {
+        Self {
+            label: name.to_string(),
+            kind: CompletionCandidateKind::TemplateName,
+            edit: CompletionEdit::unquoted_template_name(name, quote, prefix, close),
+            detail: Some("Django template".to_string()),
+            documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn template_directory(path: &str, edit: CompletionEdit) -> Self {
+        Self {
+            label: path.to_string(),
+            kind: CompletionCandidateKind::TemplateDirectory,
+            edit,
+            detail: Some("Template directory".to_string()),
+            documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn python_template_name(
+        name: &str,
+        quote: char,
+        prefix: &OffsetPrefix<'_>,
+        suffix: &OffsetSuffix<'_>,
+        closed: bool,
+    ) -> Self {
+        Self {
+            label: name.to_string(),
+            kind: CompletionCandidateKind::TemplateName,
+            edit: CompletionEdit::string_literal(name, quote, prefix, suffix, closed),
+            detail: Some("Django template".to_string()),
+            documentation: None,
+            match_score: 0,
         }
     }
 
@@ -427,6 +584,7 @@ impl CompletionCandidate {
             edit: CompletionEdit::tag_argument_with_suffix(name, prefix, suffix, close),
             detail: Some(detail),
             documentation: None,
+            match_score: 0,
         }
     }
 
@@ -443,6 +601,7 @@ impl CompletionCandidate {
             edit: CompletionEdit::load_symbol(name, prefix, suffix, needs_trailing_space),
             detail: Some("load symbol".to_string()),
             documentation: documentation.map(str::to_string),
+            match_score: 0,
         }
     }
 
@@ -458,8 +617,153 @@ impl CompletionCandidate {
             edit: CompletionEdit::plain(prefix.span, name),
             detail: Some(filter_completion_detail(availability)),
             documentation: documentation.map(str::to_string),
+            match_score: 0,
+        }
+    }
+
+    fn variable(name: &str, prefix: &OffsetPrefix<'_>, bound: bool) -> Self {
+        let (kind, detail) = if bound {
+            (CompletionCandidateKind::BoundVariable, "blocktrans binding")
+        } else {
+            (CompletionCandidateKind::Variable, "used in this template")
+        };
+        Self {
+            label: name.to_string(),
+            kind,
+            edit: CompletionEdit::plain(prefix.span, name),
+            detail: Some(detail.to_string()),
+            documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn context_variable(variable: &ContextProcessorVariable, prefix: &OffsetPrefix<'_>) -> Self {
+        Self {
+            label: variable.name().to_string(),
+            kind: CompletionCandidateKind::ContextVariable,
+            edit: CompletionEdit::plain(prefix.span, variable.name()),
+            detail: Some("context processor".to_string()),
+            documentation: Some(format!("Added by `{}`.", variable.processor())),
+            match_score: 0,
+        }
+    }
+
+    fn html_attribute(
+        attribute: &HtmlAttribute,
+        prefix: &OffsetPrefix<'_>,
+        supports_snippets: bool,
+    ) -> Self {
+        let edit = if attribute.takes_value && supports_snippets {
+            CompletionEdit::snippet(prefix.span, format!("{}=\"$1\"", attribute.name))
+        } else {
+            CompletionEdit::plain(prefix.span, attribute.name)
+        };
+        Self {
+            label: attribute.name.to_string(),
+            kind: CompletionCandidateKind::HtmlAttribute,
+            edit,
+            detail: Some(attribute.library.to_string()),
+            documentation: Some(attribute.documentation.to_string()),
+            match_score: 0,
         }
     }
+
+    fn url_name(route: &UrlRoute, quote: char, prefix: &OffsetPrefix<'_>) -> Self {
+        let inner = if quote == '\'' { '"' } else { '\'' };
+        Self {
+            label: route.name().to_string(),
+            kind: CompletionCandidateKind::UrlName,
+            edit: CompletionEdit::plain(
+                prefix.span,
+                format!("{{% url {inner}{}{inner} %}}", route.name()),
+            ),
+            detail: Some("URL name".to_string()),
+            documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn setting(setting: &DeclaredSetting, prefix: &OffsetPrefix<'_>) -> Self {
+        Self {
+            label: setting.name().to_string(),
+            kind: CompletionCandidateKind::Setting,
+            edit: CompletionEdit::plain(prefix.span, setting.name()),
+            detail: Some(setting_detail(setting)),
+            documentation: None,
+            match_score: 0,
+        }
+    }
+
+    fn forloop_attribute(attribute: &ForloopAttribute, prefix: &OffsetPrefix<'_>) -> Self {
+        Self {
+            label: attribute.name.to_string(),
+            kind: CompletionCandidateKind::ForloopAttribute,
+            edit: CompletionEdit::plain(prefix.span, attribute.name),
+            detail: Some("forloop attribute".to_string()),
+            documentation: Some(attribute.documentation.to_string()),
+            match_score: 0,
+        }
+    }
+}
+
+/// An attribute Django sets on `forloop` inside a `{% for %}` body.
+struct ForloopAttribute {
+    name: &'static str,
+    documentation: &'static str,
+    /// The first release that sets it.
+    since: Option<DjangoVersion>,
+}
+
+const FORLOOP_ATTRIBUTES: &[ForloopAttribute] = &[
+    ForloopAttribute {
+        name: "counter",
+        documentation: "The current iteration of the loop (1-indexed).",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "counter0",
+        documentation: "The current iteration of the loop (0-indexed).",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "revcounter",
+        documentation: "The number of iterations from the end of the loop (1-indexed).",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "revcounter0",
+        documentation: "The number of iterations from the end of the loop (0-indexed).",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "first",
+        documentation: "`True` if this is the first time through the loop.",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "last",
+        documentation: "`True` if this is the last time through the loop.",
+        since: None,
+    },
+    ForloopAttribute {
+        name: "length",
+        documentation: "The number of items in the loop.",
+        since: Some(DjangoVersion::new(6, 0)),
+    },
+    ForloopAttribute {
+        name: "parentloop",
+        documentation: "The `forloop` of the loop surrounding this one.",
+        since: None,
+    },
+];
+
+/// `setting: str`, or `setting: bool | str` when branches disagree.
+fn setting_detail(setting: &DeclaredSetting) -> String {
+    if setting.types().is_empty() {
+        "setting".to_string()
+    } else {
+        format!("setting: {}", setting.types().join(" | "))
+    }
 }
 
 fn tag_completion_detail(availability: &TemplateSymbolAvailability) -> String {
@@ -492,18 +796,24 @@ pub fn completion(
     offset: Offset,
     encoding: PositionEncoding,
     supports_snippets: bool,
+    config: &CompletionsConfig,
 ) -> Option<ls_types::CompletionResponse> {
     let Ok(source) = file.try_source(db) else {
         return None;
     };
-    if *source.kind() != FileKind::Template {
-        return None;
-    }
-
-    let Ok(tokens) = djls_templates::lex_template(db, file) else {
-        return None;
+    let context = match source.kind() {
+        FileKind::Template => {
+            let Ok(tokens) = djls_templates::lex_template(db, file) else {
+                return None;
+            };
+            CompletionOffsetContext::new(FileKind::Template, source.as_str(), tokens, offset)
+        }
+        FileKind::Python => {
+            CompletionOffsetContext::new(FileKind::Python, source.as_str(), &[], offset)
+        }
+        FileKind::Other => return None,
     };
-    let context = CompletionOffsetContext::new(*source.kind(), source.as_str(), tokens, offset);
+    let matcher = CompletionMatcher::new(config.fuzzy());
 
     // Dispatch on the syntax-only cursor context before requesting semantic products. Most
     // completion contexts need either no tag meaning or one occurrence lookup; only tag-name
@@ -532,6 +842,7 @@ pub fn completion(
                     close: *close,
                     tag_specs,
                     supports_snippets,
+                    matcher,
                 },
             )
         }
@@ -542,16 +853,30 @@ pub fn completion(
             close,
             ..
         }) => {
-            let spec = parsed_nodelist(db, file)
-                .and_then(|nodelist| tag_spec_at(db, file, nodelist, offset.get(), tag));
-            generate_tag_argument_candidates(
+            let nodelist = parsed_nodelist(db, file);
+            let spec =
+                nodelist.and_then(|nodelist| tag_spec_at(db, file, nodelist, offset.get(), tag));
+            let mut candidates = generate_tag_argument_candidates(
                 tag,
                 *position,
                 prefix,
                 *close,
                 spec.as_ref(),
                 supports_snippets,
-            )
+            );
+            if let Some(kind) = template_reference_kind(spec.as_ref(), *position) {
+                let quote =
+                    nodelist.map_or('"', |nodelist| prevailing_quote(nodelist.nodelist(db)));
+                candidates.extend(generate_unquoted_template_name_candidates(
+                    db,
+                    file,
+                    kind,
+                    quote,
+                    prefix,
+                    *close,
+                ));
+            }
+            candidates
         }
         CompletionOffsetContext::Template(TemplateCompletionContext::QuotedArgument {
             tag,
@@ -591,6 +916,7 @@ pub fn completion(
                     prefix,
                     suffix,
                     *close,
+                    matcher,
                 )
             } else {
                 Vec::new()
@@ -611,6 +937,7 @@ pub fn completion(
                     *library,
                     *needs_trailing_space,
                     scoped_template_libraries_for_file(db, file),
+                    matcher,
                 )
             } else {
                 Vec::new()
@@ -624,8 +951,54 @@ pub fn completion(
                 offset,
                 scoped_template_libraries_for_file(db, file),
                 prefix,
+                matcher,
             )
         }
+        CompletionOffsetContext::Template(TemplateCompletionContext::Variable { prefix }) => {
+            parsed_nodelist(db, file).map_or_else(Vec::new, |nodelist| {
+                generate_variable_candidates(
+                    &template_variable_names_at(db, file, nodelist, offset),
+                    prefix,
+                    matcher,
+                )
+            })
+        }
+        CompletionOffsetContext::Template(TemplateCompletionContext::SettingName { prefix }) => {
+            generate_setting_candidates(db, prefix, matcher)
+        }
+        CompletionOffsetContext::Template(TemplateCompletionContext::ForloopAttribute {
+            prefix,
+            parentloops,
+        }) => parsed_nodelist(db, file).map_or_else(Vec::new, |nodelist| {
+            let loops = enclosing_loops_at(db, file, nodelist, offset);
+            generate_forloop_attribute_candidates(db, loops, *parentloops, prefix, matcher)
+        }),
+        CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttribute { prefix }) => {
+            let in_embedded_code = embedded_regions(db, file)
+                .iter()
+                .any(|region| region.contains_span(Span::new(offset.get(), 0)));
+            if in_embedded_code {
+                Vec::new()
+            } else {
+                generate_html_attribute_candidates(config, prefix, supports_snippets, matcher)
+            }
+        }
+        CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttributeUrl {
+            quote,
+            prefix,
+        }) => {
+            if config.htmx() {
+                generate_url_name_candidates(db, *quote, prefix, matcher)
+            } else {
+                Vec::new()
+            }
+        }
+        CompletionOffsetContext::Python(PythonCompletionContext::TemplateName {
+            quote,
+            prefix,
+            suffix,
+            closed,
+        }) => generate_python_template_name_candidates(db, *quote, prefix, suffix, *closed),
         CompletionOffsetContext::Template(TemplateCompletionContext::Text)
         | CompletionOffsetContext::None => Vec::new(),
     };
@@ -669,6 +1042,7 @@ struct TagNameCandidateInput<'a> {
     close: TagClose,
     tag_specs: &'a TagSpecs,
     supports_snippets: bool,
+    matcher: CompletionMatcher,
 }
 
 fn completion_symbol_candidates(
@@ -717,6 +1091,7 @@ fn generate_tag_name_candidates(
         close,
         tag_specs,
         supports_snippets,
+        matcher,
     } = input;
     let mut candidates = Vec::new();
 
@@ -726,22 +1101,24 @@ fn generate_tag_name_candidates(
                 continue;
             };
             let name = end_tag.name.as_ref();
-            if name.starts_with(prefix.text) {
-                candidates.push(CompletionCandidate::end_tag(
-                    opener_name,
-                    name,
-                    prefix,
-                    needs_leading_space,
-                    close,
-                ));
+            if let Some(score) = matcher.score(prefix.text, name) {
+                candidates.push(
+                    CompletionCandidate::end_tag(
+                        opener_name,
+                        name,
+                        prefix,
+                        needs_leading_space,
+                        close,
+                    )
+                    .with_match_score(score),
+                );
             }
         }
     }
 
     let contextual_candidate_start = candidates.len();
-    for name in scoped_libraries
-        .inventory_symbol_names(TemplateSymbolKind::Tag)
-        .filter(|name| name.starts_with(prefix.text))
+    for (name, score) in
+        matcher.symbol_names(scoped_libraries, TemplateSymbolKind::Tag, prefix.text)
     {
         for candidate in completion_symbol_candidates(
             db,
@@ -752,15 +1129,18 @@ fn generate_tag_name_candidates(
             name,
             TemplateSymbolKind::Tag,
         ) {
-            candidates.push(CompletionCandidate::tag_name(
-                &candidate.symbol,
-                prefix,
-                needs_leading_space,
-                close,
-                tag_specs.get(name),
-                &candidate.availability,
-                supports_snippets,
-            ));
+            candidates.push(
+                CompletionCandidate::tag_name(
+                    &candidate.symbol,
+                    prefix,
+                    needs_leading_space,
+                    close,
+                    tag_specs.get(name),
+                    &candidate.availability,
+                    supports_snippets,
+                )
+                .with_match_score(score),
+            );
         }
     }
 
@@ -768,15 +1148,18 @@ fn generate_tag_name_candidates(
         && !scope_has_definite_symbols(scoped_libraries, TemplateSymbolKind::Tag)
     {
         for (name, spec) in tag_specs {
-            if name.starts_with(prefix.text) {
-                candidates.push(CompletionCandidate::tag_name_from_spec(
-                    name,
-                    prefix,
-                    needs_leading_space,
-                    close,
-                    spec,
-                    supports_snippets,
-                ));
+            if let Some(score) = matcher.score(prefix.text, name) {
+                candidates.push(
+                    CompletionCandidate::tag_name_from_spec(
+                        name,
+                        prefix,
+                        needs_leading_space,
+                        close,
+                        spec,
+                        supports_snippets,
+                    )
+                    .with_match_score(score),
+                );
             }
         }
     }
@@ -860,13 +1243,73 @@ fn generate_template_name_candidates(
     input: TemplateNameCandidateInput<'_, '_>,
     spec: Option<&TagSpec>,
 ) -> Vec<CompletionCandidate> {
-    let Some(spec) = spec else {
+    let Some(kind) = template_reference_kind(spec, input.position) else {
         return Vec::new();
     };
-    if !matches!(spec.role(), Some(TagRole::TemplateReference(_))) || input.position != 0 {
-        return Vec::new();
+
+    template_path_candidate_entries(db, file, input.prefix.text, kind)
+        .into_iter()
+        .map(|entry| match entry {
+            TemplatePathEntry::File { name, rank } => CompletionCandidate::template_name(
+                &name,
+                input.quote,
+                input.prefix,
+                input.suffix,
+                input.closed,
+                input.close,
+            )
+            .with_match_score(rank),
+            TemplatePathEntry::Directory { path, rank } => {
+                let edit = CompletionEdit::template_directory(&path, input.prefix, input.suffix);
+                CompletionCandidate::template_directory(&path, edit).with_match_score(rank)
+            }
+        })
+        .collect()
+}
+
+/// Template names for an `extends` or `include` argument typed without quotes, quoted in the
+/// style the template already uses.
+fn generate_unquoted_template_name_candidates(
+    db: &dyn SemanticDb,
+    file: File,
+    kind: TemplateReferenceKind,
+    quote: char,
+    prefix: &OffsetPrefix<'_>,
+    close: TagClose,
+) -> Vec<CompletionCandidate> {
+    template_path_candidate_entries(db, Some(file), prefix.text, kind)
+        .into_iter()
+        .map(|entry| match entry {
+            TemplatePathEntry::File { name, rank } => {
+                CompletionCandidate::unquoted_template_name(&name, quote, prefix, close)
+                    .with_match_score(rank)
+            }
+            TemplatePathEntry::Directory { path, rank } => {
+                let edit = CompletionEdit::unquoted_template_directory(&path, quote, prefix);
+                CompletionCandidate::template_directory(&path, edit).with_match_score(rank)
+            }
+        })
+        .collect()
+}
+
+/// The template reference an argument names, if `spec` is an `extends` or `include` tag and
+/// the argument is its first.
+fn template_reference_kind(
+    spec: Option<&TagSpec>,
+    position: usize,
+) -> Option<TemplateReferenceKind> {
+    match spec?.role() {
+        Some(TagRole::TemplateReference(kind)) if position == 0 => Some(kind),
+        _ => None,
     }
+}
 
+fn template_path_candidate_entries(
+    db: &dyn SemanticDb,
+    file: Option<File>,
+    typed: &str,
+    kind: TemplateReferenceKind,
+) -> Vec<TemplatePathEntry> {
     let Some(project) = db.project() else {
         return Vec::new();
     };
@@ -876,19 +1319,55 @@ fn generate_template_name_candidates(
         Some(file) => resolution.template_names_for_backend_scope(db, file),
         None => resolution.template_names(db).collect(),
     };
-    names
+    let ranked = names
         .into_iter()
-        .filter_map(|name| {
-            let name = name.name(db);
-            name.starts_with(input.prefix.text).then(|| {
-                CompletionCandidate::template_name(
-                    name,
-                    input.quote,
-                    input.prefix,
-                    input.suffix,
-                    input.closed,
-                    input.close,
-                )
+        .filter(|template_name| template_name.name(db).starts_with(typed))
+        .map(|template_name| {
+            (
+                template_name.name(db),
+                template_namespace_rank(db, resolution, template_name),
+            )
+        });
+    template_path_entries(ranked, typed, kind == TemplateReferenceKind::Include)
+}
+
+/// Ranks a name that resolves to the top of an app's `templates` directory after names under
+/// the app's namespace directory, so `blog/form.html` sorts ahead of a bare `form.html` that
+/// another app could shadow.
+fn template_namespace_rank<'db>(
+    db: &'db dyn SemanticDb,
+    resolution: TemplateResolution<'db>,
+    name: TemplateName<'db>,
+) -> u32 {
+    let TemplateResolutionResult::Found(origin) = resolution.resolve(db, name) else {
+        return 0;
+    };
+    match resolution.app_for_origin(db, origin) {
+        Some(app) if !app.namespaces(name.name(db)) => 1,
+        _ => 0,
+    }
+}
+
+/// Template names for a string argument in Python code, from every template backend.
+fn generate_python_template_name_candidates(
+    db: &dyn SemanticDb,
+    quote: char,
+    prefix: &OffsetPrefix<'_>,
+    suffix: &OffsetSuffix<'_>,
+    closed: bool,
+) -> Vec<CompletionCandidate> {
+    let Some(project) = db.project() else {
+        return Vec::new();
+    };
+
+    let resolution = template_resolution(db, project);
+    resolution
+        .template_names(db)
+        .filter_map(|template_name| {
+            let name = template_name.name(db);
+            name.starts_with(prefix.text).then(|| {
+                CompletionCandidate::python_template_name(name, quote, prefix, suffix, closed)
+                    .with_match_score(template_namespace_rank(db, resolution, template_name))
             })
         })
         .collect()
@@ -899,12 +1378,13 @@ fn generate_library_name_candidates(
     prefix: &OffsetPrefix<'_>,
     suffix: &OffsetSuffix<'_>,
     close: TagClose,
+    matcher: CompletionMatcher,
 ) -> Vec<CompletionCandidate> {
     let mut candidates = Vec::new();
     for name in scoped_libraries.completion_library_names() {
-        if !name.as_str().starts_with(prefix.text) {
+        let Some(score) = matcher.score(prefix.text, name.as_str()) else {
             continue;
-        }
+        };
 
         let detail = match scoped_libraries.loadable_library(&name) {
             LoadableLibraryLookup::Found(library) => {
@@ -914,13 +1394,10 @@ fn generate_library_name_candidates(
             | LoadableLibraryLookup::Inconclusive(_)
             | LoadableLibraryLookup::Absent => "Django template library".to_string(),
         };
-        candidates.push(CompletionCandidate::library_name(
-            name.as_str(),
-            prefix,
-            suffix,
-            close,
-            detail,
-        ));
+        candidates.push(
+            CompletionCandidate::library_name(name.as_str(), prefix, suffix, close, detail)
+                .with_match_score(score),
+        );
     }
 
     candidates
@@ -932,6 +1409,7 @@ fn generate_load_symbol_candidates(
     library: Option<&str>,
     needs_trailing_space: bool,
     scoped_libraries: ScopedTemplateLibraries<'_>,
+    matcher: CompletionMatcher,
 ) -> Vec<CompletionCandidate> {
     let Some(name) = library else {
         return Vec::new();
@@ -945,14 +1423,17 @@ fn generate_load_symbol_candidates(
     let mut candidates = libraries
         .into_iter()
         .flat_map(TemplateLibrary::symbols)
-        .filter(|symbol| symbol.name().starts_with(prefix.text))
-        .map(|symbol| {
-            CompletionCandidate::load_symbol(
-                symbol.name(),
-                prefix,
-                suffix,
-                needs_trailing_space,
-                symbol.doc(),
+        .filter_map(|symbol| {
+            let score = matcher.score(prefix.text, symbol.name())?;
+            Some(
+                CompletionCandidate::load_symbol(
+                    symbol.name(),
+                    prefix,
+                    suffix,
+                    needs_trailing_space,
+                    symbol.doc(),
+                )
+                .with_match_score(score),
             )
         })
         .collect::<Vec<_>>();
@@ -968,11 +1449,11 @@ fn generate_filter_candidates(
     offset: Offset,
     scoped_libraries: ScopedTemplateLibraries<'_>,
     prefix: &OffsetPrefix<'_>,
+    matcher: CompletionMatcher,
 ) -> Vec<CompletionCandidate> {
     let mut candidates = Vec::new();
-    for name in scoped_libraries
-        .inventory_symbol_names(TemplateSymbolKind::Filter)
-        .filter(|name| name.starts_with(prefix.text))
+    for (name, score) in
+        matcher.symbol_names(scoped_libraries, TemplateSymbolKind::Filter, prefix.text)
     {
         for candidate in completion_symbol_candidates(
             db,
@@ -983,12 +1464,15 @@ fn generate_filter_candidates(
             name,
             TemplateSymbolKind::Filter,
         ) {
-            candidates.push(CompletionCandidate::filter(
-                name,
-                prefix,
-                &candidate.availability,
-                candidate.symbol.doc(),
-            ));
+            candidates.push(
+                CompletionCandidate::filter(
+                    name,
+                    prefix,
+                    &candidate.availability,
+                    candidate.symbol.doc(),
+                )
+                .with_match_score(score),
+            );
         }
     }
 
@@ -997,6 +1481,113 @@ fn generate_filter_candidates(
     candidates
 }
 
+fn generate_variable_candidates(
+    names: &TemplateVariableNames,
+    prefix: &OffsetPrefix<'_>,
+    matcher: CompletionMatcher,
+) -> Vec<CompletionCandidate> {
+    let bound_names = names.bound.iter().map(|name| (name, true));
+    let used_names = names.used.iter().map(|name| (name, false));
+    let context_variables = names.context.iter().filter_map(|variable| {
+        let score = matcher.score(prefix.text, variable.name())?;
+        Some(CompletionCandidate::context_variable(variable, prefix).with_match_score(score))
+    });
+    bound_names
+        .chain(used_names)
+        .filter_map(|(name, bound)| {
+            let score = matcher.score(prefix.text, name)?;
+            Some(CompletionCandidate::variable(name, prefix, bound).with_match_score(score))
+        })
+        .chain(context_variables)
+        .collect()
+}
+
+fn generate_html_attribute_candidates(
+    config: &CompletionsConfig,
+    prefix: &OffsetPrefix<'_>,
+    supports_snippets: bool,
+    matcher: CompletionMatcher,
+) -> Vec<CompletionCandidate> {
+    enabled_html_attributes(config)
+        .filter_map(|attribute| {
+            let score = matcher.score(prefix.text, attribute.name)?;
+            Some(
+                CompletionCandidate::html_attribute(attribute, prefix, supports_snippets)
+                    .with_match_score(score),
+            )
+        })
+        .collect()
+}
+
+/// Offer a `{% url %}` tag for each named URL pattern, once per name.
+fn generate_url_name_candidates(
+    db: &dyn SemanticDb,
+    quote: char,
+    prefix: &OffsetPrefix<'_>,
+    matcher: CompletionMatcher,
+) -> Vec<CompletionCandidate> {
+    let Some(project) = db.project() else {
+        return Vec::new();
+    };
+
+    let mut seen = BTreeSet::new();
+    url_routes(db, project)
+        .iter()
+        .filter(|route| seen.insert(route.name()))
+        .filter_map(|route| {
+            let score = matcher.score(prefix.text, route.name())?;
+            Some(CompletionCandidate::url_name(route, quote, prefix).with_match_score(score))
+        })
+        .collect()
+}
+
+fn generate_setting_candidates(
+    db: &dyn SemanticDb,
+    prefix: &OffsetPrefix<'_>,
+    matcher: CompletionMatcher,
+) -> Vec<CompletionCandidate> {
+    let Some(project) = db.project() else {
+        return Vec::new();
+    };
+
+    declared_settings(db, project)
+        .iter()
+        .filter_map(|setting| {
+            let score = matcher.score(prefix.text, setting.name())?;
+            Some(CompletionCandidate::setting(setting, prefix).with_match_score(score))
+        })
+        .collect()
+}
+
+/// The `forloop` attributes available `parentloops` lookups out from the
+/// innermost of `loops` enclosing loops. `parentloop` is only offered when
+/// there is another loop outside that one.
+fn generate_forloop_attribute_candidates(
+    db: &dyn SemanticDb,
+    loops: usize,
+    parentloops: usize,
+    prefix: &OffsetPrefix<'_>,
+    matcher: CompletionMatcher,
+) -> Vec<CompletionCandidate> {
+    if loops <= parentloops {
+        return Vec::new();
+    }
+    let version = db
+        .project()
+        .and_then(|project| django_version(db, project))
+        .unwrap_or(DjangoVersion::LATEST);
+
+    FORLOOP_ATTRIBUTES
+        .iter()
+        .filter(|attribute| attribute.since.is_none_or(|since| since <= version))
+        .filter(|attribute| attribute.name != "parentloop" || loops > parentloops + 1)
+        .filter_map(|attribute| {
+            let score = matcher.score(prefix.text, attribute.name)?;
+            Some(CompletionCandidate::forloop_attribute(attribute, prefix).with_match_score(score))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
@@ -1377,6 +1968,30 @@ mod tests {
         assert_eq!(candidate.edit.insert_text, "base.html");
     }
 
+    #[test]
+    fn python_template_name_candidate_closes_open_string_only() {
+        let open = CompletionCandidate::python_template_name(
+            "blog/list.html",
+            '\'',
+            &prefix("bl"),
+            &suffix("", 2),
+            false,
+        );
+        let closed = CompletionCandidate::python_template_name(
+            "blog/list.html",
+            '\'',
+            &prefix("bl"),
+            &suffix("og", 2),
+            true,
+        );
+
+        assert_eq!(open.kind, CompletionCandidateKind::TemplateName);
+        assert_eq!(open.edit.replacement_span, Span::new(0, 2));
+        assert_eq!(open.edit.insert_text, "blog/list.html'");
+        assert_eq!(closed.edit.replacement_span, Span::new(0, 4));
+        assert_eq!(closed.edit.insert_text, "blog/list.html");
+    }
+
     #[test]
     fn template_name_candidates_are_role_and_position_gated() {
         let db = TestDatabase::new();
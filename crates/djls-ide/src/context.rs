@@ -4,9 +4,13 @@ use djls_source::Span;
 use djls_templates::TagDelimiter;
 use djls_templates::Token;
 
+use crate::html_attributes::URL_ATTRIBUTES;
+use crate::python_context::PythonCompletionContext;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum CompletionOffsetContext<'source> {
     Template(TemplateCompletionContext<'source>),
+    Python(PythonCompletionContext<'source>),
     None,
 }
 
@@ -21,7 +25,9 @@ impl<'source> CompletionOffsetContext<'source> {
             FileKind::Template => Self::Template(TemplateCompletionContext::from_tokens(
                 source, tokens, offset,
             )),
-            FileKind::Python | FileKind::Other => Self::None,
+            FileKind::Python => PythonCompletionContext::from_source(source, offset)
+                .map_or(Self::None, Self::Python),
+            FileKind::Other => Self::None,
         }
     }
 }
@@ -63,12 +69,35 @@ pub(crate) enum TemplateCompletionContext<'source> {
     Filter {
         prefix: OffsetPrefix<'source>,
     },
+    Variable {
+        prefix: OffsetPrefix<'source>,
+    },
+    /// The attribute after `settings.`, as exposed by `django-settings-export`
+    /// and similar context processors.
+    SettingName {
+        prefix: OffsetPrefix<'source>,
+    },
+    /// The attribute after `forloop.`, or after `forloop.parentloop.` with
+    /// `parentloops` counting each `parentloop`.
+    ForloopAttribute {
+        prefix: OffsetPrefix<'source>,
+        parentloops: usize,
+    },
+    /// An attribute name inside an HTML start tag, e.g. `<div hx-`.
+    HtmlAttribute {
+        prefix: OffsetPrefix<'source>,
+    },
+    /// The quoted value of an htmx request attribute, e.g. `<button hx-get="blog`.
+    HtmlAttributeUrl {
+        quote: char,
+        prefix: OffsetPrefix<'source>,
+    },
 }
 
 impl<'source> TemplateCompletionContext<'source> {
     fn from_tokens(source: &'source str, tokens: &[Token], offset: Offset) -> Self {
         let Some(token) = token_at_offset(tokens, offset) else {
-            return Self::Text;
+            return Self::from_markup(source, tokens, offset);
         };
 
         match token {
@@ -84,15 +113,129 @@ impl<'source> TemplateCompletionContext<'source> {
                 delimiter: TagDelimiter::Variable,
                 ..
             } => Self::from_variable(source, *span, offset),
+            Token::Newline { .. } | Token::Text { .. } | Token::Whitespace { .. } => {
+                Self::from_markup(source, tokens, offset)
+            }
             Token::Comment { .. }
             | Token::Eof
             | Token::Error {
                 delimiter: TagDelimiter::Comment,
                 ..
+            } => Self::Text,
+        }
+    }
+
+    /// Recognize an attribute name being typed inside an HTML start tag.
+    ///
+    /// Django constructs are skipped, so `<div class="{{ cls }}" hx-` still
+    /// counts as inside the `<div>` tag. Anything else is plain text.
+    fn from_markup(source: &'source str, tokens: &[Token], offset: Offset) -> Self {
+        let end = (offset.get() as usize).min(source.len());
+        if !source.is_char_boundary(end) {
+            return Self::Text;
+        }
+
+        let pieces = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Newline { span } | Token::Text { span, .. } | Token::Whitespace { span } => {
+                    Some(*span)
+                }
+                Token::Block { .. }
+                | Token::Comment { .. }
+                | Token::Eof
+                | Token::Error { .. }
+                | Token::Variable { .. } => None,
+            })
+            .filter(|span| span.start_usize() < end)
+            .map(|span| (span.start_usize(), span.end_usize().min(end)))
+            .collect::<Vec<_>>();
+        let Some(&(last_start, last_end)) = pieces.last() else {
+            return Self::Text;
+        };
+        if last_end != end {
+            return Self::Text;
+        }
+
+        let bytes = source.as_bytes();
+        let Some(tag_start) = pieces.iter().rev().find_map(|&(start, stop)| {
+            (start..stop).rev().find(|&index| {
+                bytes[index] == b'<'
+                    && bytes
+                        .get(index + 1)
+                        .is_some_and(|next| index + 1 < stop && next.is_ascii_alphabetic())
+            })
+        }) else {
+            return Self::Text;
+        };
+
+        let mut quote = None;
+        for &(start, stop) in &pieces {
+            let from = start.max(tag_start);
+            for (index, &byte) in bytes.get(from..stop).unwrap_or_default().iter().enumerate() {
+                match quote {
+                    Some((open, _)) if open == byte => quote = None,
+                    Some(_) => {}
+                    None if matches!(byte, b'"' | b'\'') => quote = Some((byte, from + index)),
+                    None if byte == b'>' => return Self::Text,
+                    None => {}
+                }
             }
-            | Token::Newline { .. }
-            | Token::Text { .. }
-            | Token::Whitespace { .. } => Self::Text,
+        }
+        if let Some((_, opened)) = quote {
+            return Self::from_attribute_value(source, tag_start, opened, last_start, offset);
+        }
+
+        let before = &source[last_start.max(tag_start)..end];
+        let name_start = before
+            .rfind(|c: char| !is_attribute_name_char(c))
+            .map_or(0, |index| index + 1);
+        let follows_separator = match before[..name_start].chars().next_back() {
+            Some(c) => c.is_ascii_whitespace(),
+            // The name starts right after a Django construct.
+            None => last_start > tag_start,
+        };
+        if !follows_separator {
+            return Self::Text;
+        }
+
+        Self::HtmlAttribute {
+            prefix: OffsetPrefix::new(&before[name_start..], offset),
+        }
+    }
+
+    /// Recognize a URL being typed inside the quoted value of an htmx request
+    /// attribute such as `hx-get`.
+    ///
+    /// A Django construct inside the value, as in `hx-get="{% url 'a' %}?`, ends the
+    /// recognizable part, so the value must start in the text piece being typed.
+    fn from_attribute_value(
+        source: &'source str,
+        tag_start: usize,
+        opened: usize,
+        last_start: usize,
+        offset: Offset,
+    ) -> Self {
+        if opened < last_start {
+            return Self::Text;
+        }
+
+        let before_value = source[tag_start..opened].trim_end();
+        let Some(before_equals) = before_value.strip_suffix('=') else {
+            return Self::Text;
+        };
+        let before_equals = before_equals.trim_end();
+        let name_start = before_equals
+            .rfind(|c: char| !is_attribute_name_char(c))
+            .map_or(0, |index| index + 1);
+        if !URL_ATTRIBUTES.contains(&&before_equals[name_start..]) {
+            return Self::Text;
+        }
+
+        let end = offset.get() as usize;
+        Self::HtmlAttributeUrl {
+            quote: char::from(source.as_bytes()[opened]),
+            prefix: OffsetPrefix::new(&source[opened + 1..end], offset),
         }
     }
 
@@ -133,6 +276,29 @@ impl<'source> TemplateCompletionContext<'source> {
             (0, "")
         };
 
+        if matches!(tag, "blocktrans" | "blocktranslate")
+            && let Some((name, value)) = prefix.split_once('=')
+            && is_variable_name(name)
+            && (value.is_empty() || is_variable_name(value))
+        {
+            return Self::Variable {
+                prefix: OffsetPrefix::new(value, offset),
+            };
+        }
+
+        if let Some(setting) = setting_name_prefix(prefix) {
+            return Self::SettingName {
+                prefix: OffsetPrefix::new(setting, offset),
+            };
+        }
+
+        if let Some((attribute, parentloops)) = forloop_attribute_prefix(prefix) {
+            return Self::ForloopAttribute {
+                prefix: OffsetPrefix::new(attribute, offset),
+                parentloops,
+            };
+        }
+
         if let Some((quote, quoted_prefix)) = unclosed_quote_prefix(prefix) {
             let (suffix, closed, close) =
                 OffsetSuffix::quoted_at_offset(source, offset, content_span, quote);
@@ -223,6 +389,23 @@ impl<'source> TemplateCompletionContext<'source> {
     fn from_variable(source: &'source str, content_span: Span, offset: Offset) -> Self {
         let content = content_before_offset(source, content_span, offset);
         let Some(pipe) = find_last_unquoted_pipe(content) else {
+            let prefix = content.trim_start();
+            if let Some(setting) = setting_name_prefix(prefix) {
+                return Self::SettingName {
+                    prefix: OffsetPrefix::new(setting, offset),
+                };
+            }
+            if let Some((attribute, parentloops)) = forloop_attribute_prefix(prefix) {
+                return Self::ForloopAttribute {
+                    prefix: OffsetPrefix::new(attribute, offset),
+                    parentloops,
+                };
+            }
+            if prefix.is_empty() || is_variable_name(prefix) {
+                return Self::Variable {
+                    prefix: OffsetPrefix::new(prefix, offset),
+                };
+            }
             return Self::Text;
         };
         let after_pipe = &content[pipe + 1..];
@@ -306,7 +489,7 @@ pub(crate) struct OffsetPrefix<'source> {
 }
 
 impl<'source> OffsetPrefix<'source> {
-    fn new(text: &'source str, offset: Offset) -> Self {
+    pub(crate) fn new(text: &'source str, offset: Offset) -> Self {
         Self {
             text,
             span: Span::before_offset(offset, text.len()),
@@ -321,7 +504,7 @@ pub(crate) struct OffsetSuffix<'source> {
 }
 
 impl<'source> OffsetSuffix<'source> {
-    fn new(text: &'source str, offset: Offset) -> Self {
+    pub(crate) fn new(text: &'source str, offset: Offset) -> Self {
         Self {
             text,
             span: Span::saturating_from_parts_usize(offset.get() as usize, text.len()),
@@ -503,7 +686,7 @@ fn unclosed_quote_prefix(text: &str) -> Option<(char, &str)> {
     quote.map(|quote| (quote, &text[quote_start + quote.len_utf8()..]))
 }
 
-fn is_escaped(bytes: &[u8], index: usize) -> bool {
+pub(crate) fn is_escaped(bytes: &[u8], index: usize) -> bool {
     bytes[..index]
         .iter()
         .rev()
@@ -541,6 +724,37 @@ fn find_last_unquoted_pipe(text: &str) -> Option<usize> {
     last_pipe
 }
 
+/// Whether `text` is a bare variable name, the only thing variable completion offers.
+fn is_variable_name(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_attribute_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.' | '@')
+}
+
+/// The partial setting name in `settings.<name>`, if `text` has that shape.
+fn setting_name_prefix(text: &str) -> Option<&str> {
+    let name = text.strip_prefix("settings.")?;
+    (name.is_empty() || is_variable_name(name)).then_some(name)
+}
+
+/// The attribute typed after `forloop.` and the number of `parentloop.`
+/// lookups between them.
+fn forloop_attribute_prefix(text: &str) -> Option<(&str, usize)> {
+    let mut name = text.strip_prefix("forloop.")?;
+    let mut parentloops = 0;
+    while let Some(rest) = name.strip_prefix("parentloop.") {
+        name = rest;
+        parentloops += 1;
+    }
+    (name.is_empty() || is_variable_name(name)).then_some((name, parentloops))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -575,7 +789,7 @@ mod tests {
         let source = "print('hello')";
 
         assert_eq!(
-            CompletionOffsetContext::new(FileKind::Python, source, &[], Offset::new(0),),
+            CompletionOffsetContext::new(FileKind::Other, source, &[], Offset::new(0),),
             CompletionOffsetContext::None,
         );
     }
@@ -1201,6 +1415,160 @@ mod tests {
         });
     }
 
+    #[test]
+    fn variable_without_pipe_uses_variable_syntax_context() {
+        with_syntax_context("{{ us▮ }}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::Variable {
+                    prefix: OffsetPrefix { text: "us", .. },
+                })
+            ));
+        });
+        with_syntax_context("{{ user.na▮ }}", |_, context| {
+            assert_eq!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::Text),
+            );
+        });
+    }
+
+    #[test]
+    fn blocktrans_with_value_uses_variable_syntax_context() {
+        with_syntax_context("{% blocktrans with name=us▮ %}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::Variable {
+                    prefix: OffsetPrefix { text: "us", .. },
+                })
+            ));
+        });
+        with_syntax_context("{% include \"a.html\" with name=us▮ %}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::TagArgument { .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn settings_attribute_uses_setting_name_syntax_context() {
+        with_syntax_context("{{ settings.SITE_▮ }}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::SettingName {
+                    prefix: OffsetPrefix { text: "SITE_", .. },
+                })
+            ));
+        });
+        with_syntax_context("{% if settings.▮ %}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::SettingName {
+                    prefix: OffsetPrefix { text: "", .. },
+                })
+            ));
+        });
+        with_syntax_context("{{ settings.SITE.na▮ }}", |_, context| {
+            assert_eq!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::Text),
+            );
+        });
+    }
+
+    #[test]
+    fn forloop_attribute_counts_parentloops() {
+        with_syntax_context("{{ forloop.cou▮ }}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::ForloopAttribute {
+                    prefix: OffsetPrefix { text: "cou", .. },
+                    parentloops: 0,
+                })
+            ));
+        });
+        with_syntax_context("{% if forloop.parentloop.▮ %}", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::ForloopAttribute {
+                    prefix: OffsetPrefix { text: "", .. },
+                    parentloops: 1,
+                })
+            ));
+        });
+        with_syntax_context("{{ forloop.counter.re▮ }}", |_, context| {
+            assert_eq!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::Text),
+            );
+        });
+    }
+
+    #[test]
+    fn attribute_name_in_start_tag_uses_html_attribute_syntax_context() {
+        with_syntax_context("<button class=\"a\" hx-p▮", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttribute {
+                    prefix: OffsetPrefix { text: "hx-p", .. },
+                })
+            ));
+        });
+        with_syntax_context("<div class=\"{{ cls }}\"\n     x-▮>", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttribute {
+                    prefix: OffsetPrefix { text: "x-", .. },
+                })
+            ));
+        });
+        for input in ["<div class=\"hx-▮\">", "<div>hx-▮", "<di▮", "</div hx-▮"] {
+            with_syntax_context(input, |_, context| {
+                assert_eq!(
+                    context,
+                    CompletionOffsetContext::Template(TemplateCompletionContext::Text),
+                    "{input}"
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn htmx_request_url_uses_html_attribute_url_syntax_context() {
+        with_syntax_context("<button class=\"a\" hx-get=\"blog:▮", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttributeUrl {
+                    quote: '"',
+                    prefix: OffsetPrefix { text: "blog:", .. },
+                })
+            ));
+        });
+        with_syntax_context("<form hx-post = '▮'>", |_, context| {
+            assert!(matches!(
+                context,
+                CompletionOffsetContext::Template(TemplateCompletionContext::HtmlAttributeUrl {
+                    quote: '\'',
+                    prefix: OffsetPrefix { text: "", .. },
+                })
+            ));
+        });
+        for input in [
+            "<a href=\"▮",
+            "<button hx-target=\"▮",
+            "<button hx-get=\"{% url 'a' %}?q=▮",
+        ] {
+            with_syntax_context(input, |_, context| {
+                assert_eq!(
+                    context,
+                    CompletionOffsetContext::Template(TemplateCompletionContext::Text),
+                    "{input}"
+                );
+            });
+        }
+    }
+
     #[test]
     fn quoted_pipe_stays_template_text_syntax_context() {
         with_syntax_context("{{ value:'a|b'▮", |_, context| {
@@ -1,16 +1,37 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use djls_conf::ConfigFileCheck;
+use djls_conf::DuplicatePolicy;
+use djls_project::TemplateName;
+use djls_project::TemplateResolutionResult;
+use djls_project::python_template_references;
+use djls_project::template_resolution;
+use djls_semantic::ValidationError;
 use djls_semantic::collect_template_diagnostics;
+use djls_semantic::exceeds_template_limits;
+use djls_semantic::is_foreign_dialect;
 use djls_source::File;
 use djls_source::FileKind;
+use djls_source::LineIndex;
+use djls_source::Span;
+use djls_templates::TemplateErrorAccumulator;
+use djls_templates::parse_template;
 use tower_lsp_server::ls_types;
 
+use crate::ext::DIAGNOSTIC_SOURCE;
 use crate::ext::DiagnosticExt;
+use crate::ext::SpanExt;
+use crate::ext::Utf8PathExt;
 
-/// Collect all LSP diagnostics for a template file.
+/// Collect all LSP diagnostics for a template or Python file.
 ///
 /// Returns `None` when `file` is not a diagnostics target. For template files,
 /// triggers parsing and validation via Salsa-tracked queries (cached across
 /// calls), then converts the accumulated errors to LSP types. Diagnostics are
-/// filtered and severity-adjusted per `diagnostics_config`.
+/// filtered and severity-adjusted per `diagnostics_config`, and repeated
+/// unknown tags and filters are reported per its [`DuplicatePolicy`]. Python
+/// files are checked only for template names that do not exist.
 #[must_use]
 pub fn collect_diagnostics(
     db: &dyn djls_semantic::Db,
@@ -19,16 +40,19 @@ pub fn collect_diagnostics(
     let Ok(source) = file.try_source(db) else {
         return None;
     };
-    if *source.kind() != FileKind::Template {
-        return None;
+    match source.kind() {
+        FileKind::Template => {}
+        FileKind::Python => return Some(collect_python_diagnostics(db, file)),
+        FileKind::Other => return None,
     }
 
     let mut diagnostics = Vec::new();
 
-    let config = db.diagnostics_config();
+    let config = db.diagnostics_config(file);
 
     let collected = collect_template_diagnostics(db, file);
     let line_index = file.line_index(db);
+    let uri = file.path(db).to_lsp_uri();
 
     for error in collected.template_errors {
         if let Some(diagnostic) = error.to_lsp_diagnostic(line_index, &config) {
@@ -36,19 +60,156 @@ pub fn collect_diagnostics(
         }
     }
 
+    // Index into `diagnostics` of the first report of each unknown symbol.
+    let mut first_reports = HashMap::new();
     for error in collected.validation_errors {
-        if let Some(diagnostic) = error.to_lsp_diagnostic(line_index, &config) {
-            diagnostics.push(diagnostic);
+        let Some(mut diagnostic) = error.to_lsp_diagnostic(line_index, &config) else {
+            continue;
+        };
+        diagnostic.related_information = uri
+            .as_ref()
+            .and_then(|uri| error.to_lsp_related_information(uri, line_index));
+
+        if let Some(key) = duplicate_key(&error) {
+            match first_reports.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(diagnostics.len());
+                }
+                Entry::Occupied(entry) => match config.duplicates() {
+                    DuplicatePolicy::Report => {}
+                    DuplicatePolicy::Hint => {
+                        diagnostic.severity = Some(ls_types::DiagnosticSeverity::HINT);
+                    }
+                    DuplicatePolicy::Fold => {
+                        if let Some(uri) = &uri
+                            && let Some(first) = diagnostics.get_mut(*entry.get())
+                        {
+                            let related = first.related_information.get_or_insert_default();
+                            related.push(ls_types::DiagnosticRelatedInformation {
+                                location: ls_types::Location::new(uri.clone(), diagnostic.range),
+                                message: "also used here".to_string(),
+                            });
+                            continue;
+                        }
+                        // Without a URI there is nothing to fold into, so fall back to a hint.
+                        diagnostic.severity = Some(ls_types::DiagnosticSeverity::HINT);
+                    }
+                },
+            }
         }
+
+        diagnostics.push(diagnostic);
     }
 
     Some(diagnostics)
 }
 
+/// Report the template names a Python file passes to Django that no template
+/// directory or app contains.
+///
+/// A name is reported only after an exhaustive search, so an incomplete
+/// inventory reports nothing.
+fn collect_python_diagnostics(db: &dyn djls_semantic::Db, file: File) -> Vec<ls_types::Diagnostic> {
+    let Some(project) = db.project() else {
+        return Vec::new();
+    };
+    let references = python_template_references(db, file);
+    if references.is_empty() {
+        return Vec::new();
+    }
+
+    let config = db.diagnostics_config(file);
+    let line_index = file.line_index(db);
+    let resolution = template_resolution(db, project);
+
+    references
+        .iter()
+        .filter(|reference| {
+            let name = TemplateName::new(db, reference.name().to_string());
+            matches!(resolution.resolve(db, name), TemplateResolutionResult::DoesNotExist(_))
+        })
+        .filter_map(|reference| {
+            ValidationError::TemplateNotFound {
+                name: reference.name().to_string(),
+                span: reference.span(),
+            }
+            .to_lsp_diagnostic(line_index, &config)
+        })
+        .collect()
+}
+
+/// The symbol a diagnostic is grouped by under [`DuplicatePolicy`], for
+/// unknown tags and filters.
+fn duplicate_key(error: &ValidationError) -> Option<(&'static str, String)> {
+    if let ValidationError::UnknownTag { tag, .. } = error {
+        return Some((error.code(), tag.clone()));
+    }
+    if let ValidationError::UnknownFilter { filter, .. } = error {
+        return Some((error.code(), filter.clone()));
+    }
+    None
+}
+
+/// Collect only the parser diagnostics for a template file.
+///
+/// Unlike [`collect_diagnostics`], this never consults project discovery, so
+/// it stays safe to run while the project inventory is unavailable. Files the
+/// project's `[dialects]` settings assign to another template language, and
+/// templates over their configured limits, have no diagnostics.
+#[must_use]
+pub fn collect_syntax_diagnostics(
+    db: &dyn djls_semantic::Db,
+    file: File,
+) -> Option<Vec<ls_types::Diagnostic>> {
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    if is_foreign_dialect(db, file) || exceeds_template_limits(db, file) {
+        return Some(Vec::new());
+    }
+
+    let config = db.diagnostics_config(file);
+    let line_index = file.line_index(db);
+
+    Some(
+        parse_template::accumulated::<TemplateErrorAccumulator>(db, file)
+            .iter()
+            .filter_map(|accumulator| accumulator.0.to_lsp_diagnostic(line_index, &config))
+            .collect(),
+    )
+}
+
+/// Convert the problems found in a configuration file to diagnostics on that file.
+///
+/// A configuration mistake keeps the project from loading or silently does nothing, so every
+/// problem is an error regardless of the `[diagnostics]` settings.
+#[must_use]
+pub fn config_file_diagnostics(check: &ConfigFileCheck) -> Vec<ls_types::Diagnostic> {
+    let line_index = LineIndex::from(check.content());
+    check
+        .problems()
+        .iter()
+        .map(|problem| {
+            let span = problem.span();
+            let start = u32::try_from(span.start).unwrap_or(u32::MAX);
+            let end = u32::try_from(span.end).unwrap_or(u32::MAX);
+            ls_types::Diagnostic {
+                range: Span::new(start, end.saturating_sub(start)).to_lsp_range(&line_index),
+                severity: Some(ls_types::DiagnosticSeverity::ERROR),
+                source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                message: problem.message().to_string(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use djls_conf::DiagnosticSeverity;
-    use djls_source::LineIndex;
     use djls_templates::ParseError;
     use djls_templates::TemplateError;
 
@@ -76,6 +237,32 @@ mod tests {
         );
         assert_eq!(diagnostic.range.start, ls_types::Position::new(0, 6));
         assert_eq!(diagnostic.range.end, ls_types::Position::new(0, 8));
+        assert_eq!(
+            diagnostic
+                .code_description
+                .as_ref()
+                .map(|description| description.href.as_str()),
+            Some("https://djls.joshthomas.dev/diagnostics/#t100")
+        );
+    }
+
+    #[test]
+    fn config_file_diagnostics_are_errors_on_the_offending_key() {
+        let check = ConfigFileCheck::new(
+            "/project/djls.toml".into(),
+            "debug = true\n\n[format]\nenable = true\n".to_string(),
+        );
+
+        let diagnostics = config_file_diagnostics(&check);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unknown setting `format.enable`");
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(ls_types::DiagnosticSeverity::ERROR)
+        );
+        assert_eq!(diagnostics[0].range.start, ls_types::Position::new(3, 0));
+        assert_eq!(diagnostics[0].range.end, ls_types::Position::new(3, 6));
     }
 
     #[test]
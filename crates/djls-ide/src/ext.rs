@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
@@ -7,6 +8,7 @@ use djls_semantic::ValidationError;
 use djls_source::LineIndex;
 use djls_source::Offset;
 use djls_source::PositionEncoding;
+use djls_source::PositionMapper;
 use djls_source::Span;
 use djls_templates::TemplateError;
 use tower_lsp_server::ls_types;
@@ -63,26 +65,10 @@ impl OffsetExt for Offset {
         line_index: &LineIndex,
         encoding: PositionEncoding,
     ) -> ls_types::Position {
-        let Some(source_line) = line_index.line_at_offset(source, *self) else {
-            return self.to_lsp_position(line_index);
-        };
-        let byte_offset = source_line.byte_offset(*self);
-        let line_prefix = &source_line.text()[..byte_offset];
-        let character = match encoding {
-            PositionEncoding::Utf8 => u32::try_from(line_prefix.len()).unwrap_or(u32::MAX),
-            PositionEncoding::Utf16 => line_prefix
-                .chars()
-                .map(|character| u32::try_from(character.len_utf16()).unwrap_or_default())
-                .sum(),
-            PositionEncoding::Utf32 => {
-                u32::try_from(line_prefix.chars().count()).unwrap_or(u32::MAX)
-            }
-        };
-
-        ls_types::Position {
-            line: source_line.line(),
-            character,
-        }
+        let (line, character) = PositionMapper::new(source, line_index, encoding)
+            .line_col(*self)
+            .into();
+        ls_types::Position { line, character }
     }
 }
 
@@ -141,9 +127,11 @@ impl DefinitionTargetExt for DefinitionTarget {
                 let range = ls_types::Range::default();
                 return Some((uri, range, range));
             }
+            DefinitionTarget::Route(file, span) => (file, span, span),
             DefinitionTarget::Symbol(source) => {
                 (source.file(), source.definition_span(), source.name_span())
             }
+            DefinitionTarget::View(view) => (view.file(), view.definition_span(), view.name_span()),
         };
         let text = file.try_source(db).ok()?;
         let line_index = file.line_index(db);
@@ -227,7 +215,12 @@ impl CompletionCandidateExt for CompletionCandidate {
             ),
             insert_text_format: Some(self.edit.insert_format.to_lsp_insert_text_format()),
             filter_text: Some(self.label.clone()),
-            sort_text: Some(format!("{:02}_{}", self.kind.rank(), self.label)),
+            sort_text: Some(format!(
+                "{:02}_{:04}_{}",
+                self.kind.rank(),
+                self.match_score.min(9999),
+                self.label
+            )),
             ..Default::default()
         }
     }
@@ -244,12 +237,18 @@ impl CompletionCandidateKindExt for CompletionCandidateKind {
             | CompletionCandidateKind::EndTag
             | CompletionCandidateKind::TagArgumentLiteral => ls_types::CompletionItemKind::KEYWORD,
             CompletionCandidateKind::TagArgumentChoice => ls_types::CompletionItemKind::ENUM_MEMBER,
-            CompletionCandidateKind::TagArgumentPlaceholder => {
-                ls_types::CompletionItemKind::VARIABLE
-            }
+            CompletionCandidateKind::TagArgumentPlaceholder
+            | CompletionCandidateKind::BoundVariable
+            | CompletionCandidateKind::Variable
+            | CompletionCandidateKind::ContextVariable => ls_types::CompletionItemKind::VARIABLE,
             CompletionCandidateKind::TagArgumentSnippet => ls_types::CompletionItemKind::SNIPPET,
+            CompletionCandidateKind::Setting => ls_types::CompletionItemKind::CONSTANT,
+            CompletionCandidateKind::ForloopAttribute
+            | CompletionCandidateKind::HtmlAttribute => ls_types::CompletionItemKind::PROPERTY,
             CompletionCandidateKind::TemplateName => ls_types::CompletionItemKind::FILE,
+            CompletionCandidateKind::TemplateDirectory => ls_types::CompletionItemKind::FOLDER,
             CompletionCandidateKind::LibraryName => ls_types::CompletionItemKind::MODULE,
+            CompletionCandidateKind::UrlName => ls_types::CompletionItemKind::REFERENCE,
             CompletionCandidateKind::LoadSymbol | CompletionCandidateKind::Filter => {
                 ls_types::CompletionItemKind::FUNCTION
             }
@@ -363,7 +362,7 @@ impl DiagnosticSeverityExt for DiagnosticSeverity {
     }
 }
 
-const DIAGNOSTIC_SOURCE: &str = "djls";
+pub(crate) const DIAGNOSTIC_SOURCE: &str = "djls";
 
 pub(crate) trait DiagnosticExt: std::fmt::Display {
     fn diagnostic_span(&self) -> Option<(u32, u32)>;
@@ -373,6 +372,31 @@ pub(crate) trait DiagnosticExt: std::fmt::Display {
         self.to_string()
     }
 
+    /// Secondary `(span, message)` locations reported as related information.
+    fn diagnostic_related_spans(&self) -> Vec<(Span, String)> {
+        Vec::new()
+    }
+
+    fn to_lsp_related_information(
+        &self,
+        uri: &ls_types::Uri,
+        line_index: &LineIndex,
+    ) -> Option<Vec<ls_types::DiagnosticRelatedInformation>> {
+        let related = self.diagnostic_related_spans();
+        if related.is_empty() {
+            return None;
+        }
+        Some(
+            related
+                .into_iter()
+                .map(|(span, message)| ls_types::DiagnosticRelatedInformation {
+                    location: ls_types::Location::new(uri.clone(), span.to_lsp_range(line_index)),
+                    message,
+                })
+                .collect(),
+        )
+    }
+
     fn to_lsp_diagnostic(
         &self,
         line_index: &LineIndex,
@@ -389,7 +413,9 @@ pub(crate) trait DiagnosticExt: std::fmt::Display {
             range,
             severity: Some(severity),
             code: Some(ls_types::NumberOrString::String(code.to_string())),
-            code_description: None,
+            code_description: djls_conf::diagnostic_code(code)
+                .and_then(|entry| ls_types::Uri::from_str(&entry.href()).ok())
+                .map(|href| ls_types::CodeDescription { href }),
             source: Some(DIAGNOSTIC_SOURCE.to_string()),
             message: self.diagnostic_message(),
             related_information: None,
@@ -419,6 +445,10 @@ impl DiagnosticExt for ValidationError {
     fn diagnostic_code(&self) -> &'static str {
         self.code()
     }
+
+    fn diagnostic_related_spans(&self) -> Vec<(Span, String)> {
+        self.related_spans()
+    }
 }
 
 #[cfg(test)]
@@ -439,6 +469,7 @@ mod tests {
             },
             detail: Some("Django template library (django.templatetags.static)".to_string()),
             documentation: Some("Loads static files.".to_string()),
+            match_score: 0,
         };
 
         let item = candidate.to_lsp_completion_item(source, &line_index, PositionEncoding::Utf16);
@@ -450,7 +481,7 @@ mod tests {
 
         assert_eq!(item.label, "static");
         assert_eq!(item.kind, Some(ls_types::CompletionItemKind::MODULE));
-        assert_eq!(item.sort_text.as_deref(), Some("01_static"));
+        assert_eq!(item.sort_text.as_deref(), Some("01_0000_static"));
         assert_eq!(
             item.detail.as_deref(),
             Some("Django template library (django.templatetags.static)")
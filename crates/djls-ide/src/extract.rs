@@ -0,0 +1,156 @@
+use djls_project::TemplateName;
+use djls_project::template_resolution;
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::selection_variables;
+use djls_source::File;
+use djls_source::FileKind;
+use djls_source::PositionEncoding;
+use djls_source::Span;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+use crate::ext::Utf8PathExt;
+
+/// Offer to move the selected source into a new partial next to `file`,
+/// replacing it with an `{% include %}` that passes the variables the
+/// selection reads through `with`.
+///
+/// The partial is named after `file`, as `card_partial.html` for `card.html`,
+/// with a numeric suffix when that name is taken. Nothing is offered for an
+/// empty selection, one that cuts through a tag or block, or a file outside
+/// the project's template directories, whose include name is unknown.
+#[must_use]
+pub fn extract_include_action(
+    db: &dyn SemanticDb,
+    file: File,
+    range: Span,
+    encoding: PositionEncoding,
+) -> Option<ls_types::CodeActionOrCommand> {
+    if range.length() == 0 {
+        return None;
+    }
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    let selected = source.as_str().get(range.start_usize()..range.end_usize())?;
+    if selected.trim().is_empty() {
+        return None;
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+    let variables = selection_variables(db, file, nodelist, range)?;
+    let (template_name, file_name) = partial_name(db, file)?;
+
+    let uri = file.path(db).to_lsp_uri()?;
+    let partial_uri = file.path(db).with_file_name(&file_name).to_lsp_uri()?;
+    let replacement = ls_types::TextEdit::new(
+        range.to_lsp_range_with_encoding(source.as_str(), file.line_index(db), encoding),
+        include_tag(&template_name, &variables),
+    );
+    let contents = ls_types::TextEdit::new(
+        ls_types::Range::default(),
+        format!("{}\n", selected.trim_end()),
+    );
+
+    let operations = vec![
+        ls_types::DocumentChangeOperation::Op(ls_types::ResourceOp::Create(
+            ls_types::CreateFile {
+                uri: partial_uri.clone(),
+                options: Some(ls_types::CreateFileOptions {
+                    overwrite: Some(false),
+                    ignore_if_exists: Some(false),
+                }),
+                annotation_id: None,
+            },
+        )),
+        text_document_edit(partial_uri, contents),
+        text_document_edit(uri, replacement),
+    ];
+
+    Some(ls_types::CodeActionOrCommand::CodeAction(ls_types::CodeAction {
+        title: format!("Extract selection into `{{% include \"{template_name}\" %}}`"),
+        kind: Some(ls_types::CodeActionKind::REFACTOR_EXTRACT),
+        diagnostics: None,
+        edit: Some(ls_types::WorkspaceEdit {
+            changes: None,
+            document_changes: Some(ls_types::DocumentChanges::Operations(operations)),
+            change_annotations: None,
+        }),
+        command: None,
+        is_preferred: None,
+        disabled: None,
+        data: None,
+    }))
+}
+
+/// The template name and file name of an unused partial beside `file`.
+fn partial_name(db: &dyn SemanticDb, file: File) -> Option<(String, String)> {
+    let project = db.project()?;
+    let resolution = template_resolution(db, project);
+    let current = resolution.template_names_for_file(db, file).first()?.name(db);
+
+    let (directory, base) = current.rsplit_once('/').unwrap_or(("", current.as_str()));
+    let (stem, extension) = base.rsplit_once('.').unwrap_or((base, "html"));
+
+    (1..100).find_map(|attempt| {
+        let file_name = if attempt == 1 {
+            format!("{stem}_partial.{extension}")
+        } else {
+            format!("{stem}_partial_{attempt}.{extension}")
+        };
+        let template_name = if directory.is_empty() {
+            file_name.clone()
+        } else {
+            format!("{directory}/{file_name}")
+        };
+        let taken = !resolution
+            .origins_for_name(db, TemplateName::new(db, template_name.clone()))
+            .is_empty();
+        (!taken).then_some((template_name, file_name))
+    })
+}
+
+fn include_tag(template_name: &str, variables: &[String]) -> String {
+    if variables.is_empty() {
+        return format!("{{% include \"{template_name}\" %}}");
+    }
+    let bindings = variables
+        .iter()
+        .map(|name| format!("{name}={name}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{{% include \"{template_name}\" with {bindings} %}}")
+}
+
+fn text_document_edit(
+    uri: ls_types::Uri,
+    edit: ls_types::TextEdit,
+) -> ls_types::DocumentChangeOperation {
+    ls_types::DocumentChangeOperation::Edit(ls_types::TextDocumentEdit {
+        text_document: ls_types::OptionalVersionedTextDocumentIdentifier { uri, version: None },
+        edits: vec![ls_types::OneOf::Left(edit)],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn include_tag_passes_each_variable_under_its_own_name() {
+        assert_eq!(
+            include_tag("pages/card_partial.html", &["item".to_string(), "user".to_string()]),
+            "{% include \"pages/card_partial.html\" with item=item user=user %}"
+        );
+        assert_eq!(
+            include_tag("card_partial.html", &[]),
+            "{% include \"card_partial.html\" %}"
+        );
+    }
+}
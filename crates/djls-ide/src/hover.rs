@@ -1,8 +1,9 @@
 use djls_project::EffectiveDefinitionLibrary;
 use djls_project::LibraryName;
 use djls_project::LoadableLibraryLookup;
-use djls_project::PythonModuleName;
+use djls_project::Project;
 use djls_project::ScopedTemplateLibraries;
+use djls_project::SymbolKey;
 use djls_project::TemplateLibrary;
 use djls_project::TemplateName;
 use djls_project::TemplateResolutionResult;
@@ -10,17 +11,28 @@ use djls_project::TemplateSymbol;
 use djls_project::TemplateSymbolAvailability;
 use djls_project::TemplateSymbolCandidate;
 use djls_project::TemplateSymbolKind;
+use djls_project::declared_settings;
+use djls_project::template_library_filter_signatures;
 use djls_project::template_resolution;
+use djls_semantic::AdminOverride;
+use djls_semantic::BlockSite;
 use djls_semantic::Db as SemanticDb;
 use djls_semantic::SemanticOffsetContext;
 use djls_semantic::TemplateReferenceKind;
+use djls_semantic::admin_override_for_file;
+use djls_semantic::ancestor_blocks;
+use djls_semantic::block_definition_at;
+use djls_semantic::block_overrides;
+use djls_semantic::library_filter_specs;
 use djls_semantic::resolve_reference_for_file;
 use djls_semantic::scoped_template_libraries_for_file;
 use djls_source::File;
 use djls_source::Offset;
+use djls_source::Span;
 use tower_lsp_server::ls_types;
 
 use crate::ext::SpanExt;
+use crate::ext::Utf8PathExt;
 
 pub fn hover(db: &dyn SemanticDb, file: File, offset: Offset) -> Option<ls_types::Hover> {
     let scoped_libraries = scoped_template_libraries_for_file(db, file);
@@ -52,7 +64,7 @@ pub fn hover(db: &dyn SemanticDb, file: File, offset: Offset) -> Option<ls_types
             library,
             span,
         } => Some((
-            render_library_symbol_hover(scoped_libraries, &name, &library, None)?,
+            render_library_symbol_hover(db, scoped_libraries, &name, &library, None)?,
             span,
         )),
         SemanticOffsetContext::Tag {
@@ -61,6 +73,7 @@ pub fn hover(db: &dyn SemanticDb, file: File, offset: Offset) -> Option<ls_types
             span,
         } => Some((
             render_effective_symbol_hover(
+                db,
                 scoped_libraries,
                 &name,
                 TemplateSymbolKind::Tag,
@@ -74,6 +87,7 @@ pub fn hover(db: &dyn SemanticDb, file: File, offset: Offset) -> Option<ls_types
             span,
         } => Some((
             render_effective_symbol_hover(
+                db,
                 scoped_libraries,
                 &name,
                 TemplateSymbolKind::Filter,
@@ -81,9 +95,13 @@ pub fn hover(db: &dyn SemanticDb, file: File, offset: Offset) -> Option<ls_types
             )?,
             span,
         )),
-        SemanticOffsetContext::TemplateBlock { .. }
-        | SemanticOffsetContext::Variable { .. }
-        | SemanticOffsetContext::None => None,
+        SemanticOffsetContext::TemplateBlock { name, span } => {
+            Some((render_template_block_hover(db, file, &name, span)?, span))
+        }
+        SemanticOffsetContext::Variable { name, span } => {
+            Some((render_setting_hover(db, &name)?, span))
+        }
+        SemanticOffsetContext::Route { .. } | SemanticOffsetContext::None => None,
     }?;
 
     Some(ls_types::Hover {
@@ -141,7 +159,106 @@ fn render_template_reference_hover(
     Some(sections.join("\n---\n"))
 }
 
+/// Describe `settings.NAME` when the settings module declares `NAME`. Only
+/// the value's type is shown, never the value itself.
+fn render_setting_hover(db: &dyn SemanticDb, variable: &str) -> Option<String> {
+    let name = variable.strip_prefix("settings.")?;
+    let project = db.project()?;
+    let setting = declared_settings(db, project).get(name)?;
+    let mut sections = vec![format!("```text\n(setting) {name}\n```")];
+    if !setting.types().is_empty() {
+        sections.push(format!("Type: `{}`", setting.types().join(" | ")));
+    }
+    Some(sections.join("\n---\n"))
+}
+
+/// List the templates along the `{% extends %}` chain that define block
+/// `name`, root first, then the descendants that override it.
+fn render_template_block_hover(
+    db: &dyn SemanticDb,
+    file: File,
+    name: &str,
+    name_span: Span,
+) -> Option<String> {
+    let local = block_definition_at(db, file, name_span)?;
+    let mut sections = vec![format!("```text\n(block) {name}\n```")];
+    let Some(project) = db.project() else {
+        return Some(sections.join("\n---\n"));
+    };
+    if let Some(admin) = admin_override_for_file(db, file) {
+        sections.push(render_admin_override(&admin, name));
+    }
+
+    let ancestors = ancestor_blocks(db, project, file, name);
+    let overrides = block_overrides(db, project, file, name);
+    if ancestors.is_empty() && overrides.is_empty() {
+        return Some(sections.join("\n---\n"));
+    }
+
+    let chain = ancestors
+        .iter()
+        .rev()
+        .map(|site| render_block_site(db, project, *site))
+        .chain(std::iter::once(format!(
+            "{} (this template)",
+            render_block_site(db, project, local)
+        )))
+        .enumerate()
+        .map(|(index, site)| format!("{}. {site}", index + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+    sections.push(format!("Defined in:\n\n{chain}"));
+
+    if !overrides.is_empty() {
+        let overrides = overrides
+            .iter()
+            .map(|site| format!("- {}", render_block_site(db, project, *site)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(format!("Overridden in:\n\n{overrides}"));
+    }
+
+    Some(sections.join("\n---\n"))
+}
+
+/// Which contrib admin template a block's template replaces, and whether that template has
+/// the block at all.
+fn render_admin_override(admin: &AdminOverride, name: &str) -> String {
+    let scope = match (admin.app(), admin.model()) {
+        (Some(app), Some(model)) => format!(" for model `{app}.{model}`"),
+        (Some(app), None) => format!(" for app `{app}`"),
+        (None, Some(_) | None) => String::new(),
+    };
+    let template = admin.template();
+    let block = if admin.defines_block(name) {
+        format!("`{template}` defines this block.")
+    } else {
+        format!("`{template}` does not define this block.")
+    };
+    format!("Overrides `{template}` from `django.contrib.admin`{scope}. {block}")
+}
+
+/// A link to a block definition, labelled with its template name and line.
+fn render_block_site(db: &dyn SemanticDb, project: Project, site: BlockSite) -> String {
+    let path = site.file.path(db);
+    let label = template_resolution(db, project)
+        .template_names_for_file(db, site.file)
+        .first()
+        .map_or_else(|| path.to_string(), |name| name.name(db).to_string());
+    let line = site
+        .file
+        .line_index(db)
+        .to_line_col(site.name_span.start_offset())
+        .line()
+        + 1;
+    match path.to_lsp_uri() {
+        Some(uri) => format!("[`{label}`:{line}]({}#L{line})", uri.as_str()),
+        None => format!("`{label}`:{line}"),
+    }
+}
+
 fn render_effective_symbol_hover(
+    db: &dyn SemanticDb,
     scoped_libraries: ScopedTemplateLibraries<'_>,
     name: &str,
     kind: TemplateSymbolKind,
@@ -175,7 +292,11 @@ fn render_effective_symbol_hover(
     }
 
     // The same definition may be exposed as a builtin in one backend and through a loaded
-    // library in another. Select presentation metadata independently of that exposure.
+    // library in another. Select presentation metadata independently of that exposure, and
+    // name a `{% load %}` requirement only when every backend has one.
+    let requires_load = candidates
+        .iter()
+        .all(|(library, _)| library.load_name().is_some());
     let (library, symbol) = candidates.into_iter().max_by_key(|(library, symbol)| {
         (
             symbol
@@ -186,10 +307,17 @@ fn render_effective_symbol_hover(
             library.load_name().map(LibraryName::as_str),
         )
     })?;
-    render_symbol_hover(symbol, library.module_name(), None)
+    let availability = library
+        .load_name()
+        .filter(|_| requires_load)
+        .map(|load_name| TemplateSymbolAvailability::RequiresLoad {
+            load_name: load_name.clone(),
+        });
+    render_symbol_hover(db, symbol, library, availability)
 }
 
 fn render_library_symbol_hover(
+    db: &dyn SemanticDb,
     scoped_libraries: ScopedTemplateLibraries<'_>,
     name: &str,
     library: &str,
@@ -199,10 +327,11 @@ fn render_library_symbol_hover(
     else {
         return None;
     };
-    render_symbol_from_library(library, name, kind)
+    render_symbol_from_library(db, library, name, kind)
 }
 
 fn render_symbol_from_library(
+    db: &dyn SemanticDb,
     library: &TemplateLibrary,
     name: &str,
     kind: Option<TemplateSymbolKind>,
@@ -223,28 +352,84 @@ fn render_symbol_from_library(
             .map(|load_name| TemplateSymbolAvailability::RequiresLoad {
                 load_name: load_name.clone(),
             });
-    render_symbol_hover(symbol, library.module_name(), availability)
+    render_symbol_hover(db, symbol, library, availability)
 }
 
 fn render_symbol_hover(
+    db: &dyn SemanticDb,
     symbol: &TemplateSymbol,
-    module_name: &PythonModuleName,
+    library: &TemplateLibrary,
     availability: Option<TemplateSymbolAvailability>,
 ) -> Option<String> {
+    let module_name = library.module_name();
     let candidates = [TemplateSymbolCandidate {
         symbol: symbol.clone(),
         availability: availability.unwrap_or_else(|| TemplateSymbolAvailability::Builtin {
             module: module_name.clone(),
         }),
     }];
-    let mut markdown = render_template_symbol_hover(&candidates)?;
+    let filter = match symbol.kind {
+        TemplateSymbolKind::Filter => filter_details(db, library, symbol.name()),
+        TemplateSymbolKind::Tag => None,
+    };
+    let mut markdown = render_template_symbol_hover(&candidates, filter.as_ref())?;
     markdown.push_str("\n---\nDefined in `");
     markdown.push_str(module_name.as_str());
     markdown.push_str("`.");
     Some(markdown)
 }
 
-fn render_template_symbol_hover(candidates: &[TemplateSymbolCandidate]) -> Option<String> {
+/// The signature line and decorator notes shown in a filter's hover.
+struct FilterDetails {
+    signature: String,
+    notes: Vec<&'static str>,
+}
+
+/// Filter details from the library's extracted signature, or from its arity alone when the
+/// library has no source to read parameter names from.
+fn filter_details(
+    db: &dyn SemanticDb,
+    library: &TemplateLibrary,
+    name: &str,
+) -> Option<FilterDetails> {
+    let key = SymbolKey::filter(library.module_name_str(), name);
+    if let Some(signature) = template_library_filter_signatures(db, library.id()).get(&key) {
+        let mut params = vec![signature.value_param.clone()];
+        if let Some(arg) = &signature.arg_param {
+            params.push(match &signature.arg_default {
+                Some(default) => format!("{arg}={default}"),
+                None => arg.clone(),
+            });
+        }
+        let mut notes = Vec::new();
+        if signature.is_safe {
+            notes.push("Registered with `is_safe=True`: safe input stays safe after filtering.");
+        }
+        if signature.stringfilter {
+            notes.push("Decorated with `@stringfilter`: the value is converted to a string first.");
+        }
+        return Some(FilterDetails {
+            signature: format!("{name}({})", params.join(", ")),
+            notes,
+        });
+    }
+
+    let arity = library_filter_specs(db, library.id()).get(name)?;
+    let signature = match (arity.expects_arg, arity.arg_optional) {
+        (false, _) => format!("{name}(value)"),
+        (true, false) => format!("{name}(value, arg)"),
+        (true, true) => format!("{name}(value, arg=...)"),
+    };
+    Some(FilterDetails {
+        signature,
+        notes: Vec::new(),
+    })
+}
+
+fn render_template_symbol_hover(
+    candidates: &[TemplateSymbolCandidate],
+    filter: Option<&FilterDetails>,
+) -> Option<String> {
     let candidate = candidates
         .iter()
         .find(|candidate| {
@@ -255,7 +440,7 @@ fn render_template_symbol_hover(candidates: &[TemplateSymbolCandidate]) -> Optio
         })
         .or_else(|| candidates.first())?;
 
-    let name = candidate.symbol.name();
+    let name = filter.map_or(candidate.symbol.name(), |filter| filter.signature.as_str());
     let kind = match candidate.symbol.kind {
         TemplateSymbolKind::Tag => "tag",
         TemplateSymbolKind::Filter => "filter",
@@ -270,6 +455,9 @@ fn render_template_symbol_hover(candidates: &[TemplateSymbolCandidate]) -> Optio
     {
         sections.push(doc);
     }
+    if let Some(details) = filter.filter(|details| !details.notes.is_empty()) {
+        sections.push(details.notes.join("\n\n"));
+    }
 
     sections.extend(
         candidates
@@ -431,7 +619,7 @@ mod tests {
             .expect("tag hover candidate fixture should be valid"),
         ];
 
-        let markdown = render_template_symbol_hover(&candidates);
+        let markdown = render_template_symbol_hover(&candidates, None);
 
         assert_eq!(
             markdown.as_deref(),
@@ -451,7 +639,7 @@ mod tests {
             .expect("filter hover candidate fixture should be valid"),
         ];
 
-        let markdown = render_template_symbol_hover(&candidates);
+        let markdown = render_template_symbol_hover(&candidates, None);
 
         assert_eq!(
             markdown.as_deref(),
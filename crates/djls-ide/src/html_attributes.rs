@@ -0,0 +1,110 @@
+//! Attribute sets from front-end libraries common in Django projects.
+//!
+//! These are offered when completing an attribute name inside an HTML start
+//! tag, behind the `[completions]` `htmx` and `alpine` settings. With `htmx`,
+//! the values of [`URL_ATTRIBUTES`] complete to `{% url %}` tags for the
+//! project's named URL patterns.
+
+use djls_conf::CompletionsConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HtmlAttribute {
+    pub(crate) name: &'static str,
+    /// Whether the attribute is written with a value, so completion inserts
+    /// `name=""`.
+    pub(crate) takes_value: bool,
+    pub(crate) library: &'static str,
+    pub(crate) documentation: &'static str,
+}
+
+const fn htmx(name: &'static str, documentation: &'static str) -> HtmlAttribute {
+    HtmlAttribute {
+        name,
+        takes_value: true,
+        library: "htmx",
+        documentation,
+    }
+}
+
+const fn alpine(
+    name: &'static str,
+    takes_value: bool,
+    documentation: &'static str,
+) -> HtmlAttribute {
+    HtmlAttribute {
+        name,
+        takes_value,
+        library: "Alpine.js",
+        documentation,
+    }
+}
+
+const HTMX_ATTRIBUTES: &[HtmlAttribute] = &[
+    htmx("hx-boost", "Turn links and forms inside the element into AJAX requests."),
+    htmx("hx-confirm", "Ask the user to confirm before issuing the request."),
+    htmx("hx-delete", "Issue a `DELETE` request to the given URL."),
+    htmx("hx-disable", "Disable htmx processing for the element and its children."),
+    htmx("hx-disabled-elt", "Add the `disabled` attribute to these elements during a request."),
+    htmx("hx-disinherit", "Stop children from inheriting the listed attributes."),
+    htmx("hx-encoding", "Change the request encoding, e.g. to `multipart/form-data`."),
+    htmx("hx-ext", "Enable htmx extensions for the element and its children."),
+    htmx("hx-get", "Issue a `GET` request to the given URL."),
+    htmx("hx-headers", "Add JSON-encoded headers to the request."),
+    htmx("hx-history", "Prevent sensitive data from being saved to the history cache."),
+    htmx("hx-history-elt", "Use this element as the history snapshot target."),
+    htmx("hx-include", "Include additional element values in the request."),
+    htmx("hx-indicator", "The element that gets the `htmx-request` class during a request."),
+    htmx("hx-inherit", "Allow children to inherit the listed attributes."),
+    htmx("hx-on", "Handle an event with inline script, written `hx-on:event`."),
+    htmx("hx-params", "Filter the parameters submitted with the request."),
+    htmx("hx-patch", "Issue a `PATCH` request to the given URL."),
+    htmx("hx-post", "Issue a `POST` request to the given URL."),
+    htmx("hx-preserve", "Keep the element unchanged between requests."),
+    htmx("hx-prompt", "Show a prompt and send the answer in the `HX-Prompt` header."),
+    htmx("hx-push-url", "Push a URL into the browser location history."),
+    htmx("hx-put", "Issue a `PUT` request to the given URL."),
+    htmx("hx-replace-url", "Replace the URL in the browser location bar."),
+    htmx("hx-request", "Configure the timeout and credentials of the request."),
+    htmx("hx-select", "Select the part of the response to swap in."),
+    htmx("hx-select-oob", "Select parts of the response to swap in out of band."),
+    htmx("hx-swap", "How to swap the response into the target, e.g. `outerHTML`."),
+    htmx("hx-swap-oob", "Swap this element of the response in by its `id`."),
+    htmx("hx-sync", "Synchronize requests between elements."),
+    htmx("hx-target", "The element to swap the response into."),
+    htmx("hx-trigger", "The event that triggers the request."),
+    htmx("hx-validate", "Validate form inputs before the request is sent."),
+    htmx("hx-vals", "Add JSON-encoded values to the parameters of the request."),
+];
+
+const ALPINE_ATTRIBUTES: &[HtmlAttribute] = &[
+    alpine("x-bind", true, "Bind an attribute to an expression, written `x-bind:attr`."),
+    alpine("x-cloak", false, "Hide the element until Alpine has initialized it."),
+    alpine("x-data", true, "Declare a component and its reactive data."),
+    alpine("x-effect", true, "Run an expression whenever its dependencies change."),
+    alpine("x-for", true, "Repeat a `<template>` for each item of a list."),
+    alpine("x-html", true, "Set the element's inner HTML to the result of an expression."),
+    alpine("x-id", true, "Scope the IDs generated by `$id()` to this element."),
+    alpine("x-if", true, "Add or remove a `<template>`'s content based on an expression."),
+    alpine("x-ignore", false, "Skip Alpine initialization for the element and its children."),
+    alpine("x-init", true, "Run an expression when the element is initialized."),
+    alpine("x-model", true, "Bind an input's value to data, in both directions."),
+    alpine("x-modelable", true, "Expose a property as the target of an outer `x-model`."),
+    alpine("x-on", true, "Listen for an event, written `x-on:event`."),
+    alpine("x-ref", true, "Name the element so it can be reached through `$refs`."),
+    alpine("x-show", true, "Toggle the element's visibility based on an expression."),
+    alpine("x-teleport", true, "Move a `<template>`'s content to another part of the page."),
+    alpine("x-text", true, "Set the element's text content to the result of an expression."),
+    alpine("x-transition", false, "Animate the element when `x-show` toggles it."),
+];
+
+/// htmx attributes whose value is the URL a request goes to.
+pub(crate) const URL_ATTRIBUTES: &[&str] = &["hx-get", "hx-post"];
+
+/// The attributes the configuration enables, htmx first.
+pub(crate) fn enabled_html_attributes(
+    config: &CompletionsConfig,
+) -> impl Iterator<Item = &'static HtmlAttribute> {
+    let htmx = if config.htmx() { HTMX_ATTRIBUTES } else { &[] };
+    let alpine = if config.alpine() { ALPINE_ATTRIBUTES } else { &[] };
+    htmx.iter().chain(alpine)
+}
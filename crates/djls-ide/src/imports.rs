@@ -1,7 +1,11 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
 use djls_source::LineEnding;
 use djls_source::Offset;
 use djls_source::Span;
 use djls_templates::Node;
+use djls_templates::TagBit;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct LeadingImports {
@@ -77,6 +81,108 @@ pub(crate) fn fold_spans(nodes: &[Node], source: &str) -> Vec<Span> {
     spans
 }
 
+/// Replacements that gather every `{% load %}` into sorted tags at the top of
+/// the template, just below `{% extends %}` when there is one, or `None` when
+/// the loads are already laid out that way.
+///
+/// Libraries loaded whole come first, merged into one tag when `merge` is set
+/// and one tag per library otherwise. Selective loads follow, one tag per
+/// library with their names merged and sorted, and are dropped when the same
+/// library is also loaded whole. Moving a load earlier never changes what is
+/// available after its old position, so the edits are safe anywhere.
+pub(crate) fn organized_load_edits(
+    nodes: &[Node],
+    source: &str,
+    merge: bool,
+) -> Option<Vec<(Span, String)>> {
+    let mut libraries = BTreeSet::new();
+    let mut selective: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut removals = Vec::new();
+    for node in nodes {
+        let Node::Tag { name, bits, .. } = node else {
+            continue;
+        };
+        if name != "load" || bits.is_empty() {
+            continue;
+        }
+        match bits.as_slice() {
+            [symbols @ .., from, library] if from.as_str() == "from" && !symbols.is_empty() => {
+                selective
+                    .entry(library.as_str())
+                    .or_default()
+                    .extend(symbols.iter().map(TagBit::as_str));
+            }
+            _ => libraries.extend(bits.iter().map(TagBit::as_str)),
+        }
+        removals.push(line_removal(source, node.full_span()));
+    }
+    if removals.is_empty() {
+        return None;
+    }
+    selective.retain(|library, _| !libraries.contains(library));
+
+    let mut tags = Vec::new();
+    if !merge {
+        tags.extend(libraries.iter().map(|library| format!("{{% load {library} %}}")));
+    } else if !libraries.is_empty() {
+        tags.push(format!(
+            "{{% load {} %}}",
+            libraries.iter().copied().collect::<Vec<_>>().join(" ")
+        ));
+    }
+    tags.extend(selective.iter().map(|(library, symbols)| {
+        let symbols = symbols.iter().copied().collect::<Vec<_>>().join(" ");
+        format!("{{% load {symbols} from {library} %}}")
+    }));
+
+    let line_ending = LineEnding::last_in(source).unwrap_or_default().as_str();
+    let mut block = tags.join(line_ending);
+    block.push_str(line_ending);
+
+    let target = nodes
+        .iter()
+        .find(|node| matches!(node, Node::Tag { name, .. } if name == "extends"))
+        .map_or(0, |extends| offset_after_line(source, extends.full_span().end_usize()).get());
+    if target as usize == source.len() && !source.is_empty() && !source.ends_with(['\n', '\r']) {
+        block.insert_str(0, line_ending);
+    }
+
+    let contiguous = removals
+        .iter()
+        .try_fold(target, |end, removal| (removal.start() == end).then_some(removal.end()));
+    if let Some(end) = contiguous
+        && source.get(target as usize..end as usize) == Some(block.as_str())
+    {
+        return None;
+    }
+
+    let mut edits = Vec::with_capacity(removals.len() + 1);
+    if removals.first().is_none_or(|first| first.start() != target) {
+        edits.push((Span::new(target, 0), String::new()));
+    }
+    edits.extend(removals.into_iter().map(|removal| (removal, String::new())));
+    if let Some((_, text)) = edits.first_mut() {
+        *text = block;
+    }
+    Some(edits)
+}
+
+/// The span of a tag together with its line when nothing else shares it.
+fn line_removal(source: &str, span: Span) -> Span {
+    let start = span.start_usize();
+    let end = span.end_usize();
+    let line_start = source[..start].rfind(['\n', '\r']).map_or(0, |index| index + 1);
+    let line_end = source[end..]
+        .find(['\n', '\r'])
+        .map_or(source.len(), |index| end + index);
+    if source[line_start..start].trim().is_empty() && source[end..line_end].trim().is_empty() {
+        let removal_end = offset_after_line(source, end).get() as usize;
+        Span::saturating_from_bounds_usize(line_start, removal_end)
+    } else {
+        span
+    }
+}
+
 fn offset_after_line(source: &str, offset: usize) -> Offset {
     let bytes = source.as_bytes();
     let mut offset = offset.min(source.len());
@@ -1,27 +1,48 @@
 mod code_actions;
+mod colors;
 mod completions;
 mod context;
 mod diagnostics;
 mod ext;
+mod extract;
 mod folding;
 mod formatting;
 mod hover;
+mod html_attributes;
 mod imports;
+mod linked_editing;
 mod links;
+mod matching;
 mod navigation;
+mod on_type;
+mod organize_loads;
+mod python_context;
+mod rename;
 mod snippets;
 mod symbols;
+mod template_paths;
 mod warmup;
 
 pub use code_actions::code_actions;
+pub use colors::color_presentations;
+pub use colors::document_colors;
 pub use completions::completion;
 pub use diagnostics::collect_diagnostics;
+pub use diagnostics::collect_syntax_diagnostics;
+pub use diagnostics::config_file_diagnostics;
+pub use extract::extract_include_action;
 pub use folding::collect_folding_ranges;
 pub use formatting::format_document;
 pub use hover::hover;
+pub use linked_editing::linked_editing_ranges;
 pub use links::document_links;
 pub use navigation::find_references;
 pub use navigation::goto_definition;
+pub use on_type::on_type_formatting;
+pub use organize_loads::organize_loads_action;
+pub use rename::is_template_variable_name;
+pub use rename::prepare_rename;
+pub use rename::rename;
 pub use symbols::document_symbols;
 pub use warmup::CountLabel;
 pub use warmup::PrimedTemplateLibraries;
@@ -30,4 +51,5 @@ pub use warmup::WarmCachePhase;
 pub use warmup::WarmCacheProgress;
 pub use warmup::prepare_project_template_analysis;
 pub use warmup::prime_template_library_products;
+pub use warmup::template_is_affected;
 pub use warmup::warm_cache_phases;
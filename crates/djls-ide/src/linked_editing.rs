@@ -0,0 +1,41 @@
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::linked_block_names_at;
+use djls_source::File;
+use djls_source::FileKind;
+use djls_source::Offset;
+use djls_source::PositionEncoding;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+
+/// Link the name of a block opener with the name repeated in its closer, so
+/// renaming `{% block content %}` also renames `{% endblock content %}`.
+#[must_use]
+pub fn linked_editing_ranges(
+    db: &dyn SemanticDb,
+    file: File,
+    offset: Offset,
+    encoding: PositionEncoding,
+) -> Option<ls_types::LinkedEditingRanges> {
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+
+    let linked = linked_block_names_at(db, file, nodelist, offset)?;
+    let line_index = file.line_index(db);
+    Some(ls_types::LinkedEditingRanges {
+        ranges: [linked.opener, linked.closer]
+            .iter()
+            .map(|span| span.to_lsp_range_with_encoding(source.as_str(), line_index, encoding))
+            .collect(),
+        word_pattern: None,
+    })
+}
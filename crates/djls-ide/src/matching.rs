@@ -0,0 +1,117 @@
+//! Candidate matching for completion prefixes.
+//!
+//! Every completion path that filters names by the text before the cursor goes through
+//! [`CompletionMatcher`], so prefix and fuzzy matching rank tags, filters, and libraries the same
+//! way. Scores are lower-is-better and a plain prefix match always scores `0`, which keeps prefix
+//! matches ahead of scattered fuzzy matches of the same candidate kind.
+
+use djls_project::ScopedTemplateLibraries;
+use djls_project::TemplateSymbolKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompletionMatcher {
+    Prefix,
+    Fuzzy,
+}
+
+impl CompletionMatcher {
+    pub(crate) fn new(fuzzy: bool) -> Self {
+        if fuzzy { Self::Fuzzy } else { Self::Prefix }
+    }
+
+    /// Score `candidate` against the typed `pattern`, or `None` when it does not match.
+    pub(crate) fn score(self, pattern: &str, candidate: &str) -> Option<u32> {
+        if candidate.starts_with(pattern) {
+            return Some(0);
+        }
+        match self {
+            Self::Prefix => None,
+            Self::Fuzzy => fuzzy_score(pattern, candidate),
+        }
+    }
+
+    /// Indexed symbol names of `kind` matching `pattern`, paired with their scores.
+    ///
+    /// Prefix matching range-scans the catalog's prefix index. Fuzzy matching has no index to
+    /// narrow by, so it scores the whole inventory.
+    pub(crate) fn symbol_names<'db>(
+        self,
+        scoped_libraries: ScopedTemplateLibraries<'db>,
+        kind: TemplateSymbolKind,
+        pattern: &str,
+    ) -> Vec<(&'db str, u32)> {
+        match self {
+            Self::Prefix => scoped_libraries
+                .symbols_with_prefix(kind, pattern)
+                .map(|name| (name, 0))
+                .collect(),
+            Self::Fuzzy => scoped_libraries
+                .inventory_symbol_names(kind)
+                .filter_map(|name| Some((name, self.score(pattern, name)?)))
+                .collect(),
+        }
+    }
+}
+
+/// Score `pattern` as an ASCII case-insensitive subsequence of `candidate`.
+///
+/// Each matched character costs the number of candidate characters skipped to reach it, except
+/// that a jump to the start of an `_`-separated word costs `1`. The base score of `1` keeps every
+/// fuzzy match behind the prefix matches scored by [`CompletionMatcher::score`].
+fn fuzzy_score(pattern: &str, candidate: &str) -> Option<u32> {
+    let mut score = 1_u32;
+    let mut candidate_chars = candidate.chars();
+    let mut previous = None;
+
+    for wanted in pattern.chars() {
+        let mut skipped = 0_u32;
+        loop {
+            let ch = candidate_chars.next()?;
+            let starts_word = matches!(previous, None | Some('_'));
+            previous = Some(ch);
+            if ch.eq_ignore_ascii_case(&wanted) {
+                let cost = if skipped > 0 && starts_word {
+                    1
+                } else {
+                    skipped
+                };
+                score = score.saturating_add(cost);
+                break;
+            }
+            skipped = skipped.saturating_add(1);
+        }
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_matcher_only_accepts_prefixes() {
+        let matcher = CompletionMatcher::Prefix;
+        assert_eq!(matcher.score("block", "blocktranslate"), Some(0));
+        assert_eq!(matcher.score("", "blocktranslate"), Some(0));
+        assert_eq!(matcher.score("btrans", "blocktranslate"), None);
+    }
+
+    #[test]
+    fn fuzzy_matcher_accepts_subsequences_behind_prefixes() {
+        let matcher = CompletionMatcher::Fuzzy;
+        assert_eq!(matcher.score("block", "blocktranslate"), Some(0));
+        assert_eq!(matcher.score("btrans", "blocktranslate"), Some(5));
+        assert_eq!(matcher.score("BTRANS", "blocktranslate"), Some(5));
+        assert_eq!(matcher.score("bx", "blocktranslate"), None);
+        assert_eq!(matcher.score("transb", "blocktranslate"), None);
+    }
+
+    #[test]
+    fn fuzzy_matcher_prefers_word_starts_and_tight_runs() {
+        let matcher = CompletionMatcher::Fuzzy;
+        assert_eq!(matcher.score("dn", "default_if_none"), Some(2));
+        assert_eq!(matcher.score("dn", "random_value"), None);
+        assert!(matcher.score("dfn", "default_if_none") < matcher.score("dfn", "dateformatting"));
+    }
+}
@@ -3,8 +3,10 @@ use djls_project::TemplateName;
 use djls_project::TemplateResolutionResult;
 use djls_project::TemplateSymbolKind;
 use djls_project::TemplateSymbolSource;
+use djls_project::ViewDefinition;
 use djls_project::template_resolution;
 use djls_project::template_symbol_source;
+use djls_project::url_routes;
 use djls_semantic::BlockSite;
 use djls_semantic::SemanticOffsetContext;
 use djls_semantic::TemplateReferenceKind;
@@ -33,7 +35,10 @@ use crate::ext::Utf8PathExt;
 pub(crate) enum DefinitionTarget {
     Block(BlockSite),
     File(File),
+    /// A `path()` call in a URLconf, when its view could not be found.
+    Route(File, Span),
     Symbol(TemplateSymbolSource),
+    View(ViewDefinition),
 }
 
 fn encoded_range(
@@ -277,6 +282,24 @@ pub fn goto_definition(
                 position_encoding,
             )
         }
+        SemanticOffsetContext::Route { name, span } => {
+            let project = db.project()?;
+            // Every pattern sharing the name is a candidate; the one Django reverses comes first.
+            let targets = url_routes(db, project)
+                .named(&name)
+                .map(|route| match route.view() {
+                    Some(view) => DefinitionTarget::View(view),
+                    None => DefinitionTarget::Route(route.file(), route.span()),
+                })
+                .collect();
+            exact_definition_response(
+                db,
+                encoded_range(db, file, span, position_encoding)?,
+                targets,
+                supports_location_links,
+                position_encoding,
+            )
+        }
         SemanticOffsetContext::Tag { name, span, .. } => symbol_occurrence_response(
             db,
             file,
@@ -404,6 +427,7 @@ pub fn find_references(
         }
         SemanticOffsetContext::LoadLibrary { .. }
         | SemanticOffsetContext::LoadSymbol { .. }
+        | SemanticOffsetContext::Route { .. }
         | SemanticOffsetContext::Tag { .. }
         | SemanticOffsetContext::Filter { .. }
         | SemanticOffsetContext::Variable { .. }
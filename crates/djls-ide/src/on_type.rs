@@ -0,0 +1,195 @@
+//! On-type formatting for Django block end tags.
+//!
+//! Typing the `%` of a partial closer such as `{% end` or `{% endfo` replaces
+//! it with the full end tag of the innermost block still open at that point.
+//! When enabled, typing the `}` that finishes a block opener also inserts the
+//! opener's end tag on the next line, unless the block is already closed.
+//!
+//! Block pairing follows the same tag specs as validation, so library blocks
+//! loaded with `{% load %}` are closed the same way as builtins.
+
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::TagSpecs;
+use djls_semantic::tag_specs_at;
+use djls_source::File;
+use djls_source::FileKind;
+use djls_source::LineEnding;
+use djls_source::Offset;
+use djls_source::PositionEncoding;
+use djls_source::Span;
+use djls_templates::Node;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+
+const TAG_OPEN: &str = "{%";
+
+/// Compute edits for a `textDocument/onTypeFormatting` request.
+///
+/// `offset` is the cursor position just after the typed `ch`.
+/// `insert_end_tags` enables inserting end tags after a finished block opener.
+#[must_use]
+pub fn on_type_formatting(
+    db: &dyn SemanticDb,
+    file: File,
+    offset: Offset,
+    ch: &str,
+    encoding: PositionEncoding,
+    insert_end_tags: bool,
+) -> Option<Vec<ls_types::TextEdit>> {
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+
+    let text = source.as_str();
+    let cursor = offset.get() as usize;
+    let before = text.get(..cursor)?;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let open = line_start + before.get(line_start..)?.rfind(TAG_OPEN)?;
+
+    let tag_specs = tag_specs_at(db, file, nodelist, offset.get());
+    let nodes = nodelist.nodelist(db);
+
+    let (span, new_text) = match ch {
+        "%" => complete_end_tag(text, open, cursor, nodes, &tag_specs)?,
+        "}" if insert_end_tags => insert_end_tag(text, line_start, cursor, nodes, &tag_specs)?,
+        _ => return None,
+    };
+
+    let line_index = file.line_index(db);
+    Some(vec![ls_types::TextEdit::new(
+        span.to_lsp_range_with_encoding(text, line_index, encoding),
+        new_text,
+    )])
+}
+
+/// Replace a partial closer ending at `cursor` with the innermost open block's end tag.
+fn complete_end_tag(
+    text: &str,
+    open: usize,
+    cursor: usize,
+    nodes: &[Node],
+    tag_specs: &TagSpecs,
+) -> Option<(Span, String)> {
+    let typed = text
+        .get(open..cursor)?
+        .strip_prefix(TAG_OPEN)?
+        .strip_suffix('%')?
+        .trim();
+    if !typed.starts_with("end") || typed.contains(|c: char| c.is_whitespace() || c == '%') {
+        return None;
+    }
+
+    let open_offset = u32::try_from(open).ok()?;
+    let scan = BlockScan::new(
+        nodes
+            .iter()
+            .take_while(|node| node.full_span().end() <= open_offset),
+        tag_specs,
+    );
+    let end_tag = scan.open.last()?.end_tag;
+    if !end_tag.starts_with(typed) {
+        return None;
+    }
+
+    // An editor that auto-pairs braces may already have placed the `}`.
+    let end = if text.get(cursor..)?.starts_with('}') {
+        cursor + 1
+    } else {
+        cursor
+    };
+    let new_text = format!("{{% {end_tag} %}}");
+    if text.get(open..end)? == new_text {
+        return None;
+    }
+
+    Some((Span::saturating_from_bounds_usize(open, end), new_text))
+}
+
+/// Insert the end tag of a block opener that was just finished at `cursor`.
+fn insert_end_tag(
+    text: &str,
+    line_start: usize,
+    cursor: usize,
+    nodes: &[Node],
+    tag_specs: &TagSpecs,
+) -> Option<(Span, String)> {
+    // Only complete openers that end their line; inline blocks are left alone.
+    let rest_of_line = text.get(cursor..)?.lines().next().unwrap_or_default();
+    if !rest_of_line.trim().is_empty() {
+        return None;
+    }
+
+    let cursor_offset = u32::try_from(cursor).ok()?;
+    let opener = nodes
+        .iter()
+        .find(|node| node.full_span().end() == cursor_offset)?;
+    let Node::Tag { name, .. } = opener else {
+        return None;
+    };
+    let end_tag = tag_specs.get(name.as_str())?.end_tag.as_ref()?;
+
+    let scan = BlockScan::new(nodes, tag_specs);
+    let start = opener.full_span().start();
+    let still_open = scan.open.iter().any(|block| block.start == start);
+    if !still_open && !scan.unclosed.contains(&start) {
+        return None;
+    }
+
+    let line = text.get(line_start..)?.lines().next()?;
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let line_ending = LineEnding::last_in(text).unwrap_or_default().as_str();
+    let new_text = format!("{line_ending}{indent}{{% {} %}}", end_tag.name);
+
+    Some((Span::new(cursor_offset, 0), new_text))
+}
+
+struct OpenBlock<'a> {
+    start: u32,
+    end_tag: &'a str,
+}
+
+/// Pairing of block openers and closers over a run of nodes.
+#[derive(Default)]
+struct BlockScan<'a> {
+    /// Blocks still waiting for their closer, innermost last.
+    open: Vec<OpenBlock<'a>>,
+    /// Start offsets of openers skipped over by an outer block's closer.
+    unclosed: Vec<u32>,
+}
+
+impl<'a> BlockScan<'a> {
+    fn new(nodes: impl IntoIterator<Item = &'a Node>, tag_specs: &'a TagSpecs) -> Self {
+        let mut scan = Self::default();
+        for node in nodes {
+            let Node::Tag { name, .. } = node else {
+                continue;
+            };
+            if let Some(end_tag) = tag_specs
+                .get(name.as_str())
+                .and_then(|spec| spec.end_tag.as_ref())
+            {
+                scan.open.push(OpenBlock {
+                    start: node.full_span().start(),
+                    end_tag: end_tag.name.as_ref(),
+                });
+            } else if let Some(index) = scan
+                .open
+                .iter()
+                .rposition(|block| block.end_tag == name.as_str())
+            {
+                scan.unclosed.extend(scan.open.drain(index + 1..).map(|block| block.start));
+                scan.open.pop();
+            }
+        }
+        scan
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use djls_source::File;
+use djls_source::FileKind;
+use djls_source::PositionEncoding;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+use crate::ext::Utf8PathExt;
+use crate::imports::organized_load_edits;
+
+/// Offer to merge, sort, and move every `{% load %}` to the top of `file`.
+///
+/// `merge_loads` chooses between one tag for every whole-library load and one
+/// tag per library. Nothing is offered when the loads are already organized.
+#[must_use]
+pub fn organize_loads_action(
+    db: &dyn djls_semantic::Db,
+    file: File,
+    merge_loads: bool,
+    encoding: PositionEncoding,
+) -> Option<ls_types::CodeActionOrCommand> {
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    let TemplateParseResult::Parsed(parsed) = parse_template(db, file) else {
+        return None;
+    };
+
+    let source_text = source.as_str();
+    let line_index = file.line_index(db);
+    let edits = organized_load_edits(parsed.nodelist(db), source_text, merge_loads)?
+        .into_iter()
+        .map(|(span, new_text)| {
+            ls_types::TextEdit::new(
+                span.to_lsp_range_with_encoding(source_text, line_index, encoding),
+                new_text,
+            )
+        })
+        .collect();
+    let uri = file.path(db).to_lsp_uri()?;
+
+    Some(ls_types::CodeActionOrCommand::CodeAction(ls_types::CodeAction {
+        title: "Sort and merge `{% load %}` tags".to_string(),
+        kind: Some(ls_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
+        diagnostics: None,
+        edit: Some(ls_types::WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, edits)])),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        command: None,
+        is_preferred: None,
+        disabled: None,
+        data: None,
+    }))
+}
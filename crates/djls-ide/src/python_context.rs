@@ -0,0 +1,284 @@
+//! Cursor context in Python source, found with a small lexer rather than a parser.
+//!
+//! Completion runs on files that are mid-edit and often do not parse, so this tracks only what
+//! it needs: string literals, comments, and the brackets open around the cursor.
+
+use djls_project::TEMPLATE_NAME_CALLS;
+use djls_project::TEMPLATE_NAME_KEYWORDS;
+use djls_source::Offset;
+
+use crate::context::OffsetPrefix;
+use crate::context::OffsetSuffix;
+use crate::context::is_escaped;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PythonCompletionContext<'source> {
+    /// Inside the string literal that names a template, e.g. `render(request, "blog/▮")` or
+    /// `template_name = "▮"`.
+    TemplateName {
+        quote: char,
+        prefix: OffsetPrefix<'source>,
+        suffix: OffsetSuffix<'source>,
+        closed: bool,
+    },
+}
+
+impl<'source> PythonCompletionContext<'source> {
+    pub(crate) fn from_source(source: &'source str, offset: Offset) -> Option<Self> {
+        let end = (offset.get() as usize).min(source.len());
+        if !source.is_char_boundary(end) {
+            return None;
+        }
+
+        let string = open_string_before(&source[..end])?;
+        if !names_template(&source[..string.start], &string.brackets) {
+            return None;
+        }
+
+        let quote = char::from(string.quote);
+        let prefix = &source[string.start + 1..end];
+        let line_rest = source[end..].split('\n').next().unwrap_or_default();
+        let closing = line_rest
+            .bytes()
+            .enumerate()
+            .find(|(index, byte)| {
+                *byte == string.quote && !is_escaped(line_rest.as_bytes(), *index)
+            })
+            .map(|(index, _)| index);
+        let (suffix, closed) = match closing {
+            Some(index) => (&line_rest[..index], true),
+            None => {
+                let len = line_rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ',' | ')' | ']'))
+                    .unwrap_or(line_rest.len());
+                (&line_rest[..len], false)
+            }
+        };
+
+        Some(Self::TemplateName {
+            quote,
+            prefix: OffsetPrefix::new(prefix, offset),
+            suffix: OffsetSuffix::new(suffix, offset),
+            closed,
+        })
+    }
+}
+
+/// A bracket still open at some point in the source.
+#[derive(Debug)]
+struct Bracket<'source> {
+    open: u8,
+    /// The name called, for a `(` that follows one.
+    callee: Option<&'source str>,
+    /// Commas seen directly inside the bracket so far.
+    arguments: usize,
+}
+
+/// A single-line string literal that is still open at the end of the text.
+#[derive(Debug)]
+struct OpenString<'source> {
+    /// Byte position of the opening quote.
+    start: usize,
+    quote: u8,
+    /// Brackets open around the string, innermost last.
+    brackets: Vec<Bracket<'source>>,
+}
+
+/// Find the plain string literal the end of `text` is inside, if any.
+///
+/// Triple-quoted, byte, and f-strings never name a template here, so the cursor being inside
+/// one is treated like being outside any string.
+fn open_string_before(text: &str) -> Option<OpenString<'_>> {
+    let bytes = text.as_bytes();
+    let mut brackets: Vec<Bracket<'_>> = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'#' => match text[index..].find('\n') {
+                Some(len) => index += len,
+                None => return None,
+            },
+            quote @ (b'"' | b'\'') => {
+                let triple = bytes[index..].starts_with(&[quote; 3]);
+                match string_end(bytes, index, quote, triple) {
+                    Some(end) => {
+                        index = end;
+                        continue;
+                    }
+                    None => {
+                        let string_prefix = trailing_identifier(&text[..index]);
+                        let plain = matches!(string_prefix, "" | "r" | "R" | "u" | "U");
+                        return (!triple && plain).then_some(OpenString {
+                            start: index,
+                            quote,
+                            brackets,
+                        });
+                    }
+                }
+            }
+            open @ (b'(' | b'[' | b'{') => {
+                let callee = Some(trailing_identifier(text[..index].trim_end()))
+                    .filter(|name| open == b'(' && !name.is_empty());
+                brackets.push(Bracket {
+                    open,
+                    callee,
+                    arguments: 0,
+                });
+            }
+            b')' | b']' | b'}' => {
+                brackets.pop();
+            }
+            b',' => {
+                if let Some(bracket) = brackets.last_mut() {
+                    bracket.arguments += 1;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Byte position just past the literal opened at `start`, or `None` if it is still open.
+///
+/// An unterminated single-line string ends at its line, as it does for Python's tokenizer.
+fn string_end(bytes: &[u8], start: usize, quote: u8, triple: bool) -> Option<usize> {
+    let delimiter = if triple { 3 } else { 1 };
+    let mut index = start + delimiter;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'\n' if !triple => return Some(index),
+            byte if byte == quote && (!triple || bytes[index..].starts_with(&[quote; 3])) => {
+                return Some(index + delimiter);
+            }
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// Whether a string opened just after `before_quote`, inside `brackets`, names a template.
+fn names_template(before_quote: &str, brackets: &[Bracket<'_>]) -> bool {
+    if let Some(assignment) = before_quote.trim_end().strip_suffix('=') {
+        // `==`, `<=`, and augmented assignments leave no identifier directly before the `=`.
+        let name = trailing_identifier(assignment.trim_end());
+        return TEMPLATE_NAME_KEYWORDS.contains(&name);
+    }
+
+    let mut enclosing = brackets.iter().rev();
+    let Some(mut bracket) = enclosing.next() else {
+        return false;
+    };
+    // A list or tuple of names, as `select_template` takes, counts as one argument.
+    if bracket.callee.is_none() && matches!(bracket.open, b'[' | b'(') {
+        let Some(outer) = enclosing.next() else {
+            return false;
+        };
+        bracket = outer;
+    }
+
+    bracket.callee.is_some_and(|callee| {
+        TEMPLATE_NAME_CALLS
+            .iter()
+            .any(|(name, position)| *name == callee && *position == bracket.arguments)
+    })
+}
+
+fn trailing_identifier(text: &str) -> &str {
+    let start = text
+        .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        .len();
+    &text[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURSOR: &str = "▮";
+
+    fn context(input: &str) -> Option<(String, String, bool)> {
+        let offset = input
+            .find(CURSOR)
+            .expect("test source should contain the cursor marker");
+        let source = input.replace(CURSOR, "");
+        let offset = Offset::new(u32::try_from(offset).expect("test offset should fit in u32"));
+        let PythonCompletionContext::TemplateName {
+            prefix,
+            suffix,
+            closed,
+            ..
+        } = PythonCompletionContext::from_source(&source, offset)?;
+        Some((prefix.text.to_string(), suffix.text.to_string(), closed))
+    }
+
+    fn prefix(input: &str) -> Option<String> {
+        context(input).map(|(prefix, _, _)| prefix)
+    }
+
+    #[test]
+    fn template_arguments_of_known_calls() {
+        assert_eq!(prefix("render(request, \"blog/▮"), Some("blog/".to_string()));
+        assert_eq!(prefix("loader.get_template('bl▮')"), Some("bl".to_string()));
+        assert_eq!(prefix("render_to_string(\"▮\", ctx)"), Some(String::new()));
+        assert_eq!(
+            prefix("select_template([\"a.html\", \"b▮\"])"),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            prefix("TemplateResponse(\n    request,\n    \"blog/list▮"),
+            Some("blog/list".to_string())
+        );
+    }
+
+    #[test]
+    fn template_name_assignments_and_keywords() {
+        assert_eq!(
+            prefix("class Home(TemplateView):\n    template_name = \"ho▮\""),
+            Some("ho".to_string())
+        );
+        assert_eq!(
+            prefix("path(\"\", TemplateView.as_view(template_name=\"▮\"))"),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn other_strings_are_ignored() {
+        assert_eq!(prefix("render(\"▮"), None);
+        assert_eq!(prefix("render(request, {\"key\": \"▮"), None);
+        assert_eq!(prefix("name = \"▮\""), None);
+        assert_eq!(prefix("if template_name == \"▮\""), None);
+        assert_eq!(prefix("render(request, f\"blog/{▮"), None);
+        assert_eq!(prefix("render(request, \"\"\"▮"), None);
+        assert_eq!(prefix("# render(request, \"▮"), None);
+    }
+
+    #[test]
+    fn earlier_strings_and_comments_do_not_confuse_brackets() {
+        assert_eq!(
+            prefix("x = \"(\"  # ) [\nget_template(\"▮"),
+            Some(String::new())
+        );
+        assert_eq!(
+            prefix("log(\"it's\")\nget_template('a\\'b▮"),
+            Some("a\\'b".to_string())
+        );
+    }
+
+    #[test]
+    fn suffix_runs_to_the_closing_quote() {
+        assert_eq!(
+            context("get_template(\"blog/▮list.html\")"),
+            Some(("blog/".to_string(), "list.html".to_string(), true))
+        );
+        assert_eq!(
+            context("get_template(\"blog/▮list.html, x"),
+            Some(("blog/".to_string(), "list.html".to_string(), false))
+        );
+    }
+}
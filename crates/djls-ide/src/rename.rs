@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::VariableOccurrences;
+use djls_semantic::variable_occurrences_at;
+use djls_source::File;
+use djls_source::FileKind;
+use djls_source::Offset;
+use djls_source::PositionEncoding;
+use djls_source::SourceText;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use tower_lsp_server::ls_types;
+
+use crate::ext::SpanExt;
+use crate::ext::Utf8PathExt;
+
+/// The range of the variable a rename at `offset` would change.
+#[must_use]
+pub fn prepare_rename(
+    db: &dyn SemanticDb,
+    file: File,
+    offset: Offset,
+    encoding: PositionEncoding,
+) -> Option<ls_types::PrepareRenameResponse> {
+    let (occurrences, source) = occurrences_at(db, file, offset)?;
+    let line_index = file.line_index(db);
+    Some(ls_types::PrepareRenameResponse::RangeWithPlaceholder {
+        range: occurrences
+            .target
+            .to_lsp_range_with_encoding(source.as_str(), line_index, encoding),
+        placeholder: occurrences.name,
+    })
+}
+
+/// Rename the variable at `offset` everywhere the same binding is used in
+/// `file`.
+///
+/// Returns `None` when `offset` is not on a variable; the caller checks
+/// `new_name` with [`is_template_variable_name`] first.
+#[must_use]
+pub fn rename(
+    db: &dyn SemanticDb,
+    file: File,
+    offset: Offset,
+    new_name: &str,
+    encoding: PositionEncoding,
+) -> Option<ls_types::WorkspaceEdit> {
+    let (occurrences, source) = occurrences_at(db, file, offset)?;
+    let uri = file.path(db).to_lsp_uri()?;
+    let line_index = file.line_index(db);
+    let edits = occurrences
+        .spans
+        .iter()
+        .map(|span| {
+            ls_types::TextEdit::new(
+                span.to_lsp_range_with_encoding(source.as_str(), line_index, encoding),
+                new_name.to_string(),
+            )
+        })
+        .collect();
+
+    Some(ls_types::WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, edits)])),
+        document_changes: None,
+        change_annotations: None,
+    })
+}
+
+/// Whether `name` can be used as a template variable: a letter followed by
+/// letters, digits, or underscores. Django refuses to look up names that start
+/// with an underscore.
+#[must_use]
+pub fn is_template_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(char::is_alphabetic)
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn occurrences_at(
+    db: &dyn SemanticDb,
+    file: File,
+    offset: Offset,
+) -> Option<(VariableOccurrences, SourceText)> {
+    let Ok(source) = file.try_source(db) else {
+        return None;
+    };
+    if *source.kind() != FileKind::Template {
+        return None;
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+    let occurrences = variable_occurrences_at(db, file, nodelist, offset)?;
+    Some((occurrences, source))
+}
@@ -1,7 +1,81 @@
+use djls_project::ArgumentCountConstraint;
+use djls_project::SplitPosition;
+use djls_project::TagRule;
 use djls_semantic::TagArgument;
 use djls_semantic::TagArgumentKind;
 use djls_semantic::TagSpec;
 
+/// Snippet arguments for a tag, refined by the constraints in its extracted `TagRule`.
+///
+/// Extraction already names each argument position and turns forward required keywords into
+/// literals, which covers built-ins and custom tags alike. The rest of the rule only lives in its
+/// constraints, so it is folded in here:
+/// - `ChoiceAt` positions become choice placeholders
+/// - required keywords counted from the end become literals when the argument count is exact
+/// - positions with no extracted name become a choice of the rule's known options
+fn snippet_arguments(spec: &TagSpec) -> Vec<TagArgument> {
+    let mut args = spec.arguments();
+    let Some(rule) = spec.extracted_rules() else {
+        return args;
+    };
+    let exact_len = exact_argument_count(rule);
+    let index_of = |position: SplitPosition, len: usize| match position {
+        SplitPosition::Forward(_) => position.to_bits_index(len),
+        SplitPosition::Backward(_) => exact_len
+            .filter(|exact| *exact == len)
+            .and_then(|exact| position.to_bits_index(exact)),
+    };
+
+    for keyword in &rule.required_keywords {
+        if let Some(arg) = index_of(keyword.position, args.len()).and_then(|i| args.get_mut(i))
+            && !matches!(arg.kind, TagArgumentKind::Literal(_))
+        {
+            arg.name.clone_from(&keyword.value);
+            arg.kind = TagArgumentKind::Literal(keyword.value.clone());
+        }
+    }
+
+    for choice in &rule.choice_at_constraints {
+        if let Some(arg) = index_of(choice.position, args.len()).and_then(|i| args.get_mut(i))
+            && matches!(arg.kind, TagArgumentKind::Variable)
+        {
+            arg.kind = TagArgumentKind::Choice(choice.values.clone());
+        }
+    }
+
+    if let Some(options) = rule
+        .known_options
+        .as_ref()
+        .filter(|options| !options.values.is_empty())
+    {
+        for arg in &mut args {
+            if matches!(arg.kind, TagArgumentKind::Variable)
+                && arg.name == format!("arg{}", arg.position + 1)
+            {
+                arg.name = "option".to_string();
+                arg.kind = TagArgumentKind::Choice(options.values.clone());
+            }
+        }
+    }
+
+    args
+}
+
+/// Argument count (tag name excluded) when the rule pins `len(bits)` to one value.
+fn exact_argument_count(rule: &TagRule) -> Option<usize> {
+    rule.arg_constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            ArgumentCountConstraint::Exact(len) => Some(len.saturating_sub(1)),
+            ArgumentCountConstraint::OneOf(lens) if lens.len() == 1 => {
+                lens.first().map(|len| len.saturating_sub(1))
+            }
+            ArgumentCountConstraint::Min(_)
+            | ArgumentCountConstraint::Max(_)
+            | ArgumentCountConstraint::OneOf(_) => None,
+        })
+}
+
 /// Generate an LSP snippet pattern from an array of tag arguments.
 #[must_use]
 fn generate_snippet_from_args(args: &[TagArgument]) -> String {
@@ -47,7 +121,7 @@ fn generate_snippet_from_args(args: &[TagArgument]) -> String {
 /// Generate a complete LSP snippet for a tag including the tag name
 #[must_use]
 fn generate_snippet_for_tag(tag_name: &str, spec: &TagSpec) -> String {
-    let args = spec.arguments();
+    let args = snippet_arguments(spec);
 
     let args_snippet = generate_snippet_from_args(&args);
 
@@ -84,7 +158,7 @@ pub(crate) fn generate_snippet_for_tag_with_end(tag_name: &str, spec: &TagSpec)
 /// Generate a partial snippet starting from a specific argument position
 #[must_use]
 pub(crate) fn generate_partial_snippet(spec: &TagSpec, starting_from_position: usize) -> String {
-    let args = spec.arguments();
+    let args = snippet_arguments(spec);
 
     if starting_from_position >= args.len() {
         return String::new();
@@ -228,4 +302,77 @@ mod tests {
         let snippet = generate_snippet_from_args(&args);
         assert_eq!(snippet, "${1:view_name} ${2:args} ${3:varname}");
     }
+
+    fn spec_with_rule(rule: TagRule) -> TagSpec {
+        use std::borrow::Cow;
+        use std::sync::Arc;
+
+        TagSpec::new("custom.tags".into(), None, Cow::Borrowed(&[]), false)
+            .with_extracted_rules(Arc::new(rule))
+    }
+
+    #[test]
+    fn test_snippet_from_rule_choices_and_trailing_keyword() {
+        let spec = spec_with_rule(TagRule {
+            arg_constraints: vec![ArgumentCountConstraint::Exact(5)],
+            choice_at_constraints: vec![djls_project::ChoiceAt {
+                position: SplitPosition::Forward(1),
+                values: vec!["asc".to_string(), "desc".to_string()],
+            }],
+            required_keywords: vec![djls_project::RequiredKeyword {
+                position: SplitPosition::Backward(2),
+                value: "as".to_string(),
+            }],
+            extracted_args: vec![
+                make_var("arg1", true, 0),
+                make_var("items", true, 1),
+                make_var("arg3", true, 2),
+                make_var("varname", true, 3),
+            ],
+            ..TagRule::default()
+        });
+
+        assert_eq!(
+            generate_snippet_for_tag("sorted_as", &spec),
+            "sorted_as ${1|asc,desc|} ${2:items} as ${3:varname}"
+        );
+        assert_eq!(generate_partial_snippet(&spec, 2), "as ${1:varname}");
+    }
+
+    #[test]
+    fn test_snippet_from_rule_known_options_fill_unnamed_positions() {
+        let spec = spec_with_rule(TagRule {
+            arg_constraints: vec![ArgumentCountConstraint::Min(3)],
+            known_options: Some(djls_project::KnownOptions {
+                values: vec!["compact".to_string(), "full".to_string()],
+                allow_duplicates: false,
+                rejects_unknown: true,
+            }),
+            extracted_args: vec![make_var("target", true, 0), make_var("arg2", true, 1)],
+            ..TagRule::default()
+        });
+
+        assert_eq!(
+            generate_snippet_for_tag("render_panel", &spec),
+            "render_panel ${1:target} ${2|compact,full|}"
+        );
+    }
+
+    #[test]
+    fn test_snippet_backward_keyword_needs_exact_count() {
+        let spec = spec_with_rule(TagRule {
+            arg_constraints: vec![ArgumentCountConstraint::Min(2)],
+            required_keywords: vec![djls_project::RequiredKeyword {
+                position: SplitPosition::Backward(2),
+                value: "as".to_string(),
+            }],
+            extracted_args: vec![make_var("value", true, 0), make_var("varname", true, 1)],
+            ..TagRule::default()
+        });
+
+        assert_eq!(
+            generate_snippet_for_tag("assign", &spec),
+            "assign ${1:value} ${2:varname}"
+        );
+    }
 }
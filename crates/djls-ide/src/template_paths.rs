@@ -0,0 +1,154 @@
+//! Path completion for `{% extends %}` and `{% include %}` template names.
+//!
+//! Names are offered one directory level at a time: with `admin/` typed, the next level is
+//! listed, and deeper names collapse into directory entries that complete to `admin/widgets/`.
+//! Include tags list partial templates (file names starting with `_`) ahead of full pages.
+
+use std::collections::BTreeMap;
+
+use djls_templates::Node;
+
+/// Added to the rank of non-partial files in an include tag so partials sort first.
+const NON_PARTIAL_RANK: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TemplatePathEntry {
+    /// A template name that ends at the typed directory level.
+    File { name: String, rank: u32 },
+    /// A directory segment, including its trailing `/`, holding names below the typed level.
+    Directory { path: String, rank: u32 },
+}
+
+/// The completion entries for `names` at the directory level of `typed`.
+///
+/// Each name comes with its namespace rank (lower is better). A directory takes the best rank
+/// of the names under it.
+pub(crate) fn template_path_entries<'a>(
+    names: impl IntoIterator<Item = (&'a str, u32)>,
+    typed: &str,
+    partials_first: bool,
+) -> Vec<TemplatePathEntry> {
+    let level = typed.rfind('/').map_or(0, |slash| slash + 1);
+    let mut files = Vec::new();
+    let mut directories: BTreeMap<&str, u32> = BTreeMap::new();
+
+    for (name, rank) in names {
+        if !name.starts_with(typed) {
+            continue;
+        }
+        let rest = &name[level..];
+        if let Some(slash) = rest.find('/') {
+            let path = &name[..=level + slash];
+            directories
+                .entry(path)
+                .and_modify(|best| *best = (*best).min(rank))
+                .or_insert(rank);
+        } else {
+            let rank = if partials_first && !rest.starts_with('_') {
+                rank + NON_PARTIAL_RANK
+            } else {
+                rank
+            };
+            files.push(TemplatePathEntry::File {
+                name: name.to_string(),
+                rank,
+            });
+        }
+    }
+
+    files.extend(
+        directories
+            .into_iter()
+            .map(|(path, rank)| TemplatePathEntry::Directory {
+                path: path.to_string(),
+                rank,
+            }),
+    );
+    files
+}
+
+/// The quote character the template already uses for tag string arguments, defaulting to `"`.
+pub(crate) fn prevailing_quote(nodes: &[Node]) -> char {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Tag { bits, .. } => bits.first()?.as_str().chars().next(),
+            _ => None,
+        })
+        .find(|first| matches!(first, '"' | '\''))
+        .unwrap_or('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use djls_templates::parse_template_impl;
+
+    use super::*;
+
+    fn file(name: &str, rank: u32) -> TemplatePathEntry {
+        TemplatePathEntry::File {
+            name: name.to_string(),
+            rank,
+        }
+    }
+
+    fn directory(path: &str, rank: u32) -> TemplatePathEntry {
+        TemplatePathEntry::Directory {
+            path: path.to_string(),
+            rank,
+        }
+    }
+
+    #[test]
+    fn names_below_the_typed_level_collapse_into_directories() {
+        let names = [
+            ("base.html", 0),
+            ("admin/base.html", 1),
+            ("admin/widgets/date.html", 0),
+            ("blog/list.html", 0),
+        ];
+
+        assert_eq!(
+            template_path_entries(names, "", false),
+            vec![
+                file("base.html", 0),
+                directory("admin/", 0),
+                directory("blog/", 0),
+            ]
+        );
+        assert_eq!(
+            template_path_entries(names, "admin/", false),
+            vec![
+                file("admin/base.html", 1),
+                directory("admin/widgets/", 0),
+            ]
+        );
+        assert_eq!(
+            template_path_entries(names, "admin/wi", false),
+            vec![directory("admin/widgets/", 0)]
+        );
+    }
+
+    #[test]
+    fn partials_rank_ahead_of_pages_when_requested() {
+        let names = [("blog/_card.html", 1), ("blog/list.html", 0)];
+
+        assert_eq!(
+            template_path_entries(names, "blog/", true),
+            vec![file("blog/_card.html", 1), file("blog/list.html", 2)]
+        );
+        assert_eq!(
+            template_path_entries(names, "blog/", false),
+            vec![file("blog/_card.html", 1), file("blog/list.html", 0)]
+        );
+    }
+
+    #[test]
+    fn prevailing_quote_follows_existing_tag_arguments() {
+        let (nodes, _) = parse_template_impl("{% load static %}{% include 'nav.html' %}");
+        assert_eq!(prevailing_quote(&nodes), '\'');
+
+        let (nodes, _) = parse_template_impl("{% load static %}{{ title }}");
+        assert_eq!(prevailing_quote(&nodes), '"');
+    }
+}
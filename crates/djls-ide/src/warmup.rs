@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use djls_project::Project;
 use djls_project::ProjectFactsPhase;
 use djls_project::ScopedTemplateLibraries;
+use djls_project::TemplateInventory;
+use djls_project::TemplateInventoryImpact;
 use djls_project::template_directories;
 use djls_project::template_library_catalog;
 use djls_project::template_library_definition_facts;
@@ -10,8 +13,13 @@ use djls_semantic::Db as SemanticDb;
 use djls_semantic::library_filter_specs;
 use djls_semantic::library_tag_specs;
 use djls_semantic::semantic_grammar_vocabulary;
+use djls_semantic::template_library_references_in_file;
 use djls_source::File;
+use djls_source::FileKind;
 use djls_source::path_to_file;
+use djls_templates::Node;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
 
 /// The intrinsic Template Library products covered by one complete priming pass.
 ///
@@ -23,6 +31,7 @@ pub struct PrimedTemplateLibraries {
     reprime_files: Arc<[File]>,
     full_reload_files: Arc<[File]>,
     library_count: usize,
+    inventory: TemplateInventory,
 }
 
 impl PrimedTemplateLibraries {
@@ -51,6 +60,12 @@ impl PrimedTemplateLibraries {
         self.library_count
     }
 
+    /// Libraries and tags the primed catalog makes available.
+    #[must_use]
+    pub fn inventory(&self) -> &TemplateInventory {
+        &self.inventory
+    }
+
     #[cfg(test)]
     #[must_use]
     fn covered_file_count(&self) -> usize {
@@ -58,6 +73,56 @@ impl PrimedTemplateLibraries {
     }
 }
 
+/// Whether `file` may validate differently under an inventory change.
+///
+/// A Template is affected when it loads a changed library or names one of
+/// its tags or filters, so a Template that used a tag before its library was
+/// installed picks up the new load guidance. Other files, and Templates that
+/// fail to parse, are always affected.
+#[must_use]
+pub fn template_is_affected(
+    db: &dyn SemanticDb,
+    file: File,
+    impact: &TemplateInventoryImpact,
+) -> bool {
+    let TemplateInventoryImpact::Libraries { libraries, symbols } = impact else {
+        return true;
+    };
+    if !file
+        .try_source(db)
+        .is_ok_and(|source| source.kind() == FileKind::Template)
+    {
+        return true;
+    }
+    if libraries.is_empty() {
+        return false;
+    }
+    if template_library_references_in_file(db, file)
+        .as_slice(db)
+        .iter()
+        .any(|reference| libraries.contains(reference.load_name().as_str()))
+    {
+        return true;
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return true;
+    };
+    nodelist.nodelist(db).iter().any(|node| match node {
+        Node::Tag { name, bits, .. } => {
+            symbols.contains(name)
+                || bits.iter().any(|bit| {
+                    bit.as_str()
+                        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                        .any(|word| symbols.contains(word))
+                })
+        }
+        Node::Variable { filters, .. } => {
+            filters.iter().any(|filter| symbols.contains(&filter.name))
+        }
+        Node::Comment { .. } | Node::Text { .. } | Node::Error { .. } => false,
+    })
+}
+
 /// Evaluate every intrinsic product needed by project-aware Template analysis.
 ///
 /// This deliberately does no per-Template work. Catalog assembly provides the
@@ -114,6 +179,7 @@ pub fn prime_template_library_products(db: &dyn SemanticDb) -> Option<PrimedTemp
         reprime_files: reprime_files.into(),
         full_reload_files: full_reload_files.into(),
         library_count,
+        inventory: TemplateInventory::for_project(db, project),
     })
 }
 
@@ -151,6 +217,7 @@ pub enum WarmCachePhase {
     ResolveTemplateDirs,
     IndexTemplateLibraries,
     IndexTemplates,
+    ParseTemplates,
 }
 
 impl WarmCachePhase {
@@ -182,6 +249,13 @@ impl WarmCachePhase {
                     plural: "templates",
                 }),
             },
+            Self::ParseTemplates => WarmCacheProgress {
+                message: "Parsing templates",
+                count_label: Some(CountLabel {
+                    singular: "parsed template",
+                    plural: "parsed templates",
+                }),
+            },
         }
     }
 
@@ -210,10 +284,36 @@ impl WarmCachePhase {
                 )
             }
             Self::IndexTemplates => Some(template_resolution(db, project).origins(db).count()),
+            Self::ParseTemplates => Some(parse_discovered_templates(db, project, WARM_PARSE_LIMIT)),
         }
     }
 }
 
+/// Most Templates the warm-up parses.
+///
+/// Parsed Templates stay in the database for the rest of the session, so
+/// parsing every Template of a large project up front would hold all of them
+/// whether or not they are ever opened. The rest are parsed on first use.
+const WARM_PARSE_LIMIT: usize = 256;
+
+/// Parse up to `limit` discovered Templates so the first request for one does
+/// not pay for lexing and parsing it, returning how many parsed.
+///
+/// Templates are taken in resolution order, so the project's own template
+/// directories come before those of installed apps.
+fn parse_discovered_templates(db: &dyn SemanticDb, project: Project, limit: usize) -> usize {
+    template_resolution(db, project)
+        .origins(db)
+        .take(limit)
+        .filter(|origin| {
+            matches!(
+                parse_template(db, origin.file(db)),
+                TemplateParseResult::Parsed(_)
+            )
+        })
+        .count()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct WarmCachePart {
     phase: WarmCachePhase,
@@ -237,6 +337,7 @@ const WARM_CACHE_PHASES: &[WarmCachePhase] = &[
     WarmCachePhase::ResolveTemplateDirs,
     WarmCachePhase::IndexTemplateLibraries,
     WarmCachePhase::IndexTemplates,
+    WarmCachePhase::ParseTemplates,
 ];
 
 #[must_use]
@@ -3,12 +3,14 @@ use std::io;
 use camino::Utf8Path;
 use djls_conf::DiagnosticSeverity;
 use djls_conf::DiagnosticsConfig;
+use djls_conf::DuplicatePolicy;
 use djls_ide::code_actions;
 use djls_ide::collect_diagnostics;
 use djls_source::LineCol;
 use djls_source::LineIndex;
 use djls_source::PositionEncoding;
 use djls_source::Span;
+use djls_testing::ProjectFixture;
 use djls_testing::TestDatabase;
 use djls_testing::standard_validation_db;
 use tower_lsp_server::ls_types;
@@ -130,7 +132,8 @@ fn diagnostic_codes(source: &str) -> TestResult<Vec<String>> {
 
 #[test]
 fn unloaded_tag_action_inserts_load_after_import_header() {
-    let source = "{% extends \"base.html\" %}\n{% load static %}\n{% trans \"Hi\" %}\n";
+    let source = "{% extends \"base.html\" %}\n{% load static %}\n\
+                  {% block content %}{% trans \"Hi\" %}{% endblock %}\n";
     let db = db_with_source(source).expect("validation fixture should build");
     let actions = collect_actions(&db, request_at(source, "trans"))
         .expect("unloaded tag should produce a code action response");
@@ -191,7 +194,8 @@ fn unloaded_filter_action_inserts_required_library() {
 
 #[test]
 fn insert_load_action_preserves_crlf_line_endings() {
-    let source = "{% extends \"base.html\" %}\r\n{% trans \"Hi\" %}\r\n";
+    let source = "{% extends \"base.html\" %}\r\n\
+                  {% block content %}{% trans \"Hi\" %}{% endblock %}\r\n";
     let db = db_with_source(source).expect("validation fixture should build");
     let actions = collect_actions(&db, request_at(source, "trans"))
         .expect("unloaded tag should produce a code action response");
@@ -291,6 +295,47 @@ fn unnamed_closing_block_returns_no_rename_action() {
     assert!(actions.is_empty());
 }
 
+#[test]
+fn unclosed_tag_action_inserts_end_tag_at_end_of_template() {
+    let source = "{% if flag %}\nbody\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let actions = collect_actions(&db, request_at(source, "if"))
+        .expect("unclosed tag should produce a code action response");
+    let action = only_action(actions).expect("unclosed tag should produce one action");
+    let edit = only_edit(&action).expect("end tag action should contain one edit");
+
+    assert_eq!(action.title, "Add '{% endif %}'");
+    assert_eq!(action.is_preferred, Some(true));
+    assert_eq!(edit.range.start, ls_types::Position::new(2, 0));
+    assert_eq!(edit.new_text, "{% endif %}");
+    assert_eq!(
+        diagnostic_codes(&apply_edit(source, edit))
+            .expect("edited template diagnostics should be collected"),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn unknown_load_symbol_action_removes_symbol() {
+    let source = "{% load trans static from i18n %}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let actions = collect_actions(&db, request_at(source, "static"))
+        .expect("unknown load symbol should produce a code action response");
+    let action = only_action(actions).expect("unknown load symbol should produce one action");
+    let edit = only_edit(&action).expect("removal action should contain one edit");
+
+    assert_eq!(action.title, "Remove 'static' from the load");
+    assert_eq!(action.is_preferred, Some(true));
+    assert_eq!(edit.range.start, ls_types::Position::new(0, 13));
+    assert_eq!(edit.range.end, ls_types::Position::new(0, 20));
+    assert_eq!(edit.new_text, "");
+    assert_eq!(
+        diagnostic_codes(&apply_edit(source, edit))
+            .expect("edited template diagnostics should be collected"),
+        Vec::<String>::new()
+    );
+}
+
 #[test]
 fn non_intersecting_range_returns_no_actions() {
     let source = "{% trans \"Hi\" %}\nbody\n";
@@ -318,3 +363,191 @@ fn severity_off_suppresses_diagnostic_and_code_action() {
     assert!(diagnostics.is_empty());
     assert!(actions.is_empty());
 }
+
+#[test]
+fn duplicate_block_diagnostic_links_first_definition() {
+    let source = "{% block content %}{% endblock %}\n{% block content %}{% endblock %}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let file = file(&db).expect("template fixture file should exist");
+    let diagnostics = collect_diagnostics(&db, file).expect("template should return diagnostics");
+    let diagnostic = diagnostics
+        .iter()
+        .find(|diagnostic| {
+            diagnostic.code == Some(ls_types::NumberOrString::String("S131".to_string()))
+        })
+        .expect("duplicate block should be reported");
+
+    assert_eq!(diagnostic.range.start, ls_types::Position::new(1, 9));
+    let related = diagnostic
+        .related_information
+        .as_deref()
+        .expect("duplicate block should carry related information");
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].location.uri, template_uri().expect("template URI"));
+    assert_eq!(related[0].location.range.start, ls_types::Position::new(0, 9));
+    assert_eq!(related[0].message, "first definition of this block");
+}
+
+#[test]
+fn repeated_unknown_filter_is_reported_as_hint_after_first_use() {
+    let source = "{{ a|nosuchfilter }}\n{{ b|nosuchfilter }}\n{{ c|otherfilter }}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let file = file(&db).expect("template fixture file should exist");
+    let diagnostics = collect_diagnostics(&db, file).expect("template should return diagnostics");
+    let severities = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.range.start.line, diagnostic.severity))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        severities,
+        vec![
+            (0, Some(ls_types::DiagnosticSeverity::ERROR)),
+            (1, Some(ls_types::DiagnosticSeverity::HINT)),
+            (2, Some(ls_types::DiagnosticSeverity::ERROR)),
+        ]
+    );
+}
+
+#[test]
+fn repeated_unknown_filter_folds_into_first_diagnostic() {
+    let source = "{{ a|nosuchfilter }}\n{{ b|nosuchfilter }}\n";
+    let mut config = DiagnosticsConfig::default();
+    config.set_duplicates(DuplicatePolicy::Fold);
+    let db = db_with_source_and_config(source, config)
+        .expect("validation fixture with custom diagnostics should build");
+    let file = file(&db).expect("template fixture file should exist");
+    let diagnostics = collect_diagnostics(&db, file).expect("template should return diagnostics");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start, ls_types::Position::new(0, 5));
+    let related = diagnostics[0]
+        .related_information
+        .as_deref()
+        .expect("folded diagnostic should carry related information");
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].location.uri, template_uri().expect("template URI"));
+    assert_eq!(related[0].location.range.start, ls_types::Position::new(1, 5));
+    assert_eq!(related[0].message, "also used here");
+}
+
+#[test]
+fn repeated_unknown_filter_reported_in_full_when_configured() {
+    let source = "{{ a|nosuchfilter }}\n{{ b|nosuchfilter }}\n";
+    let mut config = DiagnosticsConfig::default();
+    config.set_duplicates(DuplicatePolicy::Report);
+    let db = db_with_source_and_config(source, config)
+        .expect("validation fixture with custom diagnostics should build");
+    let file = file(&db).expect("template fixture file should exist");
+    let diagnostics = collect_diagnostics(&db, file).expect("template should return diagnostics");
+
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Some(ls_types::DiagnosticSeverity::ERROR))
+    );
+    assert_eq!(diagnostics.len(), 2);
+}
+
+#[test]
+fn unmatched_endblock_diagnostic_links_opener() {
+    let source = "{% block content %}\n{% endblock sidebar %}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let file = file(&db).expect("template fixture file should exist");
+    let diagnostics = collect_diagnostics(&db, file).expect("template should return diagnostics");
+    let diagnostic = diagnostics
+        .iter()
+        .find(|diagnostic| {
+            diagnostic.code == Some(ls_types::NumberOrString::String("S103".to_string()))
+        })
+        .expect("mismatched endblock should be reported");
+
+    let related = diagnostic
+        .related_information
+        .as_deref()
+        .expect("mismatched endblock should carry related information");
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].location.range.start, ls_types::Position::new(0, 0));
+    assert_eq!(related[0].location.range.end, ls_types::Position::new(0, 19));
+    assert_eq!(related[0].message, "'content' opened here");
+}
+
+#[test]
+fn unknown_filter_action_offers_similar_filter() {
+    let source = "{{ value|lowr }}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let actions = collect_actions(&db, request_at(source, "lowr"))
+        .expect("unknown filter should produce a code action response");
+    let action = only_action(actions).expect("unknown filter should produce one suggestion");
+    let edit = only_edit(&action).expect("suggestion action should contain one edit");
+
+    assert_eq!(action.title, "Change to 'lower'");
+    assert_eq!(action.is_preferred, None);
+    assert_eq!(edit.new_text, "lower");
+    assert_eq!(apply_edit(source, edit), "{{ value|lower }}\n");
+}
+
+#[test]
+fn unknown_library_action_offers_similar_library() {
+    let source = "{% load i18m %}\n";
+    let db = db_with_source(source).expect("validation fixture should build");
+    let actions = collect_actions(&db, request_at(source, "i18m"))
+        .expect("unknown library should produce a code action response");
+    let action = only_action(actions).expect("unknown library should produce one suggestion");
+    let edit = only_edit(&action).expect("suggestion action should contain one edit");
+
+    assert_eq!(action.title, "Change to 'i18n'");
+    assert_eq!(
+        diagnostic_codes(&apply_edit(source, edit))
+            .expect("edited template diagnostics should be collected"),
+        Vec::<String>::new()
+    );
+}
+
+fn python_view_diagnostics(dirs: &str, views: &str) -> TestResult<Vec<ls_types::Diagnostic>> {
+    let mut db = TestDatabase::new();
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            &format!(
+                "INSTALLED_APPS = []\nTEMPLATES = [{{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': {dirs}, 'APP_DIRS': False}}]\n"
+            ),
+        )
+        .file("/test/project/templates/base.html", "base")
+        .file("/test/project/blog/views.py", views)
+        .install(&mut db)?;
+    let file = db.file(Utf8Path::new("/test/project/blog/views.py"))?;
+    Ok(collect_diagnostics(&db, file)
+        .ok_or_else(|| io::Error::other("Python file should return diagnostics"))?)
+}
+
+#[test]
+fn python_render_of_missing_template_is_reported() {
+    let views = "def index(request):\n    render(request, 'base.html')\n    return render(request, 'blog/missing.html')\n";
+    let diagnostics = python_view_diagnostics("['/test/project/templates']", views)
+        .expect("Python view fixture should build");
+
+    let [diagnostic] = diagnostics.as_slice() else {
+        panic!("expected one diagnostic, got {diagnostics:?}");
+    };
+    assert_eq!(
+        diagnostic.code,
+        Some(ls_types::NumberOrString::String("S141".to_string()))
+    );
+    assert_eq!(diagnostic.range.start, ls_types::Position::new(2, 27));
+    assert_eq!(diagnostic.range.end, ls_types::Position::new(2, 46));
+    assert_eq!(
+        diagnostic.message,
+        "Template 'blog/missing.html' does not exist in any configured template directory"
+    );
+}
+
+#[test]
+fn python_render_is_not_reported_when_template_search_is_incomplete() {
+    let views = "def index(request):\n    return render(request, 'blog/missing.html')\n";
+    let diagnostics = python_view_diagnostics("[UNKNOWN, '/test/project/templates']", views)
+        .expect("Python view fixture should build");
+
+    assert!(diagnostics.is_empty());
+}
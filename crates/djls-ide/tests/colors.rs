@@ -0,0 +1,90 @@
+use camino::Utf8Path;
+use djls_ide::color_presentations;
+use djls_ide::document_colors;
+use djls_source::PositionEncoding;
+use djls_source::Span;
+use djls_testing::TestDatabase;
+use tower_lsp_server::ls_types;
+
+fn colors_in(source: &str) -> Vec<(String, [u8; 4])> {
+    let db = TestDatabase::new();
+    db.add_file("template.html", source)
+        .expect("template fixture should be added");
+    let file = db
+        .file(Utf8Path::new("template.html"))
+        .expect("template fixture file should exist");
+
+    document_colors(&db, file, PositionEncoding::Utf16)
+        .into_iter()
+        .map(|info| {
+            assert_eq!(info.range.start.line, info.range.end.line);
+            let line = source.lines().nth(info.range.start.line as usize).unwrap_or_default();
+            let text = line
+                .get(info.range.start.character as usize..info.range.end.character as usize)
+                .unwrap_or_default()
+                .to_string();
+            let color = [info.color.red, info.color.green, info.color.blue, info.color.alpha]
+                .map(|channel| {
+                    format!("{}", (channel * 255.0).round())
+                        .parse::<u8>()
+                        .expect("color channels should be within 0..=1")
+                });
+            (text, color)
+        })
+        .collect()
+}
+
+#[test]
+fn document_colors_find_hex_and_rgb_literals_in_text() {
+    let source = r##"<style>
+  .a { color: #f00; background: #00ff0080; }
+  .b { color: rgb(0, 0, 255); border-color: rgba(255 255 255 / 50%); }
+</style>
+<p style="color: #ABCDEF">{{ "#123456" }}</p>
+"##;
+
+    assert_eq!(
+        colors_in(source),
+        vec![
+            ("#f00".to_string(), [255, 0, 0, 255]),
+            ("#00ff0080".to_string(), [0, 255, 0, 128]),
+            ("rgb(0, 0, 255)".to_string(), [0, 0, 255, 255]),
+            ("rgba(255 255 255 / 50%)".to_string(), [255, 255, 255, 128]),
+            ("#ABCDEF".to_string(), [171, 205, 239, 255]),
+        ]
+    );
+}
+
+#[test]
+fn document_colors_skip_entities_fragments_and_bad_values() {
+    let source = r##"<a href="/page#top">&#123;</a> #12345 #abcdefg rgb(300, 0, 0) a#fff
+{% if x %}#fffff{% endif %}"##;
+
+    assert!(colors_in(source).is_empty());
+}
+
+#[test]
+fn color_presentations_offer_the_literal_notation_first() {
+    let source = "<p style=\"color: rgb(255, 0, 0)\"></p>";
+    let db = TestDatabase::new();
+    db.add_file("template.html", source)
+        .expect("template fixture should be added");
+    let file = db
+        .file(Utf8Path::new("template.html"))
+        .expect("template fixture file should exist");
+    let start = source.find("rgb").expect("fixture has an rgb literal");
+    let span = Span::saturating_from_bounds_usize(start, start + "rgb(255, 0, 0)".len());
+    let color = ls_types::Color {
+        red: 0.0,
+        green: 0.5,
+        blue: 1.0,
+        alpha: 0.25,
+    };
+
+    let labels = color_presentations(&db, file, span, color, PositionEncoding::Utf16)
+        .into_iter()
+        .map(|presentation| presentation.label)
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, vec!["rgba(0, 128, 255, 0.25)", "#0080ff40"]);
+}
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::io;
 
 use camino::Utf8Path;
+use djls_conf::CompletionsConfig;
 use djls_conf::TagDef;
 use djls_conf::TagLibraryDef;
 use djls_conf::TagSpecDef;
@@ -79,7 +80,14 @@ fn completion_dispatches_before_requesting_semantic_inventory() {
             .take()
             .expect("initial Salsa events should be cleared");
 
-        let response = completion(&db, file, offset, PositionEncoding::Utf16, false);
+        let response = completion(
+            &db,
+            file,
+            offset,
+            PositionEncoding::Utf16,
+            false,
+            &CompletionsConfig::default(),
+        );
         let executed = event_log
             .take_will_execute_names(&db)
             .expect("completion Salsa events should be read");
@@ -140,6 +148,7 @@ fn captured_closer_does_not_offer_colliding_standalone_arguments() {
             captured_offset,
             PositionEncoding::Utf16,
             false,
+            &CompletionsConfig::default(),
         )
         .is_none(),
         "a captured endif must not offer arguments from the colliding standalone definition"
@@ -156,6 +165,7 @@ fn captured_closer_does_not_offer_colliding_standalone_arguments() {
         standalone_offset,
         PositionEncoding::Utf16,
         false,
+        &CompletionsConfig::default(),
     )
     .expect("the standalone endif definition should offer its argument");
     let items = match response {
@@ -203,6 +213,7 @@ fn shadowed_normal_tag_named_load_gets_no_library_completion() {
                 offset,
                 PositionEncoding::Utf16,
                 false,
+                &CompletionsConfig::default(),
             )
             .is_none(),
             "a syntax-only load context must not bypass the point-resolved TagRole in {path}"
@@ -241,6 +252,7 @@ fn project_tag_completions_do_not_leak_conflicting_backend_libraries() {
             ),
             PositionEncoding::Utf16,
             false,
+            &CompletionsConfig::default(),
         )
         .ok_or_else(|| io::Error::other("tag completion should produce candidates"))?;
         Ok(match response {
@@ -294,7 +306,14 @@ fn multi_backend_effective_symbols_disagree_when_implementations_differ() {
             u32::try_from(source.len()).expect("test source offset should fit in u32") - 3,
         );
         Ok(
-            match completion(&db, file, offset, PositionEncoding::Utf16, false) {
+            match completion(
+                &db,
+                file,
+                offset,
+                PositionEncoding::Utf16,
+                false,
+                &CompletionsConfig::default(),
+            ) {
                 Some(ls_types::CompletionResponse::Array(items)) => items,
                 Some(ls_types::CompletionResponse::List(list)) => list.items,
                 None => Vec::new(),
@@ -348,7 +367,14 @@ fn multi_backend_same_definition_uses_loaded_availability_presentation() {
         .expect("shared tag template fixture should exist");
     let offset =
         Offset::new(u32::try_from(source.len()).expect("test source offset should fit in u32") - 3);
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("the shared definition should complete");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -416,7 +442,14 @@ fn configured_only_tag_survives_effective_candidates_and_completion() {
         SymbolDefinition::Unknown
     ));
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("configured-only tag should complete");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -455,7 +488,15 @@ fn conflicting_backend_signatures_do_not_offer_argument_snippets() {
         .expect("shared template fixture should exist");
 
     assert!(
-        completion(&db, file, offset, PositionEncoding::Utf16, true).is_none(),
+        completion(
+            &db,
+            file,
+            offset,
+            PositionEncoding::Utf16,
+            true,
+            &CompletionsConfig::default(),
+        )
+        .is_none(),
         "disagreeing feasible signatures must not produce an argument snippet"
     );
 }
@@ -478,7 +519,14 @@ fn template_name_completions_do_not_leak_names_from_another_backend() {
         .file(Utf8Path::new("/test/project/a/child.html"))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("template names should complete");
     let labels = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -503,7 +551,14 @@ fn template_name_completions_use_resolvable_project_names() {
         .file(Utf8Path::new(child_path))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("template names should complete inside quoted references");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -514,17 +569,11 @@ fn template_name_completions_use_resolvable_project_names() {
         .map(|item| item.label.as_str())
         .collect::<Vec<_>>();
 
-    assert_eq!(
-        labels,
-        vec![
-            "account/detail.html",
-            "base.html",
-            "child.html",
-            "shared.html",
-        ]
-    );
-    assert_eq!(items[0].kind, Some(ls_types::CompletionItemKind::FILE));
-    assert_eq!(items[0].detail.as_deref(), Some("Django template"));
+    assert_eq!(labels, vec!["account/", "base.html", "child.html", "shared.html"]);
+    assert_eq!(items[0].kind, Some(ls_types::CompletionItemKind::FOLDER));
+    assert_eq!(items[0].detail.as_deref(), Some("Template directory"));
+    assert_eq!(items[1].kind, Some(ls_types::CompletionItemKind::FILE));
+    assert_eq!(items[1].detail.as_deref(), Some("Django template"));
 }
 
 #[test]
@@ -547,7 +596,14 @@ fn template_name_completions_retain_known_templates_when_search_is_incomplete()
         .file(Utf8Path::new(child_path))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("known template names should remain completion candidates");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -561,10 +617,55 @@ fn template_name_completions_retain_known_templates_when_search_is_incomplete()
     assert_eq!(labels, ["base.html", "child.html"]);
 }
 
+#[test]
+fn template_name_completions_rank_app_namespaced_names_first() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset(r#"{% include "§" %}"#)
+        .expect("namespaced-app fixture should contain a valid cursor marker");
+    let page_path = "/test/project/templates/page.html";
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = ['blog', 'shop']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': True}]\n",
+        )
+        .file(page_path, &source)
+        .file("/test/project/blog/__init__.py", "")
+        .file("/test/project/blog/templates/form.html", "blog form")
+        .file("/test/project/blog/templates/blog/list.html", "list")
+        .file("/test/project/shop/__init__.py", "")
+        .file("/test/project/shop/templates/shop/cart.html", "cart")
+        .install(&mut db)
+        .expect("namespaced-app completion fixture should install");
+    let file = db
+        .file(Utf8Path::new(page_path))
+        .expect("page template fixture should exist");
+
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
+        .expect("app template names should complete inside quoted references");
+    let items = match response {
+        ls_types::CompletionResponse::Array(items) => items,
+        ls_types::CompletionResponse::List(list) => list.items,
+    };
+    let labels = items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, ["blog/", "shop/", "page.html", "form.html"]);
+}
+
 #[test]
 fn template_name_completion_replaces_quoted_argument_interior() {
     let mut db = TestDatabase::new();
-    let (source, offset) = source_and_offset(r#"{% extends "acc§ount/detail.html" %}"#)
+    let (source, offset) = source_and_offset(r#"{% extends "account/de§tail.html" %}"#)
         .expect("quoted template fixture should contain a valid cursor marker");
     let child_path = "/test/project/templates/child.html";
     install_template_completion_project(&mut db, child_path, &source)
@@ -573,7 +674,14 @@ fn template_name_completion_replaces_quoted_argument_interior() {
         .file(Utf8Path::new(child_path))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("template names should complete inside quoted references");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -600,6 +708,88 @@ fn template_name_completion_replaces_quoted_argument_interior() {
     );
 }
 
+#[test]
+fn template_directory_completion_replaces_only_the_typed_segment() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset(r#"{% extends "acc§ount/detail.html" %}"#)
+        .expect("quoted directory fixture should contain a valid cursor marker");
+    let child_path = "/test/project/templates/child.html";
+    install_template_completion_project(&mut db, child_path, &source)
+        .expect("template completion project fixture should install");
+    let file = db
+        .file(Utf8Path::new(child_path))
+        .expect("child template fixture should exist");
+
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
+        .expect("template directories should complete inside quoted references");
+    let items = match response {
+        ls_types::CompletionResponse::Array(items) => items,
+        ls_types::CompletionResponse::List(list) => list.items,
+    };
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].label, "account/");
+    assert_eq!(
+        items[0].text_edit,
+        Some(ls_types::CompletionTextEdit::Edit(ls_types::TextEdit::new(
+            ls_types::Range::new(
+                ls_types::Position::new(0, 12),
+                ls_types::Position::new(0, 20),
+            ),
+            "account/".to_string(),
+        )))
+    );
+}
+
+#[test]
+fn template_name_completion_quotes_unquoted_argument_in_template_style() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset("{% include 'nav.html' %}{% extends ba§")
+        .expect("unquoted template fixture should contain a valid cursor marker");
+    let child_path = "/test/project/templates/child.html";
+    install_template_completion_project(&mut db, child_path, &source)
+        .expect("template completion project fixture should install");
+    let file = db
+        .file(Utf8Path::new(child_path))
+        .expect("child template fixture should exist");
+
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
+        .expect("template names should complete for unquoted references");
+    let items = match response {
+        ls_types::CompletionResponse::Array(items) => items,
+        ls_types::CompletionResponse::List(list) => list.items,
+    };
+    let item = items
+        .iter()
+        .find(|item| item.label == "base.html")
+        .expect("expected base.html completion");
+
+    assert_eq!(
+        item.text_edit,
+        Some(ls_types::CompletionTextEdit::Edit(ls_types::TextEdit::new(
+            ls_types::Range::new(
+                ls_types::Position::new(0, 35),
+                ls_types::Position::new(0, 37),
+            ),
+            "'base.html' %}".to_string(),
+        )))
+    );
+}
+
 #[test]
 fn template_name_completion_preserves_existing_full_close_after_open_quote() {
     let mut db = TestDatabase::new();
@@ -612,7 +802,14 @@ fn template_name_completion_preserves_existing_full_close_after_open_quote() {
         .file(Utf8Path::new(child_path))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("template names should complete inside quoted references");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -651,7 +848,14 @@ fn template_name_completion_repairs_autopaired_quote_before_lone_brace() {
         .file(Utf8Path::new(child_path))
         .expect("child template fixture should exist");
 
-    let response = completion(&db, file, offset, PositionEncoding::Utf16, false)
+    let response = completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    )
         .expect("template names should complete inside quoted references");
     let items = match response {
         ls_types::CompletionResponse::Array(items) => items,
@@ -677,3 +881,419 @@ fn template_name_completion_repairs_autopaired_quote_before_lone_brace() {
         ))
     );
 }
+
+#[test]
+fn fuzzy_filter_completion_ranks_prefix_matches_first() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset("{{ value|dn§ }}")
+        .expect("fuzzy filter fixture should contain a valid cursor marker");
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False, 'OPTIONS': {'builtins': ['custom_filters']}}]\n",
+        )
+        .file(
+            "/test/project/custom_filters.py",
+            "from django import template\nregister = template.Library()\n@register.filter\ndef dn_prefix(value): pass\n@register.filter\ndef default_if_none(value): pass\n@register.filter\ndef dateformat_name(value): pass\n",
+        )
+        .file("/test/project/templates/filters.html", &source)
+        .install(&mut db)
+        .expect("fuzzy filter project fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/filters.html"))
+        .expect("filter completion fixture should exist");
+    let items_for = |fuzzy| {
+        let mut config = CompletionsConfig::default();
+        config.set_fuzzy(fuzzy);
+        match completion(&db, file, offset, PositionEncoding::Utf16, false, &config) {
+            Some(ls_types::CompletionResponse::Array(items)) => items,
+            Some(ls_types::CompletionResponse::List(list)) => list.items,
+            None => Vec::new(),
+        }
+    };
+
+    let prefix_labels = items_for(false)
+        .into_iter()
+        .map(|item| item.label)
+        .collect::<Vec<_>>();
+    assert_eq!(prefix_labels, vec!["dn_prefix"]);
+
+    let fuzzy_items = items_for(true);
+    let labels = fuzzy_items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(labels[0], "dn_prefix");
+    let sort_text = |label: &str| {
+        fuzzy_items
+            .iter()
+            .find(|item| item.label == label)
+            .and_then(|item| item.sort_text.clone())
+            .expect("fuzzy filter candidate should carry sort text")
+    };
+    assert!(sort_text("dn_prefix") < sort_text("dateformat_name"));
+    assert!(sort_text("dateformat_name") < sort_text("default_if_none"));
+}
+
+#[test]
+fn variable_completion_offers_blocktrans_bindings_before_template_variables() {
+    let db = TestDatabase::new();
+    let (source, offset) = source_and_offset(
+        "{% load i18n %}{{ username }}{% blocktrans with name=user.name %}{{ § }}{% endblocktrans %}",
+    )
+    .expect("blocktrans fixture should contain a valid cursor marker");
+    db.add_file("/blocktrans.html", &source)
+        .expect("blocktrans fixture should be added");
+    let file = db
+        .file(Utf8Path::new("/blocktrans.html"))
+        .expect("blocktrans fixture file should exist");
+
+    let labels = match completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    ) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    }
+    .into_iter()
+    .map(|item| item.label)
+    .collect::<Vec<_>>();
+
+    assert_eq!(labels, vec!["name", "username"]);
+}
+
+#[test]
+fn blocktrans_with_value_completes_template_variables() {
+    let db = TestDatabase::new();
+    let (source, offset) = source_and_offset(
+        "{% load i18n %}{{ user.name }}{% blocktrans with name=us§ %}{% endblocktrans %}",
+    )
+    .expect("blocktrans fixture should contain a valid cursor marker");
+    db.add_file("/blocktrans.html", &source)
+        .expect("blocktrans fixture should be added");
+    let file = db
+        .file(Utf8Path::new("/blocktrans.html"))
+        .expect("blocktrans fixture file should exist");
+
+    let labels = match completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    ) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    }
+    .into_iter()
+    .map(|item| item.label)
+    .collect::<Vec<_>>();
+
+    assert_eq!(labels, vec!["user"]);
+}
+
+#[test]
+fn variable_completion_offers_context_processor_variables_after_template_variables() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset("{{ title }}{{ § }}")
+        .expect("context processor fixture should contain a valid cursor marker");
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'OPTIONS': {'context_processors': ['django.contrib.auth.context_processors.auth', 'shop.context.cart']}}]\n",
+        )
+        .file("/test/project/shop/__init__.py", "")
+        .file(
+            "/test/project/shop/context.py",
+            "def cart(request):\n    context = {}\n    context['cart'] = request.session.get('cart')\n    return context\n",
+        )
+        .file("/test/project/templates/page.html", source)
+        .install(&mut db)
+        .expect("context processor fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("context processor fixture file should exist");
+
+    let items = match completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    ) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    };
+    let labels = items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, vec!["title", "cart", "csrf_token", "perms", "user"]);
+    let cart = items
+        .iter()
+        .find(|item| item.label == "cart")
+        .expect("cart should be offered");
+    assert_eq!(cart.detail.as_deref(), Some("context processor"));
+}
+
+#[test]
+fn settings_attribute_completes_declared_settings_with_types() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset("{{ settings.SITE_§ }}")
+        .expect("settings fixture should contain a valid cursor marker");
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nSITE_ID = 1\nSITE_NAME = 'Shop'\nsite_theme = 'dark'\n",
+        )
+        .file("/test/project/templates/page.html", source)
+        .install(&mut db)
+        .expect("settings fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("settings fixture file should exist");
+
+    let items = match completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    ) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    };
+    let labels = items
+        .iter()
+        .map(|item| (item.label.as_str(), item.detail.as_deref()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        labels,
+        vec![
+            ("SITE_ID", Some("setting")),
+            ("SITE_NAME", Some("setting: str")),
+        ]
+    );
+}
+
+fn forloop_labels(marked_source: &str) -> TestResult<Vec<String>> {
+    let db = TestDatabase::new();
+    let (source, offset) = source_and_offset(marked_source)?;
+    db.add_file("/loops.html", &source)?;
+    let file = db.file(Utf8Path::new("/loops.html"))?;
+
+    let items = match completion(
+        &db,
+        file,
+        offset,
+        PositionEncoding::Utf16,
+        false,
+        &CompletionsConfig::default(),
+    ) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    };
+    Ok(items.into_iter().map(|item| item.label).collect())
+}
+
+#[test]
+fn forloop_attribute_completion_follows_enclosing_loops() {
+    assert_eq!(
+        forloop_labels("{% for item in items %}{{ forloop.§ }}{% endfor %}")
+            .expect("loop fixture should complete"),
+        vec![
+            "counter",
+            "counter0",
+            "first",
+            "last",
+            "length",
+            "revcounter",
+            "revcounter0"
+        ]
+    );
+    assert!(
+        forloop_labels(
+            "{% for row in rows %}{% for cell in row %}{{ forloop.§ }}{% endfor %}{% endfor %}"
+        )
+        .expect("nested loop fixture should complete")
+        .contains(&"parentloop".to_string())
+    );
+    assert_eq!(
+        forloop_labels(
+            "{% for row in rows %}{% for cell in row %}\
+             {% if forloop.parentloop.fi§ %}{% endif %}{% endfor %}{% endfor %}"
+        )
+        .expect("parentloop fixture should complete"),
+        vec!["first"]
+    );
+    assert!(
+        forloop_labels("{{ forloop.§ }}")
+            .expect("fixture outside loops should complete")
+            .is_empty()
+    );
+    assert!(
+        forloop_labels("{% for item in items %}{% empty %}{{ forloop.§ }}{% endfor %}")
+            .expect("empty branch fixture should complete")
+            .is_empty()
+    );
+}
+
+#[test]
+fn html_attribute_completion_offers_enabled_attribute_sets() {
+    let db = TestDatabase::new();
+    let (source, offset) = source_and_offset("<button hx-p§>{% csrf_token %}</button>")
+        .expect("attribute fixture should contain a valid cursor marker");
+    db.add_file("/attributes.html", &source)
+        .expect("attribute fixture should be added");
+    let file = db
+        .file(Utf8Path::new("/attributes.html"))
+        .expect("attribute fixture file should exist");
+
+    assert!(
+        completion(
+            &db,
+            file,
+            offset,
+            PositionEncoding::Utf16,
+            true,
+            &CompletionsConfig::default(),
+        )
+        .is_none(),
+        "attribute sets are opt-in"
+    );
+
+    let mut config = CompletionsConfig::default();
+    config.set_htmx(true);
+    let items = match completion(&db, file, offset, PositionEncoding::Utf16, true, &config) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    };
+    let labels = items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            "hx-params",
+            "hx-patch",
+            "hx-post",
+            "hx-preserve",
+            "hx-prompt",
+            "hx-push-url",
+            "hx-put",
+        ]
+    );
+    let post = items
+        .iter()
+        .find(|item| item.label == "hx-post")
+        .expect("hx-post should be offered");
+    assert_eq!(post.insert_text_format, Some(ls_types::InsertTextFormat::SNIPPET));
+    assert!(
+        matches!(
+            &post.text_edit,
+            Some(ls_types::CompletionTextEdit::Edit(edit)) if edit.new_text == "hx-post=\"$1\""
+        ),
+        "{:?}",
+        post.text_edit
+    );
+}
+
+#[test]
+fn htmx_request_url_completion_offers_url_names() {
+    let mut db = TestDatabase::new();
+    let (source, offset) = source_and_offset("<button hx-post=\"blog:§\">Save</button>")
+        .expect("htmx fixture should contain a valid cursor marker");
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "ROOT_URLCONF = 'testproject.urls'\nINSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file(
+            "/test/project/testproject/urls.py",
+            "from django.urls import include, path\n\nurlpatterns = [\n    path('', home, name='home'),\n    path('blog/', include('blog.urls')),\n]\n",
+        )
+        .file("/test/project/blog/__init__.py", "")
+        .file(
+            "/test/project/blog/urls.py",
+            "from django.urls import path\n\napp_name = 'blog'\nurlpatterns = [\n    path('new/', create, name='create'),\n    path('<int:pk>/', detail, name='detail'),\n    path('<slug:slug>/', detail, name='detail'),\n]\n",
+        )
+        .file("/test/project/templates/page.html", &source)
+        .install(&mut db)
+        .expect("htmx project fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("page template fixture should exist");
+
+    assert!(
+        completion(
+            &db,
+            file,
+            offset,
+            PositionEncoding::Utf16,
+            true,
+            &CompletionsConfig::default(),
+        )
+        .is_none(),
+        "URL completion follows the htmx setting"
+    );
+
+    let mut config = CompletionsConfig::default();
+    config.set_htmx(true);
+    let items = match completion(&db, file, offset, PositionEncoding::Utf16, true, &config) {
+        Some(ls_types::CompletionResponse::Array(items)) => items,
+        Some(ls_types::CompletionResponse::List(list)) => list.items,
+        None => Vec::new(),
+    };
+    let labels = items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, vec!["blog:create", "blog:detail"]);
+    assert!(
+        matches!(
+            &items[1].text_edit,
+            Some(ls_types::CompletionTextEdit::Edit(edit))
+                if edit.new_text == "{% url 'blog:detail' %}"
+                    && edit.range.start == ls_types::Position::new(0, 17)
+                    && edit.range.end == ls_types::Position::new(0, 22)
+        ),
+        "{:?}",
+        items[1].text_edit
+    );
+}
+
+#[test]
+fn html_attribute_completion_skips_embedded_scripts() {
+    let db = TestDatabase::new();
+    let (source, offset) = source_and_offset("<script>if (a <b x-§) {}</script>")
+        .expect("script fixture should contain a valid cursor marker");
+    db.add_file("/script.html", &source)
+        .expect("script fixture should be added");
+    let file = db
+        .file(Utf8Path::new("/script.html"))
+        .expect("script fixture file should exist");
+    let mut config = CompletionsConfig::default();
+    config.set_alpine(true);
+
+    assert!(completion(&db, file, offset, PositionEncoding::Utf16, false, &config).is_none());
+}
@@ -142,6 +142,46 @@ fn filter_hover_follows_definition_collisions_at_each_load_position() {
     );
 }
 
+#[test]
+fn filter_hover_shows_signature_and_decorator_flags() {
+    let mut db = TestDatabase::new();
+    let settings = "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False, 'OPTIONS': {'libraries': {'text': 'text_tags'}}}]\n";
+    let source = "{% load text %}{{ title|shout:'!' }}";
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file("/test/project/testproject/settings.py", settings)
+        .file(
+            "/test/project/text_tags.py",
+            "from django import template\nfrom django.template.defaultfilters import stringfilter\nregister = template.Library()\n\n@register.filter(is_safe=True)\n@stringfilter\ndef shout(value, suffix='!'):\n    return value.upper() + suffix\n",
+        )
+        .file("/test/project/templates/page.html", source)
+        .install(&mut db)
+        .expect("filter signature hover project fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("page template fixture should exist");
+    let offset = Offset::new(
+        u32::try_from(
+            source
+                .find("shout")
+                .expect("test source should contain the expected text"),
+        )
+        .expect("test source offset should fit in u32"),
+    );
+
+    let markdown = hover_markdown(hover(&db, file, offset).expect("filter signature hover"))
+        .expect("filter signature hover should use markup content");
+
+    assert!(
+        markdown.starts_with("```text\n(filter) shout(value, suffix=\"!\")\n```"),
+        "{markdown}"
+    );
+    assert!(markdown.contains("`is_safe=True`"), "{markdown}");
+    assert!(markdown.contains("`@stringfilter`"), "{markdown}");
+    assert!(markdown.contains("Requires `{% load text %}`."), "{markdown}");
+    assert!(markdown.contains("Defined in `text_tags`."), "{markdown}");
+}
+
 #[test]
 fn multi_backend_same_definition_hovers_across_builtin_and_loaded_exposure() {
     let mut db = TestDatabase::new();
@@ -362,3 +402,128 @@ fn inconclusive_template_hover_describes_incomplete_search_and_possible_matches(
     assert!(!markdown.contains("`/test/project/app/templates/base.html`"));
     assert!(!markdown.contains("Template not found."));
 }
+
+#[test]
+fn template_block_hover_lists_the_override_chain() {
+    let mut db = TestDatabase::new();
+    let base_source = "{% block title %}Base{% endblock %}";
+    let middle_source = "{% extends \"base.html\" %}\n{% block title %}Middle{% endblock %}";
+    let child_source = "{% extends \"middle.html\" %}\n\n{% block title %}Child{% endblock %}";
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/test/project/templates/base.html", base_source)
+        .file("/test/project/templates/middle.html", middle_source)
+        .file("/test/project/templates/child.html", child_source)
+        .install(&mut db)
+        .expect("block override fixture should install");
+    let middle = db
+        .file(Utf8Path::new("/test/project/templates/middle.html"))
+        .expect("middle template fixture should exist");
+    let offset = Offset::new(
+        u32::try_from(
+            middle_source
+                .find("title")
+                .expect("test source should contain the block name"),
+        )
+        .expect("test source offset should fit in u32"),
+    );
+
+    let result = hover(&db, middle, offset).expect("block name should have hover");
+    let markdown = hover_markdown(result).expect("block hover should use markup content");
+
+    assert!(markdown.starts_with("```text\n(block) title\n```"), "{markdown}");
+    assert!(
+        markdown.contains(
+            "Defined in:\n\n\
+             1. [`base.html`:1](file:///test/project/templates/base.html#L1)\n\
+             2. [`middle.html`:2](file:///test/project/templates/middle.html#L2) (this template)"
+        ),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains(
+            "Overridden in:\n\n- [`child.html`:3](file:///test/project/templates/child.html#L3)"
+        ),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn template_block_hover_names_the_overridden_admin_template() {
+    let mut db = TestDatabase::new();
+    let source = "{% extends \"admin/change_form.html\" %}\n\
+                  {% block field_sets %}{% endblock %}{% block fieldsets %}{% endblock %}";
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/test/project/templates/admin/polls/question/change_form.html", source)
+        .install(&mut db)
+        .expect("admin override fixture should install");
+    let file = db
+        .file(Utf8Path::new(
+            "/test/project/templates/admin/polls/question/change_form.html",
+        ))
+        .expect("admin override fixture should exist");
+    let hover_block = |name: &str| {
+        let offset = Offset::new(
+            u32::try_from(
+                source
+                    .find(&format!("block {name}"))
+                    .expect("test source should contain the block name")
+                    + "block ".len(),
+            )
+            .expect("test source offset should fit in u32"),
+        );
+        let result = hover(&db, file, offset).expect("block name should have hover");
+        hover_markdown(result).expect("block hover should use markup content")
+    };
+
+    let markdown = hover_block("field_sets");
+    assert!(
+        markdown.contains(
+            "Overrides `admin/change_form.html` from `django.contrib.admin` for model \
+             `polls.question`. `admin/change_form.html` defines this block."
+        ),
+        "{markdown}"
+    );
+    let markdown = hover_block("fieldsets");
+    assert!(
+        markdown.contains("`admin/change_form.html` does not define this block."),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn settings_attribute_hover_shows_the_declared_type() {
+    let mut db = TestDatabase::new();
+    let source = "{{ settings.SITE_NAME }}{{ settings.MISSING }}";
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nSITE_NAME = 'Shop'\n",
+        )
+        .file("/test/project/templates/page.html", source)
+        .install(&mut db)
+        .expect("settings hover fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("settings hover fixture file should exist");
+    let offset_of = |needle: &str| {
+        let offset = source.find(needle).expect("test source should contain the needle");
+        Offset::new(u32::try_from(offset).expect("test source offset should fit in u32"))
+    };
+
+    let markdown = hover_markdown(hover(&db, file, offset_of("SITE_NAME")).expect("setting hover"))
+        .expect("setting hover should use markup content");
+
+    assert_eq!(markdown, "```text\n(setting) SITE_NAME\n```\n---\nType: `str`");
+    assert!(hover(&db, file, offset_of("MISSING")).is_none());
+}
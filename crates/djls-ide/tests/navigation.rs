@@ -1141,6 +1141,56 @@ fn goto_definition_does_not_guess_unloaded_or_member_callable_targets() {
     );
 }
 
+#[test]
+fn goto_definition_follows_url_names_to_views() {
+    let mut db = TestDatabase::new();
+    let source = r#"{% url "blog:detail" pk=1 %}{% url 'blog:feed' %}{% url name %}"#;
+    ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "ROOT_URLCONF = 'testproject.urls'\nINSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file(
+            "/test/project/testproject/urls.py",
+            "from django.urls import include, path\n\nurlpatterns = [path('blog/', include('blog.urls'))]\n",
+        )
+        .file("/test/project/blog/__init__.py", "")
+        .file(
+            "/test/project/blog/urls.py",
+            "from django.urls import path\nfrom blog.views import PostDetail\n\napp_name = 'blog'\nurlpatterns = [\n    path('<int:pk>/', PostDetail.as_view(), name='detail'),\n    path('feed/', make_feed(), name='feed'),\n]\n",
+        )
+        .file(
+            "/test/project/blog/views.py",
+            "from django.views.generic import DetailView\n\nclass PostDetail(DetailView):\n    pass\n",
+        )
+        .file("/test/project/templates/page.html", source)
+        .install(&mut db)
+        .expect("url navigation project fixture should install");
+    let file = db
+        .file(Utf8Path::new("/test/project/templates/page.html"))
+        .expect("page template fixture should exist");
+
+    let Some(ls_types::GotoDefinitionResponse::Scalar(view)) =
+        goto_definition(&db, file, offset_of(source, "detail"), false)
+    else {
+        panic!("a resolved view should be one exact target")
+    };
+    assert_eq!(view.uri.as_str(), "file:///test/project/blog/views.py");
+    assert_eq!(view.range.start, ls_types::Position::new(2, 0));
+
+    // A view built at runtime falls back to the pattern that names it.
+    let Some(ls_types::GotoDefinitionResponse::Scalar(pattern)) =
+        goto_definition(&db, file, offset_of(source, "feed"), false)
+    else {
+        panic!("an unresolved view should fall back to its pattern")
+    };
+    assert_eq!(pattern.uri.as_str(), "file:///test/project/blog/urls.py");
+    assert_eq!(pattern.range.start, ls_types::Position::new(6, 4));
+
+    assert_eq!(goto_definition(&db, file, offset_of(source, "name"), false), None);
+}
+
 #[test]
 fn find_references_resolves_extends_with_the_source_origin_skipped() {
     let mut db = TestDatabase::new();
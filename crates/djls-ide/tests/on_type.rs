@@ -0,0 +1,89 @@
+use camino::Utf8Path;
+use djls_ide::on_type_formatting;
+use djls_source::Offset;
+use djls_source::PositionEncoding;
+use djls_testing::TestDatabase;
+use tower_lsp_server::ls_types;
+
+const PATH: &str = "/templates/page.html";
+
+type TestResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+fn edits(
+    source: &str,
+    ch: &str,
+    insert_end_tags: bool,
+) -> TestResult<Option<Vec<ls_types::TextEdit>>> {
+    let db = TestDatabase::new();
+    db.add_file(PATH, source)?;
+    let file = db.file(Utf8Path::new(PATH))?;
+    let offset = Offset::new(u32::try_from(source.len())?);
+    Ok(on_type_formatting(
+        &db,
+        file,
+        offset,
+        ch,
+        PositionEncoding::Utf16,
+        insert_end_tags,
+    ))
+}
+
+#[test]
+fn completes_partial_closer_for_innermost_block() {
+    let edits = edits(
+        "{% if user %}\n{% for item in items %}\n{{ item }}\n{% end%",
+        "%",
+        false,
+    )
+    .expect("template fixture should load")
+    .expect("partial closer should be completed");
+
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].new_text, "{% endfor %}");
+    assert_eq!(edits[0].range.start, ls_types::Position::new(3, 0));
+    assert_eq!(edits[0].range.end, ls_types::Position::new(3, 7));
+}
+
+#[test]
+fn leaves_closer_for_another_block_alone() {
+    assert!(
+        edits("{% for item in items %}\n{% endif %", "%", false)
+            .expect("template fixture should load")
+            .is_none()
+    );
+}
+
+#[test]
+fn inserts_end_tag_after_unclosed_opener_when_enabled() {
+    let source = "<ul>\n  {% for item in items %}";
+
+    assert!(
+        edits(source, "}", false)
+            .expect("template fixture should load")
+            .is_none()
+    );
+
+    let edits = edits(source, "}", true)
+        .expect("template fixture should load")
+        .expect("end tag should be inserted");
+    assert_eq!(edits[0].new_text, "\n  {% endfor %}");
+    assert_eq!(edits[0].range.start, ls_types::Position::new(1, 25));
+}
+
+#[test]
+fn skips_insert_when_block_is_already_closed() {
+    let source = "{% for item in items %}\n{% endfor %}";
+    let db = TestDatabase::new();
+    db.add_file(PATH, source).expect("test should add template fixture");
+    let file = db.file(Utf8Path::new(PATH)).expect("template fixture should be readable");
+
+    let result = on_type_formatting(
+        &db,
+        file,
+        Offset::new(23),
+        "}",
+        PositionEncoding::Utf16,
+        true,
+    );
+    assert!(result.is_none());
+}
@@ -0,0 +1,98 @@
+use camino::Utf8Path;
+use djls_ide::organize_loads_action;
+use djls_source::LineCol;
+use djls_source::LineIndex;
+use djls_source::PositionEncoding;
+use djls_testing::TestDatabase;
+use tower_lsp_server::ls_types;
+
+const TEMPLATE_PATH: &str = "/test/project/templates/template.html";
+
+/// The source after applying the organize action, or `None` when none is offered.
+fn organized(source: &str, merge_loads: bool) -> Option<String> {
+    let db = TestDatabase::new();
+    db.add_file(TEMPLATE_PATH, source)
+        .expect("fixture template should be added");
+    let file = db
+        .file(Utf8Path::new(TEMPLATE_PATH))
+        .expect("fixture template should exist");
+
+    let ls_types::CodeActionOrCommand::CodeAction(action) =
+        organize_loads_action(&db, file, merge_loads, PositionEncoding::Utf16)?
+    else {
+        panic!("organize loads should return a code action");
+    };
+    let mut edits = action
+        .edit
+        .and_then(|edit| edit.changes)
+        .and_then(|changes| changes.into_values().next())
+        .expect("organize loads should edit the template");
+
+    let line_index = LineIndex::from(source);
+    let offset = |position: ls_types::Position| {
+        line_index
+            .offset(
+                source,
+                LineCol::new(position.line, position.character),
+                PositionEncoding::Utf16,
+            )
+            .get() as usize
+    };
+    edits.sort_by_key(|edit| std::cmp::Reverse(offset(edit.range.start)));
+    let mut updated = source.to_string();
+    for edit in edits {
+        updated.replace_range(offset(edit.range.start)..offset(edit.range.end), &edit.new_text);
+    }
+    Some(updated)
+}
+
+#[test]
+fn merges_and_sorts_loads_below_extends() {
+    let source = "{% extends \"base.html\" %}\n\
+                  {% load static %}\n\
+                  {% block content %}\n\
+                  {% load i18n humanize %}\n\
+                  {% trans \"Hi\" %}\n\
+                  {% endblock %}\n";
+
+    assert_eq!(
+        organized(source, true).as_deref(),
+        Some(
+            "{% extends \"base.html\" %}\n\
+             {% load humanize i18n static %}\n\
+             {% block content %}\n\
+             {% trans \"Hi\" %}\n\
+             {% endblock %}\n"
+        )
+    );
+}
+
+#[test]
+fn keeps_one_tag_per_library_without_merging() {
+    let source = "{% load static i18n %}\n<p>{% trans \"Hi\" %}</p>\n";
+
+    assert_eq!(
+        organized(source, false).as_deref(),
+        Some("{% load i18n %}\n{% load static %}\n<p>{% trans \"Hi\" %}</p>\n")
+    );
+}
+
+#[test]
+fn merges_selective_loads_from_one_library() {
+    let source = "{% load upper from custom %}\n\
+                  {% load i18n %}\n\
+                  {% load lower from custom %}\n\
+                  {% load trans from i18n %}\n";
+
+    assert_eq!(
+        organized(source, true).as_deref(),
+        Some("{% load i18n %}\n{% load lower upper from custom %}\n")
+    );
+}
+
+#[test]
+fn organized_loads_offer_nothing() {
+    let source = "{% extends \"base.html\" %}\n{% load i18n static %}\n{% block content %}{% endblock %}\n";
+
+    assert_eq!(organized(source, true), None);
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use camino::Utf8Path;
+use djls_ide::WarmCachePhase;
+use djls_ide::template_is_affected;
+use djls_project::TemplateInventoryImpact;
+use djls_testing::ProjectFixture;
+use djls_testing::SalsaEventLog;
+use djls_testing::TestDatabase;
+use djls_testing::standard_validation_db;
+
+type TestResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+const SETTINGS: &str = "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/project/templates']}]\n";
+
+/// Warm-up parses at most this many Templates.
+const WARM_PARSE_LIMIT: usize = 256;
+
+fn database_with_templates(events: SalsaEventLog, count: usize) -> TestResult<TestDatabase> {
+    let mut db = TestDatabase::with_event_log(events);
+    (0..count)
+        .fold(
+            ProjectFixture::new("/project")
+                .django_settings_module("settings")
+                .file("/project/settings.py", SETTINGS),
+            |fixture, index| {
+                fixture.file(format!("/project/templates/page_{index}.html"), "{{ value }}")
+            },
+        )
+        .install(&mut db)?;
+    Ok(db)
+}
+
+fn execution_count(names: &[String], query: &str) -> usize {
+    names
+        .iter()
+        .filter(|name| name.rsplit("::").next() == Some(query))
+        .count()
+}
+
+#[test]
+fn parse_templates_phase_parses_every_discovered_template_once() {
+    let events = SalsaEventLog::default();
+    let db = database_with_templates(events.clone(), 1)
+        .expect("warmup project fixture should install");
+    events
+        .take()
+        .expect("initial warmup Salsa events should be cleared");
+
+    let part = WarmCachePhase::ParseTemplates.run(&db);
+    assert_eq!(part.count(), Some(1));
+    let names = events
+        .take_will_execute_names(&db)
+        .expect("parse warm-up Salsa events should be read");
+    assert_eq!(execution_count(&names, "parse_template"), 1);
+
+    assert_eq!(WarmCachePhase::ParseTemplates.run(&db).count(), Some(1));
+    let names = events
+        .take_will_execute_names(&db)
+        .expect("repeated parse warm-up Salsa events should be read");
+    assert_eq!(execution_count(&names, "parse_template"), 0);
+}
+
+#[test]
+fn parse_templates_phase_stops_at_its_limit() {
+    let events = SalsaEventLog::default();
+    let db = database_with_templates(events.clone(), WARM_PARSE_LIMIT + 1)
+        .expect("warmup project fixture should install");
+    events
+        .take()
+        .expect("initial warmup Salsa events should be cleared");
+
+    let part = WarmCachePhase::ParseTemplates.run(&db);
+    assert_eq!(part.count(), Some(WARM_PARSE_LIMIT));
+    let names = events
+        .take_will_execute_names(&db)
+        .expect("limited parse warm-up Salsa events should be read");
+    assert_eq!(execution_count(&names, "parse_template"), WARM_PARSE_LIMIT);
+}
+
+#[test]
+fn inventory_impact_affects_templates_that_load_or_use_a_changed_library() {
+    let db = standard_validation_db().expect("standard validation fixture should build");
+    for (path, source) in [
+        ("/loads.html", "{% load humanize %}{{ count }}"),
+        ("/selective.html", "{% load intcomma from humanize %}"),
+        ("/uses.html", "{{ count|intcomma }}"),
+        ("/argument.html", "{% if count|intcomma %}{% endif %}"),
+        ("/unrelated.html", "{% load static %}{% static 'app.css' %}"),
+    ] {
+        db.add_file(path, source)
+            .expect("template should be added to the test database");
+    }
+    let impact = TemplateInventoryImpact::Libraries {
+        libraries: BTreeSet::from(["humanize".to_string()]),
+        symbols: BTreeSet::from(["intcomma".to_string()]),
+    };
+    let affected = |path: &str, impact: &TemplateInventoryImpact| {
+        let file = db
+            .file(Utf8Path::new(path))
+            .expect("fixture file should exist in the test database");
+        template_is_affected(&db, file, impact)
+    };
+
+    for path in ["/loads.html", "/selective.html", "/uses.html", "/argument.html"] {
+        assert!(affected(path, &impact), "{path}");
+    }
+    assert!(!affected("/unrelated.html", &impact));
+    assert!(affected("/unrelated.html", &TemplateInventoryImpact::Everything));
+}
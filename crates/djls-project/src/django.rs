@@ -0,0 +1,121 @@
+//! The Django release installed in the project's environment.
+
+use std::fmt;
+
+use ruff_python_ast::Expr;
+use ruff_python_ast::Stmt;
+
+use crate::ast::ExprExt;
+use crate::db::Db as ProjectDb;
+use crate::project::Project;
+use crate::python::PythonModuleName;
+use crate::python::PythonSourceModule;
+use crate::python::RecoveredPythonModule;
+
+/// A Django feature release, such as 5.1.
+///
+/// Patch releases never add or remove template tags, so they are not tracked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DjangoVersion {
+    major: u16,
+    minor: u16,
+}
+
+impl DjangoVersion {
+    /// The newest release djls knows the built-in tags and filters of.
+    pub const LATEST: Self = Self::new(6, 1);
+
+    #[must_use]
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+
+    #[must_use]
+    pub const fn major(self) -> u16 {
+        self.major
+    }
+
+    #[must_use]
+    pub const fn minor(self) -> u16 {
+        self.minor
+    }
+}
+
+impl fmt::Display for DjangoVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// The version of the `django` package on the project's search paths.
+///
+/// Read from the `VERSION` tuple in `django/__init__.py`. `None` when Django is not found or
+/// its version cannot be read statically; callers should then assume [`DjangoVersion::LATEST`].
+#[salsa::tracked(returns(copy))]
+pub fn django_version(db: &dyn ProjectDb, project: Project) -> Option<DjangoVersion> {
+    let name = PythonModuleName::parse("django").ok()?;
+    let module = PythonSourceModule::resolve(db, project, name)?;
+    let parsed = RecoveredPythonModule::from_file(db, module.file()).ok()??;
+    version_from_body(parsed.body(db))
+}
+
+/// The last `VERSION = (major, minor, ...)` assignment in a module body.
+fn version_from_body(body: &[Stmt]) -> Option<DjangoVersion> {
+    body.iter().rev().find_map(|stmt| {
+        let Stmt::Assign(assign) = stmt else {
+            return None;
+        };
+        if !assign
+            .targets
+            .iter()
+            .any(|target| target.name_target() == Some("VERSION"))
+        {
+            return None;
+        }
+        let Expr::Tuple(tuple) = assign.value.as_ref() else {
+            return None;
+        };
+        let mut parts = tuple.elts.iter().map(|element| {
+            element
+                .non_negative_integer()
+                .and_then(|part| u16::try_from(part).ok())
+        });
+        Some(DjangoVersion::new(parts.next()??, parts.next()??))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_parser::parse_module;
+
+    use super::*;
+
+    fn version(source: &str) -> Option<DjangoVersion> {
+        let parsed = parse_module(source).expect("source should parse");
+        version_from_body(&parsed.syntax().body)
+    }
+
+    #[test]
+    fn reads_feature_release_from_version_tuple() {
+        let source = "from django.utils.version import get_version\n\nVERSION = (5, 1, 4, \"final\", 0)\n\n__version__ = get_version(VERSION)\n";
+        assert_eq!(version(source), Some(DjangoVersion::new(5, 1)));
+        assert_eq!(
+            version("VERSION = (4, 2, 0, 'alpha', 0)\nVERSION = (6, 0, 0, 'final', 0)\n"),
+            Some(DjangoVersion::new(6, 0))
+        );
+    }
+
+    #[test]
+    fn ignores_versions_that_are_not_literal_tuples() {
+        assert_eq!(version("VERSION = get_version()\n"), None);
+        assert_eq!(version("VERSION = (major, 1, 0)\n"), None);
+        assert_eq!(version("__version__ = '5.1'\n"), None);
+    }
+
+    #[test]
+    fn orders_and_displays_feature_releases() {
+        assert!(DjangoVersion::new(4, 2) < DjangoVersion::new(5, 0));
+        assert!(DjangoVersion::new(5, 1) < DjangoVersion::LATEST);
+        assert_eq!(DjangoVersion::new(5, 1).to_string(), "5.1");
+    }
+}
@@ -1,11 +1,14 @@
 mod ast;
 mod db;
 mod discovery;
+mod django;
 mod models;
 mod project;
 mod python;
 mod settings;
+mod status;
 mod templates;
+mod urls;
 
 pub use db::Db;
 pub use discovery::CountLabel;
@@ -22,6 +25,8 @@ pub use discovery::apply_project_facts;
 pub use discovery::environment_phases;
 pub use discovery::project_facts_phases;
 pub use discovery::run_django_discovery;
+pub use django::DjangoVersion;
+pub use django::django_version;
 pub use models::ModelGraph;
 pub use models::ModelId;
 pub use models::compute_model_graph;
@@ -40,32 +45,50 @@ pub use python::file_to_module;
 pub use python::file_to_module_resolution;
 pub use python::resolve_package_dirs;
 pub use python::resolve_prefix;
+pub use settings::DeclaredSetting;
+pub use settings::DeclaredSettings;
+pub use settings::declared_settings;
+pub use status::InventorySize;
+pub use status::ProjectStatus;
+pub use templates::AppTemplateDirectory;
 pub use templates::AppTemplateSymbolLookup;
 pub use templates::ArgumentCountConstraint;
 pub use templates::AsVar;
 pub use templates::BlockSpec;
 pub use templates::BlockSpecs;
 pub use templates::ChoiceAt;
+pub use templates::ContextProcessorVariable;
+pub use templates::ContextProcessorVariables;
 pub use templates::EffectiveDefinitionLibrary;
 pub use templates::ExtractedDiagnosticConstraint;
 pub use templates::ExtractedDiagnosticMessage;
 pub use templates::ExtractedMessageArg;
 pub use templates::ExtractedMessageTemplate;
+pub use templates::FallbackLibrary;
+pub use templates::FallbackPackage;
+pub use templates::FilterArgKind;
 pub use templates::FilterArity;
 pub use templates::FilterArityMap;
+pub use templates::FilterSignature;
+pub use templates::FilterSignatureMap;
 pub use templates::InconclusiveTemplateResolution;
 pub use templates::InvalidTemplateIdentifier;
+pub use templates::KeywordOperators;
 pub use templates::KnownOptions;
 pub use templates::LibraryName;
 pub use templates::LoadableLibraryLookup;
 pub use templates::MissingTemplateLibraryLookup;
+pub use templates::PythonTemplateReference;
 pub use templates::RequiredKeyword;
 pub use templates::ScopedTemplateLibraries;
 pub use templates::ScopedTemplateReferenceResolution;
 pub use templates::ScopedTemplateSymbolLookup;
+pub use templates::ShadowedTemplate;
 pub use templates::SplitPosition;
 pub use templates::SymbolDefinition;
 pub use templates::SymbolKey;
+pub use templates::TEMPLATE_NAME_CALLS;
+pub use templates::TEMPLATE_NAME_KEYWORDS;
 pub use templates::TagArgument;
 pub use templates::TagArgumentKind;
 pub use templates::TagRule;
@@ -73,6 +96,10 @@ pub use templates::TagRuleMap;
 pub use templates::TemplateBackendScope;
 pub use templates::TemplateDirectories;
 pub use templates::TemplateDoesNotExist;
+pub use templates::TemplateInventory;
+pub use templates::TemplateInventoryDiff;
+pub use templates::TemplateInventoryImpact;
+pub use templates::TemplateInventoryTag;
 pub use templates::TemplateLibrary;
 pub use templates::TemplateLibraryAppCandidates;
 pub use templates::TemplateLibraryCatalog;
@@ -92,15 +119,24 @@ pub use templates::TemplateSymbolCandidate;
 pub use templates::TemplateSymbolKind;
 pub use templates::TemplateSymbolName;
 pub use templates::TemplateSymbolSource;
+pub use templates::app_template_directories;
+pub use templates::context_processor_variables;
+pub use templates::python_template_references;
 pub use templates::resolve_relative_name;
 pub use templates::scoped_template_libraries;
+pub use templates::shadowed_templates;
 pub use templates::template_directories;
 pub use templates::template_library_catalog;
 pub use templates::template_library_definition_facts;
 pub use templates::template_library_filter_facts;
+pub use templates::template_library_filter_signatures;
 pub use templates::template_library_tag_facts;
 pub use templates::template_resolution;
 pub use templates::template_symbol_source;
+pub use urls::UrlRoute;
+pub use urls::UrlRoutes;
+pub use urls::ViewDefinition;
+pub use urls::url_routes;
 
 // Test and benchmark support only; not part of the stable Project Facts façade.
 #[doc(hidden)]
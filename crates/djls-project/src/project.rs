@@ -1,5 +1,7 @@
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::DialectsConfig;
+use djls_conf::PathMappings;
 use djls_conf::Settings;
 use djls_conf::TagSpecDef;
 use djls_source::FileSystem;
@@ -44,6 +46,12 @@ pub struct Project {
     /// Manual TagSpecs configuration from TOML (fallback for extraction gaps)
     #[returns(ref)]
     pub tagspecs: TagSpecDef,
+    /// Template language per directory from `[dialects]`
+    #[returns(ref)]
+    pub dialects: DialectsConfig,
+    /// Container-to-workspace path prefixes from `[path_mappings]`
+    #[returns(ref)]
+    pub path_mappings: PathMappings,
 }
 
 impl Project {
@@ -89,6 +97,8 @@ impl Project {
         let pythonpath = settings.pythonpath().to_vec();
         let env_vars = Vec::new();
         let tagspecs = settings.tagspecs().clone();
+        let dialects = settings.dialects().clone();
+        let path_mappings = settings.path_mappings().clone();
 
         search_paths.register_roots(db);
         Project::builder(
@@ -99,6 +109,8 @@ impl Project {
             pythonpath,
             env_vars,
             tagspecs,
+            dialects,
+            path_mappings,
         )
         .durability(Durability::MEDIUM)
         .root_durability(Durability::HIGH)
@@ -117,6 +129,8 @@ impl Project {
         );
         let pythonpath = settings.pythonpath().to_vec();
         let tagspecs = settings.tagspecs().clone();
+        let dialects = settings.dialects().clone();
+        let path_mappings = settings.path_mappings().clone();
 
         search_paths.register_roots(db);
         Project::builder(
@@ -127,6 +141,8 @@ impl Project {
             pythonpath,
             env_vars,
             tagspecs,
+            dialects,
+            path_mappings,
         )
         .durability(Durability::MEDIUM)
         .root_durability(Durability::HIGH)
@@ -141,6 +157,8 @@ impl Project {
         let env_vars = load_env_file(db.file_system(), &root, settings);
         let pythonpath = settings.pythonpath().to_vec();
         let tagspecs = settings.tagspecs().clone();
+        let dialects = settings.dialects().clone();
+        let path_mappings = settings.path_mappings().clone();
 
         if self.interpreter(db) != &interpreter {
             self.set_interpreter(db).to(interpreter);
@@ -162,6 +180,14 @@ impl Project {
         if self.tagspecs(db) != &tagspecs {
             self.set_tagspecs(db).to(tagspecs);
         }
+
+        if self.dialects(db) != &dialects {
+            self.set_dialects(db).to(dialects);
+        }
+
+        if self.path_mappings(db) != &path_mappings {
+            self.set_path_mappings(db).to(path_mappings);
+        }
     }
 }
 
@@ -356,6 +356,22 @@ impl PythonValue {
         Some(value)
     }
 
+    /// The Python type name of a value whose type is known statically.
+    pub(crate) fn type_name(&self) -> Option<&'static str> {
+        match &self.kind {
+            PythonValueKind::Str(_) => Some("str"),
+            PythonValueKind::Bool(_) => Some("bool"),
+            PythonValueKind::Path(_) => Some("Path"),
+            PythonValueKind::List(_) => Some("list"),
+            PythonValueKind::Tuple(_) => Some("tuple"),
+            PythonValueKind::Dict(_) => Some("dict"),
+            PythonValueKind::Module(_) => Some("module"),
+            PythonValueKind::Intrinsic(_)
+            | PythonValueKind::UnsupportedLiteral
+            | PythonValueKind::Unknown(_) => None,
+        }
+    }
+
     /// Intentional owned structural projection for the stable test adapter.
     pub(crate) fn into_kind(self) -> PythonValueKind {
         self.kind
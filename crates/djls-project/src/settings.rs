@@ -1,7 +1,11 @@
+mod declared;
 mod extraction;
 mod sources;
 pub(crate) mod types;
 
+pub use declared::DeclaredSetting;
+pub use declared::DeclaredSettings;
+pub use declared::declared_settings;
 use djls_source::File;
 pub(crate) use sources::DjangoSettingsSources;
 pub(crate) use sources::settings_sources;
@@ -11,7 +15,7 @@ use crate::db::Db as ProjectDb;
 use crate::project::Project;
 use crate::python::PythonSourceModule;
 
-fn settings_module(db: &dyn ProjectDb, project: Project) -> Option<PythonSourceModule> {
+pub(crate) fn settings_module(db: &dyn ProjectDb, project: Project) -> Option<PythonSourceModule> {
     let django_settings_module = project.django_settings_module(db).as_ref()?.clone();
     PythonSourceModule::resolve(db, project, django_settings_module)
 }
@@ -0,0 +1,97 @@
+//! Names the settings module declares, for editor features that surface them.
+
+use crate::db::Db as ProjectDb;
+use crate::project::Project;
+use crate::python::evaluation::PythonBindingState;
+use crate::python::evaluation::python_module_facts;
+use crate::settings::settings_module;
+
+/// An uppercase module-level name in the settings module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeclaredSetting {
+    name: String,
+    types: Vec<&'static str>,
+}
+
+impl DeclaredSetting {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Python type names of the values the setting may hold, sorted. Empty
+    /// when no alternative has a statically known type.
+    #[must_use]
+    pub fn types(&self) -> &[&'static str] {
+        &self.types
+    }
+}
+
+/// Settings declared by the project's settings module, sorted by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeclaredSettings {
+    settings: Vec<DeclaredSetting>,
+}
+
+impl DeclaredSettings {
+    pub fn iter(&self) -> impl Iterator<Item = &DeclaredSetting> {
+        self.settings.iter()
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&DeclaredSetting> {
+        self.settings
+            .binary_search_by(|setting| setting.name.as_str().cmp(name))
+            .ok()
+            .and_then(|index| self.settings.get(index))
+    }
+}
+
+/// Collect the uppercase names bound by the settings module, including those
+/// star-imported from other settings files, with the types they may hold.
+///
+/// Only types are recorded: settings values often hold secrets that have no
+/// business appearing in completion details or hovers.
+#[salsa::tracked(returns(ref))]
+pub fn declared_settings(db: &dyn ProjectDb, project: Project) -> DeclaredSettings {
+    let Some(module) = settings_module(db, project) else {
+        return DeclaredSettings::default();
+    };
+    let Ok(facts) = python_module_facts(db, project, module) else {
+        return DeclaredSettings::default();
+    };
+
+    // `bindings` is a BTreeMap, so the settings come out sorted.
+    let settings = facts
+        .bindings
+        .iter()
+        .filter(|(name, _)| is_setting_name(name))
+        .filter_map(|(name, binding)| {
+            let mut bound = false;
+            let mut types = Vec::new();
+            for alternative in binding.alternatives() {
+                if let PythonBindingState::Bound(value) = alternative {
+                    bound = true;
+                    types.extend(value.value.type_name());
+                }
+            }
+            types.sort_unstable();
+            types.dedup();
+            bound.then(|| DeclaredSetting {
+                name: name.clone(),
+                types,
+            })
+        })
+        .collect();
+
+    DeclaredSettings { settings }
+}
+
+/// Django only reads uppercase module attributes as settings.
+fn is_setting_name(name: &str) -> bool {
+    !name.starts_with('_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
@@ -42,6 +42,7 @@ use crate::settings::types::TemplateBackendEvidence;
 use crate::settings::types::TemplateBackends;
 use crate::settings::types::TemplateContextProcessorPath;
 use crate::settings::types::TemplateDirectoryPath;
+use crate::settings::types::TemplateLoader;
 use crate::settings::types::TemplateSettingAlternatives;
 use crate::settings::types::WithOrigins;
 
@@ -605,6 +606,7 @@ fn template_settings_case(
                     libraries: backend.libraries.known.clone(),
                     builtins: backend.builtins.known.clone(),
                     context_processors: backend.context_processors.known.clone(),
+                    loaders: backend.loaders.known.clone(),
                 })
             }
             TemplateBackendEvidence::Backend(_) | TemplateBackendEvidence::Issue(_) => None,
@@ -633,6 +635,7 @@ fn partial_backend(
         libraries: SettingFieldEvidence::new(Vec::new()),
         builtins: SettingFieldEvidence::new(Vec::new()),
         context_processors: SettingFieldEvidence::new(Vec::new()),
+        loaders: SettingFieldEvidence::new(None),
     };
 
     let (backend_value, mut issues) = dict_field(mapping, "BACKEND");
@@ -744,6 +747,14 @@ fn extract_options(options: PythonMapping<'_>, backend: &mut PartialTemplateBack
         backend.builtins.issues.append(&mut issues);
     }
 
+    let (loaders_value, mut issues) = dict_field(options, "loaders");
+    backend.loaders.issues.append(&mut issues);
+    if let Some(value) = loaders_value {
+        let mut loaders = Vec::new();
+        extract_loaders(value, &mut loaders, &mut backend.loaders.issues);
+        backend.loaders.known = Some(loaders);
+    }
+
     let (processors_value, mut issues) = dict_field(options, "context_processors");
     backend.context_processors.issues.append(&mut issues);
     if let Some(value) = processors_value {
@@ -803,6 +814,97 @@ fn extract_options(options: PythonMapping<'_>, backend: &mut PartialTemplateBack
     }
 }
 
+const CACHED_LOADER: &str = "django.template.loaders.cached.Loader";
+const FILESYSTEM_LOADER: &str = "django.template.loaders.filesystem.Loader";
+const APP_DIRECTORIES_LOADER: &str = "django.template.loaders.app_directories.Loader";
+
+fn extract_loaders(
+    value: &PythonValue,
+    loaders: &mut Vec<WithOrigins<TemplateLoader>>,
+    issues: &mut Vec<SettingIssue>,
+) {
+    let Some(sequence) = collection_sequence(value) else {
+        if value.unknown_value().is_some() {
+            issues.push(unknown_value_issue(value));
+        } else {
+            issues.push(value_issue(SettingIssueKind::InvalidShape, value));
+        }
+        return;
+    };
+    for item in sequence.semantic_items() {
+        match item {
+            PythonSequenceItem::Value(value) => extract_loader(value, loaders, issues),
+            PythonSequenceItem::UnknownElement(unknown) => {
+                issues.push(issue(SettingIssueKind::UnknownElement, unknown.origins()));
+            }
+            PythonSequenceItem::UnknownUnpack(unknown) => {
+                issues.push(issue(SettingIssueKind::UnknownUnpack, unknown.origins()));
+            }
+        }
+    }
+}
+
+/// Record one loader entry: a dotted path, or a `(path, *args)` tuple.
+fn extract_loader(
+    value: &PythonValue,
+    loaders: &mut Vec<WithOrigins<TemplateLoader>>,
+    issues: &mut Vec<SettingIssue>,
+) {
+    let (path_value, arguments) = match collection_sequence(value) {
+        Some(sequence) => match sequence.semantic_items() {
+            [PythonSequenceItem::Value(path_value), arguments @ ..] => (path_value, arguments),
+            [PythonSequenceItem::UnknownElement(unknown), ..] => {
+                issues.push(issue(SettingIssueKind::UnknownElement, unknown.origins()));
+                return;
+            }
+            [PythonSequenceItem::UnknownUnpack(unknown), ..] => {
+                issues.push(issue(SettingIssueKind::UnknownUnpack, unknown.origins()));
+                return;
+            }
+            [] => {
+                issues.push(value_issue(SettingIssueKind::InvalidShape, value));
+                return;
+            }
+        },
+        None => (value, &[][..]),
+    };
+    if let Some(scalar) = path_value.known_scalar()
+        && let Some(path) = scalar.string_value()
+    {
+        if path != CACHED_LOADER {
+            loaders.push(WithOrigins::new(
+                loader_from_path(path, !arguments.is_empty()),
+                scalar.first_origin(),
+                scalar.additional_origins(),
+            ));
+            return;
+        }
+        match arguments {
+            [PythonSequenceItem::Value(wrapped)] => extract_loaders(wrapped, loaders, issues),
+            [PythonSequenceItem::UnknownElement(unknown)] => {
+                issues.push(issue(SettingIssueKind::UnknownElement, unknown.origins()));
+            }
+            [.., PythonSequenceItem::UnknownUnpack(unknown)] => {
+                issues.push(issue(SettingIssueKind::UnknownUnpack, unknown.origins()));
+            }
+            // The cached loader can't be constructed without the loaders it wraps.
+            _ => issues.push(value_issue(SettingIssueKind::InvalidShape, value)),
+        }
+    } else if path_value.unknown_value().is_some() {
+        issues.push(unknown_value_issue(path_value));
+    } else {
+        issues.push(value_issue(SettingIssueKind::InvalidShape, path_value));
+    }
+}
+
+fn loader_from_path(path: &str, has_arguments: bool) -> TemplateLoader {
+    match path {
+        FILESYSTEM_LOADER if !has_arguments => TemplateLoader::Filesystem,
+        APP_DIRECTORIES_LOADER if !has_arguments => TemplateLoader::AppDirectories,
+        _ => TemplateLoader::Other(path.to_string()),
+    }
+}
+
 fn extract_libraries(
     mapping: PythonMapping<'_>,
     libraries: &mut SettingFieldEvidence<Vec<(String, WithOrigins<PythonModuleName>)>>,
@@ -392,6 +392,24 @@ pub(crate) struct TemplateBackend {
     pub(crate) libraries: Vec<(String, WithOrigins<PythonModuleName>)>,
     pub(crate) builtins: Vec<WithOrigins<PythonModuleName>>,
     pub(crate) context_processors: Vec<WithOrigins<TemplateContextProcessorPath>>,
+    /// Explicit `OPTIONS["loaders"]`; `None` means Django's default loaders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) loaders: Option<Vec<WithOrigins<TemplateLoader>>>,
+}
+
+/// A template loader from `OPTIONS["loaders"]`, in search order.
+///
+/// The cached loader only memoizes its wrapped loaders, so it is recorded as
+/// the loaders it wraps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TemplateLoader {
+    /// `django.template.loaders.filesystem.Loader`, searching the backend's `DIRS`
+    Filesystem,
+    /// `django.template.loaders.app_directories.Loader`
+    AppDirectories,
+    /// Any other loader, including a filesystem loader given its own directories
+    Other(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -465,6 +483,12 @@ impl<T> SettingFieldEvidence<T> {
     }
 }
 
+impl<T> SettingFieldEvidence<Option<T>> {
+    pub(crate) fn is_unset(&self) -> bool {
+        self.known.is_none() && self.issues.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub(crate) struct PartialTemplateBackend {
     #[serde(skip)]
@@ -477,6 +501,8 @@ pub(crate) struct PartialTemplateBackend {
     pub(crate) builtins: SettingFieldEvidence<Vec<WithOrigins<PythonModuleName>>>,
     pub(crate) context_processors:
         SettingFieldEvidence<Vec<WithOrigins<TemplateContextProcessorPath>>>,
+    #[serde(skip_serializing_if = "SettingFieldEvidence::is_unset")]
+    pub(crate) loaders: SettingFieldEvidence<Option<Vec<WithOrigins<TemplateLoader>>>>,
 }
 
 impl PartialTemplateBackend {
@@ -488,6 +514,7 @@ impl PartialTemplateBackend {
             || !self.libraries.issues.is_empty()
             || !self.builtins.issues.is_empty()
             || !self.context_processors.issues.is_empty()
+            || !self.loaders.issues.is_empty()
     }
 
     pub(crate) fn is_malformed(&self) -> bool {
@@ -511,6 +538,7 @@ impl PartialTemplateBackend {
             .chain(&self.libraries.issues)
             .chain(&self.builtins.issues)
             .chain(&self.context_processors.issues)
+            .chain(&self.loaders.issues)
     }
 }
 
@@ -591,6 +619,10 @@ impl TemplateContextProcessorPath {
         let name = PythonModuleName::parse(path)?;
         Ok(Self(name.into_string()))
     }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 macro_rules! merge_struct_fields {
@@ -617,6 +649,7 @@ equality_is_semantic!(
     PythonModuleName,
     TemplateDirectoryPath,
     TemplateContextProcessorPath,
+    TemplateLoader,
 );
 
 merge_struct_fields!(InstalledApps { apps });
@@ -711,6 +744,7 @@ impl MergeEvidence for TemplateBackend {
             && merged
                 .context_processors
                 .merge_evidence(&other.context_processors)
+            && merged.loaders.merge_evidence(&other.loaders)
         {
             *self = merged;
             true
@@ -782,6 +816,7 @@ merge_struct_fields!(PartialTemplateBackend {
     libraries,
     builtins,
     context_processors,
+    loaders,
 });
 fn same_path_origin_files(
     left: &[WithOrigins<TemplateDirectoryPath>],
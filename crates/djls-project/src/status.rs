@@ -0,0 +1,69 @@
+//! A summary of what discovery found for a project, for status displays.
+
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::db::Db as ProjectDb;
+use crate::django::django_version;
+use crate::project::Project;
+use crate::python::SearchPath;
+use crate::templates::TemplateSymbolKind;
+use crate::templates::template_library_catalog;
+
+/// The environment, Django release, and template inventory of a project.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatus {
+    /// The `site-packages` directory installed packages are read from, when
+    /// a virtual environment was found.
+    pub environment: Option<Utf8PathBuf>,
+    pub settings_module: Option<String>,
+    pub django_version: Option<String>,
+    pub inventory: InventorySize,
+}
+
+/// How many libraries, tags, and filters templates can use.
+///
+/// Libraries only offered by an app that is not installed are not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventorySize {
+    pub libraries: usize,
+    pub tags: usize,
+    pub filters: usize,
+}
+
+impl ProjectStatus {
+    #[must_use]
+    pub fn new(db: &dyn ProjectDb, project: Project) -> Self {
+        let environment = project
+            .search_paths(db)
+            .iter()
+            .find(|search_path| matches!(search_path, SearchPath::SitePackages(_)))
+            .map(|search_path| search_path.path().to_path_buf());
+
+        let mut inventory = InventorySize::default();
+        for library in template_library_catalog(db, project).libraries() {
+            if library.available_in_app_module().is_some() {
+                continue;
+            }
+            inventory.libraries += 1;
+            for symbol in library.symbols() {
+                match symbol.kind {
+                    TemplateSymbolKind::Tag => inventory.tags += 1,
+                    TemplateSymbolKind::Filter => inventory.filters += 1,
+                }
+            }
+        }
+
+        Self {
+            environment,
+            settings_module: project
+                .django_settings_module(db)
+                .as_ref()
+                .map(|module| module.as_str().to_string()),
+            django_version: django_version(db, project).map(|version| version.to_string()),
+            inventory,
+        }
+    }
+}
@@ -1,7 +1,11 @@
 mod candidates;
+mod context;
+mod fallback;
 mod filters;
+mod inventory;
 mod libraries;
 mod names;
+mod python_references;
 mod registrations;
 mod resolution;
 mod scoped_libraries;
@@ -10,8 +14,20 @@ mod symbols;
 mod tags;
 
 pub(crate) use candidates::discover_templatetag_candidate_paths;
+pub use context::ContextProcessorVariable;
+pub use context::ContextProcessorVariables;
+pub use context::context_processor_variables;
+pub use fallback::FallbackLibrary;
+pub use fallback::FallbackPackage;
+pub use filters::FilterArgKind;
 pub use filters::FilterArity;
 pub use filters::FilterArityMap;
+pub use filters::FilterSignature;
+pub use filters::FilterSignatureMap;
+pub use inventory::TemplateInventory;
+pub use inventory::TemplateInventoryDiff;
+pub use inventory::TemplateInventoryImpact;
+pub use inventory::TemplateInventoryTag;
 pub use libraries::AppTemplateSymbolLookup;
 pub use libraries::EffectiveDefinitionLibrary;
 pub use libraries::LoadableLibraryLookup;
@@ -31,16 +47,23 @@ pub use libraries::template_library_catalog;
 pub use names::InvalidTemplateIdentifier;
 pub use names::LibraryName;
 pub use names::TemplateSymbolName;
+pub use python_references::PythonTemplateReference;
+pub use python_references::TEMPLATE_NAME_CALLS;
+pub use python_references::TEMPLATE_NAME_KEYWORDS;
+pub use python_references::python_template_references;
 pub(crate) use registrations::RegistrationKind;
 pub use registrations::TemplateLibraryDefinitionFacts;
 pub use registrations::TemplateLibraryFilterFacts;
 pub use registrations::TemplateLibraryTagFacts;
 pub use registrations::template_library_definition_facts;
 pub use registrations::template_library_filter_facts;
+pub use registrations::template_library_filter_signatures;
 pub use registrations::template_library_tag_facts;
 pub use registrations::template_symbol_source;
+pub use resolution::AppTemplateDirectory;
 pub use resolution::InconclusiveTemplateResolution;
 pub use resolution::ScopedTemplateReferenceResolution;
+pub use resolution::ShadowedTemplate;
 pub use resolution::TemplateBackendScope;
 pub use resolution::TemplateDirectories;
 pub use resolution::TemplateDoesNotExist;
@@ -48,7 +71,9 @@ pub use resolution::TemplateName;
 pub use resolution::TemplateOrigin;
 pub use resolution::TemplateResolution;
 pub use resolution::TemplateResolutionResult;
+pub use resolution::app_template_directories;
 pub use resolution::resolve_relative_name;
+pub use resolution::shadowed_templates;
 pub use resolution::template_directories;
 pub use resolution::template_resolution;
 pub use scoped_libraries::ScopedTemplateLibraries;
@@ -67,6 +92,7 @@ pub use tags::ExtractedDiagnosticConstraint;
 pub use tags::ExtractedDiagnosticMessage;
 pub use tags::ExtractedMessageArg;
 pub use tags::ExtractedMessageTemplate;
+pub use tags::KeywordOperators;
 pub use tags::KnownOptions;
 pub use tags::RequiredKeyword;
 pub use tags::SplitPosition;
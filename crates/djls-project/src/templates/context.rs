@@ -0,0 +1,269 @@
+//! Variables that `OPTIONS["context_processors"]` add to every `RequestContext`.
+
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+
+use ruff_python_ast::Expr;
+use ruff_python_ast::Stmt;
+
+use crate::ast::ExprExt;
+use crate::ast::Recurse;
+use crate::ast::walk_stmts;
+use crate::db::Db as ProjectDb;
+use crate::project::Project;
+use crate::python::PythonModuleName;
+use crate::python::PythonSourceModule;
+use crate::python::RecoveredPythonModule;
+use crate::templates::settings_cases::template_settings_cases;
+
+const DJANGO_TEMPLATES_BACKEND: &str = "django.template.backends.django.DjangoTemplates";
+
+/// Processors Django's engine runs for every request, ahead of the configured ones.
+const ENGINE_CONTEXT_PROCESSORS: &[&str] = &["django.template.context_processors.csrf"];
+
+/// Keys added by Django's own processors. Django's source is often outside the
+/// Python search paths, and several of these fill their dict conditionally.
+const DJANGO_CONTEXT_PROCESSORS: &[(&str, &[&str])] = &[
+    ("django.contrib.auth.context_processors.auth", &["perms", "user"]),
+    (
+        "django.contrib.messages.context_processors.messages",
+        &["DEFAULT_MESSAGE_LEVELS", "messages"],
+    ),
+    ("django.template.context_processors.csp", &["csp_nonce"]),
+    ("django.template.context_processors.csrf", &["csrf_token"]),
+    ("django.template.context_processors.debug", &["debug", "sql_queries"]),
+    (
+        "django.template.context_processors.i18n",
+        &["LANGUAGES", "LANGUAGE_BIDI", "LANGUAGE_CODE"],
+    ),
+    ("django.template.context_processors.media", &["MEDIA_URL"]),
+    ("django.template.context_processors.request", &["request"]),
+    ("django.template.context_processors.static", &["STATIC_URL"]),
+    ("django.template.context_processors.tz", &["TIME_ZONE"]),
+];
+
+/// A variable a context processor adds to Templates rendered with a request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextProcessorVariable {
+    name: String,
+    processor: String,
+}
+
+impl ContextProcessorVariable {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Dotted path of the processor that adds this variable.
+    #[must_use]
+    pub fn processor(&self) -> &str {
+        &self.processor
+    }
+}
+
+/// Variables the configured context processors add, sorted by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextProcessorVariables {
+    variables: Vec<ContextProcessorVariable>,
+}
+
+impl ContextProcessorVariables {
+    pub fn iter(&self) -> impl Iterator<Item = &ContextProcessorVariable> {
+        self.variables.iter()
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.variables
+            .binary_search_by(|variable| variable.name.as_str().cmp(name))
+            .is_ok()
+    }
+}
+
+/// Collect the variables added by the context processors of every feasible
+/// `DjangoTemplates` backend.
+///
+/// Django's own processors come from a fixed table. Project processors are
+/// read from the function's source: the string keys of a returned dict
+/// literal or `dict(...)` call, or of a local dict filled by subscript
+/// assignment or `.update()` and returned by name. Keys built at runtime are
+/// missed, so the result is a lower bound.
+#[salsa::tracked(returns(ref))]
+pub fn context_processor_variables(
+    db: &dyn ProjectDb,
+    project: Project,
+) -> ContextProcessorVariables {
+    let mut processors = Vec::new();
+    for settings_case in template_settings_cases(db, project).settings_cases() {
+        for backend in settings_case.backends() {
+            if backend.backend_name() != Some(DJANGO_TEMPLATES_BACKEND) {
+                continue;
+            }
+            let configured = backend.context_processors().iter().map(String::as_str);
+            for processor in ENGINE_CONTEXT_PROCESSORS.iter().copied().chain(configured) {
+                if !processors.contains(&processor) {
+                    processors.push(processor);
+                }
+            }
+        }
+    }
+
+    let mut by_name = BTreeMap::new();
+    for processor in processors {
+        for name in processor_keys(db, project, processor) {
+            by_name
+                .entry(name)
+                .or_insert_with(|| processor.to_string());
+        }
+    }
+
+    ContextProcessorVariables {
+        variables: by_name
+            .into_iter()
+            .map(|(name, processor)| ContextProcessorVariable { name, processor })
+            .collect(),
+    }
+}
+
+fn processor_keys(db: &dyn ProjectDb, project: Project, processor: &str) -> Vec<String> {
+    if let Some((_, keys)) = DJANGO_CONTEXT_PROCESSORS
+        .iter()
+        .find(|(path, _)| *path == processor)
+    {
+        return keys.iter().map(|key| (*key).to_string()).collect();
+    }
+
+    let Some((module, function)) = processor.rsplit_once('.') else {
+        return Vec::new();
+    };
+    let Ok(name) = PythonModuleName::parse(module) else {
+        return Vec::new();
+    };
+    let Some(module) = PythonSourceModule::resolve(db, project, name) else {
+        return Vec::new();
+    };
+    let Ok(Some(parsed)) = RecoveredPythonModule::from_file(db, module.file()) else {
+        return Vec::new();
+    };
+
+    // A later definition of the same name replaces an earlier one.
+    parsed
+        .body(db)
+        .iter()
+        .rev()
+        .find_map(|stmt| {
+            if let Stmt::FunctionDef(function_def) = stmt
+                && function_def.name.as_str() == function
+            {
+                Some(returned_keys(&function_def.body))
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// String keys of the dicts a function body returns.
+fn returned_keys(body: &[Stmt]) -> Vec<String> {
+    let mut locals: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut keys = Vec::new();
+    walk_stmts(body, Recurse::ControlFlow, |stmt| {
+        if let Stmt::Assign(assign) = stmt {
+            for target in &assign.targets {
+                if let Some(name) = target.name_target() {
+                    locals
+                        .entry(name)
+                        .or_default()
+                        .extend(dict_keys(&assign.value));
+                } else if let Expr::Subscript(subscript) = target
+                    && let Some(name) = subscript.value.name_target()
+                    && let Some(key) = subscript.slice.string_literal()
+                {
+                    locals.entry(name).or_default().push(key);
+                }
+            }
+        } else if let Stmt::Expr(statement) = stmt
+            && let Expr::Call(call) = statement.value.as_ref()
+            && let Expr::Attribute(attribute) = call.func.as_ref()
+            && attribute.attr.as_str() == "update"
+            && let Some(name) = attribute.value.name_target()
+        {
+            let updated = locals.entry(name).or_default();
+            updated.extend(call.arguments.args.iter().flat_map(dict_keys));
+            updated.extend(keyword_keys(call));
+        } else if let Stmt::Return(statement) = stmt
+            && let Some(value) = &statement.value
+        {
+            match value.name_target() {
+                Some(name) => keys.extend(locals.get(name).into_iter().flatten().copied()),
+                None => keys.extend(dict_keys(value)),
+            }
+        }
+        ControlFlow::Continue(())
+    });
+
+    keys.sort_unstable();
+    keys.dedup();
+    keys.into_iter().map(str::to_string).collect()
+}
+
+/// String keys of a dict literal or a `dict(...)` call.
+fn dict_keys(expr: &Expr) -> Vec<&str> {
+    if let Expr::Dict(dict) = expr {
+        dict.items
+            .iter()
+            .filter_map(|item| item.key.as_ref()?.string_literal())
+            .collect()
+    } else if let Expr::Call(call) = expr
+        && call.func.name_target() == Some("dict")
+    {
+        call.arguments
+            .args
+            .iter()
+            .flat_map(dict_keys)
+            .chain(keyword_keys(call))
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+fn keyword_keys(call: &ruff_python_ast::ExprCall) -> impl Iterator<Item = &str> {
+    call.arguments
+        .keywords
+        .iter()
+        .filter_map(|keyword| keyword.arg.as_ref().map(ruff_python_ast::Identifier::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn returned_keys_of(source: &str) -> Vec<String> {
+        let parsed = ruff_python_parser::parse_module(source).expect("source should parse");
+        let Some(Stmt::FunctionDef(function_def)) = parsed.syntax().body.first() else {
+            panic!("source should start with a function");
+        };
+        returned_keys(&function_def.body)
+    }
+
+    #[test]
+    fn returned_keys_follow_literals_calls_and_filled_locals() {
+        assert_eq!(
+            returned_keys_of("def p(request):\n    return {'site': 1, **extra}\n"),
+            ["site"]
+        );
+        assert_eq!(
+            returned_keys_of("def p(request):\n    return dict({'a': 1}, b=2)\n"),
+            ["a", "b"]
+        );
+        assert_eq!(
+            returned_keys_of(
+                "def p(request):\n    ctx = {'a': 1}\n    if request.user:\n        ctx['b'] = 2\n    ctx.update(c=3)\n    return ctx\n",
+            ),
+            ["a", "b", "c"]
+        );
+        assert!(returned_keys_of("def p(request):\n    return build(request)\n").is_empty());
+    }
+}
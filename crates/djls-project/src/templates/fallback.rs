@@ -0,0 +1,135 @@
+//! Template Libraries of popular third-party packages, bundled for projects whose Python
+//! environment cannot be inspected.
+//!
+//! Each file under `fallback/` holds the extraction results for one corpus package, written by
+//! `just corpus vendor-fallback-libraries`. When an installed app's package is on no search
+//! path, the catalog registers these libraries instead, so `{% load %}`, completion, and tag
+//! validation still know what the app provides.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::filters::FilterArity;
+use super::tags::BlockSpec;
+use super::tags::TagRule;
+
+/// Extraction results for the Template Libraries of one corpus package.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FallbackPackage {
+    /// The corpus entry the libraries were extracted from, e.g. `wagtail-7.3`.
+    pub corpus: String,
+    pub libraries: Vec<FallbackLibrary>,
+}
+
+/// One bundled Template Library and the facts extraction found for it.
+///
+/// Rules, arities, and block specs are keyed by symbol name within the library.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FallbackLibrary {
+    /// The `INSTALLED_APPS` entry whose `templatetags` package holds the library.
+    pub app: String,
+    pub load_name: String,
+    pub module: String,
+    pub tags: Vec<String>,
+    pub filters: Vec<String>,
+    pub tag_rules: BTreeMap<String, TagRule>,
+    pub filter_arities: BTreeMap<String, FilterArity>,
+    pub block_specs: BTreeMap<String, BlockSpec>,
+}
+
+const FALLBACK_SOURCES: &[(&str, &str)] = &[
+    (
+        "django-allauth.json",
+        include_str!("fallback/django-allauth.json"),
+    ),
+    (
+        "django-crispy-forms.json",
+        include_str!("fallback/django-crispy-forms.json"),
+    ),
+    ("wagtail.json", include_str!("fallback/wagtail.json")),
+];
+
+static FALLBACK_LIBRARIES: LazyLock<Vec<FallbackLibrary>> = LazyLock::new(|| {
+    FALLBACK_SOURCES
+        .iter()
+        .filter_map(|(name, source)| {
+            serde_json::from_str::<FallbackPackage>(source)
+                .inspect_err(|error| {
+                    tracing::error!("bundled fallback libraries {name} are malformed: {error}");
+                })
+                .ok()
+        })
+        .flat_map(|package| package.libraries)
+        .collect()
+});
+
+/// Bundled libraries that the installed app `app` provides.
+pub(crate) fn fallback_libraries_for_app(
+    app: &str,
+) -> impl Iterator<Item = &'static FallbackLibrary> {
+    FALLBACK_LIBRARIES
+        .iter()
+        .filter(move |library| library.app == app)
+}
+
+/// The bundled library registered by `module`, if any.
+pub(crate) fn fallback_library(module: &str) -> Option<&'static FallbackLibrary> {
+    FALLBACK_LIBRARIES
+        .iter()
+        .find(|library| library.module == module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_packages_parse_and_key_facts_by_declared_symbols() {
+        for (name, source) in FALLBACK_SOURCES {
+            let package = serde_json::from_str::<FallbackPackage>(source)
+                .unwrap_or_else(|error| panic!("{name} should parse: {error}"));
+            assert!(!package.libraries.is_empty(), "{name} should bundle libraries");
+            for library in &package.libraries {
+                assert!(
+                    library.module.starts_with(&format!("{}.templatetags.", library.app)),
+                    "{} should live in {}",
+                    library.module,
+                    library.app
+                );
+                for name in library.tag_rules.keys().chain(library.block_specs.keys()) {
+                    assert!(library.tags.contains(name), "{name} is not a bundled tag");
+                }
+                for name in library.filter_arities.keys() {
+                    assert!(library.filters.contains(name), "{name} is not a bundled filter");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn looks_up_libraries_by_app_and_module() {
+        let loads = fallback_libraries_for_app("crispy_forms")
+            .map(|library| library.load_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            loads,
+            [
+                "crispy_forms_field",
+                "crispy_forms_filters",
+                "crispy_forms_tags",
+                "crispy_forms_utils",
+            ]
+        );
+
+        let library =
+            fallback_library("allauth.templatetags.allauth").expect("allauth should be bundled");
+        assert_eq!(
+            library.block_specs["element"].end_tag.as_deref(),
+            Some("endelement")
+        );
+        assert!(fallback_library("debug_toolbar.templatetags.debug_toolbar").is_none());
+    }
+}
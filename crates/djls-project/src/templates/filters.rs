@@ -1,10 +1,23 @@
+use std::ops::ControlFlow;
+
+use ruff_python_ast::CmpOp;
+use ruff_python_ast::ExceptHandler;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Stmt;
 use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::visitor;
+use ruff_python_ast::visitor::Visitor;
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::ast::ExprExt;
+use crate::ast::Recurse;
+use crate::ast::walk_stmts;
 use crate::templates::SymbolKey;
 pub type FilterArityMap = FxHashMap<SymbolKey, FilterArity>;
+pub type FilterSignatureMap = FxHashMap<SymbolKey, FilterSignature>;
 
 /// Filter argument arity extracted from the filter function's signature.
 ///
@@ -16,6 +29,152 @@ pub struct FilterArity {
     pub expects_arg: bool,
     /// Whether the argument is optional (has a default value).
     pub arg_optional: bool,
+    /// The literal shape the argument needs, when the filter body makes it knowable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arg_kind: Option<FilterArgKind>,
+}
+
+/// Literal shape a filter argument needs, inferred from how the filter body uses it.
+///
+/// Only shapes that Django would silently mishandle are recorded: the filter
+/// still renders, but with the value unchanged or an empty string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterArgKind {
+    /// The argument is converted with `int(arg)` (e.g. `truncatewords`).
+    Integer,
+    /// The argument is passed as the format to `date_format`/`time_format` (e.g. `date`).
+    DateFormat,
+    /// The argument is split on a literal delimiter and the item count is
+    /// guarded by an early return (e.g. `pluralize`, `yesno`).
+    Delimited {
+        delimiter: String,
+        min_items: Option<usize>,
+        max_items: Option<usize>,
+    },
+}
+
+/// Parameter names, argument default, and decorator flags of a filter function.
+///
+/// Kept apart from [`FilterArity`] because none of it affects validation; hover shows it as a
+/// signature such as `floatformat(text, arg=-1)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterSignature {
+    /// The parameter that receives the value being filtered.
+    pub value_param: String,
+    /// The parameter that receives the argument after the colon, if the filter takes one.
+    pub arg_param: Option<String>,
+    /// The argument's default as Python source, or `...` when it is not a simple literal.
+    pub arg_default: Option<String>,
+    /// Whether the filter is registered with `is_safe=True`.
+    pub is_safe: bool,
+    /// Whether the filter is decorated with `@stringfilter`.
+    pub stringfilter: bool,
+    /// Whether the filter is registered with `needs_autoescape=True`, so it escapes its own
+    /// input when autoescaping is on.
+    pub needs_autoescape: bool,
+    /// Whether every `return` wraps the result in `mark_safe(...)` or `SafeString(...)`, so
+    /// the output is never escaped.
+    pub marks_safe: bool,
+}
+
+/// Extract the display signature of a filter function.
+///
+/// Uses the same parameter rules as [`extract_filter_arity`]. Flags are read from the
+/// function's decorators, so a filter registered with a `register.filter(...)` call rather
+/// than a decorator is never reported as `is_safe`.
+#[must_use]
+pub(crate) fn extract_filter_signature(func: &StmtFunctionDef) -> Option<FilterSignature> {
+    let params = &func.parameters;
+    let positional = params
+        .posonlyargs
+        .iter()
+        .chain(params.args.iter())
+        .skip_while(|param| param.parameter.name.as_str() == "self")
+        .collect::<Vec<_>>();
+    let (value, rest) = positional.split_first()?;
+    let arg = rest.first();
+
+    let mut is_safe = false;
+    let mut stringfilter = false;
+    let mut needs_autoescape = false;
+    for decorator in &func.decorator_list {
+        match &decorator.expression {
+            Expr::Call(call) => {
+                let flag = |name: &str| {
+                    call.arguments.keywords.iter().any(|keyword| {
+                        keyword.arg.as_ref().is_some_and(|arg| arg == name)
+                            && keyword.value.bool_literal() == Some(true)
+                    })
+                };
+                is_safe |= flag("is_safe");
+                needs_autoescape |= flag("needs_autoescape");
+            }
+            expression => {
+                stringfilter |= expression
+                    .path_segments()
+                    .is_some_and(|path| path.last().is_some_and(|last| last == "stringfilter"));
+            }
+        }
+    }
+
+    Some(FilterSignature {
+        value_param: value.parameter.name.to_string(),
+        arg_param: arg.map(|arg| arg.parameter.name.to_string()),
+        arg_default: arg
+            .and_then(|arg| arg.default.as_deref())
+            .map(default_source),
+        is_safe,
+        stringfilter,
+        needs_autoescape,
+        marks_safe: returns_marked_safe(func),
+    })
+}
+
+/// Whether the function has a `return` and every one returns `mark_safe(...)` or
+/// `SafeString(...)`. Filters that mark only some results safe, such as `cut` and
+/// `floatformat`, do so for input that was already safe or for numbers.
+fn returns_marked_safe(func: &StmtFunctionDef) -> bool {
+    let mut returns = 0;
+    let mut marked = true;
+    walk_stmts(&func.body, Recurse::ControlFlow, |stmt| {
+        if let Stmt::Return(ret) = stmt {
+            returns += 1;
+            marked &= ret.value.as_deref().is_some_and(is_mark_safe_call);
+        }
+        ControlFlow::Continue(())
+    });
+    returns > 0 && marked
+}
+
+fn is_mark_safe_call(expr: &Expr) -> bool {
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    call.func.path_segments().is_some_and(|path| {
+        path.last()
+            .is_some_and(|last| matches!(last.as_str(), "mark_safe" | "SafeString"))
+    })
+}
+
+/// Render a parameter default as Python source, for the literals filter defaults use.
+fn default_source(default: &Expr) -> String {
+    if let Some(value) = default.string_literal() {
+        return format!("{value:?}");
+    }
+    if let Some(value) = default.bool_literal() {
+        return if value { "True" } else { "False" }.to_string();
+    }
+    if let Some(value) = default.non_negative_integer() {
+        return value.to_string();
+    }
+    if let Some(value) = default.negative_integer() {
+        return format!("-{value}");
+    }
+    if matches!(default, Expr::NoneLiteral(_)) {
+        return "None".to_string();
+    }
+    "...".to_string()
 }
 
 /// Extract filter argument arity from a filter function's signature.
@@ -46,6 +205,7 @@ pub(crate) fn extract_filter_arity(func: &StmtFunctionDef) -> FilterArity {
         return FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         };
     }
 
@@ -65,6 +225,7 @@ pub(crate) fn extract_filter_arity(func: &StmtFunctionDef) -> FilterArity {
         return FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         };
     }
 
@@ -72,11 +233,252 @@ pub(crate) fn extract_filter_arity(func: &StmtFunctionDef) -> FilterArity {
     // Check if the extra parameter(s) have defaults.
     let extra_params = &after_self[1..];
     let all_have_defaults = extra_params.iter().all(|p| p.default.is_some());
+    let arg_name = extra_params[0].parameter.name.as_str();
 
     FilterArity {
         expects_arg: true,
         arg_optional: all_have_defaults,
+        arg_kind: extract_filter_arg_kind(&func.body, arg_name),
+    }
+}
+
+/// Infer the literal shape of a filter argument from the filter body.
+///
+/// Recognized uses:
+/// - `int(arg)` outside a `try` whose handlers fall back to using `arg`
+///   (`add` converts with `int()` but concatenates on failure)
+/// - `date_format(value, arg)` / `time_format(value, arg)`
+/// - `bits = arg.split(",")` followed by `if len(bits) > N: return ...`
+///
+/// Rebinding the argument to anything but `int(arg)` (as `floatformat` does
+/// when stripping its `g`/`u` suffixes) disables the integer inference.
+fn extract_filter_arg_kind(body: &[Stmt], arg_name: &str) -> Option<FilterArgKind> {
+    let mut uses = FilterArgUses::new(arg_name);
+    uses.visit_body(body);
+
+    if uses.date_format {
+        return Some(FilterArgKind::DateFormat);
+    }
+    if uses.int_conversion && !uses.rebound {
+        return Some(FilterArgKind::Integer);
     }
+    let (_, delimiter) = uses.split?;
+    (uses.min_items.is_some() || uses.max_items.is_some()).then_some(FilterArgKind::Delimited {
+        delimiter,
+        min_items: uses.min_items,
+        max_items: uses.max_items,
+    })
+}
+
+struct FilterArgUses<'a> {
+    arg_name: &'a str,
+    /// Depth of enclosing `try` bodies whose handlers reference the argument.
+    fallback_depth: usize,
+    int_conversion: bool,
+    rebound: bool,
+    date_format: bool,
+    /// `(split variable, delimiter)` for `bits = arg.split(",")`.
+    split: Option<(String, String)>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+}
+
+impl<'a> FilterArgUses<'a> {
+    fn new(arg_name: &'a str) -> Self {
+        Self {
+            arg_name,
+            fallback_depth: 0,
+            int_conversion: false,
+            rebound: false,
+            date_format: false,
+            split: None,
+            min_items: None,
+            max_items: None,
+        }
+    }
+
+    fn is_arg(&self, expr: &Expr) -> bool {
+        expr.name_target() == Some(self.arg_name)
+    }
+
+    fn is_int_conversion(&self, expr: &Expr) -> bool {
+        let Expr::Call(ExprCall {
+            func, arguments, ..
+        }) = expr
+        else {
+            return false;
+        };
+        func.name_target() == Some("int")
+            && arguments.keywords.is_empty()
+            && matches!(&*arguments.args, [arg] if self.is_arg(arg))
+    }
+
+    fn split_delimiter(&self, expr: &Expr) -> Option<String> {
+        let Expr::Call(ExprCall {
+            func, arguments, ..
+        }) = expr
+        else {
+            return None;
+        };
+        let Expr::Attribute(attribute) = func.as_ref() else {
+            return None;
+        };
+        if attribute.attr.as_str() != "split" || !self.is_arg(&attribute.value) {
+            return None;
+        }
+        let [delimiter] = &*arguments.args else {
+            return None;
+        };
+        delimiter.string_literal().map(str::to_string)
+    }
+
+    /// Record `if len(bits) > N: return ...` style guards on the split result.
+    fn record_length_guard(&mut self, test: &Expr, body: &[Stmt]) {
+        let Some((split_var, _)) = self.split.as_ref() else {
+            return;
+        };
+        if !matches!(body.first(), Some(Stmt::Return(_))) {
+            return;
+        }
+        let Expr::Compare(compare) = test else {
+            return;
+        };
+        let ([op], [right]) = (&*compare.ops, &*compare.comparators) else {
+            return;
+        };
+        let Expr::Call(ExprCall {
+            func, arguments, ..
+        }) = compare.left.as_ref()
+        else {
+            return;
+        };
+        if func.name_target() != Some("len")
+            || !matches!(&*arguments.args, [arg] if arg.name_target() == Some(split_var.as_str()))
+        {
+            return;
+        }
+        let Some(bound) = right.non_negative_integer() else {
+            return;
+        };
+        match op {
+            CmpOp::Gt => self.max_items = Some(bound),
+            CmpOp::GtE => self.max_items = bound.checked_sub(1),
+            CmpOp::Lt => self.min_items = Some(bound),
+            CmpOp::LtE => self.min_items = Some(bound + 1),
+            CmpOp::Eq
+            | CmpOp::NotEq
+            | CmpOp::Is
+            | CmpOp::IsNot
+            | CmpOp::In
+            | CmpOp::NotIn => {}
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for FilterArgUses<'_> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            // Nested scopes do not see rebinding of the filter argument.
+            Stmt::FunctionDef(_) | Stmt::ClassDef(_) => return,
+            Stmt::Try(stmt_try) => {
+                let falls_back_to_arg = stmt_try.handlers.iter().any(|handler| {
+                    let ExceptHandler::ExceptHandler(handler) = handler;
+                    body_references_name(&handler.body, self.arg_name)
+                });
+                if falls_back_to_arg {
+                    self.fallback_depth += 1;
+                }
+                self.visit_body(&stmt_try.body);
+                if falls_back_to_arg {
+                    self.fallback_depth -= 1;
+                }
+                for handler in &stmt_try.handlers {
+                    self.visit_except_handler(handler);
+                }
+                self.visit_body(&stmt_try.orelse);
+                self.visit_body(&stmt_try.finalbody);
+                return;
+            }
+            Stmt::Assign(assign) => {
+                if assign.targets.iter().any(|target| self.is_arg(target))
+                    && !self.is_int_conversion(&assign.value)
+                {
+                    self.rebound = true;
+                }
+                if let [target] = assign.targets.as_slice()
+                    && let Some(split_var) = target.name_target()
+                    && let Some(delimiter) = self.split_delimiter(&assign.value)
+                {
+                    self.split = Some((split_var.to_string(), delimiter));
+                }
+            }
+            Stmt::AugAssign(assign) => {
+                if self.is_arg(&assign.target) {
+                    self.rebound = true;
+                }
+            }
+            Stmt::If(stmt_if) => self.record_length_guard(&stmt_if.test, &stmt_if.body),
+            Stmt::Return(_)
+            | Stmt::Delete(_)
+            | Stmt::TypeAlias(_)
+            | Stmt::AnnAssign(_)
+            | Stmt::For(_)
+            | Stmt::While(_)
+            | Stmt::With(_)
+            | Stmt::Match(_)
+            | Stmt::Raise(_)
+            | Stmt::Assert(_)
+            | Stmt::Import(_)
+            | Stmt::ImportFrom(_)
+            | Stmt::Global(_)
+            | Stmt::Nonlocal(_)
+            | Stmt::Expr(_)
+            | Stmt::Pass(_)
+            | Stmt::Break(_)
+            | Stmt::Continue(_)
+            | Stmt::IpyEscapeCommand(_) => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if self.fallback_depth == 0 && self.is_int_conversion(expr) {
+            self.int_conversion = true;
+        }
+        if let Expr::Call(ExprCall {
+            func, arguments, ..
+        }) = expr
+            && func
+                .path_segments()
+                .and_then(|segments| segments.last().cloned())
+                .is_some_and(|name| name == "date_format" || name == "time_format")
+            && arguments.args.get(1).is_some_and(|arg| self.is_arg(arg))
+        {
+            self.date_format = true;
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+struct NameReferenceVisitor<'a> {
+    name: &'a str,
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for NameReferenceVisitor<'_> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if expr.name_target() == Some(self.name) {
+            self.found = true;
+            return;
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+fn body_references_name(body: &[Stmt], name: &str) -> bool {
+    let mut visitor = NameReferenceVisitor { name, found: false };
+    visitor.visit_body(body);
+    visitor.found
 }
 
 #[cfg(test)]
@@ -90,6 +492,7 @@ mod tests {
         let arity = FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         };
         assert!(!arity.expects_arg);
     }
@@ -99,6 +502,7 @@ mod tests {
         let arity = FilterArity {
             expects_arg: true,
             arg_optional: false,
+            arg_kind: None,
         };
         assert!(arity.expects_arg);
         assert!(!arity.arg_optional);
@@ -109,6 +513,7 @@ mod tests {
         let arity = FilterArity {
             expects_arg: true,
             arg_optional: true,
+            arg_kind: None,
         };
         assert!(arity.expects_arg);
         assert!(arity.arg_optional);
@@ -311,4 +716,159 @@ mod tests {
         assert!(!arity.expects_arg);
         assert!(!arity.arg_optional);
     }
+
+    // Argument kind inference
+
+    // Corpus: `truncatewords` in defaultfilters.py — converts with `int(arg)`
+    // and fails silently without touching `arg` again.
+    #[test]
+    fn int_conversion_infers_integer() {
+        let source = "def truncatewords(value, arg):\n    try:\n        length = int(arg)\n    except ValueError:\n        return value\n    return Truncator(value).words(length, truncate=' …')\n";
+        let func = find_function_in_source(source, "truncatewords")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, Some(FilterArgKind::Integer));
+    }
+
+    // Corpus: `get_digit` in defaultfilters.py — `arg = int(arg)` is not a rebinding
+    // that changes the accepted literal.
+    #[test]
+    fn int_self_assignment_infers_integer() {
+        let source = "def get_digit(value, arg):\n    try:\n        arg = int(arg)\n        value = int(value)\n    except ValueError:\n        return value\n    return value\n";
+        let func = find_function_in_source(source, "get_digit")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, Some(FilterArgKind::Integer));
+    }
+
+    // Corpus: `add` in defaultfilters.py — falls back to `value + arg` when
+    // `int(arg)` fails, so string arguments are meaningful.
+    #[test]
+    fn int_conversion_with_fallback_is_not_integer() {
+        let source = "def add(value, arg):\n    try:\n        return int(value) + int(arg)\n    except (ValueError, TypeError):\n        try:\n            return value + arg\n        except Exception:\n            return ''\n";
+        let func = find_function_in_source(source, "add")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, None);
+    }
+
+    // Corpus: `floatformat` rewrites `arg` to strip `g`/`u` suffixes before `int(arg)`.
+    #[test]
+    fn rebound_arg_is_not_integer() {
+        let func = django_function("django/template/defaultfilters.py", "floatformat")
+            .expect("expected Django fixture function should exist");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, None);
+    }
+
+    // Corpus: `date` in defaultfilters.py — `formats.date_format(value, arg)`
+    #[test]
+    fn date_format_call_infers_date_format() {
+        let func = django_function("django/template/defaultfilters.py", "date")
+            .expect("expected Django fixture function should exist");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, Some(FilterArgKind::DateFormat));
+    }
+
+    // Corpus: `pluralize` in defaultfilters.py
+    #[test]
+    fn split_with_upper_guard_infers_max_items() {
+        let source = "def pluralize(value, arg='s'):\n    if ',' not in arg:\n        arg = ',' + arg\n    bits = arg.split(',')\n    if len(bits) > 2:\n        return ''\n    singular_suffix, plural_suffix = bits[:2]\n";
+        let func = find_function_in_source(source, "pluralize")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(
+            arity.arg_kind,
+            Some(FilterArgKind::Delimited {
+                delimiter: ",".to_string(),
+                min_items: None,
+                max_items: Some(2),
+            })
+        );
+    }
+
+    // Corpus: `yesno` in defaultfilters.py
+    #[test]
+    fn split_with_lower_guard_infers_min_items() {
+        let source = "def yesno(value, arg=None):\n    if arg is None:\n        arg = gettext('yes,no,maybe')\n    bits = arg.split(',')\n    if len(bits) < 2:\n        return value\n";
+        let func = find_function_in_source(source, "yesno")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(
+            arity.arg_kind,
+            Some(FilterArgKind::Delimited {
+                delimiter: ",".to_string(),
+                min_items: Some(2),
+                max_items: None,
+            })
+        );
+    }
+
+    #[test]
+    fn unguarded_split_has_no_kind() {
+        let source = "def words(value, arg):\n    return value.split(arg.split(',')[0])\n";
+        let func = find_function_in_source(source, "words")
+            .expect("expected function should exist in test source");
+        let arity = extract_filter_arity(&func);
+        assert_eq!(arity.arg_kind, None);
+    }
+
+    // Display signature
+
+    // Corpus: `floatformat` — `is_safe=True` with `def floatformat(text, arg=-1):`
+    #[test]
+    fn signature_records_default_and_is_safe() {
+        let func = django_function("django/template/defaultfilters.py", "floatformat")
+            .expect("expected Django fixture function should exist");
+        let signature =
+            extract_filter_signature(&func).expect("floatformat should have a value parameter");
+        assert_eq!(signature.value_param, "text");
+        assert_eq!(signature.arg_param.as_deref(), Some("arg"));
+        assert_eq!(signature.arg_default.as_deref(), Some("-1"));
+        assert!(signature.is_safe);
+        assert!(!signature.stringfilter);
+    }
+
+    // Corpus: `title` — `@register.filter(is_safe=True)` and `@stringfilter`
+    #[test]
+    fn signature_records_stringfilter() {
+        let func = django_function("django/template/defaultfilters.py", "title")
+            .expect("expected Django fixture function should exist");
+        let signature =
+            extract_filter_signature(&func).expect("title should have a value parameter");
+        assert_eq!(signature.arg_param, None);
+        assert!(signature.is_safe);
+        assert!(signature.stringfilter);
+    }
+
+    #[test]
+    fn signature_renders_non_literal_default_as_ellipsis() {
+        let source = "@register.filter\ndef pick(self, value, choices=CHOICES):\n    return value\n";
+        let func = find_function_in_source(source, "pick")
+            .expect("expected function should exist in test source");
+        let signature =
+            extract_filter_signature(&func).expect("pick should have a value parameter");
+        assert_eq!(signature.value_param, "value");
+        assert_eq!(signature.arg_default.as_deref(), Some("..."));
+        assert!(!signature.is_safe);
+    }
+
+    #[test]
+    fn signature_marks_safe_only_when_every_return_is_marked() {
+        let source = "@register.filter(needs_autoescape=True)\ndef markdown(value, autoescape=True):\n    if not value:\n        return mark_safe('')\n    return safestring.mark_safe(render(value))\n";
+        let func = find_function_in_source(source, "markdown")
+            .expect("expected function should exist in test source");
+        let signature =
+            extract_filter_signature(&func).expect("markdown should have a value parameter");
+        assert!(signature.marks_safe);
+        assert!(signature.needs_autoescape);
+
+        // Corpus: `cut` marks its result safe only when the input already was.
+        let func = django_function("django/template/defaultfilters.py", "cut")
+            .expect("expected Django fixture function should exist");
+        let signature =
+            extract_filter_signature(&func).expect("cut should have a value parameter");
+        assert!(!signature.marks_safe);
+        assert!(!signature.needs_autoescape);
+    }
 }
@@ -0,0 +1,362 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use camino::Utf8PathBuf;
+use djls_source::File;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::libraries::TemplateLibrary;
+use super::libraries::TemplateLibraryCatalog;
+use super::libraries::template_library_catalog;
+use super::resolution::TemplateDirectories;
+use super::resolution::template_directories;
+use super::symbols::TemplateSymbolKind;
+use crate::db::Db as ProjectDb;
+use crate::django::DjangoVersion;
+use crate::django::django_version;
+use crate::project::Project;
+use crate::settings::settings_sources;
+
+/// The libraries and tags a catalog makes available, reduced to names for diffing.
+///
+/// Loadable libraries are keyed by their `{% load %}` name and builtins by
+/// module path. Libraries only offered by an app that is not installed are
+/// left out, since templates can't use them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateInventory {
+    libraries: BTreeMap<String, BTreeSet<String>>,
+    /// Filter names of each library, keyed like `libraries`.
+    filters: BTreeMap<String, BTreeSet<String>>,
+    /// Module path and source content hash of each library, keyed like `libraries`.
+    sources: BTreeMap<String, BTreeSet<(String, Option<u64>)>>,
+    /// Keys of builtin libraries, which every template uses without a `{% load %}`.
+    builtins: BTreeSet<String>,
+    /// Project facts outside the catalog that change how every template resolves.
+    context: Option<TemplateInventoryContext>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TemplateInventoryContext {
+    settings_sources: Vec<(Utf8PathBuf, Option<u64>)>,
+    template_directories: TemplateDirectories,
+    django_version: Option<DjangoVersion>,
+}
+
+impl TemplateInventory {
+    /// Inventory of the project's catalog, along with the settings sources,
+    /// template directories, and Django version every template depends on.
+    #[must_use]
+    pub fn for_project(db: &dyn ProjectDb, project: Project) -> Self {
+        let mut inventory = Self::from_catalog(template_library_catalog(db, project), |library| {
+            library.source_file().and_then(|file| source_hash(db, file))
+        });
+        inventory.context = Some(TemplateInventoryContext {
+            settings_sources: settings_sources(db, project)
+                .files()
+                .iter()
+                .map(|file| (file.path(db).to_path_buf(), source_hash(db, *file)))
+                .collect(),
+            template_directories: template_directories(db, project).clone(),
+            django_version: django_version(db, project),
+        });
+        inventory
+    }
+
+    fn from_catalog(
+        catalog: &TemplateLibraryCatalog,
+        source_hash: impl Fn(&TemplateLibrary) -> Option<u64>,
+    ) -> Self {
+        let mut inventory = Self::default();
+        for library in catalog.libraries() {
+            if library.available_in_app_module().is_some() {
+                continue;
+            }
+            let key = library.load_name().map_or_else(
+                || library.module_name_str().to_string(),
+                |name| name.as_str().to_string(),
+            );
+            if library.is_builtin() {
+                inventory.builtins.insert(key.clone());
+            }
+            for (kind, names) in [
+                (TemplateSymbolKind::Tag, &mut inventory.libraries),
+                (TemplateSymbolKind::Filter, &mut inventory.filters),
+            ] {
+                names.entry(key.clone()).or_default().extend(
+                    library
+                        .symbols()
+                        .iter()
+                        .filter(|symbol| symbol.kind == kind)
+                        .map(|symbol| symbol.name().to_string()),
+                );
+            }
+            inventory
+                .sources
+                .entry(key)
+                .or_default()
+                .insert((library.module_name_str().to_string(), source_hash(library)));
+        }
+        inventory
+    }
+
+    /// Describe what changed going from `self` to `newer`.
+    #[must_use]
+    pub fn diff(&self, newer: &Self) -> TemplateInventoryDiff {
+        let old_tags = self.tags();
+        let new_tags = newer.tags();
+        TemplateInventoryDiff {
+            added_libraries: keys_missing_from(&newer.libraries, &self.libraries),
+            removed_libraries: keys_missing_from(&self.libraries, &newer.libraries),
+            added_tags: new_tags.difference(&old_tags).cloned().collect(),
+            removed_tags: old_tags.difference(&new_tags).cloned().collect(),
+        }
+    }
+
+    /// Which templates may validate differently going from `self` to `newer`.
+    ///
+    /// A library counts as changed when it appears, disappears, or its tags,
+    /// filters, or source change. Templates are only affected through what
+    /// they load or use, unless a builtin or project-wide fact changed, in
+    /// which case every template is.
+    #[must_use]
+    pub fn impact(&self, newer: &Self) -> TemplateInventoryImpact {
+        if self.context != newer.context {
+            return TemplateInventoryImpact::Everything;
+        }
+        let changed: BTreeSet<&String> = self
+            .sources
+            .keys()
+            .chain(newer.sources.keys())
+            .filter(|key| {
+                self.libraries.get(*key) != newer.libraries.get(*key)
+                    || self.filters.get(*key) != newer.filters.get(*key)
+                    || self.sources.get(*key) != newer.sources.get(*key)
+            })
+            .collect();
+        if changed
+            .iter()
+            .any(|key| self.builtins.contains(*key) || newer.builtins.contains(*key))
+        {
+            return TemplateInventoryImpact::Everything;
+        }
+
+        let mut symbols = BTreeSet::new();
+        for inventory in [self, newer] {
+            for names in [&inventory.libraries, &inventory.filters] {
+                for key in &changed {
+                    symbols.extend(names.get(*key).into_iter().flatten().cloned());
+                }
+            }
+        }
+        TemplateInventoryImpact::Libraries {
+            libraries: changed.into_iter().cloned().collect(),
+            symbols,
+        }
+    }
+
+    fn tags(&self) -> BTreeSet<TemplateInventoryTag> {
+        self.libraries
+            .iter()
+            .flat_map(|(library, tags)| {
+                tags.iter().map(|name| TemplateInventoryTag {
+                    library: library.clone(),
+                    name: name.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn source_hash(db: &dyn ProjectDb, file: File) -> Option<u64> {
+    let source = file.try_source(db).ok()?;
+    let mut hasher = DefaultHasher::new();
+    source.as_str().hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn keys_missing_from(
+    libraries: &BTreeMap<String, BTreeSet<String>>,
+    other: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<String> {
+    libraries
+        .keys()
+        .filter(|name| !other.contains_key(*name))
+        .cloned()
+        .collect()
+}
+
+/// Templates whose diagnostics may change between two inventories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateInventoryImpact {
+    /// A builtin library or a project-wide fact changed.
+    Everything,
+    /// Only templates that load one of `libraries`, by inventory key, or use
+    /// one of `symbols`, the tag and filter names those libraries define
+    /// before or after the change.
+    Libraries {
+        libraries: BTreeSet<String>,
+        symbols: BTreeSet<String>,
+    },
+}
+
+impl TemplateInventoryImpact {
+    /// Whether no template can be affected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Libraries { libraries, .. } if libraries.is_empty())
+    }
+}
+
+/// Libraries and tags that appeared or disappeared between two inventories.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInventoryDiff {
+    pub added_libraries: Vec<String>,
+    pub removed_libraries: Vec<String>,
+    pub added_tags: Vec<TemplateInventoryTag>,
+    pub removed_tags: Vec<TemplateInventoryTag>,
+}
+
+impl TemplateInventoryDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_libraries.is_empty()
+            && self.removed_libraries.is_empty()
+            && self.added_tags.is_empty()
+            && self.removed_tags.is_empty()
+    }
+}
+
+/// A tag name qualified by the inventory key of the library that provides it.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TemplateInventoryTag {
+    pub library: String,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory(libraries: &[(&str, &[&str])]) -> TemplateInventory {
+        TemplateInventory {
+            libraries: libraries
+                .iter()
+                .map(|(library, tags)| {
+                    (
+                        (*library).to_string(),
+                        tags.iter().map(|tag| (*tag).to_string()).collect(),
+                    )
+                })
+                .collect(),
+            sources: libraries
+                .iter()
+                .map(|(library, _)| {
+                    (
+                        (*library).to_string(),
+                        BTreeSet::from([((*library).to_string(), Some(0))]),
+                    )
+                })
+                .collect(),
+            ..TemplateInventory::default()
+        }
+    }
+
+    fn names(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| (*name).to_string()).collect()
+    }
+
+    fn tag(library: &str, name: &str) -> TemplateInventoryTag {
+        TemplateInventoryTag {
+            library: library.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_libraries_with_their_tags() {
+        let old = inventory(&[("static", &["static"]), ("crispy_forms_tags", &["crispy"])]);
+        let new = inventory(&[("static", &["static"]), ("cache", &["cache"])]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added_libraries, vec!["cache".to_string()]);
+        assert_eq!(diff.removed_libraries, vec!["crispy_forms_tags".to_string()]);
+        assert_eq!(diff.added_tags, vec![tag("cache", "cache")]);
+        assert_eq!(diff.removed_tags, vec![tag("crispy_forms_tags", "crispy")]);
+    }
+
+    #[test]
+    fn diff_reports_tag_changes_within_a_library() {
+        let old = inventory(&[("extras", &["alpha", "beta"])]);
+        let new = inventory(&[("extras", &["beta", "gamma"])]);
+
+        let diff = old.diff(&new);
+
+        assert!(diff.added_libraries.is_empty());
+        assert!(diff.removed_libraries.is_empty());
+        assert_eq!(diff.added_tags, vec![tag("extras", "gamma")]);
+        assert_eq!(diff.removed_tags, vec![tag("extras", "alpha")]);
+    }
+
+    #[test]
+    fn identical_inventories_have_an_empty_diff() {
+        let old = inventory(&[("static", &["static", "get_static_prefix"])]);
+
+        assert!(old.diff(&old.clone()).is_empty());
+    }
+
+    #[test]
+    fn impact_covers_added_and_removed_libraries_and_their_tags() {
+        let old = inventory(&[("static", &["static"]), ("crispy_forms_tags", &["crispy"])]);
+        let new = inventory(&[("static", &["static"]), ("cache", &["cache"])]);
+
+        assert_eq!(
+            old.impact(&new),
+            TemplateInventoryImpact::Libraries {
+                libraries: names(&["cache", "crispy_forms_tags"]),
+                symbols: names(&["cache", "crispy"]),
+            }
+        );
+    }
+
+    #[test]
+    fn impact_covers_a_library_whose_source_changed() {
+        let old = inventory(&[("static", &["static"]), ("extras", &["alpha"])]);
+        let mut new = old.clone();
+        new.sources.insert(
+            "extras".to_string(),
+            BTreeSet::from([("extras".to_string(), Some(1))]),
+        );
+
+        assert!(old.diff(&new).is_empty());
+        assert_eq!(
+            old.impact(&new),
+            TemplateInventoryImpact::Libraries {
+                libraries: names(&["extras"]),
+                symbols: names(&["alpha"]),
+            }
+        );
+    }
+
+    #[test]
+    fn impact_covers_every_template_when_a_builtin_changes() {
+        let mut old = inventory(&[("django.template.defaulttags", &["if"])]);
+        old.builtins.insert("django.template.defaulttags".to_string());
+        let mut new = inventory(&[("django.template.defaulttags", &["if", "partial"])]);
+        new.builtins.insert("django.template.defaulttags".to_string());
+
+        assert_eq!(old.impact(&new), TemplateInventoryImpact::Everything);
+    }
+
+    #[test]
+    fn identical_inventories_affect_no_template() {
+        let old = inventory(&[("static", &["static"])]);
+
+        assert!(old.impact(&old.clone()).is_empty());
+    }
+}
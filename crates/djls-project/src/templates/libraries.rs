@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::ops::Bound;
 use std::slice::Iter;
 use std::sync::LazyLock;
 
@@ -8,6 +9,7 @@ use djls_source::File;
 
 use super::candidates::templatetag_candidates;
 use super::candidates::templatetag_candidates_in_package;
+use super::fallback::fallback_libraries_for_app;
 use super::installed_app_package_module;
 use super::names::LibraryName;
 use super::names::TemplateSymbolName;
@@ -347,8 +349,15 @@ impl TemplateLibrary {
         true
     }
 
+    /// Whether the library is one of the backend's builtins, loaded without `{% load %}`.
     #[must_use]
-    fn available_in_app_module(&self) -> Option<&PythonModuleName> {
+    pub fn is_builtin(&self) -> bool {
+        matches!(self.kind, TemplateLibraryKind::Builtin)
+    }
+
+    /// The app that provides this library when that app is not installed.
+    #[must_use]
+    pub fn available_in_app_module(&self) -> Option<&PythonModuleName> {
         match &self.kind {
             TemplateLibraryKind::AvailableInApp { app, .. } => Some(app),
             TemplateLibraryKind::Builtin | TemplateLibraryKind::Loadable { .. } => None,
@@ -1608,6 +1617,12 @@ impl TemplateLibraryCatalog {
         }
     }
 
+    /// Every discovered library, across all settings cases, in discovery order.
+    #[must_use]
+    pub fn libraries(&self) -> &[TemplateLibrary] {
+        &self.libraries
+    }
+
     #[must_use]
     pub fn empty_ref() -> &'static Self {
         static EMPTY: LazyLock<TemplateLibraryCatalog> =
@@ -1779,6 +1794,25 @@ impl TemplateLibraryCatalog {
             .map(String::as_str)
     }
 
+    /// Indexed symbol names starting with `prefix`, in name order.
+    ///
+    /// Definitions are keyed by name, so this is a range scan from the first name not ordered
+    /// before `prefix` that stops at the first name outside it.
+    pub(crate) fn inventory_symbols_with_prefix<'a>(
+        &'a self,
+        kind: TemplateSymbolKind,
+        prefix: &str,
+    ) -> impl Iterator<Item = &'a str> {
+        self.definitions_by_name
+            .get(&kind)
+            .into_iter()
+            .flat_map(move |definitions| {
+                definitions.range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            })
+            .map(|(name, _)| name.as_str())
+            .take_while(move |name| name.starts_with(prefix))
+    }
+
     fn resolved_libraries(&self) -> impl Iterator<Item = &TemplateLibrary> + '_ {
         self.resolved_libraries_in_view(LibraryScopeView::project_inventory(self))
             .into_iter()
@@ -2105,8 +2139,24 @@ fn discover_installed_app_libraries(
         };
         let Some(package_module) = installed_app_package_module(db, project, &installed_app.value)
         else {
-            discovery_remainder = true;
-            names_after_remainder.clear();
+            let bundled = fallback_app_libraries(db, &installed_app.value);
+            if bundled.is_empty() {
+                discovery_remainder = true;
+                names_after_remainder.clear();
+                continue;
+            }
+            for library in bundled {
+                let Some(load_name) = library.load_name().cloned() else {
+                    continue;
+                };
+                if app_remainder || discovery_remainder {
+                    names_after_remainder.insert(load_name.clone());
+                }
+                unresolved_names.remove(&load_name);
+                loadable_modules.insert(library.module_name().clone());
+                let index = libraries.insert_library(library);
+                app_libraries.insert(load_name, index);
+            }
             continue;
         };
         let (discovered, issues) = templatetag_package_libraries(db, project, &package_module);
@@ -2365,6 +2415,22 @@ fn insert_loadable_libraries(
     }
 }
 
+/// Bundled libraries for an installed app whose package is on no search path.
+fn fallback_app_libraries(db: &dyn ProjectDb, app: &str) -> Vec<TemplateLibrary> {
+    fallback_libraries_for_app(app)
+        .filter_map(|library| {
+            let load_name = LibraryName::parse(&library.load_name).ok()?;
+            let module = PythonModuleName::parse(&library.module).ok()?;
+            let id = TemplateLibraryId::new(db, None, module.clone());
+            let symbols = template_library_definition_facts(db, id)
+                .symbols()
+                .cloned()
+                .collect();
+            Some(TemplateLibrary::configured_loadable(id, load_name, module, symbols))
+        })
+        .collect()
+}
+
 fn templatetag_package_libraries(
     db: &dyn ProjectDb,
     project: Project,
@@ -0,0 +1,196 @@
+//! Template names Python code passes to Django's template loading APIs.
+
+use djls_source::File;
+use djls_source::Span;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::visitor;
+use ruff_python_ast::visitor::Visitor;
+
+use crate::ast::ExprExt;
+use crate::ast::RangedExt;
+use crate::db::Db as ProjectDb;
+use crate::python::RecoveredPythonModule;
+
+/// Calls whose positional argument at the given index names a template, matched by the last
+/// component of the callee so `loader.get_template` counts too.
+pub const TEMPLATE_NAME_CALLS: &[(&str, usize)] = &[
+    ("get_template", 0),
+    ("render", 1),
+    ("render_to_string", 0),
+    ("select_template", 0),
+    ("SimpleTemplateResponse", 0),
+    ("TemplateResponse", 1),
+];
+
+/// Keyword arguments and class attributes that name a template.
+pub const TEMPLATE_NAME_KEYWORDS: &[&str] = &["template_name"];
+
+/// A string literal in Python source that names a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PythonTemplateReference {
+    name: String,
+    span: Span,
+}
+
+impl PythonTemplateReference {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Span of the string literal, quotes included.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Template names a Python file passes as string literals, in source order.
+///
+/// Covers the calls in [`TEMPLATE_NAME_CALLS`], `template_name=` keyword arguments, and
+/// `template_name` class attributes. Names built at runtime are skipped, as are the candidate
+/// lists `select_template` takes, where one missing candidate is not an error.
+#[salsa::tracked(returns(ref))]
+pub fn python_template_references(db: &dyn ProjectDb, file: File) -> Vec<PythonTemplateReference> {
+    let Ok(Some(module)) = RecoveredPythonModule::from_file(db, file) else {
+        return Vec::new();
+    };
+    references_in_body(module.body(db))
+}
+
+fn references_in_body(body: &[Stmt]) -> Vec<PythonTemplateReference> {
+    let mut collector = ReferenceCollector::default();
+    collector.visit_body(body);
+    // Class attributes are recorded when the class is entered, ahead of calls nested in it.
+    collector
+        .references
+        .sort_by_key(|reference| reference.span.start());
+    collector.references
+}
+
+#[derive(Default)]
+struct ReferenceCollector {
+    references: Vec<PythonTemplateReference>,
+}
+
+impl ReferenceCollector {
+    fn record_call(&mut self, call: &ExprCall) {
+        let callee = call
+            .func
+            .path_segments()
+            .and_then(|segments| segments.last().cloned());
+        if let Some(callee) = callee
+            && let Some((_, position)) = TEMPLATE_NAME_CALLS
+                .iter()
+                .find(|(name, _)| *name == callee)
+            && let Some(argument) = call.arguments.args.get(*position)
+        {
+            self.record(argument);
+        }
+
+        for keyword in &call.arguments.keywords {
+            if keyword
+                .arg
+                .as_ref()
+                .is_some_and(|arg| TEMPLATE_NAME_KEYWORDS.contains(&arg.as_str()))
+            {
+                self.record(&keyword.value);
+            }
+        }
+    }
+
+    fn record(&mut self, value: &Expr) {
+        if let Some(name) = value.string_literal() {
+            self.references.push(PythonTemplateReference {
+                name: name.to_string(),
+                span: value.span(),
+            });
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ReferenceCollector {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if let Stmt::ClassDef(class_def) = stmt {
+            for stmt in &class_def.body {
+                if let Stmt::Assign(assign) = stmt
+                    && let [target] = assign.targets.as_slice()
+                    && target
+                        .name_target()
+                        .is_some_and(|name| TEMPLATE_NAME_KEYWORDS.contains(&name))
+                {
+                    self.record(&assign.value);
+                }
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::Call(call) = expr {
+            self.record_call(call);
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(source: &str) -> Vec<String> {
+        let parsed = ruff_python_parser::parse_module(source).expect("source should parse");
+        references_in_body(&parsed.syntax().body)
+            .into_iter()
+            .map(|reference| reference.name)
+            .collect()
+    }
+
+    #[test]
+    fn literal_names_passed_to_loading_calls() {
+        assert_eq!(
+            names(
+                "def index(request):\n    if request.user:\n        return render(request, 'home.html')\n    return shortcuts.render(request, 'blog/list.html', {})\n",
+            ),
+            ["home.html", "blog/list.html"]
+        );
+        assert_eq!(
+            names("loader.get_template('a.html')\nTemplateResponse(request, 'b.html')\n"),
+            ["a.html", "b.html"]
+        );
+        assert_eq!(names("render(request, template_name='c.html')\n"), ["c.html"]);
+    }
+
+    #[test]
+    fn template_name_class_attributes() {
+        assert_eq!(
+            names(
+                "class Home(TemplateView):\n    template_name = 'home.html'\n    def get(self):\n        return render(self.request, 'other.html')\n",
+            ),
+            ["home.html", "other.html"]
+        );
+        assert!(names("template_name = 'module_level.html'\n").is_empty());
+    }
+
+    #[test]
+    fn names_built_at_runtime_are_skipped() {
+        assert!(names("render(request, f'{app}/list.html')\n").is_empty());
+        assert!(names("render(request, name)\n").is_empty());
+        assert!(names("select_template(['a.html', 'b.html'])\n").is_empty());
+        assert!(names("render('not-a-template.html')\n").is_empty());
+    }
+
+    #[test]
+    fn spans_cover_the_string_literal() {
+        let source = "render(request, \"home.html\")\n";
+        let parsed = ruff_python_parser::parse_module(source).expect("source should parse");
+        let references = references_in_body(&parsed.syntax().body);
+        let [reference] = references.as_slice() else {
+            panic!("expected one reference, got {references:?}");
+        };
+        assert_eq!(reference.span().start(), 16);
+        assert_eq!(reference.span().length(), 11);
+    }
+}
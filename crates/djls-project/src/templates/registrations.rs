@@ -1,16 +1,21 @@
 use std::collections::BTreeMap;
 
+use ruff_python_ast::Decorator;
 use ruff_python_ast::Expr;
 use ruff_python_ast::ExprAttribute;
 use ruff_python_ast::ExprCall;
 use ruff_python_ast::Keyword;
 use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtClassDef;
 use ruff_python_ast::StmtExpr;
 use ruff_python_ast::StmtFunctionDef;
 use ruff_python_ast::visitor;
 use ruff_python_ast::visitor::Visitor;
 
+use super::fallback::FallbackLibrary;
+use super::fallback::fallback_library;
 use super::filters::FilterArityMap;
+use super::filters::FilterSignatureMap;
 use super::libraries::TemplateLibraryId;
 use super::names::TemplateSymbolName;
 use super::symbols::SymbolDefinition;
@@ -32,6 +37,18 @@ use crate::python::import::FromImportSyntax;
 /// Decorator helper names on `django.template.Library` that register filters.
 const FILTER_DECORATORS: &[&str] = &["filter"];
 
+/// `django.template.Library` members a subclass can override to change what gets registered.
+const LIBRARY_REGISTRATION_MEMBERS: &[&str] = &[
+    "__init__",
+    "tag",
+    "tag_function",
+    "filter",
+    "filter_function",
+    "simple_tag",
+    "inclusion_tag",
+    "simple_block_tag",
+];
+
 /// Information about a single tag or filter registration found in source code.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RegistrationInfo {
@@ -54,6 +71,102 @@ impl LocalFunctionSource {
             name_span: function.name.span(),
         }
     }
+
+    fn from_class(class: &StmtClassDef) -> Self {
+        Self {
+            definition_span: class.span(),
+            name_span: class.name.span(),
+        }
+    }
+}
+
+/// A module-level name whose meaning matters to registration analysis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LocalBinding {
+    Function(LocalFunctionSource),
+    Class(LocalClass),
+    /// A name imported from `classytags`, whose tag metaclass renames registered classes.
+    ClassTagBase,
+}
+
+impl LocalBinding {
+    fn source(&self) -> Option<LocalFunctionSource> {
+        match self {
+            Self::Function(source) => Some(*source),
+            Self::Class(class) => Some(class.source),
+            Self::ClassTagBase => None,
+        }
+    }
+
+    fn as_class(&self) -> Option<&LocalClass> {
+        if let Self::Class(class) = self {
+            Some(class)
+        } else {
+            None
+        }
+    }
+}
+
+/// A module-level class that may be registered as a tag or instantiated as a library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalClass {
+    source: LocalFunctionSource,
+    /// Name the class registers under; `None` when it is computed at runtime.
+    registered_name: Option<String>,
+    /// Whether the class derives from a `classytags` tag class.
+    class_tag: bool,
+    /// Inventory of a `Library()` subclass instance, when the class is one.
+    library: Option<RegistrationInventory>,
+}
+
+impl LocalClass {
+    fn from_class(
+        class: &StmtClassDef,
+        local_bindings: &BTreeMap<String, LocalBinding>,
+        template_is_django: bool,
+        library_constructor: Option<&str>,
+    ) -> Self {
+        let bases = class.bases();
+        let class_tag = bases.iter().any(|base| {
+            base.path_segments().is_some_and(|path| match path.as_slice() {
+                [name] => local_bindings.get(name).is_some_and(|binding| {
+                    matches!(binding, LocalBinding::ClassTagBase)
+                        || binding.as_class().is_some_and(|class| class.class_tag)
+                }),
+                [root, ..] => matches!(local_bindings.get(root), Some(LocalBinding::ClassTagBase)),
+                [] => false,
+            })
+        });
+        let registered_name = if class_tag {
+            classytags_registered_name(class)
+        } else {
+            Some(class.name.to_string())
+        };
+        let library = bases
+            .iter()
+            .find_map(|base| {
+                library_base_inventory(
+                    base,
+                    local_bindings,
+                    template_is_django,
+                    library_constructor,
+                )
+            })
+            .map(|inventory| {
+                let has_other_bases = bases.len() > 1 || !class.keywords().is_empty();
+                if has_other_bases || class_body_overrides_registration(&class.body) {
+                    RegistrationInventory::Open
+                } else {
+                    inventory
+                }
+            });
+        Self {
+            source: LocalFunctionSource::from_class(class),
+            registered_name,
+            class_tag,
+            library,
+        }
+    }
 }
 
 /// The style of registration, distinguishing decorator helpers.
@@ -166,6 +279,11 @@ fn statement_contains_named_binding(stmt: &Stmt) -> bool {
     visitor.found
 }
 
+/// Collect registrations made by methods decorated inside a class body.
+///
+/// Decorators run while the class is created, so `@register.simple_tag` on a
+/// method registers it just like a module-level function. Any other use of
+/// `register` in the body opens the inventory.
 fn collect_from_class_body(body: &[Stmt], analysis: &mut RegistrationSourceAnalysis) {
     let mut register_is_module_binding = true;
     for stmt in body {
@@ -179,7 +297,13 @@ fn collect_from_class_body(body: &[Stmt], analysis: &mut RegistrationSourceAnaly
             continue;
         }
         if let Stmt::ClassDef(class) = stmt {
+            // Nested class bodies see module globals, not the enclosing class's bindings.
             collect_from_class_body(&class.body, analysis);
+            if decorators_contain_register(&class.decorator_list)
+                || class_arguments_contain_register(class)
+            {
+                analysis.open_inventory();
+            }
             continue;
         }
         if let Stmt::Assign(assign) = stmt
@@ -190,9 +314,30 @@ fn collect_from_class_body(body: &[Stmt], analysis: &mut RegistrationSourceAnaly
         {
             register_is_module_binding = false;
         }
+        if statement_contains_register(stmt) {
+            analysis.open_inventory();
+        }
     }
 }
 
+fn decorators_contain_register(decorators: &[Decorator]) -> bool {
+    decorators
+        .iter()
+        .any(|decorator| contains_register(&decorator.expression))
+}
+
+fn class_arguments_contain_register(class: &StmtClassDef) -> bool {
+    class.bases().iter().any(contains_register)
+        || class
+            .keywords()
+            .iter()
+            .any(|keyword| contains_register(&keyword.value))
+}
+
+fn is_classytags_module(module: &str) -> bool {
+    module == "classytags" || module.starts_with("classytags.")
+}
+
 fn is_register_inventory_target(expr: &Expr) -> bool {
     if let Expr::Subscript(subscript) = expr {
         return is_register_inventory_target(&subscript.value);
@@ -219,28 +364,155 @@ fn call_escapes_register(call: &ExprCall) -> bool {
             .any(|keyword| contains_register(&keyword.value))
 }
 
-fn is_fresh_canonical_library(
+/// Inventory of a fresh library bound to `register`, or `None` when `expr` is not one.
+///
+/// Instances of local `Library()` subclasses count as fresh libraries. Their
+/// inventory stays open when the subclass may change how registration works.
+fn fresh_library_inventory(
     expr: &Expr,
+    local_bindings: &BTreeMap<String, LocalBinding>,
     template_is_django: bool,
     library_constructor: Option<&str>,
-) -> bool {
+) -> Option<RegistrationInventory> {
     let Expr::Call(call) = expr else {
-        return false;
+        return None;
     };
     if !call.arguments.args.is_empty() || !call.arguments.keywords.is_empty() {
-        return false;
+        return None;
     }
+    library_base_inventory(
+        &call.func,
+        local_bindings,
+        template_is_django,
+        library_constructor,
+    )
+}
+
+/// Inventory of libraries constructed from `expr`, when it names `Library` or a local subclass.
+fn library_base_inventory(
+    expr: &Expr,
+    local_bindings: &BTreeMap<String, LocalBinding>,
+    template_is_django: bool,
+    library_constructor: Option<&str>,
+) -> Option<RegistrationInventory> {
     if template_is_django
-        && call.func.path_segments().is_some_and(|path| {
+        && expr.path_segments().is_some_and(|path| {
             matches!(path.as_slice(), [template, library]
                 if template == "template" && library == "Library")
         })
     {
-        return true;
+        return Some(RegistrationInventory::Observed);
     }
-    call.func
-        .name_target()
-        .is_some_and(|name| Some(name) == library_constructor)
+    let name = expr.name_target()?;
+    if Some(name) == library_constructor {
+        return Some(RegistrationInventory::Observed);
+    }
+    local_bindings
+        .get(name)
+        .and_then(LocalBinding::as_class)
+        .and_then(|class| class.library)
+}
+
+/// Whether a `Library()` subclass body may change what its instances register.
+fn class_body_overrides_registration(body: &[Stmt]) -> bool {
+    body.iter().any(|stmt| {
+        if let Stmt::FunctionDef(function) = stmt {
+            return LIBRARY_REGISTRATION_MEMBERS.contains(&function.name.as_str());
+        }
+        if let Stmt::Assign(assign) = stmt {
+            return assign.targets.iter().any(|target| {
+                target
+                    .name_target()
+                    .is_none_or(|name| LIBRARY_REGISTRATION_MEMBERS.contains(&name))
+            });
+        }
+        if let Stmt::AnnAssign(assign) = stmt {
+            return assign
+                .target
+                .name_target()
+                .is_none_or(|name| LIBRARY_REGISTRATION_MEMBERS.contains(&name));
+        }
+        !matches!(stmt, Stmt::Expr(_) | Stmt::Pass(_))
+    })
+}
+
+/// Name a `classytags` tag class registers under.
+///
+/// The `classytags` metaclass uses the class's own `name` attribute, falling
+/// back to the class name converted to snake case. Returns `None` when `name`
+/// is not a string literal.
+fn classytags_registered_name(class: &StmtClassDef) -> Option<String> {
+    let mut name = Some(classytags_default_name(class.name.as_str()));
+    for stmt in &class.body {
+        if let Stmt::Assign(assign) = stmt
+            && assign
+                .targets
+                .iter()
+                .any(|target| target.name_target() == Some("name"))
+        {
+            name = assign
+                .value
+                .string_literal()
+                .filter(|_| assign.targets.len() == 1)
+                .map(str::to_string);
+        } else if let Stmt::AnnAssign(assign) = stmt
+            && assign.target.name_target() == Some("name")
+        {
+            name = assign
+                .value
+                .as_deref()
+                .and_then(ExprExt::string_literal)
+                .map(str::to_string);
+        }
+    }
+    name
+}
+
+/// Port of `classytags.utils.get_default_name`, which turns `CamelCase` into `camel_case`.
+///
+/// The two regex passes are kept separate so names like `HTMLTag` split the
+/// same way they do at runtime.
+fn classytags_default_name(name: &str) -> String {
+    // `(.)([A-Z][a-z]+)` -> `\1_\2`
+    let chars: Vec<char> = name.chars().collect();
+    let mut first_pass = Vec::with_capacity(chars.len());
+    let mut index = 0;
+    while index < chars.len() {
+        if index + 2 < chars.len()
+            && chars[index + 1].is_ascii_uppercase()
+            && chars[index + 2].is_ascii_lowercase()
+        {
+            first_pass.extend([chars[index], '_', chars[index + 1]]);
+            index += 2;
+            while index < chars.len() && chars[index].is_ascii_lowercase() {
+                first_pass.push(chars[index]);
+                index += 1;
+            }
+        } else {
+            first_pass.push(chars[index]);
+            index += 1;
+        }
+    }
+
+    // `([a-z0-9])([A-Z])` -> `\1_\2`
+    let mut converted = String::with_capacity(first_pass.len());
+    let mut index = 0;
+    while index < first_pass.len() {
+        let current = first_pass[index];
+        if let Some(next) = first_pass.get(index + 1)
+            && (current.is_ascii_lowercase() || current.is_ascii_digit())
+            && next.is_ascii_uppercase()
+        {
+            converted.push(current);
+            converted.push('_');
+            converted.push(*next);
+            index += 2;
+        } else {
+            converted.push(current);
+            index += 1;
+        }
+    }
+    converted.to_lowercase()
 }
 
 fn is_canonical_library_import(syntax: &FromImportSyntax, module_name: &str) -> bool {
@@ -254,16 +526,16 @@ fn is_canonical_library_import(syntax: &FromImportSyntax, module_name: &str) ->
             && module_name.starts_with("django.template."))
 }
 
-fn invalidate_local_function_target(
-    local_functions: &mut BTreeMap<String, LocalFunctionSource>,
+fn invalidate_local_binding_target(
+    local_bindings: &mut BTreeMap<String, LocalBinding>,
     target: &Expr,
 ) {
     if let Some(name) = target.name_target() {
-        local_functions.remove(name);
+        local_bindings.remove(name);
     } else if !matches!(target, Expr::Attribute(_) | Expr::Subscript(_)) {
         // Destructuring and other complex targets are uncommon in Template Library modules.
         // Clearing the map keeps navigation conservative without duplicating Python binding logic.
-        local_functions.clear();
+        local_bindings.clear();
     }
 }
 
@@ -273,13 +545,13 @@ fn analyze_registrations_from_body_in_module(
     module_name: &str,
 ) -> RegistrationSourceAnalysis {
     let mut analysis = RegistrationSourceAnalysis::default();
-    let mut local_functions = BTreeMap::new();
+    let mut local_bindings = BTreeMap::new();
     let mut template_is_django = false;
     let mut library_constructor = None;
 
     for stmt in body {
         if statement_contains_named_binding(stmt) {
-            local_functions.clear();
+            local_bindings.clear();
         }
         match stmt {
             Stmt::Import(import) => {
@@ -294,7 +566,12 @@ fn analyze_registrations_from_body_in_module(
                         analysis.registrations.clear();
                         analysis.open_inventory();
                     }
-                    local_functions.remove(clause.bound());
+                    if is_classytags_module(clause.requested()) {
+                        local_bindings
+                            .insert(clause.bound().to_string(), LocalBinding::ClassTagBase);
+                    } else {
+                        local_bindings.remove(clause.bound());
+                    }
                 }
             }
             Stmt::ImportFrom(import) => {
@@ -302,9 +579,11 @@ fn analyze_registrations_from_body_in_module(
                 if syntax.has_star() {
                     template_is_django = false;
                     library_constructor = None;
-                    local_functions.clear();
+                    local_bindings.clear();
                 }
                 let canonical_library_import = is_canonical_library_import(&syntax, module_name);
+                let classytags_import =
+                    syntax.level() == 0 && syntax.module().is_some_and(is_classytags_module);
                 for member in syntax.named_members() {
                     if member.bound() == "template" {
                         template_is_django = syntax.level() == 0
@@ -321,7 +600,12 @@ fn analyze_registrations_from_body_in_module(
                         analysis.registrations.clear();
                         analysis.open_inventory();
                     }
-                    local_functions.remove(member.bound());
+                    if classytags_import {
+                        local_bindings
+                            .insert(member.bound().to_string(), LocalBinding::ClassTagBase);
+                    } else {
+                        local_bindings.remove(member.bound());
+                    }
                 }
             }
             Stmt::Assign(assign) => {
@@ -344,13 +628,18 @@ fn analyze_registrations_from_body_in_module(
                     library_constructor = None;
                 }
                 if binds_register {
-                    let fresh_canonical = is_fresh_canonical_library(
+                    let fresh_library = fresh_library_inventory(
                         &assign.value,
+                        &local_bindings,
                         template_is_django,
                         library_constructor,
-                    );
-                    if fresh_canonical && !binds_template && !shares_register_binding {
+                    )
+                    .filter(|_| !binds_template && !shares_register_binding);
+                    if let Some(inventory) = fresh_library {
                         analysis.observe_fresh_library();
+                        if inventory == RegistrationInventory::Open {
+                            analysis.open_inventory();
+                        }
                     } else {
                         analysis.registrations.clear();
                         analysis.open_inventory();
@@ -365,7 +654,7 @@ fn analyze_registrations_from_body_in_module(
                     template_is_django = false;
                 }
                 for target in &assign.targets {
-                    invalidate_local_function_target(&mut local_functions, target);
+                    invalidate_local_binding_target(&mut local_bindings, target);
                 }
             }
             Stmt::AnnAssign(assign) => {
@@ -388,7 +677,7 @@ fn analyze_registrations_from_body_in_module(
                 {
                     library_constructor = None;
                 }
-                invalidate_local_function_target(&mut local_functions, &assign.target);
+                invalidate_local_binding_target(&mut local_bindings, &assign.target);
             }
             Stmt::AugAssign(assign) => {
                 if assign.target.name_target() == Some("register")
@@ -407,7 +696,7 @@ fn analyze_registrations_from_body_in_module(
                 {
                     library_constructor = None;
                 }
-                invalidate_local_function_target(&mut local_functions, &assign.target);
+                invalidate_local_binding_target(&mut local_bindings, &assign.target);
             }
             Stmt::Delete(delete) => {
                 if delete.targets.iter().any(|target| {
@@ -432,7 +721,7 @@ fn analyze_registrations_from_body_in_module(
                     library_constructor = None;
                 }
                 for target in &delete.targets {
-                    invalidate_local_function_target(&mut local_functions, target);
+                    invalidate_local_binding_target(&mut local_bindings, target);
                 }
             }
             Stmt::FunctionDef(function) => {
@@ -454,9 +743,12 @@ fn analyze_registrations_from_body_in_module(
                 if function.decorator_list.iter().all(|decorator| {
                     registration_decorator_rooted_at_register(&decorator.expression)
                 }) {
-                    local_functions.insert(function.name.to_string(), local_source);
+                    local_bindings.insert(
+                        function.name.to_string(),
+                        LocalBinding::Function(local_source),
+                    );
                 } else {
-                    local_functions.remove(function.name.as_str());
+                    local_bindings.remove(function.name.as_str());
                 }
             }
             Stmt::ClassDef(class) => {
@@ -470,15 +762,38 @@ fn analyze_registrations_from_body_in_module(
                     analysis.registrations.clear();
                     analysis.open_inventory();
                 }
+                let local_class = LocalClass::from_class(
+                    class,
+                    &local_bindings,
+                    template_is_django,
+                    library_constructor,
+                );
+                if let Some(registered_name) = &local_class.registered_name {
+                    collect_from_decorators(
+                        &class.decorator_list,
+                        class.name.as_str(),
+                        registered_name,
+                        Some(local_class.source),
+                        &mut analysis,
+                    );
+                } else if decorators_contain_register(&class.decorator_list) {
+                    analysis.open_inventory();
+                }
                 collect_from_class_body(&class.body, &mut analysis);
-                if statement_contains_register(stmt) {
+                if class_arguments_contain_register(class) {
                     analysis.open_inventory();
                 }
-                local_functions.remove(class.name.as_str());
+                if class.decorator_list.iter().all(|decorator| {
+                    registration_decorator_rooted_at_register(&decorator.expression)
+                }) {
+                    local_bindings.insert(class.name.to_string(), LocalBinding::Class(local_class));
+                } else {
+                    local_bindings.remove(class.name.as_str());
+                }
             }
             Stmt::Expr(StmtExpr { value, .. }) => {
                 if let Expr::Call(call) = value.as_ref() {
-                    collect_from_call_statement(call, &local_functions, &mut analysis);
+                    collect_from_call_statement(call, &local_bindings, &mut analysis);
                 } else if contains_register(value) {
                     analysis.open_inventory();
                 }
@@ -491,13 +806,13 @@ fn analyze_registrations_from_body_in_module(
             | Stmt::Try(_) => {
                 template_is_django = false;
                 library_constructor = None;
-                local_functions.clear();
+                local_bindings.clear();
                 if statement_contains_register(stmt) {
                     analysis.open_inventory();
                 }
             }
             Stmt::TypeAlias(_) => {
-                local_functions.clear();
+                local_bindings.clear();
                 if statement_contains_register(stmt) {
                     analysis.open_inventory();
                 }
@@ -588,9 +903,28 @@ fn collect_from_decorated_function(
     analysis: &mut RegistrationSourceAnalysis,
 ) {
     let func_name = func_def.name.as_str();
+    collect_from_decorators(
+        &func_def.decorator_list,
+        func_name,
+        func_name,
+        local_source,
+        analysis,
+    );
+}
 
+/// Extract registrations from the decorators of a function or class.
+///
+/// `default_name` is the name Django registers when the decorator does not
+/// give one, which differs from `func_name` for `classytags` classes.
+fn collect_from_decorators(
+    decorator_list: &[Decorator],
+    func_name: &str,
+    default_name: &str,
+    local_source: Option<LocalFunctionSource>,
+    analysis: &mut RegistrationSourceAnalysis,
+) {
     // Decorators execute from the function outward, so registrations must be recorded bottom-up.
-    for (index, decorator) in func_def.decorator_list.iter().enumerate().rev() {
+    for (index, decorator) in decorator_list.iter().enumerate().rev() {
         let expression = &decorator.expression;
         if !registration_decorator_rooted_at_register(expression) {
             if contains_register(expression) {
@@ -606,12 +940,12 @@ fn collect_from_decorated_function(
         }
 
         let registration_source = local_source.filter(|_| {
-            func_def.decorator_list[index + 1..]
+            decorator_list[index + 1..]
                 .iter()
                 .all(|decorator| registration_decorator_rooted_at_register(&decorator.expression))
         });
 
-        if let Some((name, kind)) = tag_name_from_decorator(expression, func_name) {
+        if let Some((name, kind)) = tag_name_from_decorator(expression, default_name) {
             analysis.registrations.push(RegistrationInfo {
                 name,
                 kind,
@@ -621,7 +955,7 @@ fn collect_from_decorated_function(
             continue;
         }
 
-        if let Some(name) = filter_name_from_decorator(expression, func_name) {
+        if let Some(name) = filter_name_from_decorator(expression, default_name) {
             analysis.registrations.push(RegistrationInfo {
                 name,
                 kind: RegistrationKind::Filter,
@@ -802,7 +1136,7 @@ fn filter_name_from_decorator(expr: &Expr, func_name: &str) -> Option<String> {
 /// - `register.simple_tag(func, name="alias")`
 fn collect_from_call_statement(
     call: &ExprCall,
-    local_functions: &BTreeMap<String, LocalFunctionSource>,
+    local_bindings: &BTreeMap<String, LocalBinding>,
     analysis: &mut RegistrationSourceAnalysis,
 ) {
     if !call_rooted_at_register(call) {
@@ -826,11 +1160,11 @@ fn collect_from_call_statement(
         return;
     }
 
-    if let Some((name, kind, func_name)) = tag_registration_from_call(call) {
+    if let Some((name, kind, func_name)) = tag_registration_from_call(call, local_bindings) {
         let local_source = func_name
             .as_deref()
-            .and_then(|name| local_functions.get(name))
-            .copied();
+            .and_then(|name| local_bindings.get(name))
+            .and_then(LocalBinding::source);
         analysis.registrations.push(RegistrationInfo {
             name,
             kind,
@@ -840,11 +1174,11 @@ fn collect_from_call_statement(
         return;
     }
 
-    if let Some((name, func_name)) = filter_registration_from_call(call) {
+    if let Some((name, func_name)) = filter_registration_from_call(call, local_bindings) {
         let local_source = func_name
             .as_deref()
-            .and_then(|name| local_functions.get(name))
-            .copied();
+            .and_then(|name| local_bindings.get(name))
+            .and_then(LocalBinding::source);
         analysis.registrations.push(RegistrationInfo {
             name,
             kind: RegistrationKind::Filter,
@@ -863,6 +1197,7 @@ fn collect_from_call_statement(
 /// - `register.simple_tag(func, name="alias")`
 fn tag_registration_from_call(
     call: &ExprCall,
+    local_bindings: &BTreeMap<String, LocalBinding>,
 ) -> Option<(String, RegistrationKind, Option<String>)> {
     let helper = direct_register_helper(&call.func)?;
     let kind = tag_decorator_kind(helper)?;
@@ -884,11 +1219,15 @@ fn tag_registration_from_call(
             }
             [callable] if name_override.is_none() => {
                 let func_name = callable_name(callable)?;
-                Some((func_name.clone(), kind, Some(func_name)))
+                let name = default_registered_name(&func_name, local_bindings)?;
+                Some((name, kind, Some(func_name)))
             }
             [] => {
                 let func_name = keyword_func?;
-                let name = name_override.unwrap_or_else(|| func_name.clone());
+                let name = match name_override {
+                    Some(name) => name,
+                    None => default_registered_name(&func_name, local_bindings)?,
+                };
                 Some((name, kind, Some(func_name)))
             }
             _ => None,
@@ -896,12 +1235,18 @@ fn tag_registration_from_call(
         "simple_tag" | "simple_block_tag" => match &args[..] {
             [callable] => {
                 let func_name = callable_name(callable).or(keyword_func)?;
-                let name = name_override.unwrap_or_else(|| func_name.clone());
+                let name = match name_override {
+                    Some(name) => name,
+                    None => default_registered_name(&func_name, local_bindings)?,
+                };
                 Some((name, kind, Some(func_name)))
             }
             [] => {
                 let func_name = keyword_func?;
-                let name = name_override.unwrap_or_else(|| func_name.clone());
+                let name = match name_override {
+                    Some(name) => name,
+                    None => default_registered_name(&func_name, local_bindings)?,
+                };
                 Some((name, kind, Some(func_name)))
             }
             _ => None,
@@ -912,7 +1257,10 @@ fn tag_registration_from_call(
                 [_] | [] => keyword_func,
                 _ => None,
             }?;
-            let name = name_override.unwrap_or_else(|| func_name.clone());
+            let name = match name_override {
+                Some(name) => name,
+                None => default_registered_name(&func_name, local_bindings)?,
+            };
             Some((name, kind, Some(func_name)))
         }
         _ => None,
@@ -924,7 +1272,10 @@ fn tag_registration_from_call(
 /// Returns `Some((name, func_name))` for patterns like:
 /// - `register.filter("name", func)`
 /// - `register.filter(func, name="alias")`
-fn filter_registration_from_call(call: &ExprCall) -> Option<(String, Option<String>)> {
+fn filter_registration_from_call(
+    call: &ExprCall,
+    local_bindings: &BTreeMap<String, LocalBinding>,
+) -> Option<(String, Option<String>)> {
     let helper = direct_register_helper(&call.func)?;
     if !FILTER_DECORATORS.contains(&helper) {
         return None;
@@ -944,17 +1295,36 @@ fn filter_registration_from_call(call: &ExprCall) -> Option<(String, Option<Stri
         }
         [callable] if name_override.is_none() => {
             let func_name = callable_name(callable)?;
-            Some((func_name.clone(), Some(func_name)))
+            let name = default_registered_name(&func_name, local_bindings)?;
+            Some((name, Some(func_name)))
         }
         [] => {
             let func_name = keyword_func?;
-            let name = name_override.unwrap_or_else(|| func_name.clone());
+            let name = match name_override {
+                Some(name) => name,
+                None => default_registered_name(&func_name, local_bindings)?,
+            };
             Some((name, Some(func_name)))
         }
         _ => None,
     }
 }
 
+/// Name Django registers for a callable passed without an explicit name.
+///
+/// Django uses the callable's `__name__`, so a method like `SomeNode.handle`
+/// registers as `handle`. Local `classytags` classes register under their
+/// metaclass-assigned name, and `None` means that name is not static.
+fn default_registered_name(
+    func_name: &str,
+    local_bindings: &BTreeMap<String, LocalBinding>,
+) -> Option<String> {
+    if let Some(class) = local_bindings.get(func_name).and_then(LocalBinding::as_class) {
+        return class.registered_name.clone();
+    }
+    func_name.rsplit('.').next().map(str::to_string)
+}
+
 /// Map decorator attr name to `RegistrationKind`.
 fn tag_decorator_kind(attr: &str) -> Option<RegistrationKind> {
     match attr {
@@ -1112,7 +1482,7 @@ impl TemplateLibraryDefinitionFacts {
         )
     }
 
-    pub(crate) fn symbols(&self) -> impl Iterator<Item = &TemplateSymbol> {
+    pub fn symbols(&self) -> impl Iterator<Item = &TemplateSymbol> {
         self.tags.values().chain(self.filters.values())
     }
 
@@ -1167,8 +1537,8 @@ impl TemplateLibrarySymbolSources {
 /// Canonical indexed analysis of one Template Library source module.
 ///
 /// Registration discovery happens here once. Equality-bearing projections below keep changes in
-/// Tag Definitions, Filter Definitions, source locations, Tag Rules, Block Specs, and Filter Arity
-/// independent.
+/// Tag Definitions, Filter Definitions, source locations, Tag Rules, Block Specs, Filter Arity,
+/// and Filter Signatures independent.
 #[derive(Clone, Debug, PartialEq)]
 struct TemplateLibrarySourceAnalysis {
     definitions: TemplateLibraryDefinitionFacts,
@@ -1176,6 +1546,7 @@ struct TemplateLibrarySourceAnalysis {
     tag_rules: TagRuleMap,
     block_specs: BlockSpecs,
     filter_arities: FilterArityMap,
+    filter_signatures: FilterSignatureMap,
 }
 
 impl TemplateLibrarySourceAnalysis {
@@ -1190,6 +1561,57 @@ impl TemplateLibrarySourceAnalysis {
             tag_rules: TagRuleMap::default(),
             block_specs: BlockSpecs::default(),
             filter_arities: FilterArityMap::default(),
+            filter_signatures: FilterSignatureMap::default(),
+        }
+    }
+
+    /// Analysis of a source-less library from its bundled extraction results.
+    ///
+    /// The inventory is observed, but no symbol has a navigable source.
+    fn from_fallback(key: TemplateLibraryId, library: &FallbackLibrary) -> Self {
+        let symbols = |kind: TemplateSymbolKind, names: &[String]| {
+            names
+                .iter()
+                .filter_map(|name| {
+                    let symbol = TemplateSymbol {
+                        kind,
+                        name: TemplateSymbolName::parse(name).ok()?,
+                        definition: SymbolDefinition::Exact { library: key },
+                        doc: None,
+                    };
+                    Some((name.clone(), symbol))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let module = library.module.as_str();
+        Self {
+            definitions: TemplateLibraryDefinitionFacts {
+                state: TemplateLibraryDefinitionState::Library {
+                    parse_quality: TemplateLibraryParseQuality::Exact,
+                    inventory: TemplateLibrarySymbolInventory::Observed,
+                },
+                tags: symbols(TemplateSymbolKind::Tag, library.tags.as_slice()),
+                filters: symbols(TemplateSymbolKind::Filter, library.filters.as_slice()),
+            },
+            symbol_sources: TemplateLibrarySymbolSources::default(),
+            tag_rules: library
+                .tag_rules
+                .iter()
+                .map(|(name, rule)| (SymbolKey::tag(module, name), rule.clone().into()))
+                .collect(),
+            block_specs: BlockSpecs(
+                library
+                    .block_specs
+                    .iter()
+                    .map(|(name, spec)| (SymbolKey::tag(module, name), spec.clone()))
+                    .collect(),
+            ),
+            filter_arities: library
+                .filter_arities
+                .iter()
+                .map(|(name, arity)| (SymbolKey::filter(module, name), arity.clone()))
+                .collect(),
+            filter_signatures: FilterSignatureMap::default(),
         }
     }
 }
@@ -1201,7 +1623,10 @@ fn template_library_source_analysis(
     key: TemplateLibraryId,
 ) -> TemplateLibrarySourceAnalysis {
     let Some(file) = key.file(db) else {
-        return TemplateLibrarySourceAnalysis::failed();
+        return fallback_library(key.module(db).as_str()).map_or_else(
+            TemplateLibrarySourceAnalysis::failed,
+            |library| TemplateLibrarySourceAnalysis::from_fallback(key, library),
+        );
     };
     let Ok(Some(module)) = RecoveredPythonModule::from_file(db, file) else {
         return TemplateLibrarySourceAnalysis::failed();
@@ -1218,11 +1643,13 @@ fn template_library_source_analysis(
     let mut tag_rules = TagRuleMap::default();
     let mut block_specs = BlockSpecs::default();
     let mut filter_arities = FilterArityMap::default();
+    let mut filter_signatures = FilterSignatureMap::default();
     let registration_module = key.module(db).as_str();
     let registration_analysis =
         analyze_registrations_from_body_in_module(module.body(db), registration_module);
     let mut symbols_unobserved = parse_quality == TemplateLibraryParseQuality::Recovered
         || registration_analysis.inventory_is_open();
+    let module_functions = collect_func_defs(module.body(db));
 
     for_each_registration(
         &registration_analysis,
@@ -1268,7 +1695,10 @@ fn template_library_source_analysis(
             if let Some(rule) = registration.kind.extract_tag_rule(func) {
                 tag_rules.insert(symbol_key.clone(), rule.into());
             }
-            if let Some(block_spec) = registration.kind.extract_block_spec(func) {
+            if let Some(block_spec) = registration
+                .kind
+                .extract_block_spec(func, &module_functions)
+            {
                 let end_tag = match block_spec.end_tag {
                     EndTagEvidence::Literal(end_tag) => Some(end_tag),
                     EndTagEvidence::SelfNamed => Some(format!("end{}", symbol_key.name)),
@@ -1283,6 +1713,9 @@ fn template_library_source_analysis(
                     },
                 );
             }
+            if let Some(signature) = registration.kind.extract_filter_signature(func) {
+                filter_signatures.insert(symbol_key.clone(), signature);
+            }
             if let Some(arity) = registration.kind.extract_filter_arity(func) {
                 filter_arities.insert(symbol_key, arity);
             }
@@ -1313,6 +1746,7 @@ fn template_library_source_analysis(
         tag_rules,
         block_specs,
         filter_arities,
+        filter_signatures,
     }
 }
 
@@ -1403,6 +1837,19 @@ pub fn template_library_filter_facts(
     }
 }
 
+/// Display signatures of the filters one Template Library defines, for hover.
+///
+/// Source-less libraries have none.
+#[salsa::tracked(returns(ref))]
+pub fn template_library_filter_signatures(
+    db: &dyn ProjectDb,
+    key: TemplateLibraryId,
+) -> FilterSignatureMap {
+    template_library_source_analysis(db, key)
+        .filter_signatures
+        .clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1902,6 +2349,92 @@ def my_tag(parser, token):
         assert!(!analysis.inventory_is_open());
     }
 
+    #[test]
+    fn library_subclass_instance_is_a_fresh_library() {
+        let analysis = analyze_registrations(
+            "from django import template\nclass CustomLibrary(template.Library):\n    \"\"\"Project library.\"\"\"\nregister = CustomLibrary()\n@register.simple_tag\ndef known(): pass\n",
+        );
+        assert!(analysis.defines_library());
+        assert!(!analysis.inventory_is_open());
+        assert_eq!(analysis.registrations.len(), 1);
+        assert_eq!(analysis.registrations[0].name, "known");
+    }
+
+    #[test]
+    fn library_subclass_overriding_registration_keeps_known_symbols_open() {
+        let analysis = analyze_registrations(
+            "from django.template import Library\nclass CustomLibrary(Library):\n    def tag(self, name=None, compile_function=None): pass\nregister = CustomLibrary()\n@register.simple_tag\ndef known(): pass\n",
+        );
+        assert!(analysis.inventory_is_open());
+        assert_eq!(analysis.registrations.len(), 1);
+        assert_eq!(analysis.registrations[0].name, "known");
+    }
+
+    #[test]
+    fn decorated_methods_in_class_body_are_known_and_closed() {
+        let analysis = analyze_registrations(
+            "from django import template\nregister = template.Library()\nclass Helpers:\n    @register.simple_tag(name='helper_tag')\n    @staticmethod\n    def render(): pass\n    @register.filter\n    def shout(value): return value\n",
+        );
+        assert!(!analysis.inventory_is_open());
+        let names: Vec<_> = analysis
+            .registrations
+            .iter()
+            .map(|registration| registration.name.as_str())
+            .collect();
+        assert_eq!(names, ["helper_tag", "shout"]);
+    }
+
+    #[test]
+    fn method_callable_registers_under_its_own_name() {
+        let registrations = collect_registrations(
+            "from django import template\nregister = template.Library()\nregister.tag(PanelNode.handle)\nregister.simple_tag(Helpers.render_badge)\n",
+        );
+        let tag = find_reg(&registrations, "handle");
+        assert_eq!(tag.func_name.as_deref(), Some("PanelNode.handle"));
+        let simple_tag = find_reg(&registrations, "render_badge");
+        assert_eq!(simple_tag.kind, RegistrationKind::SimpleTag);
+    }
+
+    #[test]
+    fn classytags_classes_register_under_metaclass_names() {
+        let source = "from classytags.core import Tag\nfrom django import template\nregister = template.Library()\nclass RenderPanel(Tag):\n    pass\nclass Aliased(Tag):\n    name = 'panel_alias'\nclass HTMLBadge(RenderPanel):\n    pass\nregister.tag(RenderPanel)\nregister.tag(Aliased)\nregister.tag(HTMLBadge)\n@register.tag\nclass DecoratedTag(Tag):\n    pass\n";
+        let analysis = analyze_registrations(source);
+        assert!(!analysis.inventory_is_open());
+
+        let names: Vec<_> = analysis
+            .registrations
+            .iter()
+            .map(|registration| registration.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            ["render_panel", "panel_alias", "html_badge", "decorated_tag"]
+        );
+        let panel = find_reg(&analysis.registrations, "render_panel");
+        assert_eq!(panel.func_name.as_deref(), Some("RenderPanel"));
+        assert_eq!(
+            registered_source(source, panel),
+            Some("class RenderPanel(Tag):\n    pass")
+        );
+    }
+
+    #[test]
+    fn classytags_class_with_dynamic_name_opens_inventory() {
+        let analysis = analyze_registrations(
+            "from classytags.core import Tag\nfrom django import template\nregister = template.Library()\nclass Dynamic(Tag):\n    name = PREFIX + 'panel'\nregister.tag(Dynamic)\n",
+        );
+        assert!(analysis.inventory_is_open());
+        assert!(analysis.registrations.is_empty());
+    }
+
+    #[test]
+    fn plain_class_registers_under_class_name() {
+        let registrations = collect_registrations(
+            "from django import template\nregister = template.Library()\nclass PanelTag:\n    name = 'ignored'\nregister.tag(PanelTag)\n",
+        );
+        assert_eq!(find_reg(&registrations, "PanelTag").kind, RegistrationKind::Tag);
+    }
+
     #[test]
     fn uncertain_operations_open_inventory_without_dropping_known_symbols() {
         for operation in [
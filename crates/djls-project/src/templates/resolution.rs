@@ -22,6 +22,7 @@ use crate::db::Db as ProjectDb;
 use crate::project::Project;
 use crate::python::resolve_package_dirs;
 use crate::settings::types::InstalledAppEvidence;
+use crate::settings::types::TemplateLoader;
 use crate::templates::installed_app_package_module;
 use crate::templates::settings_cases::TemplateBackendCase;
 use crate::templates::settings_cases::TemplateBackendId;
@@ -146,38 +147,141 @@ fn add_backend_roots(
     if backend.backend_name() != Some("django.template.backends.django.DjangoTemplates") {
         return;
     }
+    if backend.loaders_completeness().is_open() {
+        alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+    }
+    let Some(loaders) = backend.loaders() else {
+        // Django's default loaders search `DIRS`, then app directories when `APP_DIRS` is set.
+        add_directory_roots(db, project, backend, alternative);
+        if backend.app_directories_completeness().is_open() {
+            alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+        }
+        if backend.app_directories() == Some(true) {
+            add_app_directory_roots(db, project, backend, installed_apps, alternative);
+        }
+        return;
+    };
+    for loader in loaders {
+        match loader {
+            TemplateLoader::Filesystem => add_directory_roots(db, project, backend, alternative),
+            TemplateLoader::AppDirectories => {
+                add_app_directory_roots(db, project, backend, installed_apps, alternative);
+            }
+            TemplateLoader::Other(_) => {
+                alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+            }
+        }
+    }
+}
+
+/// Push each `DIRS` entry, mapped from the container into the workspace by `[path_mappings]`.
+fn add_directory_roots(
+    db: &dyn ProjectDb,
+    project: Project,
+    backend: &TemplateBackendCase,
+    alternative: &mut TemplateDirectoryAlternative,
+) {
+    let path_mappings = project.path_mappings(db);
     for evidence in backend.directories() {
         match evidence {
             TemplateDirectorySlot::Path(path) => {
-                alternative.push_root(path.clone(), backend.id());
+                let root = path_mappings
+                    .to_workspace(project.root(db), path)
+                    .map_or_else(|| path.clone(), |mapped| mapped.clean());
+                alternative.push_root(root, backend.id());
             }
             TemplateDirectorySlot::Unknown => {
                 alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
             }
         }
     }
-    if backend.app_directories_completeness().is_open() {
-        alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+}
+
+/// Push each installed app's `templates` directory, in `INSTALLED_APPS` order.
+fn add_app_directory_roots(
+    db: &dyn ProjectDb,
+    project: Project,
+    backend: &TemplateBackendCase,
+    installed_apps: &[InstalledAppEvidence],
+    alternative: &mut TemplateDirectoryAlternative,
+) {
+    for evidence in installed_apps {
+        let InstalledAppEvidence::Known(app) = evidence else {
+            alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+            continue;
+        };
+        let Some(package_module) = installed_app_package_module(db, project, &app.value) else {
+            alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+            continue;
+        };
+        let package_dirs = resolve_package_dirs(db, project, package_module);
+        if package_dirs.dirs.is_empty() {
+            alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
+        }
+        for package_dir in package_dirs.dirs {
+            alternative.push_root(package_dir.join("templates"), backend.id());
+        }
     }
-    if backend.app_directories() == Some(true) {
-        for evidence in installed_apps {
+}
+
+/// An installed app's `templates` directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppTemplateDirectory {
+    app: String,
+    root: Utf8PathBuf,
+}
+
+impl AppTemplateDirectory {
+    /// The app package's directory name, which by convention namespaces the app's templates:
+    /// `blog/templates/blog/detail.html` is loaded as `blog/detail.html`.
+    #[must_use]
+    pub fn app(&self) -> &str {
+        &self.app
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Utf8Path {
+        &self.root
+    }
+
+    /// Whether `name` lives under this app's namespace directory.
+    #[must_use]
+    pub fn namespaces(&self, name: &str) -> bool {
+        name.split_once('/').is_some_and(|(namespace, _)| namespace == self.app)
+    }
+}
+
+/// The `templates` directories of every installed app any settings case lists, whether or not
+/// a backend searches them.
+#[salsa::tracked(returns(ref))]
+pub fn app_template_directories(
+    db: &dyn ProjectDb,
+    project: Project,
+) -> Vec<AppTemplateDirectory> {
+    let mut directories: Vec<AppTemplateDirectory> = Vec::new();
+    for settings_case in template_settings_cases(db, project).settings_cases() {
+        for evidence in settings_case.installed_apps() {
             let InstalledAppEvidence::Known(app) = evidence else {
-                alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
                 continue;
             };
             let Some(package_module) = installed_app_package_module(db, project, &app.value) else {
-                alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
                 continue;
             };
-            let package_dirs = resolve_package_dirs(db, project, package_module);
-            if package_dirs.dirs.is_empty() {
-                alternative.mark_unknown_roots(TemplateBackendSelection::Backend(backend.id()));
-            }
-            for package_dir in package_dirs.dirs {
-                alternative.push_root(package_dir.join("templates"), backend.id());
+            for package_dir in resolve_package_dirs(db, project, package_module).dirs {
+                let Some(app) = package_dir.file_name() else {
+                    continue;
+                };
+                let root = package_dir.join("templates");
+                if directories.iter().all(|directory| directory.root != root) {
+                    directories.push(AppTemplateDirectory {
+                        app: app.to_string(),
+                        root,
+                    });
+                }
             }
         }
     }
+    directories
 }
 
 #[salsa::tracked(returns(ref))]
@@ -357,6 +461,44 @@ impl<'db> TemplateResolution<'db> {
             .map_or(&[], Vec::as_slice)
     }
 
+    /// The installed app whose `templates` directory provides `origin`, if any.
+    #[must_use]
+    pub fn app_for_origin(
+        self,
+        db: &'db dyn ProjectDb,
+        origin: TemplateOrigin<'db>,
+    ) -> Option<&'db AppTemplateDirectory> {
+        let path = origin.path_buf(db);
+        let name = origin.template_name(db).name(db);
+        app_template_directories(db, self.project(db))
+            .iter()
+            .find(|directory| {
+                path.strip_prefix(&directory.root)
+                    .is_ok_and(|relative| relative.clean().as_str() == name)
+            })
+    }
+
+    /// The origin Django's loaders find first for `origin`'s name, when that is another file.
+    ///
+    /// Lookup stays within the backends that can render `origin`, and an inconclusive search
+    /// never names a winner.
+    #[must_use]
+    pub fn shadowing_origin(
+        self,
+        db: &'db dyn ProjectDb,
+        origin: TemplateOrigin<'db>,
+    ) -> Option<TemplateOrigin<'db>> {
+        let scope = self.backend_scope_for_origin(db, origin);
+        match self.resolve_excluding_in_scope(db, origin.template_name(db), &[], &scope) {
+            TemplateResolutionResult::Found(winner) if winner.file(db) != origin.file(db) => {
+                Some(winner)
+            }
+            TemplateResolutionResult::Found(_)
+            | TemplateResolutionResult::DoesNotExist(_)
+            | TemplateResolutionResult::Inconclusive(_) => None,
+        }
+    }
+
     #[must_use]
     pub fn resolve(
         self,
@@ -842,6 +984,58 @@ pub fn template_resolution(db: &dyn ProjectDb, project: Project) -> TemplateReso
     TemplateResolution::new(db, project)
 }
 
+/// A template file that an earlier template directory hides from Django's loaders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShadowedTemplate {
+    name: String,
+    shadowed: Utf8PathBuf,
+    winner: Utf8PathBuf,
+}
+
+impl ShadowedTemplate {
+    /// The template name both files provide.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file that is never loaded under [`name`](Self::name).
+    #[must_use]
+    pub fn shadowed(&self) -> &Utf8Path {
+        &self.shadowed
+    }
+
+    /// The file the loaders return for [`name`](Self::name).
+    #[must_use]
+    pub fn winner(&self) -> &Utf8Path {
+        &self.winner
+    }
+}
+
+/// Every discovered template that another origin of the same name shadows, in loader order.
+#[salsa::tracked(returns(ref))]
+pub fn shadowed_templates(db: &dyn ProjectDb, project: Project) -> Vec<ShadowedTemplate> {
+    let resolution = template_resolution(db, project);
+    let mut shadowed: Vec<ShadowedTemplate> = Vec::new();
+    for origin in resolution.origins(db) {
+        if resolution.origins_for_name(db, origin.template_name(db)).len() < 2 {
+            continue;
+        }
+        let Some(winner) = resolution.shadowing_origin(db, origin) else {
+            continue;
+        };
+        let entry = ShadowedTemplate {
+            name: origin.template_name(db).name(db).clone(),
+            shadowed: origin.path_buf(db).clone(),
+            winner: winner.path_buf(db).clone(),
+        };
+        if !shadowed.contains(&entry) {
+            shadowed.push(entry);
+        }
+    }
+    shadowed
+}
+
 #[salsa::tracked]
 struct TemplateDirectoryIndex<'db> {
     #[tracked]
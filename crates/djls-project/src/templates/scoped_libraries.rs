@@ -161,6 +161,18 @@ impl<'db> ScopedTemplateLibraries<'db> {
         self.catalog.inventory_symbol_names(kind)
     }
 
+    /// Borrow the indexed symbol names that start with `prefix`, in name order.
+    ///
+    /// Like `inventory_symbol_names`, this is inventory enumeration; the prefix index only
+    /// avoids scanning names a completion prefix has already ruled out.
+    pub fn symbols_with_prefix(
+        self,
+        kind: TemplateSymbolKind,
+        prefix: &str,
+    ) -> impl Iterator<Item = &'db str> {
+        self.catalog.inventory_symbols_with_prefix(kind, prefix)
+    }
+
     /// Return the candidates for one name only when they are definite in every feasible backend.
     #[must_use]
     pub fn scoped_symbol_candidates(
@@ -14,6 +14,7 @@ use crate::settings::types::SettingCase;
 use crate::settings::types::TemplateBackend;
 use crate::settings::types::TemplateBackendEvidence;
 use crate::settings::types::TemplateDirectoryEvidence;
+use crate::settings::types::TemplateLoader;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(super) struct TemplateSettingsCaseId(usize);
@@ -88,6 +89,9 @@ struct TemplateBackendSettings {
     libraries_completeness: TemplateEvidenceCompleteness,
     builtins: Vec<PythonModuleName>,
     builtins_completeness: TemplateEvidenceCompleteness,
+    context_processors: Vec<String>,
+    loaders: Option<Vec<TemplateLoader>>,
+    loaders_completeness: TemplateEvidenceCompleteness,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -229,6 +233,9 @@ impl TemplateBackendSettings {
             libraries_completeness: TemplateEvidenceCompleteness::Complete,
             builtins: Vec::new(),
             builtins_completeness: TemplateEvidenceCompleteness::Complete,
+            context_processors: Vec::new(),
+            loaders: None,
+            loaders_completeness: TemplateEvidenceCompleteness::Complete,
         }
     }
 
@@ -251,6 +258,17 @@ impl TemplateBackendSettings {
                 .iter()
                 .map(|module| module.value.clone())
                 .collect(),
+            context_processors: backend
+                .context_processors
+                .iter()
+                .map(|processor| processor.value.as_str().to_string())
+                .collect(),
+            loaders: backend.loaders.as_ref().map(|loaders| {
+                loaders
+                    .iter()
+                    .map(|loader| loader.value.clone())
+                    .collect()
+            }),
             ..Self::empty()
         }
     }
@@ -298,6 +316,21 @@ impl TemplateBackendSettings {
             builtins_completeness: TemplateEvidenceCompleteness::open_if(
                 !backend.options.issues.is_empty() || !backend.builtins.issues.is_empty(),
             ),
+            context_processors: backend
+                .context_processors
+                .known
+                .iter()
+                .map(|processor| processor.value.as_str().to_string())
+                .collect(),
+            loaders: backend.loaders.known.as_ref().map(|loaders| {
+                loaders
+                    .iter()
+                    .map(|loader| loader.value.clone())
+                    .collect()
+            }),
+            loaders_completeness: TemplateEvidenceCompleteness::open_if(
+                !backend.options.issues.is_empty() || !backend.loaders.issues.is_empty(),
+            ),
         }
     }
 
@@ -349,6 +382,20 @@ impl TemplateBackendCase {
     pub(super) fn builtins_completeness(&self) -> TemplateEvidenceCompleteness {
         self.data.builtins_completeness
     }
+
+    /// Dotted paths from `OPTIONS["context_processors"]`, in settings order.
+    pub(super) fn context_processors(&self) -> &[String] {
+        &self.data.context_processors
+    }
+
+    /// Explicit loaders in search order, or `None` for Django's defaults.
+    pub(super) fn loaders(&self) -> Option<&[TemplateLoader]> {
+        self.data.loaders.as_deref()
+    }
+
+    pub(super) fn loaders_completeness(&self) -> TemplateEvidenceCompleteness {
+        self.data.loaders_completeness
+    }
 }
 
 #[salsa::tracked(returns(ref))]
@@ -5,6 +5,8 @@ pub(crate) mod expressions;
 pub(crate) mod guards;
 pub(crate) mod match_arms;
 pub(crate) mod mutations;
+pub(crate) mod operators;
+pub(crate) mod patterns;
 pub(crate) mod state;
 pub(crate) mod statements;
 
@@ -31,6 +33,7 @@ use crate::templates::tags::analysis::guards::ExtractedRuleFragment;
 use crate::templates::tags::types::ArgumentCountConstraint;
 use crate::templates::tags::types::AsVar;
 use crate::templates::tags::types::ExtractedDiagnosticMessage;
+use crate::templates::tags::types::KeywordOperators;
 use crate::templates::tags::types::KnownOptions;
 use crate::templates::tags::types::RequiredKeyword;
 use crate::templates::tags::types::SplitPosition;
@@ -68,20 +71,24 @@ pub(crate) struct AnalysisResult {
     pub constraints: ExtractedTagConstraints,
     pub diagnostic_messages: Vec<ExtractedDiagnosticMessage>,
     pub known_options: Option<KnownOptions>,
+    pub keyword_operators: Option<KeywordOperators>,
 }
 
 impl AnalysisResult {
     /// Merge another result into this one.
     ///
-    /// Constraints are combined additively. For `known_options`, the other
-    /// result's value wins if present (last write wins — matches the sequential
-    /// processing order of statements).
+    /// Constraints are combined additively. For `known_options` and
+    /// `keyword_operators`, the other result's value wins if present (last
+    /// write wins — matches the sequential processing order of statements).
     fn extend(&mut self, other: AnalysisResult) {
         self.constraints.extend(other.constraints);
         self.diagnostic_messages.extend(other.diagnostic_messages);
         if other.known_options.is_some() {
             self.known_options = other.known_options;
         }
+        if other.keyword_operators.is_some() {
+            self.keyword_operators = other.keyword_operators;
+        }
     }
 }
 
@@ -91,6 +98,7 @@ impl From<ExtractedRuleFragment> for AnalysisResult {
             constraints: rule.constraints,
             diagnostic_messages: rule.diagnostic_messages,
             known_options: None,
+            keyword_operators: None,
         }
     }
 }
@@ -161,6 +169,7 @@ pub(crate) fn analyze_compile_function(func: &StmtFunctionDef) -> TagRule {
         required_keywords: result.constraints.required_keywords,
         choice_at_constraints: result.constraints.choice_at_constraints,
         known_options: result.known_options,
+        keyword_operators: result.keyword_operators,
         diagnostic_messages: if result.diagnostic_messages.is_empty() {
             None
         } else {
@@ -19,6 +19,7 @@ pub(crate) enum AbstractValueKey {
     Unknown,
     Token,
     Parser,
+    TokenContents(usize),
     SplitResult(TokenSplit),
     SplitElement(crate::templates::tags::types::SplitPosition),
     SplitLength(TokenSplit),
@@ -33,12 +34,13 @@ impl From<&AbstractValue> for AbstractValueKey {
             AbstractValue::Unknown => AbstractValueKey::Unknown,
             AbstractValue::Token => AbstractValueKey::Token,
             AbstractValue::Parser => AbstractValueKey::Parser,
+            AbstractValue::TokenContents { start } => AbstractValueKey::TokenContents(*start),
             AbstractValue::SplitResult(split) => AbstractValueKey::SplitResult(*split),
             AbstractValue::SplitElement { index } => AbstractValueKey::SplitElement(*index),
             AbstractValue::SplitLength(split) => AbstractValueKey::SplitLength(*split),
             AbstractValue::Int(n) => AbstractValueKey::Int(*n),
             AbstractValue::Str(s) => AbstractValueKey::Str(s.clone()),
-            AbstractValue::Tuple(_) => AbstractValueKey::Other,
+            AbstractValue::Tuple(_) | AbstractValue::RegexMatch(_) => AbstractValueKey::Other,
         }
     }
 }
@@ -49,6 +51,9 @@ impl From<&AbstractValueKey> for AbstractValue {
             AbstractValueKey::Unknown | AbstractValueKey::Other => AbstractValue::Unknown,
             AbstractValueKey::Token => AbstractValue::Token,
             AbstractValueKey::Parser => AbstractValue::Parser,
+            AbstractValueKey::TokenContents(start) => {
+                AbstractValue::TokenContents { start: *start }
+            }
             AbstractValueKey::SplitResult(split) => AbstractValue::SplitResult(*split),
             AbstractValueKey::SplitElement(index) => AbstractValue::SplitElement { index: *index },
             AbstractValueKey::SplitLength(split) => AbstractValue::SplitLength(*split),
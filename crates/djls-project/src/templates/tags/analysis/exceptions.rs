@@ -100,8 +100,10 @@ fn extract_message_arg(expr: &Expr, env: &Env) -> Option<ExtractedMessageArg> {
         AbstractValue::Unknown
         | AbstractValue::Token
         | AbstractValue::Parser
+        | AbstractValue::TokenContents { .. }
         | AbstractValue::SplitResult(_)
         | AbstractValue::SplitLength(_)
-        | AbstractValue::Tuple(_) => None,
+        | AbstractValue::Tuple(_)
+        | AbstractValue::RegexMatch(_) => None,
     }
 }
@@ -12,6 +12,7 @@ use ruff_python_ast::Number;
 use crate::ast::ExprExt;
 use crate::templates::tags::analysis::CallContext;
 use crate::templates::tags::analysis::calls::resolve_call;
+use crate::templates::tags::analysis::patterns::TokenPattern;
 use crate::templates::tags::analysis::state::AbstractValue;
 use crate::templates::tags::analysis::state::Env;
 use crate::templates::tags::analysis::state::TokenSplit;
@@ -63,6 +64,10 @@ pub(super) fn eval_expr_with_ctx(
             eval_subscript(&base, slice, env)
         }
 
+        Expr::Attribute(ExprAttribute { value, attr, .. }) => {
+            eval_attribute(value, attr.as_str(), env, ctx)
+        }
+
         Expr::BoolOp(_)
         | Expr::Named(_)
         | Expr::BinOp(_)
@@ -86,7 +91,6 @@ pub(super) fn eval_expr_with_ctx(
         | Expr::BooleanLiteral(_)
         | Expr::NoneLiteral(_)
         | Expr::EllipsisLiteral(_)
-        | Expr::Attribute(_)
         | Expr::Starred(_)
         | Expr::Name(_)
         | Expr::List(_)
@@ -95,6 +99,23 @@ pub(super) fn eval_expr_with_ctx(
     }
 }
 
+/// Evaluate attribute access: `parser.token` and `token.contents`.
+fn eval_attribute(
+    value: &Expr,
+    attr: &str,
+    env: &mut Env,
+    ctx: Option<&mut CallContext<'_>>,
+) -> AbstractValue {
+    let obj = eval_expr_with_ctx(value, env, ctx);
+    if matches!(obj, AbstractValue::Parser) && attr == "token" {
+        return AbstractValue::Token;
+    }
+    if matches!(obj, AbstractValue::Token) && attr == "contents" {
+        return AbstractValue::TokenContents { start: 0 };
+    }
+    AbstractValue::Unknown
+}
+
 /// Evaluate a function/method call expression with optional context.
 fn eval_call_with_ctx(
     call: &ExprCall,
@@ -105,44 +126,38 @@ fn eval_call_with_ctx(
         let obj = eval_expr_with_ctx(value, env, ctx.as_deref_mut());
         let method = attr.as_str();
 
-        // token.split_contents()
+        // token.split_contents() or parser.token.split_contents()
         if matches!((&obj, method), (AbstractValue::Token, "split_contents")) {
             return AbstractValue::SplitResult(TokenSplit::fresh());
         }
 
-        // parser.token.split_contents()
-        if method == "split_contents"
-            && let Expr::Attribute(ExprAttribute {
-                value: inner_value,
-                attr: inner_attr,
-                ..
-            }) = value.as_ref()
-        {
-            let inner_obj = eval_expr_with_ctx(inner_value, env, ctx.as_deref_mut());
-            if matches!(inner_obj, AbstractValue::Parser) && inner_attr.as_str() == "token" {
-                return AbstractValue::SplitResult(TokenSplit::fresh());
-            }
-        }
-
         // bits.pop(0) or bits.pop()
         if method == "pop" && matches!(obj, AbstractValue::SplitResult(_)) {
             return eval_pop_return(&obj, &call.arguments);
         }
 
-        // token.contents.split(...)
-        if method == "split"
-            && let Expr::Attribute(ExprAttribute {
-                value: inner_value,
-                attr: inner_attr,
-                ..
-            }) = value.as_ref()
-        {
-            let inner_obj = eval_expr_with_ctx(inner_value, env, ctx.as_deref_mut());
-            if matches!(inner_obj, AbstractValue::Token) && inner_attr.as_str() == "contents" {
-                return eval_contents_split(&call.arguments);
+        if let AbstractValue::TokenContents { start } = obj {
+            match method {
+                // token.contents.split(...)
+                "split" => return eval_contents_split(&call.arguments, start),
+                // token.contents.strip() keeps the same words
+                "strip" | "lstrip" | "rstrip" if call.arguments.args.is_empty() => return obj,
+                _ => {}
             }
         }
 
+        // re.match(pattern, token.contents)
+        if value.name_target() == Some("re")
+            && let [pattern_arg, subject, ..] = &*call.arguments.args
+            && let AbstractValue::Str(regex) =
+                eval_expr_with_ctx(pattern_arg, env, ctx.as_deref_mut())
+            && let AbstractValue::TokenContents { start } =
+                eval_expr_with_ctx(subject, env, ctx.as_deref_mut())
+            && let Some(pattern) = TokenPattern::parse(method, &regex, start)
+        {
+            return AbstractValue::RegexMatch(pattern);
+        }
+
         // Hardcoded external summaries for parser methods
         if matches!(obj, AbstractValue::Parser) {
             match method {
@@ -201,12 +216,12 @@ fn eval_call_with_ctx(
 }
 
 /// Handle `token.contents.split(...)` patterns.
-fn eval_contents_split(args: &Arguments) -> AbstractValue {
-    if args.args.is_empty() {
-        return AbstractValue::SplitResult(TokenSplit::fresh());
-    }
+///
+/// `start` is the split position of the first word of the contents being split.
+fn eval_contents_split(args: &Arguments, start: usize) -> AbstractValue {
+    let split = TokenSplit::fresh().after_slice_from(start);
 
-    // token.contents.split(None, 1) → Tuple of [SplitElement(Forward(0)), Unknown]
+    // token.contents.split(None, 1) → Tuple of [SplitElement(Forward(0)), rest of contents]
     if args.args.len() == 2
         && let Expr::NoneLiteral(_) = &args.args[0]
         && let Expr::NumberLiteral(ExprNumberLiteral {
@@ -217,13 +232,13 @@ fn eval_contents_split(args: &Arguments) -> AbstractValue {
     {
         return AbstractValue::Tuple(vec![
             AbstractValue::SplitElement {
-                index: SplitPosition::Forward(0),
+                index: split.resolve_index(0),
             },
-            AbstractValue::Unknown,
+            AbstractValue::TokenContents { start: start + 1 },
         ]);
     }
 
-    AbstractValue::SplitResult(TokenSplit::fresh())
+    AbstractValue::SplitResult(split)
 }
 
 /// Evaluate the return value of `split_result.pop(0)` or `split_result.pop()`.
@@ -160,7 +160,8 @@ fn eval_condition(expr: &Expr, env: &mut Env) -> ExtractedTagConstraints {
         // Comparison: `len(bits) < 4` or `bits[2] != "as"`
         Expr::Compare(compare) => eval_compare(compare, env),
 
-        // Negation: `not (2 <= len(bits) <= 4)` or `not len(bits) == 3`
+        // Negation: `not (2 <= len(bits) <= 4)`, `not len(bits) == 3`, or
+        // `not m` for a regex match against token contents
         Expr::UnaryOp(ExprUnaryOp {
             op: UnaryOp::Not,
             operand,
@@ -168,6 +169,8 @@ fn eval_condition(expr: &Expr, env: &mut Env) -> ExtractedTagConstraints {
         }) => {
             if let Expr::Compare(compare) = operand.as_ref() {
                 eval_negated_compare(compare, env)
+            } else if let AbstractValue::RegexMatch(pattern) = eval_expr(operand, env) {
+                pattern.constraints()
             } else {
                 ExtractedTagConstraints::default()
             }
@@ -227,6 +230,14 @@ fn eval_compare(compare: &ExprCompare, env: &mut Env) -> ExtractedTagConstraints
     let left_val = eval_expr(left, env);
     let right_val = eval_expr(comparator, env);
 
+    // `m is None` for a regex match against token contents
+    if let AbstractValue::RegexMatch(pattern) = &left_val
+        && matches!(op, CmpOp::Is | CmpOp::Eq)
+        && matches!(comparator, Expr::NoneLiteral(_))
+    {
+        return pattern.constraints();
+    }
+
     // len(split_result) vs integer
     if let AbstractValue::SplitLength(split) = &left_val {
         if let Some(n) = comparator.non_negative_integer() {
@@ -1288,4 +1299,94 @@ def do_tag(parser, token):
             }]
         );
     }
+
+    // Fabricated: the regex pattern from Django's custom template tag docs,
+    // applied to the remainder of `token.contents.split(None, 1)`.
+    #[test]
+    fn regex_search_on_contents_remainder() {
+        let c = extract_from_source(
+            r#"
+def do_tag(parser, token):
+    tag_name, arg = token.contents.split(None, 1)
+    m = re.search(r"(.*?) as (\w+)$", arg)
+    if not m:
+        raise TemplateSyntaxError("%r tag had invalid arguments" % tag_name)
+"#,
+        );
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Min(4)]);
+        assert_eq!(
+            c.required_keywords,
+            vec![RequiredKeyword {
+                position: SplitPosition::Backward(2),
+                value: "as".to_string()
+            }]
+        );
+    }
+
+    // Fabricated: `re.match` on the stripped contents, guarded by `is None`.
+    #[test]
+    fn regex_match_on_stripped_contents() {
+        let c = extract_from_source(
+            r#"
+def do_tag(parser, token):
+    contents = token.contents.strip()
+    match = re.match(r"^\w+ for (\w+) as (\w+)$", contents)
+    if match is None:
+        raise TemplateSyntaxError("err")
+"#,
+        );
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Exact(5)]);
+        assert_eq!(
+            c.required_keywords,
+            vec![
+                RequiredKeyword {
+                    position: SplitPosition::Forward(1),
+                    value: "for".to_string()
+                },
+                RequiredKeyword {
+                    position: SplitPosition::Forward(3),
+                    value: "as".to_string()
+                },
+            ]
+        );
+    }
+
+    // Fabricated: splitting the remainder of `split(None, 1)` keeps positions
+    // relative to the full tag.
+    #[test]
+    fn split_of_contents_remainder() {
+        let c = extract_from_source(
+            r#"
+def do_tag(parser, token):
+    tag_name, rest = token.contents.split(None, 1)
+    bits = rest.split()
+    if len(bits) != 2 or bits[0] != "to":
+        raise TemplateSyntaxError("err")
+"#,
+        );
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Exact(3)]);
+        assert_eq!(
+            c.required_keywords,
+            vec![RequiredKeyword {
+                position: SplitPosition::Forward(1),
+                value: "to".to_string()
+            }]
+        );
+    }
+
+    // Fabricated: a regex over an arbitrary loop variable (like `url`'s
+    // `kwarg_re.match(bit)`) is not a constraint on the tag.
+    #[test]
+    fn regex_on_untracked_subject_is_ignored() {
+        let c = extract_from_source(
+            r#"
+def do_tag(parser, token):
+    for bit in token.split_contents()[1:]:
+        if not re.match(r"(\w+)", bit):
+            raise TemplateSyntaxError("err")
+"#,
+        );
+        assert!(c.arg_constraints.is_empty());
+        assert!(c.required_keywords.is_empty());
+    }
 }
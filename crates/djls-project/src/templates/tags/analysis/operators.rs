@@ -0,0 +1,121 @@
+use std::ops::ControlFlow;
+
+use ruff_python_ast::CmpOp;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCompare;
+use ruff_python_ast::ExprTuple;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtFor;
+
+use crate::ast::ExprExt;
+use crate::ast::Recurse;
+use crate::ast::walk_stmts;
+use crate::templates::tags::analysis::expressions::eval_expr;
+use crate::templates::tags::analysis::state::AbstractValue;
+use crate::templates::tags::analysis::state::Env;
+use crate::templates::tags::types::KeywordOperators;
+use crate::templates::tags::types::SplitPosition;
+
+/// Prefix operators recognized in a literal operator set, following `smart_if`.
+const PREFIX_OPERATORS: &[&str] = &["not"];
+
+/// Try to extract a `KeywordOperators` grammar from a `for` loop over the split result.
+///
+/// Detects the pattern:
+/// ```python
+/// for bit in bits[1:]:
+///     if bit in ("and", "or", "not"):
+///         ...
+/// ```
+///
+/// `enumerate(...)` over the split result is also accepted. Only membership
+/// tests against literal sets of two or more strings count; a single keyword
+/// compared with `==` is usually an `as` or option check, not an operator.
+pub(super) fn try_extract_operator_loop(
+    for_stmt: &StmtFor,
+    env: &mut Env,
+) -> Option<KeywordOperators> {
+    let (element_var, iter) = loop_element(for_stmt)?;
+    let AbstractValue::SplitResult(split) = eval_expr(iter, env) else {
+        return None;
+    };
+    let SplitPosition::Forward(start) = split.resolve_index(0) else {
+        return None;
+    };
+
+    let mut operators: Vec<String> = Vec::new();
+    walk_stmts(&for_stmt.body, Recurse::WithinScope, |stmt| {
+        if let Stmt::If(if_stmt) = stmt {
+            let tests = std::iter::once(if_stmt.test.as_ref())
+                .chain(if_stmt.elif_else_clauses.iter().filter_map(|c| c.test.as_ref()));
+            for test in tests {
+                for value in membership_literals(test, element_var).unwrap_or_default() {
+                    if !operators.contains(&value) {
+                        operators.push(value);
+                    }
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+
+    if operators.is_empty() {
+        return None;
+    }
+
+    let (prefix, infix): (Vec<String>, Vec<String>) = operators
+        .into_iter()
+        .partition(|operator| PREFIX_OPERATORS.contains(&operator.as_str()));
+    Some(KeywordOperators {
+        start: start.max(1),
+        infix,
+        prefix,
+    })
+}
+
+/// The element variable and iterable of `for bit in X` or `for i, bit in enumerate(X)`.
+fn loop_element(for_stmt: &StmtFor) -> Option<(&str, &Expr)> {
+    if let Some(name) = for_stmt.target.name_target() {
+        return Some((name, &for_stmt.iter));
+    }
+
+    let Expr::Tuple(ExprTuple { elts, .. }) = for_stmt.target.as_ref() else {
+        return None;
+    };
+    let [_, element] = &elts[..] else {
+        return None;
+    };
+    let Expr::Call(call) = for_stmt.iter.as_ref() else {
+        return None;
+    };
+    if call.func.name_target() != Some("enumerate") {
+        return None;
+    }
+    let [iter] = &call.arguments.args[..] else {
+        return None;
+    };
+    Some((element.name_target()?, iter))
+}
+
+/// Literal values from `element in ("a", "b")`, when there are at least two.
+fn membership_literals(test: &Expr, element_var: &str) -> Option<Vec<String>> {
+    let Expr::Compare(ExprCompare {
+        left,
+        ops,
+        comparators,
+        ..
+    }) = test
+    else {
+        return None;
+    };
+    if ops.len() != 1 || !matches!(ops[0], CmpOp::In) {
+        return None;
+    }
+    if left.name_target() != Some(element_var) {
+        return None;
+    }
+    let values = comparators
+        .first()?
+        .collection_map(|expr| expr.string_literal().map(str::to_string))?;
+    (values.len() >= 2).then_some(values)
+}
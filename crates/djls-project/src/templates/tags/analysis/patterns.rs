@@ -0,0 +1,258 @@
+//! Regex match modeling for `token.contents`.
+//!
+//! Some compile functions validate their arguments with a regular expression
+//! instead of counting `split_contents()` bits:
+//!
+//! ```python
+//! tag_name, arg = token.contents.split(None, 1)
+//! m = re.search(r"(.*?) as (\w+)", arg)
+//! if not m:
+//!     raise TemplateSyntaxError(...)
+//! ```
+//!
+//! Patterns made of space-separated words translate into argument-count and
+//! keyword constraints. Anything more elaborate (alternation, optional
+//! groups, quantified whitespace) is left unmodeled.
+
+use serde::Serialize;
+
+use crate::templates::tags::analysis::constraints::ExtractedTagConstraints;
+use crate::templates::tags::types::ArgumentCountConstraint;
+use crate::templates::tags::types::RequiredKeyword;
+use crate::templates::tags::types::SplitPosition;
+
+/// A regex match against token contents, reduced to its word structure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct TokenPattern {
+    /// Split position of the first word of the matched text.
+    start: usize,
+    anchored_start: bool,
+    anchored_end: bool,
+    words: Vec<PatternWord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+enum PatternWord {
+    /// A literal keyword, e.g. `as`
+    Literal(String),
+    /// A single argument, e.g. `(\w+)` or `[\w.]+`
+    Argument,
+    /// One or more arguments, e.g. `(.*?)`
+    Rest,
+}
+
+impl TokenPattern {
+    /// Model `re.<function>(pattern, subject)` where `subject` starts at split position `start`.
+    ///
+    /// Returns `None` for unsupported functions or patterns that don't reduce
+    /// to a sequence of space-separated words.
+    pub(crate) fn parse(function: &str, pattern: &str, start: usize) -> Option<Self> {
+        let (mut anchored_start, mut anchored_end) = match function {
+            "match" => (true, false),
+            "fullmatch" => (true, true),
+            "search" => (false, false),
+            _ => return None,
+        };
+
+        let mut pattern = pattern;
+        if let Some(rest) = pattern.strip_prefix('^') {
+            pattern = rest;
+            anchored_start = true;
+        }
+        if let Some(rest) = pattern
+            .strip_suffix("\\Z")
+            .or_else(|| pattern.strip_suffix('$'))
+        {
+            pattern = rest;
+            anchored_end = true;
+        }
+
+        let pattern = pattern.replace("\\s+", " ").replace("\\s", " ");
+        let words = pattern
+            .split(' ')
+            .map(PatternWord::parse)
+            .collect::<Option<Vec<_>>>()?;
+        if words.iter().all(|word| matches!(word, PatternWord::Rest)) {
+            return None;
+        }
+
+        Some(Self {
+            start,
+            anchored_start,
+            anchored_end,
+            words,
+        })
+    }
+
+    /// Constraints implied by a guard that raises when the pattern doesn't match.
+    ///
+    /// The subject has no leading or trailing whitespace (Django strips token
+    /// contents, and `split(None, 1)` strips the remainder), so every word of
+    /// the pattern, including `.*`, matches at least one bit.
+    pub(crate) fn constraints(&self) -> ExtractedTagConstraints {
+        let length = self.start + self.words.len();
+        let has_rest = self
+            .words
+            .iter()
+            .any(|word| matches!(word, PatternWord::Rest));
+        let constraint = if self.anchored_start && self.anchored_end && !has_rest {
+            ArgumentCountConstraint::Exact(length)
+        } else {
+            ArgumentCountConstraint::Min(length)
+        };
+
+        ExtractedTagConstraints {
+            arg_constraints: vec![constraint],
+            required_keywords: self.required_keywords(),
+            ..Default::default()
+        }
+    }
+
+    /// Literal words whose split position is fixed by an anchor.
+    fn required_keywords(&self) -> Vec<RequiredKeyword> {
+        let mut keywords = Vec::new();
+        let last = self.words.len() - 1;
+
+        if self.anchored_start {
+            for (i, word) in self.words.iter().enumerate() {
+                match word {
+                    PatternWord::Rest => break,
+                    // An unanchored final literal may be the prefix of a longer word.
+                    PatternWord::Literal(value) if i < last || self.anchored_end => {
+                        keywords.push(RequiredKeyword {
+                            position: SplitPosition::Forward(self.start + i),
+                            value: value.clone(),
+                        });
+                    }
+                    PatternWord::Literal(_) | PatternWord::Argument => {}
+                }
+            }
+        }
+
+        let fixed_tail = self
+            .words
+            .iter()
+            .rev()
+            .take_while(|word| !matches!(word, PatternWord::Rest))
+            .count();
+        if self.anchored_end && !(self.anchored_start && fixed_tail == self.words.len()) {
+            for (j, word) in self.words.iter().rev().enumerate().take(fixed_tail) {
+                // An unanchored first literal may be the suffix of a longer word.
+                if j == last {
+                    continue;
+                }
+                if let PatternWord::Literal(value) = word {
+                    keywords.push(RequiredKeyword {
+                        position: SplitPosition::Backward(j + 1),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+
+        keywords
+    }
+}
+
+impl PatternWord {
+    fn parse(word: &str) -> Option<Self> {
+        if is_literal(word) {
+            return Some(Self::Literal(word.to_string()));
+        }
+
+        let inner = unwrap_group(word).unwrap_or(word);
+        if is_literal(inner) {
+            return Some(Self::Literal(inner.to_string()));
+        }
+        if matches!(inner, ".*" | ".+" | ".*?" | ".+?") {
+            return Some(Self::Rest);
+        }
+
+        let class = inner
+            .strip_suffix("+?")
+            .or_else(|| inner.strip_suffix('+'))?;
+        let is_word_class = matches!(class, "\\w" | "\\S" | "\\d")
+            || class
+                .strip_prefix('[')
+                .and_then(|class| class.strip_suffix(']'))
+                .is_some_and(|members| {
+                    !members.is_empty()
+                        && !members.starts_with('^')
+                        && !members.contains([' ', '[', ']'])
+                        && !members.contains("\\s")
+                });
+        is_word_class.then_some(Self::Argument)
+    }
+}
+
+/// Strip a single capturing, named, or non-capturing group around `word`.
+fn unwrap_group(word: &str) -> Option<&str> {
+    let body = word.strip_prefix('(')?.strip_suffix(')')?;
+    if let Some(named) = body.strip_prefix("?P<") {
+        return named.split_once('>').map(|(_, inner)| inner);
+    }
+    if let Some(inner) = body.strip_prefix("?:") {
+        return Some(inner);
+    }
+    (!body.starts_with('?')).then_some(body)
+}
+
+fn is_literal(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(function: &str, pattern: &str, start: usize) -> ExtractedTagConstraints {
+        TokenPattern::parse(function, pattern, start)
+            .expect("pattern should be modeled")
+            .constraints()
+    }
+
+    #[test]
+    fn fullmatch_of_fixed_words_is_exact() {
+        let c = constraints("fullmatch", r"(\w+) as (?P<var>\w+)", 1);
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Exact(4)]);
+        assert_eq!(
+            c.required_keywords,
+            vec![RequiredKeyword {
+                position: SplitPosition::Forward(2),
+                value: "as".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn search_with_rest_is_minimum() {
+        let c = constraints("search", r"(.*?) as (\w+)", 1);
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Min(4)]);
+        assert!(c.required_keywords.is_empty());
+    }
+
+    #[test]
+    fn end_anchor_fixes_keywords_after_rest() {
+        let c = constraints("search", r"(.*?) as (\w+)$", 1);
+        assert_eq!(c.arg_constraints, vec![ArgumentCountConstraint::Min(4)]);
+        assert_eq!(
+            c.required_keywords,
+            vec![RequiredKeyword {
+                position: SplitPosition::Backward(2),
+                value: "as".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn unsupported_patterns_are_not_modeled() {
+        assert!(TokenPattern::parse("match", r"(\w+)( as \w+)?", 0).is_none());
+        assert!(TokenPattern::parse("match", r"(on|off)", 0).is_none());
+        assert!(TokenPattern::parse("match", r"\w+  \w+", 0).is_none());
+        assert!(TokenPattern::parse("match", r".*", 0).is_none());
+        assert!(TokenPattern::parse("sub", r"\w+", 0).is_none());
+    }
+}
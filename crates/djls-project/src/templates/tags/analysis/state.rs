@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
+use crate::templates::tags::analysis::patterns::TokenPattern;
 use crate::templates::tags::types::SplitPosition;
 
 /// Tracks how a `token.split_contents()` result has been mutated.
@@ -106,6 +107,9 @@ pub(crate) enum AbstractValue {
     Token,
     /// The `parser` parameter to the compile function
     Parser,
+    /// `token.contents`, or the unsplit remainder of `token.contents.split(None, 1)`.
+    /// `start` is the split position of its first word.
+    TokenContents { start: usize },
     /// Result of `token.split_contents()` or `token.contents.split()`.
     /// The `TokenSplit` tracks mutations (pop from front/back, slicing).
     SplitResult(TokenSplit),
@@ -120,6 +124,8 @@ pub(crate) enum AbstractValue {
     Str(String),
     /// Tuple of tracked values (for function return/destructuring)
     Tuple(Vec<AbstractValue>),
+    /// `re.match(pattern, token.contents)` and friends
+    RegexMatch(TokenPattern),
 }
 
 /// The abstract environment: maps variable names to their abstract values.
@@ -13,6 +13,7 @@ use crate::templates::tags::analysis::match_arms::extract_match_constraints;
 use crate::templates::tags::analysis::mutations::apply_pop_mutation;
 use crate::templates::tags::analysis::mutations::try_extract_option_loop;
 use crate::templates::tags::analysis::mutations::try_extract_pop_call;
+use crate::templates::tags::analysis::operators::try_extract_operator_loop;
 use crate::templates::tags::analysis::state::AbstractValue;
 use crate::templates::tags::analysis::state::Env;
 use crate::templates::tags::types::SplitPosition;
@@ -93,6 +94,7 @@ fn process_statement(stmt: &Stmt, env: &mut Env, ctx: &mut CallContext<'_>) -> A
         }
 
         Stmt::For(stmt_for) => {
+            result.keyword_operators = try_extract_operator_loop(stmt_for, env);
             result.extend(process_statements(&stmt_for.body, env, ctx));
             result.extend(process_statements(&stmt_for.orelse, env, ctx));
         }
@@ -325,10 +327,12 @@ fn process_tuple_unpack(targets: &[Expr], value: &AbstractValue, env: &mut Env)
         AbstractValue::Unknown
         | AbstractValue::Token
         | AbstractValue::Parser
+        | AbstractValue::TokenContents { .. }
         | AbstractValue::SplitElement { .. }
         | AbstractValue::SplitLength(_)
         | AbstractValue::Int(_)
-        | AbstractValue::Str(_) => {
+        | AbstractValue::Str(_)
+        | AbstractValue::RegexMatch(_) => {
             for target in targets {
                 if let Some(name) = target.name_target() {
                     env.set(name.to_string(), AbstractValue::Unknown);
@@ -594,7 +598,10 @@ def do_tag(parser, token):
                 index: SplitPosition::Forward(0)
             }
         );
-        assert_eq!(env.get("rest"), &AbstractValue::Unknown);
+        assert_eq!(
+            env.get("rest"),
+            &AbstractValue::TokenContents { start: 1 }
+        );
     }
 
     #[test]
@@ -987,6 +994,43 @@ def do_tag(parser, token):
         assert!(rule.known_options.is_none());
     }
 
+    // Fabricated: smart_if-style loop testing each bit against a literal
+    // operator set. `not` is a prefix operator; the rest are infix.
+    #[test]
+    fn operator_loop_grammar() {
+        let rule = analyze(
+            r#"
+def do_tag(parser, token):
+    bits = token.split_contents()
+    for i, bit in enumerate(bits[1:]):
+        if bit in ("and", "or", "not"):
+            continue
+"#,
+        );
+        let operators = rule
+            .keyword_operators
+            .expect("should have keyword_operators");
+        assert_eq!(operators.start, 1);
+        assert_eq!(operators.infix, vec!["and".to_string(), "or".to_string()]);
+        assert_eq!(operators.prefix, vec!["not".to_string()]);
+    }
+
+    // Fabricated: a single-keyword membership test is an `as`-style check,
+    // not an operator grammar.
+    #[test]
+    fn operator_loop_requires_literal_set() {
+        let rule = analyze(
+            r#"
+def do_tag(parser, token):
+    bits = token.split_contents()
+    for bit in bits:
+        if bit in ("as",):
+            break
+"#,
+        );
+        assert!(rule.keyword_operators.is_none());
+    }
+
     // Corpus: partialdef_func in defaulttags.py — match statement with
     // multiple case arms of different lengths (2 and 3 elements), producing
     // OneOf([2, 3]) constraint. Django 6.0+ match-based tag parsing.
@@ -1,4 +1,5 @@
 mod dynamic_end;
+mod helpers;
 mod next_token;
 mod opaque;
 mod parse_calls;
@@ -6,6 +7,7 @@ mod parse_calls;
 use ruff_python_ast::Expr;
 use ruff_python_ast::ExprAttribute;
 use ruff_python_ast::ExprCall;
+use ruff_python_ast::Stmt;
 use ruff_python_ast::StmtFunctionDef;
 
 use crate::ast::ExprExt;
@@ -54,9 +56,15 @@ pub(super) fn is_tag_name_value(value: &AbstractValue) -> bool {
 ///
 /// Also detects opaque blocks via `parser.skip_past(...)` patterns.
 ///
+/// When the function itself has no block structure, calls that hand the parser
+/// to one of `module_functions` are followed one level deep.
+///
 /// Returns `None` when no block structure is detected or inference is ambiguous.
 #[must_use]
-pub(crate) fn extract_block_spec(func: &StmtFunctionDef) -> Option<ExtractedBlockSpec> {
+pub(crate) fn extract_block_spec(
+    func: &StmtFunctionDef,
+    module_functions: &[&StmtFunctionDef],
+) -> Option<ExtractedBlockSpec> {
     let parser_var = func
         .parameters
         .args
@@ -69,23 +77,33 @@ pub(crate) fn extract_block_spec(func: &StmtFunctionDef) -> Option<ExtractedBloc
         .get(1)
         .map(|p| p.parameter.name.to_string())?;
 
+    if let Some(spec) = detect_in_body(&func.body, &parser_var, &token_var) {
+        return Some(spec);
+    }
+
+    // Try helpers that wrap parser.parse((...)): nodelist = parse_until_end(parser)
+    helpers::detect(func, &parser_var, &token_var, module_functions)
+}
+
+/// Run the block pattern detectors over one function body.
+fn detect_in_body(body: &[Stmt], parser_var: &str, token_var: &str) -> Option<ExtractedBlockSpec> {
     // Check for opaque block patterns first: parser.skip_past("endtag")
-    if let Some(spec) = opaque::detect(&func.body, &parser_var) {
+    if let Some(spec) = opaque::detect(body, parser_var) {
         return Some(spec);
     }
 
     // Try parser.parse((...)) calls with control flow classification
-    if let Some(spec) = parse_calls::detect(&func.body, &parser_var, &token_var) {
+    if let Some(spec) = parse_calls::detect(body, parser_var, token_var) {
         return Some(spec);
     }
 
     // Try dynamic end-tag patterns: parser.parse((f"end{tag_name}",))
-    if let Some(spec) = dynamic_end::detect(&func.body, &parser_var, &token_var) {
+    if let Some(spec) = dynamic_end::detect(body, parser_var, token_var) {
         return Some(spec);
     }
 
     // Try parser.next_token() loop patterns (e.g., blocktrans/blocktranslate)
-    next_token::detect(&func.body, &parser_var, &token_var)
+    next_token::detect(body, parser_var, token_var)
 }
 
 /// Check if an expression is the parser variable (or `self.parser`).
@@ -236,12 +254,38 @@ mod tests {
         panic!("no function definition found in source");
     }
 
+    fn parse_functions(source: &str) -> Vec<StmtFunctionDef> {
+        let parsed = parse_module(source).expect("valid Python");
+        parsed
+            .into_syntax()
+            .body
+            .into_iter()
+            .filter_map(|stmt| {
+                if let Stmt::FunctionDef(func_def) = stmt {
+                    Some(func_def)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn extract_with_helpers(source: &str, name: &str) -> Option<ExtractedBlockSpec> {
+        let functions = parse_functions(source);
+        let module_functions: Vec<_> = functions.iter().collect();
+        let func = functions
+            .iter()
+            .find(|func| func.name.as_str() == name)
+            .expect("compile function should be defined");
+        extract_block_spec(func, &module_functions)
+    }
+
     // Corpus: verbatim in defaulttags.py — parse(("endverbatim",)) + delete_first_token
     #[test]
     fn simple_end_tag_single_parse() {
         let func = django_function("django/template/defaulttags.py", "verbatim")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endverbatim"));
         assert!(spec.intermediates.is_empty());
         assert!(!spec.opaque);
@@ -252,7 +296,7 @@ mod tests {
     fn if_else_intermediates() {
         let func = django_function("django/template/defaulttags.py", "do_if")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endif"));
         assert!(spec.intermediates.contains(&"elif".to_string()));
         assert!(spec.intermediates.contains(&"else".to_string()));
@@ -264,7 +308,7 @@ mod tests {
     fn opaque_block_skip_past() {
         let func = django_function("django/template/defaulttags.py", "comment")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endcomment"));
         assert!(spec.intermediates.is_empty());
         assert!(spec.opaque);
@@ -281,7 +325,7 @@ def do_repeat(parser, token):
     return RepeatNode(nodelist)
 "#;
         let func = parse_function(source);
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("done"));
         assert!(spec.intermediates.is_empty());
     }
@@ -297,7 +341,7 @@ def do_custom(parser, token):
     return CustomNode(nodelist)
 "#;
         let func = parse_function(source);
-        assert!(extract_block_spec(&func).is_none());
+        assert!(extract_block_spec(&func, &[]).is_none());
     }
 
     // Fabricated: tests f-string in parser.parse() producing dynamic (None) end-tag.
@@ -315,7 +359,7 @@ def do_block(parser, token):
     return BlockNode(tag_name, nodelist)
 "#;
         let func = parse_function(source);
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag, EndTagEvidence::SelfNamed);
         assert!(spec.intermediates.is_empty());
         assert!(!spec.opaque);
@@ -327,7 +371,7 @@ def do_block(parser, token):
     fn multiple_parse_calls_classify_correctly() {
         let func = django_function("django/template/defaulttags.py", "do_for")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endfor"));
         assert_eq!(spec.intermediates, vec!["empty".to_string()]);
         assert!(!spec.opaque);
@@ -338,7 +382,7 @@ def do_block(parser, token):
     fn no_parse_calls_returns_none() {
         let func = django_function("django/template/defaulttags.py", "now")
             .expect("expected Django fixture function should exist");
-        assert!(extract_block_spec(&func).is_none());
+        assert!(extract_block_spec(&func, &[]).is_none());
     }
 
     // Fabricated: tests classytags-style self.parser.parse() pattern.
@@ -353,7 +397,7 @@ def do_block(self, token):
     return BlockNode(nodelist)
 "#;
         let func = parse_function(source);
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endblock"));
     }
 
@@ -368,7 +412,7 @@ def do_if(parser, token):
     return IfNode(nodelist)
 "#;
         let func = parse_function(source);
-        assert!(extract_block_spec(&func).is_none());
+        assert!(extract_block_spec(&func, &[]).is_none());
     }
 
     // Corpus: do_block in loader_tags.py — parse(("endblock",)) with next_token
@@ -377,7 +421,7 @@ def do_if(parser, token):
     fn simple_block_with_endblock_validation() {
         let func = django_function("django/template/loader_tags.py", "do_block")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endblock"));
         assert!(spec.intermediates.is_empty());
         assert!(!spec.opaque);
@@ -389,7 +433,7 @@ def do_if(parser, token):
     fn sequential_parse_then_check() {
         let func = django_function("django/template/defaulttags.py", "spaceless")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endspaceless"));
         assert!(spec.intermediates.is_empty());
     }
@@ -400,7 +444,7 @@ def do_if(parser, token):
     fn next_token_loop_blocktrans_pattern() {
         let func = django_function("django/templatetags/i18n.py", "do_block_translate")
             .expect("expected Django fixture function should exist");
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag, EndTagEvidence::SelfNamed);
         assert_eq!(spec.intermediates, vec!["plural".to_string()]);
         assert!(!spec.opaque);
@@ -425,7 +469,7 @@ def do_custom_block(parser, token):
     return CustomBlockNode(content)
 "#;
         let func = parse_function(source);
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endcustom"));
         assert!(spec.intermediates.is_empty());
         assert!(!spec.opaque);
@@ -458,7 +502,7 @@ def do_custom(parser, token):
     return CustomNode(nodes)
 "#;
         let func = parse_function(source);
-        let spec = extract_block_spec(&func).expect("should extract block spec");
+        let spec = extract_block_spec(&func, &[]).expect("should extract block spec");
         assert_eq!(spec.end_tag.as_literal(), Some("endcustom"));
         assert_eq!(spec.intermediates, vec!["middle".to_string()]);
     }
@@ -473,7 +517,69 @@ def do_simple(parser, token):
     return SimpleNode(bits[1])
 ";
         let func = parse_function(source);
-        assert!(extract_block_spec(&func).is_none());
+        assert!(extract_block_spec(&func, &[]).is_none());
+    }
+
+    // Fabricated: wrapper helpers like allauth's and wagtail's hard-code the
+    // stop tokens in a module-level function that receives the parser.
+    #[test]
+    fn helper_with_literal_stop_tokens() {
+        let source = r#"
+def do_panel(parser, token):
+    nodelist = _parse_body(parser)
+    return PanelNode(nodelist)
+
+def _parse_body(parser):
+    nodelist = parser.parse(("endpanel",))
+    parser.delete_first_token()
+    return nodelist
+"#;
+        let spec = extract_with_helpers(source, "do_panel").expect("should extract block spec");
+        assert_eq!(spec.end_tag.as_literal(), Some("endpanel"));
+        assert!(spec.intermediates.is_empty());
+        assert!(!spec.opaque);
+    }
+
+    // Fabricated: the stop-token tuple is passed to a shared helper as an argument.
+    #[test]
+    fn helper_with_stop_tokens_argument() {
+        let source = r#"
+def parse_until(p, end_tags):
+    nodelist = p.parse(end_tags)
+    p.delete_first_token()
+    return nodelist
+
+def do_card(parser, token):
+    nodelist = parse_until(parser, end_tags=("endcard",))
+    return CardNode(nodelist)
+"#;
+        let spec = extract_with_helpers(source, "do_card").expect("should extract block spec");
+        assert_eq!(spec.end_tag.as_literal(), Some("endcard"));
+    }
+
+    // Fabricated: helpers are only followed one level deep, and only when they
+    // receive the parser.
+    #[test]
+    fn helper_calls_are_followed_one_level() {
+        let source = r#"
+def do_outer(parser, token):
+    return middle(parser, token)
+
+def middle(parser, token):
+    return inner(parser)
+
+def inner(parser):
+    nodelist = parser.parse(("endouter",))
+    parser.delete_first_token()
+    return nodelist
+
+def do_unrelated(parser, token):
+    nodelist = inner(token)
+    return Node(nodelist)
+"#;
+        assert!(extract_with_helpers(source, "do_outer").is_none());
+        assert!(extract_with_helpers(source, "do_unrelated").is_none());
+        assert!(extract_with_helpers(source, "middle").is_some());
     }
 
     // Fabricated: function with no parameters at all returns None.
@@ -485,6 +591,6 @@ def helper():
     pass
 ";
         let func = parse_function(source);
-        assert!(extract_block_spec(&func).is_none());
+        assert!(extract_block_spec(&func, &[]).is_none());
     }
 }
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ExprContext;
+use ruff_python_ast::ExprName;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::visitor;
+use ruff_python_ast::visitor::Visitor;
+use ruff_python_ast::visitor::transformer;
+use ruff_python_ast::visitor::transformer::Transformer;
+
+use crate::ast::ExprExt;
+use crate::templates::tags::blocks::ExtractedBlockSpec;
+use crate::templates::tags::blocks::detect_in_body;
+use crate::templates::tags::blocks::is_parser_receiver;
+
+/// Detect block structure in a module-level helper that receives the compile function's parser.
+///
+/// Packages often wrap `parser.parse(("endtag",))` in a shared helper, either
+/// hard-coding the stop tokens there or passing them in as an argument. The
+/// helper body is analyzed with its parameters replaced by the call's
+/// arguments. Only one level of calls is followed.
+pub(super) fn detect(
+    func: &StmtFunctionDef,
+    parser_var: &str,
+    token_var: &str,
+    module_functions: &[&StmtFunctionDef],
+) -> Option<ExtractedBlockSpec> {
+    let mut visitor = HelperCallVisitor { calls: Vec::new() };
+    visitor.visit_body(&func.body);
+
+    visitor.calls.into_iter().find_map(|call| {
+        let name = call.func.name_target()?;
+        if name == func.name.as_str() {
+            return None;
+        }
+        let helper = module_functions
+            .iter()
+            .find(|helper| helper.name.as_str() == name)?;
+        HelperCall::bind(helper, call, parser_var, token_var)?.detect(helper)
+    })
+}
+
+/// Calls to plain names in a compile function body, in source order.
+struct HelperCallVisitor<'a> {
+    calls: Vec<&'a ExprCall>,
+}
+
+impl<'a> Visitor<'a> for HelperCallVisitor<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if matches!(stmt, Stmt::FunctionDef(_) | Stmt::ClassDef(_)) {
+            return;
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::Call(call) = expr
+            && call.func.name_target().is_some()
+        {
+            self.calls.push(call);
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+/// A helper call with its parameters bound to the caller's arguments.
+struct HelperCall {
+    parser_var: String,
+    token_var: Option<String>,
+    substitutions: BTreeMap<String, Expr>,
+}
+
+impl HelperCall {
+    /// Bind a call's arguments to the helper's parameters.
+    ///
+    /// Returns `None` unless the parser is passed and every argument lands on
+    /// a named parameter.
+    fn bind(
+        helper: &StmtFunctionDef,
+        call: &ExprCall,
+        parser_var: &str,
+        token_var: &str,
+    ) -> Option<Self> {
+        let arguments = &call.arguments;
+        if arguments
+            .args
+            .iter()
+            .any(|arg| matches!(arg, Expr::Starred(_)))
+        {
+            return None;
+        }
+
+        let parameters = &helper.parameters;
+        let positional: Vec<_> = parameters
+            .posonlyargs
+            .iter()
+            .chain(&parameters.args)
+            .collect();
+        if arguments.args.len() > positional.len() {
+            return None;
+        }
+
+        let mut bound = BTreeMap::new();
+        for (parameter, arg) in positional.iter().zip(&arguments.args) {
+            bound.insert(parameter.parameter.name.as_str(), arg);
+        }
+        for keyword in &arguments.keywords {
+            let name = keyword.arg.as_ref()?;
+            bound.insert(name.as_str(), &keyword.value);
+        }
+        for parameter in positional.iter().copied().chain(&parameters.kwonlyargs) {
+            let name = parameter.parameter.name.as_str();
+            if let Some(default) = parameter.default.as_deref() {
+                bound.entry(name).or_insert(default);
+            }
+        }
+
+        let parser_param = bound
+            .iter()
+            .find(|(_, arg)| is_parser_receiver(arg, parser_var))
+            .map(|(name, _)| (*name).to_string())?;
+        let token_param = bound
+            .iter()
+            .find(|(_, arg)| arg.name_target() == Some(token_var))
+            .map(|(name, _)| (*name).to_string());
+
+        let rebound = rebound_names(&helper.body);
+        let substitutions = bound
+            .into_iter()
+            .filter(|(name, _)| {
+                *name != parser_param
+                    && Some(*name) != token_param.as_deref()
+                    && !rebound.contains(*name)
+            })
+            .map(|(name, arg)| (name.to_string(), arg.clone()))
+            .collect();
+
+        Some(Self {
+            parser_var: parser_param,
+            token_var: token_param,
+            substitutions,
+        })
+    }
+
+    fn detect(&self, helper: &StmtFunctionDef) -> Option<ExtractedBlockSpec> {
+        let mut body = helper.body.clone();
+        if !self.substitutions.is_empty() {
+            Substitute {
+                substitutions: &self.substitutions,
+            }
+            .visit_body(&mut body);
+        }
+        // An empty token name never matches, so token-based patterns stay undetected.
+        detect_in_body(
+            &body,
+            &self.parser_var,
+            self.token_var.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// Names assigned anywhere in a helper body, whose later reads can't be substituted.
+fn rebound_names(body: &[Stmt]) -> BTreeSet<String> {
+    let mut visitor = StoredNameVisitor {
+        names: BTreeSet::new(),
+    };
+    visitor.visit_body(body);
+    visitor.names
+}
+
+struct StoredNameVisitor {
+    names: BTreeSet<String>,
+}
+
+impl<'a> Visitor<'a> for StoredNameVisitor {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::Name(ExprName {
+            id,
+            ctx: ExprContext::Store,
+            ..
+        }) = expr
+        {
+            self.names.insert(id.to_string());
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+/// Replace reads of bound parameters with the caller's argument expressions.
+struct Substitute<'a> {
+    substitutions: &'a BTreeMap<String, Expr>,
+}
+
+impl Transformer for Substitute<'_> {
+    fn visit_expr(&self, expr: &mut Expr) {
+        if let Expr::Name(ExprName {
+            id,
+            ctx: ExprContext::Load,
+            ..
+        }) = expr
+            && let Some(value) = self.substitutions.get(id.as_str())
+        {
+            *expr = value.clone();
+            return;
+        }
+        transformer::walk_expr(self, expr);
+    }
+}
@@ -1,6 +1,7 @@
 use ruff_python_ast::StmtFunctionDef;
 
 use crate::templates::FilterArity;
+use crate::templates::FilterSignature;
 use crate::templates::RegistrationKind;
 use crate::templates::TemplateSymbolKind;
 use crate::templates::filters;
@@ -34,6 +35,16 @@ impl RegistrationKind {
         }
     }
 
+    pub(crate) fn extract_filter_signature(
+        self,
+        func: &StmtFunctionDef,
+    ) -> Option<FilterSignature> {
+        match self {
+            Self::Filter => filters::extract_filter_signature(func),
+            Self::Tag | Self::SimpleTag | Self::InclusionTag | Self::SimpleBlockTag => None,
+        }
+    }
+
     pub(crate) fn extract_tag_rule(self, func: &StmtFunctionDef) -> Option<Box<TagRule>> {
         match self {
             Self::Filter => None,
@@ -54,11 +65,12 @@ impl RegistrationKind {
     pub(crate) fn extract_block_spec(
         self,
         func: &StmtFunctionDef,
+        module_functions: &[&StmtFunctionDef],
     ) -> Option<blocks::ExtractedBlockSpec> {
         match self {
             Self::Filter => None,
             Self::Tag | Self::SimpleTag | Self::InclusionTag | Self::SimpleBlockTag => {
-                blocks::extract_block_spec(func)
+                blocks::extract_block_spec(func, module_functions)
             }
         }
     }
@@ -91,6 +91,7 @@ pub(crate) fn extract_parse_bits_rule(func: &StmtFunctionDef, as_var: AsVar) ->
         required_keywords: Vec::new(),
         choice_at_constraints: Vec::new(),
         known_options: None,
+        keyword_operators: None,
         diagnostic_messages: None,
         extracted_args,
         as_var,
@@ -85,6 +85,8 @@ pub struct TagRule {
     pub choice_at_constraints: Vec<ChoiceAt>,
     pub known_options: Option<KnownOptions>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword_operators: Option<KeywordOperators>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub diagnostic_messages: Option<Vec<ExtractedDiagnosticMessage>>,
     pub extracted_args: Vec<TagArgument>,
     /// Support for Django's `{% tag args... as varname %}` form.
@@ -104,6 +106,7 @@ impl TagRule {
             || !self.required_keywords.is_empty()
             || !self.choice_at_constraints.is_empty()
             || self.known_options.is_some()
+            || self.keyword_operators.is_some()
             || self
                 .diagnostic_messages
                 .as_ref()
@@ -245,6 +248,19 @@ pub struct KnownOptions {
     pub rejects_unknown: bool,
 }
 
+/// Keyword operators that combine operands, in the style of Django's `{% if %}`.
+///
+/// Extracted from loops over the split result that test each element against
+/// a literal set, like `if bit in ("and", "or", "not")`. Infix operators must
+/// stand between two operands; prefix operators must be followed by one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordOperators {
+    /// Split position of the first bit of the operator expression.
+    pub start: usize,
+    pub infix: Vec<String>,
+    pub prefix: Vec<String>,
+}
+
 /// Block structure extracted from `parser.parse((...))` control flow patterns.
 ///
 /// Describes the end-tag and intermediate tags for a block tag, inferred
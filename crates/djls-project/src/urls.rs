@@ -0,0 +1,514 @@
+//! Named URL patterns reachable from `ROOT_URLCONF`, read statically.
+//!
+//! Starting from the URLconf module the settings name, this follows `urlpatterns` through
+//! `include()` the way Django's resolver nests them, qualifying each pattern name with the
+//! namespaces above it. Views are located by following the URLconf's imports to the `def` or
+//! `class` they name; patterns built at runtime are skipped rather than guessed.
+
+use std::ops::ControlFlow;
+
+use djls_source::File;
+use djls_source::Span;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Operator;
+use ruff_python_ast::Stmt;
+
+use crate::ast::ExprExt;
+use crate::ast::RangedExt;
+use crate::ast::Recurse;
+use crate::ast::walk_stmts;
+use crate::db::Db as ProjectDb;
+use crate::project::Project;
+use crate::python::PythonModuleName;
+use crate::python::PythonSourceModule;
+use crate::python::RecoveredPythonModule;
+use crate::python::evaluation::PythonBindingState;
+use crate::python::evaluation::python_module_facts;
+use crate::python::import::DirectImportClause;
+use crate::python::import::FromImportSyntax;
+use crate::python::module::relative_import_source;
+use crate::settings::settings_module;
+
+/// Calls that declare one URL pattern: `path(route, view, kwargs=None, name=None)`.
+const PATTERN_CALLS: &[&str] = &["path", "re_path", "url"];
+
+/// Calls whose positional arguments are themselves URL patterns.
+const PATTERN_LIST_CALLS: &[&str] = &["i18n_patterns"];
+
+/// How many modules deep `include()` and imports are followed.
+const MAX_DEPTH: usize = 16;
+
+/// The `def` or `class` a URL pattern's view names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewDefinition {
+    file: File,
+    definition_span: Span,
+    name_span: Span,
+}
+
+impl ViewDefinition {
+    #[must_use]
+    pub fn file(self) -> File {
+        self.file
+    }
+
+    #[must_use]
+    pub fn definition_span(self) -> Span {
+        self.definition_span
+    }
+
+    #[must_use]
+    pub fn name_span(self) -> Span {
+        self.name_span
+    }
+}
+
+/// A named URL pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlRoute {
+    name: String,
+    file: File,
+    span: Span,
+    view: Option<ViewDefinition>,
+}
+
+impl UrlRoute {
+    /// The name `{% url %}` reverses, qualified with its namespaces, e.g. `blog:detail`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The URLconf module that declares the pattern.
+    #[must_use]
+    pub fn file(&self) -> File {
+        self.file
+    }
+
+    /// Span of the `path(...)` call in [`file`](Self::file).
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The view the pattern routes to, when its definition could be found.
+    #[must_use]
+    pub fn view(&self) -> Option<ViewDefinition> {
+        self.view
+    }
+}
+
+/// Named URL patterns in the order Django's resolver visits them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UrlRoutes {
+    routes: Vec<UrlRoute>,
+}
+
+impl UrlRoutes {
+    pub fn iter(&self) -> impl Iterator<Item = &UrlRoute> {
+        self.routes.iter()
+    }
+
+    /// Patterns registered under `name`, which may be namespaced.
+    ///
+    /// Django reverses the last of several patterns sharing a name, so that one comes first.
+    pub fn named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a UrlRoute> {
+        self.routes.iter().rev().filter(move |route| route.name == name)
+    }
+}
+
+/// Collect the named URL patterns of the project's `ROOT_URLCONF`.
+///
+/// Every literal value the settings may assign is followed. A project with no readable
+/// `ROOT_URLCONF` has no routes.
+#[salsa::tracked(returns(ref))]
+pub fn url_routes(db: &dyn ProjectDb, project: Project) -> UrlRoutes {
+    let mut collector = RouteCollector {
+        db,
+        project,
+        routes: Vec::new(),
+        visiting: Vec::new(),
+    };
+    for name in root_urlconfs(db, project) {
+        if let Some(module) = PythonSourceModule::resolve(db, project, name) {
+            collector.urlconf(&module, "");
+        }
+    }
+
+    UrlRoutes {
+        routes: collector.routes,
+    }
+}
+
+fn root_urlconfs(db: &dyn ProjectDb, project: Project) -> Vec<PythonModuleName> {
+    let Some(module) = settings_module(db, project) else {
+        return Vec::new();
+    };
+    let Ok(facts) = python_module_facts(db, project, module) else {
+        return Vec::new();
+    };
+    let Some(binding) = facts.bindings.get("ROOT_URLCONF") else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for alternative in binding.alternatives() {
+        if let PythonBindingState::Bound(bound) = alternative
+            && let Some(scalar) = bound.value.known_scalar()
+            && let Some(value) = scalar.string_value()
+            && let Ok(name) = PythonModuleName::parse(value)
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// What a dotted name in a module refers to.
+enum Resolved {
+    Module(PythonSourceModule),
+    Definition(ViewDefinition),
+}
+
+struct RouteCollector<'db> {
+    db: &'db dyn ProjectDb,
+    project: Project,
+    routes: Vec<UrlRoute>,
+    /// URLconf modules being walked, to stop at include cycles.
+    visiting: Vec<PythonModuleName>,
+}
+
+impl<'db> RouteCollector<'db> {
+    fn body(&self, module: &PythonSourceModule) -> Option<&'db [Stmt]> {
+        let parsed = RecoveredPythonModule::from_file(self.db, module.file()).ok()??;
+        Some(parsed.body(self.db))
+    }
+
+    /// Walk the `urlpatterns` of `module`, prefixing names with `namespace`.
+    fn urlconf(&mut self, module: &PythonSourceModule, namespace: &str) {
+        if self.visiting.len() >= MAX_DEPTH || self.visiting.contains(module.name()) {
+            return;
+        }
+        let Some(body) = self.body(module) else {
+            return;
+        };
+
+        self.visiting.push(module.name().clone());
+        walk_stmts(body, Recurse::ControlFlow, |stmt| {
+            if let Stmt::Assign(assign) = stmt
+                && assign
+                    .targets
+                    .iter()
+                    .any(|target| target.name_target() == Some("urlpatterns"))
+            {
+                self.patterns(module, body, &assign.value, namespace, 0);
+            } else if let Stmt::AugAssign(assign) = stmt
+                && assign.op == Operator::Add
+                && assign.target.name_target() == Some("urlpatterns")
+            {
+                self.patterns(module, body, &assign.value, namespace, 0);
+            } else if let Stmt::Expr(statement) = stmt
+                && let Expr::Call(call) = statement.value.as_ref()
+                && let Expr::Attribute(attribute) = call.func.as_ref()
+                && attribute.value.name_target() == Some("urlpatterns")
+                && let Some(argument) = call.arguments.args.first()
+            {
+                match attribute.attr.as_str() {
+                    "append" => self.pattern(module, body, argument, namespace, 0),
+                    "extend" => self.patterns(module, body, argument, namespace, 0),
+                    _ => {}
+                }
+            }
+            ControlFlow::Continue(())
+        });
+        self.visiting.pop();
+    }
+
+    /// Walk an expression that evaluates to a list of patterns.
+    fn patterns(
+        &mut self,
+        module: &PythonSourceModule,
+        body: &'db [Stmt],
+        expr: &Expr,
+        namespace: &str,
+        depth: usize,
+    ) {
+        if depth >= MAX_DEPTH {
+            return;
+        }
+        let elements: &[Expr] = if let Expr::List(list) = expr {
+            &list.elts
+        } else if let Expr::Tuple(tuple) = expr {
+            &tuple.elts
+        } else if let Expr::Call(call) = expr
+            && callee_is(call, PATTERN_LIST_CALLS)
+        {
+            &call.arguments.args[..]
+        } else {
+            if let Expr::BinOp(binop) = expr
+                && binop.op == Operator::Add
+            {
+                self.patterns(module, body, &binop.left, namespace, depth + 1);
+                self.patterns(module, body, &binop.right, namespace, depth + 1);
+            } else if let Some(name) = expr.name_target()
+                && let Some(value) = module_assignment(body, name)
+            {
+                self.patterns(module, body, value, namespace, depth + 1);
+            }
+            return;
+        };
+        for element in elements {
+            self.pattern(module, body, element, namespace, depth);
+        }
+    }
+
+    /// Record one `path()` call, or follow it when its view is an `include()`.
+    fn pattern(
+        &mut self,
+        module: &PythonSourceModule,
+        body: &'db [Stmt],
+        expr: &Expr,
+        namespace: &str,
+        depth: usize,
+    ) {
+        let Expr::Call(call) = expr else {
+            return;
+        };
+        if !callee_is(call, PATTERN_CALLS) {
+            return;
+        }
+        let Some(view) = call
+            .arguments
+            .args
+            .get(1)
+            .or_else(|| call.arguments.find_keyword("view").map(|keyword| &keyword.value))
+        else {
+            return;
+        };
+
+        if let Expr::Call(include) = view
+            && callee_is(include, &["include"])
+        {
+            self.include(module, body, include, namespace, depth);
+            return;
+        }
+
+        let Some(name) = call
+            .arguments
+            .find_keyword("name")
+            .and_then(|keyword| keyword.value.string_literal())
+        else {
+            return;
+        };
+        let view = match self.view(module, view) {
+            Some(Resolved::Definition(definition)) => Some(definition),
+            Some(Resolved::Module(_)) | None => None,
+        };
+        self.routes.push(UrlRoute {
+            name: format!("{namespace}{name}"),
+            file: module.file(),
+            span: call.span(),
+            view,
+        });
+    }
+
+    /// Follow `include("app.urls")`, `include(("app.urls", "app"))`, `include(module)`, or
+    /// `include([...])`, with an optional `namespace=`.
+    fn include(
+        &mut self,
+        module: &PythonSourceModule,
+        body: &'db [Stmt],
+        call: &ExprCall,
+        namespace: &str,
+        depth: usize,
+    ) {
+        let Some(argument) = call.arguments.args.first() else {
+            return;
+        };
+        let explicit_namespace = call
+            .arguments
+            .find_keyword("namespace")
+            .and_then(|keyword| keyword.value.string_literal());
+        let (target, tuple_app_name) = if let Expr::Tuple(tuple) = argument
+            && let [target, app_name] = &tuple.elts[..]
+        {
+            (target, app_name.string_literal())
+        } else {
+            (argument, None)
+        };
+
+        if let Some(urlconf) = self.included_module(module, target) {
+            let declared_app_name = self.body(&urlconf).and_then(|body| {
+                module_assignment(body, "app_name").and_then(ExprExt::string_literal)
+            });
+            let nested = explicit_namespace.or(tuple_app_name).or(declared_app_name);
+            self.urlconf(&urlconf, &qualify(namespace, nested));
+        } else {
+            // A list of patterns, inline or bound to a name in this module.
+            let nested = explicit_namespace.or(tuple_app_name);
+            self.patterns(module, body, target, &qualify(namespace, nested), depth + 1);
+        }
+    }
+
+    /// The URLconf module an `include()` target names, as a dotted string or an imported module.
+    fn included_module(
+        &self,
+        module: &PythonSourceModule,
+        target: &Expr,
+    ) -> Option<PythonSourceModule> {
+        if let Some(name) = target.string_literal() {
+            let name = PythonModuleName::parse(name).ok()?;
+            PythonSourceModule::resolve(self.db, self.project, name)
+        } else {
+            match self.resolve(module, &target.path_segments()?, 0)? {
+                Resolved::Module(urlconf) => Some(urlconf),
+                Resolved::Definition(_) => None,
+            }
+        }
+    }
+
+    /// Find the definition a pattern's view expression names.
+    fn view(&self, module: &PythonSourceModule, view: &Expr) -> Option<Resolved> {
+        let mut view = view;
+        // `DetailView.as_view(...)` routes to the class; `login_required(view)` and similar
+        // decorators applied inline route to the function they wrap.
+        while let Expr::Call(call) = view {
+            view = if let Expr::Attribute(attribute) = call.func.as_ref()
+                && attribute.attr.as_str() == "as_view"
+            {
+                &attribute.value
+            } else {
+                call.arguments.args.first()?
+            };
+        }
+        self.resolve(module, &view.path_segments()?, 0)
+    }
+
+    /// Resolve a dotted name as written in `module`.
+    fn resolve(
+        &self,
+        module: &PythonSourceModule,
+        segments: &[String],
+        depth: usize,
+    ) -> Option<Resolved> {
+        let (first, rest) = segments.split_first()?;
+        let mut resolved = self.binding(module, first, depth)?;
+        for segment in rest {
+            resolved = match resolved {
+                Resolved::Module(module) => self.member(&module, segment, depth + 1)?,
+                // An attribute of a class, such as a method, routes through the class.
+                Resolved::Definition(definition) => return Some(Resolved::Definition(definition)),
+            };
+        }
+        Some(resolved)
+    }
+
+    /// Resolve `module.name`, as a binding in the module or as a submodule.
+    fn member(&self, module: &PythonSourceModule, name: &str, depth: usize) -> Option<Resolved> {
+        self.binding(module, name, depth).or_else(|| {
+            let submodule = module.name().exact_child(name)?;
+            PythonSourceModule::resolve(self.db, self.project, submodule).map(Resolved::Module)
+        })
+    }
+
+    /// Resolve the last top-level binding of `name` in `module`.
+    fn binding(&self, module: &PythonSourceModule, name: &str, depth: usize) -> Option<Resolved> {
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+        let body = self.body(module)?;
+
+        let mut resolved = None;
+        walk_stmts(body, Recurse::ControlFlow, |stmt| {
+            if let Stmt::FunctionDef(function_def) = stmt
+                && function_def.name.as_str() == name
+            {
+                resolved = Some(Resolved::Definition(ViewDefinition {
+                    file: module.file(),
+                    definition_span: function_def.span(),
+                    name_span: function_def.name.span(),
+                }));
+            } else if let Stmt::ClassDef(class_def) = stmt
+                && class_def.name.as_str() == name
+            {
+                resolved = Some(Resolved::Definition(ViewDefinition {
+                    file: module.file(),
+                    definition_span: class_def.span(),
+                    name_span: class_def.name.span(),
+                }));
+            } else if let Stmt::Import(import) = stmt {
+                for clause in DirectImportClause::lower(import) {
+                    if clause.bound() == name {
+                        resolved = PythonModuleName::parse(clause.target())
+                            .ok()
+                            .and_then(|target| {
+                                PythonSourceModule::resolve(self.db, self.project, target)
+                            })
+                            .map(Resolved::Module);
+                    }
+                }
+            } else if let Stmt::ImportFrom(import) = stmt {
+                let syntax = FromImportSyntax::lower(import);
+                for member in syntax.named_members() {
+                    if member.bound() == name {
+                        resolved = self.imported(module, &syntax, member.imported(), depth);
+                    }
+                }
+            }
+            ControlFlow::Continue(())
+        });
+        resolved
+    }
+
+    /// Resolve `imported` from a `from ... import` statement in `module`.
+    fn imported(
+        &self,
+        module: &PythonSourceModule,
+        syntax: &FromImportSyntax<'_>,
+        imported: &str,
+        depth: usize,
+    ) -> Option<Resolved> {
+        let source = relative_import_source(module.package(), syntax.level(), syntax.module())?;
+        let source = PythonModuleName::parse(&source).ok()?;
+        // A package's submodule may be imported without the package binding it, and a package
+        // need not have an `__init__.py` of its own.
+        if let Some(submodule) = source.exact_child(imported)
+            && let Some(submodule) = PythonSourceModule::resolve(self.db, self.project, submodule)
+        {
+            return Some(Resolved::Module(submodule));
+        }
+        let source = PythonSourceModule::resolve(self.db, self.project, source)?;
+        self.binding(&source, imported, depth + 1)
+    }
+}
+
+fn callee_is(call: &ExprCall, names: &[&str]) -> bool {
+    call.func
+        .path_segments()
+        .and_then(|segments| segments.last().cloned())
+        .is_some_and(|callee| names.contains(&callee.as_str()))
+}
+
+/// The value last assigned to `name` at the top level of `body`.
+fn module_assignment<'a>(body: &'a [Stmt], name: &str) -> Option<&'a Expr> {
+    body.iter().rev().find_map(|stmt| {
+        let Stmt::Assign(assign) = stmt else {
+            return None;
+        };
+        assign
+            .targets
+            .iter()
+            .any(|target| target.name_target() == Some(name))
+            .then_some(assign.value.as_ref())
+    })
+}
+
+fn qualify(namespace: &str, nested: Option<&str>) -> String {
+    match nested {
+        Some(nested) => format!("{namespace}{nested}:"),
+        None => namespace.to_string(),
+    }
+}
+
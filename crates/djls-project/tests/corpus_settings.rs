@@ -207,6 +207,8 @@ fn settings_extraction_snapshots() -> Result<(), Box<dyn std::error::Error>> {
                 pythonpath,
                 Vec::new(),
                 djls_conf::Settings::default().tagspecs().clone(),
+                djls_conf::Settings::default().dialects().clone(),
+                djls_conf::Settings::default().path_mappings().clone(),
             );
             db.set_project(project);
 
@@ -2,6 +2,8 @@ use std::io;
 use std::ptr;
 
 use camino::Utf8Path;
+use djls_conf::DialectsConfig;
+use djls_conf::PathMappings;
 use djls_conf::TagSpecDef;
 use djls_project::Interpreter;
 use djls_project::ModelGraph;
@@ -1255,6 +1257,8 @@ fn salsa_recomputes_relation_resolution_for_import_edits_only_where_needed() {
         Vec::new(),
         Vec::new(),
         TagSpecDef::default(),
+        DialectsConfig::default(),
+        PathMappings::default(),
     );
     db.set_project(project);
 
@@ -1342,6 +1342,8 @@ fn ty_symlink() {
         Vec::new(),
         Vec::new(),
         Settings::default().tagspecs().clone(),
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -2351,6 +2353,8 @@ fn ty_case_sensitive_resolution_with_symlinked_directory() {
         Vec::new(),
         Vec::new(),
         Settings::default().tagspecs().clone(),
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -2554,6 +2558,25 @@ fn python_module_resolve_records_selected_search_path() {
     );
 }
 
+#[test]
+fn django_version_reads_version_tuple_of_resolved_django_package() {
+    let db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .file(
+            "/project/django/__init__.py",
+            "from django.utils.version import get_version\n\nVERSION = (4, 2, 17, \"final\", 0)\n",
+        )
+        .build(&db)
+        .expect("Django-version project fixture should build");
+
+    assert_eq!(django_version(&db, project), Some(DjangoVersion::new(4, 2)));
+
+    let without_django = ProjectFixture::new("/other")
+        .build(&db)
+        .expect("Django-less project fixture should build");
+    assert_eq!(django_version(&db, without_django), None);
+}
+
 #[test]
 fn resolve_package_dirs_returns_regular_package_dir() {
     let db = TestDatabase::new();
@@ -10,6 +10,7 @@ use std::sync::atomic::Ordering;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::PathMappings;
 use djls_conf::Settings;
 use djls_project::Db as ProjectDb;
 use djls_project::testing::PythonSyntaxErrorClass;
@@ -1159,6 +1160,8 @@ fn readable_unreadable_rescans_recompute_ancestors_once_and_retain_dependency()
         pythonpath,
         Vec::new(),
         Settings::default().tagspecs().clone(),
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.project = Some(project);
 
@@ -1326,6 +1329,8 @@ fn project_with_file_system_failure(
         pythonpath,
         Vec::new(),
         djls_conf::Settings::default().tagspecs().clone(),
+        djls_conf::Settings::default().dialects().clone(),
+        djls_conf::Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -1370,6 +1375,8 @@ fn project_requiring_environment_application(
         vec![Utf8PathBuf::from("/vendor")],
         Vec::new(),
         Settings::default().tagspecs().clone(),
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
     Ok(project)
@@ -1747,6 +1754,8 @@ fn unreadable_root_settings_are_dynamic_never_unset() {
         pythonpath,
         Vec::new(),
         Settings::default().tagspecs().clone(),
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -1803,6 +1812,8 @@ fn django_discovery_includes_deduped_unreadable_settings_source() {
         pythonpath,
         Vec::new(),
         tag_specs,
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -1836,6 +1847,37 @@ fn template_dirs_resolve_settings_module_file() {
     assert_eq!(dirs, vec![Utf8PathBuf::from("/proj/templates")]);
 }
 
+#[test]
+fn template_dirs_map_container_paths_into_the_workspace() {
+    let mut db = TestDatabase::new();
+    let path_mappings: PathMappings = [
+        (Utf8PathBuf::from("/app"), Utf8PathBuf::from(".")),
+        (Utf8PathBuf::from("/app/vendor"), Utf8PathBuf::from("../vendor")),
+    ]
+    .into_iter()
+    .collect();
+    let project = ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .path_mappings(path_mappings)
+        .file(
+            "/proj/myproject/settings.py",
+            "TEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/app/templates', '/app/vendor/templates', '/srv/templates'], 'APP_DIRS': False}]\n",
+        )
+        .install(&mut db)
+        .expect("path mapping project fixture should build");
+
+    let dirs = complete_template_dirs(&db, project);
+
+    assert_eq!(
+        dirs,
+        vec![
+            Utf8PathBuf::from("/proj/templates"),
+            Utf8PathBuf::from("/vendor/templates"),
+            Utf8PathBuf::from("/srv/templates"),
+        ]
+    );
+}
+
 #[test]
 fn template_dirs_follow_supported_nested_insert_and_remove_mutations() {
     let mut db = TestDatabase::new();
@@ -3214,6 +3256,8 @@ fn failed_available_candidate_walk_makes_missing_library_inconclusive() {
         pythonpath,
         Vec::new(),
         tag_specs,
+        Settings::default().dialects().clone(),
+        Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -4010,6 +4054,8 @@ fn django_facts_golden_fixture() -> Result<DjangoFactsGoldenFixture, Box<dyn std
         pythonpath,
         Vec::new(),
         djls_conf::Settings::default().tagspecs().clone(),
+        djls_conf::Settings::default().dialects().clone(),
+        djls_conf::Settings::default().path_mappings().clone(),
     );
     db.set_project(project);
 
@@ -4087,3 +4133,32 @@ fn comparable_symbols(libraries: &TemplateLibraryCatalog) -> Vec<GoldenTemplateS
 
     symbols
 }
+
+#[test]
+fn declared_settings_record_uppercase_names_and_types() {
+    let mut db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("settings")
+        .file(
+            "/project/settings.py",
+            "import os\nDEBUG = True\nif os.environ.get('PROD'):\n    DEBUG = 'no'\nSITE_NAME = 'Shop'\nINSTALLED_APPS = []\n_PRIVATE = 1\nlower = 'x'\n",
+        )
+        .install(&mut db)
+        .expect("settings fixture should install");
+
+    let settings = declared_settings(&db, project);
+    let names = settings
+        .iter()
+        .map(|setting| (setting.name(), setting.types()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("DEBUG", &["bool", "str"][..]),
+            ("INSTALLED_APPS", &["list"][..]),
+            ("SITE_NAME", &["str"][..]),
+        ]
+    );
+    assert!(settings.get("SITE_NAME").is_some());
+    assert!(settings.get("lower").is_none());
+}
@@ -6,6 +6,8 @@ use std::sync::Arc;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::DialectsConfig;
+use djls_conf::PathMappings;
 use djls_conf::Settings;
 use djls_conf::TagSpecDef;
 use djls_project::Db;
@@ -157,6 +159,8 @@ fn python_project_with_paths(db: &dyn Db, pythonpath: &[Utf8PathBuf]) -> Project
         Vec::new(),
         Vec::new(),
         TagSpecDef::default(),
+        DialectsConfig::default(),
+        PathMappings::default(),
     )
 }
 
@@ -8635,6 +8639,40 @@ fn explicit_app_dirs_retains_origins_and_absence_stays_distinct() {
     );
 }
 
+#[test]
+fn explicit_loaders_flatten_cached_wrappers_and_keep_custom_paths() {
+    let settings = extract(
+        "TEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'OPTIONS': {'loaders': [('django.template.loaders.cached.Loader', ['django.template.loaders.filesystem.Loader', 'django.template.loaders.app_directories.Loader']), ('django.template.loaders.filesystem.Loader', ['/elsewhere']), 'myproject.loaders.Loader']}}]",
+    ).expect("Django settings extraction should succeed");
+    let loaders = &cases(&settings, "/templates/cases")
+        .expect("settings JSON pointer should identify an array")[0]["known"]["backends"][0]["loaders"];
+    let values = loaders
+        .as_array()
+        .expect("expected JSON field should be an array")
+        .iter()
+        .map(|loader| loader["value"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            json!("filesystem"),
+            json!("app_directories"),
+            json!({"other": "django.template.loaders.filesystem.Loader"}),
+            json!({"other": "myproject.loaders.Loader"}),
+        ]
+    );
+
+    let absent =
+        extract("TEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates'}]")
+            .expect("Django settings extraction should succeed");
+    assert!(
+        cases(&absent, "/templates/cases").expect("settings JSON pointer should identify an array")
+            [0]["known"]["backends"][0]
+            .get("loaders")
+            .is_none()
+    );
+}
+
 #[test]
 fn exact_wrong_template_member_shapes_are_malformed() {
     for member in [
@@ -8645,6 +8683,8 @@ fn exact_wrong_template_member_shapes_are_malformed() {
         "'OPTIONS': {'libraries': []}",
         "'OPTIONS': {'builtins': 'module'}",
         "'OPTIONS': {'context_processors': 'processor'}",
+        "'OPTIONS': {'loaders': 'loader'}",
+        "'OPTIONS': {'loaders': ['django.template.loaders.cached.Loader']}",
     ] {
         let source = format!(
             "TEMPLATES = [{{'BACKEND': 'django.template.backends.django.DjangoTemplates', {member}}}]"
@@ -395,6 +395,50 @@ fn source_less_configured_library_keeps_keyed_structural_facts_without_origin()
     assert_eq!(library.id().file(&db), None);
 }
 
+#[test]
+fn installed_app_missing_from_search_paths_uses_bundled_libraries() {
+    let db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("project.settings")
+        .file(
+            "/project/project/settings.py",
+            "INSTALLED_APPS = ['crispy_forms']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates'}]\n",
+        )
+        .build(&db)
+        .expect("bundled-library project fixture should build");
+
+    let libraries = template_library_catalog(&db, project);
+    let scoped_libraries = project_inventory(libraries);
+    let tags = scoped_libraries
+        .loadable_library_str("crispy_forms_tags")
+        .found()
+        .expect("bundled crispy_forms_tags should be definitively loadable");
+    assert_eq!(
+        tags.module_name_str(),
+        "crispy_forms.templatetags.crispy_forms_tags"
+    );
+    assert!(tags.source_file().is_none());
+    assert!(!tags.symbols_are_unobserved());
+    assert!(tags.symbol(TemplateSymbolKind::Tag, "crispy").is_some());
+
+    let filters = scoped_libraries
+        .loadable_library_str("crispy_forms_filters")
+        .found()
+        .expect("bundled crispy_forms_filters should be definitively loadable");
+    assert!(
+        filters
+            .symbol(TemplateSymbolKind::Filter, "as_crispy_field")
+            .is_some()
+    );
+    assert!(
+        scoped_libraries
+            .loadable_library_str("wagtailcore_tags")
+            .found()
+            .is_none(),
+        "bundled libraries of apps that are not installed stay unavailable"
+    );
+}
+
 #[test]
 fn source_less_alias_keeps_missing_same_named_available_in_app_symbols_inconclusive() {
     let db = TestDatabase::new();
@@ -657,6 +701,44 @@ fn known_symbol_candidates_preserve_builtin_and_load_semantics() {
     ));
 }
 
+#[test]
+fn prefix_index_returns_only_matching_symbol_names_in_order() {
+    let libraries = libraries(
+        false,
+        vec![
+            builtin(
+                "django.template.defaultfilters",
+                vec![
+                    symbol(TemplateSymbolKind::Filter, "date", None),
+                    symbol(TemplateSymbolKind::Filter, "default", None),
+                    symbol(TemplateSymbolKind::Filter, "default_if_none", None),
+                    symbol(TemplateSymbolKind::Filter, "dictsort", None),
+                    symbol(TemplateSymbolKind::Tag, "debug", None),
+                ],
+            )
+            .expect("builtin library fixture should be valid"),
+        ],
+    );
+    let scoped_libraries = project_inventory(&libraries);
+    let names = |prefix: &str| {
+        scoped_libraries
+            .symbols_with_prefix(TemplateSymbolKind::Filter, prefix)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names("def"), vec!["default", "default_if_none"]);
+    assert_eq!(names("d").len(), 4);
+    assert_eq!(names("default_if_none"), vec!["default_if_none"]);
+    assert!(names("deb").is_empty());
+    assert!(names("z").is_empty());
+    assert_eq!(
+        names(""),
+        scoped_libraries
+            .inventory_symbol_names(TemplateSymbolKind::Filter)
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn resolved_library_inventory_deduplicates_identical_builtin_identity() {
     let db = TestDatabase::new();
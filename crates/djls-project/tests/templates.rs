@@ -822,6 +822,90 @@ fn wholly_dynamic_dirs_weakens_app_dirs_candidates() {
     );
 }
 
+#[test]
+fn explicit_loaders_set_the_search_order() {
+    let db = TestDatabase::new();
+    let project = ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = ['blog']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'OPTIONS': {'loaders': ['django.template.loaders.app_directories.Loader', 'django.template.loaders.filesystem.Loader']}}]\n",
+        )
+        .file("/test/project/templates/base.html", "project base")
+        .file("/test/project/blog/__init__.py", "")
+        .file("/test/project/blog/templates/base.html", "app base")
+        .build(&db)
+        .expect("explicit-loaders project fixture should build");
+
+    let name = TemplateName::new(&db, "base.html".to_string());
+    let origin = match template_resolution(&db, project).resolve(&db, name) {
+        TemplateResolutionResult::Found(origin) => Some(origin),
+        TemplateResolutionResult::DoesNotExist(_) | TemplateResolutionResult::Inconclusive(_) => {
+            None
+        }
+    }
+    .expect("base.html should resolve through the app directories loader");
+
+    assert_eq!(
+        origin.path_buf(&db),
+        Utf8Path::new("/test/project/blog/templates/base.html")
+    );
+}
+
+#[test]
+fn cached_loader_searches_only_the_loaders_it_wraps() {
+    let db = TestDatabase::new();
+    let project = ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = ['blog']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'OPTIONS': {'loaders': [('django.template.loaders.cached.Loader', ['django.template.loaders.filesystem.Loader'])]}}]\n",
+        )
+        .file("/test/project/blog/__init__.py", "")
+        .file("/test/project/blog/templates/base.html", "app base")
+        .build(&db)
+        .expect("cached-loader project fixture should build");
+
+    let name = TemplateName::new(&db, "base.html".to_string());
+    let error = match template_resolution(&db, project).resolve(&db, name) {
+        TemplateResolutionResult::DoesNotExist(error) => Some(error),
+        TemplateResolutionResult::Found(_) | TemplateResolutionResult::Inconclusive(_) => None,
+    }
+    .expect("app templates should be invisible without an app directories loader");
+
+    assert_eq!(
+        error.tried,
+        [Utf8Path::new("/test/project/templates/base.html")]
+    );
+}
+
+#[test]
+fn custom_loaders_make_resolution_inconclusive() {
+    let db = TestDatabase::new();
+    let project = ProjectFixture::new("/test/project")
+        .django_settings_module("testproject.settings")
+        .file(
+            "/test/project/testproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/test/project/templates'], 'OPTIONS': {'loaders': ['myproject.loaders.DatabaseLoader', 'django.template.loaders.filesystem.Loader']}}]\n",
+        )
+        .file("/test/project/templates/base.html", "project base")
+        .build(&db)
+        .expect("custom-loader project fixture should build");
+
+    let name = TemplateName::new(&db, "base.html".to_string());
+    let search = match template_resolution(&db, project).resolve(&db, name) {
+        TemplateResolutionResult::Inconclusive(search) => Some(search),
+        TemplateResolutionResult::Found(_) | TemplateResolutionResult::DoesNotExist(_) => None,
+    }
+    .expect("a custom loader ahead of the filesystem loader should weaken its candidate");
+
+    assert_eq!(search.possible_origins.len(), 1);
+    assert_eq!(
+        search.possible_origins[0].path_buf(&db),
+        Utf8Path::new("/test/project/templates/base.html")
+    );
+}
+
 #[test]
 fn scalar_path_alternatives_follow_unanimous_and_divergent_resolution_policy() {
     let db = TestDatabase::new();
@@ -1468,3 +1552,37 @@ fn resolve_excluding_skips_excluded_origins() {
         "/test/project/app/templates/base.html"
     );
 }
+
+#[test]
+fn variables_come_from_django_and_project_processors() {
+    let mut db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("settings")
+        .file(
+            "/project/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': [], 'OPTIONS': {'context_processors': ['django.template.context_processors.request', 'shop.context.cart']}}]\n",
+        )
+        .file("/project/shop/__init__.py", "")
+        .file(
+            "/project/shop/context.py",
+            "def cart(request):\n    return {'cart': request.session.get('cart')}\n",
+        )
+        .install(&mut db)
+        .expect("context processor fixture should install");
+
+    let variables = context_processor_variables(&db, project);
+    let names = variables
+        .iter()
+        .map(|variable| (variable.name(), variable.processor()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("cart", "shop.context.cart"),
+            ("csrf_token", "django.template.context_processors.csrf"),
+            ("request", "django.template.context_processors.request"),
+        ]
+    );
+    assert!(variables.contains("cart"));
+    assert!(!variables.contains("user"));
+}
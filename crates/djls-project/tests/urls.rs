@@ -0,0 +1,108 @@
+use djls_project::Project;
+use djls_project::url_routes;
+use djls_testing::ProjectFixture;
+use djls_testing::TestDatabase;
+
+type TestResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+fn routes(db: &TestDatabase, project: Project) -> TestResult<Vec<(String, Option<String>)>> {
+    url_routes(db, project)
+        .iter()
+        .map(|route| {
+            let view = match route.view() {
+                Some(view) => {
+                    let source = view.file().try_source(db)?;
+                    let name = view.name_span();
+                    Some(format!(
+                        "{}:{}",
+                        view.file().path(db),
+                        &source.as_str()[name.start_usize()..name.end_usize()]
+                    ))
+                }
+                None => None,
+            };
+            Ok((route.name().to_string(), view))
+        })
+        .collect()
+}
+
+#[test]
+fn routes_follow_includes_and_imports_to_views() {
+    let mut db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("settings")
+        .file("/project/settings.py", "ROOT_URLCONF = 'site_urls'\n")
+        .file(
+            "/project/site_urls.py",
+            "from django.urls import include, path\nfrom blog import views as blog_views\n\nurlpatterns = [\n    path('', blog_views.home, name='home'),\n    path('blog/', include('blog.urls')),\n    path('shop/', include(('shop.urls', 'shop'), namespace='store')),\n]\n",
+        )
+        .file("/project/blog/__init__.py", "")
+        .file(
+            "/project/blog/urls.py",
+            "from django.urls import path\nfrom . import views\nfrom .views import PostDetail\n\napp_name = 'blog'\nurlpatterns = [\n    path('<int:pk>/', PostDetail.as_view(), name='detail'),\n    path('archive/', views.archive, name='archive'),\n]\n",
+        )
+        .file(
+            "/project/blog/views.py",
+            "def home(request):\n    pass\n\ndef archive(request):\n    pass\n\nclass PostDetail(DetailView):\n    pass\n",
+        )
+        .file("/project/shop/__init__.py", "")
+        .file(
+            "/project/shop/urls.py",
+            "from django.urls import path\n\ndef cart(request):\n    pass\n\nurlpatterns = [path('cart/', cart, name='cart')]\n",
+        )
+        .install(&mut db)
+        .expect("url fixture should install");
+
+    assert_eq!(
+        routes(&db, project).expect("URL routes should resolve"),
+        [
+            ("home".to_string(), Some("/project/blog/views.py:home".to_string())),
+            ("blog:detail".to_string(), Some("/project/blog/views.py:PostDetail".to_string())),
+            ("blog:archive".to_string(), Some("/project/blog/views.py:archive".to_string())),
+            ("store:cart".to_string(), Some("/project/shop/urls.py:cart".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn unresolvable_views_keep_the_pattern() {
+    let mut db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("settings")
+        .file("/project/settings.py", "ROOT_URLCONF = 'urls'\n")
+        .file(
+            "/project/urls.py",
+            "from django.urls import path\nfrom missing import view\n\nurlpatterns = [\n    path('a/', view, name='a'),\n    path('b/', make_view(), name='b'),\n    path('c/', view),\n]\nurlpatterns += [path('d/', view, name='d')]\n",
+        )
+        .install(&mut db)
+        .expect("url fixture should install");
+
+    assert_eq!(
+        routes(&db, project).expect("URL routes should resolve"),
+        [
+            ("a".to_string(), None),
+            ("b".to_string(), None),
+            ("d".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn include_cycles_terminate() {
+    let mut db = TestDatabase::new();
+    let project = ProjectFixture::new("/project")
+        .django_settings_module("settings")
+        .file("/project/settings.py", "ROOT_URLCONF = 'urls'\n")
+        .file(
+            "/project/urls.py",
+            "from django.urls import include, path\n\nurlpatterns = [\n    path('again/', include('urls'), name='ignored'),\n    path('x/', include('urls')),\n]\n",
+        )
+        .install(&mut db)
+        .expect("url fixture should install");
+
+    assert!(
+        routes(&db, project)
+            .expect("URL routes should resolve")
+            .is_empty()
+    );
+}
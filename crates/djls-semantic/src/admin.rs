@@ -0,0 +1,329 @@
+//! Knowledge of the templates `django.contrib.admin` lets projects override.
+//!
+//! The admin looks up most of its templates per model and per app first, so
+//! `admin/polls/question/change_form.html` replaces `admin/change_form.html`
+//! for one model only. The block names of Django's own templates are bundled
+//! here so an override can be checked without Django's source on disk.
+
+use djls_project::template_resolution;
+use djls_source::File;
+
+use crate::db::Db;
+
+/// A contrib admin template, its parent, and the blocks it defines itself.
+struct AdminTemplate {
+    name: &'static str,
+    parent: Option<&'static str>,
+    blocks: &'static [&'static str],
+}
+
+/// Django's admin templates that define blocks, as of Django 5.x.
+const ADMIN_TEMPLATES: &[AdminTemplate] = &[
+    AdminTemplate {
+        name: "admin/base.html",
+        parent: None,
+        blocks: &[
+            "blockbots",
+            "bodyclass",
+            "branding",
+            "breadcrumbs",
+            "coltype",
+            "content",
+            "content_subtitle",
+            "content_title",
+            "dark-mode-vars",
+            "extrahead",
+            "extrastyle",
+            "footer",
+            "header",
+            "messages",
+            "nav-breadcrumbs",
+            "nav-global",
+            "nav-sidebar",
+            "object-tools",
+            "pretitle",
+            "responsive",
+            "sidebar",
+            "stylesheet",
+            "title",
+            "userlinks",
+            "usertools",
+            "welcome-msg",
+        ],
+    },
+    AdminTemplate {
+        name: "admin/base_site.html",
+        parent: Some("admin/base.html"),
+        blocks: &["branding", "nav-global", "title"],
+    },
+    AdminTemplate {
+        name: "admin/index.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &[
+            "bodyclass",
+            "coltype",
+            "content",
+            "extrastyle",
+            "nav-breadcrumbs",
+            "nav-sidebar",
+            "sidebar",
+        ],
+    },
+    AdminTemplate {
+        name: "admin/app_index.html",
+        parent: Some("admin/index.html"),
+        blocks: &["bodyclass", "breadcrumbs", "sidebar"],
+    },
+    AdminTemplate {
+        name: "admin/change_form.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &[
+            "admin_change_form_document_ready",
+            "after_field_sets",
+            "after_related_objects",
+            "bodyclass",
+            "breadcrumbs",
+            "coltype",
+            "content",
+            "extrahead",
+            "extrastyle",
+            "field_sets",
+            "form_top",
+            "inline_field_sets",
+            "object-tools",
+            "object-tools-items",
+            "submit_buttons_bottom",
+            "submit_buttons_top",
+        ],
+    },
+    AdminTemplate {
+        name: "admin/change_list.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &[
+            "bodyclass",
+            "breadcrumbs",
+            "coltype",
+            "content",
+            "date_hierarchy",
+            "extrahead",
+            "extrastyle",
+            "filters",
+            "object-tools",
+            "object-tools-items",
+            "pagination",
+            "result_list",
+            "search",
+        ],
+    },
+    AdminTemplate {
+        name: "admin/delete_confirmation.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &["bodyclass", "breadcrumbs", "content", "extrahead"],
+    },
+    AdminTemplate {
+        name: "admin/delete_selected_confirmation.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &["bodyclass", "breadcrumbs", "content", "extrahead"],
+    },
+    AdminTemplate {
+        name: "admin/object_history.html",
+        parent: Some("admin/base_site.html"),
+        blocks: &["breadcrumbs", "content"],
+    },
+    AdminTemplate {
+        name: "admin/change_form_object_tools.html",
+        parent: None,
+        blocks: &["object-tools-items"],
+    },
+    AdminTemplate {
+        name: "admin/change_list_object_tools.html",
+        parent: None,
+        blocks: &["object-tools-items"],
+    },
+    AdminTemplate {
+        name: "admin/submit_line.html",
+        parent: None,
+        blocks: &["submit-row"],
+    },
+];
+
+/// Templates the admin looks up per app and per model before its own.
+const PER_MODEL_TEMPLATES: &[&str] = &[
+    "change_form.html",
+    "change_form_object_tools.html",
+    "change_list.html",
+    "change_list_object_tools.html",
+    "change_list_results.html",
+    "date_hierarchy.html",
+    "delete_confirmation.html",
+    "delete_selected_confirmation.html",
+    "object_history.html",
+    "pagination.html",
+    "popup_response.html",
+    "prepopulated_fields_js.html",
+    "search_form.html",
+    "submit_line.html",
+];
+
+/// Templates the admin looks up per app, but not per model.
+const PER_APP_TEMPLATES: &[&str] = &["app_index.html"];
+
+/// A project template that replaces one of `django.contrib.admin`'s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminOverride {
+    template: &'static str,
+    app: Option<String>,
+    model: Option<String>,
+}
+
+impl AdminOverride {
+    /// Recognize an override from a template name such as
+    /// `admin/polls/question/change_form.html`.
+    #[must_use]
+    pub fn from_template_name(name: &str) -> Option<Self> {
+        let rest = name.strip_prefix("admin/")?;
+        let segments = rest.split('/').collect::<Vec<_>>();
+        let (file_name, scope) = segments.split_last()?;
+        if scope.iter().any(|segment| segment.is_empty()) {
+            return None;
+        }
+        let allowed = match scope.len() {
+            0 => true,
+            1 => {
+                PER_APP_TEMPLATES.contains(file_name) || PER_MODEL_TEMPLATES.contains(file_name)
+            }
+            2 => PER_MODEL_TEMPLATES.contains(file_name),
+            _ => false,
+        };
+        if !allowed {
+            return None;
+        }
+
+        let template = admin_template_name(file_name)?;
+        Some(Self {
+            template,
+            app: scope.first().map(|app| (*app).to_string()),
+            model: scope.get(1).map(|model| (*model).to_string()),
+        })
+    }
+
+    /// The contrib template this replaces, e.g. `admin/change_form.html`.
+    #[must_use]
+    pub fn template(&self) -> &'static str {
+        self.template
+    }
+
+    /// The app label the override is scoped to, if any.
+    #[must_use]
+    pub fn app(&self) -> Option<&str> {
+        self.app.as_deref()
+    }
+
+    /// The model name the override is scoped to, if any.
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Every block name the contrib template and its parents define, sorted.
+    #[must_use]
+    pub fn block_names(&self) -> Vec<&'static str> {
+        admin_block_names(self.template)
+    }
+
+    /// Whether `name` is a block the contrib template or one of its parents
+    /// defines.
+    #[must_use]
+    pub fn defines_block(&self, name: &str) -> bool {
+        self.block_names().contains(&name)
+    }
+}
+
+fn admin_template_name(file_name: &str) -> Option<&'static str> {
+    ADMIN_TEMPLATES
+        .iter()
+        .map(|template| template.name)
+        .chain(ADMIN_LEAF_TEMPLATES.iter().copied())
+        .find(|name| name.strip_prefix("admin/") == Some(file_name))
+}
+
+/// Admin templates without blocks of their own that can still be overridden.
+const ADMIN_LEAF_TEMPLATES: &[&str] = &[
+    "admin/change_list_results.html",
+    "admin/date_hierarchy.html",
+    "admin/pagination.html",
+    "admin/popup_response.html",
+    "admin/prepopulated_fields_js.html",
+    "admin/search_form.html",
+];
+
+pub(crate) fn admin_block_names(template: &str) -> Vec<&'static str> {
+    let mut names = Vec::new();
+    let mut current = Some(template);
+    while let Some(name) = current {
+        let Some(template) = ADMIN_TEMPLATES.iter().find(|template| template.name == name) else {
+            break;
+        };
+        names.extend_from_slice(template.blocks);
+        current = template.parent;
+    }
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// The contrib admin template `file` overrides, if it is found under one of
+/// the project's template names that the admin looks up.
+///
+/// Django's own copies of the admin templates are not overrides.
+#[must_use]
+pub fn admin_override_for_file(db: &dyn Db, file: File) -> Option<AdminOverride> {
+    let path = file.path(db).as_str().replace('\\', "/");
+    if path.contains("django/contrib/admin/templates/") {
+        return None;
+    }
+    let project = db.project()?;
+    template_resolution(db, project)
+        .template_names_for_file(db, file)
+        .iter()
+        .find_map(|name| AdminOverride::from_template_name(name.name(db)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_site_app_and_model_overrides() {
+        let site = AdminOverride::from_template_name("admin/base_site.html").expect("override");
+        assert_eq!(site.template(), "admin/base_site.html");
+        assert_eq!(site.app(), None);
+
+        let model = AdminOverride::from_template_name("admin/polls/question/change_form.html")
+            .expect("override");
+        assert_eq!(model.template(), "admin/change_form.html");
+        assert_eq!(model.app(), Some("polls"));
+        assert_eq!(model.model(), Some("question"));
+        assert!(model.defines_block("field_sets"));
+        assert!(model.defines_block("extrahead"));
+        assert!(model.defines_block("branding"));
+        assert!(!model.defines_block("field_set"));
+
+        let app = AdminOverride::from_template_name("admin/polls/app_index.html").expect("app");
+        assert_eq!(app.template(), "admin/app_index.html");
+        assert!(app.defines_block("content"));
+
+        let pagination =
+            AdminOverride::from_template_name("admin/polls/pagination.html").expect("override");
+        assert!(pagination.block_names().is_empty());
+    }
+
+    #[test]
+    fn ignores_names_the_admin_does_not_look_up() {
+        assert_eq!(AdminOverride::from_template_name("admin/polls/question/base.html"), None);
+        assert_eq!(AdminOverride::from_template_name("admin/polls/question/app_index.html"), None);
+        assert_eq!(AdminOverride::from_template_name("admin/a/b/c/change_form.html"), None);
+        assert_eq!(AdminOverride::from_template_name("admin/custom.html"), None);
+        assert_eq!(AdminOverride::from_template_name("polls/change_form.html"), None);
+    }
+}
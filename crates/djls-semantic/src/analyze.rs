@@ -0,0 +1,215 @@
+//! Standalone Template analysis for embedders.
+//!
+//! Third-party tools (pre-commit hooks, test plugins, editors other than the
+//! language server) often hold a Template source string and a set of tag specs
+//! and want diagnostics back. [`analyze`] runs the same parse and validation
+//! pipeline as the server against a private, single-use database and returns
+//! owned results, so callers never deal with Salsa or tracked files.
+
+use std::sync::Arc;
+
+use camino::Utf8PathBuf;
+use djls_conf::DiagnosticsConfig;
+use djls_project::Db as ProjectDb;
+use djls_project::ModelGraph;
+use djls_project::Project;
+use djls_source::Db as SourceDb;
+use djls_source::File;
+use djls_source::FileSystem;
+use djls_source::InMemoryFileSystem;
+use djls_source::SourceFiles;
+use djls_source::Span;
+use djls_source::path_to_file;
+
+use crate::Db;
+use crate::FilterAritySpecs;
+use crate::LintRules;
+use crate::TagSpecs;
+use crate::collect_template_diagnostics;
+
+/// Path the analyzed source is registered under inside the private database.
+const ANALYSIS_PATH: &str = "/template.html";
+
+/// Diagnostics produced by [`analyze`], in source order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnalysisResult {
+    pub diagnostics: Vec<AnalysisDiagnostic>,
+}
+
+impl AnalysisResult {
+    /// Return whether the Template produced any diagnostics.
+    #[must_use]
+    pub fn has_diagnostics(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+}
+
+/// One syntax or validation diagnostic, detached from any database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnalysisDiagnostic {
+    /// Diagnostic code, e.g. `T100` for syntax errors or `S100` for validation.
+    pub code: &'static str,
+    /// Human-readable message.
+    pub message: String,
+    /// Byte span of the offending source, when the error has a location.
+    pub span: Option<Span>,
+}
+
+/// Parse and validate a Template source string against `tag_specs`.
+///
+/// Analysis runs without a project, so tag and filter meaning comes only from
+/// `tag_specs`; `{% load %}` scoping and filter arity checks that need a
+/// Django inventory are skipped. Diagnostic severity configuration is not
+/// applied — callers filter on [`AnalysisDiagnostic::code`] themselves.
+#[must_use]
+pub fn analyze(source: &str, tag_specs: &TagSpecs) -> AnalysisResult {
+    let path = Utf8PathBuf::from(ANALYSIS_PATH);
+    let mut fs = InMemoryFileSystem::new();
+    fs.add_file(path.clone(), source.to_string());
+    let db = AnalysisDatabase::new(Arc::new(fs), tag_specs.clone());
+
+    // The source was registered in memory above, so the lookup cannot miss.
+    let Ok(file) = path_to_file(&db, &path) else {
+        return AnalysisResult::default();
+    };
+    let collected = collect_template_diagnostics(&db, file);
+
+    let mut diagnostics: Vec<AnalysisDiagnostic> = collected
+        .template_errors
+        .iter()
+        .map(|error| AnalysisDiagnostic {
+            code: error.diagnostic_code(),
+            message: error.to_string(),
+            span: error
+                .primary_span()
+                .map(|(start, length)| Span::new(start, length)),
+        })
+        .chain(
+            collected
+                .validation_errors
+                .iter()
+                .map(|error| AnalysisDiagnostic {
+                    code: error.code(),
+                    message: error.to_string(),
+                    span: error.primary_span(),
+                }),
+        )
+        .collect();
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.map_or(0, Span::start));
+
+    AnalysisResult { diagnostics }
+}
+
+/// Projectless database backing a single [`analyze`] call.
+#[salsa::db]
+#[derive(Clone)]
+struct AnalysisDatabase {
+    storage: salsa::Storage<Self>,
+    fs: Arc<dyn FileSystem>,
+    files: SourceFiles,
+    tag_specs: Arc<TagSpecs>,
+}
+
+impl AnalysisDatabase {
+    fn new(fs: Arc<dyn FileSystem>, tag_specs: TagSpecs) -> Self {
+        Self {
+            storage: salsa::Storage::new(None),
+            fs,
+            files: SourceFiles::default(),
+            tag_specs: Arc::new(tag_specs),
+        }
+    }
+}
+
+#[salsa::db]
+impl salsa::Database for AnalysisDatabase {}
+
+#[salsa::db]
+impl SourceDb for AnalysisDatabase {
+    fn files(&self) -> &SourceFiles {
+        &self.files
+    }
+
+    fn file_system(&self) -> &dyn FileSystem {
+        self.fs.as_ref()
+    }
+}
+
+#[salsa::db]
+impl ProjectDb for AnalysisDatabase {
+    fn project(&self) -> Option<Project> {
+        None
+    }
+}
+
+#[salsa::db]
+impl Db for AnalysisDatabase {
+    fn projectless_tag_specs(&self) -> &TagSpecs {
+        &self.tag_specs
+    }
+
+    fn diagnostics_config(&self, _file: File) -> DiagnosticsConfig {
+        DiagnosticsConfig::default()
+    }
+
+    fn projectless_filter_arity_specs(&self) -> &FilterAritySpecs {
+        FilterAritySpecs::empty_ref()
+    }
+
+    fn model_graph(&self) -> &ModelGraph {
+        ModelGraph::empty_ref()
+    }
+
+    fn lint_rules(&self) -> &LintRules {
+        LintRules::empty_ref()
+    }
+
+    fn untracked_lint_rules(&self) -> &LintRules {
+        LintRules::empty_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin_tag_specs;
+
+    #[test]
+    fn well_formed_template_has_no_diagnostics() {
+        let result = analyze(
+            "{% if user %}{{ user.name }}{% endif %}",
+            &builtin_tag_specs(),
+        );
+        assert!(!result.has_diagnostics(), "{result:?}");
+    }
+
+    #[test]
+    fn unclosed_block_reports_owned_diagnostic() {
+        let result = analyze("{% if user %}{{ user.name }}", &builtin_tag_specs());
+        let diagnostic = result
+            .diagnostics
+            .first()
+            .expect("unclosed block should be reported");
+
+        assert_eq!(diagnostic.code, "S100");
+        assert!(diagnostic.span.is_some());
+    }
+
+    #[test]
+    fn newer_builtin_tags_validate_without_django_source() {
+        let specs = builtin_tag_specs();
+        let valid = analyze(
+            "{% querystring page=2 %}{% partialdef card inline %}x{% endpartialdef %}{% partial card %}",
+            &specs,
+        );
+        assert!(!valid.has_diagnostics(), "{valid:?}");
+
+        let invalid = analyze("{% partialdef card eager %}x{% endpartialdef %}", &specs);
+        let codes = invalid
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["S117"]);
+    }
+}
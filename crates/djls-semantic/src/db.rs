@@ -8,6 +8,7 @@ use djls_source::File;
 
 use crate::errors::ValidationError;
 use crate::filters::FilterAritySpecs;
+use crate::lint::LintRules;
 use crate::tags::TagSpecs;
 
 #[salsa::db]
@@ -17,8 +18,8 @@ pub trait Db: ProjectDb {
     /// Project-backed paths must derive meaning through per-library products.
     fn projectless_tag_specs(&self) -> &TagSpecs;
 
-    /// Get the diagnostics configuration.
-    fn diagnostics_config(&self) -> DiagnosticsConfig;
+    /// Get the diagnostics configuration that applies to `file`.
+    fn diagnostics_config(&self, file: File) -> DiagnosticsConfig;
 
     /// Explicit fixture seam for Filter validation without a Project.
     fn projectless_filter_arity_specs(&self) -> &FilterAritySpecs;
@@ -29,6 +30,17 @@ pub trait Db: ProjectDb {
     /// packages (site-packages). Returns an empty graph when no project is
     /// configured.
     fn model_graph(&self) -> &ModelGraph;
+
+    /// Custom lint rules run after built-in validation.
+    ///
+    /// These run inside tracked validation, so they must be pure functions of the Template.
+    fn lint_rules(&self) -> &LintRules;
+
+    /// Lint rules with side effects, such as external plugin processes.
+    ///
+    /// These run each time a Template's diagnostics are collected, outside tracked validation,
+    /// so their answers are never memoized.
+    fn untracked_lint_rules(&self) -> &LintRules;
 }
 
 pub fn scoped_template_libraries_for_file(db: &dyn Db, file: File) -> ScopedTemplateLibraries<'_> {
@@ -6,6 +6,9 @@ use djls_templates::parse_template;
 use crate::Db;
 use crate::ValidationError;
 use crate::ValidationErrorAccumulator;
+use crate::exceeds_template_limits;
+use crate::is_foreign_dialect;
+use crate::lint::untracked_lint_errors;
 use crate::validate_template_file;
 
 /// Syntax and semantic diagnostics collected for one Template.
@@ -25,19 +28,40 @@ impl TemplateDiagnostics {
 }
 
 /// Run Template validation and collect its syntax and semantic diagnostics.
+///
+/// Templates in another language, per [`is_foreign_dialect`], have none, and templates over
+/// their configured limits have only the S144 diagnostic saying so.
+///
+/// Validation is memoized, but [`Db::untracked_lint_rules`] run again on every call.
 #[must_use]
 pub fn collect_template_diagnostics(db: &dyn Db, file: File) -> TemplateDiagnostics {
+    if is_foreign_dialect(db, file) {
+        return TemplateDiagnostics {
+            template_errors: Vec::new(),
+            validation_errors: Vec::new(),
+        };
+    }
+
     validate_template_file(db, file);
 
-    let template_errors = parse_template::accumulated::<TemplateErrorAccumulator>(db, file)
-        .iter()
-        .map(|accumulator| accumulator.0.clone())
-        .collect();
-    let validation_errors =
+    // A template over its limits reports only why it was not validated.
+    let over_limits = exceeds_template_limits(db, file);
+    let template_errors = if over_limits {
+        Vec::new()
+    } else {
+        parse_template::accumulated::<TemplateErrorAccumulator>(db, file)
+            .iter()
+            .map(|accumulator| accumulator.0.clone())
+            .collect()
+    };
+    let mut validation_errors: Vec<ValidationError> =
         validate_template_file::accumulated::<ValidationErrorAccumulator>(db, file)
             .iter()
             .map(|accumulator| accumulator.0.clone())
             .collect();
+    if !over_limits {
+        validation_errors.extend(untracked_lint_errors(db, file));
+    }
 
     TemplateDiagnostics {
         template_errors,
@@ -2,10 +2,20 @@ use djls_source::Span;
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::fixes::Fix;
+
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize)]
 pub enum ValidationError {
     #[error("Unclosed '{tag}' tag")]
-    UnclosedTag { tag: String, span: Span },
+    UnclosedTag {
+        tag: String,
+        span: Span,
+        /// End tag that closes `tag`.
+        closer: String,
+        /// Where the block was implicitly closed: before an enclosing block's end tag, or
+        /// the end of the template.
+        closer_offset: u32,
+    },
 
     #[error("'{tag}' must be inside {context}")]
     OrphanedTag {
@@ -38,8 +48,14 @@ pub enum ValidationError {
         opener_span: Span,
     },
 
-    #[error("Unknown tag '{tag}'")]
-    UnknownTag { tag: String, span: Span },
+    #[error("Unknown tag '{tag}'{}", format_suggestions(suggestions))]
+    UnknownTag {
+        tag: String,
+        span: Span,
+        name_span: Span,
+        /// Known tags a typo could have meant, closest first.
+        suggestions: Vec<String>,
+    },
 
     #[error("Add '{app}' to INSTALLED_APPS to use tag '{tag}'")]
     TagNotInInstalledApps {
@@ -58,7 +74,7 @@ pub enum ValidationError {
 
     #[error(
         "Tag '{tag}' is available from multiple libraries: {}",
-        format_library_list(libraries)
+        format_quoted_list(libraries)
     )]
     AmbiguousUnloadedTag {
         tag: String,
@@ -66,8 +82,13 @@ pub enum ValidationError {
         span: Span,
     },
 
-    #[error("Unknown filter '{filter}'")]
-    UnknownFilter { filter: String, span: Span },
+    #[error("Unknown filter '{filter}'{}", format_suggestions(suggestions))]
+    UnknownFilter {
+        filter: String,
+        span: Span,
+        /// Known filters a typo could have meant, closest first.
+        suggestions: Vec<String>,
+    },
 
     #[error("Add '{app}' to INSTALLED_APPS to use filter '{filter}'")]
     FilterNotInInstalledApps {
@@ -86,7 +107,7 @@ pub enum ValidationError {
 
     #[error(
         "Filter '{filter}' is available from multiple libraries: {}",
-        format_library_list(libraries)
+        format_quoted_list(libraries)
     )]
     AmbiguousUnloadedFilter {
         filter: String,
@@ -107,6 +128,13 @@ pub enum ValidationError {
     #[error("Filter '{filter}' does not accept an argument")]
     FilterUnexpectedArgument { filter: String, span: Span },
 
+    #[error("{message}")]
+    InvalidFilterArgument {
+        filter: String,
+        message: String,
+        span: Span,
+    },
+
     #[error("{message}")]
     ExtractedRuleViolation {
         tag: String,
@@ -114,8 +142,22 @@ pub enum ValidationError {
         span: Span,
     },
 
-    #[error("Unknown template tag library '{name}'")]
-    UnknownLibrary { name: String, span: Span },
+    #[error("Unknown template tag library '{name}'{}", format_suggestions(suggestions))]
+    UnknownLibrary {
+        name: String,
+        span: Span,
+        /// Loadable libraries a typo could have meant, closest first.
+        suggestions: Vec<String>,
+    },
+
+    #[error("Template tag library '{library}' has no tag or filter named '{symbol}'")]
+    UnknownLoadSymbol {
+        symbol: String,
+        library: String,
+        span: Span,
+        /// Text to delete so the load no longer names the symbol.
+        removal: Span,
+    },
 
     #[error("Add '{app}' to INSTALLED_APPS to use template tag library '{name}'")]
     LibraryNotInInstalledApps {
@@ -129,17 +171,271 @@ pub enum ValidationError {
     ExtendsMustBeFirst { span: Span },
 
     #[error("The 'extends' tag can only appear once in a template")]
-    MultipleExtends { span: Span },
+    MultipleExtends { span: Span, first_span: Span },
+
+    #[error("'extends' argument '{argument}' is neither a template name nor a variable")]
+    InvalidExtendsArgument { argument: String, span: Span },
+
+    #[error("Parent template '{variable}' is chosen at runtime and cannot be followed statically")]
+    DynamicExtends { variable: String, span: Span },
+
+    #[error("'block' tag with name '{name}' appears more than once")]
+    DuplicateBlockName {
+        name: String,
+        span: Span,
+        first_span: Span,
+    },
+
+    #[error("'{tag}' only allows plain variable names; bind '{variable}' with 'with' first")]
+    TranslationVariableNotBound {
+        tag: String,
+        variable: String,
+        span: Span,
+    },
+
+    #[error("Variable '{variable}' appears in only one '{tag}' plural branch")]
+    TranslationPluralMismatch {
+        tag: String,
+        variable: String,
+        span: Span,
+    },
+
+    #[error("'{tag}' argument is not a string literal and cannot be extracted by makemessages")]
+    TranslationNonLiteral { tag: String, span: Span },
+
+    #[error("Placeholder '{placeholder}' in '{tag}' is not bound by 'with' or 'count'")]
+    TranslationPlaceholderNotBound {
+        tag: String,
+        placeholder: String,
+        span: Span,
+    },
+
+    #[error(
+        "'{template}' is included with 'only' but uses {}, which this include does not pass",
+        format_quoted_list(missing)
+    )]
+    IncludeMissingVariables {
+        template: String,
+        /// Variables the included template uses that `with` does not pass, sorted.
+        missing: Vec<String>,
+        span: Span,
+    },
+
+    #[error(
+        "Block '{name}' is not defined by admin template '{template}'{}",
+        format_suggestions(suggestions)
+    )]
+    UnknownAdminBlock {
+        name: String,
+        template: String,
+        span: Span,
+        /// Blocks of the admin template a typo could have meant, closest first.
+        suggestions: Vec<String>,
+    },
+
+    #[error("'{name}' {kind} was removed in Django {removed_in}; {advice}")]
+    RemovedTemplateSymbol {
+        /// `tag`, `filter`, or `library`.
+        kind: &'static str,
+        name: String,
+        removed_in: String,
+        advice: String,
+        span: Span,
+        /// Name to write instead, when the upgrade is a plain rename.
+        replacement: Option<String>,
+    },
+
+    #[error(
+        "'{name}' {kind} is deprecated since Django {deprecated_in} and removed in Django {removed_in}; {advice}"
+    )]
+    DeprecatedTemplateSymbol {
+        /// `tag`, `filter`, or `library`.
+        kind: &'static str,
+        name: String,
+        deprecated_in: String,
+        removed_in: String,
+        advice: String,
+        span: Span,
+        /// Name to write instead, when the upgrade is a plain rename.
+        replacement: Option<String>,
+    },
+
+    #[error(
+        "{} outside a block is never rendered in a template that extends another",
+        describe_outside_content(tag)
+    )]
+    ContentOutsideBlock {
+        /// The tag name, or `text`, `variable`, or `comment`.
+        tag: String,
+        span: Span,
+    },
+
+    #[error("'forloop' is only defined inside the body of a '{{% for %}}' loop")]
+    ForloopOutsideLoop { span: Span },
+
+    #[error("'{lookup}' needs {needed} enclosing '{{% for %}}' loops but is inside {found}")]
+    ParentloopWithoutOuterLoop {
+        /// The lookup up to its last `parentloop`, like `forloop.parentloop`.
+        lookup: String,
+        needed: usize,
+        found: usize,
+        span: Span,
+    },
+
+    #[error("'{name}' shadows {}", describe_shadowed(processor.as_deref()))]
+    ShadowedVariable {
+        name: String,
+        span: Span,
+        /// The binding of an enclosing `{% for %}`, `{% with %}`, or `{% blocktrans %}` that
+        /// `name` hides.
+        outer_span: Option<Span>,
+        /// The context processor that adds `name`, when no enclosing tag binds it.
+        processor: Option<String>,
+    },
+
+    #[error("Template '{name}' does not exist in any configured template directory")]
+    TemplateNotFound { name: String, span: Span },
+
+    #[error(
+        "Template '{name}' resolves to app '{app}' and shadows the same name in {}; namespace it as '{app}/{name}'",
+        format_quoted_list(shadowed)
+    )]
+    AmbiguousTemplateName {
+        name: String,
+        app: String,
+        /// Other installed apps with a template of the same name, in loader order.
+        shadowed: Vec<String>,
+        span: Span,
+    },
+
+    #[error("Template '{name}' is shadowed by '{winner}', which the template loaders find first")]
+    ShadowedTemplate {
+        name: String,
+        /// Path of the template file that wins the lookup.
+        winner: String,
+        span: Span,
+    },
+
+    #[error("Template has {actual} {measure}, over the limit of {limit}, so it is not validated")]
+    TemplateLimitExceeded {
+        /// What was counted: "bytes", "nodes", or "levels of nesting".
+        measure: String,
+        actual: usize,
+        limit: usize,
+        span: Span,
+    },
+
+    #[error("{}", format_unused_load(name, library.as_deref()))]
+    UnusedLoad {
+        /// The library, or for a selective load the symbol imported from `library`.
+        name: String,
+        library: Option<String>,
+        span: Span,
+        /// Text to delete so the load no longer names it.
+        removal: Span,
+    },
+
+    #[error("Tag delimiters should have one space inside them, as in '{{% {content} %}}'")]
+    TagDelimiterSpacing {
+        content: String,
+        /// The text between `{%` and `%}`.
+        span: Span,
+    },
+
+    #[error("POST form has no '{{% csrf_token %}}', so Django will reject its submissions")]
+    MissingCsrfToken { span: Span },
+
+    #[error("Template has {actual} {measure}, over the complexity limit of {limit}")]
+    TemplateTooComplex {
+        /// What was counted, such as "nodes" or "branches".
+        measure: String,
+        actual: usize,
+        limit: usize,
+        span: Span,
+    },
+
+    #[error("'<img>' has no 'alt' text")]
+    ImageWithoutAlt { span: Span },
+
+    #[error("'{field}' is rendered without a label")]
+    FieldWithoutLabel { field: String, span: Span },
+
+    #[error("id '{id}' repeats on every iteration of the enclosing '{{% for %}}'")]
+    RepeatedIdInLoop {
+        id: String,
+        span: Span,
+        loop_span: Span,
+    },
+
+    #[error("'{filter}' marks this output safe, so it is not autoescaped")]
+    OutputMarkedSafe { filter: String, span: Span },
+
+    #[error("Autoescaping is turned off until '{{% endautoescape %}}'")]
+    AutoescapeOff { span: Span },
+
+    #[error("'{filter}' returns its result marked safe and '{variable}' may hold user input")]
+    UnescapedUserInput {
+        filter: String,
+        variable: String,
+        span: Span,
+    },
+
+    #[error("{message}")]
+    LintRuleViolation {
+        /// Name of the [`LintRule`](crate::LintRule) that reported this.
+        rule: String,
+        code: &'static str,
+        message: String,
+        span: Span,
+    },
 }
 
-fn format_library_list(libraries: &[String]) -> String {
-    libraries
+fn format_quoted_list(names: &[String]) -> String {
+    names
         .iter()
-        .map(|library| format!("'{library}'"))
+        .map(|name| format!("'{name}'"))
         .collect::<Vec<_>>()
         .join(", ")
 }
 
+fn describe_shadowed(processor: Option<&str>) -> String {
+    match processor {
+        Some(processor) => format!("the variable context processor '{processor}' adds"),
+        None => "the binding of an enclosing tag".to_string(),
+    }
+}
+
+fn describe_outside_content(tag: &str) -> String {
+    match tag {
+        "text" => "Text".to_string(),
+        "variable" => "Variable".to_string(),
+        "comment" => "Comment".to_string(),
+        tag => format!("'{tag}' tag"),
+    }
+}
+
+fn format_unused_load(name: &str, library: Option<&str>) -> String {
+    match library {
+        Some(library) => format!("'{name}' is loaded from '{library}' but never used"),
+        None => format!("Template tag library '{name}' is loaded but never used"),
+    }
+}
+
+fn format_suggestions(suggestions: &[String]) -> String {
+    match suggestions {
+        [] => String::new(),
+        [only] => format!("; did you mean '{only}'?"),
+        [rest @ .., last] => {
+            let rest = rest
+                .iter()
+                .map(|suggestion| format!("'{suggestion}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("; did you mean {rest} or '{last}'?")
+        }
+    }
+}
+
 impl ValidationError {
     #[must_use]
     pub fn code(&self) -> &'static str {
@@ -164,6 +460,38 @@ impl ValidationError {
             Self::LibraryNotInInstalledApps { .. } => "S121",
             Self::ExtendsMustBeFirst { .. } => "S122",
             Self::MultipleExtends { .. } => "S123",
+            Self::InvalidFilterArgument { .. } => "S124",
+            Self::TranslationVariableNotBound { .. } => "S125",
+            Self::TranslationPluralMismatch { .. } => "S126",
+            Self::TranslationNonLiteral { .. } => "S127",
+            Self::UnknownLoadSymbol { .. } => "S128",
+            Self::InvalidExtendsArgument { .. } => "S129",
+            Self::DynamicExtends { .. } => "S130",
+            Self::DuplicateBlockName { .. } => "S131",
+            Self::TranslationPlaceholderNotBound { .. } => "S132",
+            Self::IncludeMissingVariables { .. } => "S133",
+            Self::UnknownAdminBlock { .. } => "S134",
+            Self::RemovedTemplateSymbol { .. } => "S135",
+            Self::DeprecatedTemplateSymbol { .. } => "S136",
+            Self::ContentOutsideBlock { .. } => "S137",
+            Self::ForloopOutsideLoop { .. } => "S138",
+            Self::ParentloopWithoutOuterLoop { .. } => "S139",
+            Self::ShadowedVariable { .. } => "S140",
+            Self::TemplateNotFound { .. } => "S141",
+            Self::AmbiguousTemplateName { .. } => "S142",
+            Self::ShadowedTemplate { .. } => "S143",
+            Self::TemplateLimitExceeded { .. } => "S144",
+            Self::MissingCsrfToken { .. } => "S145",
+            Self::TemplateTooComplex { .. } => "S146",
+            Self::UnusedLoad { .. } => "S147",
+            Self::TagDelimiterSpacing { .. } => "S148",
+            Self::ImageWithoutAlt { .. } => "A100",
+            Self::FieldWithoutLabel { .. } => "A101",
+            Self::RepeatedIdInLoop { .. } => "A102",
+            Self::OutputMarkedSafe { .. } => "X100",
+            Self::AutoescapeOff { .. } => "X101",
+            Self::UnescapedUserInput { .. } => "X102",
+            Self::LintRuleViolation { code, .. } => *code,
         }
     }
 
@@ -186,11 +514,282 @@ impl ValidationError {
             | Self::ExpressionSyntaxError { span, .. }
             | Self::FilterMissingArgument { span, .. }
             | Self::FilterUnexpectedArgument { span, .. }
+            | Self::InvalidFilterArgument { span, .. }
             | Self::ExtractedRuleViolation { span, .. }
             | Self::UnknownLibrary { span, .. }
+            | Self::UnknownLoadSymbol { span, .. }
             | Self::LibraryNotInInstalledApps { span, .. }
             | Self::ExtendsMustBeFirst { span, .. }
-            | Self::MultipleExtends { span, .. } => Some(*span),
+            | Self::MultipleExtends { span, .. }
+            | Self::InvalidExtendsArgument { span, .. }
+            | Self::DynamicExtends { span, .. }
+            | Self::DuplicateBlockName { span, .. }
+            | Self::TranslationVariableNotBound { span, .. }
+            | Self::TranslationPluralMismatch { span, .. }
+            | Self::TranslationNonLiteral { span, .. }
+            | Self::TranslationPlaceholderNotBound { span, .. }
+            | Self::IncludeMissingVariables { span, .. }
+            | Self::UnknownAdminBlock { span, .. }
+            | Self::RemovedTemplateSymbol { span, .. }
+            | Self::DeprecatedTemplateSymbol { span, .. }
+            | Self::ContentOutsideBlock { span, .. }
+            | Self::ForloopOutsideLoop { span }
+            | Self::ParentloopWithoutOuterLoop { span, .. }
+            | Self::ShadowedVariable { span, .. }
+            | Self::TemplateNotFound { span, .. }
+            | Self::AmbiguousTemplateName { span, .. }
+            | Self::ShadowedTemplate { span, .. }
+            | Self::TemplateLimitExceeded { span, .. }
+            | Self::MissingCsrfToken { span }
+            | Self::TemplateTooComplex { span, .. }
+            | Self::UnusedLoad { span, .. }
+            | Self::TagDelimiterSpacing { span, .. }
+            | Self::ImageWithoutAlt { span }
+            | Self::FieldWithoutLabel { span, .. }
+            | Self::RepeatedIdInLoop { span, .. }
+            | Self::OutputMarkedSafe { span, .. }
+            | Self::AutoescapeOff { span }
+            | Self::UnescapedUserInput { span, .. }
+            | Self::LintRuleViolation { span, .. } => Some(*span),
+        }
+    }
+
+    /// Secondary locations that explain the error, each with a short message.
+    #[must_use]
+    pub fn related_spans(&self) -> Vec<(Span, String)> {
+        match self {
+            Self::UnbalancedStructure {
+                expected_closing,
+                closing_span: Some(closing_span),
+                ..
+            } => vec![(*closing_span, format!("'{expected_closing}' reached here"))],
+            Self::UnmatchedBlockName {
+                expected,
+                opener_span,
+                ..
+            } => vec![(*opener_span, format!("'{expected}' opened here"))],
+            Self::MultipleExtends { first_span, .. } => {
+                vec![(*first_span, "first 'extends' tag".to_string())]
+            }
+            Self::DuplicateBlockName { first_span, .. } => {
+                vec![(*first_span, "first definition of this block".to_string())]
+            }
+            Self::ShadowedVariable {
+                outer_span: Some(outer_span),
+                ..
+            } => vec![(*outer_span, "outer binding of this name".to_string())],
+            Self::RepeatedIdInLoop { loop_span, .. } => {
+                vec![(*loop_span, "enclosing loop".to_string())]
+            }
+            Self::UnclosedTag { .. }
+            | Self::OrphanedTag { .. }
+            | Self::OrphanedClosingTag { .. }
+            | Self::UnbalancedStructure {
+                closing_span: None, ..
+            }
+            | Self::UnknownTag { .. }
+            | Self::TagNotInInstalledApps { .. }
+            | Self::UnloadedTag { .. }
+            | Self::AmbiguousUnloadedTag { .. }
+            | Self::UnknownFilter { .. }
+            | Self::FilterNotInInstalledApps { .. }
+            | Self::UnloadedFilter { .. }
+            | Self::AmbiguousUnloadedFilter { .. }
+            | Self::ExpressionSyntaxError { .. }
+            | Self::FilterMissingArgument { .. }
+            | Self::FilterUnexpectedArgument { .. }
+            | Self::InvalidFilterArgument { .. }
+            | Self::ExtractedRuleViolation { .. }
+            | Self::UnknownLibrary { .. }
+            | Self::UnknownLoadSymbol { .. }
+            | Self::LibraryNotInInstalledApps { .. }
+            | Self::ExtendsMustBeFirst { .. }
+            | Self::InvalidExtendsArgument { .. }
+            | Self::DynamicExtends { .. }
+            | Self::TranslationVariableNotBound { .. }
+            | Self::TranslationPluralMismatch { .. }
+            | Self::TranslationNonLiteral { .. }
+            | Self::TranslationPlaceholderNotBound { .. }
+            | Self::IncludeMissingVariables { .. }
+            | Self::UnknownAdminBlock { .. }
+            | Self::RemovedTemplateSymbol { .. }
+            | Self::DeprecatedTemplateSymbol { .. }
+            | Self::ContentOutsideBlock { .. }
+            | Self::ForloopOutsideLoop { .. }
+            | Self::ParentloopWithoutOuterLoop { .. }
+            | Self::ShadowedVariable {
+                outer_span: None, ..
+            }
+            | Self::TemplateNotFound { .. }
+            | Self::AmbiguousTemplateName { .. }
+            | Self::ShadowedTemplate { .. }
+            | Self::TemplateLimitExceeded { .. }
+            | Self::MissingCsrfToken { .. }
+            | Self::TemplateTooComplex { .. }
+            | Self::UnusedLoad { .. }
+            | Self::TagDelimiterSpacing { .. }
+            | Self::ImageWithoutAlt { .. }
+            | Self::FieldWithoutLabel { .. }
+            | Self::OutputMarkedSafe { .. }
+            | Self::AutoescapeOff { .. }
+            | Self::UnescapedUserInput { .. }
+            | Self::LintRuleViolation { .. } => Vec::new(),
         }
     }
+
+    /// A machine-applicable edit that resolves the error, when there is exactly one.
+    #[must_use]
+    pub fn fix(&self) -> Option<Fix> {
+        match self {
+            Self::UnclosedTag {
+                closer,
+                closer_offset,
+                ..
+            } => Some(Fix::insert(*closer_offset, format!("{{% {closer} %}}"))),
+            Self::UnmatchedBlockName {
+                expected, got_span, ..
+            } => Some(Fix::replace(*got_span, expected.clone())),
+            Self::UnknownLoadSymbol { removal, .. } | Self::UnusedLoad { removal, .. } => {
+                Some(Fix::delete(*removal))
+            }
+            Self::TagDelimiterSpacing { content, span } => {
+                Some(Fix::replace(*span, format!(" {content} ")))
+            }
+            Self::MissingCsrfToken { span } => {
+                Some(Fix::insert(span.end(), "{% csrf_token %}".to_string()))
+            }
+            Self::RemovedTemplateSymbol {
+                span, replacement, ..
+            }
+            | Self::DeprecatedTemplateSymbol {
+                span, replacement, ..
+            } => replacement
+                .as_ref()
+                .map(|replacement| Fix::replace(*span, replacement.clone())),
+            Self::OrphanedTag { .. }
+            | Self::OrphanedClosingTag { .. }
+            | Self::UnbalancedStructure { .. }
+            | Self::UnknownTag { .. }
+            | Self::TagNotInInstalledApps { .. }
+            | Self::UnloadedTag { .. }
+            | Self::AmbiguousUnloadedTag { .. }
+            | Self::UnknownFilter { .. }
+            | Self::FilterNotInInstalledApps { .. }
+            | Self::UnloadedFilter { .. }
+            | Self::AmbiguousUnloadedFilter { .. }
+            | Self::ExpressionSyntaxError { .. }
+            | Self::FilterMissingArgument { .. }
+            | Self::FilterUnexpectedArgument { .. }
+            | Self::InvalidFilterArgument { .. }
+            | Self::ExtractedRuleViolation { .. }
+            | Self::UnknownLibrary { .. }
+            | Self::LibraryNotInInstalledApps { .. }
+            | Self::ExtendsMustBeFirst { .. }
+            | Self::MultipleExtends { .. }
+            | Self::InvalidExtendsArgument { .. }
+            | Self::DynamicExtends { .. }
+            | Self::DuplicateBlockName { .. }
+            | Self::TranslationVariableNotBound { .. }
+            | Self::TranslationPluralMismatch { .. }
+            | Self::TranslationNonLiteral { .. }
+            | Self::TranslationPlaceholderNotBound { .. }
+            | Self::IncludeMissingVariables { .. }
+            | Self::UnknownAdminBlock { .. }
+            | Self::ContentOutsideBlock { .. }
+            | Self::ForloopOutsideLoop { .. }
+            | Self::ParentloopWithoutOuterLoop { .. }
+            | Self::ShadowedVariable { .. }
+            | Self::TemplateNotFound { .. }
+            | Self::AmbiguousTemplateName { .. }
+            | Self::ShadowedTemplate { .. }
+            | Self::TemplateLimitExceeded { .. }
+            | Self::TemplateTooComplex { .. }
+            | Self::ImageWithoutAlt { .. }
+            | Self::FieldWithoutLabel { .. }
+            | Self::RepeatedIdInLoop { .. }
+            | Self::OutputMarkedSafe { .. }
+            | Self::AutoescapeOff { .. }
+            | Self::UnescapedUserInput { .. }
+            | Self::LintRuleViolation { .. } => None,
+        }
+    }
+
+    /// Replacements for a misspelled name, one per suggestion, closest first.
+    ///
+    /// Unlike [`fix`](Self::fix), these are guesses, so editors offer them as quick fixes but
+    /// `djls check --fix` never applies them.
+    #[must_use]
+    pub fn suggested_replacements(&self) -> Vec<Fix> {
+        let (span, suggestions) = match self {
+            Self::UnknownTag {
+                name_span,
+                suggestions,
+                ..
+            } => (*name_span, suggestions),
+            Self::UnknownFilter {
+                filter,
+                span,
+                suggestions,
+            } => (span.with_length_usize_saturating(filter.len()), suggestions),
+            Self::UnknownLibrary {
+                span, suggestions, ..
+            }
+            | Self::UnknownAdminBlock {
+                span, suggestions, ..
+            } => (*span, suggestions),
+            Self::UnclosedTag { .. }
+            | Self::UnmatchedBlockName { .. }
+            | Self::UnknownLoadSymbol { .. }
+            | Self::OrphanedTag { .. }
+            | Self::OrphanedClosingTag { .. }
+            | Self::UnbalancedStructure { .. }
+            | Self::TagNotInInstalledApps { .. }
+            | Self::UnloadedTag { .. }
+            | Self::AmbiguousUnloadedTag { .. }
+            | Self::FilterNotInInstalledApps { .. }
+            | Self::UnloadedFilter { .. }
+            | Self::AmbiguousUnloadedFilter { .. }
+            | Self::ExpressionSyntaxError { .. }
+            | Self::FilterMissingArgument { .. }
+            | Self::FilterUnexpectedArgument { .. }
+            | Self::InvalidFilterArgument { .. }
+            | Self::ExtractedRuleViolation { .. }
+            | Self::LibraryNotInInstalledApps { .. }
+            | Self::ExtendsMustBeFirst { .. }
+            | Self::MultipleExtends { .. }
+            | Self::InvalidExtendsArgument { .. }
+            | Self::DynamicExtends { .. }
+            | Self::DuplicateBlockName { .. }
+            | Self::TranslationVariableNotBound { .. }
+            | Self::TranslationPluralMismatch { .. }
+            | Self::TranslationNonLiteral { .. }
+            | Self::TranslationPlaceholderNotBound { .. }
+            | Self::IncludeMissingVariables { .. }
+            | Self::RemovedTemplateSymbol { .. }
+            | Self::DeprecatedTemplateSymbol { .. }
+            | Self::ContentOutsideBlock { .. }
+            | Self::ForloopOutsideLoop { .. }
+            | Self::ParentloopWithoutOuterLoop { .. }
+            | Self::ShadowedVariable { .. }
+            | Self::TemplateNotFound { .. }
+            | Self::AmbiguousTemplateName { .. }
+            | Self::ShadowedTemplate { .. }
+            | Self::TemplateLimitExceeded { .. }
+            | Self::MissingCsrfToken { .. }
+            | Self::TemplateTooComplex { .. }
+            | Self::UnusedLoad { .. }
+            | Self::TagDelimiterSpacing { .. }
+            | Self::ImageWithoutAlt { .. }
+            | Self::FieldWithoutLabel { .. }
+            | Self::RepeatedIdInLoop { .. }
+            | Self::OutputMarkedSafe { .. }
+            | Self::AutoescapeOff { .. }
+            | Self::UnescapedUserInput { .. }
+            | Self::LintRuleViolation { .. } => return Vec::new(),
+        };
+        suggestions
+            .iter()
+            .map(|suggestion| Fix::replace(span, suggestion.clone()))
+            .collect()
+    }
 }
@@ -8,6 +8,7 @@ use djls_project::SymbolKey;
 use djls_project::TemplateLibraryId;
 use djls_project::TemplateSymbolKind;
 use djls_project::template_library_filter_facts;
+use djls_project::template_library_filter_signatures;
 use rustc_hash::FxHashMap;
 
 use crate::db::Db;
@@ -121,3 +122,32 @@ pub(crate) fn effective_filter_arity_in_scope(
         .flatten()
         .cloned()
 }
+
+/// Whether every effective definition of a filter at one occurrence returns its result marked
+/// safe without escaping its input first.
+///
+/// Filters registered with `needs_autoescape=True` escape their input themselves, so they do not
+/// count. An unknown or unobserved definition makes the answer `false`.
+pub(crate) fn effective_filter_marks_safe_in_scope(
+    db: &dyn Db,
+    scoped_libraries: ScopedTemplateLibraries<'_>,
+    filter_name: &str,
+    load_state: &LoadState<'_>,
+) -> bool {
+    let loaded = load_state.libraries_loading_symbol(filter_name);
+    let definitions = scoped_libraries.effective_definition_libraries(
+        filter_name,
+        TemplateSymbolKind::Filter,
+        &loaded,
+    );
+    !definitions.is_empty()
+        && definitions.into_iter().all(|definition| {
+            let EffectiveDefinitionLibrary::Known(Some(library)) = definition else {
+                return false;
+            };
+            let key = SymbolKey::filter(library.module_name_str(), filter_name);
+            template_library_filter_signatures(db, library.id())
+                .get(&key)
+                .is_some_and(|signature| signature.marks_safe && !signature.needs_autoescape)
+        })
+}
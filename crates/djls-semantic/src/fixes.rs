@@ -0,0 +1,100 @@
+use djls_source::Span;
+
+/// A machine-applicable text edit that resolves a diagnostic.
+///
+/// Fixes are only offered when the edit is unambiguous, so `djls check --fix` can apply them
+/// without review and editors can mark them as the preferred quick fix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    /// Source range to replace; empty for an insertion.
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl Fix {
+    #[must_use]
+    pub fn insert(offset: u32, text: String) -> Self {
+        Self {
+            span: Span::new(offset, 0),
+            replacement: text,
+        }
+    }
+
+    #[must_use]
+    pub fn delete(span: Span) -> Self {
+        Self {
+            span,
+            replacement: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn replace(span: Span, replacement: String) -> Self {
+        Self { span, replacement }
+    }
+}
+
+/// Apply `fixes` to `source`, returning the edited text and how many fixes were applied.
+///
+/// Fixes are applied in source order. Insertions at the same offset keep the order they were
+/// given in, and a fix overlapping one already applied is skipped, so a later run can pick it
+/// up against the updated text.
+#[must_use]
+pub fn apply_fixes(source: &str, fixes: &[Fix]) -> (String, usize) {
+    let mut ordered = fixes.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|fix| fix.span.start());
+
+    let mut output = String::with_capacity(source.len());
+    let mut cursor = 0;
+    let mut applied = 0;
+    for fix in ordered {
+        let start = fix.span.start_usize();
+        let end = fix.span.end_usize();
+        let Some(unchanged) = source.get(cursor..start) else {
+            continue;
+        };
+        if !source.is_char_boundary(end) {
+            continue;
+        }
+        output.push_str(unchanged);
+        output.push_str(&fix.replacement);
+        cursor = end;
+        applied += 1;
+    }
+    output.push_str(source.get(cursor..).unwrap_or_default());
+
+    (output, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_fixes_in_source_order_and_skips_overlaps() {
+        let source = "{% load trans static from i18n %}{% if x %}";
+        let fixes = [
+            Fix::insert(43, "{% endif %}".to_string()),
+            Fix::delete(Span::new(14, 7)),
+            Fix::delete(Span::new(16, 10)),
+        ];
+
+        let (fixed, applied) = apply_fixes(source, &fixes);
+
+        assert_eq!(fixed, "{% load trans from i18n %}{% if x %}{% endif %}");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn insertions_at_one_offset_keep_their_order() {
+        let fixes = [
+            Fix::insert(11, "{% endif %}".to_string()),
+            Fix::insert(11, "{% endfor %}".to_string()),
+        ];
+
+        let (fixed, applied) = apply_fixes("{% for x %}", &fixes);
+
+        assert_eq!(fixed, "{% for x %}{% endif %}{% endfor %}");
+        assert_eq!(applied, 2);
+    }
+}
@@ -0,0 +1,392 @@
+//! HTML start and end tags in template text.
+//!
+//! Template text is HTML cut up by variables and template tags, so one start tag can span several
+//! nodes: `<img src="{% static 'logo.png' %}" alt="">` is text, a tag, and more text. An
+//! [`HtmlScanner`] is fed the nodes of one body in source order and reports each start tag once
+//! its `>` is reached, marking attribute values that a variable or template tag writes as
+//! dynamic, along with the runs of character data between tags. Comments and the contents of
+//! `<script>` and `<style>` are skipped.
+
+use djls_source::Span;
+
+/// Stands in for a variable or template tag inside a start tag.
+const PLACEHOLDER: u8 = 0x1a;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum HtmlEvent {
+    Start(StartTag),
+    /// A `</name>` end tag, with the element name lowercased.
+    End(String),
+    /// Character data between tags that is not all whitespace. A run ends at a variable or
+    /// template tag, so `Hello {{ name }}!` is two runs.
+    Text(Span),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StartTag {
+    /// Lowercased element name.
+    pub(crate) name: String,
+    /// `<` through `>`.
+    pub(crate) span: Span,
+    pub(crate) attributes: Vec<Attribute>,
+    /// A template tag sits among the attributes and may add or change any of them.
+    pub(crate) has_template_tag: bool,
+}
+
+impl StartTag {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// The value of attribute `name`, when it is written without variables or template tags.
+    pub(crate) fn static_value(&self, name: &str) -> Option<&AttributeValue> {
+        self.attribute(name)?
+            .value
+            .as_ref()
+            .filter(|value| !value.dynamic)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Attribute {
+    /// Lowercased attribute name.
+    pub(crate) name: String,
+    /// `None` for a bare attribute such as `disabled`.
+    pub(crate) value: Option<AttributeValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AttributeValue {
+    /// The value without its quotes.
+    pub(crate) text: String,
+    pub(crate) span: Span,
+    /// Part of the value comes from a variable or template tag.
+    pub(crate) dynamic: bool,
+}
+
+/// Reports the HTML tags in one body of a template, fed node by node.
+#[derive(Default)]
+pub(crate) struct HtmlScanner {
+    pending: Option<PendingTag>,
+    /// Inside a comment, `<script>`, or `<style>`, until this text.
+    skip_until: Option<&'static str>,
+}
+
+impl HtmlScanner {
+    /// Scans a text node starting at source offset `start`.
+    pub(crate) fn text(&mut self, text: &str, start: usize) -> Vec<HtmlEvent> {
+        let lower = text.to_ascii_lowercase();
+        let mut events = Vec::new();
+        let mut pos = 0;
+        loop {
+            if let Some(until) = self.skip_until {
+                let Some(found) = lower[pos..].find(until) else {
+                    return events;
+                };
+                pos += found;
+                if until == "-->" {
+                    pos += until.len();
+                }
+                self.skip_until = None;
+            }
+
+            if let Some(mut tag) = self.pending.take() {
+                let rest = &text[pos..];
+                let close = tag.find_close(rest);
+                tag.push_text(&rest[..close.unwrap_or(rest.len())], start + pos);
+                let Some(close) = close else {
+                    self.pending = Some(tag);
+                    return events;
+                };
+                pos += close + 1;
+                let tag = tag.finish(start + pos);
+                self.skip_until = match tag.name.as_str() {
+                    "script" => Some("</script"),
+                    "style" => Some("</style"),
+                    _ => None,
+                };
+                events.push(HtmlEvent::Start(tag));
+                continue;
+            }
+
+            // A `<` that starts no tag, as in `a < b`, is part of the character data.
+            let data = pos;
+            let mut at = pos;
+            let markup = loop {
+                let Some(found) = lower[at..].find('<') else {
+                    break None;
+                };
+                at += found;
+                let rest = &lower[at + 1..];
+                let (is_end, name) = rest
+                    .strip_prefix('/')
+                    .map_or((false, rest), |name| (true, name));
+                let len = element_name_len(name);
+                if rest.starts_with("!--") || len > 0 {
+                    break Some((is_end, name, len));
+                }
+                at += 1;
+            };
+            let data_end = markup.map_or(text.len(), |_| at);
+            if !text[data..data_end].trim().is_empty() {
+                events.push(HtmlEvent::Text(Span::saturating_from_bounds_usize(
+                    start + data,
+                    start + data_end,
+                )));
+            }
+
+            let Some((is_end, name, len)) = markup else {
+                return events;
+            };
+            if len == 0 {
+                self.skip_until = Some("-->");
+                pos = at + "<!--".len();
+            } else if is_end {
+                events.push(HtmlEvent::End(name[..len].to_string()));
+                pos = lower[at..]
+                    .find('>')
+                    .map_or(lower.len(), |close| at + close + 1);
+            } else {
+                self.pending = Some(PendingTag::new(start + at, &name[..len]));
+                pos = at + 1 + len;
+            }
+        }
+    }
+
+    /// A `{{ variable }}`, which may write part of a start tag's attributes.
+    pub(crate) fn variable(&mut self, span: Span) {
+        if let Some(tag) = &mut self.pending {
+            tag.push_placeholder(span);
+        }
+    }
+
+    /// A template tag or block, which may add attributes to the start tag it sits in.
+    pub(crate) fn template_tag(&mut self, span: Span) {
+        if let Some(tag) = &mut self.pending {
+            tag.push_placeholder(span);
+            tag.has_template_tag = true;
+        }
+    }
+}
+
+/// A start tag whose `>` has not been reached.
+struct PendingTag {
+    start: usize,
+    name: String,
+    /// Attribute text so far, with [`PLACEHOLDER`] for each variable and template tag.
+    text: String,
+    /// Offset in `text` and in the source where each piece of `text` begins.
+    pieces: Vec<(usize, usize)>,
+    quote: Option<char>,
+    has_template_tag: bool,
+}
+
+impl PendingTag {
+    fn new(start: usize, name: &str) -> Self {
+        Self {
+            start,
+            name: name.to_string(),
+            text: String::new(),
+            pieces: Vec::new(),
+            quote: None,
+            has_template_tag: false,
+        }
+    }
+
+    /// The offset of the `>` ending the tag in `text`, outside quoted values.
+    fn find_close(&mut self, text: &str) -> Option<usize> {
+        for (index, c) in text.char_indices() {
+            match (self.quote, c) {
+                (Some(quote), _) if c == quote => self.quote = None,
+                (None, '"' | '\'') => self.quote = Some(c),
+                (None, '>') => return Some(index),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn push_text(&mut self, text: &str, source: usize) {
+        self.pieces.push((self.text.len(), source));
+        self.text.push_str(text);
+    }
+
+    fn push_placeholder(&mut self, span: Span) {
+        self.pieces.push((self.text.len(), span.start_usize()));
+        self.text.push(char::from(PLACEHOLDER));
+    }
+
+    fn source_offset(&self, offset: usize) -> usize {
+        let index = self
+            .pieces
+            .partition_point(|(text, _)| *text <= offset)
+            .saturating_sub(1);
+        self.pieces
+            .get(index)
+            .map_or(self.start, |(text, source)| source + (offset - text))
+    }
+
+    fn finish(self, end: usize) -> StartTag {
+        let attributes = self.attributes();
+        StartTag {
+            name: self.name,
+            span: Span::saturating_from_bounds_usize(self.start, end),
+            attributes,
+            has_template_tag: self.has_template_tag,
+        }
+    }
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let text = self.text.as_str();
+        let bytes = text.as_bytes();
+        let skip = |mut index: usize, stop: fn(u8) -> bool| {
+            while index < bytes.len() && !stop(bytes[index]) {
+                index += 1;
+            }
+            index
+        };
+
+        let mut attributes = Vec::new();
+        let mut index = 0;
+        loop {
+            // Outside values, a variable or tag separates attributes like whitespace.
+            index = skip(index, |b| {
+                !b.is_ascii_whitespace() && b != b'/' && b != PLACEHOLDER
+            });
+            if index >= bytes.len() {
+                return attributes;
+            }
+            let name_start = index;
+            index = skip(index, |b| {
+                b.is_ascii_whitespace() || matches!(b, b'=' | b'/' | PLACEHOLDER)
+            });
+            let name = text[name_start..index].to_ascii_lowercase();
+
+            let after_name = skip(index, |b| !b.is_ascii_whitespace());
+            let value = if bytes.get(after_name) == Some(&b'=') {
+                let value_start = skip(after_name + 1, |b| !b.is_ascii_whitespace());
+                let (start, end) = match bytes.get(value_start) {
+                    Some(&quote @ (b'"' | b'\'')) => {
+                        let end = text[value_start + 1..]
+                            .find(char::from(quote))
+                            .map_or(bytes.len(), |end| value_start + 1 + end);
+                        index = (end + 1).min(bytes.len());
+                        (value_start + 1, end)
+                    }
+                    _ => {
+                        index = skip(value_start, |b| b.is_ascii_whitespace());
+                        (value_start, index)
+                    }
+                };
+                let value = &text[start..end];
+                Some(AttributeValue {
+                    text: value.to_string(),
+                    span: Span::saturating_from_bounds_usize(
+                        self.source_offset(start),
+                        self.source_offset(end),
+                    ),
+                    dynamic: value.contains(char::from(PLACEHOLDER)),
+                })
+            } else {
+                None
+            };
+            attributes.push(Attribute { name, value });
+        }
+    }
+}
+
+/// Length of the element name at the start of `text`, or 0 when it does not start with one.
+fn element_name_len(text: &str) -> usize {
+    if !text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return 0;
+    }
+    text.bytes()
+        .take_while(|b| b.is_ascii_alphanumeric() || *b == b'-')
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start_tags(events: Vec<HtmlEvent>) -> Vec<StartTag> {
+        events
+            .into_iter()
+            .filter_map(|event| match event {
+                HtmlEvent::Start(tag) => Some(tag),
+                HtmlEvent::End(_) | HtmlEvent::Text(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attributes_are_read_in_any_quoting() {
+        let source = r#"<Input TYPE=text value='a > b' required id="name">"#;
+        let tags = start_tags(HtmlScanner::default().text(source, 0));
+
+        assert_eq!(tags.len(), 1);
+        let tag = &tags[0];
+        assert_eq!(tag.name, "input");
+        assert_eq!(tag.span, Span::saturating_from_bounds_usize(0, source.len()));
+        assert_eq!(
+            tag.attributes
+                .iter()
+                .map(|attribute| {
+                    (
+                        attribute.name.as_str(),
+                        attribute.value.as_ref().map(|value| value.text.as_str()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            [
+                ("type", Some("text")),
+                ("value", Some("a > b")),
+                ("required", None),
+                ("id", Some("name")),
+            ]
+        );
+        let id = tag.static_value("id").expect("id should be static");
+        assert_eq!(&source[id.span.start_usize()..id.span.end_usize()], "name");
+    }
+
+    #[test]
+    fn start_tags_continue_across_template_nodes() {
+        // <img src="{{ logo }}" {% if x %}{% endif %}alt="Logo">
+        let mut scanner = HtmlScanner::default();
+        assert!(scanner.text(r#"<img src=""#, 0).is_empty());
+        scanner.variable(Span::saturating_from_bounds_usize(10, 20));
+        assert!(scanner.text(r#"" "#, 20).is_empty());
+        scanner.template_tag(Span::saturating_from_bounds_usize(22, 43));
+        let tags = start_tags(scanner.text(r#"alt="Logo">"#, 43));
+
+        assert_eq!(tags.len(), 1);
+        let tag = &tags[0];
+        assert_eq!(tag.span, Span::saturating_from_bounds_usize(0, 54));
+        assert!(tag.has_template_tag);
+        assert!(tag.static_value("src").is_none());
+        let alt = tag.static_value("alt").expect("alt should be static");
+        assert_eq!(alt.span, Span::saturating_from_bounds_usize(48, 52));
+    }
+
+    #[test]
+    fn comments_and_scripts_are_skipped() {
+        let source = "<!-- <img> --><script>if (a<b) { x = '<form>'; }</script><p>1 < 2</p> ";
+        let events = HtmlScanner::default().text(source, 0);
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| match event {
+                    HtmlEvent::Start(tag) => format!("<{}>", tag.name),
+                    HtmlEvent::End(name) => format!("</{name}>"),
+                    HtmlEvent::Text(span) => {
+                        source[span.start_usize()..span.end_usize()].to_string()
+                    }
+                })
+                .collect::<Vec<_>>(),
+            ["<script>", "</script>", "<p>", "1 < 2", "</p>"]
+        );
+    }
+}
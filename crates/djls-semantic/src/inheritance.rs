@@ -330,6 +330,71 @@ pub fn inherited_blocks(db: &dyn Db, project: Project, file: File) -> Vec<(Strin
     inherited
 }
 
+/// How a template's blocks line up with the blocks its extends chain offers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockCoverage {
+    /// Ancestor templates, nearest first.
+    pub ancestors: Vec<File>,
+    /// Inherited blocks in [`inherited_blocks`] order, then blocks only the template defines.
+    pub blocks: Vec<CoveredBlock>,
+    pub end: ChainEnd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoveredBlock {
+    pub name: String,
+    /// Nearest ancestor definition, or `None` when no ancestor offers the block.
+    pub base: Option<BlockSite>,
+    /// The template's own definition, or `None` when it leaves the block to its ancestors.
+    pub own: Option<BlockSite>,
+}
+
+/// Which blocks from `file`'s extends chain it overrides, which it leaves untouched, and which
+/// blocks it defines that no ancestor offers.
+pub fn block_coverage(db: &dyn Db, project: Project, file: File) -> BlockCoverage {
+    let own_blocks = match parse_template(db, file) {
+        TemplateParseResult::Parsed(nodelist) => template_symbols(db, file, nodelist).blocks(),
+        TemplateParseResult::NotTemplate | TemplateParseResult::Unreadable(_) => &[],
+    };
+    let own_site = |block: &BlockDef| BlockSite {
+        file,
+        name_span: block.name_span,
+        full_span: block.full_span,
+    };
+
+    let mut blocks: Vec<CoveredBlock> = inherited_blocks(db, project, file)
+        .into_iter()
+        .map(|(name, base)| CoveredBlock {
+            own: own_blocks
+                .iter()
+                .find(|block| block.name == name)
+                .map(own_site),
+            base: Some(base),
+            name,
+        })
+        .collect();
+    for block in own_blocks {
+        if !blocks.iter().any(|covered| covered.name == block.name) {
+            blocks.push(CoveredBlock {
+                name: block.name.clone(),
+                base: None,
+                own: Some(own_site(block)),
+            });
+        }
+    }
+
+    let inheritance = template_inheritance(db, project, file);
+    BlockCoverage {
+        ancestors: inheritance
+            .ancestors(db)
+            .iter()
+            .map(|origin| origin.file(db))
+            .collect(),
+        blocks,
+        end: inheritance.end(db),
+    }
+}
+
 /// Descendant templates that define `name`, discovered through definite reverse extends edges.
 ///
 /// A physical descendant contributes an edge only when its feasible origins agree on the same
@@ -379,6 +444,46 @@ pub fn block_overrides(db: &dyn Db, project: Project, file: File, name: &str) ->
     overrides
 }
 
+/// Templates that inherit from `file`, directly or transitively, in breadth-first order.
+///
+/// Uses the same definite reverse extends edges as [`block_overrides`].
+pub fn descendant_templates(db: &dyn Db, project: Project, file: File) -> Vec<File> {
+    let resolution = template_resolution(db, project);
+    if resolution.template_names_for_file(db, file).is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidate_files = Vec::new();
+    for origin in resolution.origins(db) {
+        let candidate = origin.file(db);
+        if !candidate_files.contains(&candidate) {
+            candidate_files.push(candidate);
+        }
+    }
+
+    let mut queue = VecDeque::from([file]);
+    let mut visited_files = FxHashSet::from_iter([file]);
+    let mut descendants = Vec::new();
+
+    while let Some(target) = queue.pop_front() {
+        for descendant in &candidate_files {
+            if visited_files.contains(descendant) {
+                continue;
+            }
+            let inheritance = template_inheritance(db, project, *descendant);
+            let Some(parent) = inheritance.ancestors(db).first() else {
+                continue;
+            };
+            if parent.file(db) == target && visited_files.insert(*descendant) {
+                descendants.push(*descendant);
+                queue.push_back(*descendant);
+            }
+        }
+    }
+
+    descendants
+}
+
 enum BlockSiteLookup {
     Found(BlockSite),
     Absent,
@@ -416,7 +521,7 @@ pub fn template_symbols<'db>(
 }
 
 #[salsa::tracked(returns(ref))]
-fn template_symbols_in_scope<'db>(
+pub(crate) fn template_symbols_in_scope<'db>(
     db: &'db dyn Db,
     file: File,
     nodelist: NodeList<'db>,
@@ -1,15 +1,31 @@
+mod admin;
+mod analyze;
 mod db;
 mod diagnostics;
 mod errors;
 mod filters;
+mod fixes;
+mod html;
 mod inheritance;
+mod limits;
+mod lint;
+mod metrics;
 mod offset;
 mod references;
 mod scoping;
 mod structure;
+mod suggest;
 mod tags;
+mod trace;
+mod translation;
 mod validation;
+mod variables;
 
+pub use admin::AdminOverride;
+pub use admin::admin_override_for_file;
+pub use analyze::AnalysisDiagnostic;
+pub use analyze::AnalysisResult;
+pub use analyze::analyze;
 pub use db::Db;
 pub use db::ValidationErrorAccumulator;
 pub use db::scoped_template_libraries_for_file;
@@ -17,27 +33,46 @@ pub use diagnostics::TemplateDiagnostics;
 pub use diagnostics::collect_template_diagnostics;
 pub use djls_project::TagArgument;
 pub use djls_project::TagArgumentKind;
+use djls_conf::Dialect;
 use djls_source::File;
+use djls_templates::FilePragma;
 use djls_templates::TemplateParseResult;
 use djls_templates::parse_template;
 pub use errors::ValidationError;
 pub use filters::FilterAritySpecs;
 pub use filters::LibraryFilterSpecs;
 pub use filters::library_filter_specs;
+pub use fixes::Fix;
+pub use fixes::apply_fixes;
+pub use inheritance::BlockCoverage;
 pub use inheritance::BlockDef;
 pub use inheritance::BlockSite;
 pub use inheritance::ChainEnd;
+pub use inheritance::CoveredBlock;
 pub use inheritance::ExtendsTarget;
 pub use inheritance::PartialDef;
 pub use inheritance::TemplateInheritance;
 pub use inheritance::TemplateSymbols;
 pub use inheritance::ancestor_blocks;
+pub use inheritance::block_coverage;
 pub use inheritance::block_definition_at;
 pub use inheritance::block_overrides;
+pub use inheritance::descendant_templates;
 pub use inheritance::inherited_blocks;
 pub use inheritance::parent_block;
 pub use inheritance::template_inheritance;
 pub use inheritance::template_symbols;
+pub use limits::exceeds_template_limits;
+pub use lint::BlockVisitor;
+pub use lint::LintContext;
+pub use lint::LintRule;
+pub use lint::LintRules;
+pub use lint::LintTag;
+pub use lint::LintTemplate;
+pub use lint::LintVariable;
+pub use lint::NodeVisitor;
+pub use metrics::TemplateMetrics;
+pub use metrics::template_metrics;
 pub use offset::SemanticOffsetContext;
 pub use references::TemplateLibraryReferenceInFile;
 pub use references::TemplateLibraryReferencesInFile;
@@ -50,13 +85,16 @@ pub use references::resolve_reference_for_file;
 pub use references::resolve_reference_origins;
 pub use references::template_library_references_in_file;
 pub use references::template_references_in_file;
+use salsa::Accumulator;
 pub use scoping::effective_symbol_candidate_at;
 pub use structure::BlockRole;
 pub use structure::GrammarOpeningDefinition;
+pub use structure::LinkedBlockNames;
 pub use structure::OpaqueRegions;
 pub use structure::OutlineItem;
 pub use structure::OutlineKind;
 pub use structure::RegionId;
+pub use structure::Regions;
 pub use structure::SemanticGrammarVocabulary;
 pub use structure::TemplateFold;
 pub use structure::TemplateFoldKind;
@@ -67,6 +105,7 @@ pub use structure::build_template_folds;
 pub use structure::build_template_outline_for_file;
 pub use structure::build_template_tree_for_file;
 pub use structure::compute_opaque_regions;
+pub use structure::linked_block_names_at;
 pub use structure::semantic_grammar_vocabulary;
 pub use tags::EndTag;
 pub use tags::IntermediateTag;
@@ -75,11 +114,26 @@ pub use tags::TagRole;
 pub use tags::TagSpec;
 pub use tags::TagSpecs;
 pub use tags::builtin_tag_specs;
+pub use tags::builtin_tag_specs_for;
 pub use tags::library_tag_specs;
 pub use tags::tag_spec_at;
 pub use tags::tag_specs_at;
 pub use tags::tag_specs_for_file;
+pub use trace::TemplateTrace;
+pub use trace::TraceSegment;
+pub use trace::trace_template;
+pub use translation::UntranslatedText;
+pub use translation::untranslated_text;
+pub use variables::TemplateVariableNames;
+pub use variables::VariableOccurrences;
+pub use variables::enclosing_loops_at;
+pub use variables::selection_variables;
+pub use variables::template_variable_names_at;
+pub use variables::variable_occurrences_at;
 
+use crate::limits::template_limit_error;
+use crate::lint::run_lint_rules;
+use crate::metrics::template_complexity_errors;
 use crate::scoping::template_analysis_projection_for_file;
 use crate::validation::TemplateValidator;
 
@@ -90,10 +144,43 @@ use crate::validation::TemplateValidator;
 /// need Django meaning for a file.
 #[salsa::tracked(returns(copy))]
 pub fn validate_template_file(db: &dyn Db, file: File) {
+    if let Some(error) = template_limit_error(db, file) {
+        ValidationErrorAccumulator(error.clone()).accumulate(db);
+        return;
+    }
     let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
         return;
     };
+    if is_foreign_dialect(db, file) {
+        return;
+    }
 
     let projection = template_analysis_projection_for_file(db, file, nodelist);
-    TemplateValidator::new(db, projection).validate();
+    TemplateValidator::new(db, file, nodelist, projection).validate();
+    run_lint_rules(db, file, projection.tree(db));
+    for error in template_complexity_errors(db, file) {
+        ValidationErrorAccumulator(error).accumulate(db);
+    }
+}
+
+/// Whether `file` is written in a template language other than Django's.
+///
+/// A `{# djls: dialect=... #}` pragma decides for its file; otherwise the project's
+/// `[dialects]` settings decide by directory.
+#[must_use]
+pub fn is_foreign_dialect(db: &dyn Db, file: File) -> bool {
+    if let TemplateParseResult::Parsed(nodelist) = parse_template(db, file)
+        && let Some(dialect) = nodelist.pragma(db).as_ref().and_then(FilePragma::dialect)
+    {
+        return dialect != Dialect::Django;
+    }
+
+    let Some(project) = db.project() else {
+        return false;
+    };
+    file.path(db)
+        .strip_prefix(project.root(db))
+        .ok()
+        .and_then(|relative| project.dialects(db).dialect_for(relative))
+        .is_some_and(|dialect| dialect != Dialect::Django)
 }
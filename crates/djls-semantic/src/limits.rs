@@ -0,0 +1,112 @@
+//! Guardrails that keep pathological templates out of validation.
+//!
+//! Generated templates and vendored minified files can be large enough that validating them
+//! stalls the server. A template over any of the configured
+//! [`DiagnosticLimits`](djls_conf::DiagnosticLimits) is not validated; it gets a single
+//! informational S144 diagnostic in place of its syntax and semantic diagnostics.
+
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::Node;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use rustc_hash::FxHashSet;
+
+use crate::db::Db;
+use crate::errors::ValidationError;
+
+/// Whether `file` exceeds one of its configured limits and so is not validated.
+#[must_use]
+pub fn exceeds_template_limits(db: &dyn Db, file: File) -> bool {
+    template_limit_error(db, file).is_some()
+}
+
+/// The S144 diagnostic for the first limit `file` exceeds, if any.
+///
+/// The file size is checked before parsing, so an oversized file is never parsed here.
+#[salsa::tracked(returns(ref))]
+pub(crate) fn template_limit_error(db: &dyn Db, file: File) -> Option<ValidationError> {
+    let source = file.try_source(db).ok()?;
+    let limits = db.diagnostics_config(file).limits();
+    let first_line = source.as_str().find('\n').unwrap_or(source.as_str().len());
+    let span = Span::saturating_from_bounds_usize(0, first_line);
+
+    let exceeded = |measure: &str, actual: usize, limit: usize| {
+        (actual > limit).then(|| ValidationError::TemplateLimitExceeded {
+            measure: measure.to_string(),
+            actual,
+            limit,
+            span,
+        })
+    };
+
+    if let Some(error) = exceeded("bytes", source.as_str().len(), limits.max_file_size()) {
+        return Some(error);
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+    let nodes = nodelist.nodelist(db);
+    exceeded("nodes", nodes.len(), limits.max_nodes()).or_else(|| {
+        exceeded(
+            "levels of nesting",
+            nesting_depth(nodes),
+            limits.max_nesting_depth(),
+        )
+    })
+}
+
+/// The deepest nesting of block tags in `nodes`.
+///
+/// Tag specs are not consulted, so this stays cheap on files too large to analyze: a tag opens
+/// a block when the template also contains its `end` tag, and an `end` tag closes the nearest
+/// open block of its name along with any left unclosed inside it.
+fn nesting_depth(nodes: &[Node]) -> usize {
+    let closed: FxHashSet<&str> = nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Tag { name, .. } => name.strip_prefix("end"),
+            _ => None,
+        })
+        .collect();
+
+    let mut open: Vec<&str> = Vec::new();
+    let mut deepest = 0;
+    for node in nodes {
+        let Node::Tag { name, .. } = node else {
+            continue;
+        };
+        if let Some(opener) = name.strip_prefix("end") {
+            if let Some(index) = open.iter().rposition(|open| *open == opener) {
+                open.truncate(index);
+            }
+        } else if closed.contains(name.as_str()) {
+            open.push(name);
+            deepest = deepest.max(open.len());
+        }
+    }
+    deepest
+}
+
+#[cfg(test)]
+mod tests {
+    use djls_templates::parse_template_impl;
+
+    use super::*;
+
+    #[test]
+    fn nesting_depth_counts_blocks_closed_by_end_tags() {
+        let (nodes, _) = parse_template_impl(
+            "{% block a %}{% if x %}{% for y in z %}{% cycle 'a' 'b' %}{% endfor %}{% endif %}\
+             {% endblock %}{% if w %}{% endif %}",
+        );
+        assert_eq!(nesting_depth(&nodes), 3);
+    }
+
+    #[test]
+    fn nesting_depth_closes_blocks_left_open_inside_an_end_tag() {
+        let (nodes, _) =
+            parse_template_impl("{% block a %}{% if x %}{% endblock %}{% if y %}{% endif %}");
+        assert_eq!(nesting_depth(&nodes), 2);
+    }
+}
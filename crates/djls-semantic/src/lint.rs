@@ -0,0 +1,387 @@
+//! Custom lint rules for downstream crates.
+//!
+//! A [`LintRule`] walks each validated Template with a [`NodeVisitor`] or [`BlockVisitor`] and
+//! reports findings under its own diagnostic codes. Rules are registered in a [`LintRules`] set
+//! that the database hands out through [`Db::lint_rules`](crate::Db::lint_rules), or through
+//! [`Db::untracked_lint_rules`](crate::Db::untracked_lint_rules) for rules with side effects;
+//! their diagnostics flow through severity configuration, `djls check`, and the language server
+//! like built-in ones.
+//!
+//! Custom codes should use a prefix of their own rather than `S`, `A`, or `X`, which are reserved
+//! for built-in checks.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::LazyLock;
+
+use camino::Utf8Path;
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::Filter;
+use djls_templates::TagBit;
+use djls_templates::TagDelimiter;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::scoping::template_analysis_projection_for_file;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::structure::TemplateTree;
+
+/// A template check supplied from outside this crate.
+pub trait LintRule: Send + Sync {
+    /// Short identifier for the rule, used in logs.
+    fn name(&self) -> &str;
+
+    /// Check one Template, reporting findings through `cx`.
+    fn check(&self, template: &LintTemplate<'_>, cx: &mut LintContext);
+}
+
+/// Callbacks for each node of a Template, in source order.
+///
+/// Intermediate tags such as `{% else %}` are visited as tags; end tags are not.
+/// Bodies of opaque tags like `{% verbatim %}` are skipped.
+pub trait NodeVisitor {
+    fn visit_tag(&mut self, _cx: &mut LintContext, _tag: LintTag<'_>) {}
+
+    fn visit_variable(&mut self, _cx: &mut LintContext, _variable: LintVariable<'_>) {}
+
+    fn visit_text(&mut self, _cx: &mut LintContext, _span: Span) {}
+
+    fn visit_comment(&mut self, _cx: &mut LintContext, _span: Span) {}
+}
+
+/// Callbacks for the block structure of a Template.
+///
+/// `enter_block` and `exit_block` receive the opening tag, so a visitor can keep its own stack
+/// of enclosing blocks. Intermediate tags are passed to `visit_segment` between the two.
+pub trait BlockVisitor {
+    fn enter_block(&mut self, _cx: &mut LintContext, _block: LintTag<'_>) {}
+
+    fn visit_segment(&mut self, _cx: &mut LintContext, _segment: LintTag<'_>) {}
+
+    fn exit_block(&mut self, _cx: &mut LintContext, _block: LintTag<'_>) {}
+}
+
+impl NodeVisitor for () {}
+
+impl BlockVisitor for () {}
+
+/// A tag occurrence as seen by a [`LintRule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintTag<'a> {
+    pub name: &'a str,
+    pub name_span: Span,
+    pub bits: &'a [TagBit],
+    /// Span of the whole tag, delimiters included.
+    pub span: Span,
+}
+
+/// A variable occurrence as seen by a [`LintRule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintVariable<'a> {
+    pub var: &'a str,
+    pub var_span: Span,
+    pub filters: &'a [Filter],
+    /// Span of the whole variable, delimiters included.
+    pub span: Span,
+}
+
+/// The Template a [`LintRule`] is checking.
+pub struct LintTemplate<'a> {
+    path: &'a Utf8Path,
+    source: &'a str,
+    regions: &'a Regions,
+    root: RegionId,
+}
+
+impl<'a> LintTemplate<'a> {
+    #[must_use]
+    pub fn path(&self) -> &'a Utf8Path {
+        self.path
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Source text covered by `span`, or an empty string if it falls outside the Template.
+    #[must_use]
+    pub fn text(&self, span: Span) -> &'a str {
+        self.source
+            .get(span.start_usize()..span.end_usize())
+            .unwrap_or_default()
+    }
+
+    pub fn walk_nodes(&self, cx: &mut LintContext, visitor: &mut dyn NodeVisitor) {
+        self.walk_region(self.root, cx, visitor, &mut ());
+    }
+
+    pub fn walk_blocks(&self, cx: &mut LintContext, visitor: &mut dyn BlockVisitor) {
+        self.walk_region(self.root, cx, &mut (), visitor);
+    }
+
+    fn walk_region(
+        &self,
+        region: RegionId,
+        cx: &mut LintContext,
+        nodes: &mut dyn NodeVisitor,
+        blocks: &mut dyn BlockVisitor,
+    ) {
+        for node in self.regions.get(region).nodes() {
+            self.walk_node(node, cx, nodes, blocks);
+        }
+    }
+
+    fn walk_node(
+        &self,
+        node: &'a TemplateNode,
+        cx: &mut LintContext,
+        nodes: &mut dyn NodeVisitor,
+        blocks: &mut dyn BlockVisitor,
+    ) {
+        match node {
+            TemplateNode::Block {
+                tag,
+                name_span,
+                bits,
+                full_span,
+                body,
+                role: BlockRole::Opener,
+            } => {
+                let opener = LintTag {
+                    name: tag,
+                    name_span: *name_span,
+                    bits,
+                    span: *full_span,
+                };
+                nodes.visit_tag(cx, opener);
+                blocks.enter_block(cx, opener);
+                self.walk_block_body(*body, *full_span, cx, nodes, blocks);
+                blocks.exit_block(cx, opener);
+            }
+            TemplateNode::Block {
+                tag,
+                name_span,
+                bits,
+                full_span,
+                body,
+                role: BlockRole::Segment,
+            } => {
+                let segment = LintTag {
+                    name: tag,
+                    name_span: *name_span,
+                    bits,
+                    span: *full_span,
+                };
+                nodes.visit_tag(cx, segment);
+                blocks.visit_segment(cx, segment);
+                self.walk_region(*body, cx, nodes, blocks);
+            }
+            TemplateNode::StandaloneTag {
+                tag,
+                name_span,
+                bits,
+                full_span,
+            } => nodes.visit_tag(
+                cx,
+                LintTag {
+                    name: tag,
+                    name_span: *name_span,
+                    bits,
+                    span: *full_span,
+                },
+            ),
+            TemplateNode::Opaque {
+                tag,
+                name_span,
+                bits,
+                full_span,
+                body_span,
+            } => nodes.visit_tag(
+                cx,
+                LintTag {
+                    name: tag,
+                    name_span: *name_span,
+                    bits,
+                    span: Span::saturating_from_bounds_usize(
+                        full_span.start_usize(),
+                        body_span.start_usize(),
+                    ),
+                },
+            ),
+            TemplateNode::Variable {
+                var,
+                var_span,
+                filters,
+                span,
+            } => nodes.visit_variable(
+                cx,
+                LintVariable {
+                    var,
+                    var_span: *var_span,
+                    filters,
+                    span: with_delimiters(*span),
+                },
+            ),
+            TemplateNode::Text { span } => nodes.visit_text(cx, *span),
+            TemplateNode::Comment { span } => nodes.visit_comment(cx, with_delimiters(*span)),
+            TemplateNode::Error { .. } => {}
+        }
+    }
+
+    fn walk_block_body(
+        &self,
+        body: RegionId,
+        opener_span: Span,
+        cx: &mut LintContext,
+        nodes: &mut dyn NodeVisitor,
+        blocks: &mut dyn BlockVisitor,
+    ) {
+        for node in self.regions.get(body).nodes() {
+            match node {
+                TemplateNode::Block {
+                    body: segment_body,
+                    full_span,
+                    role: BlockRole::Segment,
+                    ..
+                } if *full_span == opener_span => {
+                    self.walk_region(*segment_body, cx, nodes, blocks);
+                }
+                TemplateNode::Block { .. }
+                | TemplateNode::Opaque { .. }
+                | TemplateNode::StandaloneTag { .. }
+                | TemplateNode::Variable { .. }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Text { .. }
+                | TemplateNode::Error { .. } => self.walk_node(node, cx, nodes, blocks),
+            }
+        }
+    }
+}
+
+/// Variable and comment nodes store their inner span; rules see the whole node, like tags.
+fn with_delimiters(span: Span) -> Span {
+    span.expand(TagDelimiter::LENGTH_U32, TagDelimiter::LENGTH_U32)
+}
+
+/// Collects the diagnostics a [`LintRule`] reports for one Template.
+pub struct LintContext {
+    rule: String,
+    errors: Vec<ValidationError>,
+}
+
+impl LintContext {
+    fn new(rule: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Report a diagnostic at `span` under `code`.
+    pub fn report(&mut self, code: &'static str, message: impl Into<String>, span: Span) {
+        self.errors.push(ValidationError::LintRuleViolation {
+            rule: self.rule.clone(),
+            code,
+            message: message.into(),
+            span,
+        });
+    }
+}
+
+/// The set of [`LintRule`]s run after built-in validation.
+///
+/// The set is read outside Salsa's dependency tracking, so it must be fixed before the
+/// database runs its first query.
+#[derive(Clone, Default)]
+pub struct LintRules(Vec<Arc<dyn LintRule>>);
+
+impl LintRules {
+    #[must_use]
+    pub fn empty_ref() -> &'static Self {
+        static EMPTY: LazyLock<LintRules> = LazyLock::new(LintRules::new);
+        &EMPTY
+    }
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn register(&mut self, rule: impl LintRule + 'static) {
+        self.0.push(Arc::new(rule));
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.0.iter().map(Arc::as_ref)
+    }
+}
+
+impl fmt::Debug for LintRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(LintRule::name))
+            .finish()
+    }
+}
+
+pub(crate) fn run_lint_rules(db: &dyn Db, file: File, tree: TemplateTree<'_>) {
+    for error in check_with_rules(db, file, tree, db.lint_rules()) {
+        ValidationErrorAccumulator(error).accumulate(db);
+    }
+}
+
+/// Run [`Db::untracked_lint_rules`](crate::Db::untracked_lint_rules) over `file`.
+///
+/// Called when diagnostics are collected rather than from `validate_template_file`, so each
+/// call asks the rules again.
+pub(crate) fn untracked_lint_errors(db: &dyn Db, file: File) -> Vec<ValidationError> {
+    let rules = db.untracked_lint_rules();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return Vec::new();
+    };
+    let projection = template_analysis_projection_for_file(db, file, nodelist);
+    check_with_rules(db, file, projection.tree(db), rules)
+}
+
+fn check_with_rules(
+    db: &dyn Db,
+    file: File,
+    tree: TemplateTree<'_>,
+    rules: &LintRules,
+) -> Vec<ValidationError> {
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    let template = LintTemplate {
+        path: file.path(db),
+        source: source.as_str(),
+        regions: tree.regions(db),
+        root: tree.root(db),
+    };
+    let mut errors = Vec::new();
+    for rule in rules.iter() {
+        let mut cx = LintContext::new(rule.name());
+        rule.check(&template, &mut cx);
+        errors.extend(cx.errors);
+    }
+    errors
+}
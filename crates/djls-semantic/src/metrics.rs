@@ -0,0 +1,142 @@
+//! Size and branching measurements of a template.
+//!
+//! [`template_metrics`] powers `djls stats` and the opt-in S146 hint, which reports a template
+//! over any of the configured [`ComplexityLimits`](djls_conf::ComplexityLimits) as a candidate
+//! for splitting into includes.
+
+use djls_conf::DiagnosticSeverity;
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use serde::Serialize;
+
+use crate::db::Db;
+use crate::errors::ValidationError;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::structure::build_template_tree_for_file;
+use crate::variables::template_context_variables;
+
+/// Tags that open a block whose body may or may not render, or render more than once.
+const BRANCH_TAGS: &[&str] = &["if", "for", "ifchanged"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct TemplateMetrics {
+    /// Parsed nodes: tags, variables, comments, and text runs.
+    pub nodes: usize,
+    /// Deepest nesting of block tags.
+    pub max_depth: usize,
+    /// `{% include %}` tags.
+    pub includes: usize,
+    /// Distinct names read from the context the template is rendered with.
+    pub variables: usize,
+    /// `{% if %}`, `{% elif %}`, `{% for %}`, and `{% ifchanged %}` tags, each a point where
+    /// rendering can take a different path.
+    pub branches: usize,
+}
+
+/// Measures `file`. A file that is not a template measures zero throughout.
+#[salsa::tracked(returns(copy))]
+pub fn template_metrics(db: &dyn Db, file: File) -> TemplateMetrics {
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return TemplateMetrics::default();
+    };
+    let tree = build_template_tree_for_file(db, file, nodelist);
+
+    let mut metrics = TemplateMetrics {
+        nodes: nodelist.nodelist(db).len(),
+        variables: template_context_variables(db, file).len(),
+        ..TemplateMetrics::default()
+    };
+    measure_region(tree.regions(db), tree.root(db), 0, &mut metrics);
+    metrics
+}
+
+fn measure_region(
+    regions: &Regions,
+    region: RegionId,
+    depth: usize,
+    metrics: &mut TemplateMetrics,
+) {
+    for node in regions.get(region).nodes() {
+        match node {
+            TemplateNode::Block {
+                tag,
+                body,
+                role: BlockRole::Opener,
+                ..
+            } => {
+                metrics.max_depth = metrics.max_depth.max(depth + 1);
+                if BRANCH_TAGS.contains(&tag.as_str()) {
+                    metrics.branches += 1;
+                }
+                for segment in regions.get(*body).nodes() {
+                    if let TemplateNode::Block { tag, body, .. } = segment {
+                        if tag == "elif" {
+                            metrics.branches += 1;
+                        }
+                        measure_region(regions, *body, depth + 1, metrics);
+                    }
+                }
+            }
+            TemplateNode::StandaloneTag { tag, .. } if tag == "include" => {
+                metrics.includes += 1;
+            }
+            TemplateNode::Block {
+                role: BlockRole::Segment,
+                ..
+            }
+            | TemplateNode::StandaloneTag { .. }
+            | TemplateNode::Opaque { .. }
+            | TemplateNode::Variable { .. }
+            | TemplateNode::Comment { .. }
+            | TemplateNode::Text { .. }
+            | TemplateNode::Error { .. } => {}
+        }
+    }
+}
+
+/// The S146 diagnostics for each complexity limit `file` exceeds, reported on its first line.
+///
+/// S146 is off by default, so templates are only measured once it is given a severity.
+pub(crate) fn template_complexity_errors(db: &dyn Db, file: File) -> Vec<ValidationError> {
+    let config = db.diagnostics_config(file);
+    if config.get_severity("S146") == DiagnosticSeverity::Off {
+        return Vec::new();
+    }
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    let limits = config.complexity();
+    let metrics = template_metrics(db, file);
+    let first_line = source.as_str().find('\n').unwrap_or(source.as_str().len());
+    let span = Span::saturating_from_bounds_usize(0, first_line);
+
+    [
+        ("nodes", metrics.nodes, limits.max_nodes()),
+        (
+            "levels of nesting",
+            metrics.max_depth,
+            limits.max_nesting_depth(),
+        ),
+        ("includes", metrics.includes, limits.max_includes()),
+        (
+            "context variables",
+            metrics.variables,
+            limits.max_variables(),
+        ),
+        ("branches", metrics.branches, limits.max_branches()),
+    ]
+    .into_iter()
+    .filter(|(_, actual, limit)| actual > limit)
+    .map(|(measure, actual, limit)| ValidationError::TemplateTooComplex {
+        measure: measure.to_string(),
+        actual,
+        limit,
+        span,
+    })
+    .collect()
+}
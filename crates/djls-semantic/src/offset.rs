@@ -2,7 +2,9 @@ use djls_project::TemplateName;
 use djls_source::File;
 use djls_source::Offset;
 use djls_source::Span;
+use djls_templates::TagBit;
 use djls_templates::TemplateParseResult;
+use djls_templates::TemplateString;
 use djls_templates::parse_template;
 
 use crate::TagSpec;
@@ -40,6 +42,10 @@ pub enum SemanticOffsetContext<'db> {
         name: String,
         span: Span,
     },
+    Route {
+        name: String,
+        span: Span,
+    },
     Tag {
         name: String,
         loaded_libraries: Vec<String>,
@@ -187,6 +193,19 @@ impl<'db> SemanticOffsetContext<'db> {
                     name: name.as_str().to_string(),
                     span: name.span,
                 })
+        } else if spec.and_then(TagSpec::role) == Some(TagRole::RouteReference) {
+            // Only a quoted name is a route; an unquoted first bit is a variable holding one.
+            match tag.bits.first().map(TagBit::template_string) {
+                Some(TemplateString::Quoted { value, span }) if span.contains(offset) => {
+                    Self::Route {
+                        name: value.to_string(),
+                        span,
+                    }
+                }
+                Some(TemplateString::Quoted { .. } | TemplateString::Unquoted(_)) | None => {
+                    Self::None
+                }
+            }
         } else {
             spec.and_then(|spec| LiteralTemplateReference::from_spec(spec, tag.bits))
                 .filter(|reference| reference.bit_span.contains(offset))
@@ -7,6 +7,7 @@ use djls_project::EffectiveDefinitionLibrary;
 use djls_project::FilterArity;
 use djls_project::LibraryName;
 use djls_project::MissingTemplateLibraryLookup;
+use djls_project::ScopedTemplateLibraries;
 use djls_project::SymbolDefinition;
 use djls_project::TemplateLibraryId;
 use djls_project::TemplateSymbolAvailability;
@@ -17,6 +18,7 @@ use djls_source::Span;
 use djls_templates::Filter;
 use djls_templates::Node;
 use djls_templates::NodeList;
+use rustc_hash::FxHashSet;
 use salsa::Accumulator;
 
 use crate::ValidationErrorAccumulator;
@@ -115,6 +117,27 @@ pub(crate) struct LoaderArgumentFact {
     pub(crate) availability: MissingTemplateLibraryLookup,
 }
 
+/// A symbol named in a selective `{% load ... from ... %}` that its library does not define.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MissingLoadSymbolFact {
+    pub(crate) symbol: LoadArgument,
+    pub(crate) library: String,
+    /// Text to delete: the symbol and its separating whitespace, or the whole tag when
+    /// every requested symbol is missing.
+    pub(crate) removal: Span,
+}
+
+/// A library, or a symbol imported from one, that no later tag or filter in the template uses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UnusedLoadFact {
+    pub(crate) argument: LoadArgument,
+    /// The library a selective load imports `argument` from; `None` for a full load.
+    pub(crate) library: Option<String>,
+    /// Text to delete: the argument and its separating whitespace, or the whole tag when
+    /// nothing it loads is used.
+    pub(crate) removal: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct ScopedTagFact {
     pub(crate) spec: Option<TagSpec>,
@@ -122,6 +145,8 @@ pub(crate) struct ScopedTagFact {
     pub(crate) structure_accepts_spelling: bool,
     pub(crate) unknown_load_can_shadow: bool,
     pub(crate) loader_arguments: Vec<LoaderArgumentFact>,
+    pub(crate) missing_load_symbols: Vec<MissingLoadSymbolFact>,
+    pub(crate) unused_loads: Vec<UnusedLoadFact>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -234,6 +259,7 @@ pub(crate) fn template_analysis_projection_for_file_in_scope<'db>(
             continue;
         }
 
+        let used_names = used_symbol_names(&active_nodes);
         let mut tag_facts = BTreeMap::new();
         let mut filter_facts = BTreeMap::new();
         let mut tag_context_cache = ContextualFactCache::default();
@@ -269,23 +295,33 @@ pub(crate) fn template_analysis_projection_for_file_in_scope<'db>(
                                 scoped_libraries,
                             ),
                         });
-                    let loader_arguments =
-                        if spec.and_then(TagSpec::role) == Some(TagRole::TemplateLibraryLoader) {
-                            LoadKind::from_loader_bits(tag.bits).map_or_else(Vec::new, |kind| {
-                                kind.into_library_arguments()
-                                    .into_iter()
-                                    .filter_map(|argument| {
-                                        let name = LibraryName::parse(argument.as_str()).ok()?;
-                                        Some(LoaderArgumentFact {
-                                            availability: scoped_libraries.missing_library(&name),
-                                            argument,
-                                        })
-                                    })
-                                    .collect()
+                    let load_kind = if spec.and_then(TagSpec::role)
+                        == Some(TagRole::TemplateLibraryLoader)
+                    {
+                        LoadKind::from_loader_bits(tag.bits)
+                    } else {
+                        None
+                    };
+                    let missing_load_symbols = load_kind.as_ref().map_or_else(Vec::new, |kind| {
+                        selective_import_missing_symbols(scoped_libraries, kind, tag.full_span)
+                    });
+                    let unused_loads = load_kind.as_ref().map_or_else(Vec::new, |kind| {
+                        let end = tag.full_span.end();
+                        let later = &used_names[used_names.partition_point(|(at, _)| *at < end)..];
+                        unused_load_arguments(scoped_libraries, kind, tag.full_span, later)
+                    });
+                    let loader_arguments = load_kind.map_or_else(Vec::new, |kind| {
+                        kind.into_library_arguments()
+                            .into_iter()
+                            .filter_map(|argument| {
+                                let name = LibraryName::parse(argument.as_str()).ok()?;
+                                Some(LoaderArgumentFact {
+                                    availability: scoped_libraries.missing_library(&name),
+                                    argument,
+                                })
                             })
-                        } else {
-                            Vec::new()
-                        };
+                            .collect()
+                    });
                     tag_facts.insert(
                         TagOccurrenceKey::from_name_span(tag.name_span),
                         ScopedTagFact {
@@ -301,6 +337,8 @@ pub(crate) fn template_analysis_projection_for_file_in_scope<'db>(
                             ),
                             unknown_load_can_shadow: contextual_fact.unknown_load_can_shadow,
                             loader_arguments,
+                            missing_load_symbols,
+                            unused_loads,
                         },
                     );
                 }
@@ -377,6 +415,179 @@ pub(crate) fn template_analysis_projection_for_file_in_scope<'db>(
     panic!("template load discovery did not converge within the number of template tags")
 }
 
+/// Symbols requested by a selective import that its library definitely lacks.
+///
+/// Only a library every settings case agrees on, with observed symbol names, can
+/// prove a symbol missing; anything less certain reports nothing.
+fn selective_import_missing_symbols(
+    scoped_libraries: ScopedTemplateLibraries<'_>,
+    kind: &LoadKind,
+    tag_span: Span,
+) -> Vec<MissingLoadSymbolFact> {
+    let LoadKind::SelectiveImport { symbols, library } = kind else {
+        return Vec::new();
+    };
+    let Some(resolved) = scoped_libraries.loadable_library_str(library.as_str()).found() else {
+        return Vec::new();
+    };
+    if resolved.symbols_are_unobserved() {
+        return Vec::new();
+    }
+
+    let missing = symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, symbol)| {
+            resolved
+                .symbol(TemplateSymbolKind::Tag, symbol.as_str())
+                .is_none()
+                && resolved
+                    .symbol(TemplateSymbolKind::Filter, symbol.as_str())
+                    .is_none()
+        })
+        .collect::<Vec<_>>();
+    let removes_every_symbol = missing.len() == symbols.len();
+
+    missing
+        .into_iter()
+        .map(|(index, symbol)| {
+            let removal = if removes_every_symbol {
+                tag_span
+            } else {
+                load_argument_removal(symbols, index)
+            };
+            MissingLoadSymbolFact {
+                symbol: symbol.clone(),
+                library: library.as_str().to_string(),
+                removal,
+            }
+        })
+        .collect()
+}
+
+/// Names that may refer to a tag or filter, each with the offset it appears at, in source order.
+///
+/// Besides tag names and the filters of variables, every word in a tag's arguments counts, so
+/// filters in `{% if items|length %}` or `{% filter lower %}` keep their library in use.
+fn used_symbol_names<'a>(nodes: &[ActiveTemplateNode<'a>]) -> Vec<(u32, &'a str)> {
+    let mut names = Vec::new();
+    for node in nodes {
+        match node {
+            ActiveTemplateNode::Tag(tag) => {
+                let at = tag.span.start();
+                names.push((at, tag.tag));
+                for bit in tag.bits {
+                    names.extend(
+                        bit.as_str()
+                            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            .filter(|word| !word.is_empty())
+                            .map(|word| (at, word)),
+                    );
+                }
+            }
+            ActiveTemplateNode::Variable(variable) => {
+                let at = variable.span.start();
+                names.extend(
+                    variable
+                        .filters
+                        .iter()
+                        .map(|filter| (at, filter.name.as_str())),
+                );
+            }
+        }
+    }
+    names
+}
+
+/// Libraries, or selectively imported symbols, that no tag or filter after the load uses.
+///
+/// Only a library every settings case agrees on, with observed symbol names, can prove a load
+/// unused; anything less certain reports nothing. Symbols the library lacks are left to
+/// [`selective_import_missing_symbols`].
+fn unused_load_arguments(
+    scoped_libraries: ScopedTemplateLibraries<'_>,
+    kind: &LoadKind,
+    tag_span: Span,
+    later: &[(u32, &str)],
+) -> Vec<UnusedLoadFact> {
+    let used: FxHashSet<&str> = later.iter().map(|(_, name)| *name).collect();
+    let (arguments, library, unused) = match kind {
+        LoadKind::FullLoad { libraries } => {
+            let unused = libraries
+                .iter()
+                .enumerate()
+                .filter(|(_, library)| {
+                    scoped_libraries
+                        .loadable_library_str(library.as_str())
+                        .found()
+                        .is_some_and(|resolved| {
+                            !resolved.symbols_are_unobserved()
+                                && !resolved
+                                    .symbols()
+                                    .iter()
+                                    .any(|symbol| used.contains(symbol.name()))
+                        })
+                })
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            (libraries, None, unused)
+        }
+        LoadKind::SelectiveImport { symbols, library } => {
+            let Some(resolved) = scoped_libraries.loadable_library_str(library.as_str()).found()
+            else {
+                return Vec::new();
+            };
+            if resolved.symbols_are_unobserved() {
+                return Vec::new();
+            }
+            let unused = symbols
+                .iter()
+                .enumerate()
+                .filter(|(_, symbol)| {
+                    let name = symbol.as_str();
+                    (resolved.symbol(TemplateSymbolKind::Tag, name).is_some()
+                        || resolved.symbol(TemplateSymbolKind::Filter, name).is_some())
+                        && !used.contains(name)
+                })
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            (symbols, Some(library.as_str().to_string()), unused)
+        }
+    };
+    let removes_every_argument = unused.len() == arguments.len();
+
+    unused
+        .into_iter()
+        .map(|index| UnusedLoadFact {
+            argument: arguments[index].clone(),
+            library: library.clone(),
+            removal: if removes_every_argument {
+                tag_span
+            } else {
+                load_argument_removal(arguments, index)
+            },
+        })
+        .collect()
+}
+
+/// Span covering `arguments[index]` and the whitespace that separates it from a neighbour.
+///
+/// The first argument takes the whitespace after it; any other takes the whitespace before it,
+/// so `from` keeps its leading space.
+fn load_argument_removal(arguments: &[LoadArgument], index: usize) -> Span {
+    let span = arguments[index].span();
+    let neighbour = if index == 0 {
+        arguments.get(1).map(|next| {
+            Span::saturating_from_bounds_usize(span.start_usize(), next.span().start_usize())
+        })
+    } else {
+        arguments.get(index - 1).map(|previous| {
+            Span::saturating_from_bounds_usize(previous.span().end_usize(), span.end_usize())
+        })
+    };
+    neighbour.unwrap_or(span)
+}
+
 fn occurrence_spec<'a>(
     grammar: &'a SparseTagGrammar,
     tag: ActiveTemplateTag<'_>,
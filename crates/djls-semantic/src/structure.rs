@@ -13,6 +13,7 @@ pub(crate) mod active;
 pub(crate) mod builder;
 pub(crate) mod folding;
 pub(crate) mod grammar;
+pub(crate) mod linked;
 pub use grammar::GrammarOpeningDefinition;
 pub use grammar::SemanticGrammarVocabulary;
 pub use grammar::semantic_grammar_vocabulary;
@@ -40,6 +41,8 @@ pub use crate::structure::folding::TemplateFold;
 pub use crate::structure::folding::TemplateFoldKind;
 pub use crate::structure::folding::build_template_folds;
 pub(crate) use crate::structure::grammar::TagClassification;
+pub use crate::structure::linked::LinkedBlockNames;
+pub use crate::structure::linked::linked_block_names_at;
 pub use crate::structure::opaque::OpaqueRegions;
 pub use crate::structure::opaque::compute_opaque_regions;
 pub use crate::structure::outline::OutlineItem;
@@ -47,7 +50,7 @@ pub use crate::structure::outline::OutlineKind;
 pub use crate::structure::outline::build_template_outline_for_file;
 pub use crate::structure::tree::BlockRole;
 pub use crate::structure::tree::RegionId;
-pub(crate) use crate::structure::tree::Regions;
+pub use crate::structure::tree::Regions;
 pub use crate::structure::tree::TemplateNode;
 pub use crate::structure::tree::TemplateRegion;
 pub use crate::structure::tree::TemplateTree;
@@ -24,6 +24,9 @@ pub(crate) struct CapturedClosingTag {
     pub name_span: Span,
     pub bits: Vec<TagBit>,
     pub full_span: Span,
+    /// First argument of the opener this closer ended, e.g. `content` in
+    /// `{% block content %}`, which a named closer must repeat.
+    pub opener_argument: Option<TagBit>,
 }
 
 impl CapturedClosingTag {
@@ -46,6 +49,8 @@ pub(crate) struct ActiveTemplateTag<'a> {
     pub span: Span,
     pub full_span: Span,
     pub structural_meaning: StructuralOccurrenceMeaning,
+    /// The container region holding this opener's segments, for block openers.
+    pub body: Option<RegionId>,
 }
 
 impl<'a> ActiveTemplateTag<'a> {
@@ -70,8 +75,14 @@ impl<'a> ActiveTemplateTag<'a> {
             ),
             full_span,
             structural_meaning,
+            body: None,
         }
     }
+
+    fn with_body(mut self, body: RegionId) -> Self {
+        self.body = Some(body);
+        self
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -163,12 +174,15 @@ fn collect_active_nodes_for_node<'a>(
             body,
             role: BlockRole::Opener,
         } => {
-            nodes.push(ActiveTemplateNode::tag(
-                tag,
-                *name_span,
-                bits,
-                *full_span,
-                StructuralOccurrenceMeaning::Definition,
+            nodes.push(ActiveTemplateNode::Tag(
+                ActiveTemplateTag::new(
+                    tag,
+                    *name_span,
+                    bits,
+                    *full_span,
+                    StructuralOccurrenceMeaning::Definition,
+                )
+                .with_body(*body),
             ));
             collect_active_nodes_for_block_body(regions, *body, *full_span, nodes);
         }
@@ -56,6 +56,8 @@ pub(crate) struct TemplateTreeBuilder<'grammar> {
     region_allocs: Vec<(Span, Option<RegionId>)>,
     captured_closers: Vec<CapturedClosingTag>,
     ops: Vec<TreeOp>,
+    /// Offset just past the last node, where blocks still open at the end are closed.
+    template_end: u32,
 }
 
 impl<'grammar> TemplateTreeBuilder<'grammar> {
@@ -67,11 +69,13 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
             region_allocs: vec![(Span::new(0, 0), None)],
             captured_closers: Vec::new(),
             ops: Vec::new(),
+            template_end: 0,
         }
     }
 
     pub(crate) fn model_data(mut self, db: &dyn Db, nodelist: NodeList<'_>) -> TemplateTreeData {
         for node in nodelist.nodelist(db) {
+            self.template_end = self.template_end.max(node.full_span().end());
             self.visit_node(node);
         }
         self.finish();
@@ -253,7 +257,13 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
             }
             None => return false,
         };
-        self.capture_closer(closer_name, name_span, closer_bits, full_span);
+        self.capture_closer(
+            closer_name,
+            name_span,
+            closer_bits,
+            full_span,
+            frame.bits.first(),
+        );
         match OpeningContract::validate_close(&frame.bits, closer_bits) {
             CloseValidation::Valid => {
                 self.finalize_frame(TreeFrame::Opaque(frame), span, full_span);
@@ -326,14 +336,20 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
         let full_span = span.expand_template_tag_marker();
         while self.stack.len() > frame_idx + 1 {
             if let Some(unclosed) = self.stack.pop() {
-                self.accumulate_unclosed(unclosed);
+                self.accumulate_unclosed(unclosed, full_span.start());
             }
         }
 
         let Some(frame) = self.stack.pop() else {
             return;
         };
-        self.capture_closer(closer_name, name_span, closer_bits, full_span);
+        self.capture_closer(
+            closer_name,
+            name_span,
+            closer_bits,
+            full_span,
+            frame.opener_bits().first(),
+        );
         match OpeningContract::validate_close(frame.opener_bits(), closer_bits) {
             CloseValidation::Valid => {
                 self.finalize_frame(frame, span, full_span);
@@ -357,12 +373,20 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
         }
     }
 
-    fn capture_closer(&mut self, tag: &str, name_span: Span, bits: &[TagBit], full_span: Span) {
+    fn capture_closer(
+        &mut self,
+        tag: &str,
+        name_span: Span,
+        bits: &[TagBit],
+        full_span: Span,
+        opener_argument: Option<&TagBit>,
+    ) {
         self.captured_closers.push(CapturedClosingTag {
             tag: tag.to_string(),
             name_span,
             bits: bits.to_vec(),
             full_span,
+            opener_argument: opener_argument.cloned(),
         });
     }
 
@@ -463,9 +487,9 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
     fn finish(&mut self) {
         while let Some(frame) = self.stack.pop() {
             match frame {
-                TreeFrame::Opaque(_) => self.accumulate_unclosed(frame),
+                TreeFrame::Opaque(_) => self.accumulate_unclosed(frame, self.template_end),
                 TreeFrame::Block(frame) if frame.contract.end_required => {
-                    self.accumulate_unclosed(TreeFrame::Block(frame));
+                    self.accumulate_unclosed(TreeFrame::Block(frame), self.template_end);
                 }
                 TreeFrame::Block(frame) => {
                     self.ops.push(TreeOp::ExtendRegionSpan {
@@ -477,12 +501,15 @@ impl<'grammar> TemplateTreeBuilder<'grammar> {
         }
     }
 
-    fn accumulate_unclosed(&mut self, frame: TreeFrame) {
+    fn accumulate_unclosed(&mut self, frame: TreeFrame, closer_offset: u32) {
         let span = frame.opener_span();
+        let closer = frame.closer_name().to_string();
         self.ops
             .push(TreeOp::AccumulateDiagnostic(ValidationError::UnclosedTag {
                 tag: frame.into_opener_name(),
                 span,
+                closer,
+                closer_offset,
             }));
     }
 }
@@ -0,0 +1,50 @@
+use djls_source::File;
+use djls_source::Offset;
+use djls_source::Span;
+use djls_templates::NodeList;
+
+use crate::db::Db;
+use crate::scoping::template_analysis_projection_for_file;
+
+/// Name arguments of a block opener and its closer that must stay in step,
+/// such as `content` in `{% block content %}` and `{% endblock content %}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkedBlockNames {
+    pub opener: Span,
+    pub closer: Span,
+}
+
+/// Return the paired block names when `offset` touches either of them.
+///
+/// Only closers that repeat their opener's name are linked. A bare
+/// `{% endblock %}` has nothing to edit alongside the opener, and a mismatched
+/// name is already reported as a diagnostic.
+#[must_use]
+pub fn linked_block_names_at(
+    db: &dyn Db,
+    file: File,
+    nodelist: NodeList<'_>,
+    offset: Offset,
+) -> Option<LinkedBlockNames> {
+    template_analysis_projection_for_file(db, file, nodelist)
+        .captured_closers(db)
+        .iter()
+        .find_map(|closer| {
+            let opener = closer.opener_argument.as_ref()?;
+            let name = closer.bits.first()?;
+            if opener.as_str() != name.as_str() {
+                return None;
+            }
+            let linked = LinkedBlockNames {
+                opener: opener.span,
+                closer: name.span,
+            };
+            (touches(linked.opener, offset) || touches(linked.closer, offset)).then_some(linked)
+        })
+}
+
+/// Like [`Span::contains`], but also accepts the offset just past the end so a
+/// cursor sitting after the last character of a name still links it.
+fn touches(span: Span, offset: Offset) -> bool {
+    (span.start()..=span.end()).contains(&offset.get())
+}
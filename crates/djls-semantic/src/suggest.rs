@@ -0,0 +1,87 @@
+//! "Did you mean" suggestions for names that match nothing in the inventory.
+
+use std::mem;
+
+/// Most suggestions offered for one unknown name.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Up to three `candidates` within typo distance of `name`, closest first.
+///
+/// The allowed distance grows with the name, a quarter of its length and at
+/// least one edit, so short names only match single typos and `endlocalize`
+/// does not turn into `localize`.
+pub(crate) fn similar_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let name_len = name.chars().count();
+    let limit = (name_len / 4).max(1);
+    let mut matches = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .filter(|candidate| candidate.chars().count().abs_diff(name_len) <= limit)
+        .filter_map(|candidate| {
+            let distance = edit_distance(name, candidate);
+            (distance <= limit).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+    matches.sort_unstable();
+    matches.dedup();
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Levenshtein distance that also counts swapping two adjacent characters as
+/// one edit, the most common typo in a name like `blocktrnas`.
+pub(crate) fn edit_distance(left: &str, right: &str) -> usize {
+    let left = left.chars().collect::<Vec<_>>();
+    let right = right.chars().collect::<Vec<_>>();
+
+    let mut before = vec![0; right.len() + 1];
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    let mut current = vec![0; right.len() + 1];
+    for (i, &left_char) in left.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != right_char);
+            let mut best = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && left_char == right[j - 1] && left[i - 1] == right_char {
+                best = best.min(before[j - 1] + 1);
+            }
+            current[j + 1] = best;
+        }
+        mem::swap(&mut before, &mut previous);
+        mem::swap(&mut previous, &mut current);
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance("blocktrnas", "blocktrans"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "if"), 2);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn similar_names_orders_by_distance_and_skips_distant_names() {
+        let candidates = ["blocktrans", "blocktranslate", "block", "localize", "trans"];
+        assert_eq!(similar_names("blocktrnas", candidates), vec!["blocktrans"]);
+        assert_eq!(similar_names("endlocalize", candidates), Vec::<&str>::new());
+        assert_eq!(similar_names("blok", candidates), vec!["block"]);
+    }
+
+    #[test]
+    fn similar_names_keeps_at_most_three() {
+        let candidates = ["cat", "bat", "hat", "mat", "rat"];
+        assert_eq!(similar_names("xat", candidates), vec!["bat", "cat", "hat"]);
+    }
+}
@@ -3,12 +3,15 @@ mod specs;
 
 use std::collections::HashSet;
 
+use djls_project::DjangoVersion;
 use djls_project::Project;
 use djls_project::ScopedTemplateLibraries;
 use djls_project::TemplateLibraryChainStep;
 use djls_project::TemplateLibraryId;
 use djls_project::TemplateSymbolKind;
+use djls_project::django_version;
 use djls_project::scoped_template_libraries;
+use djls_project::template_library_catalog;
 use djls_project::template_library_tag_facts;
 use djls_source::File;
 use djls_source::Offset;
@@ -19,6 +22,7 @@ pub use specs::IntermediateTag;
 pub use specs::TagSpec;
 pub use specs::TagSpecs;
 pub use specs::builtin_tag_specs;
+pub use specs::builtin_tag_specs_for;
 
 use crate::db::Db;
 use crate::references::TemplateReferenceKind;
@@ -63,7 +67,8 @@ impl LibraryTagSpecs {
 #[salsa::tracked(returns(ref))]
 #[allow(clippy::needless_pass_by_value)]
 pub fn library_tag_specs(db: &dyn Db, project: Project, key: TemplateLibraryId) -> LibraryTagSpecs {
-    let mut specs = builtin_tag_specs();
+    let version = django_version(db, project).unwrap_or(DjangoVersion::LATEST);
+    let mut specs = builtin_tag_specs_for(version);
     specs.retain(|_, spec| spec.module() == key.module(db).as_str());
 
     let facts = template_library_tag_facts(db, key);
@@ -81,8 +86,8 @@ pub fn library_tag_specs(db: &dyn Db, project: Project, key: TemplateLibraryId)
 /// Equality-bearing configured fallback for one Template Library.
 #[salsa::tracked(returns(ref))]
 fn configured_library_tag_specs(db: &dyn Db, project: Project, key: TemplateLibraryId) -> TagSpecs {
-    project
-        .tagspecs(db)
+    let tagspecs = project.tagspecs(db);
+    let mut specs = tagspecs
         .libraries
         .iter()
         .filter(|library| library.module == key.module(db).as_str())
@@ -90,7 +95,27 @@ fn configured_library_tag_specs(db: &dyn Db, project: Project, key: TemplateLibr
         .fold(TagSpecs::default(), |mut specs, configured| {
             specs.merge(configured);
             specs
-        })
+        });
+
+    if !tagspecs.tags.is_empty() {
+        // Name-keyed shorthand only applies to tags this library may register.
+        let library = template_library_catalog(db, project)
+            .libraries()
+            .iter()
+            .find(|library| library.id() == key);
+        if let Some(library) = library {
+            let shorthand = TagSpecs::from_tag_shorthand(
+                key.module(db).as_str(),
+                tagspecs.tags.iter().filter(|(name, _)| {
+                    library.symbols_are_unobserved()
+                        || library.symbol(TemplateSymbolKind::Tag, name).is_some()
+                }),
+            );
+            specs.merge_fallback(shorthand);
+        }
+    }
+
+    specs
 }
 
 /// Return the effective tag spec at one occurrence, but only when every feasible backend agrees.
@@ -178,7 +203,7 @@ pub(crate) fn effective_tag_spec_in_scope(
 }
 
 /// Specs effective before any file-local `{% load %}` statement.
-#[salsa::tracked(returns(ref))]
+#[salsa::tracked(returns(ref), lru = 128)]
 pub fn tag_specs_for_file(db: &dyn Db, file: File) -> TagSpecs {
     let empty = LoadedLibraries::default();
     completion_tag_specs_for_load_state(db, file, &empty.available_at(0))
@@ -232,7 +257,11 @@ pub fn tag_spec_at(
     )
 }
 
-#[salsa::tracked(returns(ref))]
+/// Specs available at `position`, after the `{% load %}` tags above it.
+///
+/// Memoized per position, so completions across a long session would otherwise keep one
+/// copy of the specs for every place the cursor has been.
+#[salsa::tracked(returns(ref), lru = 32)]
 pub fn tag_specs_at(db: &dyn Db, file: File, nodelist: NodeList<'_>, position: u32) -> TagSpecs {
     let projection = template_analysis_projection_for_file(db, file, nodelist);
     completion_tag_specs_for_load_state(
@@ -6,11 +6,13 @@ use djls_project::ExtractedDiagnosticConstraint;
 use djls_project::ExtractedDiagnosticMessage;
 use djls_project::ExtractedMessageArg;
 use djls_project::ExtractedMessageTemplate;
+use djls_project::KeywordOperators;
 use djls_project::KnownOptions;
 use djls_project::RequiredKeyword;
 use djls_project::SplitPosition;
 use djls_project::TagRule;
 use djls_source::Span;
+use djls_templates::TagBit;
 
 use crate::errors::ValidationError;
 
@@ -18,7 +20,7 @@ trait Constraint {
     fn validate(
         &self,
         tag_name: &str,
-        bits: &[String],
+        bits: &[TagBit],
         span: Span,
         message: Option<String>,
     ) -> Option<ValidationError>;
@@ -46,7 +48,7 @@ impl Constraint for ArgumentCountConstraint {
     fn validate(
         &self,
         tag_name: &str,
-        bits: &[String],
+        bits: &[TagBit],
         span: Span,
         message: Option<String>,
     ) -> Option<ValidationError> {
@@ -111,14 +113,14 @@ impl Constraint for RequiredKeyword {
     fn validate(
         &self,
         tag_name: &str,
-        bits: &[String],
-        span: Span,
+        bits: &[TagBit],
+        _span: Span,
         message: Option<String>,
     ) -> Option<ValidationError> {
         let bits_index = resolve_position_index(&self.position, bits.len())?;
         let bit = bits.get(bits_index)?;
 
-        if bit == &self.value {
+        if bit.as_str() == self.value {
             None
         } else {
             Some(ValidationError::ExtractedRuleViolation {
@@ -130,7 +132,7 @@ impl Constraint for RequiredKeyword {
                         self.value, self.position
                     ),
                 },
-                span,
+                span: bit.span,
             })
         }
     }
@@ -140,14 +142,14 @@ impl Constraint for ChoiceAt {
     fn validate(
         &self,
         tag_name: &str,
-        bits: &[String],
-        span: Span,
+        bits: &[TagBit],
+        _span: Span,
         message: Option<String>,
     ) -> Option<ValidationError> {
         let bits_index = resolve_position_index(&self.position, bits.len())?;
         let bit = bits.get(bits_index)?;
 
-        if self.values.iter().any(|value| value == bit) {
+        if self.values.iter().any(|value| value == bit.as_str()) {
             None
         } else {
             let choices = self.values.join("', '");
@@ -157,7 +159,7 @@ impl Constraint for ChoiceAt {
                     Some(message) => message,
                     None => format!("Tag '{tag_name}' argument must be one of: '{choices}'"),
                 },
-                span,
+                span: bit.span,
             })
         }
     }
@@ -165,15 +167,19 @@ impl Constraint for ChoiceAt {
 
 /// Evaluate extracted tag rules against template tag arguments.
 ///
-/// `bits` is the tag's argument list as text, excluding the tag name. Extraction
+/// `bits` is the tag's argument list, excluding the tag name. Extraction
 /// rules use Django `split_contents()` indices where the tag name is at index 0,
 /// so the evaluator adjusts by adding 1 to `bits.len()` when comparing against
 /// `ArgumentCountConstraint` values, and subtracting 1 from
 /// `RequiredKeyword.position` when indexing into `bits`.
+///
+/// Argument count violations are reported on `span`, the whole tag. Violations
+/// about one argument, like a wrong keyword or a misplaced operator, use that
+/// argument's own span.
 #[must_use]
 pub(crate) fn evaluate_tag_rules(
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
     rules: &TagRule,
     span: Span,
 ) -> Vec<ValidationError> {
@@ -182,12 +188,14 @@ pub(crate) fn evaluate_tag_rules(
     // Django's simple_tag supports `{% tag args... as varname %}` syntax.
     // The framework strips `as varname` before validating arguments, so we
     // do the same: if the last two bits are ["as", <something>], strip them.
-    let effective_bits =
-        if rules.as_var.strips_suffix() && bits.len() >= 2 && bits[bits.len() - 2] == "as" {
-            &bits[..bits.len() - 2]
-        } else {
-            bits
-        };
+    let effective_bits = if rules.as_var.strips_suffix()
+        && bits.len() >= 2
+        && bits[bits.len() - 2].as_str() == "as"
+    {
+        &bits[..bits.len() - 2]
+    } else {
+        bits
+    };
 
     let diagnostic_messages = rules.diagnostic_messages.as_deref().unwrap_or(&[]);
 
@@ -235,9 +243,9 @@ pub(crate) fn evaluate_tag_rules(
                     // Pick the first as representative error, but phrase it
                     // as a choice to be clearer.
                     let values: Vec<&str> = keywords.iter().map(|kw| kw.value.as_str()).collect();
-                    let bits_index =
-                        resolve_position_index(&keywords[0].position, effective_bits.len());
-                    if bits_index.is_some() {
+                    let bit = resolve_position_index(&keywords[0].position, effective_bits.len())
+                        .and_then(|index| effective_bits.get(index));
+                    if let Some(bit) = bit {
                         let choices = values.join("' or '");
                         errors.push(ValidationError::ExtractedRuleViolation {
                             tag: tag_name.to_string(),
@@ -245,7 +253,7 @@ pub(crate) fn evaluate_tag_rules(
                                 "Tag '{tag_name}' expects '{}' at position {}",
                                 choices, keywords[0].position
                             ),
-                            span,
+                            span: bit.span,
                         });
                     }
                 }
@@ -267,12 +275,11 @@ pub(crate) fn evaluate_tag_rules(
     }
 
     if let Some(options) = &rules.known_options {
-        errors.extend(evaluate_known_options(
-            tag_name,
-            effective_bits,
-            options,
-            span,
-        ));
+        errors.extend(evaluate_known_options(tag_name, effective_bits, options));
+    }
+
+    if let Some(operators) = &rules.keyword_operators {
+        errors.extend(evaluate_keyword_operators(tag_name, effective_bits, operators));
     }
 
     errors
@@ -282,7 +289,7 @@ fn message_for_constraint(
     messages: &[ExtractedDiagnosticMessage],
     constraint: &ExtractedDiagnosticConstraint,
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
 ) -> Option<String> {
     messages.iter().find_map(|message| {
         if &message.constraint == constraint {
@@ -296,7 +303,7 @@ fn message_for_constraint(
 fn render_message_template(
     message: &ExtractedMessageTemplate,
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
 ) -> Option<String> {
     match message {
         ExtractedMessageTemplate::Static(message) => Some(message.clone()),
@@ -310,7 +317,7 @@ fn render_percent_format(
     template: &str,
     args: &[ExtractedMessageArg],
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
 ) -> Option<String> {
     let mut rendered = String::new();
     let mut chars = template.chars().peekable();
@@ -361,7 +368,7 @@ enum FormatKind {
 fn format_arg(
     arg: &ExtractedMessageArg,
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
     kind: FormatKind,
 ) -> Option<String> {
     match (arg, kind) {
@@ -387,16 +394,16 @@ fn format_arg(
 fn split_position_value(
     position: SplitPosition,
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
 ) -> Option<String> {
     match position {
         SplitPosition::Forward(0) => Some(tag_name.to_string()),
-        SplitPosition::Forward(index) => bits.get(index - 1).cloned(),
+        SplitPosition::Forward(index) => bits.get(index - 1).map(|bit| bit.as_str().to_string()),
         SplitPosition::Backward(index) => {
             if index == 0 || index > bits.len() {
                 None
             } else {
-                bits.get(bits.len() - index).cloned()
+                bits.get(bits.len() - index).map(|bit| bit.as_str().to_string())
             }
         }
     }
@@ -413,25 +420,25 @@ fn python_repr(value: &str) -> String {
 /// and duplicate options (when `!allow_duplicates`).
 fn evaluate_known_options(
     tag_name: &str,
-    bits: &[String],
+    bits: &[TagBit],
     options: &KnownOptions,
-    span: Span,
 ) -> Vec<ValidationError> {
     let mut errors = Vec::new();
     let mut seen = Vec::new();
 
     for bit in bits {
-        let is_known = options.values.iter().any(|v| v == bit);
+        let option = bit.as_str();
+        let is_known = options.values.iter().any(|v| v == option);
 
         if is_known {
-            if !options.allow_duplicates && seen.contains(bit) {
+            if !options.allow_duplicates && seen.contains(&option) {
                 errors.push(ValidationError::ExtractedRuleViolation {
                     tag: tag_name.to_string(),
-                    message: format!("Tag '{tag_name}' received duplicate option '{bit}'"),
-                    span,
+                    message: format!("Tag '{tag_name}' received duplicate option '{option}'"),
+                    span: bit.span,
                 });
             }
-            seen.push(bit.clone());
+            seen.push(option);
         }
         // NOTE: `rejects_unknown` is not enforced — distinguishing unknown
         // options from positional values (e.g. `with key=val`) is unreliable
@@ -441,6 +448,79 @@ fn evaluate_known_options(
     errors
 }
 
+/// Evaluate keyword operator placement.
+///
+/// Infix operators need an operand on each side and prefix operators need one
+/// after them. Adjacent operands are not checked, since the extracted grammar
+/// says nothing about the rest of the tag's syntax. A prefix operator directly
+/// before an infix one is read as a compound operator, like `not in`.
+fn evaluate_keyword_operators(
+    tag_name: &str,
+    bits: &[TagBit],
+    operators: &KeywordOperators,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let start = operators.start.saturating_sub(1);
+    let Some(expression) = bits.get(start..) else {
+        return errors;
+    };
+    let is_infix = |bit: &str| operators.infix.iter().any(|op| op == bit);
+    let is_prefix = |bit: &str| operators.prefix.iter().any(|op| op == bit);
+    let misplaced = |bit: &TagBit, index: usize| ValidationError::ExtractedRuleViolation {
+        tag: tag_name.to_string(),
+        message: format!(
+            "Tag '{tag_name}' has unexpected operator '{}' at position {}",
+            bit.as_str(),
+            start + index + 1
+        ),
+        span: bit.span,
+    };
+
+    let mut expects_operand = true;
+    let mut pending_operator: Option<&TagBit> = None;
+    let mut index = 0;
+    while let Some(bit) = expression.get(index) {
+        if is_infix(bit.as_str()) {
+            if expects_operand {
+                errors.push(misplaced(bit, index));
+            }
+            expects_operand = true;
+            pending_operator = Some(bit);
+        } else if is_prefix(bit.as_str()) {
+            if !expects_operand {
+                if expression
+                    .get(index + 1)
+                    .is_some_and(|next| is_infix(next.as_str()))
+                {
+                    // Compound operator such as `not in`; the infix half is checked next.
+                    index += 1;
+                    continue;
+                }
+                errors.push(misplaced(bit, index));
+            }
+            expects_operand = true;
+            pending_operator = Some(bit);
+        } else {
+            expects_operand = false;
+            pending_operator = None;
+        }
+        index += 1;
+    }
+
+    if let Some(operator) = pending_operator {
+        errors.push(ValidationError::ExtractedRuleViolation {
+            tag: tag_name.to_string(),
+            message: format!(
+                "Tag '{tag_name}' expects an operand after '{}'",
+                operator.as_str()
+            ),
+            span: operator.span,
+        });
+    }
+
+    errors
+}
+
 #[cfg(test)]
 mod tests {
     use djls_project::AsVar;
@@ -452,8 +532,18 @@ mod tests {
         Span::new(0, 10)
     }
 
-    fn make_bits(args: &[&str]) -> Vec<String> {
-        args.iter().map(|s| (*s).to_string()).collect()
+    fn make_bits(args: &[&str]) -> Vec<TagBit> {
+        let mut offset = 0;
+        args.iter()
+            .map(|arg| {
+                let bit = TagBit::new(
+                    (*arg).to_string(),
+                    Span::saturating_from_parts_usize(offset, arg.len()),
+                );
+                offset += arg.len() + 1;
+                bit
+            })
+            .collect()
     }
 
     fn empty_rule() -> TagRule {
@@ -651,8 +741,9 @@ mod tests {
         assert_eq!(errors.len(), 1);
         assert!(matches!(
             &errors[0],
-            ValidationError::ExtractedRuleViolation { message, .. }
+            ValidationError::ExtractedRuleViolation { message, span, .. }
             if message.contains("'in'") && message.contains("position 2")
+                && *span == Span::new(5, 4)
         ));
     }
 
@@ -751,6 +842,62 @@ mod tests {
         assert!(errors.is_empty());
     }
 
+    // --- KeywordOperators tests ---
+
+    fn operator_rule() -> TagRule {
+        TagRule {
+            keyword_operators: Some(KeywordOperators {
+                start: 1,
+                infix: vec!["and".to_string(), "or".to_string(), "in".to_string()],
+                prefix: vec!["not".to_string()],
+            }),
+            ..empty_rule()
+        }
+    }
+
+    #[test]
+    fn keyword_operators_accept_well_formed_expression() {
+        let bits = make_bits(&["not", "a", "and", "b", "not", "in", "c"]);
+        let errors = evaluate_tag_rules("check", &bits, &operator_rule(), make_span());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn keyword_operators_flag_misplaced_infix() {
+        let bits = make_bits(&["and", "a", "or", "or", "b"]);
+        let errors = evaluate_tag_rules("check", &bits, &operator_rule(), make_span());
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                if let ValidationError::ExtractedRuleViolation { message, .. } = error {
+                    message.as_str()
+                } else {
+                    ""
+                }
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Tag 'check' has unexpected operator 'and' at position 1",
+                "Tag 'check' has unexpected operator 'or' at position 4",
+            ]
+        );
+    }
+
+    #[test]
+    fn keyword_operators_flag_trailing_operator() {
+        let bits = make_bits(&["a", "and", "not"]);
+        let errors = evaluate_tag_rules("check", &bits, &operator_rule(), make_span());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::ExtractedRuleViolation { message, span, .. }
+            if message == "Tag 'check' expects an operand after 'not'"
+                && *span == Span::new(6, 3)
+        ));
+    }
+
     // --- Combined tests ---
 
     #[test]
@@ -8,9 +8,15 @@ use std::sync::Arc;
 use djls_conf::ArgKindDef;
 use djls_conf::ArgTypeDef;
 use djls_conf::TagLibraryDef;
+use djls_conf::TagShorthandDef;
 use djls_conf::TagSpecDef;
 use djls_conf::TagTypeDef;
+use djls_project::ArgumentCountConstraint;
+use djls_project::AsVar;
 use djls_project::BlockSpecs;
+use djls_project::DjangoVersion;
+use djls_project::RequiredKeyword;
+use djls_project::SplitPosition;
 use djls_project::TagArgument;
 use djls_project::TagArgumentKind;
 use djls_project::TagRule;
@@ -173,6 +179,41 @@ impl TagSpecs {
         Self::from_tagspec_def(&doc)
     }
 
+    /// Build block structure from `[tags.<name>]` shorthand entries.
+    ///
+    /// Entries without an `end` still contribute their intermediates, which
+    /// only take effect once another source supplies the end tag.
+    #[must_use]
+    pub(crate) fn from_tag_shorthand<'a>(
+        module: &str,
+        tags: impl IntoIterator<Item = (&'a String, &'a TagShorthandDef)>,
+    ) -> TagSpecs {
+        let specs = tags
+            .into_iter()
+            .map(|(name, def)| {
+                let end_tag = def.end.as_ref().map(|end| EndTag {
+                    name: end.clone().into(),
+                    required: true,
+                });
+                let intermediate_tags = def
+                    .intermediates
+                    .iter()
+                    .map(|name| IntermediateTag {
+                        name: name.clone().into(),
+                    })
+                    .collect::<Vec<_>>();
+                let spec = TagSpec::new(
+                    module.to_string().into(),
+                    end_tag,
+                    Cow::Owned(intermediate_tags),
+                    false,
+                );
+                (name.clone(), spec)
+            })
+            .collect();
+        TagSpecs(specs)
+    }
+
     #[must_use]
     #[allow(clippy::too_many_lines)]
     fn from_tagspec_def(doc: &TagSpecDef) -> TagSpecs {
@@ -393,7 +434,17 @@ impl TagSpec {
     }
 
     #[must_use]
-    pub(crate) fn extracted_rules(&self) -> Option<&TagRule> {
+    pub fn intermediate_tags(&self) -> &[IntermediateTag] {
+        &self.intermediate_tags
+    }
+
+    #[must_use]
+    pub fn is_opaque(&self) -> bool {
+        self.opaque
+    }
+
+    #[must_use]
+    pub fn extracted_rules(&self) -> Option<&TagRule> {
         self.extracted_rules.as_deref()
     }
 
@@ -445,12 +496,25 @@ pub struct IntermediateTag {
 /// Returns minimal Django tag specs for use in test databases.
 ///
 /// Provides block structure (end tags, intermediates, opaque flags) for
-/// standard Django tags. Does NOT include extracted rules — tests that
-/// need argument metadata can use [`TagSpec::with_arguments`], while tests
-/// that need full validation rules should use extraction on Python source.
+/// standard Django tags. Does NOT include extracted rules, except for the
+/// tags added since Django 5.1, whose rules are written out so they validate
+/// and complete without Django's source — tests that need argument metadata
+/// can use [`TagSpec::with_arguments`], while tests that need full validation
+/// rules should use extraction on Python source.
+///
+/// The specs are those of [`DjangoVersion::LATEST`]; see [`builtin_tag_specs_for`].
 #[must_use]
-#[allow(clippy::similar_names, clippy::too_many_lines)]
 pub fn builtin_tag_specs() -> TagSpecs {
+    builtin_tag_specs_for(DjangoVersion::LATEST)
+}
+
+/// Returns the builtin tag specs of one Django release.
+///
+/// Tags added in a later release are left out, so a project on an older
+/// Django does not accept them.
+#[must_use]
+#[allow(clippy::similar_names, clippy::too_many_lines)]
+pub fn builtin_tag_specs_for(version: DjangoVersion) -> TagSpecs {
     use std::borrow::Cow::Borrowed as B;
 
     let dt = "django.template.defaulttags";
@@ -499,6 +563,13 @@ pub fn builtin_tag_specs() -> TagSpecs {
 
     let im = |name: &'static str| IntermediateTag { name: B(name) };
 
+    let arg = |name: &str, required: bool, kind: TagArgumentKind, position: usize| TagArgument {
+        name: name.to_string(),
+        required,
+        kind,
+        position,
+    };
+
     // defaulttags
     specs.insert(
         "autoescape".into(),
@@ -562,6 +633,53 @@ pub fn builtin_tag_specs() -> TagSpecs {
         "with".into(),
         block(dt, "endwith", vec![], false, TagRole::ControlTag),
     );
+    if version >= DjangoVersion::new(5, 1) {
+        // 6.0 accepts any number of mappings; 5.1 and 5.2 take a single optional one.
+        let mapping = if version >= DjangoVersion::new(6, 0) {
+            arg("args", false, TagArgumentKind::VarArgs, 0)
+        } else {
+            arg("query_dict", false, TagArgumentKind::Variable, 0)
+        };
+        specs.insert(
+            "querystring".into(),
+            simple(dt).with_extracted_rules(Arc::new(TagRule {
+                extracted_args: vec![mapping],
+                as_var: AsVar::Strip,
+                ..TagRule::default()
+            })),
+        );
+    }
+    if version >= DjangoVersion::new(6, 0) {
+        specs.insert(
+            "partial".into(),
+            simple(dt).with_extracted_rules(Arc::new(TagRule {
+                arg_constraints: vec![ArgumentCountConstraint::Exact(2)],
+                extracted_args: vec![arg("name", true, TagArgumentKind::Variable, 0)],
+                ..TagRule::default()
+            })),
+        );
+        specs.insert(
+            "partialdef".into(),
+            block(dt, "endpartialdef", vec![], false, TagRole::TemplatePartial)
+                .with_extracted_rules(Arc::new(TagRule {
+                    arg_constraints: vec![ArgumentCountConstraint::OneOf(vec![2, 3])],
+                    required_keywords: vec![RequiredKeyword {
+                        position: SplitPosition::Forward(2),
+                        value: "inline".to_string(),
+                    }],
+                    extracted_args: vec![
+                        arg("name", true, TagArgumentKind::Variable, 0),
+                        arg(
+                            "inline",
+                            false,
+                            TagArgumentKind::Literal("inline".to_string()),
+                            1,
+                        ),
+                    ],
+                    ..TagRule::default()
+                })),
+        );
+    }
 
     // loader_tags
     specs.insert(
@@ -643,7 +761,6 @@ mod tests {
     use std::ptr;
     use std::string::ToString;
 
-    use djls_project::ArgumentCountConstraint;
     use djls_project::BlockSpec;
     use djls_project::SymbolKey;
 
@@ -1056,4 +1173,55 @@ mod tests {
         assert!(rules.extracted_args[0].required);
         assert_eq!(rules.extracted_args[2].name, "iterable");
     }
+
+    #[test]
+    fn builtin_specs_leave_out_tags_added_after_the_release() {
+        let django_4_2 = builtin_tag_specs_for(DjangoVersion::new(4, 2));
+        assert!(django_4_2.get("querystring").is_none());
+        assert!(django_4_2.get("partialdef").is_none());
+
+        let django_5_1 = builtin_tag_specs_for(DjangoVersion::new(5, 1));
+        assert!(django_5_1.get("querystring").is_some());
+        assert!(django_5_1.get("partialdef").is_none());
+
+        let latest = builtin_tag_specs();
+        let partialdef = latest
+            .get("partialdef")
+            .expect("latest builtin specs should contain partialdef");
+        assert_eq!(partialdef.role(), Some(TagRole::TemplatePartial));
+        assert_eq!(
+            partialdef.end_tag.as_ref().map(|end| end.name.as_ref()),
+            Some("endpartialdef")
+        );
+        assert!(latest.get("partial").is_some());
+    }
+
+    #[test]
+    fn builtin_specs_carry_rules_for_tags_added_since_django_5_1() {
+        let querystring = |version| {
+            builtin_tag_specs_for(version)
+                .get("querystring")
+                .expect("querystring should be a builtin since Django 5.1")
+                .arguments()
+        };
+        let django_5_1 = querystring(DjangoVersion::new(5, 1));
+        assert_eq!(django_5_1.len(), 1);
+        assert_eq!(django_5_1[0].name, "query_dict");
+        assert_eq!(django_5_1[0].kind, TagArgumentKind::Variable);
+        assert_eq!(
+            querystring(DjangoVersion::new(6, 0))[0].kind,
+            TagArgumentKind::VarArgs
+        );
+
+        let latest = builtin_tag_specs();
+        let partialdef = latest
+            .get("partialdef")
+            .and_then(TagSpec::extracted_rules)
+            .expect("partialdef should carry argument rules");
+        assert_eq!(
+            partialdef.arg_constraints,
+            vec![ArgumentCountConstraint::OneOf(vec![2, 3])]
+        );
+        assert_eq!(partialdef.required_keywords[0].value, "inline");
+    }
 }
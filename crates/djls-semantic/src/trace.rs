@@ -0,0 +1,287 @@
+//! Flattened view of a Template after resolving `{% extends %}` and `{% block %}` overrides.
+
+use djls_project::Project;
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::TagDelimiter;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+
+use crate::db::Db;
+use crate::inheritance::ChainEnd;
+use crate::inheritance::template_inheritance;
+use crate::inheritance::template_symbols_in_scope;
+use crate::scoping::template_analysis_projection_for_file_in_scope;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+
+/// The Template Django renders for a file, pieced together from the files
+/// along its `{% extends %}` chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateTrace {
+    /// Source ranges in render order. Concatenated, they are the flattened
+    /// Template.
+    pub segments: Vec<TraceSegment>,
+    /// How the `{% extends %}` chain ended. Anything but [`ChainEnd::Root`]
+    /// means the topmost file traced is not the real root.
+    pub end: ChainEnd,
+}
+
+/// A run of source copied verbatim from one file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceSegment {
+    pub file: File,
+    pub span: Span,
+    /// Innermost `{% block %}` the text renders inside, if any.
+    pub block: Option<String>,
+}
+
+/// Flatten `file` by walking its topmost ancestor and filling each
+/// `{% block %}` with the nearest definition along the chain.
+///
+/// `{{ block.super }}` expands to the next definition above the one being
+/// rendered. Block tags and `{{ block.super }}` themselves are left out.
+/// Returns `None` when `file` cannot be parsed.
+#[must_use]
+pub fn trace_template(db: &dyn Db, project: Project, file: File) -> Option<TemplateTrace> {
+    let inheritance = template_inheritance(db, project, file);
+    let chain = std::iter::once(file).chain(
+        inheritance
+            .ancestors(db)
+            .iter()
+            .map(|origin| origin.file(db)),
+    );
+
+    let mut levels = Vec::new();
+    for level_file in chain {
+        // An unparseable ancestor ends the chain like a missing one would.
+        let Some(level) = TraceLevel::new(db, level_file, file) else {
+            break;
+        };
+        levels.push(level);
+    }
+    let root = levels.len().checked_sub(1)?;
+
+    let mut tracer = Tracer {
+        levels: &levels,
+        segments: Vec::new(),
+        active: Vec::new(),
+    };
+    let top = &levels[root];
+    tracer.render_region(root, top.root, 0, top.len, None);
+
+    Some(TemplateTrace {
+        segments: tracer.segments,
+        end: inheritance.end(db),
+    })
+}
+
+/// One file along the chain, with its `{% block %}` definitions.
+struct TraceLevel<'db> {
+    file: File,
+    len: u32,
+    regions: &'db Regions,
+    root: RegionId,
+    /// Name span of each `{% block %}` tag, with the block's name.
+    block_names: Vec<(Span, String)>,
+    /// Content region of each block name, first definition wins.
+    blocks: Vec<(String, RegionId)>,
+}
+
+impl<'db> TraceLevel<'db> {
+    fn new(db: &'db dyn Db, file: File, scope_file: File) -> Option<Self> {
+        let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+            return None;
+        };
+        let len = file.try_source(db).ok()?.as_str().len();
+        let projection =
+            template_analysis_projection_for_file_in_scope(db, file, nodelist, scope_file);
+        let tree = projection.tree(db);
+        let regions = tree.regions(db);
+
+        let block_names = template_symbols_in_scope(db, file, nodelist, scope_file)
+            .blocks()
+            .iter()
+            .map(|block| (block.name_span, block.name.clone()))
+            .collect::<Vec<_>>();
+
+        let mut blocks: Vec<(String, RegionId)> = Vec::new();
+        for node in regions.iter().flat_map(|region| region.nodes()) {
+            let TemplateNode::Block {
+                bits,
+                body,
+                role: BlockRole::Opener,
+                ..
+            } = node
+            else {
+                continue;
+            };
+            let Some((_, name)) = bits
+                .first()
+                .and_then(|bit| block_names.iter().find(|(span, _)| *span == bit.span))
+            else {
+                continue;
+            };
+            let content = regions.get(*body).nodes().iter().find_map(|segment| {
+                if let TemplateNode::Block {
+                    body,
+                    role: BlockRole::Segment,
+                    ..
+                } = segment
+                {
+                    Some(*body)
+                } else {
+                    None
+                }
+            });
+            if let Some(content) = content
+                && !blocks.iter().any(|(existing, _)| existing == name)
+            {
+                blocks.push((name.clone(), content));
+            }
+        }
+
+        Some(Self {
+            file,
+            len: u32::try_from(len).unwrap_or(u32::MAX),
+            regions,
+            root: tree.root(db),
+            block_names,
+            blocks,
+        })
+    }
+
+    fn block_name(&self, name_span: Span) -> Option<&str> {
+        self.block_names
+            .iter()
+            .find(|(span, _)| *span == name_span)
+            .map(|(_, name)| name.as_str())
+    }
+
+    fn block(&self, name: &str) -> Option<RegionId> {
+        self.blocks
+            .iter()
+            .find(|(block, _)| block == name)
+            .map(|(_, content)| *content)
+    }
+}
+
+struct Tracer<'a, 'db> {
+    /// The traced file first, then its ancestors nearest first.
+    levels: &'a [TraceLevel<'db>],
+    segments: Vec<TraceSegment>,
+    /// Blocks being rendered, by level and name, so a block nested inside
+    /// itself is not expanded again.
+    active: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Tracer<'a, '_> {
+    /// Render the nearest definition of `name` at or above level `from`.
+    fn render_block(&mut self, name: &'a str, from: usize) {
+        let Some((level, content)) = self
+            .levels
+            .iter()
+            .enumerate()
+            .skip(from)
+            .find_map(|(index, level)| level.block(name).map(|content| (index, content)))
+        else {
+            return;
+        };
+        if self.active.contains(&(level, name)) {
+            return;
+        }
+
+        self.active.push((level, name));
+        let span = *self.levels[level].regions.get(content).span();
+        self.render_region(level, content, span.start(), span.end(), Some(name));
+        self.active.pop();
+    }
+
+    fn render_region(
+        &mut self,
+        level: usize,
+        region: RegionId,
+        start: u32,
+        end: u32,
+        block: Option<&'a str>,
+    ) {
+        let mut cursor = start;
+        self.walk(level, region, &mut cursor, block);
+        self.emit(level, cursor, end, block);
+    }
+
+    /// Copy source up to each block tag or `{{ block.super }}` in `region`,
+    /// replacing it with what renders in its place.
+    fn walk(&mut self, level: usize, region: RegionId, cursor: &mut u32, block: Option<&'a str>) {
+        let levels = self.levels;
+        let trace_level = &levels[level];
+        for node in trace_level.regions.get(region).nodes() {
+            match node {
+                TemplateNode::Block {
+                    bits,
+                    full_span,
+                    body,
+                    role: BlockRole::Opener,
+                    ..
+                } => {
+                    let name = bits
+                        .first()
+                        .and_then(|bit| trace_level.block_name(bit.span));
+                    if let Some(name) = name {
+                        self.emit(level, *cursor, full_span.start(), block);
+                        self.render_block(name, 0);
+                        *cursor = trace_level.regions.get(*body).span().end();
+                    } else {
+                        self.walk(level, *body, cursor, block);
+                    }
+                }
+                TemplateNode::Block {
+                    body,
+                    role: BlockRole::Segment,
+                    ..
+                } => self.walk(level, *body, cursor, block),
+                TemplateNode::Variable {
+                    var, filters, span, ..
+                } if var == "block.super" && filters.is_empty() => {
+                    let Some(name) = block else {
+                        continue;
+                    };
+                    let full_span =
+                        span.expand(TagDelimiter::LENGTH_U32, TagDelimiter::LENGTH_U32);
+                    self.emit(level, *cursor, full_span.start(), block);
+                    self.render_block(name, level + 1);
+                    *cursor = full_span.end();
+                }
+                TemplateNode::Variable { .. }
+                | TemplateNode::Opaque { .. }
+                | TemplateNode::StandaloneTag { .. }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Text { .. }
+                | TemplateNode::Error { .. } => {}
+            }
+        }
+    }
+
+    fn emit(&mut self, level: usize, start: u32, end: u32, block: Option<&str>) {
+        if end <= start {
+            return;
+        }
+        let file = self.levels[level].file;
+        if let Some(last) = self.segments.last_mut()
+            && last.file == file
+            && last.span.end() == start
+            && last.block.as_deref() == block
+        {
+            last.span =
+                Span::saturating_from_bounds_usize(last.span.start() as usize, end as usize);
+            return;
+        }
+        self.segments.push(TraceSegment {
+            file,
+            span: Span::saturating_from_bounds_usize(start as usize, end as usize),
+            block: block.map(str::to_string),
+        });
+    }
+}
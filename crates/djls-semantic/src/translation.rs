@@ -0,0 +1,128 @@
+//! Template text that reaches users without going through a translation tag.
+
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+
+use crate::db::Db;
+use crate::html::HtmlEvent;
+use crate::html::HtmlScanner;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::structure::build_template_tree_for_file;
+use crate::validation::BLOCK_TRANSLATE_TAGS;
+
+/// A run of text a template renders outside `{% trans %}` and `{% blocktrans %}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UntranslatedText {
+    /// The run without its surrounding whitespace.
+    pub span: Span,
+    /// The run with its whitespace collapsed, for display.
+    pub text: String,
+}
+
+/// The text runs in `file` that users would read untranslated, in source order.
+///
+/// A run is character data between HTML tags, variables, and template tags that contains a
+/// letter once character references such as `&nbsp;` are removed. Text inside
+/// `{% blocktrans %}` bodies, comments, `<script>` and `<style>` elements, and opaque tags such
+/// as `{% verbatim %}` is not counted. Attribute values such as `title` and `alt` are not read.
+pub fn untranslated_text(db: &dyn Db, file: File) -> Vec<UntranslatedText> {
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return Vec::new();
+    };
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    let regions = tree.regions(db);
+
+    let mut found = Vec::new();
+    collect_region(source.as_str(), regions, tree.root(db), &mut found);
+    found
+}
+
+fn collect_region(
+    source: &str,
+    regions: &Regions,
+    region: RegionId,
+    found: &mut Vec<UntranslatedText>,
+) {
+    let mut scanner = HtmlScanner::default();
+    for node in regions.get(region).nodes() {
+        match node {
+            TemplateNode::Text { span } => {
+                let text = source
+                    .get(span.start_usize()..span.end_usize())
+                    .unwrap_or("");
+                for event in scanner.text(text, span.start_usize()) {
+                    if let HtmlEvent::Text(span) = event
+                        && let Some(run) = untranslated_run(source, span)
+                    {
+                        found.push(run);
+                    }
+                }
+            }
+            TemplateNode::Variable { span, .. } => scanner.variable(*span),
+            TemplateNode::StandaloneTag { full_span, .. }
+            | TemplateNode::Opaque { full_span, .. } => scanner.template_tag(*full_span),
+            TemplateNode::Block {
+                tag,
+                full_span,
+                body,
+                role: BlockRole::Opener,
+                ..
+            } => {
+                scanner.template_tag(*full_span);
+                if BLOCK_TRANSLATE_TAGS.contains(&tag.as_str()) {
+                    continue;
+                }
+                for segment in regions.get(*body).nodes() {
+                    if let TemplateNode::Block { body, .. } = segment {
+                        collect_region(source, regions, *body, found);
+                    }
+                }
+            }
+            TemplateNode::Block {
+                role: BlockRole::Segment,
+                ..
+            }
+            | TemplateNode::Comment { .. }
+            | TemplateNode::Error { .. } => {}
+        }
+    }
+}
+
+fn untranslated_run(source: &str, span: Span) -> Option<UntranslatedText> {
+    let raw = source.get(span.start_usize()..span.end_usize())?;
+    let trimmed = raw.trim();
+    if !has_words(trimmed) {
+        return None;
+    }
+    let start = span.start_usize() + (raw.len() - raw.trim_start().len());
+    Some(UntranslatedText {
+        span: Span::saturating_from_bounds_usize(start, start + trimmed.len()),
+        text: trimmed.split_whitespace().collect::<Vec<_>>().join(" "),
+    })
+}
+
+/// Whether `text` has a letter outside its character references.
+fn has_words(text: &str) -> bool {
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        if rest[..amp].chars().any(char::is_alphabetic) {
+            return true;
+        }
+        rest = &rest[amp + 1..];
+        let reference = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+            .unwrap_or(rest.len());
+        if rest[reference..].starts_with(';') {
+            rest = &rest[reference + 1..];
+        }
+    }
+    rest.chars().any(char::is_alphabetic)
+}
@@ -1,17 +1,45 @@
+mod accessibility;
+mod admin;
 mod arguments;
+mod deprecations;
+mod extends;
 mod filters;
+mod forloop;
+mod forms;
+mod i18n;
 mod if_expressions;
+mod includes;
+mod namespaces;
+mod safety;
 mod scoping;
+mod shadowed_templates;
+mod shadowing;
+mod spacing;
+
+pub(crate) use i18n::BLOCK_TRANSLATE_TAGS;
+pub(crate) use i18n::block_translate_bindings;
+
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::NodeList;
+use djls_templates::TagBit;
+use rustc_hash::FxHashMap;
+use rustc_hash::FxHashSet;
+use salsa::Accumulator;
 
 use crate::TagSpec;
+use crate::ValidationError;
+use crate::ValidationErrorAccumulator;
 use crate::db::Db;
 use crate::references::TemplateReferenceKind;
 use crate::scoping::TemplateAnalysisProjection;
 use crate::structure::ActiveTemplateNode;
 use crate::structure::ActiveTemplateTag;
 use crate::structure::ActiveTemplateVariable;
+use crate::structure::StructuralOccurrenceMeaning;
 use crate::structure::active_template_nodes;
 use crate::tags::TagRole;
+use crate::validation::deprecations::DeprecatedKind;
 
 /// Tracks the validation state for `{% extends %}` positioning rules.
 #[derive(Debug, Clone, Copy, Default)]
@@ -19,14 +47,15 @@ enum ExtendsPosition {
     #[default]
     Start,
     AfterContent,
-    AfterExtends,
+    /// Records the first `{% extends %}` tag's span.
+    AfterExtends(Span),
 }
 
 impl ExtendsPosition {
     fn record_non_text(self) -> Self {
         match self {
             Self::Start | Self::AfterContent => Self::AfterContent,
-            Self::AfterExtends => Self::AfterExtends,
+            Self::AfterExtends(first_span) => Self::AfterExtends(first_span),
         }
     }
 }
@@ -36,17 +65,32 @@ impl ExtendsPosition {
 /// Construction performs no grammar, load, symbol, or Filter reconstruction.
 pub(crate) struct TemplateValidator<'db> {
     db: &'db dyn Db,
+    file: File,
+    nodelist: NodeList<'db>,
     projection: TemplateAnalysisProjection<'db>,
     extends_position: ExtendsPosition,
+    /// Name span of the first `{% block %}` seen for each block name.
+    block_names: FxHashMap<String, Span>,
+    /// Name offsets of tags that may render a CSRF token.
+    csrf_token_sources: FxHashSet<u32>,
 }
 
 impl<'db> TemplateValidator<'db> {
     #[must_use]
-    pub(crate) fn new(db: &'db dyn Db, projection: TemplateAnalysisProjection<'db>) -> Self {
+    pub(crate) fn new(
+        db: &'db dyn Db,
+        file: File,
+        nodelist: NodeList<'db>,
+        projection: TemplateAnalysisProjection<'db>,
+    ) -> Self {
         Self {
             db,
+            file,
+            nodelist,
             projection,
             extends_position: ExtendsPosition::default(),
+            block_names: FxHashMap::default(),
+            csrf_token_sources: FxHashSet::default(),
         }
     }
 
@@ -59,6 +103,47 @@ impl<'db> TemplateValidator<'db> {
                 ActiveTemplateNode::Variable(variable) => self.validate_variable(*variable),
             }
         }
+        if matches!(self.extends_position, ExtendsPosition::AfterExtends(_))
+            && let Ok(source) = self.file.try_source(self.db)
+        {
+            extends::check_content_outside_blocks_rule(
+                self.db,
+                source.as_str(),
+                tree.regions(self.db),
+                tree.root(self.db),
+                self.db.diagnostics_config(self.file).strict_child_templates(),
+            );
+        }
+        forloop::check_forloop_rule(
+            self.db,
+            self.file,
+            tree.regions(self.db),
+            tree.root(self.db),
+        );
+        shadowing::check_shadowing_rule(self.db, tree.regions(self.db), tree.root(self.db));
+        if let Ok(source) = self.file.try_source(self.db) {
+            forms::check_csrf_token_rule(
+                self.db,
+                source.as_str(),
+                tree.regions(self.db),
+                tree.root(self.db),
+                &self.csrf_token_sources,
+            );
+            spacing::check_tag_spacing_rule(
+                self.db,
+                self.file,
+                source.as_str(),
+                self.nodelist.nodelist(self.db),
+            );
+            accessibility::check_accessibility_rule(
+                self.db,
+                source.as_str(),
+                tree.regions(self.db),
+                tree.root(self.db),
+            );
+        }
+        admin::check_admin_override_blocks_rule(self.db, self.file);
+        shadowed_templates::check_shadowed_template_rule(self.db, self.file);
     }
 
     fn validate_tag(&mut self, tag: ActiveTemplateTag<'_>) {
@@ -71,15 +156,14 @@ impl<'db> TemplateValidator<'db> {
         let effective_spec = facts.spec.as_ref();
         let effective_role = effective_spec.and_then(TagSpec::role);
 
+        if forms::may_render_csrf_token(name, effective_spec, effective_role) {
+            self.csrf_token_sources.insert(tag.name_span.start());
+        }
+
         if matches!(
             effective_role,
             Some(TagRole::TemplateReference(TemplateReferenceKind::Extends))
         ) {
-            use salsa::Accumulator;
-
-            use crate::ValidationError;
-            use crate::ValidationErrorAccumulator;
-
             match self.extends_position {
                 ExtendsPosition::Start => {}
                 ExtendsPosition::AfterContent => {
@@ -88,14 +172,31 @@ impl<'db> TemplateValidator<'db> {
                     })
                     .accumulate(self.db);
                 }
-                ExtendsPosition::AfterExtends => {
+                ExtendsPosition::AfterExtends(first_span) => {
                     ValidationErrorAccumulator(ValidationError::MultipleExtends {
                         span: tag.full_span,
+                        first_span,
                     })
                     .accumulate(self.db);
                 }
             }
-            self.extends_position = ExtendsPosition::AfterExtends;
+            if !matches!(self.extends_position, ExtendsPosition::AfterExtends(_)) {
+                self.extends_position = ExtendsPosition::AfterExtends(tag.full_span);
+            }
+            extends::check_extends_argument_rule(self.db, bits);
+        }
+
+        if matches!(
+            effective_role,
+            Some(TagRole::TemplateReference(TemplateReferenceKind::Include))
+        ) {
+            includes::check_include_context_rule(self.db, self.file, bits);
+        }
+
+        if effective_role == Some(TagRole::TemplateBlock)
+            && tag.structural_meaning == StructuralOccurrenceMeaning::Definition
+        {
+            self.record_block_name(bits);
         }
 
         if effective_role != Some(TagRole::TemplateLibraryLoader)
@@ -103,13 +204,27 @@ impl<'db> TemplateValidator<'db> {
         {
             scoping::check_tag_scoping_rule(
                 self.db,
+                self.file,
                 name,
+                tag.name_span,
                 span,
                 &facts.availability,
                 facts.unknown_load_can_shadow,
             );
         }
 
+        deprecations::check_deprecated_symbol_rule(
+            self.db,
+            DeprecatedKind::Tag,
+            name,
+            tag.name_span,
+            &facts.availability,
+        );
+
+        if let Some(TagRole::TemplateReference(kind)) = effective_role {
+            namespaces::check_template_namespace_rule(self.db, self.file, kind, bits);
+        }
+
         if let Some(spec) = effective_spec
             && let Some(rules) = spec.extracted_rules()
         {
@@ -117,16 +232,71 @@ impl<'db> TemplateValidator<'db> {
         }
 
         if effective_role == Some(TagRole::TemplateLibraryLoader) {
-            scoping::check_load_libraries_rule(self.db, &facts.loader_arguments);
+            scoping::check_load_libraries_rule(self.db, self.file, &facts.loader_arguments);
+            scoping::check_load_symbols_rule(self.db, &facts.missing_load_symbols);
+            scoping::check_unused_load_rule(self.db, self.file, &facts.unused_loads);
         }
 
         if effective_role == Some(TagRole::ControlTag) && (name == "if" || name == "elif") {
             if_expressions::check_if_expression_rule(self.db, name, bits, span);
         }
 
+        if let Some(spec) = effective_spec
+            && spec.module() == i18n::I18N_MODULE
+            && tag.structural_meaning == StructuralOccurrenceMeaning::Definition
+        {
+            match name {
+                "trans" | "translate" => i18n::check_translate_rule(self.db, name, bits),
+                "blocktrans" | "blocktranslate" => {
+                    if let Some(body) = tag.body
+                        && let Ok(source) = self.file.try_source(self.db)
+                    {
+                        let regions = self.projection.tree(self.db).regions(self.db);
+                        i18n::check_block_translate_rule(
+                            self.db,
+                            source.as_str(),
+                            name,
+                            bits,
+                            regions,
+                            body,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if name == "autoescape"
+            && effective_spec.is_some_and(|spec| spec.module() == safety::DEFAULT_TAGS_MODULE)
+            && tag.structural_meaning == StructuralOccurrenceMeaning::Definition
+        {
+            safety::check_autoescape_rule(self.db, bits, tag.full_span);
+        }
+
         self.extends_position = self.extends_position.record_non_text();
     }
 
+    /// Django rejects a template that defines the same block name twice, wherever the
+    /// second definition is nested.
+    fn record_block_name(&mut self, bits: &[TagBit]) {
+        let Some(bit) = bits.first() else {
+            return;
+        };
+        match self.block_names.get(bit.as_str()) {
+            Some(first_span) => {
+                ValidationErrorAccumulator(ValidationError::DuplicateBlockName {
+                    name: bit.as_str().to_string(),
+                    span: bit.span,
+                    first_span: *first_span,
+                })
+                .accumulate(self.db);
+            }
+            None => {
+                self.block_names.insert(bit.as_str().to_string(), bit.span);
+            }
+        }
+    }
+
     fn validate_variable(&mut self, variable: ActiveTemplateVariable<'_>) {
         for filter in variable.filters {
             let Some(facts) = self
@@ -138,16 +308,26 @@ impl<'db> TemplateValidator<'db> {
             };
             scoping::check_filter_scoping_rule(
                 self.db,
+                self.file,
                 filter,
                 &facts.availability,
                 facts.unknown_load_can_shadow,
             );
+            deprecations::check_deprecated_symbol_rule(
+                self.db,
+                DeprecatedKind::Filter,
+                &filter.name,
+                filter.span.with_length_usize_saturating(filter.name.len()),
+                &facts.availability,
+            );
             if !facts.unknown_load_can_shadow
                 && let Some(arity) = facts.arity.as_ref()
             {
                 filters::check_filter_arity_rule(self.db, filter, arity);
+                filters::check_filter_argument_rule(self.db, filter, arity);
             }
         }
+        safety::check_safe_output_rule(self.db, self.file, self.projection, variable);
 
         self.extends_position = self.extends_position.record_non_text();
     }
@@ -0,0 +1,206 @@
+use djls_source::Span;
+use djls_templates::TagBit;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::html::HtmlEvent;
+use crate::html::HtmlScanner;
+use crate::html::StartTag;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+
+/// Bound field attributes that render the field's `<label>` or `<legend>`.
+const LABEL_LOOKUPS: &[&str] = &["label_tag", "legend_tag", "as_field_group"];
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Reads the HTML a template writes for problems screen reader users run into: an `<img>`
+/// without `alt` (A100), a form field rendered in a `{% for field in form %}` loop that never
+/// renders a label (A101), and a literal `id` that a `{% for %}` body repeats on every
+/// iteration (A102). The checks are heuristics, so the A series is off unless configured.
+pub(crate) fn check_accessibility_rule(
+    db: &dyn Db,
+    source: &str,
+    regions: &Regions,
+    root: RegionId,
+) {
+    AccessibilityScan {
+        db,
+        source,
+        regions,
+    }
+    .region(root, None, None);
+}
+
+struct AccessibilityScan<'a> {
+    db: &'a dyn Db,
+    source: &'a str,
+    regions: &'a Regions,
+}
+
+impl AccessibilityScan<'_> {
+    /// `enclosing_loop` is the opening tag of the innermost `{% for %}` whose body holds
+    /// `region`, and `fields` the form field loop it belongs to, if any.
+    fn region(
+        &self,
+        region: RegionId,
+        enclosing_loop: Option<Span>,
+        mut fields: Option<&mut FieldLoop>,
+    ) {
+        let mut scanner = HtmlScanner::default();
+        for node in self.regions.get(region).nodes() {
+            match node {
+                TemplateNode::Text { span } => {
+                    let text = self
+                        .source
+                        .get(span.start_usize()..span.end_usize())
+                        .unwrap_or("");
+                    for event in scanner.text(text, span.start_usize()) {
+                        if let HtmlEvent::Start(tag) = event {
+                            self.start_tag(&tag, enclosing_loop, fields.as_deref_mut());
+                        }
+                    }
+                }
+                TemplateNode::Variable {
+                    var,
+                    var_span,
+                    span,
+                    ..
+                } => {
+                    scanner.variable(*span);
+                    if let Some(fields) = fields.as_deref_mut() {
+                        fields.variable(var, *var_span);
+                    }
+                }
+                TemplateNode::StandaloneTag { full_span, .. }
+                | TemplateNode::Opaque { full_span, .. } => scanner.template_tag(*full_span),
+                TemplateNode::Block {
+                    tag,
+                    bits,
+                    full_span,
+                    body,
+                    role: BlockRole::Opener,
+                    ..
+                } => {
+                    scanner.template_tag(*full_span);
+                    let mut own_fields = if tag == "for" {
+                        FieldLoop::new(bits)
+                    } else {
+                        None
+                    };
+                    for (index, segment) in self.regions.get(*body).nodes().iter().enumerate() {
+                        let TemplateNode::Block { body, .. } = segment else {
+                            continue;
+                        };
+                        // `{% empty %}` renders once, outside the loop.
+                        if index == 0 && tag == "for" {
+                            let inner = own_fields.as_mut().or(fields.as_deref_mut());
+                            self.region(*body, Some(*full_span), inner);
+                        } else {
+                            self.region(*body, enclosing_loop, fields.as_deref_mut());
+                        }
+                    }
+                    if let Some(own_fields) = own_fields {
+                        own_fields.report(self.db);
+                    }
+                }
+                TemplateNode::Block {
+                    role: BlockRole::Segment,
+                    ..
+                }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Error { .. } => {}
+            }
+        }
+    }
+
+    fn start_tag(
+        &self,
+        tag: &StartTag,
+        enclosing_loop: Option<Span>,
+        fields: Option<&mut FieldLoop>,
+    ) {
+        if tag.name == "img" && tag.attribute("alt").is_none() && !tag.has_template_tag {
+            ValidationErrorAccumulator(ValidationError::ImageWithoutAlt { span: tag.span })
+                .accumulate(self.db);
+        }
+
+        if tag.name == "label"
+            && let Some(fields) = fields
+        {
+            fields.labelled = true;
+        }
+
+        if let Some(loop_span) = enclosing_loop
+            && let Some(id) = tag.static_value("id")
+            && !id.text.trim().is_empty()
+        {
+            ValidationErrorAccumulator(ValidationError::RepeatedIdInLoop {
+                id: id.text.clone(),
+                span: id.span,
+                loop_span,
+            })
+            .accumulate(self.db);
+        }
+    }
+}
+
+/// A `{% for %}` over the fields of a form.
+struct FieldLoop {
+    field: String,
+    /// Each `{{ field }}`, which renders the widget.
+    widgets: Vec<Span>,
+    labelled: bool,
+}
+
+impl FieldLoop {
+    /// Recognizes `{% for field in form %}` and `{% for field in form.visible_fields %}`,
+    /// including forms with a prefix such as `profile_form`.
+    fn new(bits: &[TagBit]) -> Option<Self> {
+        let [field, keyword, iterable, ..] = bits else {
+            return None;
+        };
+        if keyword.as_str() != "in" || field.as_str().contains(',') {
+            return None;
+        }
+        let iterable = iterable.as_str().split('|').next().unwrap_or_default();
+        let last = iterable.rsplit('.').next().unwrap_or_default();
+        if !matches!(last, "form" | "visible_fields") && !last.ends_with("_form") {
+            return None;
+        }
+        Some(Self {
+            field: field.as_str().to_string(),
+            widgets: Vec::new(),
+            labelled: false,
+        })
+    }
+
+    fn variable(&mut self, var: &str, span: Span) {
+        if var == self.field {
+            self.widgets.push(span);
+        } else if var
+            .strip_prefix(self.field.as_str())
+            .and_then(|rest| rest.strip_prefix('.'))
+            .is_some_and(|lookup| LABEL_LOOKUPS.contains(&lookup))
+        {
+            self.labelled = true;
+        }
+    }
+
+    fn report(self, db: &dyn Db) {
+        if self.labelled {
+            return;
+        }
+        for span in self.widgets {
+            ValidationErrorAccumulator(ValidationError::FieldWithoutLabel {
+                field: self.field.clone(),
+                span,
+            })
+            .accumulate(db);
+        }
+    }
+}
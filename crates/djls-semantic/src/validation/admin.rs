@@ -0,0 +1,67 @@
+use djls_source::File;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use salsa::Accumulator;
+
+use crate::admin::admin_block_names;
+use crate::admin::admin_override_for_file;
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::inheritance::ExtendsTarget;
+use crate::inheritance::ancestor_blocks;
+use crate::inheritance::template_symbols;
+use crate::suggest::similar_names;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// A template that overrides an admin template and extends one of Django's admin templates can
+/// only fill blocks that template defines; any other top-level block is silently never
+/// rendered. Names are checked against the bundled admin block list, and against the parent
+/// chain when Django's own templates are on disk. Blocks nested in another block are new
+/// blocks rather than overrides and are left alone.
+pub(crate) fn check_admin_override_blocks_rule(db: &dyn Db, file: File) {
+    if admin_override_for_file(db, file).is_none() {
+        return;
+    }
+    let Some(project) = db.project() else {
+        return;
+    };
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return;
+    };
+    let symbols = template_symbols(db, file, nodelist);
+    let Some(ExtendsTarget::Literal { name: parent, .. }) = symbols.extends() else {
+        return;
+    };
+    let known = admin_block_names(parent);
+    if known.is_empty() {
+        return;
+    }
+
+    let blocks = symbols.blocks();
+    for block in blocks {
+        let nested = blocks.iter().any(|outer| {
+            outer.full_span != block.full_span
+                && outer.full_span.start() <= block.full_span.start()
+                && block.full_span.end() <= outer.full_span.end()
+        });
+        if nested || known.contains(&block.name.as_str()) {
+            continue;
+        }
+        if !ancestor_blocks(db, project, file, &block.name).is_empty() {
+            continue;
+        }
+
+        ValidationErrorAccumulator(ValidationError::UnknownAdminBlock {
+            name: block.name.clone(),
+            template: parent.clone(),
+            span: block.name_span,
+            suggestions: similar_names(&block.name, known.iter().copied())
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        })
+        .accumulate(db);
+    }
+}
@@ -0,0 +1,158 @@
+use djls_project::DjangoVersion;
+use djls_project::django_version;
+use djls_source::Span;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::scoping::symbols::SymbolAvailability;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DeprecatedKind {
+    Tag,
+    Filter,
+    Library,
+}
+
+impl DeprecatedKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Tag => "tag",
+            Self::Filter => "filter",
+            Self::Library => "library",
+        }
+    }
+}
+
+/// A builtin tag, filter, or library that Django deprecated and then removed.
+struct Deprecation {
+    kind: DeprecatedKind,
+    name: &'static str,
+    deprecated_in: DjangoVersion,
+    removed_in: DjangoVersion,
+    advice: &'static str,
+    /// Name to write instead, when the upgrade is a plain rename.
+    replacement: Option<&'static str>,
+}
+
+const DEPRECATIONS: &[Deprecation] = &[
+    Deprecation {
+        kind: DeprecatedKind::Library,
+        name: "staticfiles",
+        deprecated_in: DjangoVersion::new(2, 1),
+        removed_in: DjangoVersion::new(3, 0),
+        advice: "load 'static' instead",
+        replacement: Some("static"),
+    },
+    Deprecation {
+        kind: DeprecatedKind::Library,
+        name: "admin_static",
+        deprecated_in: DjangoVersion::new(2, 1),
+        removed_in: DjangoVersion::new(3, 0),
+        advice: "load 'static' instead",
+        replacement: Some("static"),
+    },
+    Deprecation {
+        kind: DeprecatedKind::Tag,
+        name: "ifequal",
+        deprecated_in: DjangoVersion::new(3, 1),
+        removed_in: DjangoVersion::new(4, 0),
+        advice: "use '{% if a == b %}' instead",
+        replacement: None,
+    },
+    Deprecation {
+        kind: DeprecatedKind::Tag,
+        name: "ifnotequal",
+        deprecated_in: DjangoVersion::new(3, 1),
+        removed_in: DjangoVersion::new(4, 0),
+        advice: "use '{% if a != b %}' instead",
+        replacement: None,
+    },
+    Deprecation {
+        kind: DeprecatedKind::Filter,
+        name: "length_is",
+        deprecated_in: DjangoVersion::new(4, 2),
+        removed_in: DjangoVersion::new(5, 1),
+        advice: "compare with the 'length' filter in '{% if %}' instead",
+        replacement: None,
+    },
+];
+
+fn deprecation(kind: DeprecatedKind, name: &str) -> Option<&'static Deprecation> {
+    DEPRECATIONS
+        .iter()
+        .find(|deprecation| deprecation.kind == kind && deprecation.name == name)
+}
+
+/// Internal helper for the scoping rules.
+///
+/// Reports a builtin that Django removed in place of the generic unknown-symbol error, so the
+/// message says how to upgrade. Returns whether it reported.
+pub(crate) fn report_removed_symbol(
+    db: &dyn Db,
+    kind: DeprecatedKind,
+    name: &str,
+    span: Span,
+) -> bool {
+    let Some(deprecation) = deprecation(kind, name) else {
+        return false;
+    };
+    if db
+        .project()
+        .and_then(|project| django_version(db, project))
+        .is_some_and(|version| version < deprecation.removed_in)
+    {
+        return false;
+    }
+
+    ValidationErrorAccumulator(ValidationError::RemovedTemplateSymbol {
+        kind: kind.as_str(),
+        name: name.to_string(),
+        removed_in: deprecation.removed_in.to_string(),
+        advice: deprecation.advice.to_string(),
+        span,
+        replacement: deprecation.replacement.map(str::to_string),
+    })
+    .accumulate(db);
+    true
+}
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Warns about a builtin that the project's Django still provides but has deprecated. Without
+/// a readable Django version nothing is reported.
+pub(crate) fn check_deprecated_symbol_rule(
+    db: &dyn Db,
+    kind: DeprecatedKind,
+    name: &str,
+    span: Span,
+    availability: &SymbolAvailability,
+) {
+    if !matches!(availability, SymbolAvailability::Available) {
+        return;
+    }
+    let Some(deprecation) = deprecation(kind, name) else {
+        return;
+    };
+    let Some(version) = db
+        .project()
+        .and_then(|project| django_version(db, project))
+    else {
+        return;
+    };
+    if version < deprecation.deprecated_in || version >= deprecation.removed_in {
+        return;
+    }
+
+    ValidationErrorAccumulator(ValidationError::DeprecatedTemplateSymbol {
+        kind: kind.as_str(),
+        name: name.to_string(),
+        deprecated_in: deprecation.deprecated_in.to_string(),
+        removed_in: deprecation.removed_in.to_string(),
+        advice: deprecation.advice.to_string(),
+        span,
+        replacement: deprecation.replacement.map(str::to_string),
+    })
+    .accumulate(db);
+}
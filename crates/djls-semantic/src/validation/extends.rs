@@ -0,0 +1,125 @@
+use djls_source::Span;
+use djls_templates::Operand;
+use djls_templates::OperandKind;
+use djls_templates::TagBit;
+use djls_templates::TagDelimiter;
+use djls_templates::TemplateString;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Django compiles the parent name as a filter expression, so only its leading operand decides
+/// what kind of reference it is. A quoted name is what inheritance features can follow; a
+/// variable is accepted at runtime but reported so the lost precision is visible, and a number
+/// can never name a template. Argument counts are left to the extracted tag rule.
+pub(crate) fn check_extends_argument_rule(db: &dyn Db, bits: &[TagBit]) {
+    let [bit] = bits else {
+        return;
+    };
+    if matches!(bit.template_string(), TemplateString::Quoted { .. }) {
+        return;
+    }
+
+    let head = bit.as_str().split_once('|').map_or(bit.as_str(), |(head, _)| head);
+    let span = Span::saturating_from_parts_usize(bit.span.start_usize(), head.len());
+    let error = match Operand::parse(head, span).kind {
+        OperandKind::String { .. } => return,
+        OperandKind::Number(_) => ValidationError::InvalidExtendsArgument {
+            argument: head.to_string(),
+            span,
+        },
+        OperandKind::Variable(_) => ValidationError::DynamicExtends {
+            variable: head.to_string(),
+            span,
+        },
+    };
+    ValidationErrorAccumulator(error).accumulate(db);
+}
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// A template that extends another renders only its `{% block %}` overrides, so text, variables,
+/// and other tags outside every block are silently dropped. The body of a tag such as `{% if %}`
+/// is walked too, since blocks nested inside it still override the parent. `{% load %}` and
+/// comments are allowed unless `strict` is set: loads still apply to the blocks after them, and
+/// comments never render anyway.
+pub(crate) fn check_content_outside_blocks_rule(
+    db: &dyn Db,
+    source: &str,
+    regions: &Regions,
+    region: RegionId,
+    strict: bool,
+) {
+    for node in regions.get(region).nodes() {
+        let (tag, span) = match node {
+            TemplateNode::Block {
+                tag,
+                full_span,
+                body,
+                role: BlockRole::Opener,
+                ..
+            } => {
+                if tag == "block" {
+                    continue;
+                }
+                for segment in regions.get(*body).nodes() {
+                    if let TemplateNode::Block { body, .. } = segment {
+                        check_content_outside_blocks_rule(db, source, regions, *body, strict);
+                    }
+                }
+                (tag.as_str(), *full_span)
+            }
+            TemplateNode::StandaloneTag { tag, full_span, .. } => {
+                if tag == "extends" || (tag == "load" && !strict) {
+                    continue;
+                }
+                (tag.as_str(), *full_span)
+            }
+            TemplateNode::Opaque { tag, full_span, .. } => {
+                if tag == "comment" && !strict {
+                    continue;
+                }
+                (tag.as_str(), *full_span)
+            }
+            TemplateNode::Comment { span } => {
+                if !strict {
+                    continue;
+                }
+                ("comment", with_delimiters(*span))
+            }
+            TemplateNode::Variable { span, .. } => ("variable", with_delimiters(*span)),
+            TemplateNode::Text { span } => {
+                let text = source.get(span.start_usize()..span.end_usize()).unwrap_or("");
+                let trimmed = text.trim_start();
+                if trimmed.trim_end().is_empty() {
+                    continue;
+                }
+                let start = span.start_usize() + text.len() - trimmed.len();
+                let span = Span::saturating_from_parts_usize(start, trimmed.trim_end().len());
+                ("text", span)
+            }
+            TemplateNode::Block {
+                role: BlockRole::Segment,
+                ..
+            }
+            | TemplateNode::Error { .. } => continue,
+        };
+        ValidationErrorAccumulator(ValidationError::ContentOutsideBlock {
+            tag: tag.to_string(),
+            span,
+        })
+        .accumulate(db);
+    }
+}
+
+fn with_delimiters(span: Span) -> Span {
+    span.expand(TagDelimiter::LENGTH_U32, TagDelimiter::LENGTH_U32)
+}
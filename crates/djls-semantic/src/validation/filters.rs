@@ -1,11 +1,20 @@
+use djls_project::FilterArgKind;
 use djls_project::FilterArity;
 use djls_templates::Filter;
+use djls_templates::TemplateString;
 use salsa::Accumulator;
 
 use crate::db::Db;
 use crate::db::ValidationErrorAccumulator;
 use crate::errors::ValidationError;
 
+/// Format characters understood by `django.utils.dateformat`.
+///
+/// Any other ASCII letter is emitted literally, which is almost always a
+/// mistake rather than intent (unescaped literal letters must be written as
+/// `\x`).
+const DATE_FORMAT_CHARS: &str = "aAbcdDeEfFgGhHiIjlLmMnNoOPrsStTuUwWyYzZ";
+
 /// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
 pub(crate) fn check_filter_arity_rule(db: &dyn Db, filter: &Filter, arity: &FilterArity) {
     let has_arg = filter.arg.is_some();
@@ -26,3 +35,90 @@ pub(crate) fn check_filter_arity_rule(db: &dyn Db, filter: &Filter, arity: &Filt
         .accumulate(db);
     }
 }
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Only quoted literals are checked. Unquoted arguments are numbers or
+/// variables, and variables resolve at render time.
+pub(crate) fn check_filter_argument_rule(db: &dyn Db, filter: &Filter, arity: &FilterArity) {
+    let (Some(kind), Some(arg)) = (arity.arg_kind.as_ref(), filter.arg.as_ref()) else {
+        return;
+    };
+    let TemplateString::Quoted { value, .. } = arg.template_string() else {
+        return;
+    };
+    let Some(message) = invalid_literal_message(&filter.name, kind, value) else {
+        return;
+    };
+
+    // S124: literal argument Django would silently mishandle
+    ValidationErrorAccumulator(ValidationError::InvalidFilterArgument {
+        filter: filter.name.clone(),
+        message,
+        span: arg.span,
+    })
+    .accumulate(db);
+}
+
+fn invalid_literal_message(filter: &str, kind: &FilterArgKind, value: &str) -> Option<String> {
+    match kind {
+        FilterArgKind::Integer => value
+            .trim()
+            .parse::<i64>()
+            .is_err()
+            .then(|| format!("Filter '{filter}' expects an integer argument, got \"{value}\"")),
+        FilterArgKind::DateFormat => invalid_date_format_message(filter, value),
+        FilterArgKind::Delimited {
+            delimiter,
+            min_items,
+            max_items,
+        } => {
+            let items = value.split(delimiter.as_str()).count();
+            if let Some(max) = max_items
+                && items > *max
+            {
+                return Some(format!(
+                    "Filter '{filter}' expects at most {max} '{delimiter}'-separated values, got {items}"
+                ));
+            }
+            if let Some(min) = min_items
+                && items < *min
+            {
+                return Some(format!(
+                    "Filter '{filter}' expects at least {min} '{delimiter}'-separated values, got {items}"
+                ));
+            }
+            None
+        }
+    }
+}
+
+fn invalid_date_format_message(filter: &str, value: &str) -> Option<String> {
+    // Named formats such as "SHORT_DATE_FORMAT" are looked up in settings.
+    if value.ends_with("_FORMAT")
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_uppercase() || ch == '_')
+    {
+        return None;
+    }
+
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            chars.next();
+            continue;
+        }
+        if ch == '%' {
+            return Some(format!(
+                "Filter '{filter}' format uses strftime-style '%' directives; Django date formats use letters such as \"Y-m-d\""
+            ));
+        }
+        if ch.is_ascii_alphabetic() && !DATE_FORMAT_CHARS.contains(ch) {
+            return Some(format!(
+                "Filter '{filter}' format contains unknown format character '{ch}'; escape literal letters with '\\'"
+            ));
+        }
+    }
+    None
+}
@@ -0,0 +1,65 @@
+use std::iter;
+
+use djls_project::template_resolution;
+use djls_source::File;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::references::TemplateReferenceKind;
+use crate::references::references_to_template_name;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::variables::forloop_references;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Django only defines `forloop` while rendering the body of a `{% for %}`,
+/// and `forloop.parentloop` is empty unless that loop sits inside another, so
+/// either lookup renders as an empty string where the loops are missing. A
+/// Template that another includes is skipped, since the include may sit in a
+/// loop and pass its `forloop` along with the rest of the context.
+pub(crate) fn check_forloop_rule(db: &dyn Db, file: File, regions: &Regions, root: RegionId) {
+    let references = forloop_references(regions, root);
+    if references.is_empty() || is_included(db, file) {
+        return;
+    }
+
+    for reference in references {
+        let error = if reference.loops == 0 {
+            ValidationError::ForloopOutsideLoop {
+                span: reference.span,
+            }
+        } else if reference.parentloops >= reference.loops {
+            let lookup = iter::once("forloop")
+                .chain(iter::repeat_n("parentloop", reference.parentloops))
+                .collect::<Vec<_>>()
+                .join(".");
+            ValidationError::ParentloopWithoutOuterLoop {
+                lookup,
+                needed: reference.parentloops + 1,
+                found: reference.loops,
+                span: reference.span,
+            }
+        } else {
+            continue;
+        };
+        ValidationErrorAccumulator(error).accumulate(db);
+    }
+}
+
+/// Whether any Template in the project names `file` in an `{% include %}`.
+fn is_included(db: &dyn Db, file: File) -> bool {
+    let Some(project) = db.project() else {
+        return false;
+    };
+    template_resolution(db, project)
+        .template_names_for_file(db, file)
+        .iter()
+        .any(|name| {
+            references_to_template_name(db, project, *name)
+                .iter()
+                .any(|reference| reference.kind(db) == TemplateReferenceKind::Include)
+        })
+}
@@ -0,0 +1,186 @@
+use djls_source::Span;
+use rustc_hash::FxHashSet;
+use salsa::Accumulator;
+
+use crate::TagSpec;
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::html::HtmlEvent;
+use crate::html::HtmlScanner;
+use crate::html::StartTag;
+use crate::references::TemplateReferenceKind;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::tags::TagRole;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Whether a tag can put a CSRF token into the form around it: `{% csrf_token %}` itself, an
+/// `{% include %}` or a tag from outside Django that may render one, and a `{% block %}` that a
+/// child template may fill with one. Unknown tags are given the benefit of the doubt.
+pub(crate) fn may_render_csrf_token(
+    name: &str,
+    spec: Option<&TagSpec>,
+    role: Option<TagRole>,
+) -> bool {
+    let Some(spec) = spec else {
+        return true;
+    };
+    name == "csrf_token"
+        || matches!(
+            role,
+            Some(
+                TagRole::TemplateBlock
+                    | TagRole::TemplateReference(TemplateReferenceKind::Include)
+            )
+        )
+        || !spec.module().starts_with("django.")
+}
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Django's CSRF middleware rejects a POST whose form carries no token, so a
+/// `<form method="post">` needs `{% csrf_token %}` before its `</form>`. `sources` holds the name
+/// offsets of tags that [may render one](may_render_csrf_token). Only forms that open and close
+/// in the same block body are checked; a form closed in another block or template is left alone.
+pub(crate) fn check_csrf_token_rule(
+    db: &dyn Db,
+    source: &str,
+    regions: &Regions,
+    root: RegionId,
+    sources: &FxHashSet<u32>,
+) {
+    FormScan {
+        db,
+        source,
+        regions,
+        sources,
+    }
+    .region(root);
+}
+
+struct FormScan<'a> {
+    db: &'a dyn Db,
+    source: &'a str,
+    regions: &'a Regions,
+    sources: &'a FxHashSet<u32>,
+}
+
+impl FormScan<'_> {
+    /// Reports the unprotected forms in `region` and returns whether it holds a token source
+    /// anywhere, so a form in the enclosing body counts a token inside an `{% if %}` as its own.
+    fn region(&self, region: RegionId) -> bool {
+        let mut scanner = HtmlScanner::default();
+        let mut forms = Vec::<OpenForm>::new();
+        let mut provides = false;
+        for node in self.regions.get(region).nodes() {
+            let found = match node {
+                TemplateNode::Text { span } => {
+                    let text = self
+                        .source
+                        .get(span.start_usize()..span.end_usize())
+                        .unwrap_or("");
+                    for event in scanner.text(text, span.start_usize()) {
+                        match event {
+                            HtmlEvent::Start(tag) if tag.name == "form" => {
+                                forms.push(OpenForm::new(&tag));
+                            }
+                            HtmlEvent::End(name) if name == "form" => {
+                                if let Some(form) = forms.pop() {
+                                    form.report(self.db);
+                                }
+                            }
+                            HtmlEvent::Start(_) | HtmlEvent::End(_) | HtmlEvent::Text(_) => {}
+                        }
+                    }
+                    false
+                }
+                TemplateNode::Variable { span, .. } => {
+                    scanner.variable(*span);
+                    false
+                }
+                TemplateNode::StandaloneTag {
+                    name_span,
+                    full_span,
+                    ..
+                } => {
+                    scanner.template_tag(*full_span);
+                    self.sources.contains(&name_span.start())
+                }
+                TemplateNode::Block {
+                    name_span,
+                    full_span,
+                    body,
+                    role: BlockRole::Opener,
+                    ..
+                } => {
+                    scanner.template_tag(*full_span);
+                    let mut found = self.sources.contains(&name_span.start());
+                    for segment in self.regions.get(*body).nodes() {
+                        if let TemplateNode::Block { body, .. } = segment {
+                            found |= self.region(*body);
+                        }
+                    }
+                    found
+                }
+                TemplateNode::Opaque { full_span, .. } => {
+                    scanner.template_tag(*full_span);
+                    false
+                }
+                TemplateNode::Block {
+                    role: BlockRole::Segment,
+                    ..
+                }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Error { .. } => false,
+            };
+            if found {
+                for form in &mut forms {
+                    form.covered = true;
+                }
+                provides = true;
+            }
+        }
+        provides
+    }
+}
+
+/// A `<form>` whose `</form>` has not been seen yet.
+struct OpenForm {
+    span: Span,
+    post: bool,
+    /// Posts to another site, which must not receive the token.
+    external: bool,
+    covered: bool,
+}
+
+impl OpenForm {
+    fn new(tag: &StartTag) -> Self {
+        let value = |name| {
+            tag.attribute(name)
+                .and_then(|attribute| attribute.value.as_ref())
+                .map(|value| value.text.to_ascii_lowercase())
+        };
+        Self {
+            span: tag.span,
+            post: value("method").is_some_and(|method| method.trim() == "post"),
+            external: value("action").is_some_and(|action| {
+                ["http://", "https://", "//"]
+                    .into_iter()
+                    .any(|scheme| action.starts_with(scheme))
+            }),
+            covered: false,
+        }
+    }
+
+    fn report(&self, db: &dyn Db) {
+        if !self.post || self.external || self.covered {
+            return;
+        }
+        ValidationErrorAccumulator(ValidationError::MissingCsrfToken { span: self.span })
+            .accumulate(db);
+    }
+}
@@ -0,0 +1,327 @@
+use djls_source::Span;
+use djls_templates::Filter;
+use djls_templates::TagBit;
+use djls_templates::variable_expression;
+use rustc_hash::FxHashSet;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+
+/// Module that registers Django's translation tags.
+pub(crate) const I18N_MODULE: &str = "django.templatetags.i18n";
+
+/// Names `{% blocktrans %}` is registered under.
+pub(crate) const BLOCK_TRANSLATE_TAGS: [&str; 2] = ["blocktrans", "blocktranslate"];
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// `makemessages` only extracts `{% trans %}` messages written as string
+/// literals; a variable renders fine but never reaches the catalog.
+pub(crate) fn check_translate_rule(db: &dyn Db, name: &str, bits: &[TagBit]) {
+    let Some(message) = bits.first() else {
+        return;
+    };
+    if message.as_str().starts_with(['"', '\'']) {
+        return;
+    }
+    ValidationErrorAccumulator(ValidationError::TranslationNonLiteral {
+        tag: name.to_string(),
+        span: message.span,
+    })
+    .accumulate(db);
+}
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Checks the variables inside a `{% blocktrans %}` body. Django turns each
+/// `{{ var }}` into a `%(var)s` placeholder, so lookups and filters must be
+/// bound with `with` first, and both plural branches must agree on the
+/// placeholders apart from the `count` variable. Placeholders written out by
+/// hand must name a `with` or `count` binding.
+pub(crate) fn check_block_translate_rule(
+    db: &dyn Db,
+    source: &str,
+    name: &str,
+    bits: &[TagBit],
+    regions: &Regions,
+    body: RegionId,
+) {
+    let mut singular = Vec::new();
+    let mut plural = None;
+    let bindings = block_translate_bindings(bits);
+    for node in regions.get(body).nodes() {
+        let TemplateNode::Block {
+            tag,
+            body: segment,
+            role: BlockRole::Segment,
+            ..
+        } = node
+        else {
+            continue;
+        };
+        check_placeholder_bindings(db, source, name, &bindings, regions, *segment);
+        let variables = segment_variables(regions, *segment);
+        if tag == "plural" {
+            plural = Some(variables);
+        } else {
+            singular = variables;
+        }
+    }
+
+    for variable in singular.iter().chain(plural.iter().flatten()) {
+        if variable.var.contains('.') || !variable.filters.is_empty() {
+            ValidationErrorAccumulator(ValidationError::TranslationVariableNotBound {
+                tag: name.to_string(),
+                variable: variable_expression(variable.var, variable.filters),
+                span: variable.span,
+            })
+            .accumulate(db);
+        }
+    }
+
+    if let Some(plural) = plural {
+        let count = count_variable(bits);
+        check_plural_branch(db, name, &singular, &plural, count);
+        check_plural_branch(db, name, &plural, &singular, count);
+    }
+}
+
+/// Report `%(name)s` placeholders in a segment's text that nothing binds.
+fn check_placeholder_bindings(
+    db: &dyn Db,
+    source: &str,
+    name: &str,
+    bindings: &[&str],
+    regions: &Regions,
+    segment: RegionId,
+) {
+    for node in regions.get(segment).nodes() {
+        let TemplateNode::Text { span } = node else {
+            continue;
+        };
+        let Some(text) = source.get(span.start_usize()..span.end_usize()) else {
+            continue;
+        };
+        for placeholder in text_placeholders(text) {
+            if bindings.contains(&placeholder.name) {
+                continue;
+            }
+            ValidationErrorAccumulator(ValidationError::TranslationPlaceholderNotBound {
+                tag: name.to_string(),
+                placeholder: text[placeholder.start..placeholder.end].to_string(),
+                span: Span::saturating_from_bounds_usize(
+                    span.start_usize() + placeholder.start,
+                    span.start_usize() + placeholder.end,
+                ),
+            })
+            .accumulate(db);
+        }
+    }
+}
+
+/// Names a `{% blocktrans %}` binds for its body with `with` and `count`.
+///
+/// Follows Django's option parsing: both options take `name=expr` pairs or the
+/// legacy `expr as name` form chained with `and`, and `context` and `asvar`
+/// each consume one value.
+#[must_use]
+pub(crate) fn block_translate_bindings(bits: &[TagBit]) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut index = 0;
+    while let Some(bit) = bits.get(index) {
+        index += 1;
+        match bit.as_str() {
+            "with" | "count" => {
+                if bits.get(index).and_then(TagBit::kwarg).is_some() {
+                    while let Some(kwarg) = bits.get(index).and_then(TagBit::kwarg) {
+                        names.push(kwarg.name);
+                        index += 1;
+                    }
+                    continue;
+                }
+                while let (Some(keyword), Some(name)) = (bits.get(index + 1), bits.get(index + 2))
+                {
+                    if keyword.as_str() != "as" {
+                        break;
+                    }
+                    names.push(name.as_str());
+                    index += 3;
+                    if bits.get(index).is_some_and(|bit| bit.as_str() == "and") {
+                        index += 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            "context" | "asvar" => index += 1,
+            _ => {}
+        }
+    }
+    names
+}
+
+/// A `%(name)s`-style placeholder, with byte offsets into the text it was found in.
+struct TextPlaceholder<'a> {
+    name: &'a str,
+    start: usize,
+    end: usize,
+}
+
+/// Python `%`-format placeholders written directly in `text`, skipping `%%` escapes.
+fn text_placeholders(text: &str) -> Vec<TextPlaceholder<'_>> {
+    let mut placeholders = Vec::new();
+    let mut rest = 0;
+    while let Some(found) = text[rest..].find('%') {
+        let start = rest + found;
+        let after = &text[start + 1..];
+        if after.starts_with('%') {
+            rest = start + 2;
+            continue;
+        }
+        rest = start + 1;
+        let Some(inner) = after.strip_prefix('(') else {
+            continue;
+        };
+        let Some(close) = inner.find(')') else {
+            continue;
+        };
+        let name = &inner[..close];
+        let conversion = inner[close + 1..].chars().next();
+        if name.is_empty()
+            || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            || !conversion.is_some_and(|c| c.is_ascii_alphabetic())
+        {
+            continue;
+        }
+        // `%(` + name + `)` + conversion character.
+        let end = start + 2 + close + 2;
+        placeholders.push(TextPlaceholder { name, start, end });
+        rest = end;
+    }
+    placeholders
+}
+
+/// Report placeholders in `branch` that the `other` branch never uses.
+fn check_plural_branch(
+    db: &dyn Db,
+    name: &str,
+    branch: &[BodyVariable<'_>],
+    other: &[BodyVariable<'_>],
+    count: Option<&str>,
+) {
+    let other_names: FxHashSet<&str> = other.iter().map(|variable| variable.var).collect();
+    let mut reported = FxHashSet::default();
+    for variable in branch {
+        if Some(variable.var) == count
+            || other_names.contains(variable.var)
+            || !reported.insert(variable.var)
+        {
+            continue;
+        }
+        ValidationErrorAccumulator(ValidationError::TranslationPluralMismatch {
+            tag: name.to_string(),
+            variable: variable.var.to_string(),
+            span: variable.span,
+        })
+        .accumulate(db);
+    }
+}
+
+struct BodyVariable<'a> {
+    var: &'a str,
+    filters: &'a [Filter],
+    span: Span,
+}
+
+fn segment_variables(regions: &Regions, segment: RegionId) -> Vec<BodyVariable<'_>> {
+    regions
+        .get(segment)
+        .nodes()
+        .iter()
+        .filter_map(|node| match node {
+            TemplateNode::Variable {
+                var, filters, span, ..
+            } => Some(BodyVariable {
+                var,
+                filters,
+                span: *span,
+            }),
+            TemplateNode::Block { .. }
+            | TemplateNode::Opaque { .. }
+            | TemplateNode::StandaloneTag { .. }
+            | TemplateNode::Comment { .. }
+            | TemplateNode::Text { .. }
+            | TemplateNode::Error { .. } => None,
+        })
+        .collect()
+}
+
+/// The name bound by `count`, in either `count n=expr` or the legacy
+/// `count expr as n` form.
+fn count_variable(bits: &[TagBit]) -> Option<&str> {
+    let index = bits.iter().position(|bit| bit.as_str() == "count")?;
+    if let Some(kwarg) = bits.get(index + 1)?.kwarg() {
+        return Some(kwarg.name);
+    }
+    let keyword = bits.get(index + 2)?;
+    let name = bits.get(index + 3)?;
+    (keyword.as_str() == "as").then_some(name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(source: &str) -> Vec<TagBit> {
+        source
+            .split_whitespace()
+            .map(|bit| TagBit::new(bit.to_string(), Span::new(0, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn count_variable_reads_assignment_form() {
+        let bits = bits("count counter=items|length");
+        assert_eq!(count_variable(&bits), Some("counter"));
+    }
+
+    #[test]
+    fn count_variable_reads_legacy_as_form() {
+        let bits = bits("count items|length as counter");
+        assert_eq!(count_variable(&bits), Some("counter"));
+    }
+
+    #[test]
+    fn block_translate_bindings_reads_with_and_count() {
+        let bits = bits("with name=user.name site=site.domain count counter=items|length");
+        assert_eq!(block_translate_bindings(&bits), vec!["name", "site", "counter"]);
+    }
+
+    #[test]
+    fn block_translate_bindings_reads_legacy_as_form() {
+        let bits = bits("with user.name as name and site.domain as site context \"title\" trimmed");
+        assert_eq!(block_translate_bindings(&bits), vec!["name", "site"]);
+    }
+
+    #[test]
+    fn text_placeholders_skip_escapes_and_bare_percents() {
+        let text = "%(name)s saved 100%% of %(total)d, 50% (more) and %(bad name)s";
+        let names = text_placeholders(text)
+            .iter()
+            .map(|placeholder| &text[placeholder.start..placeholder.end])
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["%(name)s", "%(total)d"]);
+    }
+
+    #[test]
+    fn count_variable_is_none_without_count() {
+        let bits = bits("with name=user.name trimmed");
+        assert_eq!(count_variable(&bits), None);
+    }
+}
@@ -0,0 +1,60 @@
+use djls_project::TemplateName;
+use djls_project::TemplateResolutionResult;
+use djls_project::template_resolution;
+use djls_source::File;
+use djls_templates::IncludeNode;
+use djls_templates::TagBit;
+use djls_templates::TemplateString;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::references::TemplateReferenceKind;
+use crate::references::resolve_reference_for_file;
+use crate::variables::template_context_variables;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// `{% include ... only %}` renders the included template with nothing but its `with`
+/// arguments, so a variable it reads that is not passed silently renders as an empty string.
+/// Only a quoted name that resolves to a single template is followed.
+pub(crate) fn check_include_context_rule(db: &dyn Db, file: File, bits: &[TagBit]) {
+    let Some(include) = IncludeNode::from_bits(bits).filter(IncludeNode::is_isolated) else {
+        return;
+    };
+    let TemplateString::Quoted { value, .. } = include.template.template_string() else {
+        return;
+    };
+    let Some(project) = db.project() else {
+        return;
+    };
+
+    let resolution = template_resolution(db, project);
+    let name = TemplateName::new(db, value.to_string());
+    let Some(TemplateResolutionResult::Found(origin)) =
+        resolve_reference_for_file(db, resolution, file, name, TemplateReferenceKind::Include)
+    else {
+        return;
+    };
+
+    let missing = template_context_variables(db, origin.file(db))
+        .into_iter()
+        .filter(|variable| {
+            !include
+                .extra_context
+                .iter()
+                .any(|kwarg| kwarg.name == variable)
+        })
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return;
+    }
+
+    ValidationErrorAccumulator(ValidationError::IncludeMissingVariables {
+        template: value.to_string(),
+        missing,
+        span: include.template.span,
+    })
+    .accumulate(db);
+}
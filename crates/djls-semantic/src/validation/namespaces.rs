@@ -0,0 +1,75 @@
+use djls_project::TemplateName;
+use djls_project::TemplateResolutionResult;
+use djls_project::template_resolution;
+use djls_source::File;
+use djls_templates::TagBit;
+use djls_templates::TemplateString;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::references::TemplateReferenceKind;
+use crate::references::resolve_reference_for_file;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// The app directories loader searches every installed app's `templates` directory in
+/// `INSTALLED_APPS` order, so a name like `form.html` that two apps provide loads whichever
+/// app comes first. Django's convention is to namespace app templates under a directory named
+/// after the app. Only a quoted name that resolves to a single app template is checked.
+pub(crate) fn check_template_namespace_rule(
+    db: &dyn Db,
+    file: File,
+    kind: TemplateReferenceKind,
+    bits: &[TagBit],
+) {
+    let Some(target) = bits.first() else {
+        return;
+    };
+    let TemplateString::Quoted { value, .. } = target.template_string() else {
+        return;
+    };
+    let Some(project) = db.project() else {
+        return;
+    };
+
+    let resolution = template_resolution(db, project);
+    let name = TemplateName::new(db, value.to_string());
+    let Some(TemplateResolutionResult::Found(origin)) =
+        resolve_reference_for_file(db, resolution, file, name, kind)
+    else {
+        return;
+    };
+    let target_name = origin.template_name(db);
+    let Some(app) = resolution.app_for_origin(db, origin) else {
+        return;
+    };
+    if app.namespaces(target_name.name(db)) {
+        return;
+    }
+
+    let mut shadowed = Vec::new();
+    for other in resolution.origins_for_name(db, target_name) {
+        if other.file(db) == origin.file(db) {
+            continue;
+        }
+        if let Some(other_app) = resolution.app_for_origin(db, *other)
+            && other_app.app() != app.app()
+            && !shadowed.iter().any(|name| name == other_app.app())
+        {
+            shadowed.push(other_app.app().to_string());
+        }
+    }
+    if shadowed.is_empty() {
+        return;
+    }
+
+    ValidationErrorAccumulator(ValidationError::AmbiguousTemplateName {
+        name: target_name.name(db).clone(),
+        app: app.app().to_string(),
+        shadowed,
+        span: target.span,
+    })
+    .accumulate(db);
+}
@@ -0,0 +1,109 @@
+use djls_source::File;
+use djls_source::Span;
+use djls_templates::TagBit;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::db::scoped_template_libraries_for_file;
+use crate::errors::ValidationError;
+use crate::filters::effective_filter_marks_safe_in_scope;
+use crate::scoping::TemplateAnalysisProjection;
+use crate::structure::ActiveTemplateVariable;
+
+pub(crate) const DEFAULT_TAGS_MODULE: &str = "django.template.defaulttags";
+
+/// Builtin filters whose only effect is to skip autoescaping.
+const SAFE_FILTERS: &[&str] = &["safe", "safeseq"];
+
+/// Variable path segments that usually hold text a site visitor typed.
+const USER_INPUT_SEGMENTS: &[&str] = &[
+    "request",
+    "GET",
+    "POST",
+    "COOKIES",
+    "META",
+    "cleaned_data",
+    "data",
+    "user",
+    "form",
+    "query",
+    "comment",
+    "message",
+    "body",
+];
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// `{% autoescape off %}` renders its whole body unescaped. X101 is a security review hint, so
+/// it is off unless configured.
+pub(crate) fn check_autoescape_rule(db: &dyn Db, bits: &[TagBit], span: Span) {
+    if bits.first().is_some_and(|bit| bit.as_str() == "off") {
+        ValidationErrorAccumulator(ValidationError::AutoescapeOff { span }).accumulate(db);
+    }
+}
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// `|safe` and `|safeseq` always skip escaping (X100). Other filters that return
+/// `mark_safe(...)` are reported only on variables that look like user input (X102), since most
+/// of them format trusted values. Both are security review hints, off unless configured.
+pub(crate) fn check_safe_output_rule(
+    db: &dyn Db,
+    file: File,
+    projection: TemplateAnalysisProjection<'_>,
+    variable: ActiveTemplateVariable<'_>,
+) {
+    let user_input = looks_like_user_input(variable.var);
+    for filter in variable.filters {
+        let span = filter.span.with_length_usize_saturating(filter.name.len());
+        if SAFE_FILTERS.contains(&filter.name.as_str()) {
+            ValidationErrorAccumulator(ValidationError::OutputMarkedSafe {
+                filter: filter.name.clone(),
+                span,
+            })
+            .accumulate(db);
+            continue;
+        }
+        if !user_input {
+            continue;
+        }
+
+        let load_state = projection
+            .loaded_libraries(db)
+            .available_at(filter.span.start());
+        if effective_filter_marks_safe_in_scope(
+            db,
+            scoped_template_libraries_for_file(db, file),
+            &filter.name,
+            &load_state,
+        ) {
+            ValidationErrorAccumulator(ValidationError::UnescapedUserInput {
+                filter: filter.name.clone(),
+                variable: variable.var.to_string(),
+                span,
+            })
+            .accumulate(db);
+        }
+    }
+}
+
+fn looks_like_user_input(variable: &str) -> bool {
+    variable
+        .split('.')
+        .any(|segment| USER_INPUT_SEGMENTS.contains(&segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_input_is_recognized_by_any_path_segment() {
+        assert!(looks_like_user_input("request.GET.q"));
+        assert!(looks_like_user_input("form.cleaned_data.bio"));
+        assert!(looks_like_user_input("comment"));
+        assert!(!looks_like_user_input("article.summary"));
+        assert!(!looks_like_user_input("requests"));
+    }
+}
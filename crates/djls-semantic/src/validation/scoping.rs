@@ -1,4 +1,8 @@
+use djls_conf::DiagnosticSeverity;
+use djls_project::LibraryName;
 use djls_project::MissingTemplateLibraryLookup;
+use djls_project::TemplateSymbolKind;
+use djls_source::File;
 use djls_source::Span;
 use djls_templates::Filter;
 use djls_templates::TagDelimiter;
@@ -6,13 +10,21 @@ use salsa::Accumulator;
 
 use crate::db::Db;
 use crate::db::ValidationErrorAccumulator;
+use crate::db::scoped_template_libraries_for_file;
 use crate::errors::ValidationError;
 use crate::scoping::LoaderArgumentFact;
+use crate::scoping::MissingLoadSymbolFact;
+use crate::scoping::UnusedLoadFact;
 use crate::scoping::symbols::SymbolAvailability;
+use crate::suggest::similar_names;
+use crate::validation::deprecations::DeprecatedKind;
+use crate::validation::deprecations::report_removed_symbol;
 
 pub(crate) fn check_tag_scoping_rule(
     db: &dyn Db,
+    file: File,
     name: &str,
+    name_span: Span,
     span: Span,
     availability: &SymbolAvailability,
     unknown_load_can_supply_symbol: bool,
@@ -22,9 +34,14 @@ pub(crate) fn check_tag_scoping_rule(
     match availability {
         SymbolAvailability::Available | SymbolAvailability::Inconclusive => {}
         SymbolAvailability::Unknown => {
+            if report_removed_symbol(db, DeprecatedKind::Tag, name, name_span) {
+                return;
+            }
             ValidationErrorAccumulator(ValidationError::UnknownTag {
                 tag: name.to_string(),
                 span: full_span,
+                name_span,
+                suggestions: similar_symbols(db, file, name, TemplateSymbolKind::Tag),
             })
             .accumulate(db);
         }
@@ -60,6 +77,7 @@ pub(crate) fn check_tag_scoping_rule(
 
 pub(crate) fn check_filter_scoping_rule(
     db: &dyn Db,
+    file: File,
     filter: &Filter,
     availability: &SymbolAvailability,
     unknown_load_can_supply_symbol: bool,
@@ -67,9 +85,14 @@ pub(crate) fn check_filter_scoping_rule(
     match availability {
         SymbolAvailability::Available | SymbolAvailability::Inconclusive => {}
         SymbolAvailability::Unknown => {
+            let name_span = filter.span.with_length_usize_saturating(filter.name.len());
+            if report_removed_symbol(db, DeprecatedKind::Filter, &filter.name, name_span) {
+                return;
+            }
             ValidationErrorAccumulator(ValidationError::UnknownFilter {
                 filter: filter.name.clone(),
                 span: filter.span,
+                suggestions: similar_symbols(db, file, &filter.name, TemplateSymbolKind::Filter),
             })
             .accumulate(db);
         }
@@ -103,7 +126,11 @@ pub(crate) fn check_filter_scoping_rule(
     }
 }
 
-pub(crate) fn check_load_libraries_rule(db: &dyn Db, arguments: &[LoaderArgumentFact]) {
+pub(crate) fn check_load_libraries_rule(
+    db: &dyn Db,
+    file: File,
+    arguments: &[LoaderArgumentFact],
+) {
     for fact in arguments {
         let lib = &fact.argument;
         match &fact.availability {
@@ -123,12 +150,67 @@ pub(crate) fn check_load_libraries_rule(db: &dyn Db, arguments: &[LoaderArgument
                 .accumulate(db);
             }
             MissingTemplateLibraryLookup::Absent => {
+                if report_removed_symbol(db, DeprecatedKind::Library, lib.as_str(), lib.span()) {
+                    continue;
+                }
                 ValidationErrorAccumulator(ValidationError::UnknownLibrary {
                     name: lib.as_str().to_string(),
                     span: lib.span(),
+                    suggestions: similar_libraries(db, file, lib.as_str()),
                 })
                 .accumulate(db);
             }
         }
     }
 }
+
+pub(crate) fn check_load_symbols_rule(db: &dyn Db, missing: &[MissingLoadSymbolFact]) {
+    for fact in missing {
+        ValidationErrorAccumulator(ValidationError::UnknownLoadSymbol {
+            symbol: fact.symbol.as_str().to_string(),
+            library: fact.library.clone(),
+            span: fact.symbol.span(),
+            removal: fact.removal,
+        })
+        .accumulate(db);
+    }
+}
+
+/// S147 is off by default, since an unused load changes nothing about how the template renders.
+pub(crate) fn check_unused_load_rule(db: &dyn Db, file: File, unused: &[UnusedLoadFact]) {
+    if db.diagnostics_config(file).get_severity("S147") == DiagnosticSeverity::Off {
+        return;
+    }
+    for fact in unused {
+        ValidationErrorAccumulator(ValidationError::UnusedLoad {
+            name: fact.argument.as_str().to_string(),
+            library: fact.library.clone(),
+            span: fact.argument.span(),
+            removal: fact.removal,
+        })
+        .accumulate(db);
+    }
+}
+
+/// Tags or filters usable in `file` whose names are a likely typo away from `name`.
+fn similar_symbols(db: &dyn Db, file: File, name: &str, kind: TemplateSymbolKind) -> Vec<String> {
+    let scoped_libraries = scoped_template_libraries_for_file(db, file);
+    similar_names(name, scoped_libraries.inventory_symbol_names(kind))
+        .into_iter()
+        .filter(|candidate| {
+            !scoped_libraries
+                .scoped_symbol_candidates(candidate, kind)
+                .is_empty()
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Loadable libraries whose names are a likely typo away from `name`.
+fn similar_libraries(db: &dyn Db, file: File, name: &str) -> Vec<String> {
+    let libraries = scoped_template_libraries_for_file(db, file).completion_library_names();
+    similar_names(name, libraries.iter().map(LibraryName::as_str))
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
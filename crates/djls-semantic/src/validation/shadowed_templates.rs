@@ -0,0 +1,45 @@
+use djls_project::template_resolution;
+use djls_source::File;
+use djls_source::Span;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// When two template directories provide the same name, Django's loaders return the first in
+/// `DIRS` and `INSTALLED_APPS` order and the other file is never loaded under that name. The
+/// shadowed file is reported on its first line, once for each name it loses.
+pub(crate) fn check_shadowed_template_rule(db: &dyn Db, file: File) {
+    let Some(project) = db.project() else {
+        return;
+    };
+    let Ok(source) = file.try_source(db) else {
+        return;
+    };
+    let first_line = source.as_str().find('\n').unwrap_or(source.as_str().len());
+    let span = Span::saturating_from_bounds_usize(0, first_line);
+
+    let resolution = template_resolution(db, project);
+    for name in resolution.template_names_for_file(db, file) {
+        let Some(origin) = resolution
+            .origins_for_name(db, *name)
+            .iter()
+            .find(|origin| origin.file(db) == file)
+        else {
+            continue;
+        };
+        let Some(winner) = resolution.shadowing_origin(db, *origin) else {
+            continue;
+        };
+
+        ValidationErrorAccumulator(ValidationError::ShadowedTemplate {
+            name: name.name(db).clone(),
+            winner: winner.path_buf(db).to_string(),
+            span,
+        })
+        .accumulate(db);
+    }
+}
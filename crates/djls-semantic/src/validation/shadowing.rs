@@ -0,0 +1,46 @@
+use djls_project::context_processor_variables;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::variables::scoped_bindings;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// A `{% for %}` or `{% with %}` name that hides an enclosing binding, or a
+/// variable the project's context processors add, changes what that name
+/// means for the rest of the body. The template renders fine, so S140 is off
+/// unless configured.
+pub(crate) fn check_shadowing_rule(db: &dyn Db, regions: &Regions, root: RegionId) {
+    let bindings = scoped_bindings(regions, root);
+    if bindings.is_empty() {
+        return;
+    }
+    let context = db
+        .project()
+        .map(|project| context_processor_variables(db, project));
+
+    for binding in bindings {
+        let processor = if binding.shadows.is_some() {
+            None
+        } else if let Some(variable) = context.and_then(|context| {
+            context
+                .iter()
+                .find(|variable| variable.name() == binding.name)
+        }) {
+            Some(variable.processor().to_string())
+        } else {
+            continue;
+        };
+        ValidationErrorAccumulator(ValidationError::ShadowedVariable {
+            name: binding.name,
+            span: binding.span,
+            outer_span: binding.shadows,
+            processor,
+        })
+        .accumulate(db);
+    }
+}
@@ -0,0 +1,39 @@
+use djls_conf::DiagnosticSeverity;
+use djls_source::File;
+use djls_templates::Node;
+use salsa::Accumulator;
+
+use crate::db::Db;
+use crate::db::ValidationErrorAccumulator;
+use crate::errors::ValidationError;
+
+/// Internal helper for [`TemplateValidator`](crate::validation::TemplateValidator).
+///
+/// Django reads `{%trans "Hi"%}` the same as `{% trans "Hi" %}`, so S148 is a stylistic check
+/// and off unless configured. Every tag is checked, end tags included; spacing between a tag's
+/// arguments is left as written.
+pub(crate) fn check_tag_spacing_rule(db: &dyn Db, file: File, source: &str, nodes: &[Node]) {
+    if db.diagnostics_config(file).get_severity("S148") == DiagnosticSeverity::Off {
+        return;
+    }
+    for node in nodes {
+        let Node::Tag { span, .. } = node else {
+            continue;
+        };
+        let Some(inner) = source.get(span.start_usize()..span.end_usize()) else {
+            continue;
+        };
+        let content = inner.trim();
+        let spaced = inner
+            .strip_prefix(' ')
+            .and_then(|inner| inner.strip_suffix(' '));
+        if content.is_empty() || spaced == Some(content) {
+            continue;
+        }
+        ValidationErrorAccumulator(ValidationError::TagDelimiterSpacing {
+            content: content.to_string(),
+            span: *span,
+        })
+        .accumulate(db);
+    }
+}
@@ -0,0 +1,248 @@
+mod scopes;
+
+use std::collections::BTreeSet;
+
+use djls_project::ContextProcessorVariable;
+use djls_project::context_processor_variables;
+use djls_source::File;
+use djls_source::Offset;
+use djls_templates::NodeList;
+use djls_templates::TagBit;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+
+use crate::db::Db;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::structure::build_template_tree_for_file;
+use crate::validation::BLOCK_TRANSLATE_TAGS;
+use crate::validation::block_translate_bindings;
+pub use scopes::VariableOccurrences;
+pub use scopes::enclosing_loops_at;
+pub(crate) use scopes::forloop_references;
+pub(crate) use scopes::scoped_bindings;
+pub use scopes::selection_variables;
+pub use scopes::variable_occurrences_at;
+
+/// Variable names a Template can refer to at an offset, as far as its own
+/// source shows them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateVariableNames {
+    /// Names bound by an enclosing `{% blocktrans %}` through `with` or `count`.
+    pub bound: Vec<String>,
+    /// Leading names of the variables used elsewhere in the Template, like
+    /// `user` for `{{ user.name }}`.
+    pub used: Vec<String>,
+    /// Variables the project's context processors add, less those already
+    /// bound or used.
+    pub context: Vec<ContextProcessorVariable>,
+}
+
+/// Collect the variable names worth offering at `offset`.
+///
+/// There is no inventory of the context a view passes in, so the names come
+/// from the Template itself, its other variables and the bindings of any
+/// `{% blocktrans %}` whose body contains `offset`, plus the variables the
+/// configured context processors add to every request.
+#[must_use]
+pub fn template_variable_names_at(
+    db: &dyn Db,
+    file: File,
+    nodelist: NodeList<'_>,
+    offset: Offset,
+) -> TemplateVariableNames {
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    let regions = tree.regions(db);
+
+    let mut bound = BTreeSet::new();
+    let mut used = BTreeSet::new();
+    for region in regions {
+        for node in region.nodes() {
+            if let TemplateNode::Variable { var, span, .. } = node
+                && !touches(span.start(), span.end(), offset)
+                && let Some(name) = leading_name(var)
+            {
+                used.insert(name);
+            }
+
+            let TemplateNode::Block {
+                tag,
+                bits,
+                body,
+                role: BlockRole::Opener,
+                ..
+            } = node
+            else {
+                continue;
+            };
+            if !BLOCK_TRANSLATE_TAGS.contains(&tag.as_str()) {
+                continue;
+            }
+            let in_body = regions.get(*body).nodes().iter().any(|child| {
+                let TemplateNode::Block { body: segment, .. } = child else {
+                    return false;
+                };
+                let span = regions.get(*segment).span();
+                touches(span.start(), span.end(), offset)
+            });
+            if in_body {
+                bound.extend(block_translate_bindings(bits));
+            }
+        }
+    }
+
+    let context = db
+        .project()
+        .map(|project| {
+            context_processor_variables(db, project)
+                .iter()
+                .filter(|variable| {
+                    !bound.contains(variable.name()) && !used.contains(variable.name())
+                })
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    TemplateVariableNames {
+        context,
+        used: used
+            .into_iter()
+            .filter(|name| !bound.contains(name))
+            .map(str::to_string)
+            .collect(),
+        bound: bound.into_iter().map(str::to_string).collect(),
+    }
+}
+
+/// Names a Template reads from the context it is rendered with.
+///
+/// These are the leading names of its `{{ }}` variables, less the names bound
+/// around them by `{% for %}`, `{% with %}`, or a `{% blocktrans %}` binding,
+/// and less any name the Template stores with `as name`. Block bindings count
+/// only inside the block's body, so `{{ item }}` after an `{% endfor %}` still
+/// reads `item`; `as` names count everywhere, wherever the tag storing them
+/// is. Tag arguments like `{% if user %}` are not counted, so the result is a
+/// lower bound on what the Template needs.
+pub(crate) fn template_context_variables(db: &dyn Db, file: File) -> Vec<String> {
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return Vec::new();
+    };
+    let tree = build_template_tree_for_file(db, file, nodelist);
+
+    let mut reads = ContextReads {
+        regions: tree.regions(db),
+        scopes: Vec::new(),
+        stored: BTreeSet::from(["block", "forloop"]),
+        used: BTreeSet::new(),
+    };
+    reads.region(tree.root(db));
+
+    reads
+        .used
+        .into_iter()
+        .filter(|name| !reads.stored.contains(name) && !matches!(*name, "True" | "False" | "None"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Walks a Template for [`template_context_variables`], keeping the names each
+/// enclosing block binds for its body.
+struct ContextReads<'a> {
+    regions: &'a Regions,
+    scopes: Vec<Vec<&'a str>>,
+    /// Names stored with `as` by tags outside any block binding them.
+    stored: BTreeSet<&'a str>,
+    used: BTreeSet<&'a str>,
+}
+
+impl ContextReads<'_> {
+    fn region(&mut self, id: RegionId) {
+        let regions = self.regions;
+        for node in regions.get(id).nodes() {
+            match node {
+                TemplateNode::Variable { var, .. } => {
+                    if let Some(name) = leading_name(var)
+                        && !self.scopes.iter().flatten().any(|bound| *bound == name)
+                    {
+                        self.used.insert(name);
+                    }
+                }
+                TemplateNode::Block {
+                    tag,
+                    bits,
+                    body,
+                    role: BlockRole::Opener,
+                    ..
+                } => {
+                    // Only the opener's own segment sees its bindings, not
+                    // `{% empty %}` or `{% else %}`.
+                    let bindings = tag_bindings(tag, bits);
+                    for (index, segment) in regions.get(*body).nodes().iter().enumerate() {
+                        let TemplateNode::Block { body, .. } = segment else {
+                            continue;
+                        };
+                        if index == 0 {
+                            self.scopes.push(bindings.clone());
+                            self.region(*body);
+                            self.scopes.pop();
+                        } else {
+                            self.region(*body);
+                        }
+                    }
+                }
+                TemplateNode::StandaloneTag { tag, bits, .. } => {
+                    self.stored.extend(tag_bindings(tag, bits));
+                }
+                TemplateNode::Block {
+                    role: BlockRole::Segment,
+                    ..
+                }
+                | TemplateNode::Opaque { .. }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Text { .. }
+                | TemplateNode::Error { .. } => {}
+            }
+        }
+    }
+}
+
+/// Names a tag binds for the rest of its body or the Template.
+fn tag_bindings<'a>(tag: &str, bits: &'a [TagBit]) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    match tag {
+        "for" => names.extend(
+            bits.iter()
+                .take_while(|bit| bit.as_str() != "in")
+                .flat_map(|bit| bit.as_str().split(','))
+                .map(str::trim)
+                .filter(|name| !name.is_empty()),
+        ),
+        "with" => names.extend(bits.iter().filter_map(TagBit::kwarg).map(|kwarg| kwarg.name)),
+        _ if BLOCK_TRANSLATE_TAGS.contains(&tag) => names.extend(block_translate_bindings(bits)),
+        _ => {}
+    }
+    names.extend(
+        bits.windows(2)
+            .filter(|pair| pair[0].as_str() == "as")
+            .map(|pair| pair[1].as_str()),
+    );
+    names
+}
+
+/// The first lookup segment of `var`, when it is a name rather than a literal.
+fn leading_name(var: &str) -> Option<&str> {
+    let name = var.split('.').next()?;
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    ((first.is_alphabetic() || first == '_') && chars.all(|c| c.is_alphanumeric() || c == '_'))
+        .then_some(name)
+}
+
+/// Whether `offset` lies within `start..=end`, so a cursor just past the end
+/// still counts.
+fn touches(start: u32, end: u32, offset: Offset) -> bool {
+    (start..=end).contains(&offset.get())
+}
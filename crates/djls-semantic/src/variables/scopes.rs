@@ -0,0 +1,525 @@
+use djls_source::File;
+use djls_source::Offset;
+use djls_source::Span;
+use djls_templates::IncludeNode;
+use djls_templates::NodeList;
+use djls_templates::Operand;
+use djls_templates::TagBit;
+use djls_templates::TagDelimiter;
+
+use super::leading_name;
+use super::touches;
+use crate::db::Db;
+use crate::structure::BlockRole;
+use crate::structure::RegionId;
+use crate::structure::Regions;
+use crate::structure::TemplateNode;
+use crate::structure::build_template_tree_for_file;
+use crate::validation::BLOCK_TRANSLATE_TAGS;
+use crate::validation::block_translate_bindings;
+
+/// Tags whose plain arguments are filter expressions that can read variables.
+const EXPRESSION_TAGS: &[&str] = &[
+    "cycle",
+    "elif",
+    "firstof",
+    "if",
+    "ifchanged",
+    "url",
+    "widthratio",
+];
+
+/// Arguments of [`EXPRESSION_TAGS`] that are keywords rather than expressions.
+const KEYWORDS: &[&str] = &[
+    "and", "or", "not", "in", "is", "==", "!=", "<", ">", "<=", ">=", "silent",
+];
+
+/// Names Django provides inside templates, which a rename must not touch.
+const RESERVED_NAMES: &[&str] = &["block", "forloop", "True", "False", "None"];
+
+/// Every occurrence of one template variable that refers to the same binding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariableOccurrences {
+    pub name: String,
+    /// The occurrence `offset` touched.
+    pub target: Span,
+    /// Occurrences in source order, including the binding itself when the
+    /// variable is bound by `{% for %}`, `{% with %}`, or `{% blocktrans %}`.
+    pub spans: Vec<Span>,
+}
+
+/// Find the variable at `offset` and every occurrence bound to the same scope.
+///
+/// `{% for %}`, `{% with %}`, and `{% blocktrans %}` bind names for their body
+/// only, so a loop variable is matched inside its loop and not in a sibling
+/// loop that reuses the name. Names no tag binds come from the render
+/// context and are matched across the whole Template, except where an inner
+/// binding shadows them. Only the first segment of a lookup such as
+/// `user.name` is a variable; arguments of tags not known to take expressions
+/// are left alone.
+#[must_use]
+pub fn variable_occurrences_at(
+    db: &dyn Db,
+    file: File,
+    nodelist: NodeList<'_>,
+    offset: Offset,
+) -> Option<VariableOccurrences> {
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    let occurrences = collect_occurrences(tree.regions(db), tree.root(db));
+    let target = occurrences
+        .iter()
+        .find(|occurrence| touches(occurrence.span.start(), occurrence.span.end(), offset))?;
+    let mut spans = occurrences
+        .iter()
+        .filter(|occurrence| occurrence.name == target.name && occurrence.scope == target.scope)
+        .map(|occurrence| occurrence.span)
+        .collect::<Vec<_>>();
+    spans.sort_by_key(|span| span.start());
+
+    Some(VariableOccurrences {
+        name: target.name.clone(),
+        target: target.span,
+        spans,
+    })
+}
+
+/// The variables the nodes in `selection` read from outside it, in order of
+/// first use, for moving `selection` into an `{% include %}`.
+///
+/// Names bound by a `{% for %}` or `{% with %}` that lies wholly inside
+/// `selection` stay with it. Returns `None` when `selection` cuts through a
+/// tag, a variable, or a block without covering one of its segment bodies
+/// exactly, since that source would not parse on its own. Text may be cut
+/// anywhere.
+#[must_use]
+pub fn selection_variables(
+    db: &dyn Db,
+    file: File,
+    nodelist: NodeList<'_>,
+    selection: Span,
+) -> Option<Vec<String>> {
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    let regions = tree.regions(db);
+    if !selection_is_whole(regions, tree.root(db), selection) {
+        return None;
+    }
+
+    let inside = |span: Span| selection.start() <= span.start() && span.end() <= selection.end();
+    let mut names: Vec<String> = Vec::new();
+    for occurrence in collect_occurrences(regions, tree.root(db)) {
+        if !inside(occurrence.span) || occurrence.scope.is_some_and(inside) {
+            continue;
+        }
+        if !names.contains(&occurrence.name) {
+            names.push(occurrence.name);
+        }
+    }
+    Some(names)
+}
+
+/// How many `{% for %}` bodies contain `offset`.
+///
+/// An `{% empty %}` branch is not counted, since Django renders it without a
+/// `forloop`.
+#[must_use]
+pub fn enclosing_loops_at(
+    db: &dyn Db,
+    file: File,
+    nodelist: NodeList<'_>,
+    offset: Offset,
+) -> usize {
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    loops_around(tree.regions(db), tree.root(db), offset)
+}
+
+fn loops_around(regions: &Regions, id: RegionId, offset: Offset) -> usize {
+    for node in regions.get(id).nodes() {
+        let TemplateNode::Block {
+            tag,
+            body,
+            role: BlockRole::Opener,
+            ..
+        } = node
+        else {
+            continue;
+        };
+        for (index, segment) in regions.get(*body).nodes().iter().enumerate() {
+            let TemplateNode::Block { body, .. } = segment else {
+                continue;
+            };
+            let span = regions.get(*body).span();
+            if touches(span.start(), span.end(), offset) {
+                let is_loop = index == 0 && tag == "for";
+                return usize::from(is_loop) + loops_around(regions, *body, offset);
+            }
+        }
+    }
+    0
+}
+
+/// A `forloop` lookup and the number of `{% for %}` bodies around it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ForloopReference {
+    /// `forloop` through its last `parentloop` segment.
+    pub(crate) span: Span,
+    /// How many `parentloop` segments follow `forloop`.
+    pub(crate) parentloops: usize,
+    /// `{% for %}` bodies enclosing the lookup. An `{% empty %}` branch is
+    /// not a loop body, since Django renders it without a `forloop`.
+    pub(crate) loops: usize,
+}
+
+/// Every `forloop` lookup in the tree rooted at `root`, in source order.
+pub(crate) fn forloop_references(regions: &Regions, root: RegionId) -> Vec<ForloopReference> {
+    let mut collector = ScopeCollector::new(regions);
+    collector.region(root);
+    let mut references = collector.forloops;
+    references.sort_by_key(|reference| reference.span.start());
+    references
+}
+
+/// A name bound by `{% for %}` or `{% with %}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ScopedBinding {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+    /// The binding of the same name in an enclosing `{% for %}`, `{% with %}`,
+    /// or `{% blocktrans %}`, which this one hides for its body.
+    pub(crate) shadows: Option<Span>,
+}
+
+/// Every `{% for %}` and `{% with %}` binding in the tree rooted at `root`, in
+/// source order.
+pub(crate) fn scoped_bindings(regions: &Regions, root: RegionId) -> Vec<ScopedBinding> {
+    let mut collector = ScopeCollector::new(regions);
+    collector.region(root);
+    collector.bindings
+}
+
+fn collect_occurrences(regions: &Regions, root: RegionId) -> Vec<Occurrence> {
+    let mut collector = ScopeCollector::new(regions);
+    collector.region(root);
+    collector.occurrences
+}
+
+/// Whether every node `selection` touches in region `id` is either inside it
+/// or plain text, descending into the one block segment that contains it.
+fn selection_is_whole(regions: &Regions, id: RegionId, selection: Span) -> bool {
+    for node in regions.get(id).nodes() {
+        let (start, end) = match node {
+            TemplateNode::Block { full_span, body, .. } => (
+                full_span.start(),
+                full_span.end().max(regions.get(*body).span().end()),
+            ),
+            TemplateNode::Opaque { full_span, .. }
+            | TemplateNode::StandaloneTag { full_span, .. }
+            | TemplateNode::Error { full_span, .. } => (full_span.start(), full_span.end()),
+            TemplateNode::Variable { span, .. } | TemplateNode::Comment { span } => {
+                let span = span.expand(TagDelimiter::LENGTH_U32, TagDelimiter::LENGTH_U32);
+                (span.start(), span.end())
+            }
+            TemplateNode::Text { .. } => continue,
+        };
+        if end <= selection.start() || selection.end() <= start {
+            continue;
+        }
+        if selection.start() <= start && end <= selection.end() {
+            continue;
+        }
+        let TemplateNode::Block { body, .. } = node else {
+            return false;
+        };
+        let segment_body = regions.get(*body).nodes().iter().find_map(|segment| {
+            let TemplateNode::Block { body, .. } = segment else {
+                return None;
+            };
+            let span = regions.get(*body).span();
+            (span.start() <= selection.start() && selection.end() <= span.end()).then_some(*body)
+        });
+        return segment_body.is_some_and(|body| selection_is_whole(regions, body, selection));
+    }
+    true
+}
+
+/// One name in the Template, tied to the block that binds it. `None` is the
+/// render context.
+struct Occurrence {
+    name: String,
+    span: Span,
+    scope: Option<Span>,
+}
+
+/// Names bound by one block for its first segment, keyed by the block's span.
+struct Scope {
+    block: Span,
+    bindings: Vec<(String, Span)>,
+}
+
+struct ScopeCollector<'a> {
+    regions: &'a Regions,
+    scopes: Vec<Scope>,
+    occurrences: Vec<Occurrence>,
+    loops: usize,
+    forloops: Vec<ForloopReference>,
+    bindings: Vec<ScopedBinding>,
+}
+
+impl<'a> ScopeCollector<'a> {
+    fn new(regions: &'a Regions) -> Self {
+        Self {
+            regions,
+            scopes: Vec::new(),
+            occurrences: Vec::new(),
+            loops: 0,
+            forloops: Vec::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    fn region(&mut self, id: RegionId) {
+        let regions = self.regions;
+        for node in regions.get(id).nodes() {
+            match node {
+                TemplateNode::Variable {
+                    var,
+                    var_span,
+                    filters,
+                    ..
+                } => {
+                    self.operand(&Operand::parse(var, *var_span));
+                    for argument in filters.iter().filter_map(|filter| filter.arg.as_ref()) {
+                        self.operand(argument.expression());
+                    }
+                }
+                TemplateNode::Block {
+                    tag,
+                    bits,
+                    full_span,
+                    body,
+                    role: BlockRole::Opener,
+                    ..
+                } => self.block(tag, bits, *full_span, *body),
+                TemplateNode::StandaloneTag { tag, bits, .. } => self.tag_arguments(tag, bits),
+                TemplateNode::Block {
+                    role: BlockRole::Segment,
+                    ..
+                }
+                | TemplateNode::Opaque { .. }
+                | TemplateNode::Comment { .. }
+                | TemplateNode::Text { .. }
+                | TemplateNode::Error { .. } => {}
+            }
+        }
+    }
+
+    fn block(&mut self, tag: &str, bits: &[TagBit], full_span: Span, container: RegionId) {
+        let bindings = self.opener_bindings(tag, bits);
+
+        let regions = self.regions;
+        for (index, segment) in regions.get(container).nodes().iter().enumerate() {
+            let TemplateNode::Block {
+                tag: segment_tag,
+                bits: segment_bits,
+                body,
+                ..
+            } = segment
+            else {
+                continue;
+            };
+            if index > 0 {
+                self.tag_arguments(segment_tag, segment_bits);
+                self.region(*body);
+                continue;
+            }
+
+            if matches!(tag, "for" | "with") {
+                for (name, span) in &bindings {
+                    let shadows = self.scopes.iter().rev().find_map(|scope| {
+                        scope
+                            .bindings
+                            .iter()
+                            .find(|(bound, _)| bound == name)
+                            .map(|(_, span)| *span)
+                    });
+                    self.bindings.push(ScopedBinding {
+                        name: name.clone(),
+                        span: *span,
+                        shadows,
+                    });
+                }
+            }
+
+            let is_loop = tag == "for";
+            self.loops += usize::from(is_loop);
+            self.scopes.push(Scope {
+                block: full_span,
+                bindings: bindings.clone(),
+            });
+            for (name, span) in &bindings {
+                self.occurrences.push(Occurrence {
+                    name: name.clone(),
+                    span: *span,
+                    scope: Some(full_span),
+                });
+            }
+            self.region(*body);
+            self.scopes.pop();
+            self.loops -= usize::from(is_loop);
+        }
+    }
+
+    /// Record the expressions an opener reads and return the names it binds
+    /// for its body.
+    fn opener_bindings(&mut self, tag: &str, bits: &[TagBit]) -> Vec<(String, Span)> {
+        let mut bindings = Vec::new();
+        match tag {
+            "for" => {
+                let Some(split) = bits.iter().position(|bit| bit.as_str() == "in") else {
+                    return bindings;
+                };
+                for bit in &bits[..split] {
+                    let start = bit.span.start_usize();
+                    let mut offset = 0;
+                    for name in bit.as_str().split(',') {
+                        let trimmed = name.trim_start();
+                        let leading = name.len() - trimmed.len();
+                        let trimmed = trimmed.trim_end();
+                        if leading_name(trimmed) == Some(trimmed) {
+                            let span = Span::saturating_from_parts_usize(
+                                start + offset + leading,
+                                trimmed.len(),
+                            );
+                            bindings.push((trimmed.to_string(), span));
+                        }
+                        offset += name.len() + 1;
+                    }
+                }
+                for bit in bits[split + 1..]
+                    .iter()
+                    .filter(|bit| bit.as_str() != "reversed")
+                {
+                    self.expression(bit);
+                }
+            }
+            "with" => {
+                for (index, bit) in bits.iter().enumerate() {
+                    if let Some(kwarg) = bit.kwarg() {
+                        self.operand(&kwarg.operand());
+                        bindings.push((kwarg.name.to_string(), kwarg.name_span));
+                    } else if bits.get(index + 1).is_some_and(|next| next.as_str() == "as") {
+                        self.expression(bit);
+                    } else if index > 0 && bits[index - 1].as_str() == "as" {
+                        bindings.push((bit.as_str().to_string(), bit.span));
+                    }
+                }
+            }
+            _ if BLOCK_TRANSLATE_TAGS.contains(&tag) => {
+                let names = block_translate_bindings(bits);
+                for (index, bit) in bits.iter().enumerate() {
+                    if let Some(kwarg) = bit.kwarg() {
+                        self.operand(&kwarg.operand());
+                        if names.contains(&kwarg.name) {
+                            bindings.push((kwarg.name.to_string(), kwarg.name_span));
+                        }
+                    } else if bits.get(index + 1).is_some_and(|next| next.as_str() == "as") {
+                        self.expression(bit);
+                    } else if index > 0
+                        && bits[index - 1].as_str() == "as"
+                        && names.contains(&bit.as_str())
+                    {
+                        bindings.push((bit.as_str().to_string(), bit.span));
+                    }
+                }
+            }
+            _ => self.tag_arguments(tag, bits),
+        }
+        bindings
+    }
+
+    /// Record the variables a tag reads, and the name it stores with `as`.
+    fn tag_arguments(&mut self, tag: &str, bits: &[TagBit]) {
+        if tag == "include" {
+            if let Some(include) = IncludeNode::from_bits(bits) {
+                self.expression(include.template);
+                for kwarg in &include.extra_context {
+                    self.operand(&kwarg.operand());
+                }
+            }
+            return;
+        }
+        let reads_expressions = EXPRESSION_TAGS.contains(&tag);
+        let mut bits = bits.iter();
+        while let Some(bit) = bits.next() {
+            if bit.as_str() == "as" {
+                if let Some(target) = bits.next() {
+                    self.reference(target.as_str(), target.span);
+                }
+                return;
+            }
+            if let Some(kwarg) = bit.kwarg() {
+                self.operand(&kwarg.operand());
+            } else if reads_expressions && !KEYWORDS.contains(&bit.as_str()) {
+                self.expression(bit);
+            }
+        }
+    }
+
+    /// Record the variables a filter expression such as `items|join:sep` reads.
+    fn expression(&mut self, bit: &TagBit) {
+        let text = bit.as_str();
+        if text.starts_with(['"', '\'']) || text.starts_with("_(") {
+            return;
+        }
+        let start = bit.span.start_usize();
+        let mut offset = 0;
+        for (index, part) in text.split('|').enumerate() {
+            if index == 0 {
+                let span = Span::saturating_from_parts_usize(start, part.len());
+                self.operand(&Operand::parse(part, span));
+            } else if let Some((name, argument)) = part.split_once(':') {
+                let argument_start = start + offset + name.len() + 1;
+                let span = Span::saturating_from_parts_usize(argument_start, argument.len());
+                self.operand(&Operand::parse(argument, span));
+            }
+            offset += part.len() + 1;
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand) {
+        let Some([first, rest @ ..]) = operand.path() else {
+            return;
+        };
+        if first.name == "forloop" {
+            let parentloops = rest
+                .iter()
+                .take_while(|segment| segment.name == "parentloop")
+                .count();
+            let end = rest[..parentloops]
+                .last()
+                .map_or(first.span.end_usize(), |segment| segment.span.end_usize());
+            self.forloops.push(ForloopReference {
+                span: Span::saturating_from_bounds_usize(first.span.start_usize(), end),
+                parentloops,
+                loops: self.loops,
+            });
+        }
+        self.reference(&first.name, first.span);
+    }
+
+    fn reference(&mut self, name: &str, span: Span) {
+        if leading_name(name) != Some(name) || RESERVED_NAMES.contains(&name) {
+            return;
+        }
+        let scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.bindings.iter().any(|(bound, _)| bound == name))
+            .map(|scope| scope.block);
+        self.occurrences.push(Occurrence {
+            name: name.to_string(),
+            span,
+            scope,
+        });
+    }
+}
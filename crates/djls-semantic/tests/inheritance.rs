@@ -12,12 +12,15 @@ use djls_semantic::TagSpec;
 use djls_semantic::TagSpecs;
 use djls_semantic::TemplateReferenceKind;
 use djls_semantic::TemplateSymbols;
+use djls_semantic::block_coverage;
 use djls_semantic::block_overrides;
 use djls_semantic::builtin_tag_specs;
+use djls_semantic::descendant_templates;
 use djls_semantic::inherited_blocks;
 use djls_semantic::parent_block;
 use djls_semantic::template_inheritance;
 use djls_semantic::template_symbols;
+use djls_semantic::trace_template;
 use djls_source::ChangeEvent;
 use djls_source::File;
 use djls_source::SourceChanges;
@@ -521,6 +524,93 @@ fn block_overrides_accepts_relative_winning_extends_target() {
     assert_eq!(overrides[0].file, child);
 }
 
+#[test]
+fn descendant_templates_follow_extends_chains_transitively() {
+    let db = TestDatabase::new();
+    let project = project_with_templates(
+        &db,
+        vec!["/test/project/templates"],
+        vec![
+            ("/test/project/templates/base.html", "{% block content %}{% endblock %}"),
+            ("/test/project/templates/section.html", "{% extends 'base.html' %}"),
+            ("/test/project/templates/page.html", "{% extends 'section.html' %}"),
+            ("/test/project/templates/other.html", "{% include 'base.html' %}"),
+        ],
+    )
+    .expect("template project fixture should build");
+    let file = |path: &str| {
+        db.file(Utf8Path::new(path))
+            .expect("fixture file should exist in the test database")
+    };
+
+    assert_eq!(
+        descendant_templates(&db, project, file("/test/project/templates/base.html")),
+        [
+            file("/test/project/templates/section.html"),
+            file("/test/project/templates/page.html"),
+        ]
+    );
+    assert!(
+        descendant_templates(&db, project, file("/test/project/templates/page.html")).is_empty()
+    );
+}
+
+#[test]
+fn block_coverage_splits_overridden_untouched_and_new_blocks() {
+    let db = TestDatabase::new();
+    let project = project_with_templates(
+        &db,
+        vec!["/test/project/templates"],
+        vec![
+            (
+                "/test/project/templates/base.html",
+                "{% block title %}{% endblock %}{% block sidebar %}{% endblock %}{% block content %}{% endblock %}",
+            ),
+            (
+                "/test/project/templates/section.html",
+                "{% extends 'base.html' %}{% block content %}Section{% endblock %}",
+            ),
+            (
+                "/test/project/templates/page.html",
+                "{% extends 'section.html' %}{% block title %}Page{% endblock %}{% block extra %}{% endblock %}",
+            ),
+        ],
+    )
+    .expect("template project fixture should build");
+    let file = |path: &str| {
+        db.file(Utf8Path::new(path))
+            .expect("fixture file should exist in the test database")
+    };
+    let base = file("/test/project/templates/base.html");
+    let section = file("/test/project/templates/section.html");
+    let page = file("/test/project/templates/page.html");
+
+    let coverage = block_coverage(&db, project, page);
+    let summary: Vec<_> = coverage
+        .blocks
+        .iter()
+        .map(|block| {
+            (
+                block.name.as_str(),
+                block.base.map(|site| site.file),
+                block.own.map(|site| site.file),
+            )
+        })
+        .collect();
+
+    assert_eq!(coverage.ancestors, [section, base]);
+    assert_eq!(coverage.end, ChainEnd::Root);
+    assert_eq!(
+        summary,
+        [
+            ("content", Some(section), None),
+            ("title", Some(base), Some(page)),
+            ("sidebar", Some(base), None),
+            ("extra", None, Some(page)),
+        ]
+    );
+}
+
 #[test]
 fn reverse_inheritance_starts_from_secondary_names_and_dedupes_physical_sites() {
     let mut db = TestDatabase::new();
@@ -804,6 +894,72 @@ fn reverse_inheritance_rejects_a_shared_child_with_backend_local_parents() {
     assert!(block_overrides(&db, project, b_base, "content").is_empty());
 }
 
+#[test]
+fn trace_template_flattens_overrides_and_block_super() {
+    let db = TestDatabase::new();
+    let project = project_with_templates(
+        &db,
+        vec!["/test/project/templates"],
+        vec![
+            (
+                "/test/project/templates/base.html",
+                "<title>{% block title %}Site{% endblock %}</title>\n{% block content %}<main>{% block body %}{% endblock %}</main>{% endblock %}",
+            ),
+            (
+                "/test/project/templates/middle.html",
+                "{% extends 'base.html' %}{% block title %}{{ block.super }} - Section{% endblock %}{% block body %}Middle{% endblock %}",
+            ),
+            (
+                "/test/project/templates/child.html",
+                "{% extends 'middle.html' %}ignored{% block body %}Child {{ block.super }}{% endblock %}",
+            ),
+        ],
+    )
+    .expect("template project fixture should build");
+    let child = db
+        .file(Utf8Path::new("/test/project/templates/child.html"))
+        .expect("fixture file should exist in the test database");
+
+    let trace = trace_template(&db, project, child).expect("child template should be traced");
+    let segments = trace
+        .segments
+        .iter()
+        .map(|segment| {
+            let source = segment
+                .file
+                .try_source(&db)
+                .expect("traced file should be readable");
+            let text = source.as_str()[segment.span.start_usize()..segment.span.end_usize()]
+                .to_string();
+            let name = segment.file.path(&db).file_name().unwrap_or_default().to_string();
+            (name, segment.block.clone(), text)
+        })
+        .collect::<Vec<_>>();
+    let text = segments
+        .iter()
+        .map(|(_, _, text)| text.as_str())
+        .collect::<String>();
+
+    assert_eq!(text, "<title>Site - Section</title>\n<main>Child Middle</main>");
+    assert_eq!(
+        segments
+            .iter()
+            .map(|(name, block, _)| (name.as_str(), block.as_deref()))
+            .collect::<Vec<_>>(),
+        [
+            ("base.html", None),
+            ("base.html", Some("title")),
+            ("middle.html", Some("title")),
+            ("base.html", None),
+            ("base.html", Some("content")),
+            ("child.html", Some("body")),
+            ("middle.html", Some("body")),
+            ("base.html", Some("content")),
+        ]
+    );
+    assert_eq!(trace.end, ChainEnd::Root);
+}
+
 #[test]
 fn template_inheritance_reports_missing_parent_as_unresolved() {
     let db = TestDatabase::new();
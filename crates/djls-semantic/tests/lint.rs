@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use camino::Utf8Path;
+use djls_semantic::BlockVisitor;
+use djls_semantic::LintContext;
+use djls_semantic::LintRule;
+use djls_semantic::LintRules;
+use djls_semantic::LintTag;
+use djls_semantic::LintTemplate;
+use djls_semantic::LintVariable;
+use djls_semantic::NodeVisitor;
+use djls_semantic::ValidationError;
+use djls_semantic::collect_template_diagnostics;
+use djls_source::Span;
+use djls_testing::TestDatabase;
+use djls_testing::collect_errors;
+use djls_testing::standard_validation_db;
+
+struct NoSafeFilter;
+
+impl LintRule for NoSafeFilter {
+    fn name(&self) -> &str {
+        "no-safe-filter"
+    }
+
+    fn check(&self, template: &LintTemplate<'_>, cx: &mut LintContext) {
+        struct Visitor;
+
+        impl NodeVisitor for Visitor {
+            fn visit_variable(&mut self, cx: &mut LintContext, variable: LintVariable<'_>) {
+                for filter in variable.filters.iter().filter(|filter| filter.name == "safe") {
+                    cx.report("L001", format!("'{}' is marked safe", variable.var), filter.span);
+                }
+            }
+        }
+
+        template.walk_nodes(cx, &mut Visitor);
+    }
+}
+
+/// Reports every block callback so the walk order is visible in the diagnostics.
+struct BlockTrace;
+
+impl LintRule for BlockTrace {
+    fn name(&self) -> &str {
+        "block-trace"
+    }
+
+    fn check(&self, template: &LintTemplate<'_>, cx: &mut LintContext) {
+        struct Visitor;
+
+        impl BlockVisitor for Visitor {
+            fn enter_block(&mut self, cx: &mut LintContext, block: LintTag<'_>) {
+                cx.report("L002", format!("enter {}", block.name), block.span);
+            }
+
+            fn visit_segment(&mut self, cx: &mut LintContext, segment: LintTag<'_>) {
+                cx.report("L002", format!("segment {}", segment.name), segment.span);
+            }
+
+            fn exit_block(&mut self, cx: &mut LintContext, block: LintTag<'_>) {
+                cx.report("L002", format!("exit {}", block.name), block.span);
+            }
+        }
+
+        template.walk_blocks(cx, &mut Visitor);
+    }
+}
+
+/// Reports how many times it has run, like a plugin whose answers can change between calls.
+struct RunCounter(Arc<AtomicUsize>);
+
+impl LintRule for RunCounter {
+    fn name(&self) -> &str {
+        "run-counter"
+    }
+
+    fn check(&self, _template: &LintTemplate<'_>, cx: &mut LintContext) {
+        let run = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        cx.report("L003", format!("run {run}"), Span::new(0, 0));
+    }
+}
+
+fn lint_db(rules: LintRules) -> TestDatabase {
+    standard_validation_db()
+        .expect("standard validation fixture should build")
+        .with_lint_rules(rules)
+}
+
+fn rule_errors(db: &TestDatabase, source: &str) -> Vec<ValidationError> {
+    collect_errors(db, "test.html", source)
+        .expect("template validation errors should be collected")
+        .into_iter()
+        .filter(|error| matches!(error, ValidationError::LintRuleViolation { .. }))
+        .collect()
+}
+
+#[test]
+fn lint_rule_reports_diagnostics_with_its_own_code() {
+    let mut rules = LintRules::new();
+    rules.register(NoSafeFilter);
+    let db = lint_db(rules);
+    let source = "{{ title }}{{ body|safe }}";
+
+    let errors = rule_errors(&db, source);
+
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let error = &errors[0];
+    assert_eq!(error.code(), "L001");
+    assert_eq!(error.to_string(), "'body' is marked safe");
+    let span = error.primary_span().expect("rule diagnostics have a span");
+    assert_eq!(&source[span.start_usize()..span.end_usize()], "safe");
+}
+
+#[test]
+fn block_visitor_sees_segments_between_enter_and_exit() {
+    let mut rules = LintRules::new();
+    rules.register(BlockTrace);
+    let db = lint_db(rules);
+
+    let errors = rule_errors(
+        &db,
+        "{% if a %}{% for x in xs %}{% empty %}{% endfor %}{% else %}{% endif %}",
+    );
+
+    assert_eq!(
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        [
+            "enter if",
+            "enter for",
+            "segment empty",
+            "exit for",
+            "segment else",
+            "exit if",
+        ]
+    );
+}
+
+#[test]
+fn rules_are_skipped_for_templates_without_findings() {
+    let mut rules = LintRules::new();
+    rules.register(NoSafeFilter);
+    rules.register(BlockTrace);
+    let db = lint_db(rules);
+
+    assert!(rule_errors(&db, "<p>{{ title|upper }}</p>").is_empty());
+}
+
+#[test]
+fn untracked_rules_run_each_time_diagnostics_are_collected() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut rules = LintRules::new();
+    rules.register(RunCounter(Arc::clone(&runs)));
+    let db = standard_validation_db()
+        .expect("standard validation fixture should build")
+        .with_untracked_lint_rules(rules);
+    db.add_file("test.html", "<p>{{ title }}</p>")
+        .expect("fixture file should be written");
+    let file = db
+        .file(Utf8Path::new("test.html"))
+        .expect("fixture file should exist in the test database");
+    let rule_messages = |diagnostics: &[ValidationError]| {
+        diagnostics
+            .iter()
+            .filter(|error| matches!(error, ValidationError::LintRuleViolation { .. }))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let first = collect_template_diagnostics(&db, file);
+    let second = collect_template_diagnostics(&db, file);
+
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert_eq!(rule_messages(&first.validation_errors), ["run 1"]);
+    assert_eq!(rule_messages(&second.validation_errors), ["run 2"]);
+}
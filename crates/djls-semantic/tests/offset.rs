@@ -229,6 +229,34 @@ fn identifies_template_block_name_context() {
     );
 }
 
+#[test]
+fn quoted_url_name_has_route_context() {
+    let db = TestDatabase::new();
+    let source = r#"{% url "blog:detail" pk=1 %}{% url name %}"#;
+
+    let context = context_for_source(
+        &db,
+        source,
+        offset_of(source, "detail").expect("fixture offset should resolve"),
+    )
+    .expect("route context fixture should load");
+    assert_eq!(
+        context,
+        SemanticOffsetContext::Route {
+            name: "blog:detail".to_string(),
+            span: Span::new(8, 11),
+        }
+    );
+
+    let context = context_for_source(
+        &db,
+        source,
+        offset_of(source, "name").expect("fixture offset should resolve"),
+    )
+    .expect("route context fixture should load");
+    assert_eq!(context, SemanticOffsetContext::None);
+}
+
 #[test]
 fn captured_intermediate_has_no_tag_definition_context() {
     let db = TestDatabase::new();
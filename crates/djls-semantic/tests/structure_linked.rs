@@ -0,0 +1,57 @@
+use camino::Utf8Path;
+use djls_semantic::LinkedBlockNames;
+use djls_semantic::linked_block_names_at;
+use djls_source::Offset;
+use djls_source::Span;
+use djls_templates::parse_template;
+use djls_testing::TestDatabase;
+
+fn linked_names_at(source: &str, offset: usize) -> anyhow::Result<Option<LinkedBlockNames>> {
+    let db = TestDatabase::new();
+    db.add_file("test.html", source)?;
+    let file = db.file(Utf8Path::new("test.html"))?;
+    let nodelist = match parse_template(&db, file) {
+        djls_templates::TemplateParseResult::Parsed(nodelist) => nodelist,
+        djls_templates::TemplateParseResult::NotTemplate => {
+            anyhow::bail!("fixture file is not a template")
+        }
+        djls_templates::TemplateParseResult::Unreadable(error) => return Err(error.into()),
+    };
+    Ok(linked_block_names_at(
+        &db,
+        file,
+        nodelist,
+        Offset::new(u32::try_from(offset)?),
+    ))
+}
+
+#[test]
+fn block_name_links_opener_and_closer() {
+    let source = "{% block content %}\n{% block inner %}{% endblock %}\n{% endblock content %}";
+    let opener = source.find("content").expect("fixture should name the block");
+    let closer = source.rfind("content").expect("fixture should name the closer");
+    let expected = Some(LinkedBlockNames {
+        opener: Span::saturating_from_parts_usize(opener, "content".len()),
+        closer: Span::saturating_from_parts_usize(closer, "content".len()),
+    });
+
+    assert_eq!(
+        linked_names_at(source, opener).expect("fixture should parse"),
+        expected
+    );
+    assert_eq!(
+        linked_names_at(source, closer + "content".len()).expect("fixture should parse"),
+        expected
+    );
+}
+
+#[test]
+fn unnamed_closer_is_not_linked() {
+    let source = "{% block content %}{% endblock %}";
+    let opener = source.find("content").expect("fixture should name the block");
+
+    assert_eq!(
+        linked_names_at(source, opener).expect("fixture should parse"),
+        None
+    );
+}
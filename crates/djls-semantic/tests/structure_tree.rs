@@ -218,6 +218,7 @@ impl From<&Node> for NodeView {
                 var_span,
                 filters,
                 span,
+                ..
             } => Self::Variable {
                 var: var.clone(),
                 var_span: *var_span,
@@ -0,0 +1,36 @@
+use camino::Utf8Path;
+use djls_semantic::untranslated_text;
+use djls_testing::standard_validation_db;
+
+#[test]
+fn untranslated_text_skips_markup_and_translated_bodies() {
+    let db = standard_validation_db().expect("standard validation fixture should build");
+    let source = "{% load i18n %}<title>{% trans \"Home\" %}</title>\n\
+                  <h1 title=\"Skipped\">Welcome back,\n   {{ user.name }}!</h1>\
+                  <!-- Not shown --><script>alert(\"hi\")</script>&nbsp;&times; 1 < 2\
+                  {% blocktrans %}Hello {{ name }}{% endblocktrans %}\
+                  {% if items %}<p>You have items</p>{% endif %}";
+    db.add_file("test.html", source)
+        .expect("fixture file should be written");
+    let file = db
+        .file(Utf8Path::new("test.html"))
+        .expect("fixture file should exist in the test database");
+
+    let found = untranslated_text(&db, file);
+
+    assert_eq!(
+        found
+            .iter()
+            .map(|run| {
+                (
+                    run.text.as_str(),
+                    &source[run.span.start_usize()..run.span.end_usize()],
+                )
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            ("Welcome back,", "Welcome back,"),
+            ("You have items", "You have items"),
+        ]
+    );
+}
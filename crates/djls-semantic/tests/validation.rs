@@ -1,12 +1,17 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::fmt::Write;
-use std::fs;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::ComplexityLimits;
+use djls_conf::DiagnosticLimits;
+use djls_conf::DiagnosticSeverity;
+use djls_conf::DiagnosticsConfig;
+use djls_conf::Dialect;
 use djls_conf::TagDef;
 use djls_conf::TagLibraryDef;
+use djls_conf::TagShorthandDef;
 use djls_conf::TagSpecDef;
 use djls_conf::TagTypeDef;
 use djls_project::ArgumentCountConstraint;
@@ -19,13 +24,16 @@ use djls_semantic::Db as SemanticDb;
 use djls_semantic::TagRole;
 use djls_semantic::TagSpec;
 use djls_semantic::TagSpecs;
+use djls_semantic::TemplateMetrics;
 use djls_semantic::ValidationError;
 use djls_semantic::ValidationErrorAccumulator;
+use djls_semantic::apply_fixes;
 use djls_semantic::builtin_tag_specs;
 use djls_semantic::library_tag_specs;
 use djls_semantic::semantic_grammar_vocabulary;
 use djls_semantic::tag_spec_at;
 use djls_semantic::tag_specs_for_file;
+use djls_semantic::template_metrics;
 use djls_semantic::validate_template_file;
 use djls_templates::parse_template;
 use djls_testing::ProjectFixture;
@@ -112,6 +120,65 @@ fn collect_file_errors(db: &TestDatabase, path: &str) -> anyhow::Result<Vec<Vali
     )
 }
 
+#[test]
+fn foreign_dialect_pragma_skips_django_validation() {
+    let db = standard_db().expect("standard validation database should be created");
+    let body = "{% if user %}{{ user|unknown_filter }}{% mystery %}\n";
+
+    let django_errors = collect_errors(&db, "django.html", body)
+        .expect("template validation errors should be collected");
+    assert!(!django_errors.is_empty());
+
+    let jinja_errors = collect_errors(
+        &db,
+        "jinja.html",
+        &format!("{{# djls: dialect=jinja2 #}}\n{body}"),
+    )
+    .expect("template validation errors should be collected");
+    assert!(jinja_errors.is_empty(), "unexpected errors: {jinja_errors:?}");
+
+    let explicit_errors = collect_errors(
+        &db,
+        "explicit.html",
+        &format!("{{# djls: dialect=django #}}\n{body}"),
+    )
+    .expect("template validation errors should be collected");
+    assert_eq!(explicit_errors.len(), django_errors.len());
+}
+
+#[test]
+fn configured_dialect_directories_skip_django_validation() {
+    let mut db = TestDatabase::new();
+    let body = "{% if user %}\n";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .dialects(
+            [(Utf8PathBuf::from("jinja2"), Dialect::Jinja2)]
+                .into_iter()
+                .collect(),
+        )
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates', '/proj/jinja2'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/page.html", body)
+        .file("/proj/jinja2/page.html", body)
+        .file(
+            "/proj/jinja2/pinned.html",
+            format!("{{# djls: dialect=django #}}\n{body}"),
+        )
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors = |path| {
+        collect_file_errors(&db, path).expect("fixture file validation errors should be collected")
+    };
+
+    assert!(!errors("/proj/templates/page.html").is_empty());
+    assert!(errors("/proj/jinja2/page.html").is_empty());
+    assert!(!errors("/proj/jinja2/pinned.html").is_empty());
+}
+
 #[test]
 fn repeated_project_symbols_keep_occurrence_diagnostics_across_load_prefixes() {
     let mut db = TestDatabase::new();
@@ -467,6 +534,54 @@ fn source_less_configured_library_preserves_block_structure() {
     )));
 }
 
+#[test]
+fn shorthand_tag_spec_adds_block_structure_to_registered_tag() {
+    let mut db = TestDatabase::new();
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .tag_specs(TagSpecDef {
+            tags: BTreeMap::from([(
+                "panel".to_string(),
+                TagShorthandDef {
+                    end: Some("endpanel".to_string()),
+                    intermediates: vec!["fallback".to_string()],
+                },
+            )]),
+            ..TagSpecDef::default()
+        })
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False, 'OPTIONS': {'libraries': {'panels': 'panel_tags'}}}]\n",
+        )
+        .file(
+            "/proj/panel_tags.py",
+            "from django import template\nregister = template.Library()\n@register.tag\ndef panel(parser, token): return build_panel(parser, token)\n",
+        )
+        .file(
+            "/proj/templates/page.html",
+            "{% load panels %}{% panel %}body{% fallback %}",
+        )
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors = collect_file_errors(&db, "/proj/templates/page.html")
+        .expect("fixture file validation errors should be collected");
+    assert!(
+        errors.iter().any(
+            |error| matches!(error, ValidationError::UnclosedTag { tag, .. } if tag == "panel")
+        ),
+        "shorthand end tag should make the registered tag a block: {errors:?}"
+    );
+    assert!(
+        !errors.iter().any(|error| matches!(
+            error,
+            ValidationError::UnknownTag { tag, .. } | ValidationError::OrphanedTag { tag, .. }
+                if tag == "fallback"
+        )),
+        "shorthand intermediates should be recognized inside the block: {errors:?}"
+    );
+}
+
 #[test]
 fn loaded_source_less_alias_suppresses_same_named_available_in_app_guidance() {
     let mut db = TestDatabase::new();
@@ -2564,6 +2679,93 @@ fn variable_before_extends_s122() {
     assert_eq!(s122.len(), 1, "Expected S122, got: {s122:?}");
 }
 
+fn content_outside_blocks(errors: &[ValidationError]) -> Vec<(String, usize)> {
+    errors
+        .iter()
+        .filter(|error| matches!(error, ValidationError::ContentOutsideBlock { .. }))
+        .map(|error| {
+            (
+                error.to_string(),
+                error.primary_span().map_or(0, |span| span.start_usize()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn content_outside_blocks_in_child_template_s137() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% extends \"base.html\" %}\n\
+                  {% load i18n %}\n\
+                  {# note #}\n\
+                  <h1>{{ title }}</h1>\n\
+                  {% if user %}{% block content %}Hi{% endblock %}{% endif %}\n";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        content_outside_blocks(&errors),
+        vec![
+            (
+                "Text outside a block is never rendered in a template that extends another"
+                    .to_string(),
+                source.find("<h1>").expect("fixture should have a heading"),
+            ),
+            (
+                "Variable outside a block is never rendered in a template that extends another"
+                    .to_string(),
+                source.find("{{ title }}").expect("fixture should print the title"),
+            ),
+            (
+                "Text outside a block is never rendered in a template that extends another"
+                    .to_string(),
+                source.find("</h1>").expect("fixture should close the heading"),
+            ),
+            (
+                "'if' tag outside a block is never rendered in a template that extends another"
+                    .to_string(),
+                source.find("{% if").expect("fixture should wrap the block"),
+            ),
+        ]
+    );
+    assert!(errors
+        .iter()
+        .filter(|error| matches!(error, ValidationError::ContentOutsideBlock { .. }))
+        .all(|error| error.code() == "S137"));
+}
+
+#[test]
+fn strict_child_templates_report_loads_and_comments_s137() {
+    let mut config = DiagnosticsConfig::default();
+    config.set_strict_child_templates(true);
+    let db = standard_db()
+        .expect("standard validation fixture should build")
+        .with_diagnostics_config(config);
+    let source = "{% extends \"base.html\" %}{% load i18n %}{# note #}{% block content %}{% endblock %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        content_outside_blocks(&errors)
+            .into_iter()
+            .map(|(_, start)| start)
+            .collect::<Vec<_>>(),
+        vec![
+            source.find("{% load").expect("fixture should load i18n"),
+            source.find("{#").expect("fixture should have a comment"),
+        ]
+    );
+}
+
+#[test]
+fn templates_without_extends_have_no_s137() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let errors = collect_all_errors(&db, "<h1>{{ title }}</h1>{% block content %}{% endblock %}")
+        .expect("template validation errors should be collected");
+
+    assert!(content_outside_blocks(&errors).is_empty());
+}
+
 #[test]
 fn multiple_extends_s123() {
     let db = standard_db().expect("standard validation fixture should build");
@@ -2575,6 +2777,14 @@ fn multiple_extends_s123() {
         .filter(|e| matches!(e, ValidationError::MultipleExtends { .. }))
         .collect();
     assert_eq!(s123.len(), 1, "Expected S123, got: {s123:?}");
+    assert!(
+        matches!(
+            s123[0],
+            ValidationError::MultipleExtends { first_span, .. }
+                if (first_span.start(), first_span.length()) == (0, 25)
+        ),
+        "S123 should point back at the first extends, got: {s123:?}"
+    );
     // First extends should NOT produce S122
     let s122: Vec<_> = errors
         .iter()
@@ -2653,102 +2863,295 @@ fn opaque_extends_after_active_extends_does_not_count_as_second_extends() {
     );
 }
 
-// Corpus / template validation tests
-//
-// These tests extract rules from real Django source files and validate
-// real templates against those rules, proving zero false positives for
-// argument validation (S114, S115, S116, S117) at scale.
-//
-// All tests skip gracefully when the corpus is unavailable.
-// Run `cargo run -p djls-testing --bin corpus -- sync` to populate it.
+fn extends_argument_errors(source: &str) -> Vec<ValidationError> {
+    let db = standard_db().expect("standard validation fixture should build");
+    collect_all_errors(&db, source)
+        .expect("template validation errors should be collected")
+        .into_iter()
+        .filter(|e| {
+            matches!(
+                e,
+                ValidationError::InvalidExtendsArgument { .. }
+                    | ValidationError::DynamicExtends { .. }
+            )
+        })
+        .collect()
+}
 
-use djls_testing::Corpus;
-use djls_testing::build_entry_specs;
-use djls_testing::build_specs_from_extraction;
-use djls_testing::collect_argument_validation_errors_with_revision;
+#[test]
+fn literal_extends_arguments_are_accepted() {
+    for source in [
+        r#"{% extends "base.html" %}"#,
+        "{% extends 'base.html' %}",
+        r#"{% extends "base.html"|lower %}"#,
+    ] {
+        let errors = extends_argument_errors(source);
+        assert!(errors.is_empty(), "{source}: {errors:?}");
+    }
+}
 
-struct FailureEntry {
-    path: Utf8PathBuf,
-    errors: Vec<String>,
+#[test]
+fn variable_extends_argument_reports_dynamic_parent() {
+    let errors = extends_argument_errors(r#"{% extends layout|default:"base.html" %}"#);
+
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let ValidationError::DynamicExtends { variable, span } = &errors[0] else {
+        panic!("expected S130, got {errors:?}");
+    };
+    assert_eq!(variable, "layout");
+    assert_eq!((span.start(), span.length()), (11, 6));
+    assert_eq!(errors[0].code(), "S130");
 }
 
-fn format_failures(failures: &[FailureEntry]) -> Result<String, std::fmt::Error> {
-    let mut out = String::new();
-    for failure in failures.iter().take(20) {
-        writeln!(out, "  {}:", failure.path)?;
-        for error in &failure.errors {
-            writeln!(out, "    - {error}")?;
-        }
-    }
-    if failures.len() > 20 {
-        writeln!(out, "  ... and {} more", failures.len() - 20)?;
-    }
-    Ok(out)
+#[test]
+fn number_extends_argument_is_invalid() {
+    let errors = extends_argument_errors("{% extends 42 %}");
+
+    assert!(
+        matches!(
+            errors.as_slice(),
+            [ValidationError::InvalidExtendsArgument { argument, .. }] if argument == "42"
+        ),
+        "{errors:?}"
+    );
 }
 
 #[test]
-fn corpus_templates_have_no_argument_false_positives() {
-    let corpus = Corpus::require().expect("synced corpus should be available for corpus tests");
+fn duplicate_block_name_reports_second_definition_with_first_location() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% block content %}{% endblock %}\n\
+                  {% if flag %}{% block content %}{% endblock %}{% endif %}\n\
+                  {% block sidebar %}{% endblock %}";
+    let errors: Vec<_> = collect_all_errors(&db, source)
+        .expect("template validation errors should be collected")
+        .into_iter()
+        .filter(|e| matches!(e, ValidationError::DuplicateBlockName { .. }))
+        .collect();
+
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let ValidationError::DuplicateBlockName {
+        name,
+        span,
+        first_span,
+    } = &errors[0]
+    else {
+        panic!("expected S131, got {errors:?}");
+    };
+    assert_eq!(name, "content");
+    assert_eq!(span.start_usize(), source.rfind("content").expect("second block name"));
+    assert_eq!(first_span.start_usize(), source.find("content").expect("first block name"));
+    assert_eq!(errors[0].code(), "S131");
+    assert_eq!(
+        errors[0].related_spans(),
+        vec![(*first_span, "first definition of this block".to_string())]
+    );
+}
+
+fn fixed_source(source: &str, errors: &[ValidationError]) -> String {
+    let fixes: Vec<_> = errors.iter().filter_map(ValidationError::fix).collect();
+    apply_fixes(source, &fixes).0
+}
+
+#[test]
+fn unclosed_tag_fixes_insert_end_tags_where_blocks_were_implicitly_closed() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% block content %}{% if a %}x{% endblock %}\n\
+                  {% for item in items %}{% with y=1 %}";
+    let errors = collect_all_errors(&db, source)
+        .expect("template validation errors should be collected");
+
+    assert_eq!(
+        errors.iter().map(ValidationError::code).collect::<Vec<_>>(),
+        ["S100", "S100", "S100"],
+        "{errors:?}"
+    );
+    assert_eq!(
+        fixed_source(source, &errors),
+        "{% block content %}{% if a %}x{% endif %}{% endblock %}\n\
+         {% for item in items %}{% with y=1 %}{% endwith %}{% endfor %}"
+    );
+}
 
-    let templates = corpus.templates_in(corpus.root());
-    let mut by_entry: BTreeMap<Utf8PathBuf, Vec<Utf8PathBuf>> = BTreeMap::new();
+#[test]
+fn unknown_load_symbol_fix_removes_only_the_missing_symbol() {
+    let db = standard_db().expect("standard validation fixture should build");
 
-    for template_path in templates {
-        let Some(entry_dir) = corpus.entry_dir_for_path(&template_path) else {
-            continue;
-        };
+    for (source, expected) in [
+        ("{% load trans static from i18n %}", "{% load trans from i18n %}"),
+        ("{% load static trans from i18n %}", "{% load trans from i18n %}"),
+        ("{% load static %}\n{% load nope from i18n %}\n", "{% load static %}\n\n"),
+    ] {
+        let errors: Vec<_> = collect_all_errors(&db, source)
+            .expect("template validation errors should be collected")
+            .into_iter()
+            .filter(|error| error.code() == "S128")
+            .collect();
 
-        by_entry.entry(entry_dir).or_default().push(template_path);
+        assert_eq!(errors.len(), 1, "{source}: {errors:?}");
+        assert_eq!(fixed_source(source, &errors), expected, "{source}");
     }
+}
+
+#[test]
+fn unmatched_block_name_fix_renames_the_closing_block() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% block content %}{% endblock sidebar %}";
+    let errors = collect_all_errors(&db, source)
+        .expect("template validation errors should be collected");
+
+    assert_eq!(
+        fixed_source(source, &errors),
+        "{% block content %}{% endblock content %}"
+    );
+}
+
+fn db_with_severity(code: &str, severity: DiagnosticSeverity) -> TestDatabase {
+    let mut config = DiagnosticsConfig::default();
+    config.set_severity(code, severity);
+    standard_db()
+        .expect("standard validation fixture should build")
+        .with_diagnostics_config(config)
+}
+
+#[test]
+fn unused_load_s147_is_off_by_default() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let errors = collect_all_errors(&db, "{% load humanize %}<p>Hi</p>")
+        .expect("template validation errors should be collected");
+
+    assert!(errors.iter().all(|error| error.code() != "S147"), "{errors:?}");
+}
+
+#[test]
+fn unused_load_fix_removes_the_unused_name_or_the_whole_tag() {
+    let db = db_with_severity("S147", DiagnosticSeverity::Warning);
+
+    for (source, expected) in [
+        ("{% load humanize %}<p>Hi</p>", "<p>Hi</p>"),
+        ("{% load i18n static %}{% trans 'Hi' %}", "{% load i18n %}{% trans 'Hi' %}"),
+        ("{% load static i18n %}{% trans 'Hi' %}", "{% load i18n %}{% trans 'Hi' %}"),
+        (
+            "{% load trans blocktrans from i18n %}{% trans 'Hi' %}",
+            "{% load trans from i18n %}{% trans 'Hi' %}",
+        ),
+        ("{{ n|intcomma }}{% load humanize %}", "{{ n|intcomma }}"),
+    ] {
+        let errors: Vec<_> = collect_all_errors(&db, source)
+            .expect("template validation errors should be collected")
+            .into_iter()
+            .filter(|error| error.code() == "S147")
+            .collect();
 
-    for templates in by_entry.values_mut() {
-        templates.sort();
+        assert_eq!(errors.len(), 1, "{source}: {errors:?}");
+        assert_eq!(fixed_source(source, &errors), expected, "{source}");
     }
+}
 
-    let mut failures = Vec::new();
+#[test]
+fn unused_load_names_the_library_a_symbol_comes_from() {
+    let db = db_with_severity("S147", DiagnosticSeverity::Warning);
+    let messages = |source| {
+        collect_all_errors(&db, source)
+            .expect("template validation errors should be collected")
+            .iter()
+            .filter(|error| error.code() == "S147")
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
 
-    for (entry_dir, templates) in by_entry {
-        if templates.is_empty() {
-            continue;
-        }
+    assert_eq!(
+        messages("{% load humanize %}"),
+        ["Template tag library 'humanize' is loaded but never used"]
+    );
+    assert_eq!(
+        messages("{% load blocktrans from i18n %}"),
+        ["'blocktrans' is loaded from 'i18n' but never used"]
+    );
+}
 
-        let (specs, arities) = build_entry_specs(&corpus, &entry_dir)
-            .expect("corpus entry tag and filter specs should build");
-        let db = TestDatabase::new()
-            .with_projectless_tag_specs(specs)
-            .with_projectless_filter_arity_specs(arities);
-
-        for (i, template_path) in templates.into_iter().enumerate() {
-            let Ok(content) = fs::read_to_string(template_path.as_std_path()) else {
-                continue;
-            };
-
-            let errors = collect_argument_validation_errors_with_revision(
-                &db,
-                "corpus_test.html",
-                i as u64,
-                &content,
-            )
-            .expect("corpus template argument errors should be collected");
-            if errors.is_empty() {
-                continue;
-            }
+#[test]
+fn loads_used_by_tags_filters_or_tag_arguments_are_not_reported() {
+    let db = db_with_severity("S147", DiagnosticSeverity::Warning);
 
-            failures.push(FailureEntry {
-                path: template_path,
-                errors: errors
-                    .into_iter()
-                    .take(5)
-                    .map(|e| format!("{e:?}"))
-                    .collect(),
-            });
-        }
+    for source in [
+        "{% load static %}{% static 'app.css' %}",
+        "{% load humanize %}{{ count|intcomma }}",
+        "{% load humanize %}{% if count|intcomma %}{% endif %}",
+        "{% load humanize %}{% filter intcomma %}1000{% endfilter %}",
+        "{% load trans from i18n %}{% trans 'Hi' %}",
+        "{% load example_unknown %}",
+    ] {
+        let errors = collect_all_errors(&db, source)
+            .expect("template validation errors should be collected");
+
+        assert!(
+            errors.iter().all(|error| error.code() != "S147"),
+            "{source}: {errors:?}"
+        );
     }
+}
+
+#[test]
+fn tag_delimiter_spacing_s148_fix_leaves_one_space_inside_each_delimiter() {
+    let source = "{%if a%}x{%  endif %}{% if b %}{% endif   %}";
+
+    let db = standard_db().expect("standard validation fixture should build");
+    let errors = collect_all_errors(&db, source)
+        .expect("template validation errors should be collected");
+    assert!(errors.iter().all(|error| error.code() != "S148"), "{errors:?}");
+
+    let db = db_with_severity("S148", DiagnosticSeverity::Hint);
+    let errors: Vec<_> = collect_all_errors(&db, source)
+        .expect("template validation errors should be collected")
+        .into_iter()
+        .filter(|error| error.code() == "S148")
+        .collect();
+
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert_eq!(
+        errors[0].to_string(),
+        "Tag delimiters should have one space inside them, as in '{% if a %}'"
+    );
+    assert_eq!(
+        fixed_source(source, &errors),
+        "{% if a %}x{% endif %}{% if b %}{% endif %}"
+    );
+}
+
+// Corpus / template validation tests
+//
+// These tests extract rules from real Django source files and validate
+// real templates against those rules, holding argument validation (S114,
+// S115, S116, S117) to the per-entry false-positive budget committed in
+// crates/djls-testing/false-positive-budget.json.
+//
+// All tests skip gracefully when the corpus is unavailable.
+// Run `cargo run -p djls-testing --bin corpus -- sync` to populate it.
+
+use djls_testing::Corpus;
+use djls_testing::FalsePositiveBudget;
+use djls_testing::FalsePositiveReport;
+use djls_testing::build_specs_from_extraction;
+use djls_testing::collect_argument_validation_errors_with_revision;
+
+#[test]
+fn corpus_templates_stay_within_false_positive_budget() {
+    let corpus = Corpus::require().expect("synced corpus should be available for corpus tests");
 
+    let report = FalsePositiveReport::collect(&corpus)
+        .expect("corpus templates should validate against extracted rules");
+    let report_path =
+        Utf8Path::new(env!("CARGO_TARGET_TMPDIR")).join("corpus-false-positives.json");
+    report
+        .write(&report_path)
+        .expect("false-positive report should be written");
+
+    let budget = FalsePositiveBudget::committed().expect("committed budget should load");
+    let over_budget = report.over_budget(&budget);
     assert!(
-        failures.is_empty(),
-        "Corpus templates have false positives:\n{}",
-        format_failures(&failures).expect("corpus failures should format")
+        over_budget.is_empty(),
+        "Corpus templates exceed their false-positive budget (full report: {report_path}):\n{}",
+        over_budget.join("\n")
     );
 }
 
@@ -2855,3 +3258,675 @@ fn corpus_known_invalid_templates_produce_errors() {
         "Expected expression syntax error for {{% if and x %}}"
     );
 }
+
+#[test]
+fn include_with_only_reports_variables_the_partial_needs_but_is_not_passed() {
+    let mut db = TestDatabase::new();
+    let partial = "{% for item in items %}{{ item.name }}{{ forloop.counter }}{% endfor %}\
+                   {% with total=count %}{{ total }}{% endwith %}{% url 'home' as home %}\
+                   {{ title }} {{ subtitle|upper }} {{ home }} {{ \"literal\" }} {{ 3 }}";
+    let page = "{% include \"card.html\" with title=heading only %}\n\
+                {% include \"card.html\" only %}\n\
+                {% include \"card.html\" %}\n\
+                {% include \"card.html\" with title=heading subtitle=tagline only %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/card.html", partial)
+        .file("/proj/templates/page.html", page)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors: Vec<_> = collect_file_errors(&db, "/proj/templates/page.html")
+        .expect("fixture file validation errors should be collected")
+        .into_iter()
+        .filter(|error| matches!(error, ValidationError::IncludeMissingVariables { .. }))
+        .collect();
+
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.to_string(), error.primary_span().map(|span| span.start_usize())))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "'card.html' is included with 'only' but uses 'subtitle', which this include \
+                 does not pass"
+                    .to_string(),
+                page.find("\"card.html\""),
+            ),
+            (
+                "'card.html' is included with 'only' but uses 'subtitle', 'title', which this \
+                 include does not pass"
+                    .to_string(),
+                page.match_indices("\"card.html\"").nth(1).map(|(offset, _)| offset),
+            ),
+        ]
+    );
+    assert_eq!(errors[0].code(), "S133");
+}
+
+#[test]
+fn un_namespaced_app_template_shadowing_another_app_is_reported() {
+    let mut db = TestDatabase::new();
+    let page = "{% include \"form.html\" %}\n\
+                {% include \"blog/list.html\" %}\n\
+                {% include \"widget.html\" %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = ['blog', 'shop']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': [], 'APP_DIRS': True}]\n",
+        )
+        .file("/proj/blog/__init__.py", "")
+        .file("/proj/blog/templates/form.html", "blog form")
+        .file("/proj/blog/templates/widget.html", "widget")
+        .file("/proj/blog/templates/blog/list.html", "blog list")
+        .file("/proj/blog/templates/blog/page.html", page)
+        .file("/proj/shop/__init__.py", "")
+        .file("/proj/shop/templates/form.html", "shop form")
+        .file("/proj/shop/templates/blog/list.html", "shop list")
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors: Vec<_> = collect_file_errors(&db, "/proj/blog/templates/blog/page.html")
+        .expect("fixture file validation errors should be collected")
+        .into_iter()
+        .filter(|error| matches!(error, ValidationError::AmbiguousTemplateName { .. }))
+        .collect();
+
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.to_string(), error.primary_span().map(|span| span.start_usize())))
+            .collect::<Vec<_>>(),
+        vec![(
+            "Template 'form.html' resolves to app 'blog' and shadows the same name in 'shop'; \
+             namespace it as 'blog/form.html'"
+                .to_string(),
+            page.find("\"form.html\""),
+        )]
+    );
+    assert_eq!(errors[0].code(), "S142");
+}
+
+#[test]
+fn template_hidden_by_an_earlier_directory_is_reported_as_shadowed() {
+    let mut db = TestDatabase::new();
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = ['blog']\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': True}]\n",
+        )
+        .file("/proj/templates/form.html", "project form")
+        .file("/proj/blog/__init__.py", "")
+        .file("/proj/blog/templates/form.html", "<form>\n</form>")
+        .file("/proj/blog/templates/list.html", "list")
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let shadowed = |path| {
+        collect_file_errors(&db, path)
+            .expect("fixture file validation errors should be collected")
+            .into_iter()
+            .filter(|error| matches!(error, ValidationError::ShadowedTemplate { .. }))
+            .map(|error| {
+                let span = error
+                    .primary_span()
+                    .map(|span| (span.start_usize(), span.end_usize()));
+                (error.to_string(), span, error.code())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        shadowed("/proj/blog/templates/form.html"),
+        vec![(
+            "Template 'form.html' is shadowed by '/proj/templates/form.html', which the template \
+             loaders find first"
+                .to_string(),
+            Some((0, 6)),
+            "S143",
+        )]
+    );
+    assert!(shadowed("/proj/templates/form.html").is_empty());
+    assert!(shadowed("/proj/blog/templates/list.html").is_empty());
+}
+
+#[test]
+fn template_over_its_limits_reports_only_s144() {
+    let mut limits = DiagnosticLimits::default();
+    limits.set_max_nesting_depth(2);
+    let mut config = DiagnosticsConfig::default();
+    config.set_limits(limits);
+    let db = standard_db()
+        .expect("standard validation fixture should build")
+        .with_diagnostics_config(config);
+
+    let shallow = collect_errors(
+        &db,
+        "shallow.html",
+        "{% if a %}{{ x|unknown_filter }}{% endif %}",
+    )
+    .expect("template validation errors should be collected");
+    assert!(shallow.iter().all(|error| error.code() != "S144"));
+
+    let deep = collect_errors(
+        &db,
+        "deep.html",
+        "{% if a %}{% if b %}{% if c %}{{ x|unknown_filter }}{% endif %}{% endif %}{% endif %}",
+    )
+    .expect("template validation errors should be collected");
+    assert_eq!(
+        deep.iter()
+            .map(|error| (error.code(), error.to_string()))
+            .collect::<Vec<_>>(),
+        vec![(
+            "S144",
+            "Template has 3 levels of nesting, over the limit of 2, so it is not validated"
+                .to_string(),
+        )]
+    );
+}
+
+#[test]
+fn template_over_a_complexity_threshold_gets_s146_once_enabled() {
+    let source = "{% for item in items %}{% if item.a %}{{ item }}{% elif item.b %}{{ other }}\
+                  {% endif %}{% endfor %}{% include 'row.html' %}";
+    let mut complexity = ComplexityLimits::default();
+    complexity.set_max_branches(2);
+    complexity.set_max_includes(0);
+
+    let mut config = DiagnosticsConfig::default();
+    config.set_complexity(complexity);
+    let db = standard_db()
+        .expect("standard validation fixture should build")
+        .with_diagnostics_config(config.clone());
+    let errors = collect_errors(&db, "off.html", source)
+        .expect("template validation errors should be collected");
+    assert!(errors.iter().all(|error| error.code() != "S146"));
+
+    config.set_severity("S146", DiagnosticSeverity::Hint);
+    let db = standard_db()
+        .expect("standard validation fixture should build")
+        .with_diagnostics_config(config);
+    let errors = collect_errors(&db, "on.html", source)
+        .expect("template validation errors should be collected");
+    let file = db
+        .file(Utf8Path::new("on.html"))
+        .expect("fixture file should exist in the test database");
+    assert_eq!(
+        template_metrics(&db, file),
+        TemplateMetrics {
+            nodes: 8,
+            max_depth: 2,
+            includes: 1,
+            variables: 1,
+            branches: 3,
+        }
+    );
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|error| error.code() == "S146")
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "Template has 1 includes, over the complexity limit of 0",
+            "Template has 3 branches, over the complexity limit of 2",
+        ]
+    );
+}
+
+fn forloop_misuses(errors: &[ValidationError]) -> Vec<(String, &'static str, usize)> {
+    errors
+        .iter()
+        .filter(|error| {
+            matches!(
+                error,
+                ValidationError::ForloopOutsideLoop { .. }
+                    | ValidationError::ParentloopWithoutOuterLoop { .. }
+            )
+        })
+        .map(|error| {
+            (
+                error.to_string(),
+                error.code(),
+                error.primary_span().map_or(0, |span| span.start_usize()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn forloop_outside_a_loop_s138() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{{ forloop.counter }}\
+                  {% for item in items %}{% if forloop.first %}{% endif %}\
+                  {% empty %}{% cycle forloop.counter 'odd' %}{% endfor %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        forloop_misuses(&errors),
+        vec![
+            (
+                "'forloop' is only defined inside the body of a '{% for %}' loop".to_string(),
+                "S138",
+                source.find("forloop.counter").expect("fixture should print the counter"),
+            ),
+            (
+                "'forloop' is only defined inside the body of a '{% for %}' loop".to_string(),
+                "S138",
+                source.rfind("forloop.counter").expect("fixture should cycle on the counter"),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn parentloop_without_an_outer_loop_s139() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% for row in rows %}{{ forloop.parentloop.counter }}\
+                  {% for cell in row %}{{ forloop.parentloop.last }}\
+                  {{ forloop.parentloop.parentloop }}{% endfor %}{% endfor %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        forloop_misuses(&errors),
+        vec![
+            (
+                "'forloop.parentloop' needs 2 enclosing '{% for %}' loops but is inside 1"
+                    .to_string(),
+                "S139",
+                source.find("forloop.parentloop").expect("fixture should use parentloop"),
+            ),
+            (
+                "'forloop.parentloop.parentloop' needs 3 enclosing '{% for %}' loops but is \
+                 inside 2"
+                    .to_string(),
+                "S139",
+                source.rfind("forloop.parentloop").expect("fixture should go two loops out"),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn included_templates_may_use_forloop_of_the_including_loop() {
+    let mut db = TestDatabase::new();
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/row.html", "<td>{{ forloop.counter }}</td>")
+        .file("/proj/templates/stray.html", "<td>{{ forloop.counter }}</td>")
+        .file(
+            "/proj/templates/table.html",
+            "{% for item in items %}{% include \"row.html\" %}{% endfor %}",
+        )
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let misuses = |path: &str| {
+        forloop_misuses(
+            &collect_file_errors(&db, path)
+                .expect("fixture file validation errors should be collected"),
+        )
+    };
+
+    assert!(misuses("/proj/templates/row.html").is_empty());
+    assert_eq!(misuses("/proj/templates/stray.html").len(), 1);
+}
+
+fn shadowed_variables(errors: &[ValidationError]) -> Vec<(String, usize, Option<usize>)> {
+    errors
+        .iter()
+        .filter(|error| matches!(error, ValidationError::ShadowedVariable { .. }))
+        .map(|error| {
+            (
+                error.to_string(),
+                error.primary_span().map_or(0, |span| span.start_usize()),
+                error
+                    .related_spans()
+                    .first()
+                    .map(|(span, _)| span.start_usize()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn nested_bindings_that_reuse_an_outer_name_s140() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{% for item in items %}{% for item in item.children %}{{ item }}{% endfor %}\
+                  {% with total=item.price %}{% with total=total|add:1 %}{{ total }}\
+                  {% endwith %}{% endwith %}{% endfor %}{% for item in others %}{% endfor %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+    let item = source
+        .match_indices("item ")
+        .map(|(start, _)| start)
+        .collect::<Vec<_>>();
+    let total = source
+        .match_indices("total=")
+        .map(|(start, _)| start)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        shadowed_variables(&errors),
+        vec![
+            (
+                "'item' shadows the binding of an enclosing tag".to_string(),
+                item[1],
+                Some(item[0]),
+            ),
+            (
+                "'total' shadows the binding of an enclosing tag".to_string(),
+                total[1],
+                Some(total[0]),
+            ),
+        ]
+    );
+    assert!(errors
+        .iter()
+        .filter(|error| matches!(error, ValidationError::ShadowedVariable { .. }))
+        .all(|error| error.code() == "S140"));
+}
+
+#[test]
+fn bindings_that_reuse_a_context_processor_name_s140() {
+    let mut db = TestDatabase::new();
+    let source = "{% for user in members %}{{ user }}{% endfor %}\
+                  {% with request=page.request %}{{ request }}{% endwith %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'OPTIONS': {'context_processors': ['django.contrib.auth.context_processors.auth']}}]\n",
+        )
+        .file("/proj/templates/members.html", source)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors = collect_file_errors(&db, "/proj/templates/members.html")
+        .expect("fixture file validation errors should be collected");
+
+    assert_eq!(
+        shadowed_variables(&errors),
+        vec![(
+            "'user' shadows the variable context processor \
+             'django.contrib.auth.context_processors.auth' adds"
+                .to_string(),
+            source.find("user in").expect("fixture should loop over members"),
+            None,
+        )]
+    );
+}
+
+#[test]
+fn admin_override_reports_blocks_the_admin_template_does_not_define() {
+    let mut db = TestDatabase::new();
+    let source = "{% extends \"admin/change_form.html\" %}\n\
+                  {% block field_set %}{% endblock %}\n\
+                  {% block content %}{% block extra %}{% endblock %}{% endblock %}\n\
+                  {% block object-tools-items %}{% endblock %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/admin/polls/question/change_form.html", source)
+        .file("/proj/templates/polls/change_form.html", source)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let admin_block_errors = |path: &str| {
+        collect_file_errors(&db, path)
+            .expect("fixture file validation errors should be collected")
+            .into_iter()
+            .filter(|error| matches!(error, ValidationError::UnknownAdminBlock { .. }))
+            .collect::<Vec<_>>()
+    };
+
+    let errors = admin_block_errors("/proj/templates/admin/polls/question/change_form.html");
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.to_string(), error.primary_span().map(|span| span.start_usize())))
+            .collect::<Vec<_>>(),
+        vec![(
+            "Block 'field_set' is not defined by admin template 'admin/change_form.html'; did \
+             you mean 'field_sets'?"
+                .to_string(),
+            source.find("field_set"),
+        )]
+    );
+    assert_eq!(errors[0].code(), "S134");
+
+    assert!(admin_block_errors("/proj/templates/polls/change_form.html").is_empty());
+}
+
+#[test]
+fn removed_builtins_report_the_release_that_removed_them() {
+    let mut db = TestDatabase::new();
+    let page = "{% ifequal a b %}{{ items|length_is:2 }}";
+    let assets = "{% load staticfiles %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/page.html", page)
+        .file("/proj/templates/assets.html", assets)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let removed = |path: &str| {
+        let errors = collect_file_errors(&db, path)
+            .expect("fixture file validation errors should be collected");
+        let unknown_ifequal = errors.iter().any(|error| {
+            matches!(error, ValidationError::UnknownTag { tag, .. } if tag == "ifequal")
+        });
+        assert!(
+            !unknown_ifequal,
+            "removed tags should not also be reported as unknown: {errors:?}"
+        );
+        errors
+            .into_iter()
+            .filter(|error| matches!(error, ValidationError::RemovedTemplateSymbol { .. }))
+            .map(|error| {
+                (
+                    error.to_string(),
+                    error.primary_span().map(|span| span.start_usize()),
+                    error.fix().map(|fix| fix.replacement),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        removed("/proj/templates/page.html"),
+        vec![
+            (
+                "'ifequal' tag was removed in Django 4.0; use '{% if a == b %}' instead".to_string(),
+                page.find("ifequal"),
+                None,
+            ),
+            (
+                "'length_is' filter was removed in Django 5.1; compare with the 'length' filter \
+                 in '{% if %}' instead"
+                    .to_string(),
+                page.find("length_is"),
+                None,
+            ),
+        ]
+    );
+    assert_eq!(
+        removed("/proj/templates/assets.html"),
+        vec![(
+            "'staticfiles' library was removed in Django 3.0; load 'static' instead".to_string(),
+            assets.find("staticfiles"),
+            Some("static".to_string()),
+        )]
+    );
+}
+
+#[test]
+fn deprecated_builtins_warn_while_the_project_django_still_provides_them() {
+    let mut db = TestDatabase::new();
+    let source = "{{ items|length_is:2 }}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file("/proj/django/__init__.py", "VERSION = (4, 2, 0, 'final', 0)\n")
+        .file("/proj/django/template/__init__.py", "")
+        .file(
+            "/proj/django/template/defaulttags.py",
+            "from django import template\nregister = template.Library()\n@register.tag\ndef load(parser, token): pass\n",
+        )
+        .file(
+            "/proj/django/template/defaultfilters.py",
+            "from django import template\nregister = template.Library()\n@register.filter\ndef length_is(value, arg): pass\n",
+        )
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/page.html", source)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors = collect_file_errors(&db, "/proj/templates/page.html")
+        .expect("fixture file validation errors should be collected");
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.code(), error.to_string()))
+            .collect::<Vec<_>>(),
+        vec![(
+            "S136",
+            "'length_is' filter is deprecated since Django 4.2 and removed in Django 5.1; \
+             compare with the 'length' filter in '{% if %}' instead"
+                .to_string(),
+        )]
+    );
+}
+
+#[test]
+fn safe_filters_and_autoescape_off_are_reported_as_review_hints() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "{{ article.body|safe }}{% autoescape off %}{{ greeting }}{% endautoescape %}\
+                  {% autoescape on %}{{ title }}{% endautoescape %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|error| error.code().starts_with('X'))
+            .map(|error| {
+                let span = error.primary_span().expect("review hints have a span");
+                (error.code(), &source[span.start_usize()..span.end_usize()])
+            })
+            .collect::<Vec<_>>(),
+        vec![("X100", "safe"), ("X101", "{% autoescape off %}")]
+    );
+}
+
+#[test]
+fn post_forms_without_a_csrf_token_s145() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "<form method=\"post\" action=\"{% url 'save' %}\">{{ form }}</form>\
+                  <FORM METHOD=POST>{% if show %}{% csrf_token %}{% endif %}</FORM>\
+                  <form method=\"post\">{% include 'fields.html' %}</form>\
+                  <form method=\"get\">{{ form }}</form>\
+                  <form method=\"post\" action=\"https://example.com/\"></form>";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+    let csrf = errors
+        .iter()
+        .filter(|error| error.code() == "S145")
+        .collect::<Vec<_>>();
+
+    assert_eq!(csrf.len(), 1, "{csrf:?}");
+    let span = csrf[0]
+        .primary_span()
+        .expect("S145 reports the form's start tag");
+    assert_eq!(
+        &source[span.start_usize()..span.end_usize()],
+        "<form method=\"post\" action=\"{% url 'save' %}\">"
+    );
+    assert_eq!(csrf[0].fix().map(|fix| fix.span.start()), Some(span.end()));
+}
+
+#[test]
+fn accessibility_checks_read_the_html_around_template_tags() {
+    let db = standard_db().expect("standard validation fixture should build");
+    let source = "<img src=\"{{ logo }}\"><img src=\"a.png\" alt=\"\">\
+                  <img {% if lazy %}loading=\"lazy\"{% endif %} src=\"b.png\">\
+                  {% for field in form %}<div id=\"row\">{{ field }}</div>{% endfor %}\
+                  {% for field in form %}{{ field.label_tag }}{{ field }}{% endfor %}\
+                  {% for item in items %}<li id=\"item-{{ item.pk }}\"></li>\
+                  {% empty %}<li id=\"empty\"></li>{% endfor %}";
+    let errors =
+        collect_all_errors(&db, source).expect("template validation errors should be collected");
+
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|error| error.code().starts_with('A'))
+            .map(|error| {
+                let span = error.primary_span().expect("accessibility checks have a span");
+                (error.code(), &source[span.start_usize()..span.end_usize()])
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            ("A100", "<img src=\"{{ logo }}\">"),
+            ("A102", "row"),
+            ("A101", "field"),
+        ]
+    );
+}
+
+#[test]
+fn include_with_only_counts_names_used_outside_the_block_binding_them() {
+    let mut db = TestDatabase::new();
+    let partial = "{% for title in titles %}{{ title }}{% empty %}{{ empty_title }}{% endfor %}\
+                   {% with label=name %}{{ label }}{% endwith %}{{ title }} {{ label }}";
+    let page = "{% include \"card.html\" with titles=items name=heading only %}";
+    ProjectFixture::new("/proj")
+        .django_settings_module("myproject.settings")
+        .file(
+            "/proj/myproject/settings.py",
+            "INSTALLED_APPS = []\nTEMPLATES = [{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['/proj/templates'], 'APP_DIRS': False}]\n",
+        )
+        .file("/proj/templates/card.html", partial)
+        .file("/proj/templates/page.html", page)
+        .install(&mut db)
+        .expect("project fixture should install into the test database");
+
+    let errors: Vec<_> = collect_file_errors(&db, "/proj/templates/page.html")
+        .expect("fixture file validation errors should be collected")
+        .into_iter()
+        .filter(|error| matches!(error, ValidationError::IncludeMissingVariables { .. }))
+        .map(|error| error.to_string())
+        .collect();
+
+    assert_eq!(
+        errors,
+        vec![
+            "'card.html' is included with 'only' but uses 'empty_title', 'label', 'title', which \
+             this include does not pass"
+                .to_string()
+        ]
+    );
+}
@@ -0,0 +1,147 @@
+use camino::Utf8Path;
+use djls_semantic::selection_variables;
+use djls_semantic::variable_occurrences_at;
+use djls_source::Offset;
+use djls_source::Span;
+use djls_templates::parse_template;
+use djls_testing::TestDatabase;
+
+/// Start offsets of every occurrence sharing a binding with the variable at `offset`.
+fn occurrence_starts(source: &str, offset: usize) -> anyhow::Result<Option<Vec<usize>>> {
+    let db = TestDatabase::new();
+    db.add_file("test.html", source)?;
+    let file = db.file(Utf8Path::new("test.html"))?;
+    let nodelist = match parse_template(&db, file) {
+        djls_templates::TemplateParseResult::Parsed(nodelist) => nodelist,
+        djls_templates::TemplateParseResult::NotTemplate => {
+            anyhow::bail!("fixture file is not a template")
+        }
+        djls_templates::TemplateParseResult::Unreadable(error) => return Err(error.into()),
+    };
+    let occurrences =
+        variable_occurrences_at(&db, file, nodelist, Offset::new(u32::try_from(offset)?));
+    Ok(occurrences.map(|occurrences| {
+        occurrences
+            .spans
+            .iter()
+            .map(|span| span.start_usize())
+            .collect()
+    }))
+}
+
+fn variables_in(source: &str, selected: &str) -> anyhow::Result<Option<Vec<String>>> {
+    let db = TestDatabase::new();
+    db.add_file("test.html", source)?;
+    let file = db.file(Utf8Path::new("test.html"))?;
+    let nodelist = match parse_template(&db, file) {
+        djls_templates::TemplateParseResult::Parsed(nodelist) => nodelist,
+        djls_templates::TemplateParseResult::NotTemplate => {
+            anyhow::bail!("fixture file is not a template")
+        }
+        djls_templates::TemplateParseResult::Unreadable(error) => return Err(error.into()),
+    };
+    let Some(start) = source.find(selected) else {
+        anyhow::bail!("fixture should contain the selection")
+    };
+    let selection = Span::saturating_from_parts_usize(start, selected.len());
+    Ok(selection_variables(&db, file, nodelist, selection))
+}
+
+fn starts_of(source: &str, needle: &str) -> Vec<usize> {
+    source.match_indices(needle).map(|(start, _)| start).collect()
+}
+
+#[test]
+fn loop_variable_stays_inside_its_loop() {
+    let source = "{% for item in items %}{{ item.name }}{% endfor %}\
+                  {% for item in others %}{{ item }}{% endfor %}";
+    let item = starts_of(source, "item ");
+    let reference = source.find("item.name").expect("fixture should use the loop variable");
+
+    assert_eq!(
+        occurrence_starts(source, reference).expect("fixture should parse"),
+        Some(vec![item[0], reference])
+    );
+}
+
+#[test]
+fn with_binding_shadows_the_context_variable() {
+    let source = "{{ user }}{% with user=request.user %}{{ user.email }}{% endwith %}\
+                  {% if user %}{% endif %}";
+    let outer = source.find("user }}").expect("fixture should print the context user");
+    let condition = source.rfind("user %}").expect("fixture should test the context user");
+    let binding = source.find("user=").expect("fixture should bind user");
+    let inner = source.find("user.email").expect("fixture should use the binding");
+
+    assert_eq!(
+        occurrence_starts(source, outer).expect("fixture should parse"),
+        Some(vec![outer, condition])
+    );
+    assert_eq!(
+        occurrence_starts(source, inner).expect("fixture should parse"),
+        Some(vec![binding, inner])
+    );
+}
+
+#[test]
+fn filter_arguments_and_loop_sources_are_references() {
+    let source = "{% for row in rows|slice:limit %}{{ row|truncatechars:limit }}{% endfor %}";
+    let limit = starts_of(source, "limit");
+
+    assert_eq!(
+        occurrence_starts(source, limit[1]).expect("fixture should parse"),
+        Some(limit)
+    );
+}
+
+#[test]
+fn forloop_is_not_renameable() {
+    let source = "{% for item in items %}{{ forloop.counter }}{% endfor %}";
+    let forloop = source.find("forloop").expect("fixture should use forloop");
+
+    assert_eq!(
+        occurrence_starts(source, forloop).expect("fixture should parse"),
+        None
+    );
+}
+
+#[test]
+fn selection_reads_outer_names_but_keeps_its_own_bindings() {
+    let source = "{% with title=page.title %}<ul>{% for item in items|slice:limit %}\
+                  <li>{{ item.name }} {{ title }}</li>{% endfor %}</ul>{% endwith %}";
+    let selected = "{% for item in items|slice:limit %}\
+                    <li>{{ item.name }} {{ title }}</li>{% endfor %}";
+
+    assert_eq!(
+        variables_in(source, selected).expect("fixture should parse"),
+        Some(vec![
+            "items".to_string(),
+            "limit".to_string(),
+            "title".to_string()
+        ])
+    );
+}
+
+#[test]
+fn selection_inside_one_segment_is_whole() {
+    let source = "{% if user %}<p>{{ user.name }}</p>{% else %}<p>Guest</p>{% endif %}";
+
+    assert_eq!(
+        variables_in(source, "<p>{{ user.name }}</p>").expect("fixture should parse"),
+        Some(vec!["user".to_string()])
+    );
+}
+
+#[test]
+fn selection_cutting_through_a_block_is_rejected() {
+    let source = "{% if user %}<p>{{ user.name }}</p>{% else %}<p>Guest</p>{% endif %}";
+
+    assert_eq!(
+        variables_in(source, "{{ user.name }}</p>{% else %}").expect("fixture should parse"),
+        None
+    );
+    assert_eq!(
+        variables_in(source, "{% if user %}<p>").expect("fixture should parse"),
+        None
+    );
+}
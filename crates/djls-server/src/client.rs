@@ -64,6 +64,17 @@ impl ClientInfo {
         &self.options.settings
     }
 
+    #[must_use]
+    pub(crate) fn allow_plugins(&self) -> bool {
+        self.options.allow_plugins
+    }
+
+    /// Replace the client-provided settings overrides, e.g. after
+    /// `workspace/didChangeConfiguration`.
+    pub(crate) fn set_options(&mut self, options: ClientOptions) {
+        self.options = options;
+    }
+
     #[must_use]
     pub(crate) fn position_encoding(&self) -> PositionEncoding {
         self.position_encoding
@@ -93,6 +104,17 @@ impl ClientInfo {
     pub(crate) fn supports_location_links(&self) -> bool {
         self.capabilities.location_links
     }
+
+    #[must_use]
+    pub(crate) fn supports_watched_files_registration(&self) -> bool {
+        self.capabilities.watched_files_registration
+    }
+
+    /// Whether workspace edits may create files, as extract refactorings need.
+    #[must_use]
+    pub(crate) fn supports_file_creation(&self) -> bool {
+        self.capabilities.file_creation
+    }
 }
 
 /// LSP client identification for client-specific behavioral overrides.
@@ -118,6 +140,8 @@ pub(crate) struct ClientCapabilities {
     snippets: bool,
     location_links: bool,
     work_done_progress: bool,
+    watched_files_registration: bool,
+    file_creation: bool,
 }
 
 impl ClientCapabilities {
@@ -157,18 +181,46 @@ impl ClientCapabilities {
             .and_then(|window| window.work_done_progress)
             .unwrap_or(false);
 
+        let watched_files_registration = capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.did_change_watched_files.as_ref())
+            .and_then(|watched_files| watched_files.dynamic_registration)
+            .unwrap_or(false);
+
+        let workspace_edit = capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.workspace_edit.as_ref());
+        let file_creation = workspace_edit.is_some_and(|workspace_edit| {
+            workspace_edit.document_changes == Some(true)
+                && workspace_edit
+                    .resource_operations
+                    .as_ref()
+                    .is_some_and(|operations| {
+                        operations.contains(&ls_types::ResourceOperationKind::Create)
+                    })
+        });
+
         Self {
             pull_diagnostics,
             workspace_diagnostic_refresh,
             snippets,
             location_links,
             work_done_progress,
+            watched_files_registration,
+            file_creation,
         }
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub(crate) struct ClientOptions {
+    /// Run the project's `[[plugins]]`. Only the client can turn this on,
+    /// since project configuration is not trusted to run commands.
+    #[serde(default)]
+    pub allow_plugins: bool,
+
     #[serde(flatten)]
     pub settings: Settings,
 
@@ -268,6 +320,60 @@ mod tests {
         assert!(!client_info.supports_location_links());
     }
 
+    #[test]
+    fn test_supports_watched_files_registration_when_dynamic_registration_is_declared() {
+        let capabilities = ls_types::ClientCapabilities {
+            workspace: Some(ls_types::WorkspaceClientCapabilities {
+                did_change_watched_files: Some(ls_types::DidChangeWatchedFilesClientCapabilities {
+                    dynamic_registration: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let client_info = ClientInfo::new(&capabilities, None, ClientOptions::default());
+
+        assert!(client_info.supports_watched_files_registration());
+    }
+
+    #[test]
+    fn test_set_options_replaces_config_overrides() {
+        let capabilities = ls_types::ClientCapabilities::default();
+        let mut client_info = ClientInfo::new(&capabilities, None, ClientOptions::default());
+        let options: ClientOptions =
+            serde_json::from_value(serde_json::json!({"venv_path": "/tmp/venv"}))
+                .expect("client options fixture should deserialize");
+
+        client_info.set_options(options);
+
+        assert_eq!(
+            client_info
+                .config_overrides()
+                .venv_path()
+                .map(camino::Utf8Path::as_str),
+            Some("/tmp/venv")
+        );
+    }
+
+    #[test]
+    fn test_allow_plugins_is_a_client_option() {
+        let capabilities = ls_types::ClientCapabilities::default();
+        let options: ClientOptions = serde_json::from_value(serde_json::json!({
+            "allow_plugins": true,
+            "venv_path": "/tmp/venv",
+        }))
+        .expect("client options fixture should deserialize");
+
+        let allowed = ClientInfo::new(&capabilities, None, options);
+        let default = ClientInfo::new(&capabilities, None, ClientOptions::default());
+
+        assert!(allowed.allow_plugins());
+        assert!(!allowed.options.unknown.contains_key("allow_plugins"));
+        assert!(!default.allow_plugins());
+    }
+
     #[test]
     fn test_negotiate_detects_sublime_client() {
         let capabilities = ls_types::ClientCapabilities::default();
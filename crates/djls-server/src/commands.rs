@@ -0,0 +1,129 @@
+//! Server commands run through `workspace/executeCommand`.
+//!
+//! They let editor users recover from a bad project state, or capture debug
+//! output for a bug report, without restarting the editor.
+
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::build_template_tree_for_file;
+use djls_source::File;
+use djls_templates::TemplateParseResult;
+use djls_templates::parse_template;
+use serde_json::Value;
+use serde_json::json;
+use tower_lsp_server::jsonrpc;
+use tower_lsp_server::ls_types;
+
+/// Rerun settings loading and Django environment discovery.
+pub(crate) const RESTART_INSPECTOR_COMMAND: &str = "djls.restartInspector";
+/// Re-read tracked sources from disk, then rebuild the template library inventory.
+pub(crate) const REBUILD_INVENTORY_COMMAND: &str = "djls.rebuildInventory";
+/// Drop every cached query result and rediscover the project.
+pub(crate) const CLEAR_CACHE_COMMAND: &str = "djls.clearCache";
+/// Return the block tree of one template as JSON.
+pub(crate) const DUMP_BLOCK_TREE_COMMAND: &str = "djls.dumpBlockTree";
+
+/// Commands advertised in the `executeCommandProvider` capability.
+pub(crate) const SERVER_COMMANDS: [&str; 4] = [
+    RESTART_INSPECTOR_COMMAND,
+    REBUILD_INVENTORY_COMMAND,
+    CLEAR_CACHE_COMMAND,
+    DUMP_BLOCK_TREE_COMMAND,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ServerCommand {
+    RestartInspector,
+    RebuildInventory,
+    ClearCache,
+    DumpBlockTree(ls_types::Uri),
+}
+
+impl ServerCommand {
+    pub(crate) fn parse(params: &ls_types::ExecuteCommandParams) -> jsonrpc::Result<Self> {
+        match params.command.as_str() {
+            RESTART_INSPECTOR_COMMAND => Ok(Self::RestartInspector),
+            REBUILD_INVENTORY_COMMAND => Ok(Self::RebuildInventory),
+            CLEAR_CACHE_COMMAND => Ok(Self::ClearCache),
+            DUMP_BLOCK_TREE_COMMAND => {
+                let uri = params
+                    .arguments
+                    .first()
+                    .cloned()
+                    .and_then(|argument| serde_json::from_value(argument).ok())
+                    .ok_or_else(|| {
+                        jsonrpc::Error::invalid_params(format!(
+                            "{DUMP_BLOCK_TREE_COMMAND} expects a document URI argument"
+                        ))
+                    })?;
+                Ok(Self::DumpBlockTree(uri))
+            }
+            command => Err(jsonrpc::Error::invalid_params(format!(
+                "Unknown command '{command}'"
+            ))),
+        }
+    }
+}
+
+/// The block tree of `file` as JSON, or `None` when it is not a readable template.
+pub(crate) fn dump_block_tree(db: &dyn SemanticDb, file: File) -> Option<Value> {
+    let TemplateParseResult::Parsed(nodelist) = parse_template(db, file) else {
+        return None;
+    };
+    let tree = build_template_tree_for_file(db, file, nodelist);
+    Some(json!({
+        "root": tree.root(db),
+        "regions": tree.regions(db),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(command: &str, arguments: Vec<Value>) -> ls_types::ExecuteCommandParams {
+        ls_types::ExecuteCommandParams {
+            command: command.to_string(),
+            arguments,
+            work_done_progress_params: ls_types::WorkDoneProgressParams::default(),
+        }
+    }
+
+    #[test]
+    fn parses_every_advertised_command() {
+        for command in SERVER_COMMANDS {
+            let arguments = vec![json!("file:///project/templates/base.html")];
+            assert!(
+                ServerCommand::parse(&params(command, arguments)).is_ok(),
+                "{command} should parse"
+            );
+        }
+    }
+
+    #[test]
+    fn dump_block_tree_reads_the_document_uri() {
+        let command = ServerCommand::parse(&params(
+            DUMP_BLOCK_TREE_COMMAND,
+            vec![json!("file:///project/templates/base.html")],
+        ))
+        .expect("dumpBlockTree with a URI should parse");
+
+        let ServerCommand::DumpBlockTree(uri) = command else {
+            panic!("expected dumpBlockTree, got {command:?}");
+        };
+        assert_eq!(uri.as_str(), "file:///project/templates/base.html");
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_missing_arguments() {
+        let unknown = ServerCommand::parse(&params("djls.frobnicate", Vec::new()))
+            .expect_err("unknown commands should be rejected");
+        assert_eq!(unknown.message, "Unknown command 'djls.frobnicate'");
+
+        let missing = ServerCommand::parse(&params(DUMP_BLOCK_TREE_COMMAND, Vec::new()))
+            .expect_err("dumpBlockTree without a URI should be rejected");
+        assert_eq!(
+            missing.message,
+            "djls.dumpBlockTree expects a document URI argument"
+        );
+    }
+}
@@ -10,6 +10,7 @@ use camino::Utf8PathBuf;
 use djls_source::FileKind;
 use djls_source::LineIndex;
 use djls_source::PositionEncoding;
+use djls_source::PositionMapper;
 use djls_source::Range;
 
 /// In-memory representation of an open document in the LSP.
@@ -117,8 +118,9 @@ impl DocumentChange {
     #[must_use]
     fn apply(&self, content: &str, line_index: &LineIndex, encoding: PositionEncoding) -> String {
         if let Some(range) = &self.range {
-            let start_offset = line_index.offset(content, range.start(), encoding).get() as usize;
-            let end_offset = line_index.offset(content, range.end(), encoding).get() as usize;
+            let mapper = PositionMapper::new(content, line_index, encoding);
+            let start_offset = mapper.offset(range.start()).get() as usize;
+            let end_offset = mapper.offset(range.end()).get() as usize;
 
             let mut result = String::with_capacity(content.len() + self.text.len());
             result.push_str(&content[..start_offset]);
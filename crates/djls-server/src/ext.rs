@@ -5,7 +5,9 @@ use djls_source::LineCol;
 use djls_source::LineIndex;
 use djls_source::Offset;
 use djls_source::PositionEncoding;
+use djls_source::PositionMapper;
 use djls_source::Range;
+use serde_json::Value;
 use tower_lsp_server::ls_types;
 
 use crate::client::Client;
@@ -23,8 +25,7 @@ impl PositionExt for ls_types::Position {
     }
 
     fn to_offset(&self, text: &str, line_index: &LineIndex, encoding: PositionEncoding) -> Offset {
-        let line_col = self.to_line_col();
-        line_index.offset(text, line_col, encoding)
+        PositionMapper::new(text, line_index, encoding).offset(self.to_line_col())
     }
 }
 
@@ -89,35 +90,65 @@ pub(crate) trait InitializeParamsExt {
 
 impl InitializeParamsExt for ls_types::InitializeParams {
     fn client_options(&self) -> ClientOptions {
-        let client_options: ClientOptions = self
-            .initialization_options
+        self.initialization_options
             .as_ref()
-            .and_then(|v| match serde_json::from_value(v.clone()) {
-                Ok(opts) => Some(opts),
-                Err(err) => {
-                    tracing::error!(
-                        "Failed to deserialize initialization options: {}. Using defaults.",
-                        err
-                    );
-                    None
-                }
-            })
-            .unwrap_or_default();
-
-        if !client_options.unknown.is_empty() {
-            tracing::warn!(
-                "Received unknown initialization options: {}",
-                client_options
-                    .unknown
-                    .keys()
-                    .map(String::as_str)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+            .and_then(|value| deserialize_client_options(value, "initialization options"))
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) trait DidChangeConfigurationParamsExt {
+    fn client_options(&self) -> Option<ClientOptions>;
+}
+
+impl DidChangeConfigurationParamsExt for ls_types::DidChangeConfigurationParams {
+    /// Client options pushed with the notification, if any.
+    ///
+    /// Clients that push settings usually scope them under the server's
+    /// section name, so a lone `djls` key is unwrapped before deserializing.
+    /// A `null` or empty payload means the client only signalled a change.
+    fn client_options(&self) -> Option<ClientOptions> {
+        if self.settings.is_null()
+            || self
+                .settings
+                .as_object()
+                .is_some_and(serde_json::Map::is_empty)
+        {
+            return None;
         }
+        let value = self
+            .settings
+            .as_object()
+            .filter(|map| map.len() == 1)
+            .and_then(|map| map.get("djls"))
+            .unwrap_or(&self.settings);
+        deserialize_client_options(value, "configuration settings")
+    }
+}
 
-        client_options
+fn deserialize_client_options(value: &Value, context: &str) -> Option<ClientOptions> {
+    let client_options: ClientOptions = match serde_json::from_value(value.clone()) {
+        Ok(opts) => opts,
+        Err(err) => {
+            tracing::error!("Failed to deserialize {}: {}. Ignoring them.", context, err);
+            return None;
+        }
+    };
+
+    if !client_options.unknown.is_empty() {
+        tracing::warn!(
+            "Received unknown {}: {}",
+            context,
+            client_options
+                .unknown
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
     }
+
+    Some(client_options)
 }
 
 pub(crate) trait ClientInfoExt {
@@ -217,6 +248,50 @@ mod tests {
         // TODO(virtual-paths): In Step 2, this should return Some(DocumentPath::Virtual(...))
     }
 
+    #[test]
+    fn test_did_change_configuration_null_settings_have_no_options() {
+        let params = ls_types::DidChangeConfigurationParams {
+            settings: Value::Null,
+        };
+        assert!(params.client_options().is_none());
+    }
+
+    #[test]
+    fn test_did_change_configuration_empty_settings_have_no_options() {
+        let params = ls_types::DidChangeConfigurationParams {
+            settings: serde_json::json!({}),
+        };
+        assert!(params.client_options().is_none());
+    }
+
+    #[test]
+    fn test_did_change_configuration_unwraps_djls_section() {
+        let params = ls_types::DidChangeConfigurationParams {
+            settings: serde_json::json!({"djls": {"django_settings_module": "app.settings"}}),
+        };
+        let options = params
+            .client_options()
+            .expect("scoped configuration settings should deserialize");
+        assert_eq!(
+            options.settings.django_settings_module(),
+            Some("app.settings")
+        );
+    }
+
+    #[test]
+    fn test_did_change_configuration_reads_unscoped_settings() {
+        let params = ls_types::DidChangeConfigurationParams {
+            settings: serde_json::json!({"django_settings_module": "app.settings"}),
+        };
+        let options = params
+            .client_options()
+            .expect("unscoped configuration settings should deserialize");
+        assert_eq!(
+            options.settings.django_settings_module(),
+            Some("app.settings")
+        );
+    }
+
     #[test]
     fn test_client_info_sublime_to_client() {
         let client_info = ls_types::ClientInfo {
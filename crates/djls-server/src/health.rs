@@ -0,0 +1,333 @@
+//! Project health reporting and degraded-mode recovery.
+//!
+//! Watches intrinsic readiness. When project discovery fails, the client is
+//! told once via `window/showMessage` and a `djls/status` notification, open
+//! templates fall back to syntax-only diagnostics, and discovery is retried
+//! with backoff until it succeeds or the project changes underneath it.
+//! Every published generation also reports what discovery found through
+//! `djls/status`, for editor status bars.
+
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+
+use djls_project::Db as _;
+use djls_project::ProjectStatus;
+use salsa::Cancelled;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::spawn as spawn_task;
+use tokio::sync::Mutex;
+use tokio::sync::watch;
+use tokio::task::spawn_blocking;
+use tokio::time::timeout;
+use tower_lsp_server::Client;
+use tower_lsp_server::ls_types;
+use tracing::debug;
+use tracing::error;
+use tracing::warn;
+
+use crate::reload::publish_syntax_diagnostics;
+use crate::session::IntrinsicGeneration;
+use crate::session::IntrinsicReadinessState;
+use crate::session::Session;
+use crate::session::SessionSnapshot;
+
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+const DEGRADED_MESSAGE: &str = "Django project discovery failed. Template syntax diagnostics \
+                                remain active; project-aware features will return once \
+                                discovery succeeds.";
+
+/// Experimental `djls/status` notification carrying the project health.
+pub(crate) enum StatusNotification {}
+
+impl ls_types::notification::Notification for StatusNotification {
+    type Params = StatusParams;
+    const METHOD: &'static str = "djls/status";
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StatusParams {
+    pub health: ProjectHealth,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<DegradedReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectStatus>,
+    /// How long the reload or re-prime that produced `project` took.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_index_ms: Option<u64>,
+}
+
+impl StatusParams {
+    fn ready(project: ProjectStatus, elapsed: Duration) -> Self {
+        Self {
+            health: ProjectHealth::Ok,
+            message: None,
+            reason: None,
+            project: Some(project),
+            last_index_ms: Some(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)),
+        }
+    }
+
+    fn degraded(reason: Option<DegradedReason>) -> Self {
+        Self {
+            health: ProjectHealth::Degraded,
+            message: Some(DEGRADED_MESSAGE.to_string()),
+            reason,
+            project: None,
+            last_index_ms: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProjectHealth {
+    /// Project discovery is current, or there is no project to discover.
+    Ok,
+    /// Project discovery failed; only syntax-level features are available.
+    Degraded,
+}
+
+/// The reload stage whose failure put the project in degraded mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DegradedReason {
+    /// The project's configuration files could not be loaded.
+    Settings,
+    /// The Python environment and search paths could not be resolved.
+    Environment,
+    /// Installed apps and template library locations could not be discovered.
+    ProjectFacts,
+    /// Template tag libraries could not be analyzed.
+    TemplateLibraries,
+}
+
+/// Doubling retry delay, reset whenever the project becomes healthy again.
+#[derive(Debug)]
+struct RetryBackoff {
+    delay: Duration,
+}
+
+impl RetryBackoff {
+    const fn new() -> Self {
+        Self {
+            delay: INITIAL_RETRY_DELAY,
+        }
+    }
+
+    fn next(&mut self) -> Duration {
+        let delay = self.delay;
+        self.delay = (self.delay * 2).min(MAX_RETRY_DELAY);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.delay = INITIAL_RETRY_DELAY;
+    }
+}
+
+/// Spawn the task that reports project health and retries failed discovery.
+///
+/// `retry` enqueues a full reload after the monitor has advanced the
+/// readiness generation. The task only holds a weak reference to the
+/// session, so it ends with the server.
+pub(crate) fn spawn_health_monitor<F>(session: &Arc<Mutex<Session>>, client: Client, retry: F)
+where
+    F: Fn() + Send + 'static,
+{
+    let session = Arc::downgrade(session);
+    spawn_task(async move {
+        let Some(mut readiness) = readiness_receiver(&session).await else {
+            return;
+        };
+        let mut degraded = false;
+        let mut backoff = RetryBackoff::new();
+
+        loop {
+            let state = *readiness.borrow_and_update();
+            match state {
+                IntrinsicReadinessState::Failed(generation) => {
+                    if !degraded {
+                        degraded = true;
+                        report_degraded(&client, degraded_reason(&session).await).await;
+                        publish_degraded_diagnostics(&client, &session).await;
+                    }
+
+                    let delay = backoff.next();
+                    match timeout(delay, readiness.changed()).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(_)) => return,
+                        Err(_) => {
+                            if restart_failed_generation(&session, generation).await {
+                                debug!(?delay, "Retrying failed project discovery");
+                                retry();
+                            }
+                        }
+                    }
+                    continue;
+                }
+                IntrinsicReadinessState::Ready(_) | IntrinsicReadinessState::ReadyWithoutProject => {
+                    // The reload that recovered reports the new status itself.
+                    if degraded {
+                        degraded = false;
+                        backoff.reset();
+                    }
+                }
+                IntrinsicReadinessState::Unready(_) => {}
+            }
+
+            if readiness.changed().await.is_err() {
+                return;
+            }
+        }
+    });
+}
+
+async fn readiness_receiver(
+    session: &Weak<Mutex<Session>>,
+) -> Option<watch::Receiver<IntrinsicReadinessState>> {
+    let session = session.upgrade()?;
+    let receiver = session.lock().await.readiness_receiver();
+    Some(receiver)
+}
+
+/// Advance the readiness generation if `generation` is still the failed one.
+///
+/// Any project change since the failure already scheduled its own reload.
+async fn restart_failed_generation(
+    session: &Weak<Mutex<Session>>,
+    generation: IntrinsicGeneration,
+) -> bool {
+    let Some(session) = session.upgrade() else {
+        return false;
+    };
+    let mut session = session.lock().await;
+    if session.readiness_state() != IntrinsicReadinessState::Failed(generation) {
+        return false;
+    }
+    session.mark_project_changed();
+    true
+}
+
+async fn degraded_reason(session: &Weak<Mutex<Session>>) -> Option<DegradedReason> {
+    let session = session.upgrade()?;
+    session.lock().await.degraded_reason()
+}
+
+async fn report_degraded(client: &Client, reason: Option<DegradedReason>) {
+    warn!(?reason, "{DEGRADED_MESSAGE}");
+    client
+        .show_message(ls_types::MessageType::WARNING, DEGRADED_MESSAGE)
+        .await;
+    send_status(client, StatusParams::degraded(reason)).await;
+}
+
+/// Report what a freshly published generation found.
+///
+/// Computed on a blocking task from `snapshot`. A cancelled computation is
+/// skipped, since the write that cancelled it starts a reload that reports
+/// again.
+pub(crate) async fn report_project_status(
+    client: &Client,
+    snapshot: SessionSnapshot,
+    elapsed: Duration,
+) {
+    let joined = spawn_blocking(move || {
+        Cancelled::catch(AssertUnwindSafe(|| {
+            let db = snapshot.db();
+            db.project().map(|project| ProjectStatus::new(db, project))
+        }))
+    })
+    .await;
+
+    match joined {
+        Ok(Ok(Some(project))) => {
+            send_status(client, StatusParams::ready(project, elapsed)).await;
+        }
+        Ok(Ok(None)) => {}
+        Ok(Err(cancelled)) => debug!(?cancelled, "Project status cancelled; skipping report"),
+        Err(error) => error!(?error, "Project status task failed; skipping report"),
+    }
+}
+
+async fn send_status(client: &Client, params: StatusParams) {
+    client.send_notification::<StatusNotification>(params).await;
+}
+
+async fn publish_degraded_diagnostics(client: &Client, session: &Weak<Mutex<Session>>) {
+    let Some(session) = session.upgrade() else {
+        return;
+    };
+    let (snapshot, documents) = {
+        let session = session.lock().await;
+        (session.snapshot(), session.open_documents())
+    };
+    publish_syntax_diagnostics(client.clone(), snapshot, documents).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use djls_project::InventorySize;
+
+    use super::*;
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_cap_and_resets() {
+        let mut backoff = RetryBackoff::new();
+
+        assert_eq!(backoff.next(), Duration::from_secs(30));
+        assert_eq!(backoff.next(), Duration::from_secs(60));
+        assert_eq!(backoff.next(), Duration::from_secs(120));
+        assert_eq!(backoff.next(), Duration::from_secs(240));
+        assert_eq!(backoff.next(), Duration::from_secs(300));
+        assert_eq!(backoff.next(), Duration::from_secs(300));
+
+        backoff.reset();
+        assert_eq!(backoff.next(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn status_params_serialize_health_and_optional_fields() {
+        let project = ProjectStatus {
+            environment: Some(Utf8PathBuf::from("/project/.venv/lib/python3.13/site-packages")),
+            settings_module: Some("config.settings".to_string()),
+            django_version: Some("5.2".to_string()),
+            inventory: InventorySize {
+                libraries: 3,
+                tags: 40,
+                filters: 62,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(StatusParams::ready(project, Duration::from_millis(1250)))
+                .expect("status should serialize"),
+            serde_json::json!({
+                "health": "ok",
+                "project": {
+                    "environment": "/project/.venv/lib/python3.13/site-packages",
+                    "settingsModule": "config.settings",
+                    "djangoVersion": "5.2",
+                    "inventory": {"libraries": 3, "tags": 40, "filters": 62},
+                },
+                "lastIndexMs": 1250,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(StatusParams::degraded(Some(DegradedReason::ProjectFacts)))
+                .expect("status should serialize"),
+            serde_json::json!({
+                "health": "degraded",
+                "message": DEGRADED_MESSAGE,
+                "reason": "project_facts",
+            })
+        );
+    }
+}
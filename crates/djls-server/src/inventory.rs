@@ -0,0 +1,62 @@
+//! Template Library inventory change notifications.
+//!
+//! After each published priming pass the session compares the available
+//! libraries and tags with the previous pass. When a package install or
+//! removal changes them, clients receive a `djls/environmentChanged`
+//! notification describing the difference.
+
+use djls_project::TemplateInventoryDiff;
+use tower_lsp_server::Client;
+use tower_lsp_server::ls_types;
+use tracing::info;
+
+/// Experimental `djls/environmentChanged` notification carrying an inventory diff.
+pub(crate) enum EnvironmentChangedNotification {}
+
+impl ls_types::notification::Notification for EnvironmentChangedNotification {
+    type Params = TemplateInventoryDiff;
+    const METHOD: &'static str = "djls/environmentChanged";
+}
+
+pub(crate) async fn notify_environment_changed(client: &Client, diff: TemplateInventoryDiff) {
+    info!(
+        "Template libraries changed: {} added, {} removed; {} tags added, {} removed",
+        diff.added_libraries.len(),
+        diff.removed_libraries.len(),
+        diff.added_tags.len(),
+        diff.removed_tags.len()
+    );
+    client
+        .send_notification::<EnvironmentChangedNotification>(diff)
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use djls_project::TemplateInventoryTag;
+
+    use super::*;
+
+    #[test]
+    fn environment_changed_params_serialize_in_camel_case() {
+        let diff = TemplateInventoryDiff {
+            added_libraries: vec!["cache".to_string()],
+            removed_libraries: vec![],
+            added_tags: vec![TemplateInventoryTag {
+                library: "cache".to_string(),
+                name: "cache".to_string(),
+            }],
+            removed_tags: vec![],
+        };
+
+        assert_eq!(
+            serde_json::to_value(diff).expect("diff should serialize"),
+            serde_json::json!({
+                "addedLibraries": ["cache"],
+                "removedLibraries": [],
+                "addedTags": [{"library": "cache", "name": "cache"}],
+                "removedTags": [],
+            })
+        );
+    }
+}
@@ -1,9 +1,15 @@
 #![cfg_attr(not(test), warn(clippy::expect_used))]
 
 mod client;
+mod commands;
 mod document;
 mod ext;
+mod health;
+mod inventory;
 mod logging;
+mod memory;
+mod partial;
+mod perf;
 mod progress;
 mod reload;
 mod server;
@@ -16,10 +22,11 @@ use anyhow::Result;
 use tower_lsp_server::LspService;
 use tower_lsp_server::Server;
 
+pub use crate::logging::LogOptions;
 use crate::server::DjangoLanguageServer;
 
 /// Run the Django language server.
-pub fn run() -> Result<()> {
+pub fn run(log_options: &LogOptions) -> Result<()> {
     if std::io::stdin().is_terminal() {
         eprintln!("Django Language Server is running directly in a terminal.");
         eprintln!(
@@ -41,7 +48,7 @@ pub fn run() -> Result<()> {
         let stdout = tokio::io::stdout();
 
         let (service, socket) = LspService::build(|client| {
-            let logging = logging::init_tracing({
+            let logging = logging::init_tracing(log_options, {
                 let client = client.clone();
                 move |message_type, message| {
                     let client = client.clone();
@@ -53,6 +60,12 @@ pub fn run() -> Result<()> {
 
             DjangoLanguageServer::new(client, logging)
         })
+        .custom_method(
+            logging::SET_LOG_LEVEL_METHOD,
+            DjangoLanguageServer::set_log_level,
+        )
+        .custom_method(perf::PERF_METHOD, DjangoLanguageServer::perf)
+        .custom_method(memory::MEMORY_METHOD, DjangoLanguageServer::memory)
         .finish();
 
         Server::new(stdin, stdout, socket).serve(service).await;
@@ -12,22 +12,69 @@
 //!
 //! The `LspLayer` automatically handles forwarding appropriate log levels
 //! to the LSP client while preserving structured logging data for file output.
+//!
+//! ## File log level
+//!
+//! The file layer's filter sits behind a reload handle. Its level comes from,
+//! in order of precedence: `RUST_LOG`, `djls serve --log-level`, and the
+//! `log_level`/`debug` settings. Clients can replace it at runtime with the
+//! `djls/setLogLevel` request, which then wins over later configuration
+//! reloads. Request handlers open `DEBUG` spans, so at that level the file log
+//! records each span's busy and idle time when it closes.
 
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use anyhow::Context;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
 use tower_lsp_server::ls_types;
 use tracing::Level;
 use tracing::field::Visit;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::RollingFileAppender;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Layer;
 use tracing_subscriber::Registry;
 use tracing_subscriber::fmt;
+use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
 use tracing_subscriber::util::SubscriberInitExt;
 
+/// Custom request that replaces the file log level at runtime.
+pub(crate) const SET_LOG_LEVEL_METHOD: &str = "djls/setLogLevel";
+
+/// Params for the `djls/setLogLevel` request.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SetLogLevelParams {
+    /// An `EnvFilter` directive, e.g. `"debug"` or `"djls_server=trace,info"`.
+    pub(crate) level: String,
+}
+
+/// Command-line logging options for the server.
+#[derive(Clone, Debug, Default)]
+pub struct LogOptions {
+    /// Write the log to this file instead of the daily-rotated log in the
+    /// cache directory.
+    pub file: Option<Utf8PathBuf>,
+    /// File log filter directive; takes precedence over the `log_level` setting.
+    pub level: Option<String>,
+}
+
+/// Reload handle for the file layer's filter.
+struct LogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// Set once `RUST_LOG`, `--log-level`, or a runtime request chose the
+    /// level, after which configuration no longer applies.
+    pinned: AtomicBool,
+}
+
+static LOG_FILTER: OnceLock<LogFilter> = OnceLock::new();
+
 /// A tracing Layer that forwards events to the LSP client.
 ///
 /// This layer intercepts tracing events and converts them to LSP log messages
@@ -129,24 +176,23 @@ impl LoggingGuard {
 /// Initialize the dual-layer tracing subscriber.
 ///
 /// Sets up:
-/// - File layer: writes to XDG cache directory (e.g., ~/.cache/djls/djls.log on Linux) with daily rotation.
+/// - File layer: writes to `options.file` when given, otherwise to the XDG cache directory
+///   (e.g., ~/.cache/djls/djls.log on Linux) with daily rotation.
 ///   Falls back to /tmp/djls.log if XDG cache directory is not available.
 ///   If file logging cannot be initialized, falls back to stderr.
 /// - LSP layer: forwards INFO+ messages to the client
-/// - `EnvFilter`: respects `RUST_LOG` env var, defaults to "info"
+/// - `EnvFilter`: respects `RUST_LOG` env var, then `options.level`, defaults to "info"
 ///
 /// Returns a [`LoggingGuard`] that must be kept alive for the logging to work.
-pub(crate) fn init_tracing<F>(send_message: F) -> LoggingGuard
+pub(crate) fn init_tracing<F>(options: &LogOptions, send_message: F) -> LoggingGuard
 where
     F: Fn(ls_types::MessageType, String) + Send + Sync + 'static,
 {
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (env_filter, pinned) = initial_filter(options.level.as_deref());
+    let (env_filter, handle) = reload::Layer::new(env_filter);
 
-    let (non_blocking, guard) = match djls_conf::log_dir() {
-        Ok(log_dir) => {
-            let file_appender = tracing_appender::rolling::daily(log_dir.as_std_path(), "djls.log");
-            tracing_appender::non_blocking(file_appender)
-        }
+    let (non_blocking, guard) = match file_appender(options.file.as_deref()) {
+        Ok(file_appender) => tracing_appender::non_blocking(file_appender),
         Err(e) => {
             eprintln!("Warning: Failed to initialize file logging: {e}");
             eprintln!("Falling back to stderr logging...");
@@ -162,6 +208,7 @@ where
         .with_target(true)
         .with_file(true)
         .with_line_number(true)
+        .with_span_events(FmtSpan::CLOSE)
         .with_filter(env_filter);
 
     let lsp_layer = LspLayer::new(send_message);
@@ -169,9 +216,84 @@ where
     let lsp_layer = lsp_layer.with_filter(tracing_subscriber::filter::LevelFilter::INFO);
 
     Registry::default().with(log_layer).with(lsp_layer).init();
+    drop(LOG_FILTER.set(LogFilter {
+        handle,
+        pinned: AtomicBool::new(pinned),
+    }));
 
     LoggingGuard {
         _file_guard: guard,
         lsp_disabled,
     }
 }
+
+/// Pick the startup file filter and whether it is pinned against configuration.
+fn initial_filter(level: Option<&str>) -> (EnvFilter, bool) {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return (filter, true);
+    }
+
+    match level.map(EnvFilter::try_new) {
+        Some(Ok(filter)) => (filter, true),
+        Some(Err(e)) => {
+            eprintln!("Warning: Ignoring invalid --log-level: {e}");
+            (EnvFilter::new("info"), false)
+        }
+        None => (EnvFilter::new("info"), false),
+    }
+}
+
+fn file_appender(path: Option<&Utf8Path>) -> anyhow::Result<RollingFileAppender> {
+    let Some(path) = path else {
+        let log_dir = djls_conf::log_dir()?;
+        return Ok(tracing_appender::rolling::daily(
+            log_dir.as_std_path(),
+            "djls.log",
+        ));
+    };
+
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Log file path has no file name: {path}"))?;
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_str().is_empty())
+        .unwrap_or_else(|| Utf8Path::new("."));
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create log directory: {dir}"))?;
+
+    Ok(tracing_appender::rolling::never(dir.as_std_path(), file_name))
+}
+
+/// Replace the file log filter at runtime.
+///
+/// The new level stays in effect across configuration reloads.
+pub(crate) fn set_log_level(directive: &str) -> anyhow::Result<()> {
+    let filter = EnvFilter::try_new(directive)
+        .with_context(|| format!("Invalid log level directive: {directive}"))?;
+    let log_filter = LOG_FILTER.get().context("Logging is not initialized")?;
+    log_filter.handle.reload(filter)?;
+    log_filter.pinned.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Apply the `log_level` setting unless the level was pinned by `RUST_LOG`,
+/// `--log-level`, or a runtime request.
+pub(crate) fn apply_configured_log_level(directive: Option<&str>) {
+    let Some(log_filter) = LOG_FILTER.get() else {
+        return;
+    };
+    if log_filter.pinned.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let directive = directive.unwrap_or("info");
+    match EnvFilter::try_new(directive) {
+        Ok(filter) => {
+            if let Err(error) = log_filter.handle.reload(filter) {
+                tracing::warn!("Failed to apply log_level '{directive}': {error}");
+            }
+        }
+        Err(error) => tracing::warn!("Ignoring invalid log_level '{directive}': {error}"),
+    }
+}
@@ -0,0 +1,106 @@
+//! Tracked sources and per-query Salsa memory for the `djls/memory` request.
+
+use djls_db::DjangoDatabase;
+use djls_db::IngredientMemory;
+use djls_db::QueryUsage;
+use djls_source::Db as _;
+use serde::Serialize;
+
+/// Custom request returning a [`MemoryReport`].
+pub(crate) const MEMORY_METHOD: &str = "djls/memory";
+
+/// Response to the `djls/memory` request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemoryReport {
+    files: FileSummary,
+    queries: Vec<QuerySummary>,
+    structs: Vec<StructSummary>,
+}
+
+impl MemoryReport {
+    pub(crate) fn new(db: &DjangoDatabase) -> Self {
+        let memory = db.memory_usage();
+        let mut usage = db.query_usage();
+        let mut queries = memory
+            .queries
+            .into_iter()
+            .map(|memos| {
+                let executions = usage
+                    .iter()
+                    .position(|usage| usage.name == memos.name)
+                    .map(|index| usage.remove(index));
+                QuerySummary::new(memos, executions.as_ref())
+            })
+            .collect::<Vec<_>>();
+        // Queries whose memos were all evicted still show how often they ran.
+        queries.extend(usage.into_iter().map(|executions| {
+            QuerySummary::new(IngredientMemory::empty(&executions.name), Some(&executions))
+        }));
+        Self {
+            files: FileSummary {
+                tracked: db.files().file_count(),
+                source_bytes: db.files().source_bytes(),
+            },
+            queries,
+            structs: memory.structs.into_iter().map(StructSummary::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileSummary {
+    tracked: usize,
+    source_bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuerySummary {
+    name: String,
+    memos: usize,
+    bytes: usize,
+    metadata_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heap_bytes: Option<usize>,
+    executed: u64,
+    keys: usize,
+}
+
+impl QuerySummary {
+    fn new(memos: IngredientMemory, executions: Option<&QueryUsage>) -> Self {
+        Self {
+            name: memos.name,
+            memos: memos.count,
+            bytes: memos.bytes,
+            metadata_bytes: memos.metadata_bytes,
+            heap_bytes: memos.heap_bytes,
+            executed: executions.map_or(0, |usage| usage.executed),
+            keys: executions.map_or(0, |usage| usage.keys),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StructSummary {
+    name: String,
+    count: usize,
+    bytes: usize,
+    metadata_bytes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heap_bytes: Option<usize>,
+}
+
+impl From<IngredientMemory> for StructSummary {
+    fn from(instances: IngredientMemory) -> Self {
+        Self {
+            name: instances.name,
+            count: instances.count,
+            bytes: instances.bytes,
+            metadata_bytes: instances.metadata_bytes,
+            heap_bytes: instances.heap_bytes,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Partial results for requests that span the whole project.
+//!
+//! When a request carries a `partialResultToken`, its results are sent in
+//! batches as `$/progress` notifications on that token while the rest are
+//! still being computed, and the final response carries nothing further.
+//! Without a token the batches are collected into the response as usual.
+
+use serde::Deserialize;
+use serde::Serialize;
+use tower_lsp_server::Client;
+use tower_lsp_server::ls_types;
+use tracing::error;
+
+/// Templates validated per snapshot before their diagnostics are sent.
+pub(crate) const PARTIAL_RESULT_BATCH_SIZE: usize = 32;
+
+/// `$/progress` carrying a partial result. `ls_types::ProgressParamsValue`
+/// only models work-done progress, so the result value travels untyped.
+enum PartialResultNotification {}
+
+impl ls_types::notification::Notification for PartialResultNotification {
+    type Params = PartialResultParams;
+    const METHOD: &'static str = "$/progress";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PartialResultParams {
+    token: ls_types::ProgressToken,
+    value: serde_json::Value,
+}
+
+/// The client's partial result token for one request.
+pub(crate) struct PartialResults {
+    client: Client,
+    token: ls_types::ProgressToken,
+}
+
+impl PartialResults {
+    /// `None` when the client did not ask for partial results.
+    pub(crate) fn new(client: &Client, params: ls_types::PartialResultParams) -> Option<Self> {
+        let token = params.partial_result_token?;
+        Some(Self {
+            client: client.clone(),
+            token,
+        })
+    }
+
+    pub(crate) async fn send(&self, value: impl Serialize) {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(error) => {
+                error!(?error, "Failed to serialize partial result");
+                return;
+            }
+        };
+        self.client
+            .send_notification::<PartialResultNotification>(PartialResultParams {
+                token: self.token.clone(),
+                value,
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn partial_result_params_carry_the_client_token() {
+        let params = PartialResultParams {
+            token: ls_types::ProgressToken::String("refs-1".to_string()),
+            value: json!([{ "uri": "file:///templates/base.html" }]),
+        };
+
+        assert_eq!(
+            serde_json::to_value(params).expect("partial result params should serialize"),
+            json!({
+                "token": "refs-1",
+                "value": [{ "uri": "file:///templates/base.html" }],
+            })
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Request and analysis-phase timing for the `djls/perf` request.
+//!
+//! Handlers record wall-clock durations per [`PerfPhase`]. Each phase keeps a
+//! bounded window of recent samples, from which the report computes latency
+//! percentiles, alongside the Salsa memo revalidation counts of the current
+//! database.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use djls_db::QueryStats;
+use djls_semantic::Db as SemanticDb;
+use djls_semantic::validate_template_file;
+use djls_source::File;
+use djls_source::FileKind;
+use djls_templates::lex_template;
+use djls_templates::parse_template;
+use serde::Serialize;
+
+/// Custom request returning a [`PerfReport`].
+pub(crate) const PERF_METHOD: &str = "djls/perf";
+
+/// Samples kept per phase; older samples are dropped first.
+const MAX_SAMPLES: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PerfPhase {
+    Lex,
+    Parse,
+    Validate,
+    Completion,
+    Hover,
+    Diagnostics,
+}
+
+impl PerfPhase {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Lex => "lex",
+            Self::Parse => "parse",
+            Self::Validate => "validate",
+            Self::Completion => "completion",
+            Self::Hover => "hover",
+            Self::Diagnostics => "diagnostics",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Samples {
+    count: u64,
+    recent: VecDeque<Duration>,
+}
+
+impl Samples {
+    fn push(&mut self, elapsed: Duration) {
+        self.count += 1;
+        if self.recent.len() == MAX_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(elapsed);
+    }
+
+    fn summary(&self) -> LatencySummary {
+        let mut sorted: Vec<Duration> = self.recent.iter().copied().collect();
+        sorted.sort_unstable();
+        LatencySummary {
+            count: self.count,
+            p50_ms: percentile_ms(&sorted, 50),
+            p90_ms: percentile_ms(&sorted, 90),
+            p99_ms: percentile_ms(&sorted, 99),
+            max_ms: sorted.last().map_or(0.0, |max| millis(*max)),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending sample list.
+fn percentile_ms(sorted: &[Duration], percentile: usize) -> f64 {
+    let rank = (sorted.len() * percentile).div_ceil(100);
+    sorted
+        .get(rank.saturating_sub(1))
+        .map_or(0.0, |sample| millis(*sample))
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Timing samples shared by every request handler.
+#[derive(Debug, Default)]
+pub(crate) struct PerfRecorder {
+    phases: Mutex<BTreeMap<PerfPhase, Samples>>,
+}
+
+impl PerfRecorder {
+    pub(crate) fn record(&self, phase: PerfPhase, elapsed: Duration) {
+        let Ok(mut phases) = self.phases.lock() else {
+            return;
+        };
+        phases.entry(phase).or_default().push(elapsed);
+    }
+
+    pub(crate) fn time<R>(&self, phase: PerfPhase, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    /// Run the lex, parse, and validation queries for a Template one at a
+    /// time so each phase is timed on its own.
+    ///
+    /// Later diagnostic collection reads the same memoized results, so this
+    /// adds no work beyond the timing itself.
+    pub(crate) fn time_template_phases(&self, db: &dyn SemanticDb, file: File) {
+        if !matches!(file.try_source(db), Ok(source) if *source.kind() == FileKind::Template) {
+            return;
+        }
+
+        self.time(PerfPhase::Lex, || {
+            lex_template(db, file);
+        });
+        self.time(PerfPhase::Parse, || {
+            parse_template(db, file);
+        });
+        self.time(PerfPhase::Validate, || validate_template_file(db, file));
+    }
+
+    pub(crate) fn report(&self, query_stats: &QueryStats) -> PerfReport {
+        let phases = self
+            .phases
+            .lock()
+            .map(|phases| {
+                phases
+                    .iter()
+                    .map(|(phase, samples)| (phase.as_str(), samples.summary()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        PerfReport {
+            phases,
+            salsa: SalsaSummary {
+                executed: query_stats.executed(),
+                revalidated: query_stats.revalidated(),
+                revalidated_percent: query_stats.revalidated_percent(),
+            },
+        }
+    }
+}
+
+/// Response to the `djls/perf` request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PerfReport {
+    phases: BTreeMap<&'static str, LatencySummary>,
+    salsa: SalsaSummary,
+}
+
+/// Latency percentiles over the most recent samples of one phase.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LatencySummary {
+    /// Samples recorded since the server started.
+    count: u64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SalsaSummary {
+    executed: u64,
+    revalidated: u64,
+    revalidated_percent: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_uses_nearest_rank_percentiles() {
+        let mut samples = Samples::default();
+        for ms in 1..=100 {
+            samples.push(Duration::from_millis(ms));
+        }
+
+        let summary = samples.summary();
+        assert_eq!(summary.count, 100);
+        assert!((summary.p50_ms - 50.0).abs() < 1e-6);
+        assert!((summary.p90_ms - 90.0).abs() < 1e-6);
+        assert!((summary.p99_ms - 99.0).abs() < 1e-6);
+        assert!((summary.max_ms - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn samples_keep_a_bounded_window_but_count_everything() {
+        let mut samples = Samples::default();
+        for _ in 0..MAX_SAMPLES + 5 {
+            samples.push(Duration::from_millis(1));
+        }
+
+        assert_eq!(samples.recent.len(), MAX_SAMPLES);
+        assert_eq!(samples.summary().count, 1005);
+    }
+}
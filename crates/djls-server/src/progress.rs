@@ -91,6 +91,28 @@ impl ProgressReporter {
             }
         }
     }
+
+    /// Begin progress on the work-done token the client sent with a request,
+    /// or on a server-created token when it sent none.
+    pub(crate) async fn begin_for_request(
+        &self,
+        token: Option<ls_types::ProgressToken>,
+        title: &str,
+    ) -> ProgressItem {
+        let Some(token) = token else {
+            return self.begin(title).await;
+        };
+
+        let title = title.to_string();
+        send_begin(&self.client, token.clone(), title.clone()).await;
+        ProgressItem {
+            title,
+            state: Some(ProgressState::Lsp {
+                client: self.client.clone(),
+                token,
+            }),
+        }
+    }
 }
 
 impl ProgressItem {
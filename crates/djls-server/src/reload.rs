@@ -13,12 +13,16 @@ use std::sync::atomic::Ordering as AtomicOrdering;
 
 use camino::Utf8PathBuf;
 use djls_conf::Settings;
+use djls_conf::check_project_config;
 use djls_db::DjangoDatabase;
 use djls_ide::PrimedTemplateLibraries;
 use djls_ide::WarmCachePart;
 use djls_ide::WarmCachePhase;
 use djls_ide::collect_diagnostics;
+use djls_ide::collect_syntax_diagnostics;
+use djls_ide::config_file_diagnostics;
 use djls_ide::prime_template_library_products;
+use djls_ide::template_is_affected;
 use djls_ide::warm_cache_phases;
 use djls_project::Db as ProjectDb;
 use djls_project::DjangoEnvironmentData;
@@ -28,10 +32,12 @@ use djls_project::Project;
 use djls_project::ProjectFactsData;
 use djls_project::ProjectFactsPart;
 use djls_project::ProjectFactsPhase;
+use djls_project::TemplateInventoryImpact;
 use djls_project::apply_django_environment;
 use djls_project::apply_project_facts;
 use djls_project::environment_phases;
 use djls_project::project_facts_phases;
+use djls_source::File;
 use djls_source::path_to_file;
 use salsa::Cancelled;
 use tokio::spawn as spawn_task;
@@ -51,6 +57,11 @@ use tracing::warn;
 use crate::client::ClientInfo;
 use crate::document::TextDocument;
 use crate::ext::UriExt;
+use crate::health::DegradedReason;
+use crate::health::report_project_status;
+use crate::health::spawn_health_monitor;
+use crate::inventory::notify_environment_changed;
+use crate::logging::apply_configured_log_level;
 use crate::progress::ProgressItem;
 use crate::progress::ProgressReporter;
 use crate::session::CancellationRetryAction;
@@ -81,6 +92,7 @@ pub(crate) struct ProjectReload {
 impl ProjectReload {
     pub(crate) fn new(session: Arc<Mutex<Session>>, client: Client) -> Self {
         let worker_session = Arc::clone(&session);
+        let health_client = client.clone();
         let reload = Self::spawn(move |job| {
             let session = Arc::clone(&worker_session);
             let client = client.clone();
@@ -94,6 +106,15 @@ impl ProjectReload {
                 }
             }
         });
+
+        let retry_tx = reload.tx.downgrade();
+        let retry_pending = Arc::clone(&reload.pending);
+        spawn_health_monitor(&session, health_client, move || {
+            if let Some(tx) = retry_tx.upgrade() {
+                enqueue_project_work(&retry_pending, &tx, ProjectWork::FullReload);
+            }
+        });
+
         Self {
             session: Some(session),
             ..reload
@@ -261,8 +282,9 @@ async fn reload_project(
         progress.report("Resolving environment").await;
     }
 
+    publish_config_diagnostics(&session, &client).await;
     if !load_and_apply_project_settings(&session, &mut environment_progress).await {
-        fail_generation(&session, generation).await;
+        fail_generation(&session, generation, DegradedReason::Settings).await;
         return ReloadRunOutcome::Complete;
     }
 
@@ -271,13 +293,13 @@ async fn reload_project(
             StageOutcome::Complete(environment) => environment,
             StageOutcome::Cancelled => return ReloadRunOutcome::Cancelled,
             StageOutcome::Failed => {
-                fail_generation(&session, generation).await;
+                fail_generation(&session, generation, DegradedReason::Environment).await;
                 return ReloadRunOutcome::Complete;
             }
         };
     if !apply_environment(&session, environment).await {
         finish_progress(&mut environment_progress, ProgressEnd::Skipped).await;
-        fail_generation(&session, generation).await;
+        fail_generation(&session, generation, DegradedReason::Environment).await;
         return ReloadRunOutcome::Complete;
     }
     finish_progress(&mut environment_progress, ProgressEnd::Complete).await;
@@ -287,36 +309,32 @@ async fn reload_project(
         StageOutcome::Complete(facts) => facts,
         StageOutcome::Cancelled => return ReloadRunOutcome::Cancelled,
         StageOutcome::Failed => {
-            fail_generation(&session, generation).await;
+            fail_generation(&session, generation, DegradedReason::ProjectFacts).await;
             return ReloadRunOutcome::Complete;
         }
     };
     if !apply_facts(&session, &facts).await {
         finish_progress(&mut facts_progress, ProgressEnd::Skipped).await;
-        fail_generation(&session, generation).await;
+        fail_generation(&session, generation, DegradedReason::ProjectFacts).await;
         return ReloadRunOutcome::Complete;
     }
     finish_progress(&mut facts_progress, ProgressEnd::Complete).await;
 
     let Some((intrinsic_snapshot, _)) = snapshot_session(&session).await else {
-        fail_generation(&session, generation).await;
+        fail_generation(&session, generation, DegradedReason::TemplateLibraries).await;
         return ReloadRunOutcome::Complete;
     };
     let primed = match prime_snapshot(intrinsic_snapshot).await {
         StageOutcome::Complete(primed) => primed,
         StageOutcome::Cancelled => return ReloadRunOutcome::Cancelled,
         StageOutcome::Failed => {
-            fail_generation(&session, generation).await;
+            fail_generation(&session, generation, DegradedReason::TemplateLibraries).await;
             return ReloadRunOutcome::Complete;
         }
     };
-    if !session
-        .lock()
-        .await
-        .publish_intrinsic_readiness(generation, &primed)
-    {
+    let Some(impact) = publish_primed(&session, &client, generation, &primed).await else {
         return ReloadRunOutcome::Complete;
-    }
+    };
 
     // Readiness is observable as soon as the required intrinsic products are
     // current. The remaining IDE cache warm-up is optional and must not hold
@@ -324,7 +342,8 @@ async fn reload_project(
     let Some((snapshot, documents)) = snapshot_session(&session).await else {
         return ReloadRunOutcome::Complete;
     };
-    refresh_or_republish_diagnostics(client, snapshot.clone(), documents).await;
+    report_project_status(&client, snapshot.clone(), start.elapsed()).await;
+    republish_affected_diagnostics(client, snapshot.clone(), documents, impact).await;
     warm_snapshot_queries(&progress, snapshot).await;
 
     tracing::info!("Project reload completed in {:?}", start.elapsed());
@@ -336,31 +355,56 @@ async fn reprime_project(session: Arc<Mutex<Session>>, client: Client) -> Reload
         let session = session.lock().await;
         (session.desired_generation(), session.snapshot())
     };
+    let start = std::time::Instant::now();
     match prime_snapshot(snapshot).await {
         StageOutcome::Complete(primed) => {
-            if !session
-                .lock()
-                .await
-                .publish_intrinsic_readiness(generation, &primed)
-            {
+            let Some(impact) = publish_primed(&session, &client, generation, &primed).await
+            else {
                 return ReloadRunOutcome::Complete;
-            }
+            };
             let Some((snapshot, documents)) = snapshot_session(&session).await else {
                 return ReloadRunOutcome::Complete;
             };
-            refresh_or_republish_diagnostics(client, snapshot, documents).await;
+            report_project_status(&client, snapshot.clone(), start.elapsed()).await;
+            republish_affected_diagnostics(client, snapshot, documents, impact).await;
             ReloadRunOutcome::Complete
         }
         StageOutcome::Cancelled => ReloadRunOutcome::Cancelled,
         StageOutcome::Failed => {
-            fail_generation(&session, generation).await;
+            fail_generation(&session, generation, DegradedReason::TemplateLibraries).await;
             ReloadRunOutcome::Complete
         }
     }
 }
 
-async fn fail_generation(session: &Arc<Mutex<Session>>, generation: u64) {
-    session.lock().await.fail_intrinsic_readiness(generation);
+/// Publish intrinsic readiness for `primed` and report any inventory change.
+///
+/// Returns which open Templates the change affects, or `None` when the
+/// generation is stale and nothing was published.
+async fn publish_primed(
+    session: &Arc<Mutex<Session>>,
+    client: &Client,
+    generation: u64,
+    primed: &PrimedTemplateLibraries,
+) -> Option<TemplateInventoryImpact> {
+    let change = {
+        let mut session = session.lock().await;
+        if !session.publish_intrinsic_readiness(generation, primed) {
+            return None;
+        }
+        session.record_template_inventory(primed.inventory())
+    };
+    if let Some(diff) = change.diff {
+        notify_environment_changed(client, diff).await;
+    }
+    Some(change.impact)
+}
+
+async fn fail_generation(session: &Arc<Mutex<Session>>, generation: u64, reason: DegradedReason) {
+    let mut session = session.lock().await;
+    if session.fail_intrinsic_readiness(generation) {
+        session.record_degraded_reason(reason);
+    }
 }
 
 async fn warm_snapshot_queries(progress: &ProgressReporter, snapshot: SessionSnapshot) {
@@ -396,6 +440,8 @@ async fn load_and_apply_project_settings(
 }
 
 async fn apply_project_settings(session: &Arc<Mutex<Session>>, settings: Settings) -> bool {
+    apply_configured_log_level(settings.log_level());
+
     let mut session_lock = session.lock().await;
     let db = session_lock.db_mut();
     if db.project().is_none() {
@@ -472,6 +518,42 @@ async fn load_project_settings(session: &Arc<Mutex<Session>>) -> StageOutcome<Se
     }
 }
 
+/// Publish schema problems in the project configuration files as diagnostics on those files.
+///
+/// Every existing file is published, so fixing a mistake clears its report, and this runs
+/// whether or not the settings then load.
+async fn publish_config_diagnostics(session: &Arc<Mutex<Session>>, client: &Client) {
+    let Some(project_root) = ({
+        let session_lock = session.lock().await;
+        let db = session_lock.db();
+        db.project().map(|project| project.root(db).clone())
+    }) else {
+        return;
+    };
+
+    let joined = spawn_blocking(move || check_project_config(&project_root)).await;
+    let checks = match classify_child_task_join(joined) {
+        ChildTaskJoin::Complete(checks) => checks,
+        ChildTaskJoin::Failed(error) => {
+            error!(?error, "Configuration check task failed");
+            return;
+        }
+    };
+
+    for check in checks {
+        let Some(lsp_uri) = ls_types::Uri::from_path(check.path()) else {
+            continue;
+        };
+        let diagnostics = config_file_diagnostics(&check);
+        debug!(
+            "Published {} configuration diagnostics for {}",
+            diagnostics.len(),
+            check.path()
+        );
+        client.publish_diagnostics(lsp_uri, diagnostics, None).await;
+    }
+}
+
 type EnvironmentJobResult = Result<EnvironmentPart, Cancelled>;
 type ProjectFactsJobResult = Result<ProjectFactsPart, Cancelled>;
 
@@ -935,10 +1017,116 @@ fn diagnostics_delivery(client_info: &ClientInfo) -> DiagnosticsDelivery {
     }
 }
 
-async fn refresh_or_republish_diagnostics(
+/// Ask pull clients to refresh diagnostics, or publish full diagnostics for
+/// `documents` to push clients.
+pub(crate) async fn refresh_or_republish_diagnostics(
+    client: Client,
+    snapshot: SessionSnapshot,
+    documents: Vec<TextDocument>,
+) {
+    deliver_diagnostics(client, snapshot, documents, DiagnosticsScope::Full).await;
+}
+
+/// Republish diagnostics for the open documents a priming pass can affect.
+///
+/// Pull clients can only be asked to refresh everything, so they are asked
+/// whenever any Template may be affected.
+async fn republish_affected_diagnostics(
+    client: Client,
+    snapshot: SessionSnapshot,
+    documents: Vec<TextDocument>,
+    impact: TemplateInventoryImpact,
+) {
+    if impact.is_empty() {
+        debug!("Template libraries unchanged; keeping published diagnostics");
+        return;
+    }
+    let documents = match impact {
+        TemplateInventoryImpact::Everything => documents,
+        TemplateInventoryImpact::Libraries { .. } => {
+            affected_documents(snapshot.clone(), documents, impact).await
+        }
+    };
+    refresh_or_republish_diagnostics(client, snapshot, documents).await;
+}
+
+/// The open documents whose diagnostics `impact` may change.
+///
+/// Falls back to every document when the check is cancelled or fails.
+async fn affected_documents(
+    snapshot: SessionSnapshot,
+    documents: Vec<TextDocument>,
+    impact: TemplateInventoryImpact,
+) -> Vec<TextDocument> {
+    let paths: Vec<Utf8PathBuf> = documents
+        .iter()
+        .map(|document| document.path().to_path_buf())
+        .collect();
+    let joined = spawn_blocking(move || {
+        Cancelled::catch(AssertUnwindSafe(|| {
+            paths
+                .iter()
+                .map(|path| {
+                    path_to_file(snapshot.db(), path)
+                        .is_ok_and(|file| template_is_affected(snapshot.db(), file, &impact))
+                })
+                .collect::<Vec<_>>()
+        }))
+    })
+    .await;
+    let Ok(Ok(affected)) = joined else {
+        debug!("Affected template check did not finish; republishing every document");
+        return documents;
+    };
+    let total = documents.len();
+    let documents: Vec<_> = documents
+        .into_iter()
+        .zip(affected)
+        .filter_map(|(document, affected)| affected.then_some(document))
+        .collect();
+    debug!(
+        "Template libraries changed; republishing {} of {} documents",
+        documents.len(),
+        total
+    );
+    documents
+}
+
+/// Deliver syntax-only diagnostics while project discovery is failed.
+///
+/// Pull clients are asked to refresh; the pull handler falls back to the same
+/// syntax-only collection when the project is not ready.
+pub(crate) async fn publish_syntax_diagnostics(
+    client: Client,
+    snapshot: SessionSnapshot,
+    documents: Vec<TextDocument>,
+) {
+    deliver_diagnostics(client, snapshot, documents, DiagnosticsScope::Syntax).await;
+}
+
+/// Which diagnostics a snapshot republish collects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DiagnosticsScope {
+    /// Parser and semantic validation diagnostics.
+    Full,
+    /// Parser diagnostics only; needs no project facts.
+    Syntax,
+}
+
+impl DiagnosticsScope {
+    fn collect(self, db: &DjangoDatabase, file: File) -> Option<Vec<ls_types::Diagnostic>> {
+        match self {
+            Self::Full => collect_diagnostics(db, file),
+            Self::Syntax => collect_syntax_diagnostics(db, file),
+        }
+    }
+}
+
+async fn deliver_diagnostics(
     client: Client,
     snapshot: SessionSnapshot,
     documents: Vec<TextDocument>,
+    scope: DiagnosticsScope,
 ) {
     if diagnostics_delivery(snapshot.client_info()) == DiagnosticsDelivery::WorkspaceRefresh {
         match client.workspace_diagnostic_refresh().await {
@@ -950,7 +1138,8 @@ async fn refresh_or_republish_diagnostics(
 
     for document in documents {
         let path = document.path().to_path_buf();
-        let Some(diagnostics) = collect_snapshot_diagnostics(snapshot.clone(), path).await else {
+        let Some(diagnostics) = collect_snapshot_diagnostics(snapshot.clone(), path, scope).await
+        else {
             continue;
         };
 
@@ -991,6 +1180,7 @@ fn classify_diagnostics_task_join(
 async fn collect_snapshot_diagnostics(
     snapshot: SessionSnapshot,
     path: Utf8PathBuf,
+    scope: DiagnosticsScope,
 ) -> Option<Vec<ls_types::Diagnostic>> {
     let mut retry_state = CancellationRetryState::new();
     loop {
@@ -999,7 +1189,7 @@ async fn collect_snapshot_diagnostics(
         let joined = spawn_blocking(move || {
             Cancelled::catch(AssertUnwindSafe(|| {
                 let file = path_to_file(snapshot.db(), &path).ok()?;
-                collect_diagnostics(snapshot.db(), file)
+                scope.collect(snapshot.db(), file)
             }))
         })
         .await;
@@ -1288,7 +1478,7 @@ mod tests {
                             classify_prime_task_join(joined),
                             StageOutcome::Failed
                         ));
-                        fail_generation(&session, 0).await;
+                        fail_generation(&session, 0, DegradedReason::TemplateLibraries).await;
                     }
                     completed_tx
                         .send(run)
@@ -1,23 +1,48 @@
+use std::collections::HashMap;
 use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use std::time::Duration;
 
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use djls_conf::PROJECT_CONFIG_FILES;
+use djls_db::Aborted;
+use djls_db::CancellationToken;
 use djls_source::FileKind;
 use djls_source::path_to_file;
 use salsa::Cancelled;
 use tokio::sync::Mutex;
 use tokio::task::spawn_blocking;
+use tokio::time::MissedTickBehavior;
 use tower_lsp_server::Client;
 use tower_lsp_server::LanguageServer;
+use tower_lsp_server::jsonrpc;
 use tower_lsp_server::jsonrpc::Result as LspResult;
 use tower_lsp_server::ls_types;
+use tracing::Instrument;
 use tracing::debug;
+use tracing::debug_span;
 use tracing::error;
 
+use crate::commands::SERVER_COMMANDS;
+use crate::commands::ServerCommand;
+use crate::commands::dump_block_tree;
 use crate::document::TextDocument;
+use crate::ext::DidChangeConfigurationParamsExt;
 use crate::ext::PositionEncodingExt;
 use crate::ext::UriExt;
 use crate::logging::LoggingGuard;
+use crate::logging::SetLogLevelParams;
+use crate::logging::set_log_level;
+use crate::memory::MemoryReport;
+use crate::partial::PARTIAL_RESULT_BATCH_SIZE;
+use crate::partial::PartialResults;
+use crate::perf::PerfPhase;
+use crate::perf::PerfRecorder;
+use crate::perf::PerfReport;
+use crate::progress::ProgressReporter;
 use crate::reload::ProjectReload;
+use crate::reload::refresh_or_republish_diagnostics;
 use crate::session::CancellationRetryAction;
 use crate::session::CancellationRetryState;
 use crate::session::DocumentMutation;
@@ -26,11 +51,28 @@ use crate::session::SNAPSHOT_CANCEL_RETRIES;
 use crate::session::Session;
 use crate::session::SessionSnapshot;
 
+const FILE_WATCHER_ID: &str = "djls-watched-files";
+
+/// Templates at least this many bytes long get their syntax errors pushed before full validation
+/// finishes when they are opened, so generated templates show their first diagnostics without
+/// waiting for every semantic check.
+const PROGRESSIVE_DIAGNOSTICS_BYTES: usize = 128 * 1024;
+
+/// How often to trim LRU-capped memos while no edit or close does it.
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+const WORKSPACE_DIAGNOSTICS_TITLE: &str = "Checking Django templates";
+const REFERENCES_TITLE: &str = "Finding template references";
+
 pub(crate) struct DjangoLanguageServer {
     client: Client,
     session: Arc<Mutex<Session>>,
     reload: ProjectReload,
     logging: LoggingGuard,
+    perf: Arc<PerfRecorder>,
+    /// Token for the diagnostics push in flight for each document, cancelled
+    /// when a newer change to the same document starts another push.
+    pending_diagnostics: Mutex<HashMap<Utf8PathBuf, CancellationToken>>,
 }
 
 impl DjangoLanguageServer {
@@ -44,6 +86,8 @@ impl DjangoLanguageServer {
             session,
             reload,
             logging,
+            perf: Arc::default(),
+            pending_diagnostics: Mutex::default(),
         }
     }
 
@@ -70,7 +114,18 @@ impl DjangoLanguageServer {
         F: Fn(&SessionSnapshot) -> R + Send + Sync + 'static,
         R: Default + Send + 'static,
     {
-        with_ready_session_snapshot(&self.session, Arc::new(f)).await
+        let token = CancellationToken::new();
+        // The server drops this future on `$/cancelRequest`, which aborts the
+        // computation instead of letting it finish unobserved.
+        let _abort = token.cancel_on_drop();
+        with_ready_session_snapshot(&self.session, &token, Arc::new(f)).await
+    }
+
+    async fn progress_reporter(&self) -> ProgressReporter {
+        let client_info = self
+            .with_session(|session| session.client_info().clone())
+            .await;
+        ProgressReporter::new(self.client.clone(), client_info)
     }
 
     /// Syntax-only requests may bypass project intrinsic readiness.
@@ -79,7 +134,60 @@ impl DjangoLanguageServer {
         F: Fn(&SessionSnapshot) -> R + Send + Sync + 'static,
         R: Default + Send + 'static,
     {
-        with_session_snapshot(&self.session, Arc::new(f)).await
+        let token = CancellationToken::new();
+        let _abort = token.cancel_on_drop();
+        with_session_snapshot(&self.session, &token, Arc::new(f)).await
+    }
+
+    /// Project discovery failed; diagnostics fall back to syntax errors only.
+    async fn is_degraded(&self) -> bool {
+        self.with_session(|session| {
+            matches!(
+                session.readiness_state(),
+                IntrinsicReadinessState::Failed(_)
+            )
+        })
+        .await
+    }
+
+    /// Handle the `djls/setLogLevel` request.
+    pub(crate) async fn set_log_level(&self, params: SetLogLevelParams) -> LspResult<()> {
+        set_log_level(&params.level)
+            .map_err(|error| jsonrpc::Error::invalid_params(format!("{error:#}")))?;
+        tracing::info!("File log level set to '{}'", params.level);
+        Ok(())
+    }
+
+    /// Handle the `djls/perf` request.
+    pub(crate) async fn perf(&self) -> LspResult<PerfReport> {
+        Ok(self
+            .with_session(|session| self.perf.report(session.db().query_stats()))
+            .await)
+    }
+
+    /// Handle the `djls/memory` request.
+    pub(crate) async fn memory(&self) -> LspResult<MemoryReport> {
+        Ok(self
+            .with_session(|session| MemoryReport::new(session.db()))
+            .await)
+    }
+
+    /// Trim LRU-capped memos every [`GARBAGE_COLLECTION_INTERVAL`].
+    ///
+    /// Edits and closes already trim them, so this covers long stretches of requests without
+    /// either, like browsing many templates.
+    fn spawn_garbage_collection(&self) {
+        let session = Arc::clone(&self.session);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(GARBAGE_COLLECTION_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately.
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                session.lock().await.collect_garbage();
+            }
+        });
     }
 
     fn schedule_document_mutation(&self, mutation: DocumentMutation) -> Option<TextDocument> {
@@ -97,7 +205,69 @@ impl DjangoLanguageServer {
         }
     }
 
-    async fn maybe_push_diagnostics(&self, document: &TextDocument) {
+    /// Ask the client to report changes to project configuration files,
+    /// Python sources, and templates.
+    ///
+    /// Python sources cover settings modules and template tag libraries that
+    /// are edited outside the editor; templates cover base templates and
+    /// includes rewritten by a `git checkout` or a code generator. Clients without dynamic
+    /// `workspace/didChangeWatchedFiles` registration still reload on
+    /// `workspace/didChangeConfiguration` and on saved documents.
+    async fn register_file_watchers(&self) {
+        if !self
+            .with_session(|session| session.client_info().supports_watched_files_registration())
+            .await
+        {
+            return;
+        }
+
+        let watchers = PROJECT_CONFIG_FILES
+            .iter()
+            .map(|name| format!("**/{name}"))
+            .chain(["**/*.py", "**/*.{html,htm,djhtml}"].map(str::to_string))
+            .map(|pattern| ls_types::FileSystemWatcher {
+                glob_pattern: ls_types::GlobPattern::String(pattern),
+                kind: None,
+            })
+            .collect();
+        let register_options =
+            serde_json::to_value(ls_types::DidChangeWatchedFilesRegistrationOptions { watchers })
+                .ok();
+        let registration = ls_types::Registration {
+            id: FILE_WATCHER_ID.to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options,
+        };
+
+        if let Err(error) = self.client.register_capability(vec![registration]).await {
+            error!(%error, "Failed to register file watchers");
+        }
+    }
+
+    /// Refresh diagnostics for open documents after a template they may extend
+    /// or include changed on disk.
+    ///
+    /// Skipped while a project reload is pending, since the reload republishes
+    /// when it finishes, and in degraded mode, where syntax diagnostics do not
+    /// depend on other templates.
+    async fn republish_ready_diagnostics(&self) {
+        let Some((snapshot, documents)) = self
+            .with_session(|session| {
+                matches!(
+                    session.readiness_state(),
+                    IntrinsicReadinessState::Ready(_)
+                        | IntrinsicReadinessState::ReadyWithoutProject
+                )
+                .then(|| (session.snapshot(), session.open_documents()))
+            })
+            .await
+        else {
+            return;
+        };
+        refresh_or_republish_diagnostics(self.client.clone(), snapshot, documents).await;
+    }
+
+    async fn maybe_push_diagnostics(&self, document: &TextDocument, push: DiagnosticsPush) {
         if self
             .with_session(|session| session.client_info().supports_pull_diagnostics())
             .await
@@ -107,16 +277,91 @@ impl DjangoLanguageServer {
         }
 
         let path = document.path().to_path_buf();
-        let Some(diagnostics) = self
-            .with_ready_snapshot(move |snapshot| {
-                let file = path_to_file(snapshot.db(), &path).ok()?;
-                djls_ide::collect_diagnostics(snapshot.db(), file)
-            })
+        let token = CancellationToken::new();
+        if let Some(previous) = self
+            .pending_diagnostics
+            .lock()
             .await
-        else {
+            .insert(path.clone(), token.clone())
+        {
+            previous.cancel();
+        }
+
+        let span = debug_span!("publish_diagnostics", path = %path);
+        let perf = Arc::clone(&self.perf);
+        let degraded = self.is_degraded().await;
+        if pushes_syntax_first(push, document, degraded) {
+            let path = path.clone();
+            let syntax = with_session_snapshot(
+                &self.session,
+                &token,
+                Arc::new(move |snapshot: &SessionSnapshot| {
+                    let file = path_to_file(snapshot.db(), &path).ok()?;
+                    djls_ide::collect_syntax_diagnostics(snapshot.db(), file)
+                }),
+            )
+            .instrument(span.clone())
+            .await;
+            // The full set replaces these once validation finishes.
+            if !token.is_cancelled()
+                && let Some(syntax) = syntax
+            {
+                self.publish_document_diagnostics(document, syntax).await;
+            }
+        }
+
+        let diagnostics = async {
+            if degraded {
+                let path = path.clone();
+                with_session_snapshot(
+                    &self.session,
+                    &token,
+                    Arc::new(move |snapshot: &SessionSnapshot| {
+                        let file = path_to_file(snapshot.db(), &path).ok()?;
+                        djls_ide::collect_syntax_diagnostics(snapshot.db(), file)
+                    }),
+                )
+                .await
+            } else {
+                let path = path.clone();
+                with_ready_session_snapshot(
+                    &self.session,
+                    &token,
+                    Arc::new(move |snapshot: &SessionSnapshot| {
+                        let file = path_to_file(snapshot.db(), &path).ok()?;
+                        perf.time(PerfPhase::Diagnostics, || {
+                            perf.time_template_phases(snapshot.db(), file);
+                            djls_ide::collect_diagnostics(snapshot.db(), file)
+                        })
+                    }),
+                )
+                .await
+            }
+        }
+        .instrument(span)
+        .await;
+
+        {
+            let mut pending = self.pending_diagnostics.lock().await;
+            if pending.get(&path) == Some(&token) {
+                pending.remove(&path);
+            }
+        }
+        // A newer change to this document superseded this push.
+        if token.is_cancelled() {
+            return;
+        }
+        let Some(diagnostics) = diagnostics else {
             return;
         };
+        self.publish_document_diagnostics(document, diagnostics).await;
+    }
 
+    async fn publish_document_diagnostics(
+        &self,
+        document: &TextDocument,
+        diagnostics: Vec<ls_types::Diagnostic>,
+    ) {
         let Some(lsp_uri) = ls_types::Uri::from_path(document.path()) else {
             return;
         };
@@ -134,7 +379,29 @@ impl DjangoLanguageServer {
     }
 }
 
-async fn with_session_snapshot<F, R>(session: &Arc<Mutex<Session>>, f: Arc<F>) -> R
+/// What caused a document's diagnostics to be pushed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DiagnosticsPush {
+    Open,
+    Change,
+}
+
+/// Whether to push a document's syntax errors before full validation finishes.
+///
+/// Only on open. After an edit the syntax-only set would replace the semantic diagnostics the
+/// editor already shows until validation caught up, so they would flicker on every keystroke.
+fn pushes_syntax_first(push: DiagnosticsPush, document: &TextDocument, degraded: bool) -> bool {
+    push == DiagnosticsPush::Open
+        && !degraded
+        && document.kind() == FileKind::Template
+        && document.content().len() >= PROGRESSIVE_DIAGNOSTICS_BYTES
+}
+
+async fn with_session_snapshot<F, R>(
+    session: &Arc<Mutex<Session>>,
+    token: &CancellationToken,
+    f: Arc<F>,
+) -> R
 where
     F: Fn(&SessionSnapshot) -> R + Send + Sync + 'static,
     R: Default + Send + 'static,
@@ -143,18 +410,21 @@ where
     loop {
         let snapshot = { session.lock().await.snapshot() };
         let f = Arc::clone(&f);
-        let result =
-            match spawn_blocking(move || Cancelled::catch(AssertUnwindSafe(|| f(&snapshot)))).await
-            {
-                Ok(result) => result,
-                Err(error) => {
-                    error!(
-                        ?error,
-                        "Syntax-only request snapshot task failed; returning fallback"
-                    );
-                    return R::default();
-                }
-            };
+        let task_token = token.clone();
+        let task = spawn_blocking(move || {
+            task_token.run(|| Cancelled::catch(AssertUnwindSafe(|| f(&snapshot))))
+        });
+        let result = match task.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(Aborted)) => {
+                debug!("Superseded request aborted; returning fallback");
+                return R::default();
+            }
+            Err(error) => {
+                error!(?error, "Syntax-only request snapshot task failed; returning fallback");
+                return R::default();
+            }
+        };
         match result {
             Ok(result) => return result,
             Err(cancelled) => match retry_state.after_cancellation() {
@@ -170,7 +440,11 @@ where
     }
 }
 
-async fn with_ready_session_snapshot<F, R>(session: &Arc<Mutex<Session>>, f: Arc<F>) -> R
+async fn with_ready_session_snapshot<F, R>(
+    session: &Arc<Mutex<Session>>,
+    token: &CancellationToken,
+    f: Arc<F>,
+) -> R
 where
     F: Fn(&SessionSnapshot) -> R + Send + Sync + 'static,
     R: Default + Send + 'static,
@@ -181,18 +455,21 @@ where
             return R::default();
         };
         let f = Arc::clone(&f);
-        let result =
-            match spawn_blocking(move || Cancelled::catch(AssertUnwindSafe(|| f(&snapshot)))).await
-            {
-                Ok(result) => result,
-                Err(error) => {
-                    error!(
-                        ?error,
-                        "Project-aware request snapshot task failed; returning fallback"
-                    );
-                    return R::default();
-                }
-            };
+        let task_token = token.clone();
+        let task = spawn_blocking(move || {
+            task_token.run(|| Cancelled::catch(AssertUnwindSafe(|| f(&snapshot))))
+        });
+        let result = match task.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(Aborted)) => {
+                debug!("Superseded request aborted; returning fallback");
+                return R::default();
+            }
+            Err(error) => {
+                error!(?error, "Project-aware request snapshot task failed; returning fallback");
+                return R::default();
+            }
+        };
 
         match result {
             Ok(result) => return result,
@@ -255,6 +532,42 @@ async fn await_ready_session_snapshot(session: &Arc<Mutex<Session>>) -> Option<S
     }
 }
 
+/// The full diagnostic report for one template in a workspace pull.
+fn workspace_diagnostic_report(
+    snapshot: &SessionSnapshot,
+    path: &Utf8Path,
+) -> Option<ls_types::WorkspaceDocumentDiagnosticReport> {
+    let uri = ls_types::Uri::from_path(path)?;
+    let file = path_to_file(snapshot.db(), path).ok()?;
+    let items = djls_ide::collect_diagnostics(snapshot.db(), file).unwrap_or_default();
+
+    Some(ls_types::WorkspaceDocumentDiagnosticReport::Full(
+        ls_types::WorkspaceFullDocumentDiagnosticReport {
+            uri,
+            version: None,
+            full_document_diagnostic_report: ls_types::FullDocumentDiagnosticReport {
+                result_id: None,
+                items,
+            },
+        },
+    ))
+}
+
+/// Whether a code action request's `only` filter admits `kind`. Kinds are
+/// hierarchical, so asking for `refactor` admits `refactor.extract`.
+fn requests_code_action_kind(
+    only: Option<&[ls_types::CodeActionKind]>,
+    kind: &ls_types::CodeActionKind,
+) -> bool {
+    only.is_none_or(|only| {
+        only.iter().any(|requested| {
+            kind.as_str()
+                .strip_prefix(requested.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    })
+}
+
 impl LanguageServer for DjangoLanguageServer {
     async fn initialize(
         &self,
@@ -280,6 +593,7 @@ impl LanguageServer for DjangoLanguageServer {
                         " ".to_string(),
                         "\"".to_string(),
                         "'".to_string(),
+                        "/".to_string(),
                     ]),
                     ..Default::default()
                 }),
@@ -304,13 +618,19 @@ impl LanguageServer for DjangoLanguageServer {
                     ls_types::DiagnosticOptions {
                         identifier: None,
                         inter_file_dependencies: false,
-                        workspace_diagnostics: false,
-                        work_done_progress_options: ls_types::WorkDoneProgressOptions::default(),
+                        workspace_diagnostics: true,
+                        work_done_progress_options: ls_types::WorkDoneProgressOptions {
+                            work_done_progress: Some(true),
+                        },
                     },
                 )),
                 code_action_provider: Some(ls_types::CodeActionProviderCapability::Options(
                     ls_types::CodeActionOptions {
-                        code_action_kinds: Some(vec![ls_types::CodeActionKind::QUICKFIX]),
+                        code_action_kinds: Some(vec![
+                            ls_types::CodeActionKind::QUICKFIX,
+                            ls_types::CodeActionKind::REFACTOR_EXTRACT,
+                            ls_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                        ]),
                         work_done_progress_options: ls_types::WorkDoneProgressOptions::default(),
                         resolve_provider: Some(false),
                     },
@@ -323,10 +643,32 @@ impl LanguageServer for DjangoLanguageServer {
                     resolve_provider: Some(false),
                     work_done_progress_options: ls_types::WorkDoneProgressOptions::default(),
                 }),
+                color_provider: Some(ls_types::ColorProviderCapability::Simple(true)),
                 hover_provider: Some(ls_types::HoverProviderCapability::Simple(true)),
                 definition_provider: Some(ls_types::OneOf::Left(true)),
-                references_provider: Some(ls_types::OneOf::Left(true)),
+                references_provider: Some(ls_types::OneOf::Right(ls_types::ReferencesOptions {
+                    work_done_progress_options: ls_types::WorkDoneProgressOptions {
+                        work_done_progress: Some(true),
+                    },
+                })),
+                rename_provider: Some(ls_types::OneOf::Right(ls_types::RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: ls_types::WorkDoneProgressOptions::default(),
+                })),
+                linked_editing_range_provider: Some(
+                    ls_types::LinkedEditingRangeServerCapabilities::Simple(true),
+                ),
                 document_formatting_provider: Some(ls_types::OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(
+                    ls_types::DocumentOnTypeFormattingOptions {
+                        first_trigger_character: "%".to_string(),
+                        more_trigger_character: Some(vec!["}".to_string()]),
+                    },
+                ),
+                execute_command_provider: Some(ls_types::ExecuteCommandOptions {
+                    commands: SERVER_COMMANDS.into_iter().map(String::from).collect(),
+                    work_done_progress_options: ls_types::WorkDoneProgressOptions::default(),
+                }),
                 ..Default::default()
             },
             server_info: Some(ls_types::ServerInfo {
@@ -341,6 +683,8 @@ impl LanguageServer for DjangoLanguageServer {
         tracing::info!("Server received initialized notification.");
 
         self.reload.request_full_reload().await;
+        self.register_file_watchers().await;
+        self.spawn_garbage_collection();
     }
 
     async fn shutdown(&self) -> LspResult<()> {
@@ -354,7 +698,7 @@ impl LanguageServer for DjangoLanguageServer {
             .await;
 
         if let Some(document) = self.schedule_document_mutation(mutation) {
-            self.maybe_push_diagnostics(&document).await;
+            self.maybe_push_diagnostics(&document, DiagnosticsPush::Open).await;
         }
     }
 
@@ -364,7 +708,7 @@ impl LanguageServer for DjangoLanguageServer {
             .await;
 
         if let Some(document) = self.schedule_document_mutation(mutation) {
-            self.maybe_push_diagnostics(&document).await;
+            self.maybe_push_diagnostics(&document, DiagnosticsPush::Change).await;
         }
     }
 
@@ -376,7 +720,7 @@ impl LanguageServer for DjangoLanguageServer {
             .await;
 
         if let Some(document) = self.schedule_document_mutation(mutation) {
-            self.maybe_push_diagnostics(&document).await;
+            self.maybe_push_diagnostics(&document, DiagnosticsPush::Change).await;
         }
     }
 
@@ -391,11 +735,13 @@ impl LanguageServer for DjangoLanguageServer {
         &self,
         params: ls_types::CodeActionParams,
     ) -> LspResult<Option<ls_types::CodeActionResponse>> {
-        if params.context.only.as_ref().is_some_and(|only| {
-            !only
-                .iter()
-                .any(|kind| kind == &ls_types::CodeActionKind::QUICKFIX)
-        }) {
+        let only = params.context.only.as_deref();
+        let quick_fixes = requests_code_action_kind(only, &ls_types::CodeActionKind::QUICKFIX);
+        let extract =
+            requests_code_action_kind(only, &ls_types::CodeActionKind::REFACTOR_EXTRACT);
+        let organize_loads =
+            requests_code_action_kind(only, &ls_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS);
+        if !quick_fixes && !extract && !organize_loads {
             return Ok(None);
         }
 
@@ -413,7 +759,24 @@ impl LanguageServer for DjangoLanguageServer {
                     return None;
                 }
 
-                djls_ide::code_actions(db, file, range, snapshot.client_info().position_encoding())
+                let encoding = snapshot.client_info().position_encoding();
+                let mut actions = if quick_fixes {
+                    djls_ide::code_actions(db, file, range, encoding)?
+                } else {
+                    Vec::new()
+                };
+                if extract && snapshot.client_info().supports_file_creation() {
+                    actions.extend(djls_ide::extract_include_action(db, file, range, encoding));
+                }
+                if organize_loads {
+                    actions.extend(djls_ide::organize_loads_action(
+                        db,
+                        file,
+                        db.settings().format().merge_loads(),
+                        encoding,
+                    ));
+                }
+                Some(actions)
             })
             .await;
 
@@ -424,6 +787,11 @@ impl LanguageServer for DjangoLanguageServer {
         &self,
         params: ls_types::CompletionParams,
     ) -> LspResult<Option<ls_types::CompletionResponse>> {
+        let span = debug_span!(
+            "completion",
+            uri = ?params.text_document_position.text_document.uri
+        );
+        let perf = Arc::clone(&self.perf);
         let response = self
             .with_ready_snapshot(move |snapshot| {
                 let (file, offset) = snapshot.position_for_document_request(
@@ -433,25 +801,32 @@ impl LanguageServer for DjangoLanguageServer {
                 )?;
                 let db = snapshot.db();
 
-                if !matches!(file.try_source(db), Ok(source) if *source.kind() == FileKind::Template)
-                {
+                if !matches!(
+                    file.try_source(db),
+                    Ok(source) if matches!(source.kind(), FileKind::Template | FileKind::Python)
+                ) {
                     return None;
                 }
 
-                djls_ide::completion(
-                    db,
-                    file,
-                    offset,
-                    snapshot.client_info().position_encoding(),
-                    snapshot.client_info().supports_snippets(),
-                )
+                perf.time(PerfPhase::Completion, || {
+                    djls_ide::completion(
+                        db,
+                        file,
+                        offset,
+                        snapshot.client_info().position_encoding(),
+                        snapshot.client_info().supports_snippets(),
+                        db.settings().completions(),
+                    )
+                })
             })
+            .instrument(span)
             .await;
 
         Ok(response)
     }
 
     async fn hover(&self, params: ls_types::HoverParams) -> LspResult<Option<ls_types::Hover>> {
+        let perf = Arc::clone(&self.perf);
         let response = self
             .with_ready_snapshot(move |snapshot| {
                 let (file, offset) = snapshot.position_for_document_request(
@@ -466,7 +841,87 @@ impl LanguageServer for DjangoLanguageServer {
                     return None;
                 }
 
-                djls_ide::hover(db, file, offset)
+                perf.time(PerfPhase::Hover, || djls_ide::hover(db, file, offset))
+            })
+            .await;
+
+        Ok(response)
+    }
+
+    async fn linked_editing_range(
+        &self,
+        params: ls_types::LinkedEditingRangeParams,
+    ) -> LspResult<Option<ls_types::LinkedEditingRanges>> {
+        let response = self
+            .with_ready_snapshot(move |snapshot| {
+                let (file, offset) = snapshot.position_for_document_request(
+                    &params.text_document_position_params.text_document,
+                    params.text_document_position_params.position,
+                    "linkedEditingRange",
+                )?;
+
+                djls_ide::linked_editing_ranges(
+                    snapshot.db(),
+                    file,
+                    offset,
+                    snapshot.client_info().position_encoding(),
+                )
+            })
+            .await;
+
+        Ok(response)
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: ls_types::TextDocumentPositionParams,
+    ) -> LspResult<Option<ls_types::PrepareRenameResponse>> {
+        let response = self
+            .with_ready_snapshot(move |snapshot| {
+                let (file, offset) = snapshot.position_for_document_request(
+                    &params.text_document,
+                    params.position,
+                    "prepareRename",
+                )?;
+
+                djls_ide::prepare_rename(
+                    snapshot.db(),
+                    file,
+                    offset,
+                    snapshot.client_info().position_encoding(),
+                )
+            })
+            .await;
+
+        Ok(response)
+    }
+
+    async fn rename(
+        &self,
+        params: ls_types::RenameParams,
+    ) -> LspResult<Option<ls_types::WorkspaceEdit>> {
+        if !djls_ide::is_template_variable_name(&params.new_name) {
+            return Err(jsonrpc::Error::invalid_params(format!(
+                "'{}' is not a valid template variable name",
+                params.new_name
+            )));
+        }
+
+        let response = self
+            .with_ready_snapshot(move |snapshot| {
+                let (file, offset) = snapshot.position_for_document_request(
+                    &params.text_document_position.text_document,
+                    params.text_document_position.position,
+                    "rename",
+                )?;
+
+                djls_ide::rename(
+                    snapshot.db(),
+                    file,
+                    offset,
+                    &params.new_name,
+                    snapshot.client_info().position_encoding(),
+                )
             })
             .await;
 
@@ -482,17 +937,38 @@ impl LanguageServer for DjangoLanguageServer {
             params.text_document.uri
         );
 
-        let diagnostics = self
-            .with_ready_snapshot(move |snapshot| {
-                let Some(file) =
-                    snapshot.file_for_document_request(&params.text_document, "diagnostic")
-                else {
-                    return Vec::new();
-                };
-
-                djls_ide::collect_diagnostics(snapshot.db(), file).unwrap_or_default()
-            })
-            .await;
+        let span = debug_span!("diagnostic", uri = ?params.text_document.uri);
+        let perf = Arc::clone(&self.perf);
+        let diagnostics = async {
+            if self.is_degraded().await {
+                self.with_snapshot(move |snapshot| {
+                    let Some(file) =
+                        snapshot.file_for_document_request(&params.text_document, "diagnostic")
+                    else {
+                        return Vec::new();
+                    };
+
+                    djls_ide::collect_syntax_diagnostics(snapshot.db(), file).unwrap_or_default()
+                })
+                .await
+            } else {
+                self.with_ready_snapshot(move |snapshot| {
+                    let Some(file) =
+                        snapshot.file_for_document_request(&params.text_document, "diagnostic")
+                    else {
+                        return Vec::new();
+                    };
+
+                    perf.time(PerfPhase::Diagnostics, || {
+                        perf.time_template_phases(snapshot.db(), file);
+                        djls_ide::collect_diagnostics(snapshot.db(), file).unwrap_or_default()
+                    })
+                })
+                .await
+            }
+        }
+        .instrument(span)
+        .await;
 
         Ok(ls_types::DocumentDiagnosticReportResult::Report(
             ls_types::DocumentDiagnosticReport::Full(
@@ -507,6 +983,61 @@ impl LanguageServer for DjangoLanguageServer {
         ))
     }
 
+    async fn workspace_diagnostic(
+        &self,
+        params: ls_types::WorkspaceDiagnosticParams,
+    ) -> LspResult<ls_types::WorkspaceDiagnosticReportResult> {
+        debug!("Received workspace diagnostic request");
+
+        let paths = self
+            .with_ready_snapshot(SessionSnapshot::workspace_template_paths)
+            .await;
+        let progress = self
+            .progress_reporter()
+            .await
+            .begin_for_request(
+                params.work_done_progress_params.work_done_token,
+                WORKSPACE_DIAGNOSTICS_TITLE,
+            )
+            .await;
+        let partial = PartialResults::new(&self.client, params.partial_result_params);
+
+        // Each batch runs on its own snapshot so results reach the client while
+        // later templates are still being validated.
+        let mut items = Vec::new();
+        let mut done = 0;
+        for batch in paths.chunks(PARTIAL_RESULT_BATCH_SIZE) {
+            let batch = batch.to_vec();
+            done += batch.len();
+            let reports = self
+                .with_ready_snapshot(move |snapshot| {
+                    batch
+                        .iter()
+                        .filter_map(|path| workspace_diagnostic_report(snapshot, path))
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            progress
+                .report_fraction(done, paths.len(), "templates")
+                .await;
+            match &partial {
+                Some(partial) => {
+                    partial
+                        .send(ls_types::WorkspaceDiagnosticReportPartialResult { items: reports })
+                        .await;
+                }
+                None => items.extend(reports),
+            }
+        }
+        progress
+            .finish(&format!("Checked {} templates", paths.len()))
+            .await;
+
+        Ok(ls_types::WorkspaceDiagnosticReportResult::Report(
+            ls_types::WorkspaceDiagnosticReport { items },
+        ))
+    }
+
     async fn folding_range(
         &self,
         params: ls_types::FoldingRangeParams,
@@ -582,6 +1113,64 @@ impl LanguageServer for DjangoLanguageServer {
         Ok(Some(links))
     }
 
+    async fn document_color(
+        &self,
+        params: ls_types::DocumentColorParams,
+    ) -> LspResult<Vec<ls_types::ColorInformation>> {
+        let colors = self
+            .with_snapshot(move |snapshot| {
+                let Some(file) =
+                    snapshot.file_for_document_request(&params.text_document, "document color")
+                else {
+                    return Vec::new();
+                };
+                let db = snapshot.db();
+
+                if !matches!(file.try_source(db), Ok(source) if *source.kind() == FileKind::Template)
+                {
+                    return Vec::new();
+                }
+
+                djls_ide::document_colors(db, file, snapshot.client_info().position_encoding())
+            })
+            .await;
+
+        Ok(colors)
+    }
+
+    async fn color_presentation(
+        &self,
+        params: ls_types::ColorPresentationParams,
+    ) -> LspResult<Vec<ls_types::ColorPresentation>> {
+        let presentations = self
+            .with_snapshot(move |snapshot| {
+                let Some((file, span)) = snapshot.range_for_document_request(
+                    &params.text_document,
+                    params.range,
+                    "color presentation",
+                ) else {
+                    return Vec::new();
+                };
+                let db = snapshot.db();
+
+                if !matches!(file.try_source(db), Ok(source) if *source.kind() == FileKind::Template)
+                {
+                    return Vec::new();
+                }
+
+                djls_ide::color_presentations(
+                    db,
+                    file,
+                    span,
+                    params.color,
+                    snapshot.client_info().position_encoding(),
+                )
+            })
+            .await;
+
+        Ok(presentations)
+    }
+
     async fn goto_definition(
         &self,
         params: ls_types::GotoDefinitionParams,
@@ -617,11 +1206,27 @@ impl LanguageServer for DjangoLanguageServer {
         &self,
         params: ls_types::ReferenceParams,
     ) -> LspResult<Option<Vec<ls_types::Location>>> {
+        let ls_types::ReferenceParams {
+            text_document_position,
+            context,
+            work_done_progress_params,
+            partial_result_params,
+        } = params;
+        let progress = match work_done_progress_params.work_done_token {
+            Some(token) => Some(
+                self.progress_reporter()
+                    .await
+                    .begin_for_request(Some(token), REFERENCES_TITLE)
+                    .await,
+            ),
+            None => None,
+        };
+
         let response = self
             .with_ready_snapshot(move |snapshot| {
                 let (file, offset) = snapshot.position_for_document_request(
-                    &params.text_document_position.text_document,
-                    params.text_document_position.position,
+                    &text_document_position.text_document,
+                    text_document_position.position,
                     "references",
                 )?;
                 let db = snapshot.db();
@@ -636,11 +1241,27 @@ impl LanguageServer for DjangoLanguageServer {
                     file,
                     offset,
                     snapshot.client_info().position_encoding(),
-                    params.context.include_declaration,
+                    context.include_declaration,
                 )
             })
             .await;
 
+        let count = response.as_ref().map_or(0, Vec::len);
+        let response = match PartialResults::new(&self.client, partial_result_params) {
+            // Locations arrive grouped by file, so each file goes out as its
+            // own chunk and the final response stays empty.
+            Some(partial) => {
+                for chunk in response.unwrap_or_default().chunk_by(|a, b| a.uri == b.uri) {
+                    partial.send(chunk).await;
+                }
+                Some(Vec::new())
+            }
+            None => response,
+        };
+        if let Some(progress) = progress {
+            progress.finish(&format!("Found {count} references")).await;
+        }
+
         Ok(response)
     }
 
@@ -682,10 +1303,97 @@ impl LanguageServer for DjangoLanguageServer {
         Ok(Some(edits))
     }
 
-    async fn did_change_configuration(&self, _params: ls_types::DidChangeConfigurationParams) {
+    async fn on_type_formatting(
+        &self,
+        params: ls_types::DocumentOnTypeFormattingParams,
+    ) -> LspResult<Option<Vec<ls_types::TextEdit>>> {
+        let edits = self
+            .with_ready_snapshot(move |snapshot| {
+                let (file, offset) = snapshot.position_for_document_request(
+                    &params.text_document_position.text_document,
+                    params.text_document_position.position,
+                    "onTypeFormatting",
+                )?;
+                let db = snapshot.db();
+
+                djls_ide::on_type_formatting(
+                    db,
+                    file,
+                    offset,
+                    &params.ch,
+                    snapshot.client_info().position_encoding(),
+                    db.settings().format().insert_end_tags(),
+                )
+            })
+            .await;
+
+        Ok(edits)
+    }
+
+    async fn execute_command(
+        &self,
+        params: ls_types::ExecuteCommandParams,
+    ) -> LspResult<Option<ls_types::LSPAny>> {
+        let command = ServerCommand::parse(&params)?;
+        tracing::info!("Executing command '{}'", params.command);
+
+        match command {
+            ServerCommand::RestartInspector => {}
+            ServerCommand::RebuildInventory => {
+                self.with_session_mut(Session::rescan_sources).await;
+            }
+            ServerCommand::ClearCache => {
+                self.with_session_mut(Session::reset_database).await;
+            }
+            ServerCommand::DumpBlockTree(uri) => {
+                let text_document = ls_types::TextDocumentIdentifier { uri };
+                let tree = self
+                    .with_ready_snapshot(move |snapshot| {
+                        let file =
+                            snapshot.file_for_document_request(&text_document, "dump block tree")?;
+                        dump_block_tree(snapshot.db(), file)
+                    })
+                    .await;
+                return Ok(tree);
+            }
+        }
+
+        self.reload.request_full_reload().await;
+        Ok(None)
+    }
+
+    async fn did_change_configuration(&self, params: ls_types::DidChangeConfigurationParams) {
         tracing::info!("Configuration change detected. Requesting project reload...");
+        if let Some(options) = params.client_options() {
+            self.with_session_mut(|session| session.set_client_options(options)).await;
+        }
         self.reload.request_full_reload().await;
     }
+
+    async fn did_change_watched_files(&self, params: ls_types::DidChangeWatchedFilesParams) {
+        let (config_changed, changes) = self
+            .with_session_mut(|session| {
+                let config_changed = params
+                    .changes
+                    .iter()
+                    .filter_map(|change| change.uri.to_utf8_path_buf())
+                    .any(|path| session.is_project_config_file(&path));
+                let changes = session.apply_watched_file_changes(&params.changes);
+                (config_changed, changes)
+            })
+            .await;
+
+        if config_changed {
+            tracing::info!("Project configuration file changed. Requesting project reload...");
+            self.reload.request_full_reload().await;
+        } else if let Some(project_work) = changes.project_work {
+            debug!(?project_work, "Watched Python source changed");
+            self.reload.request_current(project_work);
+        } else if changes.templates_changed {
+            debug!("Watched template changed on disk");
+            self.republish_ready_diagnostics().await;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -705,12 +1413,56 @@ mod tests {
     use super::*;
     use crate::session::ProjectWork;
 
+    #[test]
+    fn code_action_only_filter_admits_child_kinds() {
+        let refactor = [ls_types::CodeActionKind::REFACTOR];
+        let extract = &ls_types::CodeActionKind::REFACTOR_EXTRACT;
+
+        assert!(requests_code_action_kind(None, extract));
+        assert!(requests_code_action_kind(Some(&refactor), extract));
+        assert!(!requests_code_action_kind(
+            Some(&refactor),
+            &ls_types::CodeActionKind::QUICKFIX
+        ));
+        assert!(!requests_code_action_kind(
+            Some(&[ls_types::CodeActionKind::REFACTOR_EXTRACT]),
+            &ls_types::CodeActionKind::REFACTOR
+        ));
+    }
+
+    #[test]
+    fn large_templates_push_syntax_errors_first_only_when_opened() {
+        let document = |kind, len| {
+            TextDocument::new(
+                Utf8PathBuf::from("/project/templates/generated.html"),
+                "x".repeat(len),
+                1,
+                kind,
+            )
+        };
+        let large = document(FileKind::Template, PROGRESSIVE_DIAGNOSTICS_BYTES);
+
+        assert!(pushes_syntax_first(DiagnosticsPush::Open, &large, false));
+        assert!(!pushes_syntax_first(DiagnosticsPush::Change, &large, false));
+        assert!(!pushes_syntax_first(DiagnosticsPush::Open, &large, true));
+        assert!(!pushes_syntax_first(
+            DiagnosticsPush::Open,
+            &document(FileKind::Template, PROGRESSIVE_DIAGNOSTICS_BYTES - 1),
+            false
+        ));
+        assert!(!pushes_syntax_first(
+            DiagnosticsPush::Open,
+            &document(FileKind::Python, PROGRESSIVE_DIAGNOSTICS_BYTES),
+            false
+        ));
+    }
+
     #[tokio::test]
     async fn syntax_only_request_task_panic_returns_default() {
         let session = Arc::new(Mutex::new(Session::default()));
         let executions = Arc::new(AtomicUsize::new(0));
 
-        let response: usize = with_session_snapshot(&session, {
+        let response: usize = with_session_snapshot(&session, &CancellationToken::new(), {
             let executions = Arc::clone(&executions);
             Arc::new(move |_: &SessionSnapshot| {
                 executions.fetch_add(1, Ordering::SeqCst);
@@ -723,6 +1475,27 @@ mod tests {
         assert_eq!(executions.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn aborted_request_returns_default_without_retrying() {
+        let session = Arc::new(Mutex::new(Session::default()));
+        let executions = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+
+        let response: usize = with_session_snapshot(&session, &token, {
+            let executions = Arc::clone(&executions);
+            let token = token.clone();
+            Arc::new(move |_: &SessionSnapshot| {
+                executions.fetch_add(1, Ordering::SeqCst);
+                token.cancel();
+                std::panic::resume_unwind(Box::new(Aborted))
+            })
+        })
+        .await;
+
+        assert_eq!(response, usize::default());
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn project_aware_request_task_panic_returns_default() {
         let session = Arc::new(Mutex::new(Session::default()));
@@ -733,6 +1506,7 @@ mod tests {
             async move {
                 with_ready_session_snapshot(
                     &session,
+                    &CancellationToken::new(),
                     Arc::new(move |_: &SessionSnapshot| {
                         executions.fetch_add(1, Ordering::SeqCst);
                         panic!("synthetic project-aware request panic");
@@ -802,6 +1576,7 @@ mod tests {
             async move {
                 with_ready_session_snapshot(
                     &session,
+                    &CancellationToken::new(),
                     Arc::new(move |snapshot: &SessionSnapshot| {
                         let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
                         if attempt == 1 {
@@ -3,13 +3,20 @@
 //! This module implements the LSP session abstraction that manages project-specific
 //! state and the Salsa database for incremental computation.
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::PROJECT_CONFIG_FILES;
+use djls_conf::Settings;
 use djls_db::DjangoDatabase;
 use djls_ide::PrimedTemplateLibraries;
 use djls_project::Db as ProjectDb;
+use djls_project::TemplateInventory;
+use djls_project::TemplateInventoryDiff;
+use djls_project::TemplateInventoryImpact;
+use djls_project::template_resolution;
 use djls_source::ChangeEvent;
 use djls_source::Db as _;
 use djls_source::File;
@@ -23,12 +30,14 @@ use tokio::sync::watch;
 use tower_lsp_server::ls_types;
 
 use crate::client::ClientInfo;
+use crate::client::ClientOptions;
 use crate::document::TextDocument;
 use crate::ext::InitializeParamsExt;
 use crate::ext::PositionExt;
 use crate::ext::TextDocumentContentChangeEventExt;
 use crate::ext::TextDocumentItemExt;
 use crate::ext::UriExt;
+use crate::health::DegradedReason;
 use crate::workspace::Workspace;
 
 /// How many times snapshot-based reads retry after Salsa cancellation before
@@ -65,7 +74,17 @@ impl CancellationRetryState {
 
 pub(crate) type IntrinsicGeneration = u64;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// What a published priming pass changed since the previous one.
+#[derive(Debug, PartialEq)]
+pub(crate) struct TemplateInventoryChange {
+    /// Libraries and tags that appeared or disappeared, if any
+    pub(crate) diff: Option<TemplateInventoryDiff>,
+    /// Which open Templates need their diagnostics republished
+    pub(crate) impact: TemplateInventoryImpact,
+}
+
+/// Ordered so that a full reload dominates a re-prime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub(crate) enum ProjectWork {
     Reprime,
     FullReload,
@@ -80,6 +99,15 @@ pub(crate) enum DocumentMutation {
     },
 }
 
+/// What applying a batch of `workspace/didChangeWatchedFiles` events changed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct WatchedFileChanges {
+    /// Project work that changed Python sources require.
+    pub(crate) project_work: Option<ProjectWork>,
+    /// Whether a template that is not open in the editor changed on disk.
+    pub(crate) templates_changed: bool,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum IntrinsicReadinessState {
     ReadyWithoutProject,
@@ -364,6 +392,13 @@ pub(crate) struct Session {
 
     intrinsic_readiness: IntrinsicReadiness,
     readiness_tx: watch::Sender<IntrinsicReadinessState>,
+
+    /// Libraries and tags from the last published priming pass, with the
+    /// settings it ran under
+    template_inventory: Option<(TemplateInventory, Settings)>,
+
+    /// Why the last failed generation failed, for `djls/status`
+    degraded_reason: Option<DegradedReason>,
 }
 
 impl Session {
@@ -386,11 +421,12 @@ impl Session {
         let client_settings = client_options.settings.clone();
 
         let workspace = Workspace::new();
-        let db = DjangoDatabase::new(
+        let mut db = DjangoDatabase::new(
             workspace.overlay(),
             &client_settings,
             project_path.as_deref(),
         );
+        db.set_plugins_allowed(client_options.allow_plugins);
 
         let client_info = ClientInfo::new(
             &params.capabilities,
@@ -407,6 +443,8 @@ impl Session {
             db,
             intrinsic_readiness,
             readiness_tx,
+            template_inventory: None,
+            degraded_reason: None,
         }
     }
 
@@ -426,6 +464,18 @@ impl Session {
         self.intrinsic_readiness.watched_state()
     }
 
+    /// Trim LRU-capped memos, unless a project reload is running.
+    ///
+    /// Trimming is a write, so it would cancel the reload's snapshot queries.
+    pub(crate) fn collect_garbage(&mut self) {
+        if matches!(
+            self.readiness_state(),
+            IntrinsicReadinessState::Ready(_) | IntrinsicReadinessState::ReadyWithoutProject
+        ) {
+            self.db.collect_garbage();
+        }
+    }
+
     pub(crate) const fn desired_generation(&self) -> IntrinsicGeneration {
         self.intrinsic_readiness.desired_generation()
     }
@@ -451,11 +501,43 @@ impl Session {
             },
         );
         if published {
+            self.degraded_reason = None;
             self.publish_readiness();
         }
         published
     }
 
+    /// Replace the recorded Template Library inventory, returning what changed.
+    ///
+    /// The first inventory after startup is a baseline rather than a change, so
+    /// it has no diff and affects every Template. So does any inventory
+    /// recorded under different project settings.
+    pub(crate) fn record_template_inventory(
+        &mut self,
+        inventory: &TemplateInventory,
+    ) -> TemplateInventoryChange {
+        let settings = self.db.settings();
+        let Some((previous, previous_settings)) = self
+            .template_inventory
+            .replace((inventory.clone(), settings.clone()))
+        else {
+            return TemplateInventoryChange {
+                diff: None,
+                impact: TemplateInventoryImpact::Everything,
+            };
+        };
+        let diff = previous.diff(inventory);
+        let impact = if previous_settings == settings {
+            previous.impact(inventory)
+        } else {
+            TemplateInventoryImpact::Everything
+        };
+        TemplateInventoryChange {
+            diff: (!diff.is_empty()).then_some(diff),
+            impact,
+        }
+    }
+
     pub(crate) fn fail_intrinsic_readiness(&mut self, generation: IntrinsicGeneration) -> bool {
         let db = &self.db;
         let failed = self.intrinsic_readiness.fail(generation, |coverage| {
@@ -472,6 +554,15 @@ impl Session {
         failed
     }
 
+    /// Record why the generation just failed.
+    pub(crate) fn record_degraded_reason(&mut self, reason: DegradedReason) {
+        self.degraded_reason = Some(reason);
+    }
+
+    pub(crate) const fn degraded_reason(&self) -> Option<DegradedReason> {
+        self.degraded_reason
+    }
+
     #[cfg(test)]
     pub(crate) fn install_ready_coverage_for_test(
         &mut self,
@@ -569,6 +660,97 @@ impl Session {
         &self.client_info
     }
 
+    /// Replace the client-provided settings overrides.
+    ///
+    /// The new overrides take effect on the next full project reload, which
+    /// re-reads the project configuration files underneath them. Allowing or
+    /// disallowing plugins takes effect immediately.
+    pub(crate) fn set_client_options(&mut self, options: ClientOptions) {
+        self.db.set_plugins_allowed(options.allow_plugins);
+        self.client_info.set_options(options);
+    }
+
+    /// Whether `path` is one of the configuration files read from the
+    /// current project root.
+    pub(crate) fn is_project_config_file(&self, path: &Utf8Path) -> bool {
+        let Some(project) = self.db.project() else {
+            return false;
+        };
+        path.parent() == Some(project.root(&self.db).as_path())
+            && path
+                .file_name()
+                .is_some_and(|name| PROJECT_CONFIG_FILES.contains(&name))
+    }
+
+    /// Apply on-disk Python changes reported by the client's file watcher.
+    ///
+    /// Open documents are skipped because their editor buffers already shadow
+    /// the disk. Returns the strongest project work the changes require.
+    /// Apply file watcher events for Python sources and templates that are not
+    /// open in the editor.
+    ///
+    /// Open documents are skipped because their buffers, not the disk, are what
+    /// the database sees.
+    pub(crate) fn apply_watched_file_changes(
+        &mut self,
+        changes: &[ls_types::FileEvent],
+    ) -> WatchedFileChanges {
+        let mut outcome = WatchedFileChanges::default();
+        for change in changes {
+            let Some(path) = change.uri.to_utf8_path_buf() else {
+                continue;
+            };
+            let kind = FileKind::from(&path);
+            if kind == FileKind::Other || self.workspace.get_document(&path).is_some() {
+                continue;
+            }
+
+            let event = if change.typ == ls_types::FileChangeType::CREATED {
+                ChangeEvent::BecameVisible(path)
+            } else if change.typ == ls_types::FileChangeType::DELETED {
+                ChangeEvent::Deleted(path)
+            } else {
+                ChangeEvent::ContentChanged(path)
+            };
+            SourceChanges::new([event.clone()]).apply(&mut self.db);
+            if kind == FileKind::Template {
+                outcome.templates_changed = true;
+            } else if let Some(work) = self.mark_intrinsic_change(&event, kind) {
+                outcome.project_work = outcome.project_work.max(Some(work));
+            }
+        }
+        outcome
+    }
+
+    /// Re-read every tracked source from disk, for changes a file watcher
+    /// missed.
+    ///
+    /// Callers must follow this with a full project reload: rescanned Python
+    /// sources do not stale intrinsic readiness on their own.
+    pub(crate) fn rescan_sources(&mut self) {
+        SourceChanges::new([ChangeEvent::Rescan]).apply(&mut self.db);
+    }
+
+    /// Replace the database with a fresh one, dropping every memoized query
+    /// result.
+    ///
+    /// Open documents survive in the workspace overlay. The project keeps its
+    /// root and client settings, and readiness waits for the next full reload.
+    pub(crate) fn reset_database(&mut self) {
+        let project_root = self
+            .db
+            .project()
+            .map(|project| project.root(&self.db).clone());
+        self.db = DjangoDatabase::new(
+            self.workspace.overlay(),
+            self.client_info.config_overrides(),
+            project_root.as_deref(),
+        );
+        self.db
+            .set_plugins_allowed(self.client_info.allow_plugins());
+        self.mark_project_changed();
+    }
+
     pub(crate) fn db(&self) -> &DjangoDatabase {
         &self.db
     }
@@ -673,6 +855,9 @@ impl Session {
             return DocumentMutation::Ignored;
         };
         SourceChanges::new([change.clone()]).apply(&mut self.db);
+        // Closing is already a write, so it is a cheap point to trim LRU-capped memos
+        // even when the close changes no input.
+        self.db.collect_garbage();
         let project_work = self.mark_intrinsic_change(&change, document.kind());
 
         DocumentMutation::Applied {
@@ -758,6 +943,19 @@ impl SessionSnapshot {
         self.intrinsic_generation
     }
 
+    /// Paths of every template the project's loaders can find, sorted and
+    /// without duplicates.
+    pub(crate) fn workspace_template_paths(&self) -> Vec<Utf8PathBuf> {
+        let Some(project) = self.db.project() else {
+            return Vec::new();
+        };
+        let paths: BTreeSet<Utf8PathBuf> = template_resolution(&self.db, project)
+            .origins(&self.db)
+            .map(|origin| origin.path_buf(&self.db).clone())
+            .collect();
+        paths.into_iter().collect()
+    }
+
     /// Resolve an LSP document request to the tracked file for that URI.
     ///
     /// Open editor buffers are exposed to Salsa through the workspace overlay,
@@ -1006,6 +1204,44 @@ mod tests {
         assert_eq!(content, "updated");
     }
 
+    #[test]
+    fn reset_database_keeps_open_documents_and_the_project_root() {
+        let mut session = Session::default();
+        let project_root = session
+            .db()
+            .project()
+            .map(|project| project.root(session.db()).clone());
+        let (path, uri) = test_file_uri("reset.html");
+        match session.open_document(&ls_types::TextDocumentItem {
+            uri,
+            language_id: "django-html".to_string(),
+            version: 1,
+            text: "{% block content %}{% endblock %}".to_string(),
+        }) {
+            DocumentMutation::Applied { .. } => Some(()),
+            DocumentMutation::Ignored => None,
+        }
+        .expect("template test document should open");
+        let generation = session.desired_generation();
+
+        session.reset_database();
+
+        let db = session.db();
+        assert_eq!(
+            db.project().map(|project| project.root(db).clone()),
+            project_root
+        );
+        let file = path_to_file(db, &path).expect("open buffer should be visible to the overlay");
+        let content = file
+            .try_source(db)
+            .expect("open buffer should be readable")
+            .to_string();
+        assert_eq!(content, "{% block content %}{% endblock %}");
+        if project_root.is_some() {
+            assert!(session.desired_generation() > generation);
+        }
+    }
+
     #[test]
     fn document_mutations_return_work_when_they_stale_readiness() {
         let mut session = Session::default();
@@ -1189,6 +1425,24 @@ mod tests {
         assert_eq!(session.snapshot().intrinsic_generation(), Some(1));
     }
 
+    #[test]
+    fn first_template_inventory_is_a_baseline_and_unchanged_inventories_are_silent() {
+        let mut session = Session::default();
+        let primed =
+            prime_template_library_products(session.db()).expect("default session has a Project");
+
+        assert_eq!(
+            session.record_template_inventory(primed.inventory()),
+            TemplateInventoryChange {
+                diff: None,
+                impact: TemplateInventoryImpact::Everything,
+            }
+        );
+        let unchanged = session.record_template_inventory(primed.inventory());
+        assert_eq!(unchanged.diff, None);
+        assert!(unchanged.impact.is_empty());
+    }
+
     #[test]
     fn python_edits_before_coverage_are_conservative_but_template_edits_never_stale() {
         let mut session = Session::default();
@@ -1755,4 +2009,105 @@ pythonpath = ["{config_extra_path}"]
             .collect();
         assert_eq!(search_paths, vec![root]);
     }
+
+    #[test]
+    fn watched_python_changes_skip_open_documents_and_other_files() {
+        let mut session = Session::default();
+        let open_path = Utf8Path::new("/tmp/templatetags/open_tags.py");
+        match session.open_document(&ls_types::TextDocumentItem {
+            uri: ls_types::Uri::from_file_path(open_path.as_std_path())
+                .expect("open Python path should convert to a file URI"),
+            language_id: "python".to_string(),
+            version: 1,
+            text: String::new(),
+        }) {
+            DocumentMutation::Applied { .. } => Some(()),
+            DocumentMutation::Ignored => None,
+        }
+        .expect("open Python test document should open");
+
+        let event = |path: &str, typ| ls_types::FileEvent {
+            uri: ls_types::Uri::from_file_path(Utf8Path::new(path).as_std_path())
+                .expect("test path should convert to a file URI"),
+            typ,
+        };
+
+        assert_eq!(
+            session.apply_watched_file_changes(&[
+                event("/tmp/static/app.js", ls_types::FileChangeType::CHANGED),
+                event(open_path.as_str(), ls_types::FileChangeType::CHANGED),
+            ]),
+            WatchedFileChanges::default()
+        );
+        assert_eq!(
+            session
+                .apply_watched_file_changes(&[event(
+                    "/tmp/templatetags/closed_tags.py",
+                    ls_types::FileChangeType::CHANGED
+                )])
+                .project_work,
+            Some(ProjectWork::FullReload)
+        );
+    }
+
+    #[test]
+    fn watched_template_changes_refresh_closed_templates() {
+        let tempdir = tempdir().expect("temporary template directory should be created");
+        let root = Utf8PathBuf::from_path_buf(tempdir.path().to_path_buf())
+            .expect("temporary template path should be valid UTF-8");
+        let path = root.join("base.html");
+        std::fs::write(path.as_std_path(), "{% block content %}{% endblock %}")
+            .expect("template fixture should be written");
+
+        let mut session = Session::default();
+        let file = djls_source::path_to_file(session.db(), &path)
+            .expect("template fixture should be tracked");
+        let revision = file.revision(session.db());
+
+        std::fs::write(path.as_std_path(), "{% block body %}{% endblock %}")
+            .expect("template fixture should be rewritten");
+        let changes = session.apply_watched_file_changes(&[ls_types::FileEvent {
+            uri: ls_types::Uri::from_file_path(path.as_std_path())
+                .expect("template path should convert to a file URI"),
+            typ: ls_types::FileChangeType::CHANGED,
+        }]);
+
+        assert_eq!(
+            changes,
+            WatchedFileChanges {
+                project_work: None,
+                templates_changed: true,
+            }
+        );
+        assert!(file.revision(session.db()) > revision);
+        assert_eq!(
+            file.try_source(session.db())
+                .expect("rewritten template should be readable")
+                .as_str(),
+            "{% block body %}{% endblock %}"
+        );
+    }
+
+    #[test]
+    fn project_config_files_are_recognized_only_at_the_project_root() {
+        let tempdir = tempdir().expect("temporary project directory should be created");
+        let root = Utf8PathBuf::from_path_buf(tempdir.path().to_path_buf())
+            .expect("temporary project path should be valid UTF-8");
+        let params = ls_types::InitializeParams {
+            workspace_folders: Some(vec![ls_types::WorkspaceFolder {
+                uri: ls_types::Uri::from_file_path(root.as_std_path())
+                    .expect("project root should convert to a file URI"),
+                name: "test_project".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        let session = Session::new(&params);
+
+        assert!(session.is_project_config_file(&root.join("djls.toml")));
+        assert!(session.is_project_config_file(&root.join(".djls.toml")));
+        assert!(session.is_project_config_file(&root.join("pyproject.toml")));
+        assert!(!session.is_project_config_file(&root.join("nested/pyproject.toml")));
+        assert!(!session.is_project_config_file(&root.join("settings.py")));
+    }
 }
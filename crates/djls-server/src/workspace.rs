@@ -276,6 +276,13 @@ impl FileSystem for OverlayFileSystem {
         self.disk.read_to_string(path)
     }
 
+    fn read_bytes(&self, path: &Utf8Path) -> io::Result<Vec<u8>> {
+        if let Some(document) = self.buffers.get(path) {
+            return Ok(document.content().as_bytes().to_vec());
+        }
+        self.disk.read_bytes(path)
+    }
+
     fn exists(&self, path: &Utf8Path) -> bool {
         self.buffers.contains(path) || self.is_dir(path) || self.disk.exists(path)
     }
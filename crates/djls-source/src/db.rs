@@ -14,8 +14,8 @@ pub trait Db: salsa::Database {
 
     fn file_system(&self) -> &dyn FileSystem;
 
-    fn read_file(&self, path: &Utf8Path) -> std::io::Result<String> {
-        self.file_system().read_to_string(path)
+    fn read_file(&self, path: &Utf8Path) -> std::io::Result<Vec<u8>> {
+        self.file_system().read_bytes(path)
     }
 
     fn path_is_file(&self, path: &Utf8Path) -> bool {
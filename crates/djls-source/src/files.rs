@@ -13,6 +13,7 @@ use salsa::Setter;
 use crate::collections::FxDashMap;
 use crate::db::Db;
 use crate::line::LineIndex;
+use crate::line::PositionMapper;
 use crate::position::LineCol;
 use crate::protocol::PositionEncoding;
 
@@ -149,7 +150,7 @@ impl File {
             .unwrap_or_else(|_| SourceText::new(self.path(db), String::new()))
     }
 
-    #[salsa::tracked(returns(ref))]
+    #[salsa::tracked(returns(ref), lru = 512)]
     pub fn line_index(self, db: &dyn Db) -> LineIndex {
         let text = self.source_or_empty(db);
         LineIndex::from(text.as_str())
@@ -158,8 +159,7 @@ impl File {
     #[must_use]
     pub fn end_line_col(self, db: &dyn Db, encoding: PositionEncoding) -> LineCol {
         let source = self.source_or_empty(db);
-        let line_index = self.line_index(db);
-        line_index.end_line_col(source.as_str(), encoding)
+        PositionMapper::new(source.as_str(), self.line_index(db), encoding).end()
     }
 
     pub(crate) fn sync(self, db: &mut dyn Db) {
@@ -177,10 +177,37 @@ impl File {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceText(Arc<SourceTextInner>);
 
+/// The UTF-8 encoding of U+FEFF, which some editors write at the start of a file.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 impl SourceText {
     #[must_use]
     fn new(path: &Utf8Path, source: String) -> Self {
         let encoding = FileEncoding::from(source.as_str());
+        Self::with_encoding(path, source, encoding)
+    }
+
+    /// Decode a file's bytes, dropping a leading byte order mark.
+    ///
+    /// Bytes that are not valid UTF-8 are replaced with U+FFFD rather than
+    /// failing the read, and [`first_invalid_byte`](Self::first_invalid_byte)
+    /// records where the first replacement is.
+    #[must_use]
+    fn decode(path: &Utf8Path, mut bytes: Vec<u8>) -> Self {
+        if bytes.starts_with(UTF8_BOM) {
+            bytes.drain(..UTF8_BOM.len());
+        }
+        match String::from_utf8(bytes) {
+            Ok(source) => Self::new(path, source),
+            Err(error) => {
+                let offset = error.utf8_error().valid_up_to();
+                let source = String::from_utf8_lossy(error.as_bytes()).into_owned();
+                Self::with_encoding(path, source, FileEncoding::Lossy { offset })
+            }
+        }
+    }
+
+    fn with_encoding(path: &Utf8Path, source: String, encoding: FileEncoding) -> Self {
         let kind = FileKind::from(path);
         Self(Arc::new(SourceTextInner {
             encoding,
@@ -189,6 +216,16 @@ impl SourceText {
         }))
     }
 
+    /// Byte offset of the first U+FFFD substituted for bytes that were not
+    /// valid UTF-8, if the file had any.
+    #[must_use]
+    pub fn first_invalid_byte(&self) -> Option<usize> {
+        match self.0.encoding {
+            FileEncoding::Lossy { offset } => Some(offset),
+            FileEncoding::Ascii | FileEncoding::Utf8 => None,
+        }
+    }
+
     #[must_use]
     pub fn kind(&self) -> &FileKind {
         &self.0.kind
@@ -210,6 +247,13 @@ impl Default for SourceText {
     }
 }
 
+/// Text that was not read from a file, such as a template string parsed directly.
+impl From<&str> for SourceText {
+    fn from(source: &str) -> Self {
+        Self::new(Utf8Path::new(""), source.to_string())
+    }
+}
+
 impl AsRef<str> for SourceText {
     fn as_ref(&self) -> &str {
         self.as_str()
@@ -235,6 +279,8 @@ struct SourceTextInner {
 pub(crate) enum FileEncoding {
     Ascii,
     Utf8,
+    /// Decoded with invalid UTF-8 replaced, the first replacement at `offset`.
+    Lossy { offset: usize },
 }
 
 impl From<&str> for FileEncoding {
@@ -343,6 +389,25 @@ impl SourceFiles {
             .file
     }
 
+    /// Number of files tracked so far.
+    #[must_use]
+    pub fn file_count(&self) -> usize {
+        self.0.by_path.len()
+    }
+
+    /// Total bytes of source text held for tracked files.
+    #[must_use]
+    pub fn source_bytes(&self) -> usize {
+        self.0
+            .by_path
+            .iter()
+            .filter_map(|entry| {
+                let source = entry.value().source.as_ref().ok()?;
+                Some(source.as_str().len())
+            })
+            .sum()
+    }
+
     #[must_use]
     pub fn try_file(&self, path: &Utf8Path) -> Option<File> {
         self.0.by_path.get(path).map(|entry| entry.value().file)
@@ -499,7 +564,7 @@ pub(crate) fn sync_known_paths(db: &mut dyn Db) {
 
 fn read_source(db: &dyn Db, path: &Utf8Path) -> Result<SourceText, FileReadError> {
     db.read_file(path)
-        .map(|source| SourceText::new(path, source))
+        .map(|bytes| SourceText::decode(path, bytes))
         .map_err(|error| FileReadError::new(path.to_owned(), error.kind()))
 }
 
@@ -525,3 +590,24 @@ fn sync_file(db: &mut dyn Db, path: &Utf8Path) {
     }
     db.bump_file_revision(file);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoding_drops_a_byte_order_mark() {
+        let source = SourceText::decode(Utf8Path::new("a.html"), b"\xEF\xBB\xBF{{ x }}".to_vec());
+
+        assert_eq!(source.as_str(), "{{ x }}");
+        assert_eq!(source.first_invalid_byte(), None);
+    }
+
+    #[test]
+    fn decoding_replaces_invalid_utf8() {
+        let source = SourceText::decode(Utf8Path::new("a.html"), b"caf\xE9 {{ x }}".to_vec());
+
+        assert_eq!(source.as_str(), "caf\u{FFFD} {{ x }}");
+        assert_eq!(source.first_invalid_byte(), Some(3));
+    }
+}
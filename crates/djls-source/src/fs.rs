@@ -11,11 +11,14 @@
 
 use std::io;
 use std::sync::Mutex;
+#[cfg(feature = "os")]
 use std::sync::OnceLock;
 
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+#[cfg(feature = "os")]
 use ignore::WalkBuilder;
+#[cfg(feature = "os")]
 use ignore::overrides::OverrideBuilder;
 use rustc_hash::FxHashMap;
 
@@ -172,6 +175,13 @@ pub enum RootWalk {
 pub trait FileSystem: Send + Sync {
     /// Read a UTF-8 text file from this filesystem view.
     fn read_to_string(&self, path: &Utf8Path) -> io::Result<String>;
+    /// Read a file's raw bytes from this filesystem view.
+    ///
+    /// Views that only hold text can rely on the default, which returns the
+    /// bytes of [`read_to_string`](Self::read_to_string).
+    fn read_bytes(&self, path: &Utf8Path) -> io::Result<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
     /// Return whether a path exists as a file or directory.
     fn exists(&self, path: &Utf8Path) -> bool;
     /// Return whether a path is a regular file.
@@ -197,6 +207,12 @@ where
             .read_to_string(path)
     }
 
+    fn read_bytes(&self, path: &Utf8Path) -> io::Result<Vec<u8>> {
+        self.lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .read_bytes(path)
+    }
+
     fn exists(&self, path: &Utf8Path) -> bool {
         self.lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner)
@@ -480,11 +496,13 @@ impl FileSystem for InMemoryFileSystem {
 }
 
 /// Standard filesystem implementation that uses `std::fs` and the `ignore` crate.
+#[cfg(feature = "os")]
 #[derive(Default)]
 pub struct OsFileSystem {
     case_sensitivity: OnceLock<CaseSensitivity>,
 }
 
+#[cfg(feature = "os")]
 impl OsFileSystem {
     fn path_exists_case_sensitive_fast(path: &Utf8Path, prefix: &Utf8Path) -> Option<bool> {
         let Ok(canonicalized) = path.as_std_path().canonicalize() else {
@@ -527,6 +545,7 @@ impl OsFileSystem {
     }
 }
 
+#[cfg(feature = "os")]
 fn canonicalized_path_matches_requested_suffix(
     canonicalized: &Utf8Path,
     path: &Utf8Path,
@@ -548,6 +567,7 @@ fn canonicalized_path_matches_requested_suffix(
     )
 }
 
+#[cfg(feature = "os")]
 fn simplify_verbatim_prefix(path: &Utf8Path) -> &Utf8Path {
     if cfg!(windows) && path.as_str().starts_with(r"\\?\") {
         Utf8Path::new(&path.as_str()[r"\\?\".len()..])
@@ -556,11 +576,16 @@ fn simplify_verbatim_prefix(path: &Utf8Path) -> &Utf8Path {
     }
 }
 
+#[cfg(feature = "os")]
 impl FileSystem for OsFileSystem {
     fn read_to_string(&self, path: &Utf8Path) -> io::Result<String> {
         std::fs::read_to_string(path)
     }
 
+    fn read_bytes(&self, path: &Utf8Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
     fn exists(&self, path: &Utf8Path) -> bool {
         path.exists()
     }
@@ -858,6 +883,7 @@ mod tests {
         assert_eq!(relatives, vec!["visible.html"]);
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn canonicalized_compare_ignores_prefix_casing_mismatch() {
         assert_eq!(
@@ -870,6 +896,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn canonicalized_compare_rejects_suffix_casing_mismatch() {
         assert_eq!(
@@ -882,6 +909,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn canonicalized_compare_defers_non_casing_difference() {
         assert_eq!(
@@ -894,11 +922,13 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "os")]
     fn temp_path(dir: &tempfile::TempDir) -> Utf8PathBuf {
         Utf8PathBuf::from_path_buf(dir.path().to_path_buf())
             .expect("temporary directory path should be valid UTF-8")
     }
 
+    #[cfg(feature = "os")]
     fn entry_names(entries: &[WalkEntry]) -> Vec<&str> {
         entries
             .iter()
@@ -924,6 +954,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_skips_hidden_directories_by_default() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -943,6 +974,7 @@ mod tests {
         assert!(!names.contains(&"secret.html"));
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_can_include_hidden_directories() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -964,6 +996,7 @@ mod tests {
         assert!(names.contains(&"secret.html"));
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_respects_gitignore() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -990,6 +1023,7 @@ mod tests {
         assert!(!names.contains(&"skip.html"));
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_no_ignore_disables_gitignore() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -1018,6 +1052,7 @@ mod tests {
         assert!(names.contains(&"found.html"));
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_applies_glob_overrides() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -1040,6 +1075,7 @@ mod tests {
         assert!(!names.contains(&"style.css"));
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_limits_max_depth() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
@@ -1061,6 +1097,7 @@ mod tests {
         assert!(!names.contains(&"deep.html"));
     }
 
+    #[cfg(feature = "os")]
     #[cfg(unix)]
     #[test]
     fn os_detailed_walk_retains_entries_when_another_entry_errors() {
@@ -1092,6 +1129,7 @@ mod tests {
         assert!(!issues.is_empty());
     }
 
+    #[cfg(feature = "os")]
     #[test]
     fn os_walk_single_file_root_uses_file_name_as_relative_path() {
         let dir = tempfile::tempdir().expect("temporary directory should be created");
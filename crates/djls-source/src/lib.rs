@@ -28,6 +28,7 @@ pub use files::path_to_file;
 pub use fs::CaseSensitivity;
 pub use fs::FileSystem;
 pub use fs::InMemoryFileSystem;
+#[cfg(feature = "os")]
 pub use fs::OsFileSystem;
 pub use fs::RootWalk;
 pub use fs::WalkEntry;
@@ -35,6 +36,7 @@ pub use fs::WalkEntryKind;
 pub use fs::WalkOptions;
 pub use line::LineEnding;
 pub use line::LineIndex;
+pub use line::PositionMapper;
 pub use line::SourceLine;
 pub use origin::Origin;
 pub use path::SafeJoinError;
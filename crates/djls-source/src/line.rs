@@ -149,25 +149,6 @@ impl LineIndex {
         })
     }
 
-    // TODO(source-api): Revisit methods that take both `LineIndex` and source text.
-    // The index is derived from that text, so public callers should usually go through `File`.
-    #[must_use]
-    pub(crate) fn end_line_col(&self, text: &str, encoding: PositionEncoding) -> LineCol {
-        let line = u32::try_from(self.lines().len().saturating_sub(1)).unwrap_or_default();
-        let line_start = self.lines().last().copied().unwrap_or_default() as usize;
-        let line_text = &text[line_start.min(text.len())..];
-        let column = match encoding {
-            PositionEncoding::Utf8 => u32::try_from(line_text.len()).unwrap_or(u32::MAX),
-            PositionEncoding::Utf16 => line_text
-                .chars()
-                .map(|character| u32::try_from(character.len_utf16()).unwrap_or_default())
-                .sum(),
-            PositionEncoding::Utf32 => u32::try_from(line_text.chars().count()).unwrap_or(u32::MAX),
-        };
-
-        LineCol::new(line, column)
-    }
-
     #[must_use]
     pub fn to_line_col(&self, offset: Offset) -> LineCol {
         if self.lines().is_empty() {
@@ -185,84 +166,84 @@ impl LineIndex {
 
         LineCol::new(u32::try_from(line).unwrap_or_default(), column)
     }
+}
 
-    // TODO(source-api): Revisit methods that take both `LineIndex` and source text.
-    // The index is derived from that text, so public callers should usually go through `File`.
-    #[must_use]
-    pub fn offset(&self, text: &str, line_col: LineCol, encoding: PositionEncoding) -> Offset {
-        let line = line_col.line();
-        let character = line_col.column();
-
-        // Handle line bounds - if line > line_count, return document length
-        let line_start_utf8 = match self.lines().get(line as usize) {
-            Some(start) => *start,
-            None => return Offset::new(u32::try_from(text.len()).unwrap_or(u32::MAX)),
-        };
+/// Converts between byte offsets and encoded line/column positions in one text.
+///
+/// Columns count code units of the mapper's [`PositionEncoding`]. Conversions
+/// clamp instead of failing: a line past the end resolves to the end of the
+/// text, a column past the end of a line resolves to the end of that line's
+/// content (before its line ending), and an offset inside a character or line
+/// ending resolves to the position before it.
+#[derive(Clone, Copy, Debug)]
+pub struct PositionMapper<'text> {
+    text: &'text str,
+    index: &'text LineIndex,
+    encoding: PositionEncoding,
+}
 
-        if character == 0 {
-            return Offset::new(line_start_utf8);
+impl<'text> PositionMapper<'text> {
+    /// `index` must be the [`LineIndex`] built from `text`.
+    #[must_use]
+    pub fn new(text: &'text str, index: &'text LineIndex, encoding: PositionEncoding) -> Self {
+        Self {
+            text,
+            index,
+            encoding,
         }
+    }
 
-        let next_line_start = self
-            .lines()
-            .get(line as usize + 1)
-            .copied()
-            .unwrap_or_else(|| u32::try_from(text.len()).unwrap_or(u32::MAX));
-
-        let line_start_usize = (line_start_utf8 as usize).min(text.len());
-        let next_line_start_usize = (next_line_start as usize).min(text.len());
+    #[must_use]
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
 
-        // Ensure valid range (start <= end)
-        if line_start_usize > next_line_start_usize {
-            // Corrupt line index - return clamped offset
-            return Offset::new(u32::try_from(text.len()).unwrap_or(u32::MAX));
-        }
+    #[must_use]
+    pub fn line_col(&self, offset: Offset) -> LineCol {
+        let Some(line) = self.index.line_at_offset(self.text, offset) else {
+            return self.index.to_line_col(offset);
+        };
+        let prefix = &line.text()[..line.byte_offset(offset)];
+        LineCol::new(line.line(), self.encoding.column_width(prefix))
+    }
 
-        let line_text = &text[line_start_usize..next_line_start_usize];
+    #[must_use]
+    pub fn offset(&self, line_col: LineCol) -> Offset {
+        let Some(line_start) = self.index.line_start(line_col.line()) else {
+            return self.end_offset();
+        };
+        let Some(line) = self.index.line_at_offset(self.text, Offset::new(line_start)) else {
+            return self.end_offset();
+        };
+        let text = line.text();
+        let column = line_col.column();
 
-        // Fast path optimization for ASCII text, all encodings are equivalent to byte offsets
-        if line_text.is_ascii() {
-            let char_offset = character.min(u32::try_from(line_text.len()).unwrap_or(u32::MAX));
-            return Offset::new(line_start_utf8 + char_offset);
+        // Every encoding counts one unit per ASCII character.
+        if text.is_ascii() {
+            let width = column.min(u32::try_from(text.len()).unwrap_or(u32::MAX));
+            return Offset::new(line_start.saturating_add(width));
         }
 
-        match encoding {
-            PositionEncoding::Utf8 => {
-                // UTF-8: character positions are already byte offsets
-                let char_offset = character.min(u32::try_from(line_text.len()).unwrap_or(u32::MAX));
-                Offset::new(line_start_utf8 + char_offset)
-            }
-            PositionEncoding::Utf16 => {
-                // UTF-16: count UTF-16 code units
-                let mut utf16_pos = 0;
-                let mut utf8_pos = 0;
-
-                for c in line_text.chars() {
-                    if utf16_pos >= character {
-                        break;
-                    }
-                    utf16_pos += u32::try_from(c.len_utf16()).unwrap_or(0);
-                    utf8_pos += u32::try_from(c.len_utf8()).unwrap_or(0);
-                }
-
-                // If character position exceeds line length, clamp to line end
-                Offset::new(line_start_utf8 + utf8_pos)
-            }
-            PositionEncoding::Utf32 => {
-                // UTF-32: count Unicode code points (characters)
-                let mut utf8_pos = 0;
-
-                for (char_count, c) in line_text.chars().enumerate() {
-                    if char_count >= character as usize {
-                        break;
-                    }
-                    utf8_pos += u32::try_from(c.len_utf8()).unwrap_or(0);
-                }
-
-                // If character position exceeds line length, clamp to line end
-                Offset::new(line_start_utf8 + utf8_pos)
+        let mut units = 0;
+        let mut bytes = 0;
+        for character in text.chars() {
+            if units >= column {
+                break;
             }
+            units += self.encoding.char_width(character);
+            bytes += u32::try_from(character.len_utf8()).unwrap_or_default();
         }
+        Offset::new(line_start.saturating_add(bytes))
+    }
+
+    /// Position just past the last character of the text.
+    #[must_use]
+    pub fn end(&self) -> LineCol {
+        self.line_col(self.end_offset())
+    }
+
+    fn end_offset(&self) -> Offset {
+        Offset::new(u32::try_from(self.text.len()).unwrap_or(u32::MAX))
     }
 }
 
@@ -369,32 +350,83 @@ mod tests {
     }
 
     #[test]
-    fn test_end_line_col_with_trailing_newline() {
+    fn test_mapper_end_with_trailing_newline() {
         let text = "first\nsecond\n";
         let index = LineIndex::from(text);
 
         assert_eq!(
-            index.end_line_col(text, PositionEncoding::Utf16),
+            PositionMapper::new(text, &index, PositionEncoding::Utf16).end(),
             LineCol::new(2, 0),
         );
     }
 
     #[test]
-    fn test_end_line_col_uses_position_encoding() {
+    fn test_mapper_end_uses_position_encoding() {
         let text = "emoji: 🐍";
         let index = LineIndex::from(text);
 
         assert_eq!(
-            index.end_line_col(text, PositionEncoding::Utf8),
+            PositionMapper::new(text, &index, PositionEncoding::Utf8).end(),
             LineCol::new(0, 11),
         );
         assert_eq!(
-            index.end_line_col(text, PositionEncoding::Utf16),
+            PositionMapper::new(text, &index, PositionEncoding::Utf16).end(),
             LineCol::new(0, 9),
         );
         assert_eq!(
-            index.end_line_col(text, PositionEncoding::Utf32),
+            PositionMapper::new(text, &index, PositionEncoding::Utf32).end(),
             LineCol::new(0, 8),
         );
     }
+
+    #[test]
+    fn test_mapper_round_trips_non_ascii_columns() {
+        let text = "a🐍b\né";
+        let index = LineIndex::from(text);
+
+        for (encoding, column) in [
+            (PositionEncoding::Utf8, 5),
+            (PositionEncoding::Utf16, 3),
+            (PositionEncoding::Utf32, 2),
+        ] {
+            let mapper = PositionMapper::new(text, &index, encoding);
+            assert_eq!(mapper.line_col(Offset::new(5)), LineCol::new(0, column));
+            assert_eq!(mapper.offset(LineCol::new(0, column)), Offset::new(5));
+        }
+    }
+
+    #[test]
+    fn test_mapper_round_trips_columns_after_crlf() {
+        let text = "{% if %}\r\né{{ x }}\r\n";
+        let index = LineIndex::from(text);
+        let variable = Offset::new(12);
+
+        for (encoding, column) in [
+            (PositionEncoding::Utf8, 2),
+            (PositionEncoding::Utf16, 1),
+            (PositionEncoding::Utf32, 1),
+        ] {
+            let mapper = PositionMapper::new(text, &index, encoding);
+            assert_eq!(mapper.line_col(variable), LineCol::new(1, column));
+            assert_eq!(mapper.offset(LineCol::new(1, column)), variable);
+        }
+        assert_eq!(
+            PositionMapper::new(text, &index, PositionEncoding::Utf16).end(),
+            LineCol::new(2, 0),
+        );
+    }
+
+    #[test]
+    fn test_mapper_clamps_out_of_range_positions() {
+        let text = "ab\r\ncd";
+        let index = LineIndex::from(text);
+        let mapper = PositionMapper::new(text, &index, PositionEncoding::Utf16);
+
+        // Columns stop before the line ending, lines past the end at the text end.
+        assert_eq!(mapper.offset(LineCol::new(0, 10)), Offset::new(2));
+        assert_eq!(mapper.offset(LineCol::new(5, 0)), Offset::new(6));
+        // Offsets inside the line ending or past the text clamp back.
+        assert_eq!(mapper.line_col(Offset::new(3)), LineCol::new(0, 2));
+        assert_eq!(mapper.line_col(Offset::new(40)), LineCol::new(1, 2));
+    }
 }
@@ -1,3 +1,4 @@
+use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
 
@@ -128,7 +129,7 @@ impl Range {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Span {
     start: u32,
     length: u32,
@@ -24,6 +24,31 @@ pub enum PositionEncoding {
     Utf32,
 }
 
+impl PositionEncoding {
+    /// Code units `character` occupies in this encoding.
+    #[must_use]
+    pub(crate) fn char_width(self, character: char) -> u32 {
+        let width = match self {
+            Self::Utf8 => character.len_utf8(),
+            Self::Utf16 => character.len_utf16(),
+            Self::Utf32 => 1,
+        };
+        u32::try_from(width).unwrap_or_default()
+    }
+
+    /// Code units `text` occupies in this encoding.
+    #[must_use]
+    pub(crate) fn column_width(self, text: &str) -> u32 {
+        match self {
+            Self::Utf8 => u32::try_from(text.len()).unwrap_or(u32::MAX),
+            Self::Utf16 | Self::Utf32 => text
+                .chars()
+                .map(|character| self.char_width(character))
+                .sum(),
+        }
+    }
+}
+
 impl fmt::Display for PositionEncoding {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
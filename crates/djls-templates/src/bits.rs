@@ -1,28 +1,84 @@
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use djls_source::SourceText;
 use djls_source::Span;
 use serde::Serialize;
+use serde::Serializer;
 
+use crate::expression::Operand;
 use crate::quotes::TemplateString;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct TagBit {
-    pub(crate) text: String,
+    text: BitText,
     pub span: Span,
 }
 
 impl TagBit {
     #[must_use]
     pub fn new(text: String, span: Span) -> Self {
+        Self {
+            text: BitText::Owned(text.into_boxed_str()),
+            span,
+        }
+    }
+
+    /// A parsed bit whose text is `source[span]`, sharing `source` instead of copying it.
+    ///
+    /// Falls back to an owned copy of `text` if the span does not cover exactly that text.
+    #[must_use]
+    pub(crate) fn in_source(source: &SourceText, text: &str, span: Span) -> Self {
+        let (start, end) = (span.start_usize(), span.end_usize());
+        let text = match (source.get(start..end), u32::try_from(end)) {
+            (Some(slice), Ok(end)) if slice == text => BitText::Source {
+                source: source.clone(),
+                start: span.start(),
+                end,
+            },
+            _ => BitText::Owned(text.into()),
+        };
         Self { text, span }
     }
 
     #[must_use]
     pub fn as_str(&self) -> &str {
-        &self.text
+        self.text.as_str()
     }
 
     #[must_use]
     pub fn template_string(&self) -> TemplateString<'_> {
-        TemplateString::parse(&self.text, self.span)
+        TemplateString::parse(self.as_str(), self.span)
+    }
+
+    /// This bit as a filter expression operand, such as a quoted or `_()`
+    /// translated string, a number, or a lookup path.
+    #[must_use]
+    pub fn operand(&self) -> Operand {
+        Operand::parse(self.as_str(), self.span)
+    }
+
+    /// Split a `name=value` bit the way Django's `token_kwargs` does.
+    ///
+    /// The name must be a word (letters, digits, underscores) and the value
+    /// non-empty, so quoted strings and `_("...")` markers are never kwargs.
+    #[must_use]
+    pub fn kwarg(&self) -> Option<TagKwarg<'_>> {
+        let (name, value) = self.as_str().split_once('=')?;
+        if name.is_empty()
+            || value.is_empty()
+            || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            return None;
+        }
+        let start = self.span.start_usize();
+        Some(TagKwarg {
+            name,
+            name_span: Span::saturating_from_parts_usize(start, name.len()),
+            value,
+            value_span: Span::saturating_from_parts_usize(start + name.len() + 1, value.len()),
+        })
     }
 }
 
@@ -32,22 +88,107 @@ impl AsRef<str> for TagBit {
     }
 }
 
+/// The text of a [`TagBit`].
+///
+/// Bits from the parser borrow a range of the [`SourceText`] they were parsed from, so a tag's
+/// arguments cost no allocation each. Bits built elsewhere own their text. Equality, hashing,
+/// and serialization see only the text, so both forms of the same bit compare equal.
+///
+/// `SourceText` is a thin pointer and the range is two `u32`s, as in [`Span`], so the shared
+/// form is the same width as a `Box<str>`.
+#[derive(Clone)]
+enum BitText {
+    Owned(Box<str>),
+    Source {
+        source: SourceText,
+        start: u32,
+        end: u32,
+    },
+}
+
+impl BitText {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Owned(text) => text,
+            Self::Source { source, start, end } => &source[*start as usize..*end as usize],
+        }
+    }
+}
+
+impl PartialEq for BitText {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BitText {}
+
+impl Hash for BitText {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for BitText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Serialize for BitText {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A `name=value` tag argument, with spans for both halves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TagKwarg<'a> {
+    pub name: &'a str,
+    pub name_span: Span,
+    pub value: &'a str,
+    pub value_span: Span,
+}
+
+impl TagKwarg<'_> {
+    #[must_use]
+    pub fn operand(&self) -> Operand {
+        Operand::parse(self.value, self.value_span)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct FilterArgument {
     pub(crate) text: String,
-    pub(crate) span: Span,
+    pub span: Span,
+    expression: Operand,
 }
 
 impl FilterArgument {
     #[must_use]
     pub(crate) fn new(text: String, span: Span) -> Self {
-        Self { text, span }
+        let expression = Operand::parse(&text, span);
+        Self {
+            text,
+            span,
+            expression,
+        }
+    }
+
+    #[must_use]
+    pub fn expression(&self) -> &Operand {
+        &self.expression
     }
 
     #[must_use]
     pub(crate) fn as_str(&self) -> &str {
         &self.text
     }
+
+    #[must_use]
+    pub fn template_string(&self) -> TemplateString<'_> {
+        TemplateString::parse(&self.text, self.span)
+    }
 }
 
 impl AsRef<str> for FilterArgument {
@@ -55,3 +196,59 @@ impl AsRef<str> for FilterArgument {
         self.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::OperandKind;
+
+    fn bit(text: &str) -> TagBit {
+        TagBit::new(
+            text.to_string(),
+            Span::saturating_from_parts_usize(20, text.len()),
+        )
+    }
+
+    #[test]
+    fn kwarg_spans_cover_name_and_value() {
+        let bit = bit("title='A B'");
+        let kwarg = bit.kwarg().expect("kwarg should parse");
+
+        assert_eq!(kwarg.name, "title");
+        assert_eq!(kwarg.name_span, Span::new(20, 5));
+        assert_eq!(kwarg.value, "'A B'");
+        assert_eq!(kwarg.value_span, Span::new(26, 5));
+        assert!(matches!(
+            kwarg.operand().kind,
+            OperandKind::String { value, .. } if value == "A B"
+        ));
+    }
+
+    #[test]
+    fn source_backed_bits_equal_owned_bits_with_the_same_text() {
+        let source = SourceText::from("{% include 'card.html' %}");
+        let span = Span::saturating_from_parts_usize(11, 11);
+        let shared = TagBit::in_source(&source, "'card.html'", span);
+
+        assert_eq!(shared.as_str(), "'card.html'");
+        assert_eq!(shared, TagBit::new("'card.html'".to_string(), span));
+        assert!(matches!(shared.text, BitText::Source { .. }));
+        assert!(matches!(
+            TagBit::in_source(&source, "other", span).text,
+            BitText::Owned(_)
+        ));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn bit_text_is_three_words() {
+        assert_eq!(size_of::<BitText>(), 24);
+    }
+
+    #[test]
+    fn quoted_and_translated_bits_are_not_kwargs() {
+        for text in ["'a=b'", r#"_("a=b")"#, "=value", "name=", "a.b=c"] {
+            assert_eq!(bit(text).kwarg(), None, "{text}");
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! `<script>` and `<style>` regions embedded in template text.
+//!
+//! Each region carries a virtual copy of the element's content in which every
+//! Django tag, variable, and comment is blanked out with spaces. Byte offsets
+//! and line breaks are preserved, so positions in the virtual content map
+//! one-to-one onto the template and it can be handed to a JavaScript or CSS
+//! tool as is.
+
+use djls_source::Span;
+
+use crate::tokens::Token;
+
+/// The language of an embedded region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedLanguage {
+    JavaScript,
+    /// `<script type="application/json">` and other JSON data blocks.
+    Json,
+    Css,
+}
+
+impl EmbeddedLanguage {
+    /// The LSP language identifier for this language.
+    #[must_use]
+    pub fn language_id(self) -> &'static str {
+        match self {
+            Self::JavaScript => "javascript",
+            Self::Json => "json",
+            Self::Css => "css",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedRegion {
+    pub language: EmbeddedLanguage,
+    /// The element's content, between its opening and closing tags. An
+    /// unclosed element runs to the end of the file.
+    pub span: Span,
+    /// The content with Django constructs replaced by spaces.
+    pub content: String,
+}
+
+impl EmbeddedRegion {
+    /// Whether `span` lies entirely inside this region.
+    #[must_use]
+    pub fn contains_span(&self, span: Span) -> bool {
+        self.span.start() <= span.start() && span.end() <= self.span.end()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Element {
+    Script,
+    Style,
+}
+
+impl Element {
+    const ALL: [Self; 2] = [Self::Script, Self::Style];
+
+    fn name(self) -> &'static [u8] {
+        match self {
+            Self::Script => b"script",
+            Self::Style => b"style",
+        }
+    }
+
+    /// The language of the element's content given its `type` attribute, or
+    /// `None` for client-side templates and other content that is not code.
+    fn language(self, mime_type: Option<&str>) -> Option<EmbeddedLanguage> {
+        let mime_type = mime_type.map(|value| value.trim().to_ascii_lowercase());
+        match (self, mime_type.as_deref()) {
+            (
+                Self::Script,
+                None
+                | Some(
+                    "" | "module" | "text/javascript" | "application/javascript"
+                    | "text/ecmascript" | "application/ecmascript",
+                ),
+            ) => Some(EmbeddedLanguage::JavaScript),
+            (
+                Self::Script,
+                Some("application/json" | "application/ld+json" | "importmap" | "speculationrules"),
+            ) => Some(EmbeddedLanguage::Json),
+            (Self::Style, None | Some("" | "text/css")) => Some(EmbeddedLanguage::Css),
+            (Self::Script | Self::Style, Some(_)) => None,
+        }
+    }
+}
+
+/// Find the `<script>` and `<style>` regions in a lexed template.
+pub(crate) fn regions_from_tokens(source: &str, tokens: &[Token]) -> Vec<EmbeddedRegion> {
+    let masked = mask_django_constructs(source, tokens);
+    let bytes = masked.as_bytes();
+    let mut regions = Vec::new();
+    let mut position = 0;
+
+    while let Some(found) = find_byte(bytes, b'<', position) {
+        if bytes[found..].starts_with(b"<!--") {
+            position = find_ascii_case_insensitive(bytes, b"-->", found + 4)
+                .map_or(bytes.len(), |end| end + 3);
+            continue;
+        }
+
+        let Some(element) = Element::ALL
+            .into_iter()
+            .find(|element| starts_tag(bytes, found + 1, element.name()))
+        else {
+            position = found + 1;
+            continue;
+        };
+
+        let attributes_start = found + 1 + element.name().len();
+        let Some(tag_end) = opening_tag_end(bytes, attributes_start) else {
+            break;
+        };
+        let content_start = tag_end + 1;
+        let mut closer = b"</".to_vec();
+        closer.extend_from_slice(element.name());
+        let content_end =
+            find_ascii_case_insensitive(bytes, &closer, content_start).unwrap_or(bytes.len());
+
+        let mime_type = attribute_value(&masked[attributes_start..tag_end], "type");
+        if let Some(language) = element.language(mime_type) {
+            regions.push(EmbeddedRegion {
+                language,
+                span: Span::saturating_from_bounds_usize(content_start, content_end),
+                content: masked[content_start..content_end].to_string(),
+            });
+        }
+
+        position = content_end;
+    }
+
+    regions
+}
+
+/// Copy `source`, replacing every byte of a Django tag, variable, or comment
+/// with a space, except line breaks.
+fn mask_django_constructs(source: &str, tokens: &[Token]) -> String {
+    let mut bytes = source.as_bytes().to_vec();
+    for token in tokens {
+        let span = match token {
+            Token::Block { .. }
+            | Token::Comment { .. }
+            | Token::Error { .. }
+            | Token::Variable { .. } => token.full_span(),
+            Token::Eof
+            | Token::Newline { .. }
+            | Token::Text { .. }
+            | Token::Whitespace { .. } => None,
+        };
+        let Some(span) = span else {
+            continue;
+        };
+        let end = span.end_usize().min(bytes.len());
+        let start = span.start_usize().min(end);
+        for byte in &mut bytes[start..end] {
+            if !matches!(byte, b'\n' | b'\r') {
+                *byte = b' ';
+            }
+        }
+    }
+    // Only whole multi-byte sequences are replaced, and each byte becomes an
+    // ASCII space, so the result is still valid UTF-8.
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Whether an element named `name` opens at `start`, e.g. `script` followed
+/// by whitespace, `>`, or `/`.
+fn starts_tag(bytes: &[u8], start: usize, name: &[u8]) -> bool {
+    let end = start + name.len();
+    bytes
+        .get(start..end)
+        .is_some_and(|candidate| candidate.eq_ignore_ascii_case(name))
+        && bytes
+            .get(end)
+            .is_none_or(|next| next.is_ascii_whitespace() || matches!(next, b'>' | b'/'))
+}
+
+/// The position of the `>` that closes an opening tag, skipping quoted
+/// attribute values.
+fn opening_tag_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (index, byte) in bytes.iter().enumerate().skip(start) {
+        match (quote, byte) {
+            (None, b'"' | b'\'') => quote = Some(*byte),
+            (None, b'>') => return Some(index),
+            (Some(open), _) if open == *byte => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The value of attribute `name` in the text between an element's name and
+/// the `>` of its opening tag.
+fn attribute_value<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let (attribute, after_name) = rest.split_at(name_end);
+        let after_name = after_name.trim_start();
+
+        let Some(after_equals) = after_name.strip_prefix('=') else {
+            rest = after_name;
+            continue;
+        };
+        let after_equals = after_equals.trim_start();
+        let (value, after_value) = match after_equals.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = &after_equals[1..];
+                let end = inner.find(quote).unwrap_or(inner.len());
+                (&inner[..end], inner.get(end + 1..).unwrap_or_default())
+            }
+            Some(_) | None => {
+                let end = after_equals
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(after_equals.len());
+                after_equals.split_at(end)
+            }
+        };
+
+        if attribute.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+        rest = after_value;
+    }
+}
+
+fn find_byte(bytes: &[u8], needle: u8, start: usize) -> Option<usize> {
+    bytes
+        .get(start..)?
+        .iter()
+        .position(|byte| *byte == needle)
+        .map(|index| start + index)
+}
+
+fn find_ascii_case_insensitive(bytes: &[u8], needle: &[u8], start: usize) -> Option<usize> {
+    bytes
+        .get(start..)?
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+        .map(|index| start + index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_regions_impl;
+
+    #[test]
+    fn finds_script_and_style_content_with_django_constructs_blanked() {
+        let source = "<style>\n.a { color: {{ color }}; }\n</style>\
+            <SCRIPT type=\"module\">const url = \"{% url 'home' %}\";</SCRIPT>";
+        let found = embedded_regions_impl(source);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].language, EmbeddedLanguage::Css);
+        assert_eq!(found[0].content, "\n.a { color:            ; }\n");
+        assert_eq!(found[1].language, EmbeddedLanguage::JavaScript);
+        assert_eq!(found[1].content, "const url = \"                \";");
+        for region in &found {
+            assert_eq!(region.content.len(), region.span.length_usize());
+        }
+        assert_eq!(
+            &source[found[1].span.start_usize()..found[1].span.end_usize()],
+            "const url = \"{% url 'home' %}\";"
+        );
+    }
+
+    #[test]
+    fn skips_comments_templates_and_non_code_script_types() {
+        let source = "{# <script>commented()</script> #}\
+            <!-- <style>.hidden {}</style> -->\
+            <script type=\"text/x-template\"><div></div></script>\
+            <script type='application/ld+json'>{\"@type\": \"Person\"}</script>\
+            <scripts>not a script</scripts>";
+        let found = embedded_regions_impl(source);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].language, EmbeddedLanguage::Json);
+        assert_eq!(found[0].content, "{\"@type\": \"Person\"}");
+    }
+
+    #[test]
+    fn unclosed_element_runs_to_end_of_file() {
+        let found = embedded_regions_impl("<script>let x = 1;\n{% if a %}x++;{% endif %}");
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].content, "let x = 1;\n          x++;           ");
+    }
+}
@@ -13,6 +13,12 @@ pub enum TemplateError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Template is not valid UTF-8; invalid bytes are shown as U+FFFD")]
+    InvalidUtf8 {
+        /// Byte offset of the first replacement character.
+        position: usize,
+    },
 }
 
 impl TemplateError {
@@ -22,6 +28,7 @@ impl TemplateError {
             TemplateError::Parser(_) => "T100",
             TemplateError::Io(_) => "T900",
             TemplateError::Config(_) => "T901",
+            TemplateError::InvalidUtf8 { .. } => "T902",
         }
     }
 
@@ -43,6 +50,9 @@ impl TemplateError {
 
                 Some((position.try_into().ok()?, length.try_into().ok()?))
             }
+            TemplateError::InvalidUtf8 { position } => {
+                Some(((*position).try_into().ok()?, '\u{FFFD}'.len_utf8().try_into().ok()?))
+            }
             TemplateError::Io(_) | TemplateError::Config(_) => None,
         }
     }
@@ -94,6 +104,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn invalid_utf8_points_at_the_first_replacement_character() {
+        let error = TemplateError::InvalidUtf8 { position: 7 };
+
+        assert_eq!(error.diagnostic_code(), "T902");
+        assert_eq!(error.primary_span(), Some((7, 3)));
+    }
+
     #[test]
     fn malformed_construct_span_survives_parse_pipeline() {
         let source = "Hello {{ value";
@@ -0,0 +1,175 @@
+use djls_source::Span;
+use serde::Serialize;
+
+use crate::quotes::TemplateString;
+
+/// A filter expression operand: the value before the first `|`, or a filter argument.
+///
+/// Mirrors how Django's `Variable` classifies its input: numbers and quoted
+/// strings (optionally wrapped in `_()` for translation) are literals, and
+/// anything else is a dotted lookup path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Operand {
+    pub kind: OperandKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperandKind {
+    /// `"text"`, `'text'`, or `_("text")`
+    String {
+        value: String,
+        value_span: Span,
+        translated: bool,
+    },
+    /// `42`, `-1.5`
+    Number(String),
+    /// `user.profile.name`, one segment per lookup
+    Variable(Vec<PathSegment>),
+}
+
+/// One `.`-separated lookup in a variable path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct PathSegment {
+    pub name: String,
+    pub span: Span,
+}
+
+impl Operand {
+    /// Classify trimmed operand `text` located at `span`.
+    #[must_use]
+    pub fn parse(text: &str, span: Span) -> Self {
+        let kind = if let Some(inner) = text
+            .strip_prefix("_(")
+            .and_then(|inner| inner.strip_suffix(')'))
+            && let Some(kind) = string_kind(inner, span.start() + 2, true)
+        {
+            kind
+        } else if let Some(kind) = string_kind(text, span.start(), false) {
+            kind
+        } else if is_number(text) {
+            OperandKind::Number(text.to_string())
+        } else {
+            OperandKind::Variable(path_segments(text, span.start()))
+        };
+        Self { kind, span }
+    }
+
+    /// The lookup path, when this operand is a variable.
+    #[must_use]
+    pub fn path(&self) -> Option<&[PathSegment]> {
+        match &self.kind {
+            OperandKind::Variable(segments) => Some(segments),
+            OperandKind::String { .. } | OperandKind::Number(_) => None,
+        }
+    }
+}
+
+fn string_kind(text: &str, start: u32, translated: bool) -> Option<OperandKind> {
+    let span = Span::saturating_from_parts_usize(start as usize, text.len());
+    match TemplateString::parse(text, span) {
+        TemplateString::Quoted { value, span } => Some(OperandKind::String {
+            value: value.to_string(),
+            value_span: span,
+            translated,
+        }),
+        TemplateString::Unquoted(_) => None,
+    }
+}
+
+/// Whether Django's `Variable` would read `text` as a numeric literal.
+///
+/// Django tries `float()` when the text contains `.` or `e`, and `int()` otherwise.
+fn is_number(text: &str) -> bool {
+    if text.contains(['.', 'e', 'E']) {
+        !text.ends_with('.')
+            && !text.contains(|c: char| c.is_ascii_alphabetic() && !matches!(c, 'e' | 'E'))
+            && text.parse::<f64>().is_ok()
+    } else {
+        let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+fn path_segments(text: &str, start: u32) -> Vec<PathSegment> {
+    let mut offset = start as usize;
+    text.split('.')
+        .map(|name| {
+            let segment = PathSegment {
+                name: name.to_string(),
+                span: Span::saturating_from_parts_usize(offset, name.len()),
+            };
+            offset += name.len() + 1;
+            segment
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Operand {
+        Operand::parse(text, Span::saturating_from_parts_usize(10, text.len()))
+    }
+
+    #[test]
+    fn variable_paths_have_a_span_per_segment() {
+        let operand = parse("user.profile.0");
+
+        assert_eq!(
+            operand.path(),
+            Some(
+                &[
+                    PathSegment {
+                        name: "user".to_string(),
+                        span: Span::new(10, 4),
+                    },
+                    PathSegment {
+                        name: "profile".to_string(),
+                        span: Span::new(15, 7),
+                    },
+                    PathSegment {
+                        name: "0".to_string(),
+                        span: Span::new(23, 1),
+                    },
+                ][..]
+            )
+        );
+    }
+
+    #[test]
+    fn quoted_and_translated_strings_are_literals() {
+        assert_eq!(
+            parse("'hello world'").kind,
+            OperandKind::String {
+                value: "hello world".to_string(),
+                value_span: Span::new(11, 11),
+                translated: false,
+            }
+        );
+        assert_eq!(
+            parse("_(\"Save\")").kind,
+            OperandKind::String {
+                value: "Save".to_string(),
+                value_span: Span::new(13, 4),
+                translated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn numbers_follow_django_literal_detection() {
+        for text in ["42", "-1.5", "1e3", ".5"] {
+            assert_eq!(
+                parse(text).kind,
+                OperandKind::Number(text.to_string()),
+                "{text}"
+            );
+        }
+        for text in ["1.", "inf", "1e", "value", "_(value)"] {
+            assert!(parse(text).path().is_some(), "{text}");
+        }
+    }
+}
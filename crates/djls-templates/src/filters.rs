@@ -32,6 +32,17 @@ impl Filter {
     }
 }
 
+/// Render a variable and its filters as written between `{{ }}`, e.g. `value|default:'x'`.
+#[must_use]
+pub fn variable_expression(var: &str, filters: &[Filter]) -> String {
+    let mut expression = var.to_string();
+    for filter in filters {
+        expression.push('|');
+        expression.push_str(&filter.label());
+    }
+    expression
+}
+
 /// Saturating conversion from `usize` to `u32`, clamping at `u32::MAX`.
 fn usize_to_u32(val: usize) -> u32 {
     u32::try_from(val).unwrap_or(u32::MAX)
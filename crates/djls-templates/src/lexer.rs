@@ -70,7 +70,7 @@ impl Lexer {
 
         self.consume_n(TagDelimiter::LENGTH);
 
-        match self.consume_until_delimiter(delimiter.closer()) {
+        match self.consume_until_delimiter(delimiter) {
             Ok(text) => {
                 let len = text.len();
                 let span = Span::saturating_from_parts_usize(content_start, len);
@@ -167,12 +167,26 @@ impl Lexer {
         }
     }
 
-    fn consume_until_delimiter(&mut self, delimiter: &str) -> Result<String, String> {
+    /// Consume a tag's content up to its closer.
+    ///
+    /// A `{%` or `{{` before the closer, outside a string literal, means this
+    /// tag was never closed, so the closer belongs to a later tag. Stopping
+    /// there keeps the error local instead of swallowing every tag in between.
+    /// Comments may contain commented-out tags, so they only end at their own
+    /// closer.
+    fn consume_until_delimiter(&mut self, delimiter: TagDelimiter) -> Result<String, String> {
         let offset = self.current;
-
-        if let Some(pos) = memmem::find(self.remaining_source().as_bytes(), delimiter.as_bytes()) {
-            self.current += pos;
-            return Ok(self.consumed_source_from(offset).to_string());
+        let remaining = self.remaining_source().as_bytes();
+
+        if let Some(pos) = memmem::find(remaining, delimiter.closer().as_bytes()) {
+            let reopens = match delimiter {
+                TagDelimiter::Block | TagDelimiter::Variable => opens_tag(&remaining[..pos]),
+                TagDelimiter::Comment => false,
+            };
+            if !reopens {
+                self.current += pos;
+                return Ok(self.consumed_source_from(offset).to_string());
+            }
         }
 
         self.current += self.consume_until_stop_char();
@@ -209,6 +223,41 @@ impl Lexer {
     }
 }
 
+/// Whether `content` contains a `{%` or `{{` opener outside a quoted string.
+///
+/// String literals may hold openers, as in `{% trans "{{ name }}" %}`. A quote that is never
+/// closed hides nothing, so an unclosed tag running into the next one is still caught.
+fn opens_tag(content: &[u8]) -> bool {
+    let mut index = 0;
+    while index < content.len() {
+        match content[index] {
+            quote @ (b'"' | b'\'') => {
+                if let Some(end) = closing_quote(&content[index + 1..], quote) {
+                    index += end + 1;
+                }
+            }
+            b'{' if matches!(content.get(index + 1), Some(b'%' | b'{')) => return true,
+            _ => {}
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Offset of the `quote` that ends a string literal in `content`, skipping backslash escapes.
+fn closing_quote(content: &[u8], quote: u8) -> Option<usize> {
+    let mut index = 0;
+    while index < content.len() {
+        match content[index] {
+            b'\\' => index += 1,
+            byte if byte == quote => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -415,6 +464,82 @@ mod tests {
         insta::assert_yaml_snapshot!(snapshot);
     }
 
+    #[test]
+    fn test_tokenize_unclosed_tag_stops_at_next_tag() {
+        let source = "{% if user <p>{{ user.name }}</p>{% endif %}";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize();
+
+        assert!(matches!(
+            &tokens[0],
+            Token::Error {
+                content,
+                delimiter: TagDelimiter::Block,
+                ..
+            } if content == " if user <p>"
+        ));
+        assert!(matches!(
+            &tokens[1],
+            Token::Variable { content, .. } if content == " user.name "
+        ));
+        assert!(matches!(
+            &tokens[3],
+            Token::Block { content, .. } if content == " endif "
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_openers_inside_string_literals() {
+        let source = r#"{% if x == "{{" %}{% trans "{{ name }}" %}{% endif %}"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize();
+
+        assert!(matches!(
+            &tokens[0],
+            Token::Block { content, .. } if content == r#" if x == "{{" "#
+        ));
+        assert!(matches!(
+            &tokens[1],
+            Token::Block { content, .. } if content == r#" trans "{{ name }}" "#
+        ));
+        assert!(matches!(
+            &tokens[2],
+            Token::Block { content, .. } if content == " endif "
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_unclosed_quote_does_not_hide_next_tag() {
+        let source = r#"{% if x == "a <p>{{ user.name }}</p>{% endif %}"#;
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize();
+
+        assert!(matches!(
+            &tokens[0],
+            Token::Error {
+                content,
+                delimiter: TagDelimiter::Block,
+                ..
+            } if content == r#" if x == "a <p>"#
+        ));
+        assert!(matches!(
+            &tokens[1],
+            Token::Variable { content, .. } if content == " user.name "
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_comment_may_contain_tags() {
+        let source = "{# {% if user %}{{ user.name }} #}";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize();
+
+        assert!(matches!(
+            &tokens[0],
+            Token::Comment { content, .. } if content == " {% if user %}{{ user.name }} "
+        ));
+    }
+
     #[test]
     fn test_tokenize_unclosed_style() {
         let source = "<style>body { color: blue; ";
@@ -18,6 +18,9 @@
 //!
 //! - [`NodeList`]: Parsed template nodes
 //! - [`Node`]: Individual parsed template node
+//! - [`EmbeddedRegion`]: `<script>`/`<style>` content with Django constructs blanked out
+//! - [`FilePragma`]: Per-file `{# djls: ... #}` overrides attached to the [`NodeList`]
+//! - [`reprint`]: Writes parsed nodes back to source with canonical tag spacing
 //! - [`TemplateErrorAccumulator`]: Salsa accumulator for parse errors
 //!
 //! ## Example
@@ -37,28 +40,43 @@
 
 mod bits;
 mod db;
+mod embedded;
 mod error;
+mod expression;
 mod filters;
 mod lexer;
 mod nodelist;
 mod parser;
+mod pragma;
 mod quotes;
+mod reprint;
 mod tokens;
 mod visitor;
 
 pub use bits::FilterArgument;
 pub use bits::TagBit;
+pub use bits::TagKwarg;
 pub use db::TemplateErrorAccumulator;
 use djls_source::Db;
 use djls_source::File;
 use djls_source::FileKind;
 use djls_source::FileReadError;
+use djls_source::SourceText;
+pub use embedded::EmbeddedLanguage;
+pub use embedded::EmbeddedRegion;
 pub use error::TemplateError;
+pub use expression::Operand;
+pub use expression::OperandKind;
+pub use expression::PathSegment;
 pub use filters::Filter;
+pub use filters::variable_expression;
+pub use nodelist::IncludeNode;
 pub use nodelist::Node;
 pub use nodelist::NodeList;
 pub use parser::ParseError;
+pub use pragma::FilePragma;
 pub use quotes::TemplateString;
+pub use reprint::reprint;
 use salsa::Accumulator;
 pub use tokens::TagDelimiter;
 pub use tokens::Token;
@@ -92,7 +110,9 @@ impl<'db> TemplateParseResult<'db> {
 }
 
 /// Lex a Django template file.
-#[salsa::tracked(returns(ref))]
+///
+/// Token streams are cheap to rebuild, so only the most recently used are kept.
+#[salsa::tracked(returns(ref), lru = 64)]
 pub fn lex_template(db: &dyn Db, file: File) -> Result<Vec<Token>, FileReadError> {
     let source = file.try_source(db)?;
     if *source.kind() != FileKind::Template {
@@ -109,6 +129,32 @@ pub fn lex_template_impl(source: &str) -> Vec<Token> {
     lexer.tokenize()
 }
 
+/// The `<script>` and `<style>` regions of a template, in source order.
+///
+/// Each region's content has its Django constructs blanked out, so it can be
+/// forwarded to a JavaScript or CSS language server, and template checks can
+/// tell which text is code rather than markup.
+#[salsa::tracked(returns(ref))]
+pub fn embedded_regions(db: &dyn Db, file: File) -> Vec<EmbeddedRegion> {
+    let Ok(source) = file.try_source(db) else {
+        return Vec::new();
+    };
+    if *source.kind() != FileKind::Template {
+        return Vec::new();
+    }
+    let Ok(tokens) = lex_template(db, file) else {
+        return Vec::new();
+    };
+
+    embedded::regions_from_tokens(source.as_ref(), tokens)
+}
+
+/// Find the `<script>` and `<style>` regions of a template without a database.
+#[must_use]
+pub fn embedded_regions_impl(source: &str) -> Vec<EmbeddedRegion> {
+    embedded::regions_from_tokens(source, &lex_template_impl(source))
+}
+
 /// Parse a Django template file and accumulate diagnostics.
 ///
 /// Diagnostics can be retrieved using:
@@ -125,16 +171,23 @@ pub fn parse_template(db: &dyn Db, file: File) -> TemplateParseResult<'_> {
     if *source.kind() != FileKind::Template {
         return TemplateParseResult::NotTemplate;
     }
+    if let Some(position) = source.first_invalid_byte() {
+        TemplateErrorAccumulator(TemplateError::InvalidUtf8 { position }).accumulate(db);
+    }
 
-    let (nodes, errors) = parse_template_impl(source.as_ref());
+    let tokens = lex_template_impl(source.as_ref());
+    let pragma = FilePragma::from_tokens(&tokens);
+    let (nodes, errors) = parser::Parser::new(tokens, source).parse();
 
-    // Accumulate any errors via Salsa
-    for error in errors {
-        TemplateErrorAccumulator(error.into()).accumulate(db);
+    // Accumulate any errors via Salsa, unless a pragma says this is not Django syntax
+    if !pragma.as_ref().is_some_and(FilePragma::is_foreign_dialect) {
+        for error in errors {
+            TemplateErrorAccumulator(error.into()).accumulate(db);
+        }
     }
 
     // Always return a NodeList (may contain Error nodes if there were parse errors)
-    TemplateParseResult::Parsed(NodeList::new(db, nodes))
+    TemplateParseResult::Parsed(NodeList::new(db, nodes, pragma))
 }
 
 /// Parse a template using the pure parser (no database needed)
@@ -142,6 +195,6 @@ pub fn parse_template(db: &dyn Db, file: File) -> TemplateParseResult<'_> {
 #[must_use]
 pub fn parse_template_impl(source: &str) -> (Vec<Node>, Vec<ParseError>) {
     let tokens = lex_template_impl(source);
-    let mut parser = parser::Parser::new(tokens);
+    let mut parser = parser::Parser::new(tokens, SourceText::from(source));
     parser.parse()
 }
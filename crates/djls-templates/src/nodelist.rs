@@ -1,8 +1,11 @@
 use djls_source::Span;
 
 use crate::bits::TagBit;
+use crate::bits::TagKwarg;
+use crate::expression::Operand;
 use crate::filters::Filter;
 use crate::parser::ParseError;
+use crate::pragma::FilePragma;
 use crate::tokens::TagDelimiter;
 
 #[salsa::tracked(debug)]
@@ -10,6 +13,10 @@ pub struct NodeList<'db> {
     #[tracked]
     #[returns(ref)]
     pub nodelist: Vec<Node>,
+    /// Settings from the file's leading `{# djls: ... #}` comment, if it has one.
+    #[tracked]
+    #[returns(ref)]
+    pub pragma: Option<FilePragma>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -30,6 +37,8 @@ pub enum Node {
     Variable {
         var: String,
         var_span: Span,
+        /// `var` parsed into a literal or lookup path
+        expression: Operand,
         filters: Vec<Filter>,
         span: Span,
     },
@@ -52,3 +61,135 @@ impl Node {
         }
     }
 }
+
+/// The options of an `{% include %}` tag, parsed from its bits.
+///
+/// Follows Django's `do_include`: the template expression comes first, then
+/// `with` (followed by one or more `name=value` bindings) and `only`, each at
+/// most once and in either order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncludeNode<'a> {
+    pub template: &'a TagBit,
+    pub extra_context: Vec<TagKwarg<'a>>,
+    /// Span of the `only` keyword; when present the included template sees
+    /// only `extra_context`, not the including template's context.
+    pub only: Option<Span>,
+}
+
+impl<'a> IncludeNode<'a> {
+    /// Parse the bits of an include tag, excluding the tag name.
+    ///
+    /// Returns `None` for bits Django would reject; tag validation reports
+    /// those separately.
+    #[must_use]
+    pub fn from_bits(bits: &'a [TagBit]) -> Option<Self> {
+        let (template, mut remaining) = bits.split_first()?;
+        let mut extra_context = None;
+        let mut only = None;
+
+        while let Some((option, rest)) = remaining.split_first() {
+            remaining = rest;
+            match option.as_str() {
+                "with" if extra_context.is_none() => {
+                    let bindings = remaining
+                        .iter()
+                        .map_while(TagBit::kwarg)
+                        .collect::<Vec<_>>();
+                    if bindings.is_empty() {
+                        return None;
+                    }
+                    remaining = &remaining[bindings.len()..];
+                    extra_context = Some(bindings);
+                }
+                "only" if only.is_none() => only = Some(option.span),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            template,
+            extra_context: extra_context.unwrap_or_default(),
+            only,
+        })
+    }
+
+    #[must_use]
+    pub fn is_isolated(&self) -> bool {
+        self.only.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(source: &str) -> Vec<TagBit> {
+        let mut offset = 0;
+        source
+            .split(' ')
+            .map(|text| {
+                let bit = TagBit::new(
+                    text.to_string(),
+                    Span::saturating_from_parts_usize(offset, text.len()),
+                );
+                offset += text.len() + 1;
+                bit
+            })
+            .collect()
+    }
+
+    #[test]
+    fn include_without_options() {
+        let bits = bits("'card.html'");
+        let include = IncludeNode::from_bits(&bits).expect("include should parse");
+
+        assert_eq!(include.template.as_str(), "'card.html'");
+        assert!(include.extra_context.is_empty());
+        assert!(!include.is_isolated());
+    }
+
+    #[test]
+    fn include_with_bindings_and_only_in_either_order() {
+        for source in [
+            "'card.html' with title=page.title count=3 only",
+            "'card.html' only with title=page.title count=3",
+        ] {
+            let bits = bits(source);
+            let include = IncludeNode::from_bits(&bits).expect("include should parse");
+
+            let names = include
+                .extra_context
+                .iter()
+                .map(|binding| (binding.name, binding.value))
+                .collect::<Vec<_>>();
+            assert_eq!(names, [("title", "page.title"), ("count", "3")]);
+            assert!(include.is_isolated(), "{source}");
+        }
+    }
+
+    #[test]
+    fn binding_spans_cover_name_and_value() {
+        let bits = bits("'card.html' with title=page.title");
+        let include = IncludeNode::from_bits(&bits).expect("include should parse");
+        let binding = &include.extra_context[0];
+
+        assert_eq!(binding.name_span, Span::new(17, 5));
+        assert_eq!(binding.value_span, Span::new(23, 10));
+    }
+
+    #[test]
+    fn invalid_options_do_not_parse() {
+        for source in [
+            "'card.html' with",
+            "'card.html' with only",
+            "'card.html' only only",
+            "'card.html' with a=1 with b=2",
+            "'card.html' with a as b",
+            "'card.html' extra",
+        ] {
+            let bits = bits(source);
+            assert_eq!(IncludeNode::from_bits(&bits), None, "{source}");
+        }
+        assert_eq!(IncludeNode::from_bits(&[]), None);
+    }
+}
@@ -1,8 +1,10 @@
+use djls_source::SourceText;
 use djls_source::Span;
 use serde::Serialize;
 use thiserror::Error;
 
 use crate::bits::TagBit;
+use crate::expression::Operand;
 use crate::filters::Filter;
 use crate::filters::parse_filter;
 use crate::filters::split_variable_expression;
@@ -13,12 +15,18 @@ use crate::tokens::Token;
 pub(crate) struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// The template text the tokens were lexed from, shared by every parsed [`TagBit`].
+    source: SourceText,
 }
 
 impl Parser {
     #[must_use]
-    pub(crate) fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+    pub(crate) fn new(tokens: Vec<Token>, source: SourceText) -> Self {
+        Self {
+            tokens,
+            current: 0,
+            source,
+        }
     }
 
     pub(crate) fn parse(&mut self) -> (Vec<Node>, Vec<ParseError>) {
@@ -92,7 +100,8 @@ impl Parser {
         };
 
         let span = token.content_span_or_fallback();
-        let (name, name_span, bits) = Self::parse_tag_args(content_ref, span.start_usize())?;
+        let (name, name_span, bits) =
+            Self::parse_tag_args(&self.source, content_ref, span.start_usize())?;
 
         Ok(Node::Tag {
             name,
@@ -103,6 +112,7 @@ impl Parser {
     }
 
     fn parse_tag_args(
+        source: &SourceText,
         content: &str,
         position: usize,
     ) -> Result<(String, Span, Vec<TagBit>), ParseError> {
@@ -113,8 +123,9 @@ impl Parser {
             Span::saturating_from_parts_usize(position + name.start_byte, name.text.len());
         let bits = iter
             .map(|segment| {
-                TagBit::new(
-                    segment.text.to_string(),
+                TagBit::in_source(
+                    source,
+                    segment.text,
                     Span::saturating_from_parts_usize(
                         position + segment.start_byte,
                         segment.text.len(),
@@ -236,9 +247,12 @@ impl Parser {
             }
         }
 
+        let expression = Operand::parse(&var, var_span);
+
         Ok(Node::Variable {
             var,
             var_span,
+            expression,
             filters,
             span,
         })
@@ -354,12 +368,13 @@ mod tests {
     use serde::Serialize;
 
     use super::*;
+    use crate::expression::OperandKind;
     use crate::lexer::Lexer;
 
     fn parse_test_template(source: &str) -> Vec<Node> {
         let mut lexer = Lexer::new(source);
         let tokens = lexer.tokenize();
-        let mut parser = Parser::new(tokens);
+        let mut parser = Parser::new(tokens, SourceText::from(source));
         let (nodes, _errors) = parser.parse();
         nodes
     }
@@ -378,7 +393,7 @@ mod tests {
     impl TestSpannedText {
         fn from_tag(bit: &crate::TagBit) -> Self {
             Self {
-                text: bit.text.clone(),
+                text: bit.as_str().to_string(),
                 span: (&bit.span).into(),
             }
         }
@@ -467,9 +482,9 @@ mod tests {
                 },
                 Node::Variable {
                     var,
-                    var_span: _,
                     filters,
                     span,
+                    ..
                 } => TestNode::Variable {
                     var: var.clone(),
                     filters: filters.iter().map(TestFilter::from_filter).collect(),
@@ -550,6 +565,31 @@ mod tests {
             insta::assert_yaml_snapshot!(test_nodelist);
         }
 
+        #[test]
+        fn test_variable_expression_carries_operand_spans() {
+            let nodelist = parse_test_template("{{ user.name|default:'x' }}");
+
+            let Node::Variable {
+                expression,
+                filters,
+                ..
+            } = &nodelist[0]
+            else {
+                panic!("expected a variable node");
+            };
+            let path: Vec<_> = expression
+                .path()
+                .expect("lookup path")
+                .iter()
+                .map(|segment| (segment.name.as_str(), segment.span))
+                .collect();
+            assert_eq!(path, vec![("user", Span::new(3, 4)), ("name", Span::new(8, 4))]);
+            assert!(matches!(
+                &filters[0].arg.as_ref().expect("argument").expression().kind,
+                OperandKind::String { value, .. } if value == "x"
+            ));
+        }
+
         #[test]
         fn test_malformed_filter_expression_becomes_error_node() {
             let source = "{{ value| }}";
@@ -758,29 +798,26 @@ mod tests {
             insta::assert_yaml_snapshot!(test_nodelist);
         }
 
-        // TODO: fix this so we can test against errors returned by parsing
-        // #[test]
-        // fn test_parse_error_recovery() {
-        //     let source = r#"<div class="container">
-        //     <h1>Header</h1>
-        //     {% %}
-        //         {# This if is unclosed which does matter #}
-        //         <p>Welcome {{ user.name }}</p>
-        //         <div>
-        //             {# This div is unclosed which doesn't matter #}
-        //         {% for item in items %}
-        //             <span>{{ item }}</span>
-        //         {% endfor %}
-        //     <footer>Page Footer</footer>
-        // </div>"#;
-        //     let tokens = Lexer::new(source).tokenize().unwrap();
-        //     let mut parser = create_test_parser(tokens);
-        //     let (nodelist, errors) = parser.parse().unwrap();
-        //     let nodelist = convert_nodelist_for_testing(ast.nodelist(parser.db), parser.db);
-        //     insta::assert_yaml_snapshot!(nodelist);
-        //     assert_eq!(errors.len(), 1);
-        //     assert!(matches!(&errors[0], ParserError::EmptyTag));
-        // }
+        #[test]
+        fn test_parse_recovers_after_unclosed_tag() {
+            let source = "<h1>{% if user</h1>\n<p>{{ user.name }}</p>\n{% endif %}";
+            let mut lexer = Lexer::new(source);
+            let mut parser = Parser::new(lexer.tokenize(), SourceText::from(source));
+            let (nodelist, errors) = parser.parse();
+
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(&errors[0], ParseError::MalformedConstruct { .. }));
+            assert!(
+                nodelist
+                    .iter()
+                    .any(|node| matches!(node, Node::Variable { var, .. } if var == "user.name"))
+            );
+            assert!(
+                nodelist
+                    .iter()
+                    .any(|node| matches!(node, Node::Tag { name, .. } if name == "endif"))
+            );
+        }
     }
 
     mod full_templates {
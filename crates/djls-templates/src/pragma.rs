@@ -0,0 +1,92 @@
+//! Per-file `{# djls: ... #}` pragmas.
+//!
+//! A template can override engine detection with a comment as its first token:
+//!
+//! ```text
+//! {# djls: dialect=jinja2 #}
+//! ```
+//!
+//! Settings are comma-separated `key=value` pairs. Unknown keys and unrecognized values are
+//! ignored so a typo never turns an ordinary comment into an error.
+
+use djls_conf::Dialect;
+
+use crate::tokens::Token;
+
+const PRAGMA_PREFIX: &str = "djls:";
+
+/// Settings from a file's leading `{# djls: ... #}` comment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FilePragma {
+    dialect: Option<Dialect>,
+}
+
+impl FilePragma {
+    /// Read the pragma from the first token of a lexed template, if it is a pragma comment.
+    #[must_use]
+    pub fn from_tokens(tokens: &[Token]) -> Option<Self> {
+        let Some(Token::Comment { content, .. }) = tokens.first() else {
+            return None;
+        };
+        Self::parse(content)
+    }
+
+    fn parse(comment: &str) -> Option<Self> {
+        let settings = comment.trim().strip_prefix(PRAGMA_PREFIX)?;
+        let mut pragma = Self::default();
+        for setting in settings.split(',') {
+            let Some((key, value)) = setting.split_once('=') else {
+                continue;
+            };
+            if key.trim() == "dialect" {
+                pragma.dialect = Dialect::from_name(value.trim()).or(pragma.dialect);
+            }
+        }
+        Some(pragma)
+    }
+
+    /// The dialect the pragma declares, if any.
+    #[must_use]
+    pub fn dialect(&self) -> Option<Dialect> {
+        self.dialect
+    }
+
+    /// Whether the pragma opts the file out of Django template analysis.
+    #[must_use]
+    pub fn is_foreign_dialect(&self) -> bool {
+        self.dialect.is_some_and(|dialect| dialect != Dialect::Django)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lex_template_impl;
+
+    #[test]
+    fn test_reads_dialect_from_leading_comment() {
+        let tokens = lex_template_impl("{# djls: dialect=jinja2 #}\n{{ x }}");
+        let pragma = FilePragma::from_tokens(&tokens).expect("leading pragma should be read");
+
+        assert_eq!(pragma.dialect(), Some(Dialect::Jinja2));
+        assert!(pragma.is_foreign_dialect());
+    }
+
+    #[test]
+    fn test_ignores_comments_that_are_not_first_or_not_pragmas() {
+        let later = lex_template_impl("text\n{# djls: dialect=jinja2 #}");
+        assert_eq!(FilePragma::from_tokens(&later), None);
+
+        let plain = lex_template_impl("{# a regular comment #}");
+        assert_eq!(FilePragma::from_tokens(&plain), None);
+    }
+
+    #[test]
+    fn test_ignores_unknown_keys_and_values() {
+        let tokens = lex_template_impl("{# djls: dialect=mako, color=blue #}");
+        let pragma = FilePragma::from_tokens(&tokens).expect("pragma prefix should be read");
+
+        assert_eq!(pragma, FilePragma::default());
+        assert!(!pragma.is_foreign_dialect());
+    }
+}
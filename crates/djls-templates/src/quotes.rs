@@ -372,6 +372,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn split_unquoted_whitespace_keeps_translated_strings_and_kwargs_together() {
+        assert_eq!(
+            split_on_unquoted_whitespace_text(r#"with greeting=_("Hello there") name='A B' only"#),
+            vec!["with", r#"greeting=_("Hello there")"#, "name='A B'", "only"]
+        );
+    }
+
     #[test]
     fn split_unquoted_whitespace_empty() {
         assert!(split_on_unquoted_whitespace_text("").is_empty());
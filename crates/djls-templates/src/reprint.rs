@@ -0,0 +1,200 @@
+use crate::bits::TagBit;
+use crate::filters::Filter;
+use crate::filters::variable_expression;
+use crate::nodelist::Node;
+use crate::quotes::split_on_unquoted_whitespace;
+use crate::tokens::TagDelimiter;
+
+/// Write parsed nodes back out as template source.
+///
+/// Tags and variables are rebuilt from their parsed parts with canonical
+/// spacing (`{% name bit bit %}`, `{{ var|filter:arg }}`). Text, comments,
+/// malformed constructs, and any source the parser skipped while recovering
+/// from an error are copied from `source` unchanged, so reparsing the output
+/// yields the same nodes.
+#[must_use]
+pub fn reprint(source: &str, nodes: &[Node]) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut cursor = 0;
+
+    for node in nodes {
+        let span = node.full_span();
+        let (start, end) = (span.start_usize(), span.end_usize());
+        if start < cursor {
+            continue;
+        }
+        out.push_str(source.get(cursor..start).unwrap_or_default());
+
+        match node {
+            Node::Tag { name, bits, .. } => push_tag(&mut out, name, bits),
+            Node::Variable { var, filters, .. } => push_variable(&mut out, var, filters),
+            Node::Comment { .. } | Node::Text { .. } | Node::Error { .. } => {
+                out.push_str(source.get(start..end).unwrap_or_default());
+            }
+        }
+        cursor = end;
+    }
+
+    out.push_str(source.get(cursor..).unwrap_or_default());
+    out
+}
+
+fn push_tag(out: &mut String, name: &str, bits: &[TagBit]) {
+    let last = bits.last().map_or(name, TagBit::as_str);
+    let content = std::iter::once(name)
+        .chain(bits.iter().map(TagBit::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    out.push_str(TagDelimiter::Block.opener());
+    out.push(' ');
+    out.push_str(&content);
+    // A last bit with an unterminated quote runs to the closer, so a space
+    // before `%}` would become part of it.
+    let padded = format!("{content} ");
+    if split_on_unquoted_whitespace(&padded)
+        .last()
+        .is_some_and(|segment| segment.text == last)
+    {
+        out.push(' ');
+    }
+    out.push_str(TagDelimiter::Block.closer());
+}
+
+fn push_variable(out: &mut String, var: &str, filters: &[Filter]) {
+    out.push_str(TagDelimiter::Variable.opener());
+    out.push(' ');
+    out.push_str(&variable_expression(var, filters));
+    out.push(' ');
+    out.push_str(TagDelimiter::Variable.closer());
+}
+
+#[cfg(test)]
+mod tests {
+    use djls_source::Span;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::parse_template_impl;
+
+    fn roundtrip(source: &str) -> String {
+        let (nodes, _) = parse_template_impl(source);
+        reprint(source, &nodes)
+    }
+
+    #[test]
+    fn tags_and_variables_get_canonical_spacing() {
+        assert_eq!(
+            roundtrip("{%if  x\n%}<p>{{a | upper:'b' }}</p>{#  note #}{% endif%}"),
+            "{% if x %}<p>{{ a|upper:'b' }}</p>{#  note #}{% endif %}"
+        );
+    }
+
+    #[test]
+    fn unterminated_quote_keeps_its_trailing_space() {
+        assert_eq!(roundtrip("{% trans 'a %}"), "{% trans 'a %}");
+        assert_eq!(roundtrip("{% trans 'a%}"), "{% trans 'a%}");
+    }
+
+    #[test]
+    fn source_skipped_during_error_recovery_is_kept() {
+        assert_eq!(roundtrip("{% %} text\n{{ x }}"), "{% %} text\n{{ x }}");
+        assert_eq!(roundtrip("{% if x\n{{ y}}"), "{% if x\n{{ y }}");
+    }
+
+    /// Node content that survives reprinting; spans and error positions move.
+    #[derive(Debug, PartialEq, Eq)]
+    enum Shape {
+        Tag(String, Vec<String>),
+        Variable(String, Vec<String>),
+        Comment(String),
+        Text(String),
+        Error,
+    }
+
+    fn shapes(source: &str, nodes: &[Node]) -> Vec<Shape> {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Tag { name, bits, .. } => Shape::Tag(
+                    name.clone(),
+                    bits.iter().map(|bit| bit.as_str().to_string()).collect(),
+                ),
+                Node::Variable { var, filters, .. } => {
+                    Shape::Variable(var.clone(), filters.iter().map(Filter::label).collect())
+                }
+                Node::Comment { content, .. } => Shape::Comment(content.clone()),
+                Node::Text { span } => {
+                    Shape::Text(slice(source, *span).unwrap_or_default().to_string())
+                }
+                Node::Error { .. } => Shape::Error,
+            })
+            .collect()
+    }
+
+    fn slice(source: &str, span: Span) -> Option<&str> {
+        source.get(span.start_usize()..span.end_usize())
+    }
+
+    fn check_spans(source: &str, nodes: &[Node]) -> Result<(), TestCaseError> {
+        let mut previous_end = 0;
+        for node in nodes {
+            let span = node.full_span();
+            prop_assert!(slice(source, span).is_some(), "{node:?} is outside the source");
+            prop_assert!(
+                span.start_usize() >= previous_end,
+                "{node:?} overlaps the node before it"
+            );
+            previous_end = span.end_usize();
+
+            match node {
+                Node::Tag {
+                    name,
+                    name_span,
+                    bits,
+                    ..
+                } => {
+                    prop_assert_eq!(slice(source, *name_span), Some(name.as_str()));
+                    for bit in bits {
+                        prop_assert_eq!(slice(source, bit.span), Some(bit.as_str()));
+                    }
+                }
+                Node::Variable { var, var_span, .. } => {
+                    prop_assert_eq!(slice(source, *var_span), Some(var.as_str()));
+                }
+                Node::Comment { .. } | Node::Text { .. } | Node::Error { .. } => {}
+            }
+        }
+        Ok(())
+    }
+
+    const FRAGMENTS: &[&str] = &[
+        "{%", "%}", "{{", "}}", "{#", "#}", "{", "}", "%", "#", " ", "  ", "\t", "\n", "\r\n",
+        "if", "endif", "for", "x", "x.y", "|", ":", "upper", "default", "'a b'", "\"q\"", "'",
+        "\"", "\\", "=", "as", "é", "<p>", "_(\"t\")",
+    ];
+
+    fn template_source() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(FRAGMENTS), 0..48)
+            .prop_map(|fragments| fragments.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn parsed_spans_slice_the_source_in_order(source in template_source()) {
+            let (nodes, _) = parse_template_impl(&source);
+            check_spans(&source, &nodes)?;
+        }
+
+        #[test]
+        fn reprinted_source_reparses_to_the_same_nodes(source in template_source()) {
+            let (nodes, _) = parse_template_impl(&source);
+            let reprinted = reprint(&source, &nodes);
+            let (reparsed, _) = parse_template_impl(&reprinted);
+
+            check_spans(&reprinted, &reparsed)?;
+            prop_assert_eq!(shapes(&source, &nodes), shapes(&reprinted, &reparsed));
+            prop_assert_eq!(reprint(&reprinted, &reparsed), reprinted);
+        }
+    }
+}
@@ -32,6 +32,7 @@ fn walk_node<V: Visitor + ?Sized>(visitor: &mut V, node: &Node) {
             var_span,
             filters,
             span,
+            ..
         } => visitor.visit_variable(var, *var_span, filters, *span),
         Node::Comment { content, span } => visitor.visit_comment(content, *span),
         Node::Text { span } => visitor.visit_text(*span),
@@ -9,6 +9,7 @@ use djls_project::ModelGraph;
 use djls_project::Project;
 use djls_semantic::Db as SemanticDb;
 use djls_semantic::FilterAritySpecs;
+use djls_semantic::LintRules;
 use djls_semantic::TagSpecs;
 use djls_semantic::builtin_tag_specs;
 use djls_source::File;
@@ -82,6 +83,8 @@ pub struct TestDatabase {
     projectless_tag_specs: TagSpecs,
     projectless_filter_arity_specs: FilterAritySpecs,
     diagnostics_config: djls_conf::DiagnosticsConfig,
+    lint_rules: Arc<LintRules>,
+    untracked_lint_rules: Arc<LintRules>,
     project: Option<Project>,
     storage: salsa::Storage<Self>,
 }
@@ -119,6 +122,8 @@ impl TestDatabase {
             projectless_tag_specs: builtin_tag_specs(),
             projectless_filter_arity_specs: FilterAritySpecs::new(),
             diagnostics_config: djls_conf::DiagnosticsConfig::default(),
+            lint_rules: Arc::new(LintRules::new()),
+            untracked_lint_rules: Arc::new(LintRules::new()),
             project: None,
             storage,
         }
@@ -145,6 +150,18 @@ impl TestDatabase {
         self
     }
 
+    #[must_use]
+    pub fn with_lint_rules(mut self, rules: LintRules) -> Self {
+        self.lint_rules = Arc::new(rules);
+        self
+    }
+
+    #[must_use]
+    pub fn with_untracked_lint_rules(mut self, rules: LintRules) -> Self {
+        self.untracked_lint_rules = Arc::new(rules);
+        self
+    }
+
     /// Add an in-memory file to the test filesystem.
     pub fn add_file(&self, path: &str, content: &str) -> anyhow::Result<()> {
         self.fs
@@ -271,7 +288,7 @@ impl SemanticDb for TestDatabase {
         &self.projectless_tag_specs
     }
 
-    fn diagnostics_config(&self) -> djls_conf::DiagnosticsConfig {
+    fn diagnostics_config(&self, _file: File) -> djls_conf::DiagnosticsConfig {
         self.diagnostics_config.clone()
     }
 
@@ -282,4 +299,12 @@ impl SemanticDb for TestDatabase {
     fn model_graph(&self) -> &ModelGraph {
         ModelGraph::empty_ref()
     }
+
+    fn lint_rules(&self) -> &LintRules {
+        &self.lint_rules
+    }
+
+    fn untracked_lint_rules(&self) -> &LintRules {
+        &self.untracked_lint_rules
+    }
 }
@@ -0,0 +1,228 @@
+//! Corpus-wide argument validation measured against a committed false-positive budget.
+//!
+//! Corpus templates render in their upstream projects, so every argument diagnostic they
+//! produce is a false positive. The budget records how many each corpus entry may have.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::Context as _;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Corpus;
+use crate::TestDatabase;
+use crate::build_entry_specs;
+use crate::collect_argument_validation_errors_with_revision;
+
+pub struct FalsePositiveReportOptions {
+    pub output: Option<Utf8PathBuf>,
+}
+
+/// One argument diagnostic reported on a corpus template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusFinding {
+    /// Template path relative to the corpus entry.
+    pub path: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// Validation results for one corpus entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryFalsePositives {
+    pub templates: usize,
+    pub findings: Vec<CorpusFinding>,
+}
+
+/// Argument diagnostics for every synced corpus entry, keyed by entry name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FalsePositiveReport {
+    pub entries: BTreeMap<String, EntryFalsePositives>,
+}
+
+impl FalsePositiveReport {
+    /// Validate every corpus template against the rules extracted from its entry (and from
+    /// the latest Django for non-Django entries).
+    pub fn collect(corpus: &Corpus) -> anyhow::Result<Self> {
+        let mut by_entry: BTreeMap<Utf8PathBuf, Vec<Utf8PathBuf>> = BTreeMap::new();
+        for template_path in corpus.templates_in(corpus.root()) {
+            let Some(entry_dir) = corpus.entry_dir_for_path(&template_path) else {
+                continue;
+            };
+            by_entry.entry(entry_dir).or_default().push(template_path);
+        }
+
+        let mut report = Self::default();
+        for (entry_dir, mut templates) in by_entry {
+            let Some(entry_name) = entry_dir.file_name() else {
+                continue;
+            };
+            templates.sort();
+            let entry = validate_entry(corpus, &entry_dir, &templates)?;
+            report.entries.insert(entry_name.to_owned(), entry);
+        }
+        Ok(report)
+    }
+
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.entries.values().map(|entry| entry.findings.len()).sum()
+    }
+
+    /// One message per entry with more findings than its budget allows.
+    #[must_use]
+    pub fn over_budget(&self, budget: &FalsePositiveBudget) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|(name, entry)| entry.findings.len() > budget.allowed(name))
+            .map(|(name, entry)| {
+                let mut message = format!(
+                    "{name}: {} false positives, budget {}",
+                    entry.findings.len(),
+                    budget.allowed(name)
+                );
+                for finding in entry.findings.iter().take(5) {
+                    message.push_str(&format!(
+                        "\n    {} [{}] {}",
+                        finding.path, finding.code, finding.message
+                    ));
+                }
+                message
+            })
+            .collect()
+    }
+
+    pub fn write(&self, path: &Utf8Path) -> anyhow::Result<()> {
+        let content = format!("{}\n", serde_json::to_string_pretty(self)?);
+        fs::write(path.as_std_path(), content)
+            .with_context(|| format!("failed to write {path}"))
+    }
+}
+
+/// Allowed false positives per corpus entry. Entries not listed are allowed none.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FalsePositiveBudget {
+    pub entries: BTreeMap<String, usize>,
+}
+
+impl FalsePositiveBudget {
+    /// The budget committed next to the corpus manifest.
+    pub fn committed() -> anyhow::Result<Self> {
+        let path = Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("false-positive-budget.json");
+        Self::load(&path)
+    }
+
+    pub fn load(path: &Utf8Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path.as_std_path())
+            .with_context(|| format!("failed to read {path}"))?;
+        serde_json::from_str(&content).with_context(|| format!("failed to parse {path}"))
+    }
+
+    #[must_use]
+    pub fn allowed(&self, entry: &str) -> usize {
+        self.entries.get(entry).copied().unwrap_or(0)
+    }
+}
+
+/// Validate the corpus, write the report for triage, and print entries over budget.
+pub fn false_positive_report(options: FalsePositiveReportOptions) -> anyhow::Result<()> {
+    let corpus = Corpus::require()?;
+    let output = options
+        .output
+        .unwrap_or_else(|| Utf8PathBuf::from("corpus-false-positives.json"));
+
+    let report = FalsePositiveReport::collect(&corpus)?;
+    report.write(&output)?;
+
+    let budget = FalsePositiveBudget::committed()?;
+    for message in report.over_budget(&budget) {
+        println!("{message}");
+    }
+    tracing::info!(%output, total = report.total(), "false-positive report written");
+    Ok(())
+}
+
+fn validate_entry(
+    corpus: &Corpus,
+    entry_dir: &Utf8Path,
+    templates: &[Utf8PathBuf],
+) -> anyhow::Result<EntryFalsePositives> {
+    let (specs, arities) = build_entry_specs(corpus, entry_dir)?;
+    let db = TestDatabase::new()
+        .with_projectless_tag_specs(specs)
+        .with_projectless_filter_arity_specs(arities);
+
+    let mut entry = EntryFalsePositives::default();
+    for (revision, template_path) in templates.iter().enumerate() {
+        let Ok(content) = fs::read_to_string(template_path.as_std_path()) else {
+            continue;
+        };
+        entry.templates += 1;
+
+        let errors = collect_argument_validation_errors_with_revision(
+            &db,
+            "corpus_test.html",
+            u64::try_from(revision)?,
+            &content,
+        )?;
+        let path = template_path
+            .strip_prefix(entry_dir)
+            .unwrap_or(template_path)
+            .to_string();
+        entry
+            .findings
+            .extend(errors.into_iter().map(|error| CorpusFinding {
+                path: path.clone(),
+                code: error.code().to_string(),
+                message: error.to_string(),
+            }));
+    }
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(path: &str) -> CorpusFinding {
+        CorpusFinding {
+            path: path.to_string(),
+            code: "S117".to_string(),
+            message: "'thumbnail' tag takes 2 arguments".to_string(),
+        }
+    }
+
+    #[test]
+    fn unlisted_entries_have_no_budget() {
+        let report = FalsePositiveReport {
+            entries: BTreeMap::from([
+                (
+                    "sorl-thumbnail".to_string(),
+                    EntryFalsePositives {
+                        templates: 3,
+                        findings: vec![finding("a.html"), finding("b.html")],
+                    },
+                ),
+                (
+                    "wagtail-7.3".to_string(),
+                    EntryFalsePositives {
+                        templates: 4,
+                        findings: vec![finding("c.html")],
+                    },
+                ),
+            ]),
+        };
+        let budget: FalsePositiveBudget =
+            serde_json::from_str(r#"{"sorl-thumbnail": 2}"#).expect("budget should parse");
+
+        assert_eq!(report.total(), 3);
+        assert_eq!(
+            report.over_budget(&budget),
+            ["wagtail-7.3: 1 false positives, budget 0\n    c.html [S117] 'thumbnail' tag takes 2 arguments"]
+        );
+    }
+}
@@ -5,6 +5,8 @@ use std::collections::HashMap;
 use anyhow::Context as _;
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_conf::DialectsConfig;
+use djls_conf::PathMappings;
 use djls_conf::Settings;
 use djls_conf::TagSpecDef;
 use djls_project::ArgumentCountConstraint;
@@ -13,6 +15,7 @@ use djls_project::Db as ProjectDb;
 use djls_project::ExtractedDiagnosticConstraint;
 use djls_project::ExtractedDiagnosticMessage;
 use djls_project::ExtractedMessageTemplate;
+use djls_project::FilterArgKind;
 use djls_project::FilterArity;
 use djls_project::Interpreter;
 use djls_project::LibraryName;
@@ -268,6 +271,8 @@ pub struct ProjectFixture {
     search_paths: Option<SearchPaths>,
     register_roots: bool,
     tag_specs: TagSpecDef,
+    dialects: DialectsConfig,
+    path_mappings: PathMappings,
 }
 
 impl ProjectFixture {
@@ -284,6 +289,8 @@ impl ProjectFixture {
             search_paths: None,
             register_roots: true,
             tag_specs: settings.tagspecs().clone(),
+            dialects: settings.dialects().clone(),
+            path_mappings: settings.path_mappings().clone(),
         }
     }
 
@@ -309,6 +316,18 @@ impl ProjectFixture {
         self
     }
 
+    #[must_use]
+    pub fn dialects(mut self, dialects: DialectsConfig) -> Self {
+        self.dialects = dialects;
+        self
+    }
+
+    #[must_use]
+    pub fn path_mappings(mut self, path_mappings: PathMappings) -> Self {
+        self.path_mappings = path_mappings;
+        self
+    }
+
     #[must_use]
     pub fn interpreter(mut self, interpreter: Interpreter) -> Self {
         self.interpreter = interpreter;
@@ -355,6 +374,8 @@ impl ProjectFixture {
             self.pythonpath,
             self.env_vars,
             self.tag_specs,
+            self.dialects,
+            self.path_mappings,
         ))
     }
 
@@ -522,14 +543,11 @@ pub fn render_diagnostic_snapshot(
         let message = err.to_string();
         let code = err.code();
 
+        let related = err.related_spans();
         let mut diag = Diagnostic::new(source, path, code, &message, Severity::Error, span, "");
 
-        if let ValidationError::UnbalancedStructure {
-            closing_span: Some(cs),
-            ..
-        } = err
-        {
-            diag = diag.annotation(*cs, "", false);
+        for (related_span, label) in &related {
+            diag = diag.annotation(*related_span, label, false);
         }
 
         parts.push(renderer.render(&diag));
@@ -711,7 +729,7 @@ def widthratio(parser, token):
             format!(
                 "{}\n{}",
                 include_str!("../../djls-project/src/templates/tags/testdata/django_defaultfilters.py"),
-                "@register.filter\ndef title(value): pass\n@register.filter\ndef lower(value): pass\n@register.filter\ndef length(value): pass\n@register.filter\ndef default(value, arg): pass\n@register.filter\ndef truncatewords(value, arg): pass\n@register.filter\ndef date(value, arg=None): pass\n@register.filter\ndef upper(value): pass\n"
+                "@register.filter\ndef title(value): pass\n@register.filter\ndef lower(value): pass\n@register.filter\ndef length(value): pass\n@register.filter\ndef default(value, arg): pass\n@register.filter\ndef truncatewords(value, arg): return int(arg)\n@register.filter\ndef date(value, arg=None): return formats.date_format(value, arg)\n@register.filter\ndef pluralize(value, arg='s'):\n    bits = arg.split(',')\n    if len(bits) > 2:\n        return ''\n@register.filter\ndef upper(value): pass\n"
             ),
         )
         .file(
@@ -966,6 +984,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         },
     );
     specs.insert(
@@ -973,6 +992,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         },
     );
     specs.insert(
@@ -980,6 +1000,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: false,
             arg_optional: false,
+            arg_kind: None,
         },
     );
     specs.insert(
@@ -987,6 +1008,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: true,
             arg_optional: false,
+            arg_kind: None,
         },
     );
     specs.insert(
@@ -994,6 +1016,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: true,
             arg_optional: false,
+            arg_kind: Some(FilterArgKind::Integer),
         },
     );
     specs.insert(
@@ -1001,6 +1024,7 @@ fn standard_filter_arities() -> FilterAritySpecs {
         FilterArity {
             expects_arg: true,
             arg_optional: true,
+            arg_kind: Some(FilterArgKind::DateFormat),
         },
     );
     specs
@@ -1,8 +1,10 @@
 mod corpus;
 mod db;
 mod extraction;
+mod false_positives;
 mod fixtures;
 mod mdtest;
+mod report;
 mod vendor;
 
 pub use corpus::Corpus;
@@ -21,6 +23,12 @@ pub use extraction::ExtractionBundle;
 pub use extraction::SortedExtractionResult;
 pub use extraction::extract_bundle;
 pub use extraction::sorted_snapshot;
+pub use false_positives::CorpusFinding;
+pub use false_positives::EntryFalsePositives;
+pub use false_positives::FalsePositiveBudget;
+pub use false_positives::FalsePositiveReport;
+pub use false_positives::FalsePositiveReportOptions;
+pub use false_positives::false_positive_report;
 pub use fixtures::ProjectFixture;
 pub use fixtures::build_entry_specs;
 pub use fixtures::build_specs_from_extraction;
@@ -38,5 +46,11 @@ pub use mdtest::Scenario;
 pub use mdtest::ScenarioFile;
 pub use mdtest::run_suite;
 pub use mdtest::run_suite_with;
+pub use report::ExtractReport;
+pub use report::ExtractReportOptions;
+pub use report::ExtractionCounts;
+pub use report::extract_report;
+pub use vendor::VendorFallbackLibrariesOptions;
 pub use vendor::VendorSpecFixturesOptions;
+pub use vendor::vendor_fallback_libraries;
 pub use vendor::vendor_spec_fixtures;
@@ -4,9 +4,12 @@ use camino::Utf8Path;
 use camino::Utf8PathBuf;
 use clap::Parser;
 use clap::Subcommand;
+use djls_testing::ExtractReportOptions;
+use djls_testing::FalsePositiveReportOptions;
 use djls_testing::LockFilter;
 use djls_testing::Lockfile;
 use djls_testing::Manifest;
+use djls_testing::VendorFallbackLibrariesOptions;
 use djls_testing::VendorSpecFixturesOptions;
 
 #[derive(Parser)]
@@ -52,6 +55,32 @@ enum Command {
         #[arg(long)]
         output_dir: Option<Utf8PathBuf>,
     },
+    /// Regenerate the bundled fallback Template Libraries from the synced corpus
+    VendorFallbackLibraries {
+        /// Check whether the bundled libraries match the working tree without writing changes
+        #[arg(long)]
+        check: bool,
+
+        /// Output directory (defaults to crates/djls-project/src/templates/fallback)
+        #[arg(long)]
+        output_dir: Option<Utf8PathBuf>,
+    },
+    /// Extract every templatetag module in the corpus and diff the counts against the report
+    ExtractReport {
+        /// Fail if the counts differ from the committed report without writing changes
+        #[arg(long)]
+        check: bool,
+
+        /// Report path (defaults to crates/djls-testing/extract-report.json)
+        #[arg(long)]
+        output: Option<Utf8PathBuf>,
+    },
+    /// Validate every corpus template and write the argument diagnostics for triage
+    FalsePositiveReport {
+        /// Report path (defaults to corpus-false-positives.json in the working directory)
+        #[arg(long)]
+        output: Option<Utf8PathBuf>,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
@@ -116,6 +145,18 @@ fn main() -> anyhow::Result<()> {
         Command::VendorSpecFixtures { check, output_dir } => {
             djls_testing::vendor_spec_fixtures(VendorSpecFixturesOptions { check, output_dir })?;
         }
+        Command::VendorFallbackLibraries { check, output_dir } => {
+            djls_testing::vendor_fallback_libraries(VendorFallbackLibrariesOptions {
+                check,
+                output_dir,
+            })?;
+        }
+        Command::ExtractReport { check, output } => {
+            djls_testing::extract_report(ExtractReportOptions { check, output })?;
+        }
+        Command::FalsePositiveReport { output } => {
+            djls_testing::false_positive_report(FalsePositiveReportOptions { output })?;
+        }
     }
 
     Ok(())
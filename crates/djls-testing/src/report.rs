@@ -0,0 +1,236 @@
+//! Corpus-wide extraction summary, committed so regressions show up in review.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::Context as _;
+use anyhow::bail;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use djls_project::PythonModuleName;
+use djls_project::TemplateLibraryId;
+use djls_project::TemplateSymbolKind;
+use djls_project::template_library_definition_facts;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Corpus;
+use crate::TestDatabase;
+use crate::extract_bundle;
+use crate::module_name_from_file;
+
+pub struct ExtractReportOptions {
+    pub check: bool,
+    pub output: Option<Utf8PathBuf>,
+}
+
+/// What extraction found in one corpus entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionCounts {
+    pub modules: usize,
+    pub tags: usize,
+    pub filters: usize,
+    pub tag_rules: usize,
+    pub block_specs: usize,
+    pub filter_arities: usize,
+}
+
+impl ExtractionCounts {
+    fn fields(self) -> [(&'static str, usize); 6] {
+        [
+            ("modules", self.modules),
+            ("tags", self.tags),
+            ("filters", self.filters),
+            ("tag_rules", self.tag_rules),
+            ("block_specs", self.block_specs),
+            ("filter_arities", self.filter_arities),
+        ]
+    }
+}
+
+/// Extraction counts for every synced corpus entry, keyed by entry name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractReport {
+    pub packages: BTreeMap<String, ExtractionCounts>,
+}
+
+/// Extract every templatetag module in the corpus, print how the counts moved since the
+/// committed report, and write the new report.
+pub fn extract_report(options: ExtractReportOptions) -> anyhow::Result<()> {
+    let corpus = Corpus::require()?;
+    let output = options.output.unwrap_or_else(default_report_path);
+
+    let report = build_report(&corpus)?;
+    let previous = match fs::read_to_string(output.as_std_path()) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {output}"))?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => ExtractReport::default(),
+        Err(error) => return Err(error).with_context(|| format!("failed to read {output}")),
+    };
+
+    let changes = diff_reports(&previous, &report);
+    for change in &changes {
+        println!("{change}");
+    }
+
+    if options.check {
+        if !changes.is_empty() {
+            bail!(
+                "extraction report {output} is out of date\nrun `just corpus extract-report` to update it"
+            );
+        }
+        return Ok(());
+    }
+
+    let content = format!("{}\n", serde_json::to_string_pretty(&report)?);
+    fs::write(output.as_std_path(), content)
+        .with_context(|| format!("failed to write {output}"))?;
+    tracing::info!(%output, changed = changes.len(), "extraction report written");
+    Ok(())
+}
+
+fn default_report_path() -> Utf8PathBuf {
+    Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("extract-report.json")
+}
+
+fn build_report(corpus: &Corpus) -> anyhow::Result<ExtractReport> {
+    let repos_dir = corpus.root().join("repos");
+    let mut entries = fs::read_dir(repos_dir.as_std_path())
+        .with_context(|| format!("failed to read {repos_dir}"))?
+        .filter_map(Result::ok)
+        .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.path()).ok())
+        .filter(|dir| dir.join(".complete.json").as_std_path().exists())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let mut report = ExtractReport::default();
+    for entry_dir in entries {
+        let Some(entry_name) = entry_dir.file_name() else {
+            continue;
+        };
+        let counts = entry_counts(&entry_dir)?;
+        if counts.modules > 0 {
+            report.packages.insert(entry_name.to_owned(), counts);
+        }
+    }
+    Ok(report)
+}
+
+fn entry_counts(entry_dir: &Utf8Path) -> anyhow::Result<ExtractionCounts> {
+    let db = TestDatabase::new();
+    let mut counts = ExtractionCounts::default();
+
+    for file_path in Corpus::extraction_targets_in(entry_dir) {
+        let source = fs::read_to_string(file_path.as_std_path())
+            .with_context(|| format!("failed to read {file_path}"))?;
+        let Ok(module) = PythonModuleName::parse(&module_name_from_file(&file_path)) else {
+            continue;
+        };
+        db.add_file(file_path.as_str(), &source)?;
+        let file = db.file(&file_path)?;
+
+        let key = TemplateLibraryId::new(&db, Some(file), module.clone());
+        for symbol in template_library_definition_facts(&db, key).symbols() {
+            match symbol.kind {
+                TemplateSymbolKind::Tag => counts.tags += 1,
+                TemplateSymbolKind::Filter => counts.filters += 1,
+            }
+        }
+        let bundle = extract_bundle(&db, file, module);
+        counts.modules += 1;
+        counts.tag_rules += bundle.tag_rules.len();
+        counts.block_specs += bundle.block_specs.0.len();
+        counts.filter_arities += bundle.filter_arities.len();
+    }
+
+    Ok(counts)
+}
+
+/// One line per corpus entry whose counts changed between two reports.
+fn diff_reports(previous: &ExtractReport, current: &ExtractReport) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (name, old) in &previous.packages {
+        if !current.packages.contains_key(name) {
+            changes.push(format!("- {name} ({})", describe(*old)));
+        }
+    }
+    for (name, new) in &current.packages {
+        match previous.packages.get(name) {
+            None => changes.push(format!("+ {name} ({})", describe(*new))),
+            Some(old) if old != new => {
+                let moved = old
+                    .fields()
+                    .into_iter()
+                    .zip(new.fields())
+                    .filter(|((_, before), (_, after))| before != after)
+                    .map(|((field, before), (_, after))| format!("{field} {before} -> {after}"))
+                    .collect::<Vec<_>>();
+                changes.push(format!("~ {name}: {}", moved.join(", ")));
+            }
+            Some(_) => {}
+        }
+    }
+    changes
+}
+
+fn describe(counts: ExtractionCounts) -> String {
+    counts
+        .fields()
+        .into_iter()
+        .map(|(field, count)| format!("{field} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(entries: &[(&str, ExtractionCounts)]) -> ExtractReport {
+        ExtractReport {
+            packages: entries
+                .iter()
+                .map(|(name, counts)| ((*name).to_string(), *counts))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_lists_added_removed_and_changed_entries() {
+        let counts = ExtractionCounts {
+            modules: 2,
+            tags: 10,
+            filters: 4,
+            tag_rules: 8,
+            block_specs: 3,
+            filter_arities: 4,
+        };
+        let previous = report(&[
+            ("django-5.2", counts),
+            ("sentry", counts),
+            ("wagtail-7.3", counts),
+        ]);
+        let current = report(&[
+            ("django-5.2", counts),
+            ("django-6.0", counts),
+            (
+                "wagtail-7.3",
+                ExtractionCounts {
+                    tag_rules: 6,
+                    block_specs: 2,
+                    ..counts
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            diff_reports(&previous, &current),
+            [
+                "- sentry (modules 2, tags 10, filters 4, tag_rules 8, block_specs 3, filter_arities 4)",
+                "+ django-6.0 (modules 2, tags 10, filters 4, tag_rules 8, block_specs 3, filter_arities 4)",
+                "~ wagtail-7.3: tag_rules 8 -> 6, block_specs 3 -> 2",
+            ]
+        );
+        assert!(diff_reports(&current, &current).is_empty());
+    }
+}
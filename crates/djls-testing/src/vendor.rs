@@ -4,8 +4,17 @@ use anyhow::Context as _;
 use anyhow::bail;
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
+use djls_project::FallbackLibrary;
+use djls_project::FallbackPackage;
+use djls_project::PythonModuleName;
+use djls_project::TemplateLibraryId;
+use djls_project::TemplateSymbolKind;
+use djls_project::template_library_definition_facts;
+use djls_source::File;
 
 use crate::Corpus;
+use crate::TestDatabase;
+use crate::extract_bundle;
 
 pub struct VendorSpecFixturesOptions {
     pub check: bool,
@@ -42,10 +51,48 @@ pub fn vendor_spec_fixtures(options: VendorSpecFixturesOptions) -> anyhow::Resul
     Ok(())
 }
 
+pub struct VendorFallbackLibrariesOptions {
+    pub check: bool,
+    pub output_dir: Option<Utf8PathBuf>,
+}
+
+/// Regenerate the Template Libraries djls-project bundles for projects whose
+/// Python environment cannot be inspected.
+pub fn vendor_fallback_libraries(options: VendorFallbackLibrariesOptions) -> anyhow::Result<()> {
+    let corpus = Corpus::require()?;
+    let output_dir = options
+        .output_dir
+        .unwrap_or_else(default_fallback_library_dir);
+
+    let mut stale = Vec::new();
+    for package in FALLBACK_PACKAGES {
+        let content = render_fallback_package(&corpus, package)?;
+        let output_path = output_dir.join(package.output_file);
+        if options.check {
+            check_fixture(&output_path, &content, &mut stale)?;
+        } else {
+            write_fixture(&output_path, &content)?;
+        }
+    }
+
+    if !stale.is_empty() {
+        bail!(
+            "bundled fallback libraries are out of date:\n  {}\nrun `just corpus vendor-fallback-libraries` to update them",
+            stale.join("\n  ")
+        );
+    }
+
+    Ok(())
+}
+
 fn default_spec_fixture_dir() -> Utf8PathBuf {
     Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../djls-project/src/templates/tags/testdata")
 }
 
+fn default_fallback_library_dir() -> Utf8PathBuf {
+    Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../djls-project/src/templates/fallback")
+}
+
 fn write_fixture(path: &Utf8Path, content: &str) -> anyhow::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent.as_std_path())
@@ -97,6 +144,95 @@ fn render_fixture(corpus: &Corpus, fixture: &SpecFixture) -> anyhow::Result<Stri
     Ok(format!("{}\n", chunks.join("\n\n").trim_end()))
 }
 
+fn render_fallback_package(
+    corpus: &Corpus,
+    package: &FallbackPackageSource,
+) -> anyhow::Result<String> {
+    let package_dir = corpus.latest_package(package.package).ok_or_else(|| {
+        anyhow::anyhow!(
+            "synced corpus package `{}` not found; run `just corpus sync`",
+            package.package
+        )
+    })?;
+    let entry_name = package_dir.file_name().ok_or_else(|| {
+        anyhow::anyhow!("corpus package path has no final component: {package_dir}")
+    })?;
+
+    let db = TestDatabase::new();
+    let mut libraries = Vec::new();
+    for library in package.libraries {
+        let source_path = package_dir.join(library.relative_path);
+        let source = fs::read_to_string(source_path.as_std_path())
+            .with_context(|| format!("failed to read {source_path}"))?;
+        let module = library
+            .relative_path
+            .trim_end_matches(".py")
+            .replace('/', ".");
+        let module = PythonModuleName::parse(&module)
+            .with_context(|| format!("invalid module name derived from `{source_path}`"))?;
+        db.add_file(source_path.as_str(), &source)?;
+        let file = db.file(&source_path)?;
+        libraries.push(extract_fallback_library(&db, file, library.app, module));
+    }
+    libraries.sort_by(|a, b| (&a.app, &a.load_name).cmp(&(&b.app, &b.load_name)));
+
+    let package = FallbackPackage {
+        corpus: entry_name.to_owned(),
+        libraries,
+    };
+    Ok(format!("{}\n", serde_json::to_string_pretty(&package)?))
+}
+
+fn extract_fallback_library(
+    db: &TestDatabase,
+    file: File,
+    app: &str,
+    module: PythonModuleName,
+) -> FallbackLibrary {
+    let key = TemplateLibraryId::new(db, Some(file), module.clone());
+    let mut tags = Vec::new();
+    let mut filters = Vec::new();
+    for symbol in template_library_definition_facts(db, key).symbols() {
+        match symbol.kind {
+            TemplateSymbolKind::Tag => tags.push(symbol.name().to_owned()),
+            TemplateSymbolKind::Filter => filters.push(symbol.name().to_owned()),
+        }
+    }
+    tags.sort();
+    filters.sort();
+
+    let bundle = extract_bundle(db, file, module.clone());
+    let load_name = module
+        .as_str()
+        .rsplit('.')
+        .next()
+        .unwrap_or(module.as_str())
+        .to_owned();
+    FallbackLibrary {
+        app: app.to_owned(),
+        load_name,
+        module: module.as_str().to_owned(),
+        tags,
+        filters,
+        tag_rules: bundle
+            .tag_rules
+            .iter()
+            .map(|(key, rule)| (key.name.clone(), rule.as_ref().clone()))
+            .collect(),
+        filter_arities: bundle
+            .filter_arities
+            .iter()
+            .map(|(key, arity)| (key.name.clone(), arity.clone()))
+            .collect(),
+        block_specs: bundle
+            .block_specs
+            .0
+            .iter()
+            .map(|(key, spec)| (key.name.clone(), spec.clone()))
+            .collect(),
+    }
+}
+
 fn fixture_header(entry_name: &str, relative_path: &str) -> String {
     format!(
         "# Vendored unit-test fixture.\n# Corpus: {entry_name}/{relative_path}\n# Keep snippets minimal: live corpus drift is covered by crates/djls-project/tests/corpus*.rs.\n\nfrom django import template\n\nregister = template.Library()"
@@ -315,6 +451,101 @@ const SPEC_FIXTURES: &[SpecFixture] = &[
     },
 ];
 
+struct FallbackPackageSource {
+    package: &'static str,
+    output_file: &'static str,
+    libraries: &'static [FallbackLibrarySource],
+}
+
+/// A Template Library module and the installed app that provides it.
+struct FallbackLibrarySource {
+    app: &'static str,
+    relative_path: &'static str,
+}
+
+// django-debug-toolbar registers no Template Library, so there is nothing to bundle for it.
+// Wagtail's admin-only libraries are left out: they are only loaded by Wagtail's own templates.
+const FALLBACK_PACKAGES: &[FallbackPackageSource] = &[
+    FallbackPackageSource {
+        package: "django-allauth",
+        output_file: "django-allauth.json",
+        libraries: &[
+            FallbackLibrarySource {
+                app: "allauth",
+                relative_path: "allauth/templatetags/allauth.py",
+            },
+            FallbackLibrarySource {
+                app: "allauth.account",
+                relative_path: "allauth/account/templatetags/account.py",
+            },
+            FallbackLibrarySource {
+                app: "allauth.socialaccount",
+                relative_path: "allauth/socialaccount/templatetags/socialaccount.py",
+            },
+        ],
+    },
+    FallbackPackageSource {
+        package: "django-crispy-forms",
+        output_file: "django-crispy-forms.json",
+        libraries: &[
+            FallbackLibrarySource {
+                app: "crispy_forms",
+                relative_path: "crispy_forms/templatetags/crispy_forms_field.py",
+            },
+            FallbackLibrarySource {
+                app: "crispy_forms",
+                relative_path: "crispy_forms/templatetags/crispy_forms_filters.py",
+            },
+            FallbackLibrarySource {
+                app: "crispy_forms",
+                relative_path: "crispy_forms/templatetags/crispy_forms_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "crispy_forms",
+                relative_path: "crispy_forms/templatetags/crispy_forms_utils.py",
+            },
+        ],
+    },
+    FallbackPackageSource {
+        package: "wagtail",
+        output_file: "wagtail.json",
+        libraries: &[
+            FallbackLibrarySource {
+                app: "wagtail",
+                relative_path: "wagtail/templatetags/wagtail_cache.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail",
+                relative_path: "wagtail/templatetags/wagtailcore_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.admin",
+                relative_path: "wagtail/admin/templatetags/wagtailuserbar.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.contrib.routable_page",
+                relative_path: "wagtail/contrib/routable_page/templatetags/wagtailroutablepage_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.contrib.search_promotions",
+                relative_path: "wagtail/contrib/search_promotions/templatetags/wagtailsearchpromotions_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.contrib.settings",
+                relative_path: "wagtail/contrib/settings/templatetags/wagtailsettings_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.embeds",
+                relative_path: "wagtail/embeds/templatetags/wagtailembeds_tags.py",
+            },
+            FallbackLibrarySource {
+                app: "wagtail.images",
+                relative_path: "wagtail/images/templatetags/wagtailimages_tags.py",
+            },
+        ],
+    },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
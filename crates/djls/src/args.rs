@@ -2,7 +2,7 @@ use clap::Parser;
 
 #[derive(Parser, Debug, Clone)]
 pub(crate) struct Args {
-    /// Do not print any output.
+    /// Do not print any output other than a final summary.
     #[arg(global = true, long, short, conflicts_with = "verbose")]
     pub quiet: bool,
 
@@ -1,10 +1,12 @@
+use std::fmt;
+
 use camino::Utf8Path;
 use camino::Utf8PathBuf;
 use djls_conf::DiagnosticSeverity;
 use djls_conf::DiagnosticsConfig;
 use djls_semantic::Db as SemanticDb;
-use djls_semantic::TemplateDiagnostics;
-use djls_semantic::ValidationError;
+use djls_semantic::Fix;
+use djls_semantic::apply_fixes;
 use djls_semantic::collect_template_diagnostics;
 use djls_source::Diagnostic;
 use djls_source::DiagnosticRenderer;
@@ -13,82 +15,217 @@ use djls_source::FileReadError;
 use djls_source::Severity;
 use djls_source::SourceText;
 use djls_source::Span;
-use djls_templates::TemplateError;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Identity of one diagnostic that survives edits elsewhere in its Template.
+///
+/// Offsets shift whenever text above a diagnostic changes, so watch mode compares the code,
+/// message, and covered source text instead.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DiagnosticKey {
+    code: String,
+    message: String,
+    text: String,
+}
+
+impl DiagnosticKey {
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Source text covered by the diagnostic's primary span.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn new(code: String, message: String, source: &str, span: Span) -> Self {
+        let text = source
+            .get(span.start_usize()..span.end_usize())
+            .unwrap_or_default()
+            .to_string();
+        Self {
+            code,
+            message,
+            text,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+/// A rendered diagnostic and the key used to diff it against an earlier check.
+#[derive(Clone, Debug)]
+pub struct RenderedDiagnostic {
+    pub key: DiagnosticKey,
+    pub severity: DiagnosticSeverity,
+    pub output: String,
+}
+
+/// One syntax or validation diagnostic, reduced to what terminal output needs.
+///
+/// Unlike the errors it is built from, it can be serialized, so `djls check` can cache it and
+/// render it again on a later run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckedDiagnostic {
+    code: String,
+    message: String,
+    /// Where the diagnostic points, or `None` when it cannot be rendered.
+    span: Option<Span>,
+    related: Vec<(Span, String)>,
+    /// Not cached: `djls check --fix` always checks Templates afresh.
+    #[serde(skip)]
+    fix: Option<Fix>,
+}
 
 /// A readable Template and its collected diagnostics, ready for terminal output.
 pub struct CheckedTemplate {
     path: Utf8PathBuf,
     source: SourceText,
-    diagnostics: TemplateDiagnostics,
+    diagnostics: Vec<CheckedDiagnostic>,
 }
 
 impl CheckedTemplate {
+    /// Pair a Template with diagnostics collected earlier, in terminal output order.
+    #[must_use]
+    pub fn new(path: Utf8PathBuf, source: SourceText, diagnostics: Vec<CheckedDiagnostic>) -> Self {
+        Self {
+            path,
+            source,
+            diagnostics,
+        }
+    }
+
     /// Return the Template path used in rendered diagnostics.
     #[must_use]
     pub fn path(&self) -> &Utf8Path {
         &self.path
     }
 
+    /// Return every collected diagnostic, including those `config` disables.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[CheckedDiagnostic] {
+        &self.diagnostics
+    }
+
     /// Return whether syntax or validation produced any diagnostics.
     #[must_use]
     pub fn has_diagnostics(&self) -> bool {
-        self.diagnostics.has_diagnostics()
+        !self.diagnostics.is_empty()
     }
 
     /// Count diagnostics enabled by `config` that can be rendered.
     #[must_use]
     pub fn renderable_diagnostic_count(&self, config: &DiagnosticsConfig) -> usize {
         self.diagnostics
-            .template_errors
             .iter()
-            .filter(|error| diagnostic_is_enabled(config, error.diagnostic_code()))
+            .filter(|diagnostic| {
+                diagnostic_is_enabled(config, &diagnostic.code) && diagnostic.span.is_some()
+            })
             .count()
-            + self
-                .diagnostics
-                .validation_errors
-                .iter()
-                .filter(|error| {
-                    diagnostic_is_enabled(config, error.code()) && error.primary_span().is_some()
-                })
-                .count()
+    }
+
+    /// Fixes offered by enabled diagnostics.
+    ///
+    /// Diagnostics are kept in primary span order, so walking them backwards puts the end tag
+    /// of the innermost unclosed block first when several blocks close at one offset.
+    #[must_use]
+    pub fn fixes(&self, config: &DiagnosticsConfig) -> Vec<Fix> {
+        self.diagnostics
+            .iter()
+            .rev()
+            .filter(|diagnostic| diagnostic_is_enabled(config, &diagnostic.code))
+            .filter_map(|diagnostic| diagnostic.fix.clone())
+            .collect()
+    }
+
+    /// Source text with every applicable fix applied, and how many were applied, or `None`
+    /// when no enabled diagnostic offers a fix.
+    #[must_use]
+    pub fn fixed_source(&self, config: &DiagnosticsConfig) -> Option<(String, usize)> {
+        let fixes = self.fixes(config);
+        if fixes.is_empty() {
+            return None;
+        }
+        Some(apply_fixes(self.source.as_str(), &fixes))
     }
 
     /// Render enabled diagnostics in terminal output order.
     #[must_use]
     pub fn render(&self, config: &DiagnosticsConfig, fmt: &DiagnosticRenderer) -> Vec<String> {
-        let mut results = Vec::with_capacity(self.renderable_diagnostic_count(config));
+        self.render_keyed(config, fmt)
+            .into_iter()
+            .map(|diagnostic| diagnostic.output)
+            .collect()
+    }
+
+    /// Render enabled diagnostics in terminal output order, keeping each one's identity.
+    #[must_use]
+    pub fn render_keyed(
+        &self,
+        config: &DiagnosticsConfig,
+        fmt: &DiagnosticRenderer,
+    ) -> Vec<RenderedDiagnostic> {
         let path = self.path.as_str();
         let source = self.source.as_str();
 
-        for error in &self.diagnostics.template_errors {
-            if let Some(output) = render_template_error(source, path, error, config, fmt) {
-                results.push(output);
-            }
-        }
-
-        for error in &self.diagnostics.validation_errors {
-            if let Some(output) = render_validation_error(source, path, error, config, fmt) {
-                results.push(output);
-            }
-        }
-
-        results
+        self.diagnostics
+            .iter()
+            .filter_map(|diagnostic| render_diagnostic(source, path, diagnostic, config, fmt))
+            .collect()
     }
 }
 
 /// Read, validate, and collect one Template for terminal reporting.
+///
+/// Syntax errors come first, then validation errors in primary span order.
 pub fn check_template(db: &dyn SemanticDb, file: File) -> Result<CheckedTemplate, FileReadError> {
     let source = file.try_source(db)?;
-    let mut diagnostics = collect_template_diagnostics(db, file);
-    diagnostics
+    let mut collected = collect_template_diagnostics(db, file);
+    collected
         .validation_errors
         .sort_by_cached_key(|error| error.primary_span().map_or(0, Span::start));
 
-    Ok(CheckedTemplate {
-        path: file.path(db).to_owned(),
+    let template_errors = collected.template_errors.iter().map(|error| {
+        let span = error.primary_span().map_or_else(
+            || Span::new(0, 0),
+            |(start, length)| Span::new(start, length),
+        );
+        CheckedDiagnostic {
+            code: error.diagnostic_code().to_string(),
+            message: error.to_string(),
+            span: Some(span),
+            related: Vec::new(),
+            fix: None,
+        }
+    });
+    let validation_errors = collected
+        .validation_errors
+        .iter()
+        .map(|error| CheckedDiagnostic {
+            code: error.code().to_string(),
+            message: error.to_string(),
+            span: error.primary_span(),
+            related: error.related_spans(),
+            fix: error.fix(),
+        });
+
+    Ok(CheckedTemplate::new(
+        file.path(db).to_owned(),
         source,
-        diagnostics,
-    })
+        template_errors.chain(validation_errors).collect(),
+    ))
 }
 
 fn diagnostic_is_enabled(config: &DiagnosticsConfig, code: &str) -> bool {
@@ -104,62 +241,32 @@ fn to_render_severity(severity: DiagnosticSeverity) -> Severity {
     }
 }
 
-fn render_template_error(
-    source: &str,
-    path: &str,
-    error: &TemplateError,
-    config: &DiagnosticsConfig,
-    fmt: &DiagnosticRenderer,
-) -> Option<String> {
-    let code = error.diagnostic_code();
-    let severity = config.get_severity(code);
-    if severity == DiagnosticSeverity::Off {
-        return None;
-    }
-
-    let message = error.to_string();
-    let span = error.primary_span().map_or_else(
-        || Span::new(0, 0),
-        |(start, length)| Span::new(start, length),
-    );
-    let diag = Diagnostic::new(
-        source,
-        path,
-        code,
-        &message,
-        to_render_severity(severity),
-        span,
-        "",
-    );
-    Some(fmt.render(&diag))
-}
-
-fn render_validation_error(
+fn render_diagnostic(
     source: &str,
     path: &str,
-    error: &ValidationError,
+    diagnostic: &CheckedDiagnostic,
     config: &DiagnosticsConfig,
     fmt: &DiagnosticRenderer,
-) -> Option<String> {
-    let code = error.code();
+) -> Option<RenderedDiagnostic> {
+    let code = diagnostic.code.as_str();
     let severity = config.get_severity(code);
     if severity == DiagnosticSeverity::Off {
         return None;
     }
 
-    let span = error.primary_span()?;
-    let message = error.to_string();
+    let span = diagnostic.span?;
+    let message = diagnostic.message.as_str();
     let render_severity = to_render_severity(severity);
 
-    let mut diag = Diagnostic::new(source, path, code, &message, render_severity, span, "");
+    let mut diag = Diagnostic::new(source, path, code, message, render_severity, span, "");
 
-    if let ValidationError::UnbalancedStructure {
-        closing_span: Some(cs),
-        ..
-    } = error
-    {
-        diag = diag.annotation(*cs, "", false);
+    for (related_span, label) in &diagnostic.related {
+        diag = diag.annotation(*related_span, label, false);
     }
 
-    Some(fmt.render(&diag))
+    Some(RenderedDiagnostic {
+        output: fmt.render(&diag),
+        severity,
+        key: DiagnosticKey::new(code.to_string(), message.to_string(), source, span),
+    })
 }
@@ -25,8 +25,16 @@ pub(crate) fn run(args: Vec<String>) -> Result<()> {
     });
 
     let result = match &cli.command {
+        DjlsCommand::Blocks(cmd) => cmd.execute(&cli.args),
         DjlsCommand::Check(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Explain(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::I18nReport(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Inspect(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Parse(cmd) => cmd.execute(&cli.args),
         DjlsCommand::Serve(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Stats(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Status(cmd) => cmd.execute(&cli.args),
+        DjlsCommand::Trace(cmd) => cmd.execute(&cli.args),
     };
 
     match result {
@@ -37,7 +45,7 @@ pub(crate) fn run(args: Vec<String>) -> Result<()> {
                 msg.push_str(", caused by ");
                 msg.push_str(&source.to_string());
             }
-            Exit::error().with_message(msg).process_exit()
+            Exit::internal_error().with_message(msg).process_exit()
         }
     }
 }
@@ -1,6 +1,14 @@
+mod blocks;
 mod check;
 mod common;
+mod explain;
+mod i18n_report;
+mod inspect;
+mod parse;
 mod serve;
+mod stats;
+mod status;
+mod trace;
 
 use anyhow::Result;
 use clap::Subcommand;
@@ -14,8 +22,24 @@ pub(crate) trait Command {
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum DjlsCommand {
+    /// List which blocks from a template's extends chain it overrides
+    Blocks(self::blocks::Blocks),
     /// Check Django template files for errors
     Check(self::check::Check),
+    /// Explain a diagnostic code
+    Explain(self::explain::Explain),
+    /// List template text that is not wrapped in a translation tag
+    I18nReport(self::i18n_report::I18nReport),
+    /// Inspect what djls has discovered about the project
+    Inspect(self::inspect::Inspect),
+    /// Print the tokens, nodes, and block tree of a template
+    Parse(self::parse::Parse),
     /// Start the LSP server
     Serve(self::serve::Serve),
+    /// Print size and branching measurements for each template
+    Stats(self::stats::Stats),
+    /// Print the project's environment, Django version, and inventory size
+    Status(self::status::Status),
+    /// Print a template flattened through its extends chain
+    Trace(self::trace::Trace),
 }
@@ -0,0 +1,284 @@
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use clap::ValueEnum;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_project::run_django_discovery;
+use djls_semantic::BlockCoverage;
+use djls_semantic::BlockSite;
+use djls_semantic::ChainEnd;
+use djls_semantic::CoveredBlock;
+use djls_semantic::block_coverage;
+use djls_source::File;
+use djls_source::OsFileSystem;
+use djls_source::path_to_file;
+use serde::Serialize;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Blocks {
+    /// Template file to audit.
+    path: Utf8PathBuf,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Aligned columns for reading in a terminal.
+    Table,
+    /// A JSON object with the extends chain and every block.
+    Json,
+}
+
+impl Command for Blocks {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let path = if self.path.is_relative() {
+            project_root.join(&self.path)
+        } else {
+            self.path.clone()
+        };
+
+        let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
+        let mut db = DjangoDatabase::new(
+            Arc::new(OsFileSystem::default()),
+            &settings,
+            Some(&project_root),
+        );
+        db.apply_project_settings(settings);
+        run_django_discovery(&mut db)
+            .context("Failed to discover Django environment")?
+            .context("No Project configured for blocks")?;
+        let project = db.project().context("No Project configured for blocks")?;
+
+        let file =
+            path_to_file(&db, &path).with_context(|| format!("Failed to open template '{path}'"))?;
+        let coverage = block_coverage(&db, project, file);
+        let report = CoverageReport::new(&db, &project_root, file, &coverage);
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        match self.format {
+            OutputFormat::Table => write_table(&mut stdout, &report)?,
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut stdout, &report)?;
+                writeln!(stdout)?;
+            }
+        }
+
+        if coverage.ancestors.is_empty() && coverage.end == ChainEnd::Root {
+            eprintln!("note: '{}' does not extend another template", report.template);
+        } else if let Some(note) = chain_end_note(&coverage.end) {
+            eprintln!("note: {note}");
+        }
+
+        Ok(Exit::success())
+    }
+}
+
+#[derive(Serialize)]
+struct CoverageReport {
+    template: String,
+    extends: Vec<String>,
+    blocks: Vec<BlockEntry>,
+}
+
+#[derive(Serialize)]
+struct BlockEntry {
+    name: String,
+    status: BlockStatus,
+    /// Where the nearest ancestor defines the block.
+    base: Option<String>,
+    /// Where the audited template defines the block.
+    #[serde(rename = "override")]
+    own: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BlockStatus {
+    /// Offered by an ancestor and redefined by the template.
+    Overridden,
+    /// Offered by an ancestor and left alone.
+    Untouched,
+    /// Defined by the template but offered by no ancestor.
+    New,
+}
+
+impl BlockStatus {
+    fn of(block: &CoveredBlock) -> Self {
+        match (block.base, block.own) {
+            (Some(_), Some(_)) => Self::Overridden,
+            (Some(_), None) => Self::Untouched,
+            (None, _) => Self::New,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Overridden => "overridden",
+            Self::Untouched => "untouched",
+            Self::New => "new",
+        }
+    }
+}
+
+impl CoverageReport {
+    fn new(
+        db: &DjangoDatabase,
+        project_root: &Utf8Path,
+        file: File,
+        coverage: &BlockCoverage,
+    ) -> Self {
+        let location = |site: BlockSite| {
+            let line = site
+                .file
+                .line_index(db)
+                .to_line_col(site.name_span.start_offset())
+                .line();
+            format!("{}:{}", display_path(db, project_root, site.file), line + 1)
+        };
+
+        Self {
+            template: display_path(db, project_root, file),
+            extends: coverage
+                .ancestors
+                .iter()
+                .map(|ancestor| display_path(db, project_root, *ancestor))
+                .collect(),
+            blocks: coverage
+                .blocks
+                .iter()
+                .map(|block| BlockEntry {
+                    name: block.name.clone(),
+                    status: BlockStatus::of(block),
+                    base: block.base.map(location),
+                    own: block.own.map(location),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn display_path(db: &DjangoDatabase, project_root: &Utf8Path, file: File) -> String {
+    let path = file.path(db).as_path();
+    path.strip_prefix(project_root).unwrap_or(path).to_string()
+}
+
+fn write_table(out: &mut impl Write, report: &CoverageReport) -> Result<()> {
+    if !report.extends.is_empty() {
+        writeln!(
+            out,
+            "{} extends {}",
+            report.template,
+            report.extends.join(" -> ")
+        )?;
+        writeln!(out)?;
+    }
+
+    let header = ["BLOCK", "STATUS", "BASE", "OVERRIDE"];
+    let rows: Vec<[String; 4]> = report
+        .blocks
+        .iter()
+        .map(|block| {
+            [
+                block.name.clone(),
+                block.status.as_str().to_string(),
+                block.base.clone().unwrap_or_else(|| "-".to_string()),
+                block.own.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let header = header.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+fn chain_end_note(end: &ChainEnd) -> Option<String> {
+    match end {
+        ChainEnd::Root => None,
+        ChainEnd::Dynamic { .. } => Some(
+            "the topmost template extends a variable, so blocks above it are not listed"
+                .to_string(),
+        ),
+        ChainEnd::Unresolved { name } => Some(format!(
+            "parent template '{name}' was not found, so blocks above it are not listed"
+        )),
+        ChainEnd::InconclusiveParent { name } => Some(format!(
+            "parent template '{name}' may resolve to more than one file, \
+             so blocks above it are not listed"
+        )),
+        ChainEnd::Cycle => Some("the extends chain loops back on itself".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_lists_chain_then_aligned_blocks() {
+        let report = CoverageReport {
+            template: "templates/page.html".to_string(),
+            extends: vec![
+                "templates/section.html".to_string(),
+                "templates/base.html".to_string(),
+            ],
+            blocks: vec![
+                BlockEntry {
+                    name: "title".to_string(),
+                    status: BlockStatus::Overridden,
+                    base: Some("templates/base.html:1".to_string()),
+                    own: Some("templates/page.html:2".to_string()),
+                },
+                BlockEntry {
+                    name: "sidebar".to_string(),
+                    status: BlockStatus::Untouched,
+                    base: Some("templates/base.html:3".to_string()),
+                    own: None,
+                },
+            ],
+        };
+        let mut out = Vec::new();
+        write_table(&mut out, &report).expect("writing to a Vec should succeed");
+
+        assert_eq!(
+            String::from_utf8(out).expect("table output should be UTF-8"),
+            "templates/page.html extends templates/section.html -> templates/base.html\n\
+             \n\
+             BLOCK    STATUS      BASE                   OVERRIDE\n\
+             title    overridden  templates/base.html:1  templates/page.html:2\n\
+             sidebar  untouched   templates/base.html:3  -\n"
+        );
+    }
+}
@@ -1,3 +1,10 @@
+mod baseline;
+mod cache;
+mod fix;
+mod shadowed;
+mod watch;
+
+use std::hash::Hasher;
 use std::io::Read as _;
 use std::io::Result as IoResult;
 use std::io::Write as _;
@@ -33,8 +40,15 @@ use rayon::scope;
 
 use crate::args::Args;
 use crate::commands::Command;
+use crate::commands::check::baseline::Baseline;
+use crate::commands::check::baseline::DEFAULT_BASELINE_FILE;
+use crate::commands::check::cache::CheckCache;
+use crate::commands::check::fix::check_and_fix_files;
+use crate::commands::check::shadowed::report_shadowed_templates;
+use crate::commands::check::watch::WatchSession;
 use crate::commands::common::ColorMode;
 use crate::commands::common::discover_files;
+use crate::commands::common::discovery_roots;
 use crate::commands::common::resolve_project_root;
 use crate::exit::Exit;
 
@@ -130,6 +144,12 @@ pub(crate) struct Check {
     /// Django project.
     paths: Vec<Utf8PathBuf>,
 
+    /// Read more Template paths, one per line, from a file, or from stdin with `-`.
+    /// An empty list checks nothing instead of falling back to Template discovery, so
+    /// pre-commit and lint-staged runs with no matching files exit cleanly.
+    #[arg(long, value_name = "PATH")]
+    files_from: Option<Utf8PathBuf>,
+
     /// Select specific diagnostic codes to enable (e.g. S100,S101).
     #[arg(long, value_delimiter = ',')]
     select: Vec<String>,
@@ -163,6 +183,49 @@ pub(crate) struct Check {
     /// When to use colors.
     #[arg(long, value_enum, default_value_t = ColorMode::Auto)]
     color: ColorMode,
+
+    /// Run the lint rule servers listed under `[[plugins]]` in the project configuration.
+    /// Plugins execute commands the project names, so they only run with this flag.
+    #[arg(long, default_value_t = false)]
+    allow_plugins: bool,
+
+    /// Apply fixes for diagnostics that have exactly one mechanical resolution, such as a
+    /// missing end tag, then report the diagnostics that remain.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["watch", "write_baseline"])]
+    fix: bool,
+
+    /// Keep running and re-check Templates, and the Templates that extend them, as they
+    /// change on disk. Only new and resolved diagnostics are printed after the first check.
+    #[arg(short = 'w', long, default_value_t = false)]
+    watch: bool,
+
+    /// Suppress diagnostics recorded in this baseline file. Defaults to
+    /// `.djls-baseline.json` in the project root when that file exists.
+    #[arg(long, value_name = "PATH", conflicts_with = "no_baseline")]
+    baseline: Option<Utf8PathBuf>,
+
+    /// Report every diagnostic, even those recorded in a baseline file.
+    #[arg(long, default_value_t = false)]
+    no_baseline: bool,
+
+    /// Record every current diagnostic in a baseline file and exit successfully.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["watch", "baseline", "no_baseline"])]
+    write_baseline: Option<Utf8PathBuf>,
+
+    /// Store results in this directory and reuse them on later runs for Templates whose
+    /// source, and the sources of the Templates they extend or include, are unchanged. Not
+    /// used when reading a Template from stdin.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["fix", "watch"])]
+    cache_dir: Option<Utf8PathBuf>,
+
+    /// List every Template that another template directory hides from Django's loaders,
+    /// with the file that is loaded instead, rather than checking Templates.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["paths", "files_from", "fix", "watch", "write_baseline", "cache_dir"]
+    )]
+    shadowed: bool,
 }
 
 fn require_configured_discovery(
@@ -177,15 +240,39 @@ impl Command for Check {
     fn execute(&self, args: &Args) -> Result<Exit> {
         let project_root = resolve_project_root()?;
         let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
-        let input = CheckInput::collect(&self.paths)?;
+        let Some(paths) = self.requested_paths()? else {
+            return Ok(Exit::success());
+        };
+        let reads_stdin = paths.iter().any(|path| path.as_str() == "-");
+        if self.watch && reads_stdin {
+            bail!("Cannot use --watch when reading a Template from stdin");
+        }
+        if self.fix && reads_stdin {
+            bail!("Cannot use --fix when reading a Template from stdin");
+        }
+        let input = CheckInput::collect(&paths)?;
 
         let config = build_diagnostics_config(&settings, &self.select, &self.ignore);
         let fmt = pick_renderer(self.color);
         let quiet = args.quiet;
 
         let mut db = DjangoDatabase::new(input.file_system(), &settings, Some(&project_root));
+        let uses_cache = self.cache_dir.is_some() && matches!(input, CheckInput::Files { .. });
+        let cache_settings = uses_cache.then(|| settings.clone());
         db.apply_project_settings(settings);
-        require_configured_discovery(run_django_discovery(&mut db))?;
+        db.set_plugins_allowed(self.allow_plugins);
+        let facts = require_configured_discovery(run_django_discovery(&mut db))?;
+        if self.shadowed {
+            return report_shadowed_templates(&db, &project_root, quiet);
+        }
+        let cache = self
+            .cache_dir
+            .as_deref()
+            .zip(cache_settings)
+            .map(|(dir, settings)| {
+                let plugins_root = self.allow_plugins.then_some(project_root.as_path());
+                CheckCache::open(dir, cache::fingerprint(&settings, &facts, plugins_root))
+            });
 
         let walk_options = WalkOptions {
             hidden: self.hidden,
@@ -194,8 +281,8 @@ impl Command for Check {
             follow_links: self.follow,
             max_depth: self.max_depth,
         };
-        let files = input.files(&self.paths, &db, &project_root, &walk_options);
-        if files.is_empty() {
+        let files = input.files(&paths, &db, &project_root, &walk_options);
+        if files.is_empty() && !self.watch && self.write_baseline.is_none() {
             return Ok(Exit::success());
         }
 
@@ -204,17 +291,108 @@ impl Command for Check {
         prepare_project_template_analysis(&db)
             .context("Failed to prepare project Template analysis")?;
 
-        let results = check_files_parallel(db, files)?;
-        report_results(results, &config, &fmt, quiet, input.summary())
+        if self.watch {
+            let roots = discovery_roots(&paths, &db, &project_root);
+            let results = check_files_parallel(db.clone(), files.clone())?;
+            return WatchSession::new(db, files, &config, &fmt, quiet).run(results, &roots);
+        }
+
+        let results = if self.fix {
+            check_and_fix_files(db, files, &config, quiet)?
+        } else if let Some(mut cache) = cache {
+            let results = cache.check_files(&db, files)?;
+            cache.save()?;
+            results
+        } else {
+            check_files_parallel(db, files)?
+        };
+        // Discovered Template paths are canonical, so baseline paths are made relative to
+        // the canonical project root.
+        let baseline_root = project_root
+            .canonicalize_utf8()
+            .unwrap_or_else(|_| project_root.clone());
+
+        if let Some(path) = &self.write_baseline {
+            let baseline = Baseline::from_results(&results, &config, &fmt, &baseline_root);
+            baseline.write(path)?;
+            let count = baseline.len();
+            let word = if count == 1 { "diagnostic" } else { "diagnostics" };
+            return Ok(Exit::success().with_message(format!("Wrote {count} {word} to {path}.")));
+        }
+
+        let baseline = self.load_baseline(&project_root)?;
+        let report = Report {
+            config: &config,
+            fmt: &fmt,
+            quiet,
+            summary_style: input.summary(),
+        };
+        report_results(results, &report, baseline, &baseline_root)
     }
 }
 
-fn report_results(
-    mut results: Vec<CheckedTemplate>,
-    config: &DiagnosticsConfig,
-    fmt: &DiagnosticRenderer,
+impl Check {
+    fn load_baseline(&self, project_root: &Utf8Path) -> Result<Option<Baseline>> {
+        if self.no_baseline {
+            return Ok(None);
+        }
+        match &self.baseline {
+            Some(path) => Baseline::load(path)?
+                .map(Some)
+                .with_context(|| format!("Baseline file {path} does not exist")),
+            None => Baseline::load(&project_root.join(DEFAULT_BASELINE_FILE)),
+        }
+    }
+
+    /// Positional paths plus any read with `--files-from`.
+    ///
+    /// Returns `None` when a file list was given but named nothing to check.
+    fn requested_paths(&self) -> Result<Option<Vec<Utf8PathBuf>>> {
+        let Some(list) = &self.files_from else {
+            return Ok(Some(self.paths.clone()));
+        };
+
+        let contents = if list.as_str() == "-" {
+            if self.paths.iter().any(|path| path.as_str() == "-") {
+                bail!("Cannot read both a Template and `--files-from` from stdin");
+            }
+            let mut contents = String::new();
+            stdin()
+                .read_to_string(&mut contents)
+                .context("Failed to read file list from stdin")?;
+            contents
+        } else {
+            std::fs::read_to_string(list)
+                .with_context(|| format!("Failed to read file list {list}"))?
+        };
+
+        let mut paths = self.paths.clone();
+        paths.extend(parse_file_list(&contents));
+        Ok((!paths.is_empty()).then_some(paths))
+    }
+}
+
+/// Split a `--files-from` list into paths, one per line, skipping blank lines.
+fn parse_file_list(contents: &str) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(Utf8PathBuf::from)
+}
+
+struct Report<'a> {
+    config: &'a DiagnosticsConfig,
+    fmt: &'a DiagnosticRenderer,
     quiet: bool,
     summary_style: SummaryStyle,
+}
+
+fn report_results(
+    mut results: Vec<CheckedTemplate>,
+    report: &Report<'_>,
+    mut baseline: Option<Baseline>,
+    baseline_root: &Utf8Path,
 ) -> Result<Exit> {
     results.sort_by(|left, right| left.path().cmp(right.path()));
 
@@ -224,8 +402,8 @@ fn report_results(
     let mut stdout = stdout.lock();
 
     for result in results {
-        if quiet {
-            let count = result.renderable_diagnostic_count(config);
+        if report.quiet && baseline.is_none() {
+            let count = result.renderable_diagnostic_count(report.config);
             if count > 0 {
                 file_count += 1;
                 error_count += count;
@@ -233,27 +411,30 @@ fn report_results(
             continue;
         }
 
-        let rendered = result.render(config, fmt);
+        let mut rendered = result.render_keyed(report.config, report.fmt);
+        if let Some(baseline) = &mut baseline {
+            rendered = baseline.suppress(result.path(), baseline_root, rendered);
+        }
         if rendered.is_empty() {
             continue;
         }
 
         file_count += 1;
         error_count += rendered.len();
-        for output in rendered {
-            writeln!(stdout, "{output}\n")?;
+        if report.quiet {
+            continue;
+        }
+        for diagnostic in rendered {
+            writeln!(stdout, "{}\n", diagnostic.output)?;
         }
     }
 
     if error_count == 0 {
         return Ok(Exit::success());
     }
-    if quiet {
-        return Ok(Exit::error());
-    }
 
     let error_word = if error_count == 1 { "error" } else { "errors" };
-    let message = match summary_style {
+    let message = match report.summary_style {
         SummaryStyle::Files => {
             let file_word = if file_count == 1 { "file" } else { "files" };
             format!("Found {error_count} {error_word} in {file_count} {file_word}.")
@@ -296,6 +477,30 @@ fn check_files_parallel(
     rx.into_iter().collect()
 }
 
+/// FNV-1a. Unlike the `std` hashers it gives the same result on every run, so its hashes can
+/// be written to baseline and cache files.
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 struct SingleFileOverlay {
     path: Utf8PathBuf,
     contents: String,
@@ -321,6 +526,14 @@ impl FileSystem for SingleFileOverlay {
         }
     }
 
+    fn read_bytes(&self, path: &Utf8Path) -> IoResult<Vec<u8>> {
+        if path == self.path {
+            Ok(self.contents.clone().into_bytes())
+        } else {
+            self.disk.read_bytes(path)
+        }
+    }
+
     fn exists(&self, path: &Utf8Path) -> bool {
         path == self.path || self.disk.exists(path)
     }
@@ -394,6 +607,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_list_skips_blank_lines_and_surrounding_whitespace() {
+        let paths: Vec<_> =
+            parse_file_list("templates/a.html\n\n  templates/b.html \r\n").collect();
+
+        assert_eq!(
+            paths,
+            [
+                Utf8PathBuf::from("templates/a.html"),
+                Utf8PathBuf::from("templates/b.html"),
+            ]
+        );
+    }
+
     #[test]
     fn missing_project_remains_distinct_from_environment_assembly_error() {
         let error = require_configured_discovery(Ok(None))
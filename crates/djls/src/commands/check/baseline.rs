@@ -0,0 +1,198 @@
+//! Baseline files for adopting `djls check` on projects with existing findings.
+//!
+//! A baseline records each known diagnostic by its Template path relative to the project
+//! root, its code, and a hash of its message and covered source text. Line numbers are left
+//! out, so unrelated edits above a recorded diagnostic do not resurface it. Identical
+//! diagnostics in one Template are counted, and only that many are suppressed.
+
+use std::collections::BTreeMap;
+use std::hash::Hasher as _;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use camino::Utf8Component;
+use camino::Utf8Path;
+use djls::CheckedTemplate;
+use djls::DiagnosticKey;
+use djls::RenderedDiagnostic;
+use djls_conf::DiagnosticsConfig;
+use djls_source::DiagnosticRenderer;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::commands::check::StableHasher;
+
+/// File name `djls check` reads from the project root when `--baseline` is not given.
+pub(super) const DEFAULT_BASELINE_FILE: &str = ".djls-baseline.json";
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Fingerprint {
+    file: String,
+    code: String,
+    hash: String,
+}
+
+impl Fingerprint {
+    fn new(file: &str, key: &DiagnosticKey) -> Self {
+        Self {
+            file: file.to_string(),
+            code: key.code().to_string(),
+            hash: format!("{:016x}", context_hash(key)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    diagnostics: Vec<BaselineEntry>,
+}
+
+/// Diagnostics to leave out of `djls check` output, with how many of each remain.
+#[derive(Debug, Default)]
+pub(super) struct Baseline {
+    entries: BTreeMap<Fingerprint, usize>,
+}
+
+impl Baseline {
+    /// Read a baseline file, or return `None` when `path` does not exist.
+    pub(super) fn load(path: &Utf8Path) -> Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(error).with_context(|| format!("Failed to read baseline {path}"));
+            }
+        };
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline {path}"))?;
+        if file.version != BASELINE_VERSION {
+            bail!(
+                "Unsupported baseline version {} in {path}; regenerate it with --write-baseline",
+                file.version
+            );
+        }
+
+        let entries = file
+            .diagnostics
+            .into_iter()
+            .map(|entry| (entry.fingerprint, entry.count))
+            .collect();
+        Ok(Some(Self { entries }))
+    }
+
+    /// Record every enabled diagnostic in `results`.
+    pub(super) fn from_results(
+        results: &[CheckedTemplate],
+        config: &DiagnosticsConfig,
+        fmt: &DiagnosticRenderer,
+        project_root: &Utf8Path,
+    ) -> Self {
+        let mut entries = BTreeMap::new();
+        for result in results {
+            let file = baseline_path(result.path(), project_root);
+            for diagnostic in result.render_keyed(config, fmt) {
+                *entries
+                    .entry(Fingerprint::new(&file, &diagnostic.key))
+                    .or_default() += 1;
+            }
+        }
+        Self { entries }
+    }
+
+    /// Total number of diagnostics recorded.
+    pub(super) fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub(super) fn write(&self, path: &Utf8Path) -> Result<()> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            diagnostics: self
+                .entries
+                .iter()
+                .map(|(fingerprint, count)| BaselineEntry {
+                    fingerprint: fingerprint.clone(),
+                    count: *count,
+                })
+                .collect(),
+        };
+        let mut contents = serde_json::to_string_pretty(&file)?;
+        contents.push('\n');
+        std::fs::write(path, contents).with_context(|| format!("Failed to write baseline {path}"))
+    }
+
+    /// Drop diagnostics of `template` the baseline still has room for.
+    pub(super) fn suppress(
+        &mut self,
+        template: &Utf8Path,
+        project_root: &Utf8Path,
+        diagnostics: Vec<RenderedDiagnostic>,
+    ) -> Vec<RenderedDiagnostic> {
+        let file = baseline_path(template, project_root);
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let fingerprint = Fingerprint::new(&file, &diagnostic.key);
+                match self.entries.get_mut(&fingerprint) {
+                    Some(remaining) if *remaining > 0 => {
+                        *remaining -= 1;
+                        false
+                    }
+                    Some(_) | None => true,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Project-relative path with `/` separators, so baselines are portable between checkouts.
+fn baseline_path(template: &Utf8Path, project_root: &Utf8Path) -> String {
+    match template.strip_prefix(project_root) {
+        Ok(relative) => relative
+            .components()
+            .map(Utf8Component::as_str)
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => template.to_string(),
+    }
+}
+
+/// FNV-1a over the message and covered text, which stays stable across builds and platforms.
+fn context_hash(key: &DiagnosticKey) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(key.message().as_bytes());
+    hasher.write(&[0]);
+    hasher.write(key.text().as_bytes());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_paths_are_project_relative_with_forward_slashes() {
+        assert_eq!(
+            baseline_path(
+                Utf8Path::new("/project/templates/app/page.html"),
+                Utf8Path::new("/project")
+            ),
+            "templates/app/page.html"
+        );
+        assert_eq!(
+            baseline_path(Utf8Path::new("/elsewhere/page.html"), Utf8Path::new("/project")),
+            "/elsewhere/page.html"
+        );
+    }
+}
@@ -0,0 +1,469 @@
+//! `djls check --cache-dir`: reuse diagnostics from earlier runs for unchanged Templates.
+//!
+//! Each cached Template records a hash of every source its diagnostics were computed from: the
+//! Template itself, the Templates it extends, and the Templates it includes. An entry is reused
+//! only while all of those hashes still match. A run-wide fingerprint covers the djls version,
+//! the settings that shape analysis, and the Python sources Django discovery read, so editing
+//! `settings.py` or a templatetags module starts the cache over. Severities are applied when
+//! diagnostics are rendered, so `--select`, `--ignore`, and most severity settings reuse the
+//! cache; the exceptions are codes like S146 that are only computed while enabled, and
+//! severities set in `[overrides]`. When plugins are allowed, the files their commands name are
+//! hashed too.
+
+use std::collections::BTreeMap;
+use std::hash::Hasher as _;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use djls::CheckedDiagnostic;
+use djls::CheckedTemplate;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_project::ProjectFactsData;
+use djls_project::TemplateResolutionResult;
+use djls_project::template_resolution;
+use djls_semantic::ChainEnd;
+use djls_semantic::TemplateReferenceKind;
+use djls_semantic::resolve_reference_for_file;
+use djls_semantic::template_inheritance;
+use djls_semantic::template_references_in_file;
+use djls_source::File;
+use djls_source::path_to_file;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::commands::check::StableHasher;
+use crate::commands::check::check_files_parallel;
+
+const CACHE_FILE: &str = "check.json";
+
+const CACHE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Hash of each source the diagnostics were computed from, the Template itself first.
+    sources: Vec<(Utf8PathBuf, u64)>,
+    diagnostics: Vec<CheckedDiagnostic>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    fingerprint: u64,
+    entries: BTreeMap<Utf8PathBuf, CacheEntry>,
+}
+
+/// Diagnostics from earlier `djls check` runs, keyed by Template path.
+pub(super) struct CheckCache {
+    dir: Utf8PathBuf,
+    fingerprint: u64,
+    entries: BTreeMap<Utf8PathBuf, CacheEntry>,
+    changed: bool,
+}
+
+impl CheckCache {
+    /// Open the cache in `dir` for a run with the given [`fingerprint`]. A missing,
+    /// unreadable, or outdated cache starts empty rather than failing the check.
+    pub(super) fn open(dir: &Utf8Path, fingerprint: u64) -> Self {
+        let entries = std::fs::read_to_string(dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CacheFile>(&contents).ok())
+            .filter(|file| file.version == CACHE_VERSION && file.fingerprint == fingerprint)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            dir: dir.to_owned(),
+            fingerprint,
+            entries,
+            changed: false,
+        }
+    }
+
+    /// Check `files`, reusing cached diagnostics for Templates whose sources are unchanged and
+    /// recording fresh results for the rest.
+    pub(super) fn check_files(
+        &mut self,
+        db: &DjangoDatabase,
+        files: Vec<Utf8PathBuf>,
+    ) -> Result<Vec<CheckedTemplate>> {
+        let mut results = Vec::new();
+        let mut stale = Vec::new();
+        for path in files {
+            match self.lookup(db, &path) {
+                Some(result) if result.has_diagnostics() => results.push(result),
+                Some(_) => {}
+                None => stale.push(path),
+            }
+        }
+
+        let mut fresh = check_files_parallel(db.clone(), stale.clone())?
+            .into_iter()
+            .map(|result| (result.path().to_owned(), result))
+            .collect::<BTreeMap<_, _>>();
+        for path in stale {
+            let result = fresh.remove(&path);
+            self.store(db, path, result.as_ref());
+            results.extend(result);
+        }
+        // Results whose path was normalized differently are reported but not cached.
+        results.extend(fresh.into_values());
+
+        Ok(results)
+    }
+
+    /// Write the cache back to disk if this run changed it, dropping entries for Templates
+    /// that no longer exist.
+    pub(super) fn save(mut self) -> Result<()> {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| path.exists());
+        if !self.changed && self.entries.len() == before {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory {}", self.dir))?;
+        // Keep the cache out of version control, as ruff and pytest do.
+        let gitignore = self.dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(&gitignore, "*\n")
+                .with_context(|| format!("Failed to write {gitignore}"))?;
+        }
+
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            fingerprint: self.fingerprint,
+            entries: self.entries,
+        };
+        let contents = serde_json::to_string(&file).context("Failed to serialize check cache")?;
+        // Write then rename, so an interrupted run cannot leave a truncated cache behind.
+        let path = self.dir.join(CACHE_FILE);
+        let partial = self.dir.join(format!("{CACHE_FILE}.tmp"));
+        std::fs::write(&partial, contents)
+            .with_context(|| format!("Failed to write check cache {partial}"))?;
+        std::fs::rename(&partial, &path)
+            .with_context(|| format!("Failed to write check cache {path}"))?;
+        Ok(())
+    }
+
+    fn lookup(&self, db: &DjangoDatabase, path: &Utf8Path) -> Option<CheckedTemplate> {
+        let entry = self.entries.get(path)?;
+        let unchanged = entry
+            .sources
+            .iter()
+            .all(|(source, hash)| source_hash(db, source) == Some(*hash));
+        if !unchanged {
+            return None;
+        }
+
+        let source = path_to_file(db, path).ok()?.try_source(db).ok()?;
+        Some(CheckedTemplate::new(
+            path.to_owned(),
+            source,
+            entry.diagnostics.clone(),
+        ))
+    }
+
+    fn store(&mut self, db: &DjangoDatabase, path: Utf8PathBuf, result: Option<&CheckedTemplate>) {
+        let Ok(file) = path_to_file(db, &path) else {
+            return;
+        };
+        let Some(dependencies) = template_dependencies(db, file) else {
+            self.changed |= self.entries.remove(&path).is_some();
+            return;
+        };
+        let sources = std::iter::once(path.clone())
+            .chain(dependencies)
+            .map(|source| source_hash(db, &source).map(|hash| (source, hash)))
+            .collect::<Option<Vec<_>>>();
+        let Some(sources) = sources else {
+            self.changed |= self.entries.remove(&path).is_some();
+            return;
+        };
+
+        let diagnostics = result.map_or_else(Vec::new, |result| result.diagnostics().to_vec());
+        self.entries.insert(
+            path,
+            CacheEntry {
+                sources,
+                diagnostics,
+            },
+        );
+        self.changed = true;
+    }
+}
+
+/// Templates whose contents a Template's diagnostics depend on: every ancestor along its
+/// `{% extends %}` chain and every Template it includes by name.
+///
+/// Returns `None` when a referenced name does not resolve to exactly one Template, since adding
+/// a Template could then change the diagnostics without changing any recorded source.
+fn template_dependencies(db: &DjangoDatabase, file: File) -> Option<Vec<Utf8PathBuf>> {
+    let project = db.project()?;
+    let inheritance = template_inheritance(db, project, file);
+    match inheritance.end(db) {
+        ChainEnd::Root | ChainEnd::Dynamic { .. } | ChainEnd::Cycle => {}
+        ChainEnd::Unresolved { .. } | ChainEnd::InconclusiveParent { .. } => return None,
+    }
+
+    let mut dependencies = inheritance
+        .ancestors(db)
+        .iter()
+        .map(|origin| origin.file(db).path(db).to_owned())
+        .collect::<Vec<_>>();
+
+    let resolution = template_resolution(db, project);
+    for reference in template_references_in_file(db, project, file).as_slice(db) {
+        if reference.kind() != TemplateReferenceKind::Include {
+            continue;
+        }
+        let resolved = resolve_reference_for_file(
+            db,
+            resolution,
+            file,
+            reference.target_template_name(),
+            reference.kind(),
+        );
+        match resolved {
+            Some(TemplateResolutionResult::Found(origin)) => {
+                dependencies.push(origin.path_buf(db));
+            }
+            Some(
+                TemplateResolutionResult::DoesNotExist(_)
+                | TemplateResolutionResult::Inconclusive(_),
+            ) => return None,
+            None => {}
+        }
+    }
+
+    dependencies.sort();
+    dependencies.dedup();
+    Some(dependencies)
+}
+
+fn source_hash(db: &DjangoDatabase, path: &Utf8Path) -> Option<u64> {
+    let source = path_to_file(db, path).ok()?.try_source(db).ok()?;
+    let mut hasher = StableHasher::default();
+    hasher.write(source.as_str().as_bytes());
+    Some(hasher.finish())
+}
+
+/// Codes whose checks only run while enabled, so their severity changes the cached results.
+const SEVERITY_GATED_CODES: &[&str] = &["S146"];
+
+/// Hash everything outside the Templates themselves that can change their diagnostics.
+///
+/// `plugins_root` is the project root when `[[plugins]]` are allowed to run.
+pub(super) fn fingerprint(
+    settings: &Settings,
+    facts: &ProjectFactsData,
+    plugins_root: Option<&Utf8Path>,
+) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hash_settings(&mut hasher, settings, plugins_root);
+
+    for path in facts.file_paths() {
+        hash_file(&mut hasher, path);
+    }
+
+    hasher.finish()
+}
+
+/// Hash the settings that shape analysis. Maps with an unstable order are hashed sorted, so
+/// the same configuration always hashes the same across runs.
+///
+/// Tag specs carry `extra` hash maps, so they are hashed as a JSON value, whose object keys
+/// `serde_json` keeps sorted, rather than through their `Debug` output.
+fn hash_settings(hasher: &mut StableHasher, settings: &Settings, plugins_root: Option<&Utf8Path>) {
+    let diagnostics = settings.diagnostics();
+    let tagspecs = serde_json::to_value(settings.tagspecs())
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let analysis_settings = format!(
+        "{:?}\0{:?}\0{:?}\0{:?}\0{tagspecs}\0{:?}\0{:?}\0{:?}\0{:?}\0{:?}",
+        settings.venv_path(),
+        settings.django_settings_module(),
+        settings.pythonpath(),
+        settings.env_file(),
+        settings.dialects(),
+        settings.path_mappings(),
+        diagnostics.strict_child_templates(),
+        diagnostics.limits(),
+        diagnostics.complexity(),
+    );
+    hasher.write(analysis_settings.as_bytes());
+
+    for code in SEVERITY_GATED_CODES {
+        hasher.write(&[0]);
+        hasher.write(format!("{code}={:?}", diagnostics.get_severity(code)).as_bytes());
+    }
+    for (pattern, config) in settings.overrides() {
+        let mut severities: Vec<_> = config
+            .severity()
+            .iter()
+            .map(|(code, severity)| format!("{code}={severity:?}"))
+            .collect();
+        severities.sort();
+        hasher.write(&[0]);
+        hasher.write(format!("{pattern}\0{}", severities.join(",")).as_bytes());
+    }
+
+    // Plugin answers are cached like built-in diagnostics, so the plugin's files stand in for
+    // its version.
+    if let Some(root) = plugins_root {
+        for plugin in settings.plugins() {
+            hasher.write(&[0]);
+            hasher.write(format!("{plugin:?}").as_bytes());
+            for path in plugin.files(root) {
+                hash_file(hasher, &path);
+            }
+        }
+    }
+}
+
+fn hash_file(hasher: &mut StableHasher, path: &Utf8Path) {
+    hasher.write(&[0]);
+    hasher.write(path.as_str().as_bytes());
+    hasher.write(&[0]);
+    if let Ok(contents) = std::fs::read(path) {
+        hasher.write(&contents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_with_entry(dir: &Utf8Path, fingerprint: u64, template: &Utf8Path) -> CheckCache {
+        let mut cache = CheckCache::open(dir, fingerprint);
+        cache.entries.insert(
+            template.to_owned(),
+            CacheEntry {
+                sources: vec![(template.to_owned(), 1)],
+                diagnostics: Vec::new(),
+            },
+        );
+        cache.changed = true;
+        cache
+    }
+
+    #[test]
+    fn saved_entries_are_reused_only_with_the_same_fingerprint() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let dir = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let template = dir.join("page.html");
+        std::fs::write(&template, "{{ title }}").expect("template should be written");
+
+        cache_with_entry(&dir.join("cache"), 7, &template)
+            .save()
+            .expect("cache should be saved");
+
+        assert!(
+            CheckCache::open(&dir.join("cache"), 7)
+                .entries
+                .contains_key(&template)
+        );
+        assert!(CheckCache::open(&dir.join("cache"), 8).entries.is_empty());
+        assert!(dir.join("cache/.gitignore").is_file());
+    }
+
+    #[test]
+    fn save_drops_entries_for_deleted_templates() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let dir = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let template = dir.join("deleted.html");
+
+        cache_with_entry(&dir.join("cache"), 7, &template)
+            .save()
+            .expect("cache should be saved");
+
+        assert!(CheckCache::open(&dir.join("cache"), 7).entries.is_empty());
+    }
+
+    fn settings_hash(project: &Utf8Path, config: &str, plugins_root: Option<&Utf8Path>) -> u64 {
+        std::fs::write(project.join("djls.toml"), config).expect("config should be written");
+        let settings = Settings::new(project, None).expect("config should load");
+        let mut hasher = StableHasher::default();
+        hash_settings(&mut hasher, &settings, plugins_root);
+        hasher.finish()
+    }
+
+    #[test]
+    fn settings_that_change_validation_change_the_fingerprint() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let project = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let base = settings_hash(project, "", None);
+
+        for config in [
+            "[diagnostics]\nstrict_child_templates = true\n",
+            "[diagnostics.limits]\nmax_nodes = 10\n",
+            "[diagnostics.complexity]\nmax_branches = 1\n",
+            "[diagnostics.severity]\nS146 = \"warning\"\n",
+            "[dialects]\n\"jinja2\" = \"jinja2\"\n",
+            "[overrides.\"legacy/**\"]\ndialect = \"jinja2\"\n",
+            "[overrides.\"legacy/**\".diagnostics.severity]\nS146 = \"hint\"\n",
+        ] {
+            assert_ne!(settings_hash(project, config, None), base, "{config}");
+        }
+    }
+
+    #[test]
+    fn severities_that_apply_at_render_time_keep_the_fingerprint() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let project = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let base = settings_hash(project, "", None);
+
+        assert_eq!(
+            settings_hash(project, "[diagnostics.severity]\nS101 = \"off\"\n", None),
+            base
+        );
+    }
+
+    #[test]
+    fn override_severities_hash_the_same_in_any_order() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let project = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let config = "[overrides.\"legacy/**\".diagnostics.severity]\n\
+                      S100 = \"off\"\nS101 = \"hint\"\nS102 = \"info\"\nS103 = \"warning\"\n";
+
+        let hashes: Vec<_> = (0..8)
+            .map(|_| settings_hash(project, config, None))
+            .collect();
+
+        assert!(hashes.windows(2).all(|pair| pair[0] == pair[1]), "{hashes:?}");
+    }
+
+    #[test]
+    fn tagspec_extra_keys_hash_the_same_in_any_order() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let project = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let config = "[tagspecs.extra]\n\
+                      alpha = 1\nbravo = 2\ncharlie = 3\ndelta = 4\necho = 5\nfoxtrot = 6\n";
+
+        let hashes: Vec<_> = (0..8)
+            .map(|_| settings_hash(project, config, None))
+            .collect();
+
+        assert!(hashes.windows(2).all(|pair| pair[0] == pair[1]), "{hashes:?}");
+    }
+
+    #[test]
+    fn allowed_plugin_files_change_the_fingerprint() {
+        let dir = tempfile::tempdir().expect("temporary directory should be created");
+        let project = Utf8Path::from_path(dir.path()).expect("temporary directory should be UTF-8");
+        let config = "[[plugins]]\nname = \"house-style\"\ncommand = [\"sh\", \"rules.sh\"]\n";
+        std::fs::write(project.join("rules.sh"), "echo one\n").expect("script should be written");
+        let not_allowed = settings_hash(project, config, None);
+        let before = settings_hash(project, config, Some(project));
+
+        std::fs::write(project.join("rules.sh"), "echo two\n").expect("script should be written");
+
+        assert_eq!(settings_hash(project, config, None), not_allowed);
+        assert_ne!(settings_hash(project, config, Some(project)), before);
+    }
+}
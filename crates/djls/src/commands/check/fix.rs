@@ -0,0 +1,62 @@
+//! `djls check --fix`: apply machine-applicable fixes, then re-check the Templates they changed.
+//!
+//! Fixes are applied in one pass. A fix that overlaps another is left for the next run, and the
+//! re-check reports whatever remains.
+
+use std::io::Write as _;
+use std::io::stdout;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8PathBuf;
+use djls::CheckedTemplate;
+use djls_conf::DiagnosticsConfig;
+use djls_db::DjangoDatabase;
+use djls_source::ChangeEvent;
+use djls_source::SourceChanges;
+
+use crate::commands::check::check_files_parallel;
+
+pub(super) fn check_and_fix_files(
+    mut db: DjangoDatabase,
+    files: Vec<Utf8PathBuf>,
+    config: &DiagnosticsConfig,
+    quiet: bool,
+) -> Result<Vec<CheckedTemplate>> {
+    let results = check_files_parallel(db.clone(), files.clone())?;
+
+    let mut fixed_paths = Vec::new();
+    let mut fix_count = 0;
+    for result in &results {
+        let Some((source, count)) = result.fixed_source(config) else {
+            continue;
+        };
+        if count == 0 {
+            continue;
+        }
+        std::fs::write(result.path(), source)
+            .with_context(|| format!("Failed to write fixes to {}", result.path()))?;
+        fixed_paths.push(result.path().to_owned());
+        fix_count += count;
+    }
+    if fixed_paths.is_empty() {
+        return Ok(results);
+    }
+
+    if !quiet {
+        let fix_word = if fix_count == 1 { "diagnostic" } else { "diagnostics" };
+        let file_count = fixed_paths.len();
+        let file_word = if file_count == 1 { "file" } else { "files" };
+        writeln!(
+            stdout().lock(),
+            "Fixed {fix_count} {fix_word} in {file_count} {file_word}.\n"
+        )?;
+    }
+
+    let changes = fixed_paths
+        .into_iter()
+        .map(ChangeEvent::ContentChanged)
+        .collect::<Vec<_>>();
+    SourceChanges::new(changes).apply(&mut db);
+    check_files_parallel(db, files)
+}
@@ -0,0 +1,58 @@
+//! `djls check --shadowed`: list Templates that an earlier template directory hides.
+//!
+//! Each line names the shadowed file, the file Django's loaders return instead, and the Template
+//! Name both provide. Shadowing is reported for information only and does not fail the command.
+
+use std::io::Write as _;
+use std::io::stdout;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_project::shadowed_templates;
+
+use crate::exit::Exit;
+
+pub(super) fn report_shadowed_templates(
+    db: &DjangoDatabase,
+    project_root: &Utf8Path,
+    quiet: bool,
+) -> Result<Exit> {
+    let Some(project) = db.project() else {
+        return Ok(Exit::success());
+    };
+    let shadowed = shadowed_templates(db, project);
+    if shadowed.is_empty() {
+        return Ok(Exit::success());
+    }
+
+    let canonical_root = project_root.canonicalize_utf8().ok();
+    let display = |path: &Utf8Path| {
+        let relative = path.strip_prefix(project_root).ok().or_else(|| {
+            canonical_root
+                .as_deref()
+                .and_then(|root| path.strip_prefix(root).ok())
+        });
+        relative.unwrap_or(path).to_string()
+    };
+
+    if !quiet {
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        for template in shadowed {
+            writeln!(
+                stdout,
+                "{} is shadowed by {} for '{}'",
+                display(template.shadowed()),
+                display(template.winner()),
+                template.name()
+            )?;
+        }
+        writeln!(stdout)?;
+    }
+
+    let count = shadowed.len();
+    let word = if count == 1 { "template" } else { "templates" };
+    Ok(Exit::success().with_message(format!("Found {count} shadowed {word}.")))
+}
@@ -0,0 +1,271 @@
+//! `djls check --watch`: re-check Templates as they change on disk.
+//!
+//! The database from the initial check is kept alive, so each change only recomputes the Salsa
+//! queries it invalidated. A changed Template is re-checked together with every Template that
+//! extends it, and only diagnostics that appeared or disappeared since the previous report are
+//! printed.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write as _;
+use std::io::stdout;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use djls::CheckedTemplate;
+use djls::DiagnosticKey;
+use djls::check_template;
+use djls_conf::DiagnosticSeverity;
+use djls_conf::DiagnosticsConfig;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_semantic::descendant_templates;
+use djls_source::ChangeEvent;
+use djls_source::DiagnosticRenderer;
+use djls_source::SourceChanges;
+use djls_source::path_to_file;
+use notify::EventKind;
+use notify::RecursiveMode;
+use notify::Watcher as _;
+
+use crate::commands::common::is_template;
+use crate::exit::Exit;
+
+/// How long to keep collecting events after the first one, so an editor's save (often a
+/// truncate, write, and rename) is checked once.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub(super) struct WatchSession<'a> {
+    db: DjangoDatabase,
+    config: &'a DiagnosticsConfig,
+    fmt: &'a DiagnosticRenderer,
+    quiet: bool,
+    /// Templates known to exist on disk.
+    files: BTreeSet<Utf8PathBuf>,
+    /// Diagnostics last reported for each Template that had any, with their severity and output.
+    reported: BTreeMap<Utf8PathBuf, BTreeMap<DiagnosticKey, (DiagnosticSeverity, String)>>,
+}
+
+impl<'a> WatchSession<'a> {
+    pub(super) fn new(
+        db: DjangoDatabase,
+        files: Vec<Utf8PathBuf>,
+        config: &'a DiagnosticsConfig,
+        fmt: &'a DiagnosticRenderer,
+        quiet: bool,
+    ) -> Self {
+        Self {
+            db,
+            config,
+            fmt,
+            quiet,
+            files: files.into_iter().collect(),
+            reported: BTreeMap::new(),
+        }
+    }
+
+    /// Print the initial check and block, re-checking Templates under `roots` as they change.
+    pub(super) fn run(
+        mut self,
+        mut initial: Vec<CheckedTemplate>,
+        roots: &[Utf8PathBuf],
+    ) -> Result<Exit> {
+        initial.sort_by(|left, right| left.path().cmp(right.path()));
+        for checked in &initial {
+            self.report(checked.path().to_owned(), Some(checked))?;
+        }
+        self.print_status()?;
+
+        let (tx, rx) = channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                drop(tx.send(event));
+            })
+            .context("Failed to start file watcher")?;
+        for root in roots {
+            watcher
+                .watch(root.as_std_path(), RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {root}"))?;
+        }
+
+        while let Some(changed) = next_batch(&rx) {
+            if changed.is_empty() {
+                continue;
+            }
+            self.apply(&changed);
+            for path in self.affected(&changed) {
+                let checked = self.check(&path);
+                self.report(path, checked.as_ref())?;
+            }
+            self.print_status()?;
+        }
+
+        Ok(Exit::success())
+    }
+
+    /// Record on-disk changes to `changed` Templates in the database.
+    fn apply(&mut self, changed: &BTreeSet<Utf8PathBuf>) {
+        let events = changed.iter().map(|path| {
+            if !path.as_std_path().is_file() {
+                self.files.remove(path);
+                ChangeEvent::Deleted(path.clone())
+            } else if self.files.insert(path.clone()) {
+                ChangeEvent::BecameVisible(path.clone())
+            } else {
+                ChangeEvent::ContentChanged(path.clone())
+            }
+        });
+        SourceChanges::new(events.collect::<Vec<_>>()).apply(&mut self.db);
+    }
+
+    /// Changed Templates plus every Template that extends one of them.
+    fn affected(&self, changed: &BTreeSet<Utf8PathBuf>) -> BTreeSet<Utf8PathBuf> {
+        let mut affected = changed.clone();
+        let Some(project) = self.db.project() else {
+            return affected;
+        };
+        for path in changed {
+            let Ok(file) = path_to_file(&self.db, path) else {
+                continue;
+            };
+            for descendant in descendant_templates(&self.db, project, file) {
+                affected.insert(descendant.path(&self.db).to_owned());
+            }
+        }
+        affected
+    }
+
+    fn check(&self, path: &Utf8Path) -> Option<CheckedTemplate> {
+        let file = path_to_file(&self.db, path).ok()?;
+        check_template(&self.db, file).ok()
+    }
+
+    /// Print diagnostics for `path` that are new or resolved since its last report.
+    fn report(&mut self, path: Utf8PathBuf, checked: Option<&CheckedTemplate>) -> Result<()> {
+        let current: BTreeMap<DiagnosticKey, (DiagnosticSeverity, String)> = checked
+            .map(|checked| checked.render_keyed(self.config, self.fmt))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.key, (diagnostic.severity, diagnostic.output)))
+            .collect();
+        let previous = self.reported.remove(&path).unwrap_or_default();
+
+        if !self.quiet {
+            let stdout = stdout();
+            let mut stdout = stdout.lock();
+            for key in previous.keys().filter(|key| !current.contains_key(*key)) {
+                writeln!(stdout, "Resolved in {path}: {key}\n")?;
+            }
+            for (key, (_, output)) in &current {
+                if !previous.contains_key(key) {
+                    writeln!(stdout, "{output}\n")?;
+                }
+            }
+        }
+
+        if !current.is_empty() {
+            self.reported.insert(path, current);
+        }
+        Ok(())
+    }
+
+    fn print_status(&self) -> Result<()> {
+        let mut counts = [0usize; 4];
+        for (severity, _) in self.reported.values().flat_map(BTreeMap::values) {
+            let index = match severity {
+                DiagnosticSeverity::Error => 0,
+                DiagnosticSeverity::Warning => 1,
+                DiagnosticSeverity::Info => 2,
+                DiagnosticSeverity::Hint | DiagnosticSeverity::Off => 3,
+            };
+            counts[index] += 1;
+        }
+        let file_count = self.reported.len();
+        let file_word = if file_count == 1 { "file" } else { "files" };
+        writeln!(
+            stdout().lock(),
+            "Found {} in {file_count} {file_word}. Watching for changes...",
+            severity_counts(counts)
+        )?;
+        Ok(())
+    }
+}
+
+/// Describe error, warning, info, and hint counts, leaving out severities with none.
+///
+/// Errors are always named, so a clean check reads "0 errors".
+fn severity_counts([errors, warnings, infos, hints]: [usize; 4]) -> String {
+    let mut parts = vec![count_phrase(errors, "error")];
+    for (count, word) in [(warnings, "warning"), (infos, "info"), (hints, "hint")] {
+        if count > 0 {
+            parts.push(count_phrase(count, word));
+        }
+    }
+    parts.join(", ")
+}
+
+fn count_phrase(count: usize, word: &str) -> String {
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {word}{plural}")
+}
+
+/// Wait for the next file event and gather the Template paths touched by it and any events
+/// that follow within [`DEBOUNCE`]. Returns `None` once the watcher has shut down.
+fn next_batch(rx: &Receiver<notify::Result<notify::Event>>) -> Option<BTreeSet<Utf8PathBuf>> {
+    let mut changed = BTreeSet::new();
+    collect_event(&mut changed, rx.recv().ok()?);
+    loop {
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(event) => collect_event(&mut changed, event),
+            Err(RecvTimeoutError::Timeout) => return Some(changed),
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+fn collect_event(changed: &mut BTreeSet<Utf8PathBuf>, event: notify::Result<notify::Event>) {
+    let Ok(event) = event else {
+        return;
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return;
+    }
+    for path in event.paths {
+        let Ok(path) = Utf8PathBuf::from_path_buf(path) else {
+            continue;
+        };
+        if is_template(&path) {
+            changed.insert(canonical_path(path));
+        }
+    }
+}
+
+/// Match the canonical paths the initial discovery produced; deleted paths stay as reported.
+fn canonical_path(path: Utf8PathBuf) -> Utf8PathBuf {
+    match path.as_std_path().canonicalize() {
+        Ok(canonical) => {
+            #[cfg(windows)]
+            let canonical = dunce::simplified(&canonical).to_path_buf();
+            Utf8PathBuf::from_path_buf(canonical).unwrap_or(path)
+        }
+        Err(_) => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_counts_name_errors_and_other_severities_present() {
+        assert_eq!(severity_counts([0, 0, 0, 0]), "0 errors");
+        assert_eq!(severity_counts([1, 0, 0, 0]), "1 error");
+        assert_eq!(severity_counts([0, 2, 0, 1]), "0 errors, 2 warnings, 1 hint");
+    }
+}
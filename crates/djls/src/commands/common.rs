@@ -79,7 +79,7 @@ pub(crate) fn discover_files(
 /// visit extra files than silently skip templates the settings could not enumerate. A fully
 /// extracted configuration with no roots gets no fallback, so the scan does not invent roots
 /// the project never declared.
-fn discovery_roots(
+pub(crate) fn discovery_roots(
     paths: &[Utf8PathBuf],
     db: &DjangoDatabase,
     project_root: &Utf8Path,
@@ -0,0 +1,76 @@
+use std::io::Write;
+use std::io::stdout;
+
+use anyhow::Result;
+use clap::Parser;
+use djls_conf::DIAGNOSTIC_CODES;
+use djls_conf::DiagnosticCode;
+use djls_conf::diagnostic_code;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Explain {
+    /// Diagnostic code, such as S109. Lists every code when omitted.
+    code: Option<String>,
+}
+
+impl Command for Explain {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+
+        let Some(code) = self.code.as_deref() else {
+            write_code_list(&mut stdout)?;
+            return Ok(Exit::success());
+        };
+        let Some(entry) = diagnostic_code(code) else {
+            return Ok(Exit::error().with_message(format!(
+                "Unknown diagnostic code '{code}'. Run `djls explain` to list every code."
+            )));
+        };
+        write_explanation(&mut stdout, entry)?;
+        Ok(Exit::success())
+    }
+}
+
+fn write_code_list(out: &mut impl Write) -> Result<()> {
+    for entry in DIAGNOSTIC_CODES {
+        writeln!(out, "{}  {}", entry.code, entry.title)?;
+    }
+    Ok(())
+}
+
+fn write_explanation(out: &mut impl Write, entry: &DiagnosticCode) -> Result<()> {
+    writeln!(out, "{}: {}", entry.code, entry.title)?;
+    writeln!(out)?;
+    writeln!(out, "{}", entry.description)?;
+    if let Some(example) = entry.example {
+        writeln!(out)?;
+        writeln!(out, "Example:")?;
+        writeln!(out)?;
+        writeln!(out, "    {example}")?;
+    }
+    writeln!(out)?;
+    writeln!(out, "{}", entry.href())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explanation_includes_example_and_docs_link() {
+        let entry = diagnostic_code("S109").expect("S109 should be registered");
+        let mut out = Vec::new();
+        write_explanation(&mut out, entry).expect("writing into a buffer should succeed");
+        let output = String::from_utf8(out).expect("explanation should be UTF-8");
+
+        assert!(output.starts_with("S109: Unloaded tag\n\n"));
+        assert!(output.contains("\n    {% static 'logo.png' %}\n"));
+        assert!(output.ends_with("https://djls.joshthomas.dev/diagnostics/#s109\n"));
+    }
+}
@@ -0,0 +1,266 @@
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use clap::ValueEnum;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::run_django_discovery;
+use djls_semantic::untranslated_text;
+use djls_source::OsFileSystem;
+use djls_source::WalkOptions;
+use djls_source::path_to_file;
+use serde::Serialize;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::discover_files;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct I18nReport {
+    /// Template files or directories to report on. If omitted, discovers Template
+    /// directories from the Django project.
+    paths: Vec<Utf8PathBuf>,
+
+    /// Exit with an error when more than this many text runs are untranslated. The
+    /// default of 0 fails on any untranslated text.
+    #[arg(long, default_value_t = 0)]
+    threshold: usize,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Each untranslated text run, then a count per Template.
+    Table,
+    /// A JSON object with the total and every Template's text runs.
+    Json,
+}
+
+impl Command for I18nReport {
+    fn execute(&self, args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
+        let mut db = DjangoDatabase::new(
+            Arc::new(OsFileSystem::default()),
+            &settings,
+            Some(&project_root),
+        );
+        db.apply_project_settings(settings);
+        run_django_discovery(&mut db)
+            .context("Failed to discover Django environment")?
+            .context("No Project configured for i18n-report")?;
+
+        let files = discover_files(&self.paths, &db, &project_root, &WalkOptions::default());
+        // Discovered Template paths are canonical, so they are shown relative to the
+        // canonical project root.
+        let display_root = project_root
+            .canonicalize_utf8()
+            .unwrap_or_else(|_| project_root.clone());
+        let report = UntranslatedReport::collect(&db, &display_root, &files, self.threshold);
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        match self.format {
+            OutputFormat::Table => {
+                if !args.quiet {
+                    write_table(&mut stdout, &report)?;
+                }
+            }
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut stdout, &report)?;
+                writeln!(stdout)?;
+            }
+        }
+
+        let summary = report.summary(files.len());
+        if report.untranslated > self.threshold {
+            let message = format!("{summary}, over the threshold of {}.", self.threshold);
+            return Ok(Exit::error().with_message(message));
+        }
+        if args.quiet {
+            return Ok(Exit::success());
+        }
+        Ok(Exit::success().with_message(format!("{summary}.")))
+    }
+}
+
+#[derive(Serialize)]
+struct UntranslatedReport {
+    untranslated: usize,
+    threshold: usize,
+    /// Templates with untranslated text, in path order.
+    templates: Vec<TemplateEntry>,
+}
+
+#[derive(Serialize)]
+struct TemplateEntry {
+    template: String,
+    untranslated: usize,
+    text: Vec<TextEntry>,
+}
+
+#[derive(Serialize)]
+struct TextEntry {
+    /// 1-based.
+    line: u32,
+    /// 1-based.
+    column: u32,
+    text: String,
+}
+
+impl UntranslatedReport {
+    fn collect(
+        db: &DjangoDatabase,
+        display_root: &Utf8Path,
+        files: &[Utf8PathBuf],
+        threshold: usize,
+    ) -> Self {
+        let mut templates = Vec::new();
+        for path in files {
+            let Ok(file) = path_to_file(db, path) else {
+                continue;
+            };
+            let found = untranslated_text(db, file);
+            if found.is_empty() {
+                continue;
+            }
+            let index = file.line_index(db);
+            templates.push(TemplateEntry {
+                template: path.strip_prefix(display_root).unwrap_or(path).to_string(),
+                untranslated: found.len(),
+                text: found
+                    .into_iter()
+                    .map(|run| {
+                        let (line, column) = index.to_line_col(run.span.start_offset()).into();
+                        TextEntry {
+                            line: line + 1,
+                            column: column + 1,
+                            text: run.text,
+                        }
+                    })
+                    .collect(),
+            });
+        }
+
+        Self {
+            untranslated: templates.iter().map(|entry| entry.untranslated).sum(),
+            threshold,
+            templates,
+        }
+    }
+
+    fn summary(&self, checked: usize) -> String {
+        let run_word = if self.untranslated == 1 {
+            "text run"
+        } else {
+            "text runs"
+        };
+        let template_word = if checked == 1 { "template" } else { "templates" };
+        format!(
+            "Found {} untranslated {run_word} in {} of {checked} {template_word}",
+            self.untranslated,
+            self.templates.len()
+        )
+    }
+}
+
+fn write_table(out: &mut impl Write, report: &UntranslatedReport) -> Result<()> {
+    if report.templates.is_empty() {
+        return Ok(());
+    }
+
+    for template in &report.templates {
+        for text in &template.text {
+            writeln!(
+                out,
+                "{}:{}:{}  {}",
+                template.template, text.line, text.column, text.text
+            )?;
+        }
+    }
+    writeln!(out)?;
+
+    let header = ["TEMPLATE", "UNTRANSLATED"];
+    let width = report
+        .templates
+        .iter()
+        .map(|template| template.template.len())
+        .fold(header[0].len(), usize::max);
+    writeln!(out, "{:<width$}  {}", header[0], header[1])?;
+    for template in &report.templates {
+        writeln!(
+            out,
+            "{:<width$}  {}",
+            template.template, template.untranslated
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_lists_text_runs_then_counts_per_template() {
+        let report = UntranslatedReport {
+            untranslated: 3,
+            threshold: 0,
+            templates: vec![
+                TemplateEntry {
+                    template: "templates/account/login.html".to_string(),
+                    untranslated: 2,
+                    text: vec![
+                        TextEntry {
+                            line: 4,
+                            column: 9,
+                            text: "Sign in".to_string(),
+                        },
+                        TextEntry {
+                            line: 12,
+                            column: 5,
+                            text: "Forgot your password?".to_string(),
+                        },
+                    ],
+                },
+                TemplateEntry {
+                    template: "templates/base.html".to_string(),
+                    untranslated: 1,
+                    text: vec![TextEntry {
+                        line: 2,
+                        column: 12,
+                        text: "My Site".to_string(),
+                    }],
+                },
+            ],
+        };
+        let mut out = Vec::new();
+        write_table(&mut out, &report).expect("writing to a Vec should succeed");
+
+        assert_eq!(
+            String::from_utf8(out).expect("table output should be UTF-8"),
+            "templates/account/login.html:4:9  Sign in\n\
+             templates/account/login.html:12:5  Forgot your password?\n\
+             templates/base.html:2:12  My Site\n\
+             \n\
+             TEMPLATE                      UNTRANSLATED\n\
+             templates/account/login.html  2\n\
+             templates/base.html           1\n"
+        );
+        assert_eq!(
+            report.summary(5),
+            "Found 3 untranslated text runs in 2 of 5 templates"
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use std::io::Write as _;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::ArgumentCountConstraint;
+use djls_project::Db as _;
+use djls_project::FilterArgKind;
+use djls_project::FilterArity;
+use djls_project::LibraryName;
+use djls_project::PythonModuleName;
+use djls_project::TagArgument;
+use djls_project::TagArgumentKind;
+use djls_project::TagRule;
+use djls_project::TemplateLibrary;
+use djls_project::TemplateSymbolKind;
+use djls_project::run_django_discovery;
+use djls_project::template_library_catalog;
+use djls_semantic::TagSpec;
+use djls_semantic::library_filter_specs;
+use djls_semantic::library_tag_specs;
+use djls_source::OsFileSystem;
+use serde::Serialize;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Inspect {
+    #[command(subcommand)]
+    target: InspectTarget,
+}
+
+#[derive(Debug, Subcommand)]
+enum InspectTarget {
+    /// Print the tag and filter inventory with its extracted rules
+    Tags(InspectTags),
+}
+
+impl Command for Inspect {
+    fn execute(&self, args: &Args) -> Result<Exit> {
+        match &self.target {
+            InspectTarget::Tags(cmd) => cmd.execute(args),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+struct InspectTags {
+    /// Only show tags and filters with this name.
+    name: Option<String>,
+
+    /// Only show symbols from this library, by `{% load %}` name or module path.
+    #[arg(long)]
+    library: Option<String>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Aligned columns for reading in a terminal.
+    Table,
+    /// A JSON array with the full extracted rules.
+    Json,
+}
+
+impl Command for InspectTags {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
+
+        let mut db = DjangoDatabase::new(
+            Arc::new(OsFileSystem::default()),
+            &settings,
+            Some(&project_root),
+        );
+        db.apply_project_settings(settings);
+        run_django_discovery(&mut db)
+            .context("Failed to discover Django environment")?
+            .context("No Project configured for inspect")?;
+        let project = db.project().context("No Project configured for inspect")?;
+
+        let catalog = template_library_catalog(&db, project);
+        let mut entries = Vec::new();
+        for library in catalog.libraries() {
+            if !self.matches_library(library) {
+                continue;
+            }
+            for symbol in library.symbols() {
+                if self.name.as_deref().is_some_and(|name| name != symbol.name()) {
+                    continue;
+                }
+                let details = match symbol.kind {
+                    TemplateSymbolKind::Tag => SymbolDetails::Tag(
+                        library_tag_specs(&db, project, library.id()).get(symbol.name()),
+                    ),
+                    TemplateSymbolKind::Filter => SymbolDetails::Filter(
+                        library_filter_specs(&db, library.id()).get(symbol.name()),
+                    ),
+                };
+                entries.push(InventoryEntry::new(library, symbol.name(), details));
+            }
+        }
+        entries.sort_by(|left, right| {
+            (left.kind, left.name, left.module).cmp(&(right.kind, right.name, right.module))
+        });
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        match self.format {
+            OutputFormat::Table => write_table(&mut stdout, &entries)?,
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut stdout, &entries)?;
+                writeln!(stdout)?;
+            }
+        }
+        Ok(Exit::success())
+    }
+}
+
+impl InspectTags {
+    fn matches_library(&self, library: &TemplateLibrary) -> bool {
+        let Some(wanted) = self.library.as_deref() else {
+            return true;
+        };
+        library.module_name_str() == wanted
+            || library
+                .load_name()
+                .is_some_and(|load_name| load_name.as_str() == wanted)
+    }
+}
+
+/// How a library reaches templates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LibraryStatus {
+    /// Available everywhere without `{% load %}`.
+    Builtin,
+    /// Available after `{% load %}`.
+    Loadable,
+    /// Found in an app that is not in `INSTALLED_APPS`.
+    Uninstalled,
+}
+
+impl LibraryStatus {
+    fn of(library: &TemplateLibrary) -> Self {
+        if library.is_builtin() {
+            Self::Builtin
+        } else if library.available_in_app_module().is_some() {
+            Self::Uninstalled
+        } else {
+            Self::Loadable
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Loadable => "loadable",
+            Self::Uninstalled => "uninstalled",
+        }
+    }
+}
+
+enum SymbolDetails<'a> {
+    Tag(Option<&'a TagSpec>),
+    Filter(Option<&'a FilterArity>),
+}
+
+#[derive(Serialize)]
+struct InventoryEntry<'a> {
+    kind: &'static str,
+    name: &'a str,
+    library: Option<&'a str>,
+    module: &'a str,
+    status: LibraryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<BlockShape<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rules: Option<&'a TagRule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arity: Option<&'a FilterArity>,
+}
+
+#[derive(Serialize)]
+struct BlockShape<'a> {
+    end_tag: &'a str,
+    end_tag_required: bool,
+    intermediates: Vec<&'a str>,
+    opaque: bool,
+}
+
+impl<'a> InventoryEntry<'a> {
+    fn new(library: &'a TemplateLibrary, name: &'a str, details: SymbolDetails<'a>) -> Self {
+        let (kind, block, rules, arity) = match details {
+            SymbolDetails::Tag(spec) => (
+                "tag",
+                spec.and_then(block_shape),
+                spec.and_then(TagSpec::extracted_rules),
+                None,
+            ),
+            SymbolDetails::Filter(arity) => ("filter", None, None, arity),
+        };
+        Self {
+            kind,
+            name,
+            library: library.load_name().map(LibraryName::as_str),
+            module: library.module_name_str(),
+            status: LibraryStatus::of(library),
+            app: library
+                .available_in_app_module()
+                .map(PythonModuleName::as_str),
+            block,
+            rules,
+            arity,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(block) = &self.block {
+            let optional = if block.end_tag_required { "" } else { "?" };
+            parts.push(format!("end: {}{optional}", block.end_tag));
+            if !block.intermediates.is_empty() {
+                parts.push(format!("intermediates: {}", block.intermediates.join(", ")));
+            }
+            if block.opaque {
+                parts.push("opaque".to_string());
+            }
+        }
+        if let Some(rules) = self.rules {
+            if !rules.extracted_args.is_empty() {
+                parts.push(format!("args: {}", arguments_summary(&rules.extracted_args)));
+            }
+            if !rules.arg_constraints.is_empty() {
+                let constraints: Vec<_> = rules
+                    .arg_constraints
+                    .iter()
+                    .map(constraint_summary)
+                    .collect();
+                parts.push(format!("bits: {}", constraints.join(", ")));
+            }
+        }
+        if let Some(arity) = self.arity {
+            parts.push(arity_summary(arity));
+        }
+        parts.join("; ")
+    }
+}
+
+fn block_shape(spec: &TagSpec) -> Option<BlockShape<'_>> {
+    let end_tag = spec.end_tag.as_ref()?;
+    Some(BlockShape {
+        end_tag: &end_tag.name,
+        end_tag_required: end_tag.required,
+        intermediates: spec
+            .intermediate_tags()
+            .iter()
+            .map(|tag| &*tag.name)
+            .collect(),
+        opaque: spec.is_opaque(),
+    })
+}
+
+fn arguments_summary(arguments: &[TagArgument]) -> String {
+    let rendered: Vec<_> = arguments
+        .iter()
+        .map(|argument| {
+            let shape = match &argument.kind {
+                TagArgumentKind::Variable => argument.name.clone(),
+                TagArgumentKind::Literal(literal) => literal.clone(),
+                TagArgumentKind::Choice(choices) => choices.join("|"),
+                TagArgumentKind::VarArgs => format!("{}...", argument.name),
+                TagArgumentKind::Keyword => format!("{}=", argument.name),
+            };
+            if argument.required {
+                shape
+            } else {
+                format!("[{shape}]")
+            }
+        })
+        .collect();
+    rendered.join(" ")
+}
+
+fn constraint_summary(constraint: &ArgumentCountConstraint) -> String {
+    match constraint {
+        ArgumentCountConstraint::Exact(count) => format!("=={count}"),
+        ArgumentCountConstraint::Min(count) => format!(">={count}"),
+        ArgumentCountConstraint::Max(count) => format!("<={count}"),
+        ArgumentCountConstraint::OneOf(counts) => {
+            let counts: Vec<_> = counts.iter().map(ToString::to_string).collect();
+            format!("in {{{}}}", counts.join(","))
+        }
+    }
+}
+
+fn arity_summary(arity: &FilterArity) -> String {
+    let argument = match (arity.expects_arg, arity.arg_optional) {
+        (false, _) => return "no argument".to_string(),
+        (true, true) => "optional argument",
+        (true, false) => "argument required",
+    };
+    match &arity.arg_kind {
+        None => argument.to_string(),
+        Some(FilterArgKind::Integer) => format!("{argument} (integer)"),
+        Some(FilterArgKind::DateFormat) => format!("{argument} (date format)"),
+        Some(FilterArgKind::Delimited {
+            delimiter,
+            min_items,
+            max_items,
+        }) => {
+            let items = match (min_items, max_items) {
+                (Some(min), Some(max)) => format!(", {min}-{max} items"),
+                (Some(min), None) => format!(", at least {min} items"),
+                (None, Some(max)) => format!(", at most {max} items"),
+                (None, None) => String::new(),
+            };
+            format!("{argument} (split on {delimiter:?}{items})")
+        }
+    }
+}
+
+fn write_table(out: &mut impl std::io::Write, entries: &[InventoryEntry<'_>]) -> Result<()> {
+    let header = ["KIND", "NAME", "LIBRARY", "STATUS", "MODULE", "RULES"];
+    let rows: Vec<[String; 6]> = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.kind.to_string(),
+                entry.name.to_string(),
+                entry.library.unwrap_or("-").to_string(),
+                entry.status.as_str().to_string(),
+                entry.module.to_string(),
+                entry.summary(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let header = header.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arity_summary_describes_argument_shape() {
+        let arity = FilterArity {
+            expects_arg: true,
+            arg_optional: true,
+            arg_kind: Some(FilterArgKind::Delimited {
+                delimiter: ",".to_string(),
+                min_items: None,
+                max_items: Some(2),
+            }),
+        };
+
+        assert_eq!(
+            arity_summary(&arity),
+            "optional argument (split on \",\", at most 2 items)"
+        );
+        assert_eq!(
+            arity_summary(&FilterArity {
+                expects_arg: false,
+                arg_optional: false,
+                arg_kind: None,
+            }),
+            "no argument"
+        );
+    }
+
+    #[test]
+    fn arguments_summary_marks_optional_arguments() {
+        let arguments = [
+            TagArgument {
+                name: "item".to_string(),
+                required: true,
+                kind: TagArgumentKind::Variable,
+                position: 0,
+            },
+            TagArgument {
+                name: "in".to_string(),
+                required: true,
+                kind: TagArgumentKind::Literal("in".to_string()),
+                position: 1,
+            },
+            TagArgument {
+                name: "reversed".to_string(),
+                required: false,
+                kind: TagArgumentKind::Choice(vec!["reversed".to_string()]),
+                position: 2,
+            },
+        ];
+
+        assert_eq!(arguments_summary(&arguments), "item in [reversed]");
+    }
+}
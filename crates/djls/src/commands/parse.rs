@@ -0,0 +1,266 @@
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::run_django_discovery;
+use djls_semantic::BlockRole;
+use djls_semantic::RegionId;
+use djls_semantic::Regions;
+use djls_semantic::TemplateNode;
+use djls_semantic::TemplateTree;
+use djls_semantic::build_template_tree_for_file;
+use djls_source::LineIndex;
+use djls_source::OsFileSystem;
+use djls_source::Span;
+use djls_source::path_to_file;
+use djls_templates::Node;
+use djls_templates::TagBit;
+use djls_templates::TemplateParseResult;
+use djls_templates::Token;
+use djls_templates::lex_template_impl;
+use djls_templates::parse_template;
+use djls_templates::parse_template_impl;
+use djls_templates::variable_expression;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Parse {
+    /// Template file to parse.
+    path: Utf8PathBuf,
+
+    /// Print the lexer token stream.
+    #[arg(long)]
+    tokens: bool,
+
+    /// Print the flat node list produced by the parser.
+    #[arg(long)]
+    nodes: bool,
+
+    /// Print the block tree built from the project's tag specs.
+    #[arg(long)]
+    blocks: bool,
+}
+
+impl Command for Parse {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let path = if self.path.is_relative() {
+            project_root.join(&self.path)
+        } else {
+            self.path.clone()
+        };
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read template '{path}'"))?;
+        let index = LineIndex::from(source.as_str());
+
+        // Without any section flags, print everything.
+        let all = !(self.tokens || self.nodes || self.blocks);
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+
+        if all || self.tokens {
+            writeln!(stdout, "== tokens ==")?;
+            write_tokens(&mut stdout, &index, &lex_template_impl(&source))?;
+        }
+        if all || self.nodes {
+            writeln!(stdout, "== nodes ==")?;
+            write_nodes(&mut stdout, &index, &parse_template_impl(&source).0)?;
+        }
+        if all || self.blocks {
+            let settings =
+                Settings::new(&project_root, None).context("Failed to load settings")?;
+            let mut db = DjangoDatabase::new(
+                Arc::new(OsFileSystem::default()),
+                &settings,
+                Some(&project_root),
+            );
+            db.apply_project_settings(settings);
+            if let Err(error) = run_django_discovery(&mut db) {
+                eprintln!("Project discovery failed ({error}); blocks use builtin tag specs only");
+            }
+
+            let file = path_to_file(&db, &path)
+                .with_context(|| format!("Failed to open template '{path}'"))?;
+            let TemplateParseResult::Parsed(nodelist) = parse_template(&db, file) else {
+                bail!("'{path}' is not a readable Django template");
+            };
+            let tree = build_template_tree_for_file(&db, file, nodelist);
+
+            writeln!(stdout, "== blocks ==")?;
+            write_blocks(&mut stdout, &index, &db, tree)?;
+        }
+
+        Ok(Exit::success())
+    }
+}
+
+/// Render a span as its byte range followed by its 1-based line and column.
+fn location(index: &LineIndex, span: Span) -> String {
+    let (line, column) = index.to_line_col(span.start_offset()).into();
+    let range = format!("{}..{}", span.start(), span.end());
+    let position = format!("{}:{}", line + 1, column + 1);
+    format!("{range:<12} {position:<8}")
+}
+
+fn write_tokens(out: &mut impl Write, index: &LineIndex, tokens: &[Token]) -> Result<()> {
+    for token in tokens {
+        let description = match token {
+            Token::Block { content, .. } => format!("Block {content:?}"),
+            Token::Comment { content, .. } => format!("Comment {content:?}"),
+            Token::Error {
+                content, delimiter, ..
+            } => format!("Error {delimiter:?} {content:?}"),
+            Token::Eof => "Eof".to_string(),
+            Token::Newline { .. } => "Newline".to_string(),
+            Token::Text { content, .. } => format!("Text {content:?}"),
+            Token::Variable { content, .. } => format!("Variable {content:?}"),
+            Token::Whitespace { .. } => "Whitespace".to_string(),
+        };
+        match token.full_span() {
+            Some(span) => writeln!(out, "{} {description}", location(index, span))?,
+            None => writeln!(out, "{:<21} {description}", "")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_nodes(out: &mut impl Write, index: &LineIndex, nodes: &[Node]) -> Result<()> {
+    for node in nodes {
+        let description = match node {
+            Node::Tag { name, bits, .. } => format!("Tag {name}{}", bits_suffix(bits)),
+            Node::Comment { content, .. } => format!("Comment {content:?}"),
+            Node::Text { .. } => "Text".to_string(),
+            Node::Variable { var, filters, .. } => {
+                format!("Variable {}", variable_expression(var, filters))
+            }
+            Node::Error { error, .. } => format!("Error {error}"),
+        };
+        writeln!(out, "{} {description}", location(index, node.full_span()))?;
+    }
+    Ok(())
+}
+
+fn write_blocks(
+    out: &mut impl Write,
+    index: &LineIndex,
+    db: &DjangoDatabase,
+    tree: TemplateTree<'_>,
+) -> Result<()> {
+    write_region(out, index, tree.regions(db), tree.root(db), 0)
+}
+
+fn write_region(
+    out: &mut impl Write,
+    index: &LineIndex,
+    regions: &Regions,
+    id: RegionId,
+    depth: usize,
+) -> Result<()> {
+    let region = regions.get(id);
+    let indent = "  ".repeat(depth);
+    writeln!(
+        out,
+        "{} {indent}region {}",
+        location(index, *region.span()),
+        id.id()
+    )?;
+
+    for node in region.nodes() {
+        let (span, description, body) = match node {
+            TemplateNode::Block {
+                tag,
+                bits,
+                full_span,
+                body,
+                role,
+                ..
+            } => {
+                let role = match role {
+                    BlockRole::Opener => "opener",
+                    BlockRole::Segment => "segment",
+                };
+                let description = format!("Block {tag}{} ({role})", bits_suffix(bits));
+                (*full_span, description, Some(*body))
+            }
+            TemplateNode::Opaque {
+                tag, bits, full_span, ..
+            } => (*full_span, format!("Opaque {tag}{}", bits_suffix(bits)), None),
+            TemplateNode::StandaloneTag {
+                tag, bits, full_span, ..
+            } => (*full_span, format!("Tag {tag}{}", bits_suffix(bits)), None),
+            TemplateNode::Variable {
+                var, filters, span, ..
+            } => (
+                *span,
+                format!("Variable {}", variable_expression(var, filters)),
+                None,
+            ),
+            TemplateNode::Comment { span } => (*span, "Comment".to_string(), None),
+            TemplateNode::Text { span } => (*span, "Text".to_string(), None),
+            TemplateNode::Error { full_span, .. } => (*full_span, "Error".to_string(), None),
+        };
+        writeln!(out, "{} {indent}  {description}", location(index, span))?;
+        if let Some(body) = body {
+            write_region(out, index, regions, body, depth + 2)?;
+        }
+    }
+    Ok(())
+}
+
+fn bits_suffix(bits: &[TagBit]) -> String {
+    bits.iter().fold(String::new(), |mut rendered, bit| {
+        rendered.push(' ');
+        rendered.push_str(bit.as_str());
+        rendered
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, write: fn(&mut Vec<u8>, &LineIndex, &str) -> Result<()>) -> String {
+        let index = LineIndex::from(source);
+        let mut out = Vec::new();
+        write(&mut out, &index, source).expect("rendering into a buffer should succeed");
+        String::from_utf8(out).expect("rendered output should be UTF-8")
+    }
+
+    #[test]
+    fn nodes_render_spans_with_line_and_column() {
+        let output = render("hi\n{{ user.name|default }}", |out, index, source| {
+            write_nodes(out, index, &parse_template_impl(source).0)
+        });
+        let variable = output
+            .lines()
+            .find(|line| line.contains("Variable"))
+            .expect("variable node should be rendered");
+
+        assert!(variable.starts_with("3.."));
+        assert!(variable.contains(" 2:1 "));
+        assert!(variable.ends_with("Variable user.name|default"));
+    }
+
+    #[test]
+    fn tokens_render_eof_without_a_span() {
+        let output = render("{% if x %}", |out, index, source| {
+            write_tokens(out, index, &lex_template_impl(source))
+        });
+        let lines: Vec<_> = output.lines().collect();
+
+        assert!(lines[0].starts_with("0..10        1:1"));
+        assert!(lines[0].contains("Block"));
+        assert_eq!(lines.last().map(|line| line.trim()), Some("Eof"));
+    }
+}
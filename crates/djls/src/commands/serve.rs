@@ -1,7 +1,9 @@
 use anyhow::Result;
 use anyhow::bail;
+use camino::Utf8PathBuf;
 use clap::Parser;
 use clap::ValueEnum;
+use djls_server::LogOptions;
 
 use crate::args::Args;
 use crate::commands::Command;
@@ -11,6 +13,16 @@ use crate::exit::Exit;
 pub(crate) struct Serve {
     #[arg(short, long, default_value_t = ConnectionType::Stdio, value_enum)]
     connection_type: ConnectionType,
+
+    /// Write the server log to this file instead of the cache directory.
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<Utf8PathBuf>,
+
+    /// File log level or filter directive (e.g. `debug`, `djls_server=trace,info`).
+    ///
+    /// Overrides the `log_level` setting; `RUST_LOG` still takes precedence.
+    #[arg(long, value_name = "LEVEL")]
+    log_level: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -23,7 +35,10 @@ impl Command for Serve {
     fn execute(&self, _args: &Args) -> Result<Exit> {
         match self.connection_type {
             ConnectionType::Stdio => {
-                djls_server::run()?;
+                djls_server::run(&LogOptions {
+                    file: self.log_file.clone(),
+                    level: self.log_level.clone(),
+                })?;
                 Ok(Exit::success())
             }
             ConnectionType::Tcp => bail!("`djls serve --connection-type tcp` is not supported yet"),
@@ -0,0 +1,215 @@
+use std::cmp::Reverse;
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use clap::ValueEnum;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::run_django_discovery;
+use djls_semantic::TemplateMetrics;
+use djls_semantic::template_metrics;
+use djls_source::OsFileSystem;
+use djls_source::WalkOptions;
+use djls_source::path_to_file;
+use serde::Serialize;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::discover_files;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Stats {
+    /// Template files or directories to measure. If omitted, discovers Template
+    /// directories from the Django project.
+    paths: Vec<Utf8PathBuf>,
+
+    /// Measurement to order Templates by, largest first.
+    #[arg(long, value_enum, default_value_t = SortKey::Nodes)]
+    sort: SortKey,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SortKey {
+    Nodes,
+    Depth,
+    Includes,
+    Variables,
+    Branches,
+}
+
+impl SortKey {
+    fn of(self, metrics: &TemplateMetrics) -> usize {
+        match self {
+            Self::Nodes => metrics.nodes,
+            Self::Depth => metrics.max_depth,
+            Self::Includes => metrics.includes,
+            Self::Variables => metrics.variables,
+            Self::Branches => metrics.branches,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Aligned columns for reading in a terminal.
+    Table,
+    /// A JSON array with one object per Template.
+    Json,
+}
+
+#[derive(Serialize)]
+struct TemplateStats {
+    template: String,
+    #[serde(flatten)]
+    metrics: TemplateMetrics,
+}
+
+impl Command for Stats {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
+        let mut db = DjangoDatabase::new(
+            Arc::new(OsFileSystem::default()),
+            &settings,
+            Some(&project_root),
+        );
+        db.apply_project_settings(settings);
+        run_django_discovery(&mut db)
+            .context("Failed to discover Django environment")?
+            .context("No Project configured for stats")?;
+
+        let files = discover_files(&self.paths, &db, &project_root, &WalkOptions::default());
+        // Discovered Template paths are canonical, so they are shown relative to the
+        // canonical project root.
+        let display_root = project_root
+            .canonicalize_utf8()
+            .unwrap_or_else(|_| project_root.clone());
+        let mut stats: Vec<TemplateStats> = files
+            .iter()
+            .filter_map(|path| {
+                let file = path_to_file(&db, path).ok()?;
+                let template = path.strip_prefix(&display_root).unwrap_or(path);
+                Some(TemplateStats {
+                    template: template.to_string(),
+                    metrics: template_metrics(&db, file),
+                })
+            })
+            .collect();
+        // Paths are already sorted, and the sort is stable, so ties stay in path order.
+        stats.sort_by_key(|entry| Reverse(self.sort.of(&entry.metrics)));
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        match self.format {
+            OutputFormat::Table => write_table(&mut stdout, &stats)?,
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut stdout, &stats)?;
+                writeln!(stdout)?;
+            }
+        }
+
+        Ok(Exit::success())
+    }
+}
+
+fn write_table(out: &mut impl Write, stats: &[TemplateStats]) -> Result<()> {
+    let header = [
+        "TEMPLATE",
+        "NODES",
+        "DEPTH",
+        "INCLUDES",
+        "VARIABLES",
+        "BRANCHES",
+    ];
+    let rows: Vec<[String; 6]> = stats
+        .iter()
+        .map(|entry| {
+            let metrics = &entry.metrics;
+            [
+                entry.template.clone(),
+                metrics.nodes.to_string(),
+                metrics.max_depth.to_string(),
+                metrics.includes.to_string(),
+                metrics.variables.to_string(),
+                metrics.branches.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let header = header.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        // Template paths are left-aligned and the counts right-aligned.
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(index, (cell, width))| {
+                if index == 0 {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_right_aligns_counts() {
+        let stats = [
+            TemplateStats {
+                template: "templates/dashboard.html".to_string(),
+                metrics: TemplateMetrics {
+                    nodes: 812,
+                    max_depth: 6,
+                    includes: 12,
+                    variables: 37,
+                    branches: 44,
+                },
+            },
+            TemplateStats {
+                template: "templates/base.html".to_string(),
+                metrics: TemplateMetrics {
+                    nodes: 140,
+                    max_depth: 3,
+                    includes: 2,
+                    variables: 9,
+                    branches: 6,
+                },
+            },
+        ];
+        let mut out = Vec::new();
+        write_table(&mut out, &stats).expect("writing to a Vec should succeed");
+
+        assert_eq!(
+            String::from_utf8(out).expect("table output should be UTF-8"),
+            "TEMPLATE                  NODES  DEPTH  INCLUDES  VARIABLES  BRANCHES\n\
+             templates/dashboard.html    812      6        12         37        44\n\
+             templates/base.html         140      3         2          9         6\n"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Result;
+use clap::Parser;
+use clap::ValueEnum;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_project::ProjectStatus;
+use djls_project::run_django_discovery;
+use djls_source::OsFileSystem;
+use serde::Serialize;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Status {
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// One labelled line per field.
+    Text,
+    /// The same fields the server sends in `djls/status` notifications.
+    Json,
+}
+
+/// Mirrors the server's `djls/status` notification params.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusReport {
+    health: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<ProjectStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_index_ms: Option<u64>,
+}
+
+impl StatusReport {
+    fn degraded(reason: &'static str, error: &anyhow::Error) -> Self {
+        Self {
+            health: "degraded",
+            message: Some(format!("{error:#}")),
+            reason: Some(reason),
+            project: None,
+            last_index_ms: None,
+        }
+    }
+}
+
+impl Command for Status {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let report = collect_status()?;
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        match self.format {
+            OutputFormat::Text => write_text(&mut stdout, &report)?,
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut stdout, &report)?;
+                writeln!(stdout)?;
+            }
+        }
+
+        if report.health == "ok" {
+            Ok(Exit::success())
+        } else {
+            Ok(Exit::error())
+        }
+    }
+}
+
+/// Run discovery the way the server's reload does and time it.
+fn collect_status() -> Result<StatusReport> {
+    let project_root = resolve_project_root()?;
+    let start = Instant::now();
+
+    let settings = match Settings::new(&project_root, None) {
+        Ok(settings) => settings,
+        Err(error) => return Ok(StatusReport::degraded("settings", &error.into())),
+    };
+    let mut db = DjangoDatabase::new(
+        Arc::new(OsFileSystem::default()),
+        &settings,
+        Some(&project_root),
+    );
+    db.apply_project_settings(settings);
+    if let Err(error) = run_django_discovery(&mut db) {
+        return Ok(StatusReport::degraded("environment", &error.into()));
+    }
+
+    let project = db.project().map(|project| ProjectStatus::new(&db, project));
+    Ok(StatusReport {
+        health: "ok",
+        message: None,
+        reason: None,
+        project,
+        last_index_ms: Some(u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX)),
+    })
+}
+
+fn write_text(out: &mut impl Write, report: &StatusReport) -> Result<()> {
+    writeln!(out, "Health:           {}", report.health)?;
+    if let Some(reason) = report.reason {
+        writeln!(out, "Failed stage:     {reason}")?;
+    }
+    if let Some(message) = &report.message {
+        writeln!(out, "Message:          {message}")?;
+    }
+    if let Some(project) = &report.project {
+        let environment = project
+            .environment
+            .as_ref()
+            .map_or_else(|| "not found".to_string(), ToString::to_string);
+        writeln!(out, "Environment:      {environment}")?;
+        writeln!(
+            out,
+            "Settings module:  {}",
+            project.settings_module.as_deref().unwrap_or("not configured")
+        )?;
+        writeln!(
+            out,
+            "Django version:   {}",
+            project.django_version.as_deref().unwrap_or("unknown")
+        )?;
+        writeln!(out, "Libraries:        {}", project.inventory.libraries)?;
+        writeln!(out, "Tags:             {}", project.inventory.tags)?;
+        writeln!(out, "Filters:          {}", project.inventory.filters)?;
+    }
+    if let Some(elapsed) = report.last_index_ms {
+        writeln!(out, "Indexed in:       {elapsed} ms")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use djls_project::InventorySize;
+
+    use super::*;
+
+    #[test]
+    fn text_output_lists_every_field() {
+        let report = StatusReport {
+            health: "ok",
+            message: None,
+            reason: None,
+            project: Some(ProjectStatus {
+                environment: Some(Utf8PathBuf::from("/project/.venv/lib/python3.13/site-packages")),
+                settings_module: Some("config.settings".to_string()),
+                django_version: None,
+                inventory: InventorySize {
+                    libraries: 3,
+                    tags: 40,
+                    filters: 62,
+                },
+            }),
+            last_index_ms: Some(412),
+        };
+        let mut out = Vec::new();
+        write_text(&mut out, &report).expect("writing into a buffer should succeed");
+
+        assert_eq!(
+            String::from_utf8(out).expect("status should be UTF-8"),
+            "Health:           ok\n\
+             Environment:      /project/.venv/lib/python3.13/site-packages\n\
+             Settings module:  config.settings\n\
+             Django version:   unknown\n\
+             Libraries:        3\n\
+             Tags:             40\n\
+             Filters:          62\n\
+             Indexed in:       412 ms\n"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::io::stdout;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use djls_conf::Settings;
+use djls_db::DjangoDatabase;
+use djls_project::Db as _;
+use djls_project::run_django_discovery;
+use djls_semantic::ChainEnd;
+use djls_semantic::TemplateTrace;
+use djls_semantic::trace_template;
+use djls_source::File;
+use djls_source::LineIndex;
+use djls_source::OsFileSystem;
+use djls_source::path_to_file;
+
+use crate::args::Args;
+use crate::commands::Command;
+use crate::commands::common::resolve_project_root;
+use crate::exit::Exit;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Trace {
+    /// Template file to trace.
+    path: Utf8PathBuf,
+
+    /// Print the flattened template without origin markers.
+    #[arg(long)]
+    no_markers: bool,
+}
+
+impl Command for Trace {
+    fn execute(&self, _args: &Args) -> Result<Exit> {
+        let project_root = resolve_project_root()?;
+        let path = if self.path.is_relative() {
+            project_root.join(&self.path)
+        } else {
+            self.path.clone()
+        };
+
+        let settings = Settings::new(&project_root, None).context("Failed to load settings")?;
+        let mut db = DjangoDatabase::new(
+            Arc::new(OsFileSystem::default()),
+            &settings,
+            Some(&project_root),
+        );
+        db.apply_project_settings(settings);
+        run_django_discovery(&mut db)
+            .context("Failed to discover Django environment")?
+            .context("No Project configured for trace")?;
+        let project = db.project().context("No Project configured for trace")?;
+
+        let file =
+            path_to_file(&db, &path).with_context(|| format!("Failed to open template '{path}'"))?;
+        let trace = trace_template(&db, project, file)
+            .with_context(|| format!("'{path}' is not a readable Django template"))?;
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        write_trace(&mut stdout, &db, &project_root, &trace, !self.no_markers)?;
+
+        if let Some(note) = chain_end_note(&trace.end) {
+            eprintln!("note: {note}");
+        }
+
+        Ok(Exit::success())
+    }
+}
+
+/// Write the flattened template, preceding each change of file or block with
+/// a `{# ... #}` marker naming where the following text comes from.
+fn write_trace(
+    out: &mut impl Write,
+    db: &DjangoDatabase,
+    project_root: &Utf8Path,
+    trace: &TemplateTrace,
+    markers: bool,
+) -> Result<()> {
+    let mut sources: HashMap<File, (String, LineIndex)> = HashMap::new();
+    let mut previous: Option<(File, Option<&str>)> = None;
+
+    for segment in &trace.segments {
+        let (source, index) = sources.entry(segment.file).or_insert_with(|| {
+            let source = segment
+                .file
+                .try_source(db)
+                .map(|source| source.as_str().to_string())
+                .unwrap_or_default();
+            let index = LineIndex::from(source.as_str());
+            (source, index)
+        });
+
+        let origin = (segment.file, segment.block.as_deref());
+        if markers && previous != Some(origin) {
+            let path = segment.file.path(db).as_path();
+            let path = path.strip_prefix(project_root).unwrap_or(path);
+            let line = index.to_line_col(segment.span.start_offset()).line() + 1;
+            write!(out, "{}", marker(path, line, segment.block.as_deref()))?;
+        }
+        previous = Some(origin);
+
+        let text = source
+            .get(segment.span.start_usize()..segment.span.end_usize())
+            .unwrap_or_default();
+        write!(out, "{text}")?;
+    }
+
+    Ok(())
+}
+
+fn marker(path: &Utf8Path, line: u32, block: Option<&str>) -> String {
+    match block {
+        Some(block) => format!("{{# djls: {path}:{line} block {block} #}}"),
+        None => format!("{{# djls: {path}:{line} #}}"),
+    }
+}
+
+fn chain_end_note(end: &ChainEnd) -> Option<String> {
+    match end {
+        ChainEnd::Root => None,
+        ChainEnd::Dynamic { .. } => Some(
+            "the topmost template extends a variable, so its parent is not shown".to_string(),
+        ),
+        ChainEnd::Unresolved { name } => Some(format!(
+            "parent template '{name}' was not found, so the trace stops below it"
+        )),
+        ChainEnd::InconclusiveParent { name } => Some(format!(
+            "parent template '{name}' may resolve to more than one file, \
+             so the trace stops below it"
+        )),
+        ChainEnd::Cycle => Some("the extends chain loops back on itself".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markers_name_file_line_and_block() {
+        assert_eq!(
+            marker(Utf8Path::new("templates/base.html"), 3, Some("content")),
+            "{# djls: templates/base.html:3 block content #}"
+        );
+        assert_eq!(
+            marker(Utf8Path::new("templates/base.html"), 1, None),
+            "{# djls: templates/base.html:1 #}"
+        );
+    }
+}
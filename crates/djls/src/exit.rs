@@ -5,6 +5,7 @@ use std::fmt;
 pub(crate) enum ExitStatus {
     Success,
     Error,
+    InternalError,
 }
 
 impl ExitStatus {
@@ -12,6 +13,7 @@ impl ExitStatus {
         match self {
             ExitStatus::Success => 0,
             ExitStatus::Error => 1,
+            ExitStatus::InternalError => 2,
         }
     }
 
@@ -19,6 +21,7 @@ impl ExitStatus {
         match self {
             ExitStatus::Success => "Command succeeded",
             ExitStatus::Error => "Command error",
+            ExitStatus::InternalError => "Internal error",
         }
     }
 }
@@ -52,6 +55,10 @@ impl Exit {
         Self::new(ExitStatus::Error)
     }
 
+    pub(crate) fn internal_error() -> Self {
+        Self::new(ExitStatus::InternalError)
+    }
+
     pub(crate) fn with_message<S: Into<String>>(mut self, message: S) -> Self {
         self.message = Some(message.into());
         self
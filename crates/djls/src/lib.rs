@@ -2,5 +2,8 @@
 
 mod check;
 
+pub use check::CheckedDiagnostic;
 pub use check::CheckedTemplate;
+pub use check::DiagnosticKey;
+pub use check::RenderedDiagnostic;
 pub use check::check_template;
@@ -323,7 +323,7 @@ fn check_plural_errors_with_singular_file_summary_exactly() {
 }
 
 #[test]
-fn check_quiet_counts_enabled_diagnostics_without_rendering() {
+fn check_quiet_prints_only_the_summary() {
     let dir = tempdir().expect("temporary test directory should be created");
     setup_project(dir.path()).expect("test project fixture should be configured");
 
@@ -341,13 +341,12 @@ fn check_quiet_counts_enabled_diagnostics_without_rendering() {
     assert_eq!(output.status.code(), Some(1));
     assert!(
         output.stdout.is_empty(),
-        "quiet check must not render diagnostics or a summary: {}",
+        "quiet check must not render diagnostics: {}",
         String::from_utf8_lossy(&output.stdout)
     );
-    assert!(
-        output.stderr.is_empty(),
-        "quiet check must not write stderr: {}",
-        String::from_utf8_lossy(&output.stderr)
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Found 1 error in 1 file.\n"
     );
 }
 
@@ -539,7 +538,7 @@ fn check_rejects_mixed_stdin_and_paths() {
         .output()
         .expect("djls check process should run");
 
-    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.status.code(), Some(2));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         stderr.contains("Cannot mix `-` (stdin) with file or directory paths"),
@@ -559,7 +558,7 @@ fn check_invalid_settings_error_precedes_mixed_stdin_and_paths_error() {
         .output()
         .expect("djls check process should run");
 
-    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.status.code(), Some(2));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         stderr.contains("Failed to load settings"),
@@ -699,3 +698,298 @@ fn check_no_templates_exits_zero() {
         output.status.code(),
     );
 }
+
+#[test]
+fn check_files_from_stdin_checks_only_listed_templates() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let templates = dir.path().join("templates");
+    fs::create_dir_all(&templates).expect("test fixture directory should be created");
+    fs::write(templates.join("listed.html"), "{% block content %}\n")
+        .expect("test fixture file should be written");
+    fs::write(templates.join("unlisted.html"), "{% block content %}\n")
+        .expect("test fixture file should be written");
+
+    let mut child = Command::new(djls_binary())
+        .args(["check", "--files-from", "-"])
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("djls check process should start");
+    child
+        .stdin
+        .take()
+        .expect("piped stdin should be available")
+        .write_all(b"templates/listed.html\nsetup.py\n")
+        .expect("file list should be written to djls stdin");
+    let output = child
+        .wait_with_output()
+        .expect("djls check process should finish");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("listed.html"), "{stdout}");
+    assert!(!stdout.contains("unlisted.html"), "{stdout}");
+}
+
+#[test]
+fn check_empty_files_from_list_exits_zero_without_discovery() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let templates = dir.path().join("templates");
+    fs::create_dir_all(&templates).expect("test fixture directory should be created");
+    fs::write(templates.join("broken.html"), "{% block content %}\n")
+        .expect("test fixture file should be written");
+    fs::write(dir.path().join("files.txt"), "\n").expect("file list should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--files-from", "files.txt"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    assert!(
+        output.status.success(),
+        "Expected exit 0, got {:?}\nstderr: {}",
+        output.status.code(),
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn check_missing_files_from_list_is_an_internal_error() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--files-from", "missing.txt"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to read file list"), "{stderr}");
+}
+
+#[test]
+fn check_baseline_suppresses_recorded_diagnostics_only() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let templates = dir.path().join("templates");
+    fs::create_dir_all(&templates).expect("test fixture directory should be created");
+    fs::write(templates.join("legacy.html"), "{% block content %}\n")
+        .expect("test fixture file should be written");
+
+    let written = Command::new(djls_binary())
+        .args(["check", "--write-baseline", ".djls-baseline.json", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+    assert!(
+        written.status.success(),
+        "Expected exit 0, got {:?}\nstderr: {}",
+        written.status.code(),
+        String::from_utf8_lossy(&written.stderr),
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&written.stderr),
+        "Wrote 1 diagnostic to .djls-baseline.json.\n"
+    );
+
+    // Shifting the recorded diagnostic down a line keeps it suppressed.
+    fs::write(templates.join("legacy.html"), "<p>Intro</p>\n{% block content %}\n")
+        .expect("test fixture file should be updated");
+    let suppressed = Command::new(djls_binary())
+        .args(["check", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+    assert!(
+        suppressed.status.success(),
+        "Expected baseline to suppress the recorded diagnostic, got:\n{}",
+        String::from_utf8_lossy(&suppressed.stdout),
+    );
+
+    fs::write(templates.join("new.html"), "{% block sidebar %}\n")
+        .expect("test fixture file should be written");
+    let reported = Command::new(djls_binary())
+        .args(["check", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+    assert_eq!(reported.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&reported.stdout);
+    assert!(stdout.contains("new.html"), "{stdout}");
+    assert!(!stdout.contains("legacy.html"), "{stdout}");
+
+    let unfiltered = Command::new(djls_binary())
+        .args(["check", "--no-baseline", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+    assert_eq!(
+        String::from_utf8_lossy(&unfiltered.stderr),
+        "Found 2 errors in 2 files.\n"
+    );
+}
+
+#[test]
+fn check_fix_inserts_missing_end_tag_and_rechecks() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let templates = dir.path().join("templates");
+    fs::create_dir_all(&templates).expect("test fixture directory should be created");
+    fs::write(
+        templates.join("broken.html"),
+        "{% block content %}\n<p>Hello</p>\n",
+    )
+    .expect("test fixture file should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--fix", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    assert!(
+        output.status.success(),
+        "Expected exit 0 after fixing, got {:?}\nstdout: {}",
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout),
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Fixed 1 diagnostic in 1 file.\n\n"
+    );
+    assert_eq!(
+        fs::read_to_string(templates.join("broken.html"))
+            .expect("fixed template should be readable"),
+        "{% block content %}\n<p>Hello</p>\n{% endblock %}"
+    );
+}
+
+/// A project whose `[[plugins]]` entry runs a script that flags every Template.
+fn setup_plugin_project(dir: &Path) -> std::io::Result<()> {
+    setup_project(dir)?;
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(dir.join("djls.toml"))?;
+    config.write_all(b"\n[[plugins]]\nname = \"house-style\"\ncommand = [\"sh\", \"rules.sh\"]\n")?;
+    fs::write(
+        dir.join("rules.sh"),
+        "while read -r request; do\n\
+         echo '{\"diagnostics\": [{\"code\": \"ORG001\", \"message\": \"Avoid inline styles\", \
+         \"start\": 3, \"end\": 12}]}'\n\
+         done\n",
+    )?;
+
+    let templates = dir.join("templates");
+    fs::create_dir_all(&templates)?;
+    fs::write(templates.join("page.html"), "<p style=\"x\">Hi</p>\n")
+}
+
+#[cfg(unix)]
+#[test]
+fn check_reports_diagnostics_from_plugin_rule_servers() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_plugin_project(dir.path()).expect("plugin project fixture should be configured");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--allow-plugins", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("ORG001") && stdout.contains("Avoid inline styles"),
+        "Expected the plugin diagnostic in output:\n{stdout}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn check_does_not_run_plugins_unless_allowed() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_plugin_project(dir.path()).expect("plugin project fixture should be configured");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "templates/"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(
+        !stdout.contains("ORG001"),
+        "Expected no plugin diagnostics without --allow-plugins:\n{stdout}"
+    );
+}
+
+#[test]
+fn check_rejects_fix_with_stdin() {
+    let dir = tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--fix", "-"])
+        .current_dir(dir.path())
+        .stdin(Stdio::null())
+        .output()
+        .expect("djls check process should run");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Cannot use --fix when reading a Template from stdin"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn check_shadowed_lists_templates_hidden_by_an_earlier_directory() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path()).expect("test project fixture should be configured");
+
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+    for templates in [&first, &second] {
+        std::fs::create_dir_all(templates).expect("test fixture directory should be created");
+        std::fs::write(templates.join("base.html"), "<html></html>\n")
+            .expect("test fixture file should be written");
+    }
+    std::fs::write(second.join("only.html"), "<p></p>\n")
+        .expect("test fixture file should be written");
+    configure_template_directories(dir.path(), &[&first, &second])
+        .expect("test Template directories should be configured");
+
+    let output = Command::new(djls_binary())
+        .args(["check", "--shadowed"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls check process should run");
+
+    assert!(
+        output.status.success(),
+        "Expected exit 0, got {:?}\nstdout: {}\nstderr: {}",
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "second/base.html is shadowed by first/base.html for 'base.html'\n\n"
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "Found 1 shadowed template.\n");
+}
@@ -0,0 +1,416 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn djls_binary() -> PathBuf {
+    PathBuf::from(env!("CARGO_BIN_EXE_djls"))
+}
+
+/// Configure `dir` as a Django project whose only Template directory is `templates/`, with
+/// `options` as the Template engine's `OPTIONS`.
+fn setup_project(dir: &Path, options: &str) -> std::io::Result<PathBuf> {
+    let templates = dir.join("templates");
+    fs::create_dir_all(&templates)?;
+    fs::write(
+        dir.join("djls.toml"),
+        "django_settings_module = \"settings\"\n",
+    )?;
+    fs::write(
+        dir.join("settings.py"),
+        format!(
+            "INSTALLED_APPS = []\nTEMPLATES = [{{'BACKEND': 'django.template.backends.django.DjangoTemplates', 'DIRS': ['{}'], 'APP_DIRS': False, 'OPTIONS': {options}}}]\n",
+            templates.display()
+        ),
+    )?;
+    Ok(templates)
+}
+
+#[test]
+fn inspect_tags_lists_symbols_of_one_library() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    setup_project(dir.path(), "{'libraries': {'shop': 'shop_tags'}}")
+        .expect("test project fixture should be configured");
+    fs::write(
+        dir.path().join("shop_tags.py"),
+        "from django import template\nregister = template.Library()\n@register.simple_tag\ndef cart_count(): pass\n@register.filter\ndef price(value): pass\n",
+    )
+    .expect("tag library fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["inspect", "tags", "--library", "shop"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls inspect process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().take(5).collect())
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            vec!["KIND", "NAME", "LIBRARY", "STATUS", "MODULE"],
+            vec!["filter", "price", "shop", "loadable", "shop_tags"],
+            vec!["tag", "cart_count", "shop", "loadable", "shop_tags"],
+        ],
+        "{stdout}"
+    );
+}
+
+#[test]
+fn parse_nodes_prints_only_the_node_section() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    fs::write(
+        dir.path().join("page.html"),
+        "hi\n{{ user.name|default:'x' }}\n",
+    )
+    .expect("Template fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["parse", "page.html", "--nodes"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls parse process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.first(), Some(&"== nodes =="), "{stdout}");
+    assert!(!stdout.contains("== tokens =="), "{stdout}");
+    let variable = lines
+        .iter()
+        .find(|line| line.contains("Variable"))
+        .expect("variable node should be printed");
+    assert!(variable.starts_with("3.."), "{variable}");
+    assert!(variable.contains(" 2:1 "), "{variable}");
+    assert!(
+        variable.ends_with("Variable user.name|default:'x'"),
+        "{variable}"
+    );
+}
+
+#[test]
+fn parse_missing_template_is_an_internal_error() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+
+    let output = Command::new(djls_binary())
+        .args(["parse", "missing.html"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls parse process should run");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to read template"), "{stderr}");
+}
+
+fn setup_chain(dir: &Path) -> std::io::Result<()> {
+    let templates = setup_project(dir, "{}")?;
+    fs::write(
+        templates.join("base.html"),
+        "<title>{% block title %}Site{% endblock %}</title>\n",
+    )?;
+    fs::write(
+        templates.join("page.html"),
+        "{% extends 'base.html' %}{% block title %}Page{% endblock %}\n",
+    )
+}
+
+#[test]
+fn trace_without_markers_prints_the_flattened_template() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    setup_chain(dir.path()).expect("Template chain fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["trace", "templates/page.html", "--no-markers"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls trace process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "<title>Page</title>\n"
+    );
+}
+
+#[test]
+fn trace_markers_name_the_file_and_block_of_each_part() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    setup_chain(dir.path()).expect("Template chain fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["trace", "templates/page.html"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls trace process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("{# djls: templates/base.html:1 #}<title>"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("{# djls: templates/page.html:1 block title #}Page"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("Site"), "{stdout}");
+}
+
+#[test]
+fn explain_prints_code_description_and_docs_link() {
+    let output = Command::new(djls_binary())
+        .args(["explain", "S109"])
+        .output()
+        .expect("djls explain process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("S109: Unloaded tag\n\n"), "{stdout}");
+    assert!(
+        stdout.ends_with("https://djls.joshthomas.dev/diagnostics/#s109\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_without_code_lists_every_code() {
+    let output = Command::new(djls_binary())
+        .arg("explain")
+        .output()
+        .expect("djls explain process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().any(|line| line == "S109  Unloaded tag"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_unknown_code_exits_one() {
+    let output = Command::new(djls_binary())
+        .args(["explain", "S999"])
+        .output()
+        .expect("djls explain process should run");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Unknown diagnostic code 'S999'. Run `djls explain` to list every code.\n"
+    );
+}
+
+#[test]
+fn status_reports_degraded_settings_and_exits_one() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    fs::write(dir.path().join("djls.toml"), "this is not toml\n")
+        .expect("invalid configuration fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .arg("status")
+        .current_dir(dir.path())
+        .output()
+        .expect("djls status process should run");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("Health:           degraded\nFailed stage:     settings\nMessage:"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn status_reports_the_configured_settings_module() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    fs::write(
+        dir.path().join("djls.toml"),
+        "django_settings_module = \"settings\"\n",
+    )
+    .expect("configuration fixture should be written");
+    fs::write(dir.path().join("settings.py"), "INSTALLED_APPS = []\n")
+        .expect("settings fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .arg("status")
+        .current_dir(dir.path())
+        .output()
+        .expect("djls status process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Health:           ok\n"), "{stdout}");
+    assert!(stdout.contains("Settings module:  settings\n"), "{stdout}");
+}
+
+#[test]
+fn blocks_table_lists_the_chain_and_each_block_status() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    let templates =
+        setup_project(dir.path(), "{}").expect("test project fixture should be configured");
+    fs::write(
+        templates.join("base.html"),
+        "{% block title %}{% endblock %}\n{% block content %}{% endblock %}\n",
+    )
+    .expect("base Template fixture should be written");
+    fs::write(
+        templates.join("page.html"),
+        "{% extends 'base.html' %}\n{% block content %}{% endblock %}\n{% block extra %}{% endblock %}\n",
+    )
+    .expect("child Template fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["blocks", "templates/page.html"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls blocks process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines.first(),
+        Some(&"templates/page.html extends templates/base.html"),
+        "{stdout}"
+    );
+    let status = |name: &str| {
+        lines
+            .iter()
+            .find(|line| line.split_whitespace().next() == Some(name))
+            .and_then(|line| line.split_whitespace().nth(1))
+    };
+    assert_eq!(status("BLOCK"), Some("STATUS"), "{stdout}");
+    assert_eq!(status("title"), Some("untouched"), "{stdout}");
+    assert_eq!(status("content"), Some("overridden"), "{stdout}");
+    assert_eq!(status("extra"), Some("new"), "{stdout}");
+}
+
+#[test]
+fn i18n_report_lists_untranslated_text_and_fails_over_the_threshold() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    let templates =
+        setup_project(dir.path(), "{}").expect("test project fixture should be configured");
+    fs::write(
+        templates.join("page.html"),
+        "<h1>Welcome</h1>\n<p>{{ name }}</p>\n",
+    )
+    .expect("Template fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .arg("i18n-report")
+        .current_dir(dir.path())
+        .output()
+        .expect("djls i18n-report process should run");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "templates/page.html:1:5  Welcome\n\
+         \n\
+         TEMPLATE             UNTRANSLATED\n\
+         templates/page.html  1\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Found 1 untranslated text run in 1 of 1 template, over the threshold of 0.\n"
+    );
+}
+
+#[test]
+fn i18n_report_within_the_threshold_exits_zero() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    let templates =
+        setup_project(dir.path(), "{}").expect("test project fixture should be configured");
+    fs::write(templates.join("page.html"), "<h1>Welcome</h1>\n")
+        .expect("Template fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .args(["i18n-report", "--threshold", "1"])
+        .current_dir(dir.path())
+        .output()
+        .expect("djls i18n-report process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Found 1 untranslated text run in 1 of 1 template.\n"
+    );
+}
+
+#[test]
+fn stats_orders_templates_by_node_count() {
+    let dir = tempfile::tempdir().expect("temporary test directory should be created");
+    let templates =
+        setup_project(dir.path(), "{}").expect("test project fixture should be configured");
+    fs::write(templates.join("a_small.html"), "<p>hi</p>\n")
+        .expect("small Template fixture should be written");
+    fs::write(
+        templates.join("b_large.html"),
+        "{% if user %}{{ user }}{% else %}{{ guest }}{% endif %}\n{% include 'a_small.html' %}\n",
+    )
+    .expect("large Template fixture should be written");
+
+    let output = Command::new(djls_binary())
+        .arg("stats")
+        .current_dir(dir.path())
+        .output()
+        .expect("djls stats process should run");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines.first().map(|line| line.split_whitespace().collect::<Vec<_>>()),
+        Some(vec!["TEMPLATE", "NODES", "DEPTH", "INCLUDES", "VARIABLES", "BRANCHES"]),
+        "{stdout}"
+    );
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[1].starts_with("templates/b_large.html"), "{stdout}");
+    assert!(lines[2].starts_with("templates/a_small.html"), "{stdout}");
+}
@@ -12,7 +12,7 @@ fn serve_tcp_reports_unsupported_connection_type() {
         .output()
         .expect("djls serve process should run");
 
-    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.status.code(), Some(2));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         stderr.contains("`djls serve --connection-type tcp` is not supported yet"),